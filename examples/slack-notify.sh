@@ -0,0 +1,26 @@
+#!/bin/sh
+# Posts key lifecycle events to a Slack incoming webhook. Requires `jq` and
+# `curl`. Configure it in translation-check.toml:
+#
+#   [hooks]
+#   new-base-keys = { command = "examples/slack-notify.sh" }
+#   deleted-keys = { command = "examples/slack-notify.sh" }
+#   coverage-dropped-below-threshold = { command = "examples/slack-notify.sh", threshold = 95 }
+#
+# and export SLACK_WEBHOOK_URL in the CI job.
+set -eu
+
+: "${SLACK_WEBHOOK_URL:?set SLACK_WEBHOOK_URL to the Slack incoming webhook URL}"
+
+case "$TRANSLATION_CHECK_EVENT" in
+  new-base-keys) title="New translation keys" ;;
+  deleted-keys) title="Deleted translation keys" ;;
+  *) title="Translation coverage dropped below the threshold" ;;
+esac
+
+lines=$(jq -r 'if .key then "• `\(.key)`"
+  else "• \(.lang | ascii_upcase): \(.coverage)% (was \(.previous)%, threshold \(.threshold)%)" end')
+
+jq -n --arg text "*$title*
+$lines" '{ text: $text }' |
+  curl -fsS -X POST -H 'Content-Type: application/json' --data @- "$SLACK_WEBHOOK_URL" >/dev/null
@@ -0,0 +1,21 @@
+#![no_main]
+
+use check_translations::{java_properties, po, xlsx, yaml};
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary bytes must never make the translation file parsers panic, nor
+// the reader of the XLSX sheets `import` reads. Run with a per-input
+// timeout to catch runaway inputs:
+//
+//     cargo +nightly fuzz run file_formats -- -timeout=2
+fuzz_target!(|data: &[u8]| {
+    let _ = xlsx::read(data);
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = yaml::parse(text);
+    let _ = java_properties::parse(text);
+    let _ = po::parse(text, false, "fr");
+    let _ = po::parse(text, true, "fr");
+});
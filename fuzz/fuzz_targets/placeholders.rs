@@ -0,0 +1,27 @@
+#![no_main]
+
+use check_translations::checks::extract_variables;
+use check_translations::icu;
+use check_translations::interpolation::find_call_sites;
+use check_translations::toml;
+use libfuzzer_sys::fuzz_target;
+use std::path::Path;
+
+// Arbitrary text must never make the placeholder, ICU message, call site
+// or config parsers panic. Run with a per-input timeout to catch runaway
+// inputs:
+//
+//     cargo +nightly fuzz run placeholders -- -timeout=2
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    for var in extract_variables(text) {
+        assert!(text.contains(&format!("{{{}}}", var)));
+    }
+    let _ = icu::parse(text);
+    find_call_sites(Path::new("fuzz.ts"), text);
+    find_call_sites(Path::new("fuzz.vue"), text);
+    let _ = toml::parse(text);
+});
@@ -0,0 +1,113 @@
+use crate::checks::extract_variables;
+use crate::model::{Entry, LanguageData, Project, ValueKind};
+use crate::schema::Schema;
+use std::collections::{HashMap, HashSet};
+
+// The keys every language is compared against, with the placeholders of each
+// base value. They come from the base language folder, or from a published
+// schema when the base language is owned by another team.
+pub struct Base<'a> {
+    // The language folder the keys were read from, which is not compared
+    // against itself.
+    pub lang: Option<&'a str>,
+    pub name: String,
+    pub placeholders: HashMap<String, HashSet<String>>,
+    data: Option<&'a LanguageData>,
+    source: String,
+}
+
+impl<'a> Base<'a> {
+    pub fn language(project: &'a Project, lang: &'a str) -> Base<'a> {
+        let data = project.base();
+        Base {
+            lang: Some(lang),
+            name: lang.to_string(),
+            placeholders: data
+                .values
+                .iter()
+                .map(|(key, entry)| (key.to_string(), extract_variables(&entry.value)))
+                .collect(),
+            data: Some(data),
+            source: "Unknown file".to_string(),
+        }
+    }
+
+    pub fn schema(schema: Schema, source: &str) -> Base<'a> {
+        Base {
+            lang: None,
+            name: schema.base_lang.unwrap_or_else(|| "schema".to_string()),
+            placeholders: schema.placeholders,
+            data: None,
+            source: source.to_string(),
+        }
+    }
+
+    // How the base is named in expected-value labels, e.g. `FR`.
+    pub fn label(&self) -> String {
+        self.name.to_uppercase()
+    }
+
+    // The entries of the base language. A published schema only has keys
+    // and placeholders, so it has none.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Entry)> {
+        self.data
+            .into_iter()
+            .flat_map(|data| data.values.iter())
+            .map(|(key, entry)| (&**key, entry))
+    }
+
+    // The base value of `key`, unknown for a published schema.
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.data?.value(key)
+    }
+
+    // The JSON type of the base value of `key`, unknown for a published
+    // schema.
+    pub fn kind(&self, key: &str) -> Option<ValueKind> {
+        Some(self.data?.values.get(key)?.kind)
+    }
+
+    // The keys coverage counts: each value, and each empty array, which
+    // holds none.
+    pub fn counted_keys(&self) -> Vec<&str> {
+        self.placeholders
+            .keys()
+            .map(String::as_str)
+            .chain(
+                self.arrays()
+                    .filter(|(_, length)| *length == 0)
+                    .map(|(path, _)| path),
+            )
+            .collect()
+    }
+
+    // The arrays of the base language with their length.
+    pub fn arrays(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.data
+            .into_iter()
+            .flat_map(|data| data.arrays.iter())
+            .map(|(key, (length, _))| (&**key, *length))
+    }
+
+    // The key source files refer to for `key`: the outermost array holding
+    // it, as they look up a whole array at once, or the key itself.
+    pub fn usage_key<'k>(&self, key: &'k str) -> &'k str {
+        self.data
+            .and_then(|data| data.outermost_array(key))
+            .unwrap_or(key)
+    }
+
+    pub fn do_not_translate(&self, key: &str) -> bool {
+        self.data
+            .and_then(|data| data.values.get(key))
+            .is_some_and(|entry| entry.do_not_translate)
+    }
+
+    // The file defining `key`, or the schema it was published in.
+    pub fn file(&self, key: &str) -> String {
+        self.data
+            .and_then(|data| data.file(key))
+            .map(str::to_string)
+            .unwrap_or_else(|| self.source.clone())
+    }
+}
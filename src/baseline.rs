@@ -0,0 +1,154 @@
+use crate::report::{Check, Finding, Report};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// What a finding is recognized by from one run to the next: its check and
+// what it is about. Lines and details are left out, since they change with
+// unrelated edits to the same file. A missing key is recognized without
+// its file, the one it should be added to, which baselines written before
+// it was reported recorded as `Unknown file`.
+type Fingerprint = (Check, Option<String>, Option<String>, Option<String>);
+
+fn fingerprint(
+    check: Check,
+    lang: Option<String>,
+    key: Option<String>,
+    file: Option<String>,
+) -> Fingerprint {
+    let file = file.filter(|_| check != Check::MissingKey);
+    (check, lang, key, file)
+}
+
+fn fingerprint_of(finding: &Finding) -> Fingerprint {
+    fingerprint(
+        finding.check,
+        finding.lang.clone(),
+        finding.key.clone(),
+        finding.file.clone(),
+    )
+}
+
+// The findings a project had when it adopted the tool, written by
+// `--write-baseline`, so that a run only fails on the findings added since.
+// Each fingerprint is counted, for the same problem found twice in a file
+// to only be accepted twice.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Baseline {
+    findings: BTreeMap<Fingerprint, usize>,
+}
+
+impl Baseline {
+    // The findings of `report`, those about waivers aside.
+    pub fn of(report: &Report) -> Baseline {
+        let mut findings = BTreeMap::new();
+        for finding in report
+            .findings
+            .iter()
+            .filter(|finding| !matches!(finding.check, Check::ExpiredWaiver | Check::UnusedWaiver))
+        {
+            *findings.entry(fingerprint_of(finding)).or_default() += 1;
+        }
+        Baseline { findings }
+    }
+
+    // Accepts one more finding like `finding`.
+    pub fn add(&mut self, finding: &Finding) {
+        *self.findings.entry(fingerprint_of(finding)).or_default() += 1;
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            format!(
+                "failed to read baseline {}: {}; `--write-baseline {}` writes it",
+                path.display(),
+                err,
+                path.display()
+            )
+        })?;
+        let json: Value = serde_json::from_str(&content)
+            .map_err(|err| format!("invalid baseline {}: {}", path.display(), err))?;
+        let mut findings = BTreeMap::new();
+        let entries = json
+            .get("findings")
+            .and_then(Value::as_array)
+            .ok_or_else(|| format!("invalid baseline {}: no `findings`", path.display()))?;
+        for entry in entries {
+            let id = entry
+                .get("check")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let check = Check::from_id(id).ok_or_else(|| {
+                format!(
+                    "invalid baseline {}: unknown check `{}`",
+                    path.display(),
+                    id
+                )
+            })?;
+            let field = |name: &str| entry.get(name).and_then(Value::as_str).map(str::to_string);
+            let count = entry.get("count").and_then(Value::as_u64).unwrap_or(1) as usize;
+            *findings
+                .entry(fingerprint(
+                    check,
+                    field("lang"),
+                    field("key"),
+                    field("file"),
+                ))
+                .or_default() += count;
+        }
+        Ok(Baseline { findings })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let findings: Vec<Value> = self
+            .findings
+            .iter()
+            .map(|((check, lang, key, file), count)| {
+                let mut entry = json!({ "check": check.info().id });
+                for (name, value) in [("lang", lang), ("key", key), ("file", file)] {
+                    if let Some(value) = value {
+                        entry[name] = json!(value);
+                    }
+                }
+                entry["count"] = json!(count);
+                entry
+            })
+            .collect();
+        let content = serde_json::to_string_pretty(&json!({
+            "version": 1,
+            "findings": findings,
+        }))
+        .unwrap()
+            + "\n";
+        fs::write(path, content)
+            .map_err(|err| format!("failed to write baseline {}: {}", path.display(), err))
+    }
+
+    pub fn len(&self) -> usize {
+        self.findings.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    // Drops the findings of the baseline from `report`, counting them in
+    // it, and returns how many findings of the baseline are gone.
+    pub fn apply(&self, report: &mut Report) -> usize {
+        let mut left = self.findings.clone();
+        let mut baselined = 0;
+        report
+            .findings
+            .retain(|finding| match left.get_mut(&fingerprint_of(finding)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    baselined += finding.weight;
+                    false
+                }
+                _ => true,
+            });
+        *report.baselined.get_or_insert(0) += baselined;
+        left.values().sum()
+    }
+}
@@ -0,0 +1,239 @@
+use crate::config::BundleAsset;
+use crate::loader::{Flattened, flatten_json};
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use regex::Regex;
+use serde_json::{Deserializer, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// The translations the build output ships: the keys of each language, with
+// the file each was first found in, relative to the build output.
+#[derive(Debug, Default)]
+pub struct Bundle {
+    pub languages: BTreeMap<String, BTreeMap<String, String>>,
+    // How many built files were read.
+    pub files: usize,
+}
+
+impl Bundle {
+    // Reads the files of every asset under `dist`. Only the languages the
+    // source tree has are taken from objects of languages, so that other
+    // JSON a script embeds isn't mistaken for translations.
+    pub fn scan(dist: &Path, assets: &[BundleAsset], languages: &[&str]) -> Result<Bundle, String> {
+        let mut bundle = Bundle::default();
+        for asset in assets {
+            let pattern = format!(
+                "{}/{}",
+                glob::Pattern::escape(&dist.display().to_string()),
+                asset.glob()
+            );
+            let names = file_regex(&asset.files);
+            let mut paths: Vec<_> = glob::glob(&pattern)
+                .map_err(|err| format!("invalid `bundle.assets.files` glob: {}", err))?
+                .filter_map(Result::ok)
+                .filter(|path| path.is_file())
+                .collect();
+            paths.sort();
+            for path in paths {
+                let relative = path
+                    .strip_prefix(dist)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let lang = match names.captures(&relative).and_then(|cap| cap.name("lang")) {
+                    Some(lang) => Some(lang.as_str().to_string()),
+                    None if asset.files.contains("{lang}") => continue,
+                    None => None,
+                };
+                let bytes = fs::read(&path)
+                    .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+                let text = String::from_utf8_lossy(&bytes);
+                let documents =
+                    if relative.ends_with(".json") {
+                        vec![serde_json::from_str(&text).map_err(|err| {
+                            format!("invalid JSON in {}: {}", path.display(), err)
+                        })?]
+                    } else {
+                        embedded_json(&text)
+                    };
+                bundle.files += 1;
+                for document in &documents {
+                    let Some(target) = asset
+                        .path
+                        .iter()
+                        .try_fold(document, |value, segment| value.get(segment))
+                    else {
+                        continue;
+                    };
+                    match &lang {
+                        Some(lang) => bundle.add(lang, target, &relative),
+                        None => {
+                            for lang in languages {
+                                if let Some(messages) = target.get(*lang) {
+                                    bundle.add(lang, messages, &relative);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(bundle)
+    }
+
+    fn add(&mut self, lang: &str, messages: &Value, file: &str) {
+        if !messages.is_object() {
+            return;
+        }
+        let flattened = Flattened::default();
+        flatten_json(messages, String::new(), &flattened);
+        let keys = self.languages.entry(lang.to_string()).or_default();
+        for (key, _) in flattened.leaves {
+            keys.entry(key).or_insert_with(|| file.to_string());
+        }
+    }
+
+    // Languages of the source tree the build output lacks, keys the source
+    // has and the bundle dropped, and keys the bundle still ships although
+    // the source no longer has them.
+    pub fn compare(&self, project: &Project, dist: &Path) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for lang in project.languages() {
+            let Some(data) = project.get(lang) else {
+                continue;
+            };
+            let Some(shipped) = self.languages.get(lang) else {
+                findings.push(
+                    Finding::new(Check::BundleMissingLanguage)
+                        .lang(lang)
+                        .detail("Keys", data.values.len().to_string())
+                        .file(dist.display().to_string()),
+                );
+                continue;
+            };
+            let mut missing: Vec<&str> = data
+                .values
+                .keys()
+                .map(|key| &**key)
+                .filter(|key| !shipped.contains_key(*key))
+                .collect();
+            missing.sort();
+            for key in missing {
+                findings.push(
+                    Finding::new(Check::BundleMissingKey)
+                        .lang(lang)
+                        .key(key)
+                        .file(data.file(key).unwrap_or_default()),
+                );
+            }
+            for (key, file) in shipped {
+                if !data.values.contains_key(key.as_str()) {
+                    findings.push(
+                        Finding::new(Check::BundleStaleKey)
+                            .lang(lang)
+                            .key(key)
+                            .file(dist.join(file).display().to_string()),
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
+
+// Matches the paths of `files`, relative to the build output, capturing the
+// `{lang}` segment.
+fn file_regex(files: &str) -> Regex {
+    let mut regex = String::from("^");
+    let mut rest = files;
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("**/") {
+            regex.push_str("(?:.*/)?");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("**") {
+            regex.push_str(".*");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("{lang}") {
+            regex.push_str("(?P<lang>[^/]+)");
+            rest = tail;
+        } else {
+            let c = rest.chars().next().unwrap();
+            match c {
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).unwrap()
+}
+
+// The JSON objects a script embeds, minified or not: the string passed to
+// `JSON.parse(...)`, as bundlers inline large JSON imports, and object
+// literals that happen to be valid JSON. Nested objects are only read as
+// part of the outermost one.
+pub fn embedded_json(text: &str) -> Vec<Value> {
+    let mut documents = Vec::new();
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        if let Some(literal) = rest.strip_prefix("JSON.parse(")
+            && let Some((source, length)) = string_literal(literal)
+        {
+            if let Ok(value @ Value::Object(_)) = serde_json::from_str(&source) {
+                documents.push(value);
+            }
+            pos += "JSON.parse(".len() + length;
+            continue;
+        }
+        if rest.starts_with('{') {
+            let mut stream = Deserializer::from_str(rest).into_iter::<Value>();
+            if let Some(Ok(value @ Value::Object(_))) = stream.next() {
+                documents.push(value);
+                pos += stream.byte_offset();
+                continue;
+            }
+        }
+        pos += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    documents
+}
+
+// The text of the JavaScript string literal `text` opens with, and the
+// length of the literal and its quotes.
+fn string_literal(text: &str) -> Option<(String, usize)> {
+    let quote = text
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                let (_, escaped) = chars.next()?;
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    'u' | 'x' => {
+                        let digits = if escaped == 'u' { 4 } else { 2 };
+                        let hex: String = (0..digits)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    other => value.push(other),
+                }
+            }
+            c if c == quote => return Some((value, index + c.len_utf8())),
+            c => value.push(c),
+        }
+    }
+    None
+}
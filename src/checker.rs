@@ -0,0 +1,827 @@
+use crate::base::Base;
+use crate::baseline::Baseline;
+use crate::checks::{self, check_interpolations, check_loader_config, check_translations};
+use crate::cli::{Only, Options};
+use crate::config::{self, Config};
+use crate::constants;
+use crate::content::ContentRules;
+use crate::context::Manifest;
+use crate::model::{self, Project};
+use crate::openapi;
+use crate::report::{Check, Finding, Report, Severity};
+use crate::schema::Schema;
+use crate::scope::{Scope, Touched};
+use crate::setup;
+use crate::state::{STATE_FILE, State};
+use crate::suppressions::Suppressions;
+use crate::timings::Timings;
+use crate::translation_set::LoadOptions;
+use crate::usage::{
+    self, CallKey, SCAN_CACHE_FILE, ScanCache, UsageMode, check_translations_usage, find_call_keys,
+    references_by_file, relative_to_root, source_files, unused_by_calls,
+};
+use crate::waivers::{self, Waivers};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// Runs the checks of the binary over a translation directory and returns
+// the `Report` of the `Finding`s a run reports, for the renderers of
+// `render` or a tool's own output. `new` runs those needing no
+// configuration file: unreadable files, missing and extra keys,
+// placeholders, and unused keys once sources are given; `config` adds
+// those a configuration file enables.
+pub struct Checker {
+    options: Options,
+    config: Config,
+}
+
+// What a run checks beside the project: the part of it a change scopes,
+// the lines `--changed-since` keeps findings on, and the state of the
+// previous run the languages are compared with.
+pub struct Run<'a> {
+    pub scope: &'a Scope,
+    pub touched: Option<&'a Touched>,
+    pub previous: Option<&'a State>,
+    pub timings: &'a Timings,
+}
+
+// The report of a run, and what the binary prints or writes of it.
+#[derive(Default)]
+pub struct Checked {
+    pub report: Report,
+    // `--check-only` stopped at the first group of checks failing the run;
+    // the report is settled as a full one would be.
+    pub stopped: bool,
+    // The baseline applied: the one to write with `--write-baseline`, or
+    // the one given.
+    pub baseline: Option<Baseline>,
+    // The findings of the given baseline no longer found.
+    pub fixed: usize,
+    // The context manifest, and the links it attaches per finding.
+    pub context: Option<(Manifest, usize)>,
+    pub warnings: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+impl Checker {
+    pub fn new(options: LoadOptions) -> Checker {
+        Checker {
+            options: Options {
+                base_lang: options.base_lang,
+                src_dirs: Vec::new(),
+                extensions: options.extensions,
+                key_case_insensitive: options.key_case_insensitive,
+                low_memory: options.low_memory,
+                scan_cache: false,
+                ..Options::default()
+            },
+            config: Config::default(),
+        }
+    }
+
+    // The checker of a run of the binary, with its options and
+    // configuration file.
+    #[doc(hidden)]
+    pub fn configured(options: Options, config: Config) -> Checker {
+        Checker { options, config }
+    }
+
+    // Scans these files or folders, reporting the base keys none of them
+    // uses. Folders are searched for the extensions of `LoadOptions`.
+    pub fn sources(mut self, sources: &[PathBuf]) -> Checker {
+        self.options.src_dirs = sources.to_vec();
+        self.options.given.insert("--src-dir".to_string());
+        self
+    }
+
+    // Runs the checks `config` enables too, as a run reading it does.
+    pub fn config(mut self, config: Config) -> Checker {
+        self.config = config;
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn configuration(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn check(&self, path: &Path) -> Result<Report, String> {
+        model::layout(path)?;
+        let options = Options {
+            i18n_dir: path.to_path_buf(),
+            ..self.options.clone()
+        };
+        let timings = Timings::default();
+        let project = setup::discover(&options, &self.config, &timings);
+        if project.get(&options.base_lang).is_none() {
+            return Err(format!(
+                "no base language `{}` in {}",
+                options.base_lang,
+                path.display()
+            ));
+        }
+        let base = Base::language(&project, &options.base_lang);
+        let checked = self.run(
+            &project,
+            &base,
+            &Run {
+                scope: &Scope::Full("every file".to_string()),
+                touched: None,
+                previous: None,
+                timings: &timings,
+            },
+        )?;
+        let mut report = checked.report;
+        crate::loader::locate_keys(&mut report.findings);
+        report.sort();
+        Ok(report)
+    }
+
+    // Runs the checks over `project`, compared with `base`, and applies the
+    // severities, waivers and baseline of the options.
+    pub fn run(&self, project: &Project, base: &Base, run: &Run) -> Result<Checked, String> {
+        let options = &self.options;
+        let config = &self.config;
+        let mut warnings = Vec::new();
+        let mut notes = Vec::new();
+        let mut report = Report::default();
+        let languages: Vec<&str> = project.languages().collect();
+
+        let today = waivers::today();
+        let source = config
+            .source
+            .as_ref()
+            .map(|path| path.display().to_string());
+        let config_path = source.as_deref().unwrap_or(config::CONFIG_FILE);
+        let mut accepted = Vec::new();
+        if !config.ignores.is_empty() {
+            accepted.push(Waivers {
+                source: config_path.to_string(),
+                waivers: config.ignores.clone(),
+            });
+        }
+        if let Some(path) = &options.waivers {
+            accepted.push(Waivers::load(path)?);
+        }
+        // The baseline written by this run replaces the one given.
+        let baseline = options
+            .baseline
+            .as_ref()
+            .filter(|_| options.write_baseline.is_none())
+            .map(|path| {
+                // A review accepting findings starts the baseline it adds them to.
+                if options.interactive && !path.exists() {
+                    return Ok(Baseline::default());
+                }
+                Baseline::load(path)
+            })
+            .transpose()?;
+        // `--check-only` stops as soon as a group of checks fails the run, with
+        // the counts found so far, skipping the others.
+        let settle = |report: &Report| {
+            let mut settled = report.clone();
+            settled.override_severities(&config.severities);
+            for waivers in &accepted {
+                waivers.apply(&mut settled, &today);
+            }
+            if let Some(baseline) = &baseline {
+                baseline.apply(&mut settled);
+            }
+            if let Some(touched) = run.touched {
+                settled.findings.retain(|finding| touched.contains(finding));
+            }
+            settled.has_errors().then_some(settled)
+        };
+        let stopped = |report: Report| Checked {
+            report,
+            stopped: true,
+            ..Checked::default()
+        };
+
+        if options.only != Some(Only::UnusedKeys) {
+            run.timings.time("load languages", || project.load_all());
+            let schema = options
+                .schema_snapshot
+                .as_ref()
+                .filter(|path| path.exists())
+                .map(|path| Schema::load(path))
+                .transpose()?;
+
+            report.extend(run.timings.time("check translations", || {
+                check_translations(
+                    base,
+                    project,
+                    &HashSet::new(),
+                    schema.as_ref(),
+                    options.key_case_insensitive,
+                    options.max_value_length,
+                )
+            }));
+            report.extend(run.timings.time("check do-not-translate values", || {
+                checks::do_not_translate::check_do_not_translate(base, project)
+            }));
+            report.extend(run.timings.time("check markup", || {
+                checks::markup::check_markup(base, project)
+            }));
+            if options.check_untranslated {
+                let severity = if options.strict {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                };
+                report.extend(run.timings.time("check untranslated values", || {
+                    checks::untranslated::check_untranslated(
+                        base,
+                        project,
+                        &config.untranslated,
+                        severity,
+                    )
+                }));
+            }
+            let reviews = run.timings.time("check reviews", || {
+                checks::review::check_reviews(project, &options.require_reviewed)
+            });
+            report.extend(reviews?);
+            let scaffolds = run.timings.time("check scaffolds", || {
+                checks::scaffolds::check_stale_scaffolds(base, project)
+            });
+            report.extend(scaffolds?);
+            report.extend(run.timings.time("check normalization", || {
+                checks::normalization::check_normalization(project)
+            }));
+            report.extend(run.timings.time("check quotes", || {
+                checks::quotes::check_quotes(project, options.max_value_length)
+            }));
+            report.extend(run.timings.time("check scripts", || {
+                checks::scripts::check_scripts(base, project, &config.scripts)
+            }));
+            if !config.base_only_namespaces.is_empty() {
+                report.base_only = Some(checks::base_only::check_base_only(
+                    base,
+                    project,
+                    &config.base_only_namespaces,
+                    &mut report.findings,
+                ));
+            }
+            let base_issues = checks::base_quality::check_base_quality(base, &mut report.findings);
+            report.extend(base_issues);
+            if let Some(plural_forms) = &config.plural_forms {
+                let plural_issues =
+                    checks::plurals::check_plural_forms(base, plural_forms, &mut report.findings);
+                report.extend(plural_issues);
+                let category_issues = checks::plurals::check_plural_categories(
+                    base,
+                    project,
+                    plural_forms,
+                    &mut report.findings,
+                    options.max_value_length,
+                );
+                report.extend(category_issues);
+            }
+            report.consistency_checked = true;
+            if options.check_only
+                && let Some(settled) = settle(&report)
+            {
+                return Ok(stopped(settled));
+            }
+        }
+        report.translation_files = languages
+            .iter()
+            .filter(|lang| report.consistency_checked || base.lang == Some(**lang))
+            .map(|lang| project.files(lang).len())
+            .sum();
+
+        if !config.linked_keys.is_empty() && options.only != Some(Only::UnusedKeys) {
+            report.extend(run.timings.time("check linked keys", || {
+                checks::linked_keys::check_linked_keys(project, &config.linked_keys)
+            }));
+        }
+
+        if options.only != Some(Only::UnusedKeys) {
+            report.extend(run.timings.time("check links", || {
+                checks::links::check_links(project, config.linkable_keys.as_deref())
+            }));
+        }
+
+        if let Some(siblings) = &config.sibling_duplicates
+            && options.only != Some(Only::UnusedKeys)
+        {
+            report.extend(run.timings.time("check sibling duplicates", || {
+                checks::siblings::check_sibling_duplicates(base, project, siblings)
+            }));
+        }
+
+        if !config.reserved_placeholders.is_empty() && options.only != Some(Only::UnusedKeys) {
+            report.extend(run.timings.time("check reserved placeholders", || {
+                checks::reserved::check_reserved_placeholders(
+                    base,
+                    project,
+                    &config.reserved_placeholders,
+                )
+            }));
+        }
+
+        if options.only != Some(Only::UnusedKeys) {
+            report.extend(run.timings.time("check placeholder counts", || {
+                checks::placeholders::check_placeholder_counts(base, project)
+            }));
+        }
+
+        if config.placeholder_position && options.only != Some(Only::UnusedKeys) {
+            report.extend(run.timings.time("check placeholder positions", || {
+                checks::placeholders::check_placeholder_positions(base, project)
+            }));
+        }
+
+        if config.icu && options.only != Some(Only::UnusedKeys) {
+            let icu_issues = run.timings.time("check icu messages", || {
+                checks::icu::check_icu_messages(base, project, &mut report.findings)
+            });
+            report.extend(icu_issues);
+        }
+
+        if !config.styles.is_empty() && options.only != Some(Only::UnusedKeys) {
+            report.extend(run.timings.time("check styles", || {
+                checks::style::check_styles(project, &config.styles)
+            }));
+        }
+
+        if let Some(key_naming) = &config.key_naming
+            && options.only != Some(Only::UnusedKeys)
+        {
+            report.extend(run.timings.time("check key naming", || {
+                checks::key_naming::check_key_naming(project, key_naming)
+            }));
+        }
+
+        if let Some(path) = &options.content_rules
+            && options.only != Some(Only::UnusedKeys)
+        {
+            let rules = ContentRules::load(path)?;
+            report.extend(
+                run.timings
+                    .time("check content rules", || rules.check(project)),
+            );
+        }
+
+        let loader_config_path = options.loader_config.clone().or_else(|| {
+            config
+                .loader_config
+                .as_ref()
+                .and_then(|loader| loader.path.clone())
+        });
+        if let Some(path) = loader_config_path {
+            let Some(loader) = &config.loader_config else {
+                return Err(format!(
+                    "`--loader-config` requires a `[loader_config]` pattern in {}",
+                    config::CONFIG_FILE
+                ));
+            };
+            report.extend(check_loader_config(project, loader, &path)?);
+        }
+
+        if options.check_only
+            && let Some(settled) = settle(&report)
+        {
+            return Ok(stopped(settled));
+        }
+        let changed_sources = match run.scope {
+            Scope::Changed { sources, .. } => Some(sources),
+            Scope::Full(_) => None,
+        };
+        if options.only != Some(Only::Consistency)
+            && changed_sources.is_none_or(|sources| !sources.is_empty())
+        {
+            let roots = options.sources();
+            // A source folder given on the command line or in the configuration
+            // file that is missing or holds no source file is a typo rather than
+            // a project using no key. Without one, `src` is only scanned when
+            // there is something to scan.
+            let roots_given = options.given.contains("--src-dir")
+                || config.project.src_dir.is_some()
+                || !config.project.sources.is_empty();
+            if roots_given && let Some(root) = roots.iter().find(|root| !root.dir.exists()) {
+                return Err(format!(
+                    "source folder {} doesn't exist",
+                    root.dir.display()
+                ));
+            }
+            let mut files: Vec<PathBuf> = match changed_sources {
+                Some(sources) => sources.clone(),
+                None => run
+                    .timings
+                    .time("collect source files", || source_files(&roots)),
+            };
+            if roots_given
+                && changed_sources.is_none()
+                && let Some(root) = roots
+                    .iter()
+                    .find(|root| !files.iter().any(|file| file.starts_with(&root.dir)))
+            {
+                return Err(format!(
+                    "source folder {} holds no .{} file",
+                    root.dir.display(),
+                    root.extensions.join(", .")
+                ));
+            }
+            // Vendored and generated sources neither use keys nor miss any.
+            files.retain(|file| {
+                !config
+                    .exclude
+                    .excludes_source(relative_to_root(file, &roots))
+            });
+
+            // The elements of an array are used through the array.
+            let base_keys: HashSet<String> = base
+                .placeholders
+                .keys()
+                .map(|key| base.usage_key(key).to_string())
+                .collect();
+
+            // Generated constants files only declare keys, they never use them.
+            let (definitions, sources): (Vec<PathBuf>, Vec<PathBuf>) =
+                files.iter().cloned().partition(|file| {
+                    config
+                        .key_constants
+                        .as_ref()
+                        .is_some_and(|constants| constants.matches(relative_to_root(file, &roots)))
+                });
+            // End-to-end tests are scanned apart, for the keys nothing else uses.
+            let (e2e, sources): (Vec<PathBuf>, Vec<PathBuf>) = sources
+                .into_iter()
+                .partition(|file| config.e2e.matches(relative_to_root(file, &roots)));
+
+            let calls = (config.usage.mode == UsageMode::Calls).then(|| {
+                run.timings.time("find translation calls", || {
+                    find_call_keys(&files, &config.usage.call_patterns)
+                })
+            });
+
+            // Whether a key is used anywhere needs every source file, and some
+            // to have been scanned.
+            if run.scope.is_full() && !files.is_empty() {
+                let scanned: Result<HashSet<String>, String> =
+                    run.timings.time("scan sources", || match &calls {
+                        Some(calls) => {
+                            let sources: HashSet<&PathBuf> = sources.iter().collect();
+                            let calls: Vec<CallKey> = calls
+                                .iter()
+                                .filter(|call| sources.contains(&call.file))
+                                .cloned()
+                                .collect();
+                            Ok(unused_by_calls(
+                                &base_keys,
+                                &calls,
+                                options.key_case_insensitive,
+                            ))
+                        }
+                        None => {
+                            // Editor buffers aren't what the disk holds. A process
+                            // checking more than once keeps the cache in memory.
+                            let mut cache = ((options.scan_cache || usage::keeps_scans())
+                                && options.buffers.is_none())
+                            .then(|| {
+                                ScanCache::resume(
+                                    &options.schema_cache_dir.join(SCAN_CACHE_FILE),
+                                    &base_keys,
+                                    options.key_case_insensitive,
+                                    options.low_memory,
+                                    options.scan_cache,
+                                )
+                            });
+                            let unused = check_translations_usage(
+                                &base_keys,
+                                &sources,
+                                options.key_case_insensitive,
+                                options.low_memory,
+                                cache.as_mut(),
+                            )?;
+                            if let Some(mut cache) = cache {
+                                if options.scan_cache
+                                    && let Err(err) = cache.save(&sources)
+                                {
+                                    warnings.push(err);
+                                }
+                                if options.timings {
+                                    notes.push(format!(
+                                        "Scan cache: {} of {} source files unchanged",
+                                        cache.hits(),
+                                        sources.len()
+                                    ));
+                                }
+                                cache.keep(&sources);
+                            }
+                            Ok(unused)
+                        }
+                    });
+                let mut unused_keys = scanned?;
+
+                unused_keys.retain(|key| {
+                    !config
+                        .usage
+                        .dynamic_keys
+                        .iter()
+                        .any(|pattern| pattern.matches(key))
+                });
+
+                let fold = |key: &str| {
+                    if options.key_case_insensitive {
+                        key.to_lowercase()
+                    } else {
+                        key.to_string()
+                    }
+                };
+                let mut unused_constants: HashMap<String, Vec<String>> = HashMap::new();
+                if config.key_constants.is_some() {
+                    let resolution = run.timings.time("resolve key constants", || {
+                        constants::resolve(&definitions, &sources)
+                    });
+                    warnings.extend(resolution.warnings.iter().cloned());
+                    let used: HashSet<String> =
+                        resolution.usages.keys().map(|key| fold(key)).collect();
+                    unused_keys.retain(|key| !used.contains(&fold(key)));
+                    for constant in &resolution.unused {
+                        unused_constants
+                            .entry(fold(&constant.key))
+                            .or_default()
+                            .push(format!("{} ({})", constant.name, constant.location()));
+                    }
+                }
+
+                if !options.scan_openapi.is_empty() {
+                    let (groups, spec_warnings) = run.timings.time("scan OpenAPI specs", || {
+                        openapi::scan(&options.scan_openapi)
+                    });
+                    warnings.extend(spec_warnings);
+                    let known: HashSet<String> = base_keys.iter().map(|key| fold(key)).collect();
+                    let used: HashSet<String> = groups
+                        .iter()
+                        .flat_map(|group| &group.references)
+                        .map(|reference| fold(&reference.key))
+                        .filter(|key| known.contains(key))
+                        .collect();
+                    unused_keys.retain(|key| !used.contains(&fold(key)));
+                    // An enum naming at least one key is an enum of keys; the others,
+                    // such as statuses, are left alone.
+                    let mut missing: Vec<&openapi::Reference> = groups
+                        .iter()
+                        .filter(|group| {
+                            group.extension
+                                || group
+                                    .references
+                                    .iter()
+                                    .any(|reference| known.contains(&fold(&reference.key)))
+                        })
+                        .flat_map(|group| &group.references)
+                        .filter(|reference| !known.contains(&fold(&reference.key)))
+                        .collect();
+                    missing.dedup();
+                    report.extend(missing.into_iter().map(|reference| {
+                        Finding::new(Check::BackendMissingKey)
+                            .key(&reference.key)
+                            .file(reference.file.display().to_string())
+                            .line(reference.line)
+                    }));
+                }
+
+                if !e2e.is_empty() && !unused_keys.is_empty() {
+                    let tests = run.timings.time("scan end-to-end tests", || {
+                        references_by_file(&e2e, &unused_keys, options.key_case_insensitive)
+                    });
+                    let mut tested: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                    for (file, references) in &tests {
+                        for (key, line) in references {
+                            tested.entry(key.clone()).or_default().push(format!(
+                                "{}:{}",
+                                file.display(),
+                                line
+                            ));
+                        }
+                    }
+                    unused_keys.retain(|key| !tested.contains_key(key));
+                    report.extend(tested.into_iter().map(|(key, tests)| {
+                        let finding = Finding::new(Check::E2eOnlyUsage)
+                            .key(&key)
+                            .file(base.file(&key))
+                            .detail("E2E tests", tests.join(", "));
+                        match base.lang {
+                            Some(lang) => finding.lang(lang),
+                            None => finding,
+                        }
+                    }));
+                }
+
+                report.extend(unused_keys.iter().map(|key| {
+                    let mut finding = Finding::new(Check::UnusedKey).key(key).file(base.file(key));
+                    if let Some(constants) = unused_constants.get(&fold(key)) {
+                        finding = finding.detail("Constant", constants.join(", "));
+                    }
+                    match base.lang {
+                        Some(lang) => finding.lang(lang),
+                        None => finding,
+                    }
+                }));
+                report.usage_scanned = true;
+                report.source_roots = roots
+                    .iter()
+                    .map(|root| root.dir.display().to_string())
+                    .collect();
+            }
+            report.source_files = files.len();
+
+            let mut interpolations = run.timings.time("check interpolations", || {
+                check_interpolations(
+                    base,
+                    &files,
+                    &config.reserved_placeholders,
+                    options.key_case_insensitive,
+                    calls.as_deref(),
+                    config.icu,
+                )
+            });
+            if !config.routes.is_empty() {
+                interpolations.extend(run.timings.time("check routes", || {
+                    let references =
+                        references_by_file(&sources, &base_keys, options.key_case_insensitive);
+                    checks::routes::check_routes(base, project, &references, &roots, &config.routes)
+                }));
+            }
+            // A key the base lacks may still be in other languages, left behind
+            // when the base dropped it or added ahead of it.
+            for finding in &mut interpolations {
+                let Some(key) = finding
+                    .key
+                    .as_deref()
+                    .filter(|_| finding.check == Check::UndefinedKey)
+                else {
+                    continue;
+                };
+                let defined: Vec<String> = languages
+                    .iter()
+                    .filter(|lang| {
+                        project
+                            .get(lang)
+                            .is_some_and(|data| data.values.contains_key(key))
+                    })
+                    .map(|lang| lang.to_uppercase())
+                    .collect();
+                if !defined.is_empty() {
+                    finding
+                        .details
+                        .push(("Defined in".to_string(), defined.join(", ")));
+                }
+            }
+            let mut suppressions = run
+                .timings
+                .time("scan suppressions", || Suppressions::scan(&files));
+            suppressions.apply(&mut interpolations);
+            report.extend(interpolations);
+            report.extend(suppressions.findings(config.suppressions.unterminated));
+        }
+
+        let state_name = options
+            .state
+            .as_deref()
+            .unwrap_or(Path::new(STATE_FILE))
+            .display()
+            .to_string();
+        if run.scope.is_full() {
+            report.extend(checks::languages::check_languages(
+                project,
+                config
+                    .expected_languages
+                    .as_deref()
+                    .map(|expected| (expected, config_path)),
+                run.previous
+                    .filter(|_| !options.update_state)
+                    .map(|state| (state, state_name.as_str())),
+            ));
+        }
+
+        report.extend(checks::check_unreadable_files(project));
+        report.extend(checks::check_duplicate_keys(project));
+        report.extend(checks::check_fuzzy_translations(project));
+        report.extend(checks::check_alias_conflicts(
+            project,
+            options.max_value_length,
+        ));
+        report.extend(checks::file_names::check_file_names(project));
+        report.extend(checks::hygiene::check_hygiene(project));
+        report.extend(checks::hygiene::check_unassigned_files(project));
+        if let Some(file_format) = &config.file_format {
+            report.extend(checks::line_endings::check_line_endings(
+                project,
+                file_format,
+            ));
+        }
+        checks::verify_file_attributions(project, &mut report);
+        // Coverage is measured before waivers apply, since a waived missing key
+        // is still missing; keys the project ignores don't count against it.
+        if report.consistency_checked {
+            let ignores = accepted.first().filter(|_| !config.ignores.is_empty());
+            let exempt =
+                |lang: &str, key: &str| exempt_from_coverage(config, ignores, &today, lang, key);
+            let compared: Vec<&str> = languages
+                .iter()
+                .copied()
+                .filter(|lang| Some(*lang) != base.lang)
+                .collect();
+            let blank: HashSet<(String, String)> = project
+                .flat_map_languages(|lang, data| {
+                    data.values
+                        .iter()
+                        .filter(|(_, entry)| entry.is_blank())
+                        .map(|(key, _)| (lang.to_string(), key.to_string()))
+                        .collect()
+                })
+                .into_iter()
+                .collect();
+            report.measure_coverage(&compared, &base.counted_keys(), exempt, |lang, key| {
+                blank.contains(&(lang.to_string(), key.to_string()))
+            });
+            for coverage in &mut report.coverage {
+                let folders = project.folders(&coverage.lang);
+                if folders.len() > 1 {
+                    coverage.folders = folders;
+                }
+            }
+            if let Some(minimum) = options.min_coverage {
+                let below: Vec<Finding> = report
+                    .coverage
+                    .iter()
+                    .filter(|coverage| coverage.percent() < minimum)
+                    .map(|coverage| {
+                        Finding::new(Check::CoverageBelowMinimum)
+                            .lang(&coverage.lang)
+                            .detail("Coverage", format!("{:.1}%", coverage.percent()))
+                            .detail("Minimum", format!("{}%", minimum))
+                    })
+                    .collect();
+                report.extend(below);
+            }
+        }
+        let context = match &config.context {
+            Some(context) => {
+                let manifest = Manifest::load(&context.manifest)?;
+                report.extend(manifest.stale(base.placeholders.keys().map(String::as_str)));
+                Some((manifest, context.max_links))
+            }
+            None => None,
+        };
+        report.override_severities(&config.severities);
+        for waivers in &accepted {
+            waivers.apply(&mut report, &today);
+        }
+        // The baseline written by this run takes every finding left.
+        let baseline = match &options.write_baseline {
+            Some(_) => Some(Baseline::of(&report)),
+            None => baseline,
+        };
+        let fixed = baseline
+            .as_ref()
+            .map_or(0, |baseline| baseline.apply(&mut report));
+        // Sources may still quote the excluded keys the languages no longer
+        // define.
+        report.findings.retain(|finding| {
+            finding
+                .key
+                .as_deref()
+                .is_none_or(|key| !config.exclude.excludes_key(key))
+        });
+        if let Some(touched) = run.touched {
+            report.findings.retain(|finding| touched.contains(finding));
+        }
+        Ok(Checked {
+            report,
+            stopped: false,
+            baseline,
+            fixed,
+            context,
+            warnings,
+            notes,
+        })
+    }
+}
+
+// Whether coverage and `stats` leave `key` out of the total of `lang`:
+// base-only keys, and those an `[[ignore]]` entry accepts as missing from
+// it.
+pub fn exempt_from_coverage(
+    config: &Config,
+    ignores: Option<&Waivers>,
+    today: &str,
+    lang: &str,
+    key: &str,
+) -> bool {
+    config
+        .base_only_namespaces
+        .iter()
+        .any(|pattern| pattern.covers(key))
+        || ignores.is_some_and(|ignores| {
+            ignores.accepts(&Finding::new(Check::MissingKey).lang(lang).key(key), today)
+        })
+}
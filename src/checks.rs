@@ -0,0 +1,1027 @@
+pub mod base_only;
+pub mod base_quality;
+pub mod do_not_translate;
+pub mod file_names;
+pub mod hygiene;
+pub mod icu;
+pub mod key_naming;
+pub mod languages;
+pub mod line_endings;
+pub mod linked_keys;
+pub mod links;
+pub mod markup;
+pub mod normalization;
+pub mod placeholders;
+pub mod plurals;
+pub mod quotes;
+pub mod reserved;
+pub mod review;
+pub mod routes;
+pub mod scaffolds;
+pub mod scripts;
+pub mod shapes;
+pub mod siblings;
+pub mod style;
+pub mod untranslated;
+
+use crate::base::Base;
+use crate::checks::reserved::Reserved;
+use crate::config::LoaderConfig;
+use crate::handles;
+use crate::interpolation;
+use crate::model::{LanguageData, Layout, Project, normalize_file_name};
+use crate::module;
+use crate::report::{Check, Excerpt, Finding, Report};
+use crate::schema::Schema;
+use crate::unicode;
+use crate::usage::CallKey;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+lazy_static! {
+    // Every syntax at once, longest first, so that `{{count}}` is read as one
+    // double-brace placeholder rather than a single-brace one inside braces.
+    static ref PLACEHOLDER_REGEX: Regex =
+        Regex::new(r"\{\{\s*(\w+)\s*}}|%\{(\w+)}|\{(\w+)}|%(?:\d+\$)?[sd]").unwrap();
+}
+
+// How a placeholder is spelled in a translation string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    // `{name}`, as in vue-i18n and ICU messages.
+    SingleBrace,
+    // `{{name}}`, as in i18next.
+    DoubleBrace,
+    // `%{name}`, as in Rails and Polyglot.
+    PercentBrace,
+    // `%s`, `%d` or `%1$s`, as in printf.
+    Positional,
+    // A pattern of `--placeholder-pattern`.
+    Custom,
+}
+
+impl Syntax {
+    pub fn name(self) -> &'static str {
+        match self {
+            Syntax::SingleBrace => "single-brace",
+            Syntax::DoubleBrace => "double-brace",
+            Syntax::PercentBrace => "percent-brace",
+            Syntax::Positional => "positional",
+            Syntax::Custom => "custom",
+        }
+    }
+
+    fn bit(self) -> usize {
+        1 << self as usize
+    }
+}
+
+// Which syntaxes count as placeholders, from `--placeholder-style`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    SingleBrace,
+    DoubleBrace,
+    Percent,
+    All,
+}
+
+impl PlaceholderStyle {
+    pub const ALL: [PlaceholderStyle; 4] = [
+        PlaceholderStyle::SingleBrace,
+        PlaceholderStyle::DoubleBrace,
+        PlaceholderStyle::Percent,
+        PlaceholderStyle::All,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PlaceholderStyle::SingleBrace => "single-brace",
+            PlaceholderStyle::DoubleBrace => "double-brace",
+            PlaceholderStyle::Percent => "percent",
+            PlaceholderStyle::All => "all",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<PlaceholderStyle> {
+        PlaceholderStyle::ALL
+            .into_iter()
+            .find(|style| style.name() == name)
+    }
+
+    fn syntaxes(self) -> &'static [Syntax] {
+        match self {
+            PlaceholderStyle::SingleBrace => &[Syntax::SingleBrace],
+            PlaceholderStyle::DoubleBrace => &[Syntax::DoubleBrace],
+            PlaceholderStyle::Percent => &[Syntax::PercentBrace, Syntax::Positional],
+            PlaceholderStyle::All => &[
+                Syntax::SingleBrace,
+                Syntax::DoubleBrace,
+                Syntax::PercentBrace,
+                Syntax::Positional,
+            ],
+        }
+    }
+}
+
+// A `--placeholder-pattern`: a regular expression whose first group
+// captures the name of the placeholder it matches.
+pub fn parse_placeholder_pattern(pattern: &str) -> Result<Regex, String> {
+    match Regex::new(pattern) {
+        Ok(regex) if regex.captures_len() > 1 => Ok(regex),
+        Ok(_) => Err("it captures no placeholder name".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+// The syntaxes every check reads placeholders with, one bit per `Syntax`,
+// single-brace until `set_placeholder_syntaxes`.
+static PLACEHOLDER_SYNTAXES: AtomicUsize = AtomicUsize::new(1);
+
+lazy_static! {
+    static ref PLACEHOLDER_PATTERNS: RwLock<Vec<Regex>> = RwLock::new(Vec::new());
+}
+
+// `--placeholder-style` and `--placeholder-pattern`, or their `[project]`
+// defaults.
+pub fn set_placeholder_syntaxes(styles: &[PlaceholderStyle], patterns: &[Regex]) {
+    let mut bits = styles
+        .iter()
+        .flat_map(|style| style.syntaxes())
+        .fold(0, |bits, syntax| bits | syntax.bit());
+    if !patterns.is_empty() {
+        bits |= Syntax::Custom.bit();
+    }
+    PLACEHOLDER_SYNTAXES.store(bits, Ordering::SeqCst);
+    *PLACEHOLDER_PATTERNS.write().unwrap() = patterns.to_vec();
+}
+
+fn reads(syntax: Syntax) -> bool {
+    PLACEHOLDER_SYNTAXES.load(Ordering::SeqCst) & syntax.bit() != 0
+}
+
+// Whether more than one syntax is read.
+fn mixed_syntaxes() -> bool {
+    PLACEHOLDER_SYNTAXES.load(Ordering::SeqCst).count_ones() > 1
+}
+
+// Where each placeholder like `{productName}` sits in a translation string,
+// with its name and syntax. A positional placeholder is named as written,
+// `%s` or `%1$s`. `'{name}'` is an ICU-escaped literal, not a placeholder.
+// Custom patterns only match where no other placeholder was found.
+pub fn placeholder_spans(text: &str) -> Vec<(Range<usize>, &str, Syntax)> {
+    let mut spans: Vec<(Range<usize>, &str, Syntax)> = PLACEHOLDER_REGEX
+        .captures_iter(text)
+        .filter_map(|cap| {
+            let whole = cap.get(0).unwrap();
+            let (syntax, name) = match (cap.get(1), cap.get(2), cap.get(3)) {
+                (Some(name), _, _) => (Syntax::DoubleBrace, name),
+                (_, Some(name), _) => (Syntax::PercentBrace, name),
+                (_, _, Some(name)) => (Syntax::SingleBrace, name),
+                _ => (Syntax::Positional, whole),
+            };
+            if !reads(syntax) || syntax == Syntax::SingleBrace && icu_quoted(text, &whole.range()) {
+                return None;
+            }
+            Some((whole.range(), name.as_str(), syntax))
+        })
+        .collect();
+    if !reads(Syntax::Custom) {
+        return spans;
+    }
+    for pattern in PLACEHOLDER_PATTERNS.read().unwrap().iter() {
+        for cap in pattern.captures_iter(text) {
+            let whole = cap.get(0).unwrap();
+            let overlaps = spans
+                .iter()
+                .any(|(range, _, _)| range.start < whole.end() && whole.start() < range.end);
+            if let Some(name) = cap.get(1).filter(|_| !whole.is_empty() && !overlaps) {
+                spans.push((whole.range(), name.as_str(), Syntax::Custom));
+            }
+        }
+    }
+    spans.sort_by_key(|(range, _, _)| range.start);
+    spans
+}
+
+// Whether a placeholder sits between single quotes, which ICU reads as
+// literal text; a doubled quote is an escaped quote instead.
+fn icu_quoted(text: &str, range: &Range<usize>) -> bool {
+    let before = &text[..range.start];
+    let after = &text[range.end..];
+    before.ends_with('\'')
+        && !before.ends_with("''")
+        && after.starts_with('\'')
+        && !after.starts_with("''")
+}
+
+// Where each variable like `{productName}` sits in a translation string,
+// braces included, with its name.
+pub fn variable_spans(text: &str) -> Vec<(Range<usize>, &str)> {
+    placeholder_spans(text)
+        .into_iter()
+        .map(|(range, name, _)| (range, name))
+        .collect()
+}
+
+// Extracts variables like `{productName}` format from a translation string
+pub fn extract_variables(text: &str) -> HashSet<String> {
+    variable_spans(text)
+        .into_iter()
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+// How many times each variable of `text` is used, where a set keeps each
+// once: `{email} ou {email}` counts `email` twice.
+pub fn count_variables(text: &str) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for (_, name) in variable_spans(text) {
+        *counts.entry(name.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+// `{name}: 2 (DE), 1 (FR)` for each placeholder one of the values uses more
+// than once, or `None` when none is repeated.
+pub fn format_counts(values: [(&str, String); 2]) -> Option<String> {
+    let [(first, first_label), (second, second_label)] = values;
+    let (first, second) = (count_variables(first), count_variables(second));
+    let names: BTreeSet<&String> = first.keys().chain(second.keys()).collect();
+    let repeated: Vec<String> = names
+        .into_iter()
+        .filter_map(|name| {
+            let (a, b) = (
+                first.get(name).copied().unwrap_or(0),
+                second.get(name).copied().unwrap_or(0),
+            );
+            (a > 1 || b > 1).then(|| {
+                format!(
+                    "{{{}}}: {} ({}), {} ({})",
+                    name, a, first_label, b, second_label
+                )
+            })
+        })
+        .collect();
+    (!repeated.is_empty()).then(|| repeated.join("; "))
+}
+
+// The placeholder a translation renamed, as `(found, expected)`: the one
+// base placeholder the value lacks replaced by the only one it has instead,
+// as many times as the base uses it. With several renamed, which one became
+// which is guesswork, so nothing is returned.
+pub fn renamed_placeholder(
+    base_vars: &HashSet<String>,
+    base_value: Option<&str>,
+    value: &str,
+) -> Option<(String, String)> {
+    let vars = extract_variables(value);
+    let mut missing = base_vars.difference(&vars);
+    let mut extra = vars.difference(base_vars);
+    let (Some(expected), None, Some(found), None) =
+        (missing.next(), missing.next(), extra.next(), extra.next())
+    else {
+        return None;
+    };
+    let uses = |text: &str, var: &str| {
+        variable_spans(text)
+            .into_iter()
+            .filter(|(_, name)| *name == var)
+            .count()
+    };
+    if base_value.is_some_and(|base_value| uses(base_value, expected) != uses(value, found)) {
+        return None;
+    }
+    Some((found.clone(), expected.clone()))
+}
+
+// Quotes a mismatched value with the variables the other side lacks
+// highlighted, using the same spans the comparison was made from.
+fn mismatch_excerpt(
+    label: String,
+    text: &str,
+    other_vars: &HashSet<String>,
+    max_value_length: usize,
+) -> Excerpt {
+    let highlights = variable_spans(text)
+        .into_iter()
+        .filter(|(_, name)| !other_vars.contains(*name))
+        .map(|(range, _)| range)
+        .collect();
+    Excerpt::new(label, text, highlights, max_value_length)
+}
+
+// The placeholders one side has and the other lacks, as written and with
+// their syntax, so that `{{count}}` in a base using `{count}` is told apart.
+fn mismatch_syntaxes(sides: [(&str, &HashSet<String>); 2]) -> String {
+    let mut offending = BTreeSet::new();
+    for (text, other_vars) in sides {
+        for (range, name, syntax) in placeholder_spans(text) {
+            if !other_vars.contains(name) {
+                offending.insert(format!("{} ({})", &text[range], syntax.name()));
+            }
+        }
+    }
+    offending.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+// Where a missing key belongs: the file of the language named like the
+// base file defining the key, once file name rules are applied to both.
+pub fn expected_file(project: &Project, base: &Base, lang: &str, key: &str) -> String {
+    let base_file = base.file(key);
+    let mut name = Path::new(&base_file)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or(base_file);
+    // A base module such as `en.ts` stands for the language's `de.json`.
+    if let Some((stem, extension)) = name.rsplit_once('.')
+        && module::EXTENSIONS.contains(&extension)
+    {
+        let stem = if Some(stem) == base.lang { lang } else { stem };
+        name = format!("{}.json", stem);
+    }
+    // Files named after their language: `en.common.json` stands for
+    // `de.common.json`.
+    if project.layout() == Layout::Files {
+        if let Some(rest) = base.lang.and_then(|base_lang| name.strip_prefix(base_lang)) {
+            name = format!("{}{}", lang, rest);
+        }
+        return project.base_path().join(name).display().to_string();
+    }
+    // A gettext template stands for each language's catalog.
+    if let Some(stem) = name.strip_suffix(".pot") {
+        name = format!("{}.po", stem);
+    }
+    let rules = project.file_name_rules();
+    if !rules.is_empty() {
+        name = normalize_file_name(base.lang.unwrap_or_default(), &name, rules);
+        let same = project.files(lang).into_iter().find(|file| {
+            file.file_name().is_some_and(|file_name| {
+                normalize_file_name(lang, &file_name.to_string_lossy(), rules) == name
+            })
+        });
+        if let Some(file) = same {
+            return file.display().to_string();
+        }
+    }
+    // The folders under the base language's, such as `LC_MESSAGES`, are
+    // kept.
+    let folder = base
+        .lang
+        .and_then(|base_lang| {
+            Path::new(&base.file(key))
+                .parent()?
+                .strip_prefix(project.base_path().join(base_lang))
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .unwrap_or_default();
+    project
+        .base_path()
+        .join(lang)
+        .join(folder)
+        .join(name)
+        .display()
+        .to_string()
+}
+
+fn get_translation_file(data: &LanguageData, key: &str) -> String {
+    data.file(key)
+        .map(str::to_string)
+        .unwrap_or_else(|| "Unknown file".to_string())
+}
+
+fn format_vars(vars: &HashSet<String>) -> String {
+    format!("{:?}", vars.iter().collect::<BTreeSet<_>>())
+}
+
+// The finding for `other_key`, the translation of the base key `key`, using
+// other placeholders, telling a placeholder renamed by the translation
+// apart. The caller sets the key reported.
+pub fn variable_mismatch(
+    base: &Base,
+    key: &str,
+    lang: &str,
+    data: &LanguageData,
+    other_key: &str,
+    max_value_length: usize,
+) -> Finding {
+    let base_vars = &base.placeholders[key];
+    let other_vars = extract_variables(&data.values[other_key].value);
+    let base_file = base.file(key);
+    let other_file = get_translation_file(data, other_key);
+    let other_value = &data.values[other_key].value;
+    // A renamed placeholder, typically translated like `{nombre}`
+    // for `{name}`, is told apart as `--fix` can rename it back.
+    let renamed = renamed_placeholder(base_vars, base.value(key), other_value);
+    let check = match renamed {
+        Some(_) => Check::TranslatedPlaceholder,
+        None => Check::VariableMismatch,
+    };
+    let mut finding = Finding::new(check)
+        .lang(lang)
+        .detail(
+            format!("Expected variables ({})", base.label()),
+            format_vars(base_vars),
+        )
+        .detail(
+            format!("Found variables ({})", lang.to_uppercase()),
+            format_vars(&other_vars),
+        );
+    // With one syntax read, spelling it out says nothing.
+    let syntaxes = mismatch_syntaxes([
+        (base.value(key).unwrap_or(""), &other_vars),
+        (other_value, base_vars),
+    ]);
+    if mixed_syntaxes() && !syntaxes.is_empty() {
+        finding = finding.detail("Syntax", syntaxes);
+    }
+    if let Some((found, expected)) = &renamed {
+        finding = finding.detail(
+            "Renamed",
+            format!("{{{}}} should be {{{}}}", found, expected),
+        );
+    }
+    if let Some(counts) = format_counts([
+        (other_value, lang.to_uppercase()),
+        (base.value(key).unwrap_or(""), base.label()),
+    ]) {
+        finding = finding.detail("Counts", counts);
+    }
+    finding = finding
+        .detail(
+            "Location",
+            format!("Expected in {} but found in {}", base_file, other_file),
+        )
+        .file(other_file);
+    if let Some(base_value) = base.value(key) {
+        finding = finding.value(mismatch_excerpt(
+            base.label(),
+            base_value,
+            &other_vars,
+            max_value_length,
+        ));
+    }
+    finding.value(mismatch_excerpt(
+        lang.to_uppercase(),
+        other_value,
+        base_vars,
+        max_value_length,
+    ))
+}
+
+// The keys each language misses or adds compared with the base, and the
+// values whose placeholders differ from the base; of `unused_keys`, those
+// a language still defines.
+pub fn check_translations(
+    base: &Base,
+    project: &Project,
+    unused_keys: &HashSet<String>,
+    schema: Option<&Schema>,
+    case_insensitive: bool,
+    max_value_length: usize,
+) -> Vec<Finding> {
+    let (base_keys, base_collisions) = key_index(
+        base.placeholders.keys().map(String::as_str),
+        case_insensitive,
+    );
+    let mut findings = case_collisions(base.lang, &base_collisions);
+
+    // Mismatches caused by the base value itself gaining or losing
+    // placeholders since the schema snapshot, reported once per key.
+    let base_changes: DashMap<&str, Vec<String>> = DashMap::new();
+
+    let language_findings = project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        let (other_keys, collisions) = key_index(data.keys(), case_insensitive);
+        let mut findings = case_collisions(Some(lang), &collisions);
+
+        let mut missing: Vec<&str> = base_keys
+            .iter()
+            .filter(|(canonical, _)| !other_keys.contains_key(*canonical))
+            .map(|(_, key)| *key)
+            .collect();
+        let mut extra: Vec<&str> = other_keys
+            .iter()
+            .filter(|(canonical, _)| !base_keys.contains_key(*canonical))
+            .map(|(_, key)| *key)
+            .collect();
+        findings.extend(shapes::check_shapes(
+            base,
+            lang,
+            data,
+            &mut missing,
+            &mut extra,
+        ));
+
+        // A missing key is reported in the file it should be added to, when
+        // the base is a language to tell it from.
+        for key in missing {
+            let file = match base.lang {
+                Some(_) => expected_file(project, base, lang, key),
+                None => get_translation_file(data, key),
+            };
+            findings.push(
+                Finding::new(Check::MissingKey)
+                    .lang(lang)
+                    .key(key)
+                    .file(file),
+            );
+        }
+
+        for key in extra {
+            findings.push(
+                Finding::new(Check::ExtraKey)
+                    .lang(lang)
+                    .key(key)
+                    .file(get_translation_file(data, key)),
+            );
+        }
+
+        for (canonical, key) in &base_keys {
+            let Some(other_key) = other_keys.get(canonical) else {
+                continue;
+            };
+
+            if unicode::nfc(key) != unicode::nfc(other_key) {
+                findings.push(
+                    Finding::new(Check::KeyCaseMismatch)
+                        .lang(lang)
+                        .key(key)
+                        .detail(format!("Found ({})", lang.to_uppercase()), *other_key)
+                        .file(get_translation_file(data, other_key)),
+                );
+            }
+
+            let found = data.values[*other_key].kind;
+            if let Some(expected) = base.kind(key).filter(|expected| *expected != found) {
+                findings.push(
+                    Finding::new(Check::TypeMismatch)
+                        .lang(lang)
+                        .key(key)
+                        .detail(format!("Expected ({})", base.label()), expected.name())
+                        .detail(format!("Found ({})", lang.to_uppercase()), found.name())
+                        .file(get_translation_file(data, other_key)),
+                );
+                continue;
+            }
+
+            let base_vars = &base.placeholders[*key];
+            let other_vars = extract_variables(&data.values[*other_key].value);
+
+            if *base_vars != other_vars {
+                if schema
+                    .is_some_and(|schema| schema.changed_placeholders(key, base_vars).is_some())
+                {
+                    base_changes.entry(key).or_default().push(lang.to_string());
+                    continue;
+                }
+
+                findings.push(
+                    variable_mismatch(base, key, lang, data, other_key, max_value_length).key(key),
+                );
+            }
+        }
+
+        for key in unused_keys {
+            if data.contains(key) {
+                findings.push(
+                    Finding::new(Check::UnusedKeyInTranslation)
+                        .lang(lang)
+                        .key(key)
+                        .file(get_translation_file(data, key)),
+                );
+            }
+        }
+
+        findings
+    });
+    findings.extend(language_findings);
+
+    for (key, mut langs) in base_changes {
+        langs.sort();
+        let base_vars = &base.placeholders[key];
+        let snapshot_vars = schema
+            .unwrap()
+            .changed_placeholders(key, base_vars)
+            .unwrap();
+
+        findings.push(
+            Finding::new(Check::BasePlaceholdersChanged)
+                .key(key)
+                .message(format!(
+                    "Base placeholders changed for {}; {} languages need updating",
+                    key,
+                    langs.len()
+                ))
+                .detail("Snapshot variables", format_vars(snapshot_vars))
+                .detail(
+                    format!("Base variables ({})", base.label()),
+                    format_vars(base_vars),
+                )
+                .detail("Languages", langs.join(", "))
+                .detail("File", base.file(key))
+                .file(base.file(key))
+                .weight(langs.len()),
+        );
+    }
+
+    findings
+}
+
+// Maps each key to its comparison form: its NFC spelling, also lowercased
+// when keys are compared case-insensitively. Keys that end up sharing a comparison form are
+// returned as collisions.
+fn key_index<'a>(
+    keys: impl Iterator<Item = &'a str>,
+    case_insensitive: bool,
+) -> (HashMap<String, &'a str>, Vec<Vec<&'a str>>) {
+    if !case_insensitive {
+        return (
+            keys.map(|key| (unicode::nfc(key).into_owned(), key))
+                .collect(),
+            vec![],
+        );
+    }
+
+    let mut groups: HashMap<String, Vec<&str>> = HashMap::new();
+    for key in keys {
+        groups
+            .entry(unicode::nfc(key).to_lowercase())
+            .or_default()
+            .push(key);
+    }
+
+    let mut index = HashMap::new();
+    let mut collisions = Vec::new();
+    for (canonical, mut group) in groups {
+        group.sort();
+        index.insert(canonical, group[0]);
+        if group.len() > 1 {
+            collisions.push(group);
+        }
+    }
+
+    (index, collisions)
+}
+
+fn case_collisions(lang: Option<&str>, collisions: &[Vec<&str>]) -> Vec<Finding> {
+    collisions
+        .iter()
+        .map(|group| {
+            let finding = Finding::new(Check::KeyCaseCollision).detail("Keys", group.join(", "));
+            match lang {
+                Some(lang) => finding.lang(lang),
+                None => finding,
+            }
+        })
+        .collect()
+}
+
+// Shown instead of a translation file that no longer exists by the time the
+// report is written.
+pub const MOVED_FILE: &str = "(file moved or deleted during run)";
+
+// The translation files that couldn't be read, and the languages left out
+// of the comparison because of them.
+pub fn check_unreadable_files(project: &Project) -> Vec<Finding> {
+    let unreadable = project.unreadable_files();
+    let mut findings: Vec<Finding> = unreadable
+        .iter()
+        .map(|(file, (lang, err))| {
+            Finding::new(Check::UnreadableFile)
+                .lang(lang)
+                .detail("Error", err)
+                .file(&**file)
+        })
+        .collect();
+    for lang in project.excluded_languages() {
+        let failed = unreadable
+            .values()
+            .filter(|(file_lang, _)| *file_lang == lang)
+            .count();
+        let files = project.files(&lang).len();
+        let message = match files {
+            0 => "its folder is unreadable".to_string(),
+            _ => format!("{} of {} files unreadable", failed, files),
+        };
+        findings.push(
+            Finding::new(Check::LanguageExcluded)
+                .lang(&lang)
+                .message(message),
+        );
+    }
+    findings
+}
+
+// A key defined in two files of a language keeps the value of the file read
+// last, in name order; the others are silently lost. So does a member a JSON
+// file defines twice in the same object, of which the first definition is
+// named with its line and column, the finding pointing at the one kept.
+pub fn check_duplicate_keys(project: &Project) -> Vec<Finding> {
+    project
+        .duplicate_keys()
+        .into_iter()
+        .map(|((lang, key), (dropped, kept))| {
+            let dropped: Vec<&str> = dropped.iter().map(|file| &**file).collect();
+            Finding::new(Check::DuplicateKey)
+                .lang(&lang)
+                .key(&key)
+                .detail("Ignored definition in", dropped.join(", "))
+                .file(&*kept)
+        })
+        .collect()
+}
+
+// gettext messages flagged `fuzzy` were guessed by `msgmerge` from a
+// similar message, and `msgfmt` leaves them out until someone reviews them:
+// the application shows the base text instead.
+pub fn check_fuzzy_translations(project: &Project) -> Vec<Finding> {
+    project
+        .fuzzy_entries()
+        .into_iter()
+        .map(|((lang, key), file)| {
+            Finding::new(Check::FuzzyTranslation)
+                .lang(&lang)
+                .key(&key)
+                .file(&*file)
+        })
+        .collect()
+}
+
+// A key the folders of an aliased language both define keeps the value of
+// the language's own folder; the other value is never shown to anyone.
+pub fn check_alias_conflicts(project: &Project, max_value_length: usize) -> Vec<Finding> {
+    project
+        .alias_conflicts()
+        .into_iter()
+        .map(|((lang, key), conflict)| {
+            let label = |file: &str| project.folder_of(&lang, Path::new(file));
+            Finding::new(Check::AliasConflict)
+                .lang(&lang)
+                .key(&key)
+                .detail("Other value in", &*conflict.other)
+                .file(&*conflict.kept)
+                .value(Excerpt::new(
+                    label(&conflict.kept),
+                    &conflict.kept_value,
+                    Vec::new(),
+                    max_value_length,
+                ))
+                .value(Excerpt::new(
+                    label(&conflict.other),
+                    &conflict.other_value,
+                    Vec::new(),
+                    max_value_length,
+                ))
+        })
+        .collect()
+}
+
+// Makes sure every translation file a finding points at still exists under
+// that exact name, replacing the ones that don't with `MOVED_FILE`. A plain
+// `exists()` is not enough: on case-insensitive file systems it also accepts
+// the old name of a file renamed by case only, such as `Common.json` after a
+// rename to `common.json`.
+pub fn verify_file_attributions(project: &Project, report: &mut Report) {
+    let mut listings: HashMap<PathBuf, HashSet<OsString>> = HashMap::new();
+    let mut exists = |path: &Path| {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return false;
+        };
+        listings
+            .entry(parent.to_path_buf())
+            .or_insert_with(|| {
+                let dir = if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent
+                };
+                fs::read_dir(dir)
+                    .map(|entries| entries.flatten().map(|entry| entry.file_name()).collect())
+                    .unwrap_or_default()
+            })
+            .contains(name)
+    };
+    let phantoms: Vec<Arc<str>> = project
+        .read_files()
+        .into_iter()
+        .filter(|file| !exists(Path::new(&**file)))
+        .collect();
+    if phantoms.is_empty() {
+        return;
+    }
+
+    for finding in &mut report.findings {
+        if finding
+            .file
+            .as_deref()
+            .is_some_and(|file| phantoms.iter().any(|phantom| **phantom == *file))
+        {
+            finding.file = Some(MOVED_FILE.to_string());
+        }
+        // Details such as the location of a variable mismatch name files too.
+        for (_, value) in &mut finding.details {
+            for phantom in &phantoms {
+                if value.contains(&**phantom) {
+                    *value = value.replace(&**phantom, MOVED_FILE);
+                }
+            }
+        }
+    }
+}
+
+// Cross-checks the variables supplied at translation call sites against the
+// placeholders of the base value, and against the rules of reserved names.
+// Keys are undefined when a `t()` call site, or any of `calls` when the
+// sources are searched for translation calls, names one the base lacks. An
+// array is defined by its elements, as call sites look it up whole.
+pub fn check_interpolations(
+    base: &Base,
+    files: &[PathBuf],
+    reserved: &Reserved,
+    case_insensitive: bool,
+    calls: Option<&[CallKey]>,
+    icu: bool,
+) -> Vec<Finding> {
+    let base_placeholders = &base.placeholders;
+    let arrays: HashSet<&str> = base_placeholders
+        .keys()
+        .map(|key| base.usage_key(key))
+        .collect();
+    let defined = |key: &str| base_placeholders.contains_key(key) || arrays.contains(key);
+    let (call_sites, undefined): (Vec<_>, Vec<_>) = files
+        .par_iter()
+        .filter_map(|file_path| {
+            handles::read_to_string(file_path)
+                .ok()
+                .map(|content| interpolation::find_call_sites(file_path, &content))
+        })
+        .flatten()
+        .partition(|site| base_placeholders.contains_key(&site.key));
+    let undefined: Vec<(&str, &Path, usize)> = match calls {
+        Some(calls) => calls
+            .iter()
+            .filter(|call| !call.prefix && !defined(&call.key))
+            .map(|call| (call.key.as_str(), call.file.as_path(), call.line))
+            .collect(),
+        None => undefined
+            .iter()
+            .filter(|site| !defined(&site.key))
+            .map(|site| (site.key.as_str(), site.file.as_path(), site.line))
+            .collect(),
+    };
+
+    // Keys written with another case still resolve when keys are matched
+    // case-insensitively; they are only reported when defined nowhere.
+    let folded: HashSet<String> = if case_insensitive {
+        base_placeholders
+            .keys()
+            .map(|key| key.as_str())
+            .chain(arrays.iter().copied())
+            .map(str::to_lowercase)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    let mut findings: Vec<Finding> = undefined
+        .iter()
+        .filter(|(key, _, _)| !folded.contains(&key.to_lowercase()))
+        .map(|(key, file, line)| {
+            Finding::new(Check::UndefinedKey)
+                .key(key)
+                .file(file.display().to_string())
+                .line(*line)
+        })
+        .collect();
+
+    findings.extend(reserved::check_reserved_arguments(&call_sites, reserved));
+
+    for site in &call_sites {
+        let file = site.file.display().to_string();
+        let Some(supplied) = &site.supplied else {
+            findings.push(
+                Finding::new(Check::UnverifiableInterpolation)
+                    .key(&site.key)
+                    .file(file)
+                    .line(site.line),
+            );
+            continue;
+        };
+
+        // Read as an ICU message, the words of the cases aren't
+        // placeholders.
+        let arguments: Option<HashSet<String>> = base
+            .value(&site.key)
+            .filter(|_| icu)
+            .and_then(|value| crate::icu::parse(value).ok())
+            .map(|arguments| {
+                arguments
+                    .into_iter()
+                    .map(|argument| argument.name)
+                    .collect()
+            });
+        // Positional placeholders are filled by order, not by name.
+        let required = arguments.as_ref().unwrap_or(&base_placeholders[&site.key]);
+        let missing: BTreeSet<_> = required
+            .difference(supplied)
+            .filter(|name| !name.starts_with('%'))
+            .collect();
+        if !missing.is_empty() {
+            findings.push(
+                Finding::new(Check::MissingInterpolation)
+                    .key(&site.key)
+                    .detail(
+                        "Missing",
+                        format!("{:?}", missing.iter().collect::<Vec<_>>()),
+                    )
+                    .file(file)
+                    .line(site.line),
+            );
+        }
+    }
+
+    findings
+}
+
+// Cross-checks the translation files bundled by the application's i18n
+// loader configuration against the files found on disk for each language.
+// Files listed but absent fail at runtime and count as errors; files on disk
+// that are never bundled are only warned about.
+pub fn check_loader_config(
+    project: &Project,
+    loader: &LoaderConfig,
+    source_path: &Path,
+) -> Result<Vec<Finding>, String> {
+    let source = fs::read_to_string(source_path).map_err(|err| {
+        format!(
+            "failed to read loader config {}: {}",
+            source_path.display(),
+            err
+        )
+    })?;
+
+    let mut listed: BTreeMap<Option<String>, BTreeSet<String>> = BTreeMap::new();
+    for caps in loader.pattern.captures_iter(&source) {
+        let lang = caps.name("lang").map(|lang| lang.as_str().to_string());
+        listed
+            .entry(lang)
+            .or_default()
+            .insert(caps["file"].to_string());
+    }
+    if listed.is_empty() {
+        return Err(format!(
+            "the loader config pattern matched no files in {}",
+            source_path.display()
+        ));
+    }
+
+    let mut languages: BTreeSet<&str> = project.languages().collect();
+    languages.extend(listed.keys().flatten().map(|lang| lang.as_str()));
+
+    let mut findings = Vec::new();
+    for lang in languages {
+        let expected: BTreeSet<&String> = listed
+            .get(&None)
+            .into_iter()
+            .chain(listed.get(&Some(lang.to_string())))
+            .flatten()
+            .collect();
+        let on_disk: BTreeSet<String> = project
+            .files(lang)
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect();
+        let folder = project.language_dir(lang);
+
+        for file in on_disk.iter().filter(|file| !expected.contains(file)) {
+            findings.push(
+                Finding::new(Check::LoaderUnreferencedFile)
+                    .lang(lang)
+                    .file(folder.join(file).display().to_string()),
+            );
+        }
+
+        for file in expected.iter().filter(|file| !on_disk.contains(**file)) {
+            findings.push(
+                Finding::new(Check::LoaderMissingFile)
+                    .lang(lang)
+                    .file(folder.join(file).display().to_string()),
+            );
+        }
+    }
+
+    Ok(findings)
+}
@@ -0,0 +1,42 @@
+use crate::base::Base;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use crate::selector::KeyPattern;
+
+// Keys under `base_only_namespaces`, such as development strings, are kept
+// in the base language only by design: their missing-key findings are
+// dropped, and a translation defining one is an error, the sign of wasted
+// translation effort. They are still checked in the base and for usage.
+// Returns the number of exempted base keys, for the summary.
+pub fn check_base_only(
+    base: &Base,
+    project: &Project,
+    namespaces: &[KeyPattern],
+    findings: &mut Vec<Finding>,
+) -> usize {
+    let covered = |key: &str| namespaces.iter().any(|pattern| pattern.covers(key));
+    findings.retain(|finding| {
+        finding.check != Check::MissingKey || !finding.key.as_deref().is_some_and(covered)
+    });
+
+    findings.extend(project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+        let mut keys: Vec<&str> = data
+            .keys()
+            .filter(|key| base.placeholders.contains_key(*key) && covered(key))
+            .collect();
+        keys.sort();
+        keys.into_iter()
+            .map(|key| {
+                Finding::new(Check::BaseOnlyKeyTranslated)
+                    .lang(lang)
+                    .key(key)
+                    .file(data.file(key).unwrap_or_default())
+            })
+            .collect()
+    }));
+
+    base.placeholders.keys().filter(|key| covered(key)).count()
+}
@@ -0,0 +1,93 @@
+use crate::base::Base;
+use crate::checks::variable_spans;
+use crate::loader::FileFormat;
+use crate::model::Entry;
+use crate::report::{Check, Finding};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Checks whose findings only restate a bad base value in every language.
+const DERIVED_CHECKS: [Check; 3] = [
+    Check::VariableMismatch,
+    Check::TranslatedPlaceholder,
+    Check::BasePlaceholdersChanged,
+];
+
+// gettext keys its messages by their base text, so a value being its key is
+// how a catalog is meant to be.
+fn problem(key: &str, entry: &Entry) -> Option<&'static str> {
+    let value = &entry.value;
+    if value.trim().is_empty() {
+        Some("empty value")
+    } else if value == key && FileFormat::of(&entry.file) != Some(FileFormat::Gettext) {
+        Some("value is the key")
+    } else if only_placeholders(value) {
+        Some("only placeholders")
+    } else {
+        None
+    }
+}
+
+// Whether a value has placeholders and no letters or digits besides them.
+fn only_placeholders(value: &str) -> bool {
+    let spans = variable_spans(value);
+    let mut rest = String::new();
+    let mut end = 0;
+    for (range, _) in &spans {
+        rest.push_str(&value[end..range.start]);
+        end = range.end;
+    }
+    rest.push_str(&value[end..]);
+    !spans.is_empty() && !rest.chars().any(char::is_alphanumeric)
+}
+
+// Base values that are almost certainly mistakes, typically left behind by a
+// bad merge: empty, made of placeholders only, or the key itself. Each one is
+// reported once as the root cause, and the findings it causes in the other
+// languages are dropped from `findings` and only counted on it.
+pub fn check_base_quality(base: &Base, findings: &mut Vec<Finding>) -> Vec<Finding> {
+    let problems: BTreeMap<&str, &str> = base
+        .entries()
+        .filter_map(|(key, entry)| Some((key, problem(key, entry)?)))
+        .collect();
+    if problems.is_empty() {
+        return Vec::new();
+    }
+
+    let mut suppressed: BTreeMap<&str, (usize, BTreeSet<String>)> = BTreeMap::new();
+    findings.retain(|finding| {
+        let Some(key) = finding.key.as_deref() else {
+            return true;
+        };
+        let Some((key, _)) = problems
+            .get_key_value(key)
+            .filter(|_| DERIVED_CHECKS.contains(&finding.check))
+        else {
+            return true;
+        };
+        let (count, langs) = suppressed.entry(key).or_default();
+        *count += finding.weight;
+        langs.extend(finding.lang.clone());
+        false
+    });
+
+    problems
+        .into_iter()
+        .map(|(key, problem)| {
+            let mut finding = Finding::new(Check::SuspiciousBaseValue)
+                .key(key)
+                .detail("Problem", problem)
+                .file(base.file(key));
+            if let Some(lang) = base.lang {
+                finding = finding.lang(lang);
+            }
+            if let Some((count, langs)) = suppressed.get(key) {
+                let langs: Vec<&str> = langs.iter().map(String::as_str).collect();
+                finding = finding.detail(
+                    "Suppressed",
+                    format!("{} findings in {}", count, langs.join(", ")),
+                );
+            }
+            finding
+        })
+        .collect()
+}
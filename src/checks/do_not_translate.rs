@@ -0,0 +1,43 @@
+use crate::base::Base;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+
+// Values marked as not to be translated, such as SKUs, legal entity names or
+// error codes, must be byte-identical to the base in every language.
+pub fn check_do_not_translate(base: &Base, project: &Project) -> Vec<Finding> {
+    let marked: Vec<_> = base
+        .entries()
+        .filter(|(_, entry)| entry.do_not_translate)
+        .collect();
+    if marked.is_empty() {
+        return Vec::new();
+    }
+
+    project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        marked
+            .iter()
+            .filter_map(|(key, expected)| {
+                let found = data.values.get(*key)?;
+                (found.value != expected.value).then(|| {
+                    Finding::new(Check::DoNotTranslateChanged)
+                        .lang(lang)
+                        .key(key)
+                        .detail(
+                            format!("Expected ({})", base.label()),
+                            format!("{:?}", expected.value),
+                        )
+                        .detail(
+                            format!("Found ({})", lang.to_uppercase()),
+                            format!("{:?}", found.value),
+                        )
+                        .detail("File", &*found.file)
+                        .file(&*found.file)
+                })
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,60 @@
+use crate::loader::translation_stem;
+use crate::model::{FileNameRule, Layout, Project};
+use crate::report::{Check, Finding};
+
+// Language folders whose files follow different naming conventions, some
+// named `common.json` and others `de.common.json`, usually half-way through
+// a migration. `[file_names] strip` lets the layouts be compared meanwhile.
+pub fn check_file_names(project: &Project) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    // Files named after their language all carry it.
+    if project.layout() == Layout::Files {
+        return findings;
+    }
+    for lang in project.languages() {
+        let mut plain = Vec::new();
+        let mut named = FileNameRule::ALL.map(|rule| (rule, Vec::new()));
+        for file in project.files(lang) {
+            let Some(name) = file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            let stem = translation_stem(&name).unwrap_or(&name);
+            match named
+                .iter_mut()
+                .find(|(rule, _)| rule.strip(lang, stem).is_some())
+            {
+                Some((_, names)) => names.push(name),
+                None => plain.push(name),
+            }
+        }
+
+        let conventions = [("Plain names", plain)]
+            .into_iter()
+            .chain(named.into_iter().map(|(rule, names)| (label(rule), names)));
+        let conventions: Vec<(&str, Vec<String>)> =
+            conventions.filter(|(_, names)| !names.is_empty()).collect();
+        if conventions.len() < 2 {
+            continue;
+        }
+
+        let folder = project.base_path().join(lang).display().to_string();
+        let mut finding = Finding::new(Check::MixedFileNames)
+            .lang(lang)
+            .detail("Folder", &folder);
+        for (label, names) in conventions {
+            finding = finding.detail(label, names.join(", "));
+        }
+        findings.push(finding.file(folder));
+    }
+    findings
+}
+
+fn label(rule: FileNameRule) -> &'static str {
+    match rule {
+        FileNameRule::LangPrefix => "Language prefix",
+        FileNameRule::LangSuffix => "Language suffix",
+    }
+}
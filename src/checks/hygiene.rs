@@ -0,0 +1,219 @@
+use crate::loader::{Flattened, flatten_json, parse_document};
+use crate::model::{Layout, Project};
+use crate::report::{Check, Finding};
+use crate::review::review_file;
+use crate::scaffold::scaffold_file;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Files operating systems and file managers leave behind.
+const OS_METADATA: [&str; 4] = [".DS_Store", "Thumbs.db", "desktop.ini", "Icon\r"];
+
+// The endings of editor swap files, backups and unresolved merge leftovers.
+const BACKUP_SUFFIXES: [&str; 5] = ["~", ".swp", ".swo", ".orig", ".rej"];
+
+// Characters Windows refuses in file names, on top of control characters.
+const ILLEGAL_CHARACTERS: [char; 8] = ['<', '>', ':', '"', '|', '?', '*', '\\'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    OsMetadata,
+    EditorBackup,
+    IllegalName,
+    Empty,
+    NotTranslation,
+}
+
+impl Problem {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Problem::OsMetadata => "operating system metadata",
+            Problem::EditorBackup => "editor backup or merge leftover",
+            Problem::IllegalName => "control or reserved characters in its name",
+            Problem::Empty => "empty file",
+            Problem::NotTranslation => "not a translation file",
+        }
+    }
+
+    pub fn action(self) -> &'static str {
+        match self {
+            Problem::OsMetadata => "delete it and add its name to .gitignore",
+            Problem::EditorBackup => "delete it",
+            Problem::IllegalName => "rename it",
+            Problem::Empty => "add its translations or delete it",
+            Problem::NotTranslation => "move it out of the translation directory",
+        }
+    }
+
+    // Whether `--fix hygiene` may delete the file: nobody wants these.
+    pub fn is_junk(self) -> bool {
+        matches!(self, Problem::OsMetadata | Problem::EditorBackup)
+    }
+}
+
+// A file of the translation directory that has no business there.
+#[derive(Debug, Clone)]
+pub struct StrayFile {
+    pub path: PathBuf,
+    pub lang: Option<String>,
+    pub problem: Problem,
+}
+
+fn classify(name: &str, path: &Path, known: bool) -> Option<Problem> {
+    if OS_METADATA.contains(&name) || name.starts_with("._") {
+        return Some(Problem::OsMetadata);
+    }
+    if BACKUP_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+        return Some(Problem::EditorBackup);
+    }
+    if name
+        .chars()
+        .any(|c| c.is_control() || ILLEGAL_CHARACTERS.contains(&c))
+    {
+        return Some(Problem::IllegalName);
+    }
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() == 0) {
+        return Some(Problem::Empty);
+    }
+    (!known).then_some(Problem::NotTranslation)
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        // `.gitkeep`, `.gitignore` and the like are kept on purpose.
+        if entry.file_name().to_string_lossy().starts_with(".git") {
+            continue;
+        }
+        if path.is_dir() {
+            walk(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+// Every file of the translation directory that isn't a translation file,
+// the base module or a review status or scaffold file the project reads,
+// along with the translation files left empty. Sorted by path.
+pub fn stray_files(project: &Project) -> Vec<StrayFile> {
+    let mut known: HashSet<PathBuf> = HashSet::new();
+    for lang in project.languages() {
+        known.extend(project.files(lang));
+        known.insert(review_file(project, lang));
+        known.insert(scaffold_file(project, lang));
+    }
+    // Unassigned files are reported as such, and the folders declared as
+    // holding no language keep what they like.
+    known.extend(project.unassigned_files());
+    let kept: Vec<PathBuf> = project.non_language_dirs().collect();
+
+    let mut files = Vec::new();
+    walk(project.base_path(), &mut files);
+    files.sort();
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let known = known.contains(&path) || kept.iter().any(|dir| path.starts_with(dir));
+            let name = path.file_name()?.to_string_lossy().to_string();
+            // The language the file belongs to, going by its folder or name.
+            let owner = match project.layout() {
+                Layout::Folders => path
+                    .strip_prefix(project.base_path())
+                    .ok()
+                    .and_then(|relative| relative.components().next())
+                    .map(|lang| lang.as_os_str().to_string_lossy().to_string()),
+                Layout::Files => name.split('.').next().map(str::to_string),
+            };
+            if owner
+                .as_deref()
+                .is_some_and(|lang| project.leaves_out(lang))
+            {
+                return None;
+            }
+            let problem = classify(&name, &path, known)?;
+            let lang = owner
+                .filter(|_| project.layout() == Layout::Folders)
+                .filter(|lang| project.languages().any(|known| known == lang));
+            Some(StrayFile {
+                path,
+                lang,
+                problem,
+            })
+        })
+        .collect()
+}
+
+// Whether the file reads as a translation file whatever its name, which
+// `--fix hygiene` never deletes.
+pub fn parses_as_translations(path: &Path) -> bool {
+    fs::read(path)
+        .ok()
+        .and_then(|content| serde_json::from_slice::<Value>(&content).ok())
+        .is_some_and(|value| value.is_object())
+}
+
+pub fn check_hygiene(project: &Project) -> Vec<Finding> {
+    stray_files(project)
+        .into_iter()
+        .map(|stray| {
+            let mut finding = Finding::new(Check::StrayFile)
+                .detail("Problem", stray.problem.describe())
+                .detail("Action", stray.problem.action())
+                .file(stray.path.display().to_string().escape_debug().to_string());
+            if let Some(lang) = &stray.lang {
+                finding = finding.lang(lang);
+            }
+            finding
+        })
+        .collect()
+}
+
+// The translation files no language reads, such as a `common.json` saved next to
+// the language folders, with how many keys they hold and how many of those
+// the base language lacks, which would be lost with the file.
+pub fn check_unassigned_files(project: &Project) -> Vec<Finding> {
+    // A published schema may stand in for the base language folder.
+    let base = project.get(project.base_lang());
+    project
+        .unassigned_files()
+        .into_iter()
+        .map(|path| {
+            let mut finding = Finding::new(Check::UnassignedFile);
+            if let Some(document) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| parse_document(&path, &content, project.base_lang()).ok())
+            {
+                let flattened = Flattened::default();
+                flatten_json(&document.value, String::new(), &flattened);
+                let keys = match base {
+                    Some(base) => format!(
+                        "{} ({} not in {})",
+                        flattened.leaves.len(),
+                        flattened
+                            .leaves
+                            .iter()
+                            .filter(|leaf| !base.contains(leaf.key()))
+                            .count(),
+                        project.base_lang()
+                    ),
+                    None => flattened.leaves.len().to_string(),
+                };
+                finding = finding.detail("Keys", keys);
+            }
+            let action = if path.parent() == Some(project.base_path()) {
+                "move its keys into the language files"
+            } else {
+                "move its keys into the language files, or list its folder in `languages.non_language_dirs`"
+            };
+            finding
+                .detail("Action", action)
+                .file(path.display().to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,199 @@
+use crate::base::Base;
+use crate::icu::{self, Argument, Kind};
+use crate::model::{Project, ValueKind};
+use crate::plural_rules;
+use crate::report::{Check, Finding};
+use dashmap::DashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// Checks whose findings only restate an ICU argument mismatch or a value
+// that couldn't be read.
+const DERIVED_CHECKS: [Check; 1] = [Check::VariableMismatch];
+
+fn format_arguments<'a>(arguments: impl Iterator<Item = &'a Argument>) -> String {
+    let arguments: BTreeSet<String> = arguments.map(Argument::to_string).collect();
+    arguments.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+// The cases of the `select` arguments, and the exact `=N` cases of the
+// plural ones, as `gender: female`: those a translation keeps whatever its
+// language, where plural categories depend on it.
+fn fixed_cases(arguments: &[Argument]) -> BTreeSet<String> {
+    arguments
+        .iter()
+        .flat_map(|argument| {
+            argument
+                .cases
+                .iter()
+                .filter(|case| argument.kind == Kind::Select || case.starts_with('='))
+                .map(move |case| format!("{}: {}", argument.name, case))
+        })
+        .collect()
+}
+
+// The argument mismatch of a translation, if any, comparing the arguments
+// by name and type, then the cases every language keeps.
+fn mismatch(expected: &[Argument], actual: &[Argument]) -> Option<Vec<(&'static str, String)>> {
+    let signature = |argument: &&Argument| (argument.name.clone(), argument.kind.clone());
+    let expected_set: HashSet<_> = expected
+        .iter()
+        .map(|argument| signature(&argument))
+        .collect();
+    let actual_set: HashSet<_> = actual.iter().map(|argument| signature(&argument)).collect();
+    let mut details = Vec::new();
+    let missing = expected
+        .iter()
+        .filter(|argument| !actual_set.contains(&signature(argument)));
+    let missing = format_arguments(missing);
+    if !missing.is_empty() {
+        details.push(("Missing", missing));
+    }
+    let unexpected = actual
+        .iter()
+        .filter(|argument| !expected_set.contains(&signature(argument)));
+    let unexpected = format_arguments(unexpected);
+    if !unexpected.is_empty() {
+        details.push(("Unexpected", unexpected));
+    }
+
+    let expected_cases = fixed_cases(expected);
+    let actual_cases = fixed_cases(actual);
+    let missing: Vec<&str> = expected_cases
+        .difference(&actual_cases)
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        details.push(("Missing cases", missing.join(", ")));
+    }
+    let unexpected: Vec<&str> = actual_cases
+        .difference(&expected_cases)
+        .map(String::as_str)
+        .collect();
+    if !unexpected.is_empty() {
+        details.push(("Unexpected cases", unexpected.join(", ")));
+    }
+    (!details.is_empty()).then_some(details)
+}
+
+// Values read as ICU MessageFormat, as `[icu]` enables: those that aren't
+// valid messages, translations whose arguments or `select` and `=N` cases
+// differ from the base, and `plural` arguments lacking a category their
+// language requires, such as `few` in Polish, reported as plural category
+// findings on the key. Languages without plural rules in the table, and
+// `selectordinal` arguments, aren't checked for categories. Variable
+// mismatches on the values reported here, or whose base and translation
+// both read as messages, are dropped from `findings`: the words of a case
+// look like placeholders to them.
+pub fn check_icu_messages(
+    base: &Base,
+    project: &Project,
+    findings: &mut Vec<Finding>,
+) -> Vec<Finding> {
+    let expected: HashMap<&str, Vec<Argument>> = base
+        .entries()
+        .filter(|(_, entry)| entry.kind == ValueKind::String)
+        .filter_map(|(key, entry)| Some((key, icu::parse(&entry.value).ok()?)))
+        .collect();
+
+    // The translations parsed as their base value did, whose arguments are
+    // then all there is to compare.
+    let compared: DashSet<(String, String)> = DashSet::new();
+    let issues = project.flat_map_languages(|lang, data| {
+        let categories = plural_rules::categories(lang).map(|(_, categories)| categories);
+        let mut issues = Vec::new();
+        let mut keys: Vec<&str> = data
+            .values
+            .iter()
+            .filter(|(_, entry)| entry.kind == ValueKind::String)
+            .map(|(key, _)| &**key)
+            .collect();
+        keys.sort_unstable();
+        for key in keys {
+            let entry = &data.values[key];
+            let arguments = match icu::parse(&entry.value) {
+                Ok(arguments) => arguments,
+                Err(err) => {
+                    issues.push(
+                        Finding::new(Check::IcuSyntaxError)
+                            .lang(lang)
+                            .key(key)
+                            .detail("Error", err.to_string())
+                            .detail("Value", format!("{:?}", entry.value))
+                            .file(&*entry.file),
+                    );
+                    continue;
+                }
+            };
+
+            for argument in arguments.iter().filter(|a| a.kind == Kind::Plural) {
+                let Some(categories) = categories else {
+                    break;
+                };
+                let missing: Vec<_> = categories
+                    .iter()
+                    .filter(|category| !category.optional)
+                    .filter(|category| !argument.cases.iter().any(|case| case == category.name))
+                    .collect();
+                if missing.is_empty() {
+                    continue;
+                }
+                let names: Vec<&str> = missing.iter().map(|category| category.name).collect();
+                let examples: Vec<String> = missing
+                    .iter()
+                    .map(|category| format!("{}: {}", category.name, category.examples))
+                    .collect();
+                issues.push(
+                    Finding::new(Check::PluralCategoryMissing)
+                        .lang(lang)
+                        .key(key)
+                        .detail("Argument", format!("{{{}}}", argument.name))
+                        .detail("Missing", names.join(", "))
+                        .detail("Examples", examples.join("; "))
+                        .file(&*entry.file),
+                );
+            }
+
+            if base.lang == Some(lang) {
+                continue;
+            }
+            let Some(expected) = expected.get(key) else {
+                continue;
+            };
+            compared.insert((lang.to_string(), key.to_string()));
+            if let Some(details) = mismatch(expected, &arguments) {
+                let mut finding = Finding::new(Check::IcuArgumentMismatch).lang(lang).key(key);
+                for (label, value) in details {
+                    finding = finding.detail(label, value);
+                }
+                issues.push(
+                    finding
+                        .detail(
+                            format!("Base ({})", base.label()),
+                            format!("{:?}", base.value(key).unwrap_or_default()),
+                        )
+                        .detail(
+                            format!("Value ({})", lang.to_uppercase()),
+                            format!("{:?}", entry.value),
+                        )
+                        .file(&*entry.file),
+                );
+            }
+        }
+        issues
+    });
+
+    let reported: HashSet<(&str, &str)> = issues
+        .iter()
+        .filter(|issue| issue.check != Check::PluralCategoryMissing)
+        .filter_map(|issue| Some((issue.lang.as_deref()?, issue.key.as_deref()?)))
+        .collect();
+    findings.retain(|finding| {
+        let (Some(lang), Some(key)) = (&finding.lang, &finding.key) else {
+            return true;
+        };
+        !(DERIVED_CHECKS.contains(&finding.check)
+            && (reported.contains(&(lang.as_str(), key.as_str()))
+                || compared.contains(&(lang.clone(), key.clone()))))
+    });
+    issues
+}
@@ -0,0 +1,188 @@
+use crate::config::KeyNamingConfig;
+use crate::loader::{FileFormat, translation_stem};
+use crate::model::{FileNameRule, Layout, Project};
+use crate::report::{Check, Finding};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+// The CLDR plural categories, as suffixes of the plural forms of a key.
+const PLURAL_SUFFIXES: [&str; 6] = ["_zero", "_one", "_two", "_few", "_many", "_other"];
+
+// How the segments of a key are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCase {
+    // `saveButton`
+    Camel,
+    // `SaveButton`
+    Pascal,
+    // `save_button`
+    Snake,
+    // `save-button`
+    Kebab,
+}
+
+impl KeyCase {
+    pub fn parse(name: &str) -> Option<KeyCase> {
+        match name {
+            "camel" => Some(KeyCase::Camel),
+            "pascal" => Some(KeyCase::Pascal),
+            "snake" => Some(KeyCase::Snake),
+            "kebab" => Some(KeyCase::Kebab),
+            _ => None,
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            KeyCase::Camel => "lowerCamelCase",
+            KeyCase::Pascal => "PascalCase",
+            KeyCase::Snake => "snake_case",
+            KeyCase::Kebab => "kebab-case",
+        }
+    }
+
+    // Whether `segment` is written in this case. Digits count as lower case
+    // letters, though a segment never starts with one.
+    fn follows(self, segment: &str) -> bool {
+        let mut chars = segment.chars();
+        let Some(first) = chars.next() else {
+            return false;
+        };
+        let rest = chars.as_str();
+        let separated = |separator: char| {
+            first.is_ascii_lowercase()
+                && !rest.starts_with(separator)
+                && !segment.ends_with(separator)
+                && !segment.contains(&format!("{0}{0}", separator))
+                && rest
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == separator)
+        };
+        match self {
+            KeyCase::Camel => {
+                first.is_ascii_lowercase() && rest.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            KeyCase::Pascal => {
+                first.is_ascii_uppercase() && rest.chars().all(|c| c.is_ascii_alphanumeric())
+            }
+            KeyCase::Snake => separated('_'),
+            KeyCase::Kebab => separated('-'),
+        }
+    }
+}
+
+// The namespace the keys of a translation file must start with: the name of
+// the file, without the language it carries. Files named after their
+// language alone, base modules and gettext catalogs, keyed by their
+// messages, have none.
+fn namespace<'f>(project: &Project, lang: &str, file: &'f Path) -> Option<&'f str> {
+    let name = file.file_name()?.to_str()?;
+    if FileFormat::of(name) == Some(FileFormat::Gettext) {
+        return None;
+    }
+    let stem = translation_stem(name)?;
+    let stem = FileNameRule::ALL
+        .into_iter()
+        .filter(|rule| {
+            project.layout() == Layout::Files || project.file_name_rules().contains(rule)
+        })
+        .find_map(|rule| rule.strip(lang, stem))
+        .unwrap_or(stem);
+    (stem != lang).then_some(stem)
+}
+
+// The base keys breaking a `[key_naming]` rule: a segment not written in
+// `case` or not matching `pattern`, more segments than `max_depth`, or with
+// `file_namespace`, a first segment other than the name of its file. The
+// elements of an array are judged by the array, and plural suffixes such as
+// `_one` aren't part of the segment.
+pub fn check_key_naming(project: &Project, config: &KeyNamingConfig) -> Vec<Finding> {
+    let lang = project.base_lang();
+    let base = project.base();
+    let keys: BTreeSet<(&str, &str)> = base
+        .values
+        .iter()
+        .map(|(key, entry)| (base.outermost_array(key).unwrap_or(key), &*entry.file))
+        .collect();
+
+    let mut findings = Vec::new();
+    for (key, file) in keys {
+        let finding = |rule: &str| {
+            Finding::new(Check::KeyNaming)
+                .lang(lang)
+                .key(key)
+                .detail("Rule", rule)
+        };
+        let segments: Vec<&str> = key.split('.').collect();
+        let last = segments.len() - 1;
+        let mut case = Vec::new();
+        let mut pattern = Vec::new();
+        for (index, segment) in segments.iter().enumerate() {
+            let segment = if index == last {
+                PLURAL_SUFFIXES
+                    .iter()
+                    .find_map(|suffix| segment.strip_suffix(suffix))
+                    .filter(|stem| !stem.is_empty())
+                    .unwrap_or(segment)
+            } else {
+                segment
+            };
+            if config
+                .case
+                .is_some_and(|expected| !expected.follows(segment))
+            {
+                case.push(segment);
+            }
+            if config
+                .pattern
+                .as_ref()
+                .is_some_and(|expected| !expected.is_match(segment))
+            {
+                pattern.push(segment);
+            }
+        }
+        if let Some(expected) = config.case
+            && !case.is_empty()
+        {
+            findings.push(
+                finding("case")
+                    .detail("Expected", expected.describe())
+                    .detail("Segments", case.join(", "))
+                    .file(file),
+            );
+        }
+        if let Some(expected) = &config.pattern
+            && !pattern.is_empty()
+        {
+            findings.push(
+                finding("pattern")
+                    .detail("Expected", format!("matching `{}`", expected.as_str()))
+                    .detail("Segments", pattern.join(", "))
+                    .file(file),
+            );
+        }
+        if let Some(max_depth) = config.max_depth
+            && segments.len() > max_depth
+        {
+            findings.push(
+                finding("max_depth")
+                    .detail(
+                        "Depth",
+                        format!("{} (at most {})", segments.len(), max_depth),
+                    )
+                    .file(file),
+            );
+        }
+        if config.file_namespace
+            && let Some(namespace) = namespace(project, lang, Path::new(file))
+            && segments[0] != namespace
+        {
+            findings.push(
+                finding("file_namespace")
+                    .detail("Expected", format!("{}.*", namespace))
+                    .file(file),
+            );
+        }
+    }
+    findings
+}
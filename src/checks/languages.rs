@@ -0,0 +1,101 @@
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use crate::state::State;
+
+// `1240` as `1,240`.
+fn thousands(count: usize) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn keys(count: usize) -> String {
+    match count {
+        1 => "1 key".to_string(),
+        count => format!("{} keys", thousands(count)),
+    }
+}
+
+// Languages that appeared or disappeared since they were acknowledged, so a
+// folder deleted by accident doesn't just mean fewer languages get checked.
+// The languages are acknowledged by `languages.expected` in the
+// configuration when it's set, and by the state file otherwise. State files
+// written before languages were recorded acknowledge nothing.
+pub fn check_languages(
+    project: &Project,
+    expected: Option<(&[String], &str)>,
+    state: Option<(&State, &str)>,
+) -> Vec<Finding> {
+    let key_count = |lang: &str| project.get(lang).map_or(0, |data| data.keys().count());
+    let mut findings = Vec::new();
+
+    if let Some((expected, config)) = expected {
+        for lang in expected {
+            if project.get(lang).is_none() {
+                findings.push(
+                    Finding::new(Check::LanguageRemoved)
+                        .lang(lang)
+                        .message(format!(
+                            "Language {} is listed in `languages.expected` but has no folder",
+                            lang
+                        ))
+                        .file(config),
+                );
+            }
+        }
+        for lang in project.languages() {
+            if !expected.iter().any(|listed| listed == lang) {
+                findings.push(
+                    Finding::new(Check::LanguageAdded)
+                        .lang(lang)
+                        .message(format!(
+                            "Language {} is not listed in `languages.expected` — {}",
+                            lang,
+                            keys(key_count(lang))
+                        ))
+                        .file(config),
+                );
+            }
+        }
+    } else if let Some((state, path)) = state
+        && !state.languages.is_empty()
+    {
+        for (lang, count) in &state.languages {
+            if project.get(lang).is_none() {
+                findings.push(
+                    Finding::new(Check::LanguageRemoved)
+                        .lang(lang)
+                        .message(format!(
+                            "Language {} removed since the last acknowledged state — {} gone",
+                            lang,
+                            keys(*count)
+                        ))
+                        .detail("Acknowledge with", "--update-state")
+                        .file(path),
+                );
+            }
+        }
+        for lang in project.languages() {
+            if !state.languages.contains_key(lang) {
+                findings.push(
+                    Finding::new(Check::LanguageAdded)
+                        .lang(lang)
+                        .message(format!(
+                            "Language {} added since the last acknowledged state — {}",
+                            lang,
+                            keys(key_count(lang))
+                        ))
+                        .detail("Acknowledge with", "--update-state")
+                        .file(path),
+                );
+            }
+        }
+    }
+    findings
+}
@@ -0,0 +1,168 @@
+use crate::config::{Eol, FileFormatConfig};
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use std::fs;
+
+// The line breaks of a file, counted on its bytes so that a file that
+// isn't valid UTF-8 is still described.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LineBreaks {
+    pub lf: usize,
+    pub crlf: usize,
+    // Lone carriage returns, as classic Mac OS wrote them.
+    pub cr: usize,
+    pub final_newline: bool,
+}
+
+impl LineBreaks {
+    pub fn count(bytes: &[u8]) -> LineBreaks {
+        let mut breaks = LineBreaks::default();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'\r' if bytes.get(pos + 1) == Some(&b'\n') => {
+                    breaks.crlf += 1;
+                    pos += 1;
+                }
+                b'\r' => breaks.cr += 1,
+                b'\n' => breaks.lf += 1,
+                _ => {}
+            }
+            pos += 1;
+        }
+        breaks.final_newline = matches!(bytes.last(), Some(b'\n' | b'\r'));
+        breaks
+    }
+
+    // `lf`, `crlf` or `cr`, `mixed (…)` with the count of each when a file
+    // uses several, `none` for a single line.
+    pub fn style(&self) -> String {
+        let used: Vec<(&str, usize)> = [("lf", self.lf), ("crlf", self.crlf), ("cr", self.cr)]
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        match used[..] {
+            [] => "none".to_string(),
+            [(name, _)] => name.to_string(),
+            _ => {
+                let counts: Vec<String> = used
+                    .iter()
+                    .map(|(name, count)| format!("{} {}", count, name.to_uppercase()))
+                    .collect();
+                format!("mixed ({})", counts.join(", "))
+            }
+        }
+    }
+
+    // Whether every line break is `eol`.
+    pub fn only(&self, eol: Eol) -> bool {
+        match eol {
+            Eol::Lf => self.crlf == 0 && self.cr == 0,
+            Eol::Crlf => self.lf == 0 && self.cr == 0,
+        }
+    }
+
+    // The line break a file mostly uses, for a final newline added without
+    // an `eol` policy.
+    fn usual(&self) -> Eol {
+        if self.crlf > self.lf + self.cr {
+            Eol::Crlf
+        } else {
+            Eol::Lf
+        }
+    }
+}
+
+// JSON translation files whose line breaks or final newline don't follow
+// `[file_format]`. Base modules are left to the project's formatter.
+pub fn check_line_endings(project: &Project, format: &FileFormatConfig) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for lang in project.languages() {
+        for path in project.files(lang) {
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            // Unreadable and empty files are reported on their own.
+            let Ok(bytes) = fs::read(&path) else {
+                continue;
+            };
+            if bytes.is_empty() {
+                continue;
+            }
+            let breaks = LineBreaks::count(&bytes);
+            let mut finding = Finding::new(Check::LineEndings).lang(lang);
+            let mut broken = false;
+            if let Some(eol) = format.eol
+                && !breaks.only(eol)
+            {
+                finding = finding
+                    .detail("Line endings", breaks.style())
+                    .detail("Expected", eol.name());
+                broken = true;
+            }
+            match format.final_newline {
+                Some(true) if !breaks.final_newline => {
+                    finding = finding.detail("Final newline", "missing");
+                    broken = true;
+                }
+                Some(false) if breaks.final_newline => {
+                    finding = finding.detail("Final newline", "unexpected");
+                    broken = true;
+                }
+                _ => {}
+            }
+            if broken {
+                findings.push(finding.file(path.display().to_string()));
+            }
+        }
+    }
+    findings
+}
+
+// `--fix eol`: the line breaks between JSON tokens written as `eol`, and
+// the final newline added or removed. Whatever is inside a string literal
+// is kept byte for byte; a raw line break there isn't JSON anyway.
+pub fn fix_line_endings(content: &str, format: &FileFormatConfig) -> String {
+    let breaks = LineBreaks::count(content.as_bytes());
+    let eol = format.eol.unwrap_or_else(|| breaks.usual());
+    let mut fixed = String::with_capacity(content.len());
+    let (mut in_string, mut escaped) = (false, false);
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            fixed.push(c);
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                fixed.push(c);
+            }
+            '\r' | '\n' if format.eol.is_some() => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                fixed.push_str(eol.as_str());
+            }
+            _ => fixed.push(c),
+        }
+    }
+    match format.final_newline {
+        Some(true) if !fixed.is_empty() && !fixed.ends_with(['\n', '\r']) => {
+            fixed.push_str(eol.as_str());
+        }
+        Some(false) if !in_string => {
+            let end = fixed.trim_end_matches(['\n', '\r']).len();
+            fixed.truncate(end);
+        }
+        _ => {}
+    }
+    fixed
+}
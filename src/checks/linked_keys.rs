@@ -0,0 +1,75 @@
+use crate::config::LinkedKeys;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use crate::unicode;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+// Verifies that every member of a linked key group has the same value in
+// each language, ignoring differences in Unicode normalization. Groups declared with wildcards are expanded per wildcard
+// match, so `shop.buttons.*` and `admin.buttons.*` link `shop.buttons.save`
+// with `admin.buttons.save`.
+pub fn check_linked_keys(project: &Project, groups: &[LinkedKeys]) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let mut keys: Vec<&str> = data.keys().collect();
+        keys.sort();
+
+        let mut findings = Vec::new();
+        for group in groups {
+            // Members found per wildcard match, for every pattern of the
+            // group. Only the key range sharing a pattern's literal prefix
+            // is scanned.
+            let mut instances: BTreeMap<Vec<String>, BTreeMap<usize, &str>> = BTreeMap::new();
+            for (index, pattern) in group.members.iter().enumerate() {
+                let prefix = pattern.prefix();
+                let start = keys.partition_point(|key| *key < prefix);
+                for key in keys[start..]
+                    .iter()
+                    .take_while(|key| key.starts_with(prefix))
+                {
+                    if let Some(captures) = pattern.captures(key) {
+                        instances.entry(captures).or_default().insert(index, key);
+                    }
+                }
+            }
+
+            for (captures, present) in &instances {
+                let values: BTreeSet<Cow<str>> = present
+                    .values()
+                    .map(|key| unicode::nfc(&data.values[*key].value))
+                    .collect();
+                let members: Vec<String> = group
+                    .members
+                    .iter()
+                    .map(|pattern| pattern.expand(captures))
+                    .collect();
+
+                if values.len() > 1 {
+                    let first = present.values().next().unwrap();
+                    let mut finding = Finding::new(Check::LinkedKeyDrift)
+                        .lang(lang)
+                        .key(&members[0])
+                        .file(&*data.values[*first].file);
+                    for key in present.values() {
+                        let entry = &data.values[*key];
+                        finding =
+                            finding.detail(*key, format!("{:?} ({})", entry.value, entry.file));
+                    }
+                    findings.push(finding);
+                }
+
+                for (index, member) in members.iter().enumerate() {
+                    if !present.contains_key(&index) {
+                        findings.push(
+                            Finding::new(Check::LinkedKeyMissing)
+                                .lang(lang)
+                                .key(member)
+                                .detail("Group", members.join(", ")),
+                        );
+                    }
+                }
+            }
+        }
+        findings
+    })
+}
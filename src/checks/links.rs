@@ -0,0 +1,128 @@
+use crate::model::{LanguageData, Project};
+use crate::report::{Check, Finding};
+use crate::selector::KeyPattern;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+
+lazy_static! {
+    // vue-i18n's linked messages: `@:key`, with an optional modifier as in
+    // `@.lower:key`, or with the key in `@:(key)` or `@:{'key'}`.
+    static ref LINK_REGEX: Regex =
+        Regex::new(r"@(?:\.\w+)?:(?:\(([^)]+)\)|\{'([^']+)'\}|([\w\-|./]+))").unwrap();
+}
+
+// The keys a value links to, in order. A bare key ending a sentence, as in
+// `See @:legal.terms.`, doesn't keep the period.
+pub fn links(value: &str) -> Vec<&str> {
+    LINK_REGEX
+        .captures_iter(value)
+        .filter_map(|caps| match (caps.get(1).or(caps.get(2)), caps.get(3)) {
+            (Some(key), _) => Some(key.as_str()),
+            (None, Some(key)) => Some(key.as_str().trim_end_matches('.')),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+// Reports, in every language, the links into keys outside `linkable` (when
+// the allowlist is configured) and the links that lead back to the key they
+// start from, which never finish resolving.
+pub fn check_links(project: &Project, linkable: Option<&[KeyPattern]>) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let mut keys: Vec<&str> = data.keys().collect();
+        keys.sort();
+
+        let mut findings = Vec::new();
+        let mut graph: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for key in &keys {
+            let targets = links(data.value(key).unwrap_or_default());
+            if let Some(linkable) = linkable {
+                for target in &targets {
+                    if !linkable
+                        .iter()
+                        .any(|pattern| pattern.captures(target).is_some())
+                    {
+                        findings.push(
+                            Finding::new(Check::LinkNotAllowed)
+                                .lang(lang)
+                                .key(key)
+                                .file(data.file(key).unwrap_or_default())
+                                .detail("Link", format!("@:{}", target))
+                                .detail(
+                                    "Linkable keys",
+                                    linkable
+                                        .iter()
+                                        .map(KeyPattern::as_str)
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                )
+                                .detail("File", data.file(key).unwrap_or_default()),
+                        );
+                    }
+                }
+            }
+            let targets: Vec<&str> = targets
+                .into_iter()
+                .filter(|target| data.contains(target))
+                .collect();
+            if !targets.is_empty() {
+                graph.insert(key, targets);
+            }
+        }
+
+        for cycle in cycles(&graph) {
+            let chain: Vec<&str> = cycle.iter().copied().chain([cycle[0]]).collect();
+            findings.push(
+                Finding::new(Check::LinkCycle)
+                    .lang(lang)
+                    .key(cycle[0])
+                    .file(data.file(cycle[0]).unwrap_or_default())
+                    .detail("Chain", chain.join(" → "))
+                    .detail("Files", files(data, &cycle)),
+            );
+        }
+        findings
+    })
+}
+
+// The distinct files the keys of a cycle are defined in.
+fn files(data: &LanguageData, cycle: &[&str]) -> String {
+    let files: BTreeSet<&str> = cycle.iter().filter_map(|key| data.file(key)).collect();
+    files.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+// One cycle per back edge found by a depth-first walk, each rotated to start
+// at its smallest key so that it is reported once.
+fn cycles<'a>(graph: &BTreeMap<&'a str, Vec<&'a str>>) -> BTreeSet<Vec<&'a str>> {
+    fn visit<'a>(
+        key: &'a str,
+        graph: &BTreeMap<&'a str, Vec<&'a str>>,
+        path: &mut Vec<&'a str>,
+        done: &mut BTreeSet<&'a str>,
+        cycles: &mut BTreeSet<Vec<&'a str>>,
+    ) {
+        if let Some(start) = path.iter().position(|step| *step == key) {
+            let mut cycle = path[start..].to_vec();
+            let smallest = (0..cycle.len()).min_by_key(|index| cycle[*index]).unwrap();
+            cycle.rotate_left(smallest);
+            cycles.insert(cycle);
+            return;
+        }
+        if !done.insert(key) {
+            return;
+        }
+        path.push(key);
+        for target in graph.get(key).into_iter().flatten() {
+            visit(target, graph, path, done, cycles);
+        }
+        path.pop();
+    }
+
+    let mut cycles = BTreeSet::new();
+    let mut done = BTreeSet::new();
+    for key in graph.keys() {
+        visit(key, graph, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+}
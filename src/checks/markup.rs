@@ -0,0 +1,220 @@
+use crate::base::Base;
+use crate::model::{Project, ValueKind};
+use crate::report::{Check, Finding};
+use std::collections::BTreeMap;
+
+// Elements that never hold anything, so `<br>` needs no closing tag.
+const VOID_ELEMENTS: [&str; 8] = ["br", "hr", "img", "input", "wbr", "meta", "link", "source"];
+
+// The tags of a value: how many times each element, by lowercased name,
+// sits in each parent element, `None` standing for the top level, and the
+// first place the tags don't balance. Numbered tags, as in react-i18next's
+// `<0>link</0>`, are elements like any other.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Markup {
+    pub elements: BTreeMap<(Option<String>, String), usize>,
+    pub unbalanced: Option<String>,
+}
+
+impl Markup {
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.unbalanced.is_none()
+    }
+
+    fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for ((_, name), count) in &self.elements {
+            *counts.entry(name.as_str()).or_default() += count;
+        }
+        counts
+    }
+}
+
+// The end of the tag starting at `start`, after its `>`, skipping quoted
+// attribute values.
+fn tag_end(value: &str, start: usize) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in value[start..].char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '>') => return Some(start + index + 1),
+            (None, '<') if index > 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tag_name(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':')))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+// Reads the tags of `value`. A `<` that doesn't start a tag, as in
+// "a < b", is text, and comments are skipped.
+pub fn markup(value: &str) -> Markup {
+    let mut markup = Markup::default();
+    let mut open: Vec<String> = Vec::new();
+    let mut at = 0;
+    while let Some(found) = value[at..].find('<') {
+        let start = at + found;
+        let rest = &value[start + 1..];
+        if rest.starts_with("!--") {
+            at = value[start..]
+                .find("-->")
+                .map_or(value.len(), |end| start + end + 3);
+            continue;
+        }
+        let closing = rest.starts_with('/');
+        let name = tag_name(if closing { &rest[1..] } else { rest });
+        let Some(end) = (!name.is_empty()).then(|| tag_end(value, start)).flatten() else {
+            at = start + 1;
+            continue;
+        };
+        at = end;
+        let name = name.to_ascii_lowercase();
+        if closing {
+            match open.iter().rposition(|element| *element == name) {
+                Some(index) => {
+                    if index + 1 < open.len() && markup.unbalanced.is_none() {
+                        markup.unbalanced =
+                            Some(format!("</{}> closes <{}>", name, open[open.len() - 1]));
+                    }
+                    open.truncate(index);
+                }
+                None => {
+                    markup
+                        .unbalanced
+                        .get_or_insert_with(|| format!("</{}> closes no tag", name));
+                }
+            }
+            continue;
+        }
+        *markup
+            .elements
+            .entry((open.last().cloned(), name.clone()))
+            .or_default() += 1;
+        let self_closing = value[..end - 1].ends_with('/');
+        if !self_closing && !VOID_ELEMENTS.contains(&name.as_str()) {
+            open.push(name);
+        }
+    }
+    if let Some(name) = open.first() {
+        markup
+            .unbalanced
+            .get_or_insert_with(|| format!("<{}> is never closed", name));
+    }
+    markup
+}
+
+fn format_element(parent: &Option<String>, name: &str) -> String {
+    match parent {
+        Some(parent) => format!("<{}> in <{}>", name, parent),
+        None => format!("<{}> at the top level", name),
+    }
+}
+
+// The tags `expected` has more of than `actual`, as `<strong>` or
+// `<strong> ×2`.
+fn fewer(expected: &BTreeMap<&str, usize>, actual: &BTreeMap<&str, usize>) -> String {
+    expected
+        .iter()
+        .filter_map(|(name, count)| {
+            let missing = count - actual.get(name).copied().unwrap_or(0).min(*count);
+            match missing {
+                0 => None,
+                1 => Some(format!("<{}>", name)),
+                _ => Some(format!("<{}> ×{}", name, missing)),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// What differs between the tags of a base value and of its translation:
+// tags dropped, added or renamed, tags that don't balance, and elements
+// moved into another one. Siblings may come in another order, as word
+// order differs between languages.
+pub fn mismatch(expected: &Markup, actual: &Markup) -> Vec<(&'static str, String)> {
+    let mut details = Vec::new();
+    let (expected_counts, actual_counts) = (expected.counts(), actual.counts());
+    let missing = fewer(&expected_counts, &actual_counts);
+    if !missing.is_empty() {
+        details.push(("Missing", missing));
+    }
+    let unexpected = fewer(&actual_counts, &expected_counts);
+    if !unexpected.is_empty() {
+        details.push(("Unexpected", unexpected));
+    }
+    if let Some(unbalanced) = &actual.unbalanced {
+        details.push(("Unbalanced", unbalanced.clone()));
+    }
+    if details.is_empty() && expected.elements != actual.elements {
+        let moved: Vec<String> = actual
+            .elements
+            .iter()
+            .filter(|(element, count)| expected.elements.get(*element) != Some(*count))
+            .map(|((parent, name), _)| format_element(parent, name))
+            .collect();
+        details.push(("Nesting", moved.join(", ")));
+    }
+    details
+}
+
+// Translations whose inline markup differs from the base value's, which
+// breaks rendering without an error: a dropped `</strong>` bolds the rest of
+// the page. Base values whose own tags don't balance, and do-not-translate
+// values, aren't compared.
+pub fn check_markup(base: &Base, project: &Project) -> Vec<Finding> {
+    let expected: Vec<(&str, &str, Markup)> = base
+        .entries()
+        .filter(|(_, entry)| entry.kind == ValueKind::String && !entry.do_not_translate)
+        .map(|(key, entry)| (key, entry.value.as_str(), markup(&entry.value)))
+        .filter(|(_, _, markup)| markup.unbalanced.is_none())
+        .collect();
+
+    project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        for (key, base_value, expected) in &expected {
+            let Some(entry) = data
+                .values
+                .get(*key)
+                .filter(|entry| entry.kind == ValueKind::String)
+            else {
+                continue;
+            };
+            let actual = markup(&entry.value);
+            if expected.is_empty() && actual.is_empty() {
+                continue;
+            }
+            let details = mismatch(expected, &actual);
+            if details.is_empty() {
+                continue;
+            }
+            let mut finding = Finding::new(Check::MarkupMismatch).lang(lang).key(key);
+            for (label, value) in details {
+                finding = finding.detail(label, value);
+            }
+            findings.push(
+                finding
+                    .detail(
+                        format!("Expected ({})", base.label()),
+                        format!("{:?}", base_value),
+                    )
+                    .detail(
+                        format!("Found ({})", lang.to_uppercase()),
+                        format!("{:?}", entry.value),
+                    )
+                    .file(&*entry.file),
+            );
+        }
+        findings
+    })
+}
@@ -0,0 +1,35 @@
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use crate::unicode::{self, Decomposed};
+
+fn describe(runs: &[Decomposed]) -> String {
+    runs.iter()
+        .map(Decomposed::describe)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Keys and values stored decomposed (NFD), as some macOS tools export them.
+// They look identical to their NFC spelling but compare differently.
+pub fn check_normalization(project: &Project) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            for (part, text) in [("key", &**key), ("value", entry.value.as_str())] {
+                let runs = unicode::decomposed(text);
+                if runs.is_empty() {
+                    continue;
+                }
+                findings.push(
+                    Finding::new(Check::NotNfc)
+                        .lang(lang)
+                        .key(key)
+                        .detail("In", part)
+                        .detail("Code points", describe(&runs))
+                        .file(&*entry.file),
+                );
+            }
+        }
+        findings
+    })
+}
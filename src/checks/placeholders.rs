@@ -0,0 +1,201 @@
+use crate::base::Base;
+use crate::checks::{count_variables, format_counts, variable_spans};
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Where a placeholder sits in a value, ignoring the surrounding whitespace
+// and a trailing punctuation mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Leading,
+    Medial,
+    Trailing,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Position::Leading => "leading",
+            Position::Medial => "medial",
+            Position::Trailing => "trailing",
+        })
+    }
+}
+
+// The position of each placeholder of `value`, or `None` when the value
+// is nothing but placeholders, whose order is always free.
+fn positions(value: &str) -> Option<BTreeMap<&str, Position>> {
+    let start = value.len() - value.trim_start().len();
+    let end = value
+        .trim_end()
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() && c != '}')
+        .trim_end()
+        .len();
+
+    let spans = variable_spans(value);
+    let mut text = value.to_string();
+    for (range, _) in spans.iter().rev() {
+        text.replace_range(range.clone(), "");
+    }
+    if !text.chars().any(char::is_alphanumeric) {
+        return None;
+    }
+
+    Some(
+        spans
+            .into_iter()
+            .map(|(range, name)| {
+                let position = if range.start <= start {
+                    Position::Leading
+                } else if range.end >= end {
+                    Position::Trailing
+                } else {
+                    Position::Medial
+                };
+                (name, position)
+            })
+            .collect(),
+    )
+}
+
+// Translations moving a placeholder shared with the base to or from the
+// start or the end of the value, like a German value opening with `{name}`
+// where the French base has it mid-sentence. Often legitimate, these are
+// worth a linguist's look as the structure may come from missing context.
+pub fn check_placeholder_positions(base: &Base, project: &Project) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            let Some(base_value) = base.value(key) else {
+                continue;
+            };
+            let (Some(expected), Some(actual)) = (positions(base_value), positions(&entry.value))
+            else {
+                continue;
+            };
+
+            let moved: Vec<String> = expected
+                .iter()
+                .filter_map(|(name, expected)| {
+                    let actual = actual.get(name).filter(|actual| *actual != expected)?;
+                    Some(format!(
+                        "{{{}}} {} in {}, {} in {}",
+                        name,
+                        expected,
+                        base.label(),
+                        actual,
+                        lang.to_uppercase()
+                    ))
+                })
+                .collect();
+            if moved.is_empty() {
+                continue;
+            }
+
+            findings.push(
+                Finding::new(Check::PlaceholderPosition)
+                    .lang(lang)
+                    .key(key)
+                    .detail("Moved", moved.join(", "))
+                    .detail(
+                        format!("Base ({})", base.label()),
+                        format!("{:?}", base_value),
+                    )
+                    .detail(
+                        format!("Value ({})", lang.to_uppercase()),
+                        format!("{:?}", entry.value),
+                    )
+                    .file(data.file(key).unwrap_or_default()),
+            );
+        }
+        findings
+    })
+}
+
+// How often a base value may use one placeholder before it looks like a
+// copy-paste slip rather than wording.
+pub const BASE_REPEAT_LIMIT: usize = 3;
+
+// Values using one of their placeholders more times than the base does,
+// like `Envoyer à {email} ou {email}` where a second placeholder was meant,
+// which the set comparison of variable mismatches can't see. Values whose
+// placeholders differ from the base are reported as variable mismatches
+// instead. Base values repeating a placeholder `BASE_REPEAT_LIMIT` times or
+// more are reported too.
+pub fn check_placeholder_counts(base: &Base, project: &Project) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = base
+        .entries()
+        .filter_map(|(key, entry)| {
+            let repeated: Vec<String> = count_variables(&entry.value)
+                .into_iter()
+                .filter(|(_, count)| *count >= BASE_REPEAT_LIMIT)
+                .map(|(name, count)| format!("{{{}}}: {} ({})", name, count, base.label()))
+                .collect();
+            if repeated.is_empty() {
+                return None;
+            }
+            let mut finding = Finding::new(Check::DuplicatePlaceholder)
+                .key(key)
+                .detail("Counts", repeated.join("; "))
+                .detail(
+                    format!("Base ({})", base.label()),
+                    format!("{:?}", entry.value),
+                )
+                .file(base.file(key));
+            if let Some(lang) = base.lang {
+                finding = finding.lang(lang);
+            }
+            Some(finding)
+        })
+        .collect();
+
+    findings.extend(project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            let Some(base_value) = base.value(key) else {
+                continue;
+            };
+            let expected = count_variables(base_value);
+            let found = count_variables(&entry.value);
+            if !found.keys().eq(expected.keys())
+                || found.iter().all(|(name, count)| *count <= expected[name])
+            {
+                continue;
+            }
+            let Some(counts) = format_counts([
+                (&entry.value, lang.to_uppercase()),
+                (base_value, base.label()),
+            ]) else {
+                continue;
+            };
+
+            findings.push(
+                Finding::new(Check::DuplicatePlaceholder)
+                    .lang(lang)
+                    .key(key)
+                    .detail("Counts", counts)
+                    .detail(
+                        format!("Base ({})", base.label()),
+                        format!("{:?}", base_value),
+                    )
+                    .detail(
+                        format!("Value ({})", lang.to_uppercase()),
+                        format!("{:?}", entry.value),
+                    )
+                    .file(data.file(key).unwrap_or_default()),
+            );
+        }
+        findings
+    }));
+    findings
+}
@@ -0,0 +1,229 @@
+use crate::base::Base;
+use crate::checks::{self, extract_variables};
+use crate::config::PluralFormsConfig;
+use crate::model::Project;
+use crate::plural_rules::{self, Category};
+use crate::report::{Check, Finding};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+// Checks whose findings on a plural form only restate the base forms
+// disagreeing.
+const DERIVED_CHECKS: [Check; 2] = [Check::VariableMismatch, Check::TranslatedPlaceholder];
+
+fn format_vars(vars: &HashSet<String>) -> String {
+    if vars.is_empty() {
+        return "none".to_string();
+    }
+    let vars: BTreeSet<String> = vars.iter().map(|var| format!("{{{}}}", var)).collect();
+    vars.into_iter().collect::<Vec<_>>().join(", ")
+}
+
+// Base keys whose plural forms, like `item_one` and `item_other`, don't use
+// the same placeholders. Languages are compared form by form, so only a
+// translation disagreeing with its base form shows it, as a mismatch that
+// goes away with the base fix: these are dropped from `findings` and only
+// counted on the base key.
+pub fn check_plural_forms(
+    base: &Base,
+    config: &PluralFormsConfig,
+    findings: &mut Vec<Finding>,
+) -> Vec<Finding> {
+    // The forms of each logical key, in the order of the suffixes.
+    let mut keys: BTreeMap<&str, BTreeMap<usize, &str>> = BTreeMap::new();
+    for key in base.placeholders.keys() {
+        if let Some((stem, suffix)) = key.rsplit_once('_')
+            && let Some(index) = config.suffixes.iter().position(|known| known == suffix)
+        {
+            keys.entry(stem).or_default().insert(index, key);
+        }
+    }
+    keys.retain(|_, forms| {
+        let mut sets = forms.values().map(|key| &base.placeholders[*key]);
+        let first = sets.next();
+        forms.len() > 1 && sets.any(|set| Some(set) != first)
+    });
+    if keys.is_empty() {
+        return Vec::new();
+    }
+
+    let stems: BTreeMap<&str, &str> = keys
+        .iter()
+        .flat_map(|(stem, forms)| forms.values().map(move |key| (*key, *stem)))
+        .collect();
+    let mut suppressed: BTreeMap<&str, (usize, BTreeSet<String>)> = BTreeMap::new();
+    findings.retain(|finding| {
+        let Some(stem) = finding
+            .key
+            .as_deref()
+            .and_then(|key| stems.get(key))
+            .filter(|_| DERIVED_CHECKS.contains(&finding.check))
+        else {
+            return true;
+        };
+        let (count, langs) = suppressed.entry(stem).or_default();
+        *count += finding.weight;
+        langs.extend(finding.lang.clone());
+        false
+    });
+
+    keys.into_iter()
+        .map(|(stem, forms)| {
+            let file = base.file(forms.values().next().unwrap());
+            let mut finding = Finding::new(Check::PluralFormMismatch).key(stem);
+            if let Some(lang) = base.lang {
+                finding = finding.lang(lang);
+            }
+            for key in forms.values() {
+                finding = finding.detail(*key, format_vars(&base.placeholders[*key]));
+            }
+            if let Some((count, langs)) = suppressed.get(stem) {
+                let langs: Vec<&str> = langs.iter().map(String::as_str).collect();
+                finding = finding.detail(
+                    "Suppressed",
+                    format!("{} findings in {}", count, langs.join(", ")),
+                );
+            }
+            finding.file(file)
+        })
+        .collect()
+}
+
+// The CLDR plural categories, in their usual order.
+const CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+// The stems of the base keys with plural forms: those whose `_other` form
+// is defined, when `other` is one of the suffixes.
+fn plural_stems<'a>(base: &'a Base, config: &PluralFormsConfig) -> BTreeSet<&'a str> {
+    if !config.suffixes.iter().any(|suffix| suffix == "other") {
+        return BTreeSet::new();
+    }
+    base.placeholders
+        .keys()
+        .filter_map(|key| key.strip_suffix("_other"))
+        .collect()
+}
+
+// Plural keys whose forms don't match the CLDR categories of their
+// language, as the table shipped with the binary has them: a form the
+// language requires missing, like `item_few` in Russian, or a form it never
+// uses, like `item_few` in German. The forms of a language are its own, so
+// the missing and extra key findings on them are dropped from `findings`,
+// and a form the base doesn't have is compared with the base `_other` form
+// for its placeholders. Languages the table doesn't know are listed instead
+// of being checked.
+pub fn check_plural_categories(
+    base: &Base,
+    project: &Project,
+    config: &PluralFormsConfig,
+    findings: &mut Vec<Finding>,
+    max_value_length: usize,
+) -> Vec<Finding> {
+    let stems = plural_stems(base, config);
+    if stems.is_empty() {
+        return Vec::new();
+    }
+    let suffixes: Vec<&str> = CATEGORIES
+        .into_iter()
+        .filter(|category| config.suffixes.iter().any(|suffix| suffix == category))
+        .collect();
+    let rules: BTreeMap<&str, Option<&[Category]>> = project
+        .languages()
+        .map(|lang| {
+            let categories = plural_rules::categories(lang).map(|(_, categories)| categories);
+            (lang, categories)
+        })
+        .collect();
+
+    findings.retain(|finding| {
+        let (Some(lang), Some(key)) = (&finding.lang, &finding.key) else {
+            return true;
+        };
+        let is_form = key
+            .rsplit_once('_')
+            .is_some_and(|(stem, suffix)| stems.contains(stem) && suffixes.contains(&suffix));
+        let known = matches!(rules.get(lang.as_str()), Some(Some(_)));
+        !(is_form && known && matches!(finding.check, Check::MissingKey | Check::ExtraKey))
+    });
+
+    let mut issues = Vec::new();
+    for (lang, categories) in rules {
+        let Some(categories) = categories else {
+            issues.push(
+                Finding::new(Check::PluralRulesUnknown)
+                    .lang(lang)
+                    .detail("Tag", lang)
+                    .detail("Skipped", format!("{} plural keys", stems.len())),
+            );
+            continue;
+        };
+        let Some(data) = project.get(lang) else {
+            continue;
+        };
+        for stem in &stems {
+            let forms: Vec<(&str, String)> = suffixes
+                .iter()
+                .map(|suffix| (*suffix, format!("{}_{}", stem, suffix)))
+                .filter(|(_, key)| data.values.contains_key(key.as_str()))
+                .collect();
+            // A key missing altogether is reported form by form already.
+            let Some((_, first)) = forms.first() else {
+                continue;
+            };
+            let file = data.values[first.as_str()].file.to_string();
+
+            let missing: Vec<&Category> = categories
+                .iter()
+                .filter(|category| !category.optional)
+                .filter(|category| !forms.iter().any(|(suffix, _)| *suffix == category.name))
+                .collect();
+            if !missing.is_empty() {
+                let names: Vec<&str> = missing.iter().map(|category| category.name).collect();
+                let examples: Vec<String> = missing
+                    .iter()
+                    .map(|category| format!("{}: {}", category.name, category.examples))
+                    .collect();
+                issues.push(
+                    Finding::new(Check::PluralCategoryMissing)
+                        .lang(lang)
+                        .key(stem)
+                        .detail("Missing", names.join(", "))
+                        .detail("Examples", examples.join("; "))
+                        .file(&file),
+                );
+            }
+
+            let unused: Vec<&str> = forms
+                .iter()
+                .map(|(suffix, _)| *suffix)
+                .filter(|suffix| !categories.iter().any(|category| category.name == *suffix))
+                .collect();
+            if !unused.is_empty() {
+                issues.push(
+                    Finding::new(Check::PluralCategoryUnused)
+                        .lang(lang)
+                        .key(stem)
+                        .detail("Unused", unused.join(", "))
+                        .file(&file),
+                );
+            }
+
+            if base.lang == Some(lang) {
+                continue;
+            }
+            let other = format!("{}_other", stem);
+            for (_, key) in &forms {
+                if base.placeholders.contains_key(key) {
+                    continue;
+                }
+                let vars = extract_variables(&data.values[key.as_str()].value);
+                if vars != base.placeholders[&other] {
+                    issues.push(
+                        checks::variable_mismatch(base, &other, lang, data, key, max_value_length)
+                            .key(key),
+                    );
+                }
+            }
+        }
+    }
+    issues
+}
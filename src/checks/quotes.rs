@@ -0,0 +1,152 @@
+use crate::model::Project;
+use crate::report::{Check, Excerpt, Finding};
+use std::ops::Range;
+
+// Entities spelling a quote, decoded when the value isn't HTML.
+const ENTITIES: [(&str, char); 5] = [
+    ("&apos;", '\''),
+    ("&#39;", '\''),
+    ("&#x27;", '\''),
+    ("&quot;", '"'),
+    ("&#34;", '"'),
+];
+
+// A quote spelled in a way that shows up as is once rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteIssue {
+    pub span: Range<usize>,
+    pub problem: &'static str,
+    // What the span should read, when that is unambiguous.
+    pub replacement: Option<char>,
+}
+
+// Values are HTML when they hold a tag or when their key says so, as in
+// `terms_html` or `legal.bodyHtml`; their entities are left alone.
+fn is_html(key: &str, value: &str) -> bool {
+    let last = key.rsplit('.').next().unwrap_or(key);
+    last.to_ascii_lowercase().ends_with("html")
+        || value.match_indices('<').any(|(index, _)| {
+            value[index + 1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/')
+        })
+}
+
+// Backslash escapes that survived JSON decoding (`\'` and `\"` are fixable,
+// a literal `\n` or `\t` may be meant), quote entities outside HTML, and
+// smart quotes or guillemets opened without being closed or the reverse.
+pub fn quote_issues(key: &str, value: &str) -> Vec<QuoteIssue> {
+    let mut issues = Vec::new();
+    let mut chars = value.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+        let (problem, replacement) = match chars.peek().map(|(_, next)| *next) {
+            Some('\'') => ("backslash-escaped apostrophe", Some('\'')),
+            Some('"') => ("backslash-escaped quote", Some('"')),
+            Some('n') => ("literal \\n", None),
+            Some('t') => ("literal \\t", None),
+            _ => continue,
+        };
+        chars.next();
+        issues.push(QuoteIssue {
+            span: index..index + 2,
+            problem,
+            replacement,
+        });
+    }
+
+    if !is_html(key, value) {
+        for (entity, quote) in ENTITIES {
+            issues.extend(value.match_indices(entity).map(|(index, _)| QuoteIssue {
+                span: index..index + entity.len(),
+                problem: if quote == '\'' {
+                    "apostrophe entity outside HTML"
+                } else {
+                    "quote entity outside HTML"
+                },
+                replacement: Some(quote),
+            }));
+        }
+    }
+
+    // Languages pair `“` with `”`, `„` with `“` or `”`: a value with an odd
+    // number of them leaves one open. Guillemets open and close in either
+    // direction (`«…»`, `»…«`), but always as many of each.
+    let doubles: Vec<(usize, char)> = value
+        .char_indices()
+        .filter(|(_, c)| matches!(c, '“' | '”' | '„'))
+        .collect();
+    if doubles.len() % 2 == 1 {
+        let (index, c) = doubles[doubles.len() - 1];
+        issues.push(QuoteIssue {
+            span: index..index + c.len_utf8(),
+            problem: "unpaired smart quote",
+            replacement: None,
+        });
+    }
+    let opening = value.matches('«').count();
+    let closing = value.matches('»').count();
+    if opening != closing {
+        let lone = if opening > closing { '«' } else { '»' };
+        let index = value.rfind(lone).unwrap();
+        issues.push(QuoteIssue {
+            span: index..index + lone.len_utf8(),
+            problem: "unpaired guillemet",
+            replacement: None,
+        });
+    }
+
+    issues.sort_by_key(|issue| issue.span.start);
+    issues
+}
+
+// The value with its unambiguous issues fixed, if it has any.
+pub fn fix_quotes(key: &str, value: &str) -> Option<String> {
+    let issues = quote_issues(key, value);
+    if issues.iter().all(|issue| issue.replacement.is_none()) {
+        return None;
+    }
+    let mut fixed = value.to_string();
+    for issue in issues.iter().rev() {
+        if let Some(replacement) = issue.replacement {
+            fixed.replace_range(issue.span.clone(), &replacement.to_string());
+        }
+    }
+    Some(fixed)
+}
+
+pub fn check_quotes(project: &Project, max_value_length: usize) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let mut keys: Vec<&str> = data.keys().collect();
+        keys.sort();
+        let mut findings = Vec::new();
+        for key in keys {
+            let entry = &data.values[key];
+            let issues = quote_issues(key, &entry.value);
+            if issues.is_empty() {
+                continue;
+            }
+            let mut finding = Finding::new(Check::QuoteEscape)
+                .lang(lang)
+                .key(key)
+                .file(&*entry.file);
+            for issue in &issues {
+                finding = finding.detail(
+                    "Found",
+                    format!("{} ({})", &entry.value[issue.span.clone()], issue.problem),
+                );
+            }
+            if issues.iter().any(|issue| issue.replacement.is_some()) {
+                finding = finding.detail("Fix", "--fix quote-escapes");
+            }
+            let highlights = issues.iter().map(|issue| issue.span.clone()).collect();
+            findings.push(finding.value(Excerpt::new(
+                lang.to_uppercase(),
+                &entry.value,
+                highlights,
+                max_value_length,
+            )));
+        }
+        findings
+    })
+}
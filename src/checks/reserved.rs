@@ -0,0 +1,112 @@
+use crate::base::Base;
+use crate::checks::extract_variables;
+use crate::interpolation::CallSite;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use std::collections::BTreeMap;
+
+// What a placeholder name reserved by the formatting library allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    // The name may not appear in any value.
+    Forbidden,
+    // A translation uses the name exactly when its base value does.
+    MustMatchBase,
+    // Call sites may only supply it a number; a string literal is reported.
+    NumericOnly,
+}
+
+impl Rule {
+    pub fn parse(name: &str) -> Option<Rule> {
+        match name {
+            "forbidden" => Some(Rule::Forbidden),
+            "must-match-base" => Some(Rule::MustMatchBase),
+            "numeric-only" => Some(Rule::NumericOnly),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Forbidden => "forbidden",
+            Rule::MustMatchBase => "must-match-base",
+            Rule::NumericOnly => "numeric-only",
+        }
+    }
+}
+
+// The rules of each reserved name, from `[reserved_placeholders]`.
+pub type Reserved = BTreeMap<String, Vec<Rule>>;
+
+fn violation(name: &str, rule: Rule) -> Finding {
+    Finding::new(Check::ReservedPlaceholder)
+        .detail("Placeholder", format!("{{{}}}", name))
+        .detail("Rule", rule.name())
+}
+
+// The reserved names a value of any language, the base included, uses
+// against their rules.
+pub fn check_reserved_placeholders(
+    base: &Base,
+    project: &Project,
+    reserved: &Reserved,
+) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            let variables = extract_variables(&entry.value);
+            let base_variables = base.placeholders.get(&**key);
+            for (name, rules) in reserved {
+                let used = variables.contains(name);
+                for rule in rules {
+                    let message = match rule {
+                        Rule::Forbidden if used => "reserved by the formatter".to_string(),
+                        Rule::MustMatchBase if Some(lang) != base.lang => {
+                            let Some(base_variables) = base_variables else {
+                                continue;
+                            };
+                            match (used, base_variables.contains(name)) {
+                                (true, false) => format!("not used by the base ({})", base.label()),
+                                (false, true) => {
+                                    format!("used by the base ({}) but missing", base.label())
+                                }
+                                _ => continue,
+                            }
+                        }
+                        _ => continue,
+                    };
+                    findings.push(
+                        violation(name, *rule)
+                            .lang(lang)
+                            .key(key)
+                            .message(message)
+                            .file(data.file(key).unwrap_or_default()),
+                    );
+                }
+            }
+        }
+        findings
+    })
+}
+
+// Call sites supplying a string literal to a `numeric-only` name.
+pub fn check_reserved_arguments(sites: &[CallSite], reserved: &Reserved) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for site in sites {
+        for name in &site.literals {
+            if reserved
+                .get(name)
+                .is_some_and(|rules| rules.contains(&Rule::NumericOnly))
+            {
+                findings.push(
+                    violation(name, Rule::NumericOnly)
+                        .key(&site.key)
+                        .message("supplied a string literal")
+                        .file(site.file.display().to_string())
+                        .line(site.line),
+                );
+            }
+        }
+    }
+    findings
+}
@@ -0,0 +1,57 @@
+use crate::model::Project;
+use crate::report::{Check, Finding, Severity};
+use crate::review::{ReviewStatus, review_file};
+
+// Reports keys whose value was never approved or changed since it was.
+// Languages listed in `required` gate the build; the others are only checked
+// when they have a review status file, and their findings are warnings.
+pub fn check_reviews(project: &Project, required: &[String]) -> Result<Vec<Finding>, String> {
+    let mut statuses = Vec::new();
+    for lang in project.languages() {
+        let path = review_file(project, lang);
+        let status = ReviewStatus::load(&path)?;
+        let gating = required.iter().any(|required| required == lang);
+        if status.is_some() || gating {
+            statuses.push((lang, status.unwrap_or_default(), gating));
+        }
+    }
+    for lang in required {
+        if !project.languages().any(|known| known == lang) {
+            return Err(format!(
+                "`--require-reviewed` lists `{}`, which has no language folder",
+                lang
+            ));
+        }
+    }
+
+    Ok(project.flat_map_languages(|lang, data| {
+        let Some((_, status, gating)) = statuses.iter().find(|(checked, _, _)| *checked == lang)
+        else {
+            return Vec::new();
+        };
+        let severity = if *gating {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            let finding = match status.reviews.get(&**key) {
+                Some(review) if review.approves(&entry.value) => continue,
+                Some(review) => {
+                    Finding::new(Check::ModifiedSinceReview).detail("Reviewer", &review.reviewer)
+                }
+                None => Finding::new(Check::Unreviewed),
+            };
+            findings.push(
+                finding
+                    .lang(lang)
+                    .key(key)
+                    .file(&*entry.file)
+                    .severity(severity),
+            );
+        }
+        findings
+    }))
+}
@@ -0,0 +1,68 @@
+use crate::base::Base;
+use crate::config::Route;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use crate::usage::{SourceRoot, relative_to_root};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+// Verifies, for an application lazy-loading its translation files per
+// route, that each key a source file uses is defined in a file its route
+// loads. A source shared by several routes may only use the files all of
+// them load. Sources outside every route aren't checked.
+pub fn check_routes(
+    base: &Base,
+    project: &Project,
+    references: &[(PathBuf, Vec<(String, usize)>)],
+    sources: &[SourceRoot],
+    routes: &[Route],
+) -> Vec<Finding> {
+    let Some(lang) = base.lang else {
+        return Vec::new();
+    };
+    let folder = project.language_dir(lang);
+
+    let mut findings = Vec::new();
+    for (file, usages) in references {
+        let relative = relative_to_root(file, sources);
+        let mut matching = routes
+            .iter()
+            .filter(|route| route.sources.matches_path(relative));
+        let Some(first) = matching.next() else {
+            continue;
+        };
+        let loaded = matching.fold(first.files.clone(), |loaded, route| {
+            loaded.intersection(&route.files).cloned().collect()
+        });
+
+        for (key, line) in usages {
+            let defined_in = base.file(key);
+            let path = Path::new(&defined_in);
+            let name = path
+                .strip_prefix(&folder)
+                .ok()
+                .or_else(|| path.file_name().map(Path::new))
+                .map(|name| name.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            if loaded.contains(&name) {
+                continue;
+            }
+            findings.push(
+                Finding::new(Check::UnloadedKey)
+                    .key(key)
+                    .detail("Defined in", defined_in.clone())
+                    .detail("Loaded", loaded_files(&loaded))
+                    .file(file.display().to_string())
+                    .line(*line),
+            );
+        }
+    }
+    findings
+}
+
+fn loaded_files(loaded: &BTreeSet<String>) -> String {
+    if loaded.is_empty() {
+        return "no file".to_string();
+    }
+    loaded.iter().cloned().collect::<Vec<_>>().join(", ")
+}
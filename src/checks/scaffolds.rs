@@ -0,0 +1,41 @@
+use crate::base::Base;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use crate::review::value_hash;
+use crate::scaffold::{Scaffolds, scaffold_file};
+
+// Reports the keys still holding the value `--fix sync-keys` gave them
+// while the base value they were scaffolded from changed since. Keys
+// translated since are left alone, whatever their provenance says.
+pub fn check_stale_scaffolds(base: &Base, project: &Project) -> Result<Vec<Finding>, String> {
+    let mut scaffolds = Vec::new();
+    for lang in project.languages() {
+        if let Some(loaded) = Scaffolds::load(&scaffold_file(project, lang))? {
+            scaffolds.push((lang, loaded));
+        }
+    }
+
+    Ok(project.flat_map_languages(|lang, data| {
+        let Some((_, scaffolds)) = scaffolds.iter().find(|(scaffolded, _)| *scaffolded == lang)
+        else {
+            return Vec::new();
+        };
+        let mut findings = Vec::new();
+        for (key, record) in &scaffolds.records {
+            let (Some(entry), Some(base_value)) = (data.values.get(key.as_str()), base.value(key))
+            else {
+                continue;
+            };
+            if record.scaffolds(&entry.value) && record.base_hash != value_hash(base_value) {
+                findings.push(
+                    Finding::new(Check::StaleScaffold)
+                        .lang(lang)
+                        .key(key)
+                        .file(&*entry.file)
+                        .detail("Scaffolded", &record.generated_at),
+                );
+            }
+        }
+        findings
+    }))
+}
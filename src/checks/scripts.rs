@@ -0,0 +1,226 @@
+use crate::base::Base;
+use crate::config::ScriptsConfig;
+use crate::model::{Project, ValueKind};
+use crate::report::{Check, Finding};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+lazy_static! {
+    // Placeholders and markup, whose letters never belong to the language.
+    static ref NON_TEXT_REGEX: Regex = Regex::new(r"\{[^}]*}|<[^>]*>").unwrap();
+}
+
+// Values with fewer letters than this say too little about their script.
+const MIN_LETTERS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Bengali,
+    Thai,
+    Georgian,
+    Hangul,
+    Hiragana,
+    Katakana,
+    Han,
+}
+
+// Code point ranges of each script, enough to tell apart the scripts
+// translations are delivered in. Letters outside them are not counted.
+const SCRIPT_RANGES: &[(u32, u32, Script)] = &[
+    (0x0041, 0x005A, Script::Latin),
+    (0x0061, 0x007A, Script::Latin),
+    (0x00AA, 0x00AA, Script::Latin),
+    (0x00BA, 0x00BA, Script::Latin),
+    (0x00C0, 0x02AF, Script::Latin),
+    (0x0370, 0x03FF, Script::Greek),
+    (0x0400, 0x052F, Script::Cyrillic),
+    (0x0530, 0x058F, Script::Armenian),
+    (0x0590, 0x05FF, Script::Hebrew),
+    (0x0600, 0x06FF, Script::Arabic),
+    (0x0750, 0x077F, Script::Arabic),
+    (0x08A0, 0x08FF, Script::Arabic),
+    (0x0900, 0x097F, Script::Devanagari),
+    (0x0980, 0x09FF, Script::Bengali),
+    (0x0E00, 0x0E7F, Script::Thai),
+    (0x10A0, 0x10FF, Script::Georgian),
+    (0x1100, 0x11FF, Script::Hangul),
+    (0x1C80, 0x1C8F, Script::Cyrillic),
+    (0x1E00, 0x1EFF, Script::Latin),
+    (0x1F00, 0x1FFF, Script::Greek),
+    (0x2C60, 0x2C7F, Script::Latin),
+    (0x2D00, 0x2D2F, Script::Georgian),
+    (0x2DE0, 0x2DFF, Script::Cyrillic),
+    (0x2E80, 0x2FDF, Script::Han),
+    (0x3005, 0x3007, Script::Han),
+    (0x3040, 0x309F, Script::Hiragana),
+    (0x30A0, 0x30FF, Script::Katakana),
+    (0x3130, 0x318F, Script::Hangul),
+    (0x31F0, 0x31FF, Script::Katakana),
+    (0x3400, 0x4DBF, Script::Han),
+    (0x4E00, 0x9FFF, Script::Han),
+    (0xA640, 0xA69F, Script::Cyrillic),
+    (0xA720, 0xA7FF, Script::Latin),
+    (0xAC00, 0xD7AF, Script::Hangul),
+    (0xF900, 0xFAFF, Script::Han),
+    (0xFB1D, 0xFB4F, Script::Hebrew),
+    (0xFB50, 0xFDFF, Script::Arabic),
+    (0xFE70, 0xFEFF, Script::Arabic),
+    (0xFF21, 0xFF3A, Script::Latin),
+    (0xFF41, 0xFF5A, Script::Latin),
+    (0xFF66, 0xFF9F, Script::Katakana),
+    (0x20000, 0x2FFFF, Script::Han),
+];
+
+impl Script {
+    pub const ALL: [Script; 14] = [
+        Script::Latin,
+        Script::Greek,
+        Script::Cyrillic,
+        Script::Armenian,
+        Script::Hebrew,
+        Script::Arabic,
+        Script::Devanagari,
+        Script::Bengali,
+        Script::Thai,
+        Script::Georgian,
+        Script::Hangul,
+        Script::Hiragana,
+        Script::Katakana,
+        Script::Han,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Greek => "Greek",
+            Script::Cyrillic => "Cyrillic",
+            Script::Armenian => "Armenian",
+            Script::Hebrew => "Hebrew",
+            Script::Arabic => "Arabic",
+            Script::Devanagari => "Devanagari",
+            Script::Bengali => "Bengali",
+            Script::Thai => "Thai",
+            Script::Georgian => "Georgian",
+            Script::Hangul => "Hangul",
+            Script::Hiragana => "Hiragana",
+            Script::Katakana => "Katakana",
+            Script::Han => "Han",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Script> {
+        Script::ALL
+            .into_iter()
+            .find(|script| script.name().eq_ignore_ascii_case(name))
+    }
+
+    fn of(c: char) -> Option<Script> {
+        let c = c as u32;
+        let index = SCRIPT_RANGES.partition_point(|(_, end, _)| *end < c);
+        SCRIPT_RANGES
+            .get(index)
+            .filter(|(start, _, _)| *start <= c)
+            .map(|(_, _, script)| *script)
+    }
+}
+
+// The scripts a language is written in, by primary language subtag.
+fn default_scripts(lang: &str) -> &'static [Script] {
+    let primary = lang
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match primary.as_str() {
+        "en" | "fr" | "de" | "es" | "it" | "nl" | "pt" | "pl" | "cs" | "sk" | "sl" | "hr"
+        | "ro" | "hu" | "sv" | "da" | "no" | "nb" | "nn" | "fi" | "et" | "lv" | "lt" | "tr"
+        | "id" | "ms" | "vi" | "ca" | "eu" | "gl" | "ga" | "is" | "sq" | "af" | "sw" | "tl" => {
+            &[Script::Latin]
+        }
+        "ru" | "uk" | "be" | "bg" | "mk" | "kk" | "ky" | "mn" => &[Script::Cyrillic],
+        "sr" => &[Script::Cyrillic, Script::Latin],
+        "el" => &[Script::Greek],
+        "hy" => &[Script::Armenian],
+        "he" | "yi" => &[Script::Hebrew],
+        "ar" | "fa" | "ur" => &[Script::Arabic],
+        "hi" | "mr" | "ne" => &[Script::Devanagari],
+        "bn" => &[Script::Bengali],
+        "th" => &[Script::Thai],
+        "ka" => &[Script::Georgian],
+        "ko" => &[Script::Hangul, Script::Han],
+        "ja" => &[Script::Hiragana, Script::Katakana, Script::Han],
+        "zh" => &[Script::Han],
+        _ => &[],
+    }
+}
+
+// Flags values whose letters are mostly from a script the language is not
+// written in, such as Russian text delivered for French. Brand names and
+// other short foreign words stay under the threshold, and values that must not
+// be translated are skipped.
+pub fn check_scripts(base: &Base, project: &Project, config: &ScriptsConfig) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let expected = config
+            .expected
+            .get(lang)
+            .map(Vec::as_slice)
+            .unwrap_or_else(|| default_scripts(lang));
+        if expected.is_empty() {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            if entry.kind != ValueKind::String {
+                continue;
+            }
+            if base.do_not_translate(key) {
+                continue;
+            }
+            let text = NON_TEXT_REGEX.replace_all(&entry.value, "");
+            let mut counts: BTreeMap<Script, usize> = BTreeMap::new();
+            for script in text
+                .chars()
+                .filter(|c| c.is_alphabetic())
+                .filter_map(Script::of)
+            {
+                *counts.entry(script).or_default() += 1;
+            }
+
+            let letters: usize = counts.values().sum();
+            let unexpected: usize = counts
+                .iter()
+                .filter(|(script, _)| !expected.contains(script))
+                .map(|(_, count)| count)
+                .sum();
+            if letters < MIN_LETTERS || (unexpected as f64) <= config.threshold * letters as f64 {
+                continue;
+            }
+
+            let (script, count) = counts
+                .iter()
+                .filter(|(script, _)| !expected.contains(script))
+                .max_by_key(|(_, count)| **count)
+                .unwrap();
+            findings.push(
+                Finding::new(Check::UnexpectedScript)
+                    .lang(lang)
+                    .key(key)
+                    .detail(
+                        "Script",
+                        format!("{} ({}%)", script.name(), count * 100 / letters),
+                    )
+                    .file(&*entry.file),
+            );
+        }
+        findings
+    })
+}
@@ -0,0 +1,150 @@
+use super::get_translation_file;
+use crate::base::Base;
+use crate::model::LanguageData;
+use crate::report::{Check, Finding};
+use std::collections::HashSet;
+
+// What the keys of a language say about a path, when it holds no value.
+enum Cover {
+    // The whole subtree under a path whose type differs.
+    Subtree(String),
+    // The elements of an array from an index on, beyond the shorter side.
+    Elements(String, usize),
+}
+
+impl Cover {
+    fn covers(&self, key: &str) -> bool {
+        match self {
+            Cover::Subtree(path) => key == path || under(key, path),
+            Cover::Elements(path, from) => element(key, path).is_some_and(|index| index >= *from),
+        }
+    }
+}
+
+fn under(key: &str, path: &str) -> bool {
+    key.strip_prefix(path)
+        .is_some_and(|rest| rest.starts_with('.'))
+}
+
+// The index of the element of the array at `path` holding `key`.
+fn element(key: &str, path: &str) -> Option<usize> {
+    let rest = key.strip_prefix(path)?.strip_prefix('.')?;
+    rest.split('.').next()?.parse().ok()
+}
+
+// `a` and `a.b` for `a.b.c`.
+fn parents(key: &str) -> impl Iterator<Item = &str> {
+    key.match_indices('.').map(move |(end, _)| &key[..end])
+}
+
+// Compares the structure around the keys missing from a language or extra
+// in it. A path holding a value on one side and an array or object on the
+// other is one type mismatch rather than missing and extra keys, and an
+// array of another length is reported once rather than per element. The
+// missing and extra keys these findings stand for are removed, and an array
+// only one side has is one missing or extra key.
+pub fn check_shapes<'a>(
+    base: &'a Base,
+    lang: &str,
+    data: &'a LanguageData,
+    missing: &mut Vec<&'a str>,
+    extra: &mut Vec<&'a str>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut covers = Vec::new();
+    let base_arrays: HashSet<&str> = base.arrays().map(|(path, _)| path).collect();
+    let container = |is_array: bool| if is_array { "array" } else { "object" };
+    let mismatch = |key: &str, expected: &str, found: &str, file: String| {
+        Finding::new(Check::TypeMismatch)
+            .lang(lang)
+            .key(key)
+            .detail(format!("Expected ({})", base.label()), expected)
+            .detail(format!("Found ({})", lang.to_uppercase()), found)
+            .file(file)
+    };
+
+    // A value where the other side has an array or an object.
+    let missing_parents: HashSet<&str> = missing.iter().flat_map(|key| parents(key)).collect();
+    let extra_parents: HashSet<&str> = extra.iter().flat_map(|key| parents(key)).collect();
+    for key in missing.iter() {
+        let is_array = data.arrays.contains_key(*key);
+        if is_array || extra_parents.contains(key) {
+            let expected = base.kind(key).map_or("string", |kind| kind.name());
+            let file = extra.iter().find(|other| under(other, key)).map_or_else(
+                || get_translation_file(data, key),
+                |other| get_translation_file(data, other),
+            );
+            findings.push(mismatch(key, expected, container(is_array), file));
+            covers.push(Cover::Subtree(key.to_string()));
+        }
+    }
+    for key in extra.iter() {
+        let is_array = base_arrays.contains(key);
+        if is_array || missing_parents.contains(key) {
+            let found = data.values[*key].kind.name();
+            findings.push(mismatch(
+                key,
+                container(is_array),
+                found,
+                get_translation_file(data, key),
+            ));
+            covers.push(Cover::Subtree(key.to_string()));
+        }
+    }
+
+    // Arrays only one side has, or holding an object on the other side.
+    for (path, length) in base.arrays() {
+        match data.arrays.get(path) {
+            Some((found, file)) if *found != length => {
+                findings.push(
+                    Finding::new(Check::ArrayLengthMismatch)
+                        .lang(lang)
+                        .key(path)
+                        .detail(format!("Length ({})", base.label()), length.to_string())
+                        .detail(
+                            format!("Length ({})", lang.to_uppercase()),
+                            found.to_string(),
+                        )
+                        .file(&**file),
+                );
+                covers.push(Cover::Elements(path.to_string(), length.min(*found)));
+            }
+            Some(_) => {}
+            None if data.contains(path) => {}
+            None if data.keys().any(|key| under(key, path)) => {
+                findings.push(mismatch(
+                    path,
+                    "array",
+                    "object",
+                    get_translation_file(data, path),
+                ));
+                covers.push(Cover::Subtree(path.to_string()));
+            }
+            None => {
+                missing.push(path);
+                covers.push(Cover::Elements(path.to_string(), 0));
+            }
+        }
+    }
+    for path in data.arrays.keys() {
+        if base_arrays.contains(&**path) || base.placeholders.contains_key(&**path) {
+            continue;
+        }
+        if base.placeholders.keys().any(|key| under(key, path)) {
+            findings.push(mismatch(
+                path,
+                "object",
+                "array",
+                get_translation_file(data, path),
+            ));
+            covers.push(Cover::Subtree(path.to_string()));
+        } else {
+            extra.push(path);
+            covers.push(Cover::Elements(path.to_string(), 0));
+        }
+    }
+
+    missing.retain(|key| !covers.iter().any(|cover| cover.covers(key)));
+    extra.retain(|key| !covers.iter().any(|cover| cover.covers(key)));
+    findings
+}
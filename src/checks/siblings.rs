@@ -0,0 +1,93 @@
+use crate::base::Base;
+use crate::config::SiblingDuplicatesConfig;
+use crate::model::{Project, ValueKind};
+use crate::report::{Check, Finding};
+use std::collections::BTreeMap;
+
+// Sibling keys, differing in a single segment like `checkout.shipping.title`
+// and `checkout.billing.title`, that have the same translation although
+// their base values differ. Repeating a value the base repeats too is
+// legitimate; a translation that doesn't follow the base is usually a value
+// copy-pasted and never edited.
+pub fn check_sibling_duplicates(
+    base: &Base,
+    project: &Project,
+    config: &SiblingDuplicatesConfig,
+) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        // Keys per translated value and key with one segment left out, so
+        // that each pair of siblings shares exactly one group.
+        let mut siblings: BTreeMap<(&str, usize, Vec<&str>), Vec<&str>> = BTreeMap::new();
+        for (key, entry) in &data.values {
+            if entry.kind != ValueKind::String {
+                continue;
+            }
+            if entry.value.trim().chars().count() < config.min_length || base.do_not_translate(key)
+            {
+                continue;
+            }
+            let segments: Vec<&str> = key.split('.').collect();
+            if segments.len() < 2 {
+                continue;
+            }
+            for position in 0..segments.len() {
+                let mut others = segments.clone();
+                others.remove(position);
+                siblings
+                    .entry((entry.value.as_str(), position, others))
+                    .or_default()
+                    .push(key);
+            }
+        }
+
+        let mut findings = Vec::new();
+        for ((value, _, _), mut keys) in siblings {
+            keys.sort();
+            for (i, first) in keys.iter().enumerate() {
+                for second in &keys[i + 1..] {
+                    let (Some(first_base), Some(second_base)) =
+                        (base.value(first), base.value(second))
+                    else {
+                        continue;
+                    };
+                    if first_base == second_base {
+                        continue;
+                    }
+
+                    let first_file = data.file(first).unwrap_or_default();
+                    let second_file = data.file(second).unwrap_or_default();
+                    let files = if first_file == second_file {
+                        first_file.to_string()
+                    } else {
+                        format!("{}, {}", first_file, second_file)
+                    };
+                    findings.push(
+                        Finding::new(Check::SiblingDuplicate)
+                            .lang(lang)
+                            .key(first)
+                            .detail("Sibling", *second)
+                            .detail(
+                                format!("Base ({}) of {}", base.label(), first),
+                                format!("{:?}", first_base),
+                            )
+                            .detail(
+                                format!("Base ({}) of {}", base.label(), second),
+                                format!("{:?}", second_base),
+                            )
+                            .detail(
+                                format!("Shared value ({})", lang.to_uppercase()),
+                                format!("{:?}", value),
+                            )
+                            .detail("Files", files)
+                            .file(first_file),
+                    );
+                }
+            }
+        }
+        findings
+    })
+}
@@ -0,0 +1,125 @@
+use crate::checks::placeholder_spans;
+use crate::config::StyleRule;
+use crate::model::{Project, ValueKind};
+use crate::report::{Check, Finding};
+
+// How the words of a value are capitalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capitalization {
+    // "Save changes": sentences start with a capital, other words don't.
+    Sentence,
+    // "Save All Changes": words start with a capital, except words of up
+    // to three letters past the start of a sentence, such as "and" or "of".
+    Title,
+    // "save changes": no capitals at all.
+    Lower,
+}
+
+impl Capitalization {
+    pub fn parse(name: &str) -> Option<Capitalization> {
+        match name {
+            "sentence" => Some(Capitalization::Sentence),
+            "title" => Some(Capitalization::Title),
+            "lower" => Some(Capitalization::Lower),
+            _ => None,
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Capitalization::Sentence => "sentence case",
+            Capitalization::Title => "title case",
+            Capitalization::Lower => "lower case",
+        }
+    }
+
+    // Whether `words` follow the capitalization. Each word comes with
+    // whether it starts a sentence; words without a cased letter, such as
+    // Japanese, pass either way.
+    fn follows(self, words: &[(&str, bool)], acronyms: &[String]) -> bool {
+        words
+            .iter()
+            .filter(|(word, _)| !acronyms.iter().any(|acronym| acronym == word))
+            .all(|(word, starts_sentence)| {
+                let first = word.chars().find(|c| c.is_alphabetic()).unwrap();
+                match self {
+                    Capitalization::Sentence if *starts_sentence => !first.is_lowercase(),
+                    Capitalization::Sentence => !first.is_uppercase(),
+                    Capitalization::Title => {
+                        !first.is_lowercase() || (!starts_sentence && word.chars().count() <= 3)
+                    }
+                    Capitalization::Lower => !word.chars().any(char::is_uppercase),
+                }
+            })
+    }
+}
+
+// The words of `value` with whether each starts a sentence, placeholders
+// left out and trimmed of the punctuation around them, so that
+// `"{count} items"` and `"« Save »"` are judged by their words.
+fn words(value: &str) -> Vec<(&str, bool)> {
+    let mut text = value.to_string();
+    for (range, _, _) in placeholder_spans(value).iter().rev() {
+        text.replace_range(range.clone(), &" ".repeat(range.len()));
+    }
+
+    let mut words = Vec::new();
+    let mut starts_sentence = true;
+    let mut offset = 0;
+    for raw in text.split_whitespace() {
+        let start = offset + text[offset..].find(raw).unwrap();
+        offset = start + raw.len();
+        let word = value[start..offset].trim_matches(|c: char| !c.is_alphanumeric());
+        if word.chars().any(char::is_alphabetic) {
+            words.push((word, starts_sentence));
+            starts_sentence = false;
+        }
+        if raw.ends_with(['.', '!', '?']) {
+            starts_sentence = true;
+        }
+    }
+    words
+}
+
+// Values of the keys each `[style]` namespace covers that stray from its
+// capitalization or don't match the pattern set for their language.
+pub fn check_styles(project: &Project, styles: &[StyleRule]) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            if entry.kind != ValueKind::String {
+                continue;
+            }
+            for style in styles.iter().filter(|style| style.keys.matches(key)) {
+                let mut expected = Vec::new();
+                if let Some(capitalization) = style.capitalization
+                    && (style.langs.is_empty() || style.langs.iter().any(|styled| styled == lang))
+                {
+                    let words = words(&entry.value);
+                    if !words.is_empty() && !capitalization.follows(&words, &style.acronyms) {
+                        expected.push(capitalization.describe().to_string());
+                    }
+                }
+                if let Some(pattern) = style.patterns.get(lang)
+                    && !pattern.is_match(&entry.value)
+                {
+                    expected.push(format!("matching `{}`", pattern.as_str()));
+                }
+                if expected.is_empty() {
+                    continue;
+                }
+                findings.push(
+                    Finding::new(Check::StyleViolation)
+                        .lang(lang)
+                        .key(key)
+                        .detail("Namespace", style.keys.as_str())
+                        .detail("Expected", expected.join(", "))
+                        .detail("Value", format!("{:?}", entry.value))
+                        .file(&*entry.file)
+                        .severity(style.severity),
+                );
+            }
+        }
+        findings
+    })
+}
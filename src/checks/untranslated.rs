@@ -0,0 +1,59 @@
+use crate::base::Base;
+use crate::checks::variable_spans;
+use crate::config::UntranslatedConfig;
+use crate::model::{Project, ValueKind};
+use crate::report::{Check, Finding, Severity};
+
+// Whether `value` has a letter outside its placeholders, so that numbers and
+// values such as `{count}` can stay the same in every language.
+fn has_letters(value: &str) -> bool {
+    let mut text = value.to_string();
+    for (range, _) in variable_spans(value).iter().rev() {
+        text.replace_range(range.clone(), "");
+    }
+    text.chars().any(char::is_alphabetic)
+}
+
+// With `--check-untranslated`: translations left empty or blank, and
+// translations still holding the base value, like a French value pasted into
+// the English file as a stopgap. Values shorter than `min_length`
+// characters, such as "OK", do-not-translate values and those the config
+// allows are expected to match the base. Empty base values are already
+// suspicious base values.
+pub fn check_untranslated(
+    base: &Base,
+    project: &Project,
+    config: &UntranslatedConfig,
+    severity: Severity,
+) -> Vec<Finding> {
+    project.flat_map_languages(|lang, data| {
+        if Some(lang) == base.lang {
+            return Vec::new();
+        }
+
+        let mut findings = Vec::new();
+        for (key, entry) in &data.values {
+            if entry.kind != ValueKind::String {
+                continue;
+            }
+            let check = if entry.value.trim().is_empty() {
+                Check::EmptyValue
+            } else if base.value(key) == Some(entry.value.as_str())
+                && !base.do_not_translate(key)
+                && entry.value.chars().count() >= config.min_length
+                && has_letters(&entry.value)
+                && !config.allows(key, &entry.value)
+            {
+                Check::UntranslatedValue
+            } else {
+                continue;
+            };
+            let mut finding = Finding::new(check).lang(lang).key(key);
+            if check == Check::UntranslatedValue {
+                finding = finding.detail("Value", format!("{:?}", entry.value));
+            }
+            findings.push(finding.file(&*entry.file).severity(severity));
+        }
+        findings
+    })
+}
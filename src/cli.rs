@@ -0,0 +1,2617 @@
+use crate::checks::{self, PlaceholderStyle};
+use crate::config::{CONFIG_FILE, ProjectConfig};
+use crate::daemon;
+use crate::detect::Candidate;
+use crate::model::{DEFAULT_BASE_LANG, parse_lang};
+use crate::normalize;
+use crate::partition::Partition;
+use crate::redact::Redaction;
+use crate::remote::RemoteSchema;
+use crate::render::human::{GroupBy, HumanOptions};
+use crate::render::junit::JunitSuites;
+use crate::rollup;
+use crate::search::Field;
+use crate::selector::KeyPattern;
+use crate::sheet::SheetFormat;
+use crate::state::STATE_FILE;
+use crate::usage::{DEFAULT_EXTENSIONS, SourceRoot};
+use crate::worklist::{Section, parse_section};
+use regex::Regex;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// The translation folders looked for under the project root, in order, when
+// none is given or configured.
+pub const CONVENTIONAL_I18N_DIRS: [&str; 6] = [
+    "src/assets/i18n",
+    "src/i18n",
+    "src/locales",
+    "i18n",
+    "locales",
+    "public/locales",
+];
+const DEFAULT_SRC_DIR: &str = "src";
+const DEFAULT_SCHEMA_CACHE_DIR: &str = ".translation-check-cache";
+const DEFAULT_SCHEMA_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_MAX_VALUE_LENGTH: usize = 80;
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const DEFAULT_SAMPLE_COUNT: usize = 50;
+
+// What `--help` prints for the run, one line per option. The README
+// describes each of them at length.
+const RUN_USAGE: &str = "\
+Checks the translation files of a project against its base language.
+
+Usage: check_translations [check|unused|fix] [options] [i18n-dir]
+
+Runs:
+  check                           the default run
+  unused                          only the unused-key scan, as --only unused-keys
+  fix                             adds missing keys, as --fix sync-keys
+
+Files:
+  --cwd <path>                    project root, instead of detecting it
+  --i18n-dir <path>               translation folder, as the positional argument
+  --base-lang <lang>              language the others are compared with
+  --src-dir <path>                source folder scanned for key usages, repeatable
+  --ext, --extensions <list>      extensions of the source files scanned (ts,js,vue)
+  --config <path>                 project settings (translation-check.toml)
+  --loader-config <path>          cross-check the files of the i18n loader configuration
+  --changed-files <path>          only check the files listed, `-` for stdin
+  --since <git-ref>               only check the files changed since the ref
+  --changed-since <git-ref>       only report what changed since the ref
+  --buffers <path>                read unsaved file contents from a JSON object
+
+Checks:
+  --only unused-keys|consistency  run one group of checks
+  --check-untranslated            report empty values and values equal to the base
+  --strict                        report empty and untranslated values as errors
+  --key-case-insensitive          compare keys regardless of case
+  --placeholder-style <styles>    single-brace, double-brace, percent or all
+  --placeholder-pattern <regex>   also read the placeholders a regex captures
+  --schema-snapshot <path>        committed snapshot of the base placeholders
+  --update-schema-snapshot        write the current base placeholders to it
+  --schema-url <url>              compare with a published base schema
+  --schema-token-env <var>        bearer token for the schema download
+  --schema-timeout <seconds>      timeout of the schema download (10)
+  --schema-cache-dir <path>       schema and scan cache (.translation-check-cache)
+  --require-fresh-schema          fail instead of using the cached schema
+  --no-cache                      scan every source file again
+  --scan-openapi <glob>           read keys from OpenAPI specs, repeatable
+  --content-rules <path>          forbidden words and required phrases
+  --require-reviewed <langs>      fail on unreviewed translations of these languages
+  --min-coverage <percent>        fail when a language translates less
+  --fail-on warnings|unowned      fail on warnings, or on files without owners
+  --partition <index>/<count>     only report one shard of the run
+  --partition-by prefix           how keys are assigned to shards
+
+Fixes:
+  --fix <fixes>                   nfc, translated-placeholders, quote-escapes,
+                                  hygiene, eol or sync-keys, comma-separated
+  --allow-base-writes             let fixes rewrite the base language files
+  --dry-run                       print the changes instead of writing them
+  --backup-dir <path>             keep a copy of every file rewritten
+  --yes                           don't ask before deleting stray files
+  --interactive                   review the findings on a keyboard-driven screen
+  --read-only                     never write to the project
+
+Reports:
+  --format <format>               console report: human, json, junit, html, plain or sarif
+  --report <format>:<path>        also write the report there, repeatable
+  --output <path>                 write the human report to a file
+  --html <path>                   as --report html:<path>
+  --color auto|always|never       colored console output
+  -q, --quiet                     only print the summary line
+  --check-only                    stop at the first error, print the summary only
+  --coverage-only                 only print the coverage table
+  --max-findings <n>              stop listing findings after n
+  --max-value-length <n>          cut quoted values after n characters (80)
+  --group-by language|file|owner  how findings are grouped
+  --junit-suites language|check   one JUnit test suite per language or check
+  --codeowners <path>             list the owners of each finding
+  --rollup-threshold <n>          one row for a key in more than n languages (5)
+  --no-rollup                     keep every row
+  --redact-values                 hash translated values in the reports
+  --redact-keys <glob>            only hash the values of these keys, repeatable
+  --suggest-fixes                 add JSON Patches to the JSON report
+  --timings                       print the duration of each phase
+  --print-config                  print the effective settings and exit
+
+State:
+  --state <path>                  state file (translation-check.state.json)
+  --update-state                  rewrite the state file from this run
+  --no-hooks                      skip the configured hooks
+  --metrics-file <path>           write Prometheus metrics
+  --statsd <host:port>            push StatsD gauges
+  --waivers <path>                drop the findings a waivers file accepts
+  --emit-waivers <path>           write a waiver for every finding
+  --baseline <path>               drop the findings recorded in a baseline
+  --no-baseline                   ignore the baseline of the configuration
+  --write-baseline <path>         record the findings of this run
+
+Runtime:
+  --watch                         check again whenever a file changes
+  --socket <path>                 where a `serve` server listens
+  --no-daemon                     check in this process even when one does
+  --low-memory                    check languages one at a time
+  --max-open-files <n>            files open at once while reading
+  -h, --help                      print this help
+
+Commands:
+  worklist, export, import, review, sample, preview, search, stats, trend,
+  format, merge-reports, verify-bundle, plural-rules, schema-report,
+  lint-config, jsonrpc, serve
+
+Run `check_translations <command> --help` for the options of a command.
+";
+
+// The usage of each command, as `--help` prints it after the command.
+const COMMAND_USAGES: [(&str, &str); 17] = [
+    (
+        "worklist",
+        "\
+Writes what a language has left to translate, as Markdown or CSV.
+
+Usage: check_translations worklist --lang <lang> [options] [i18n-dir]
+
+  --lang <lang>                   language of the worklist
+  --output <path>                 write it to a file instead of stdout
+  --format markdown|csv           format of the worklist (markdown)
+  --sections <names>              sections to include, comma-separated
+  --schema-snapshot <path>        list keys whose base placeholders changed
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "export",
+        "\
+Exports the missing and empty translations of a language as a sheet for
+translators.
+
+Usage: check_translations export --lang <lang> [options] [i18n-dir]
+
+  --lang <lang>                   language to export
+  --output <path>                 write the sheet to a file instead of stdout
+  --format csv|xlsx               format of the sheet (from --output, or csv)
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "import",
+        "\
+Merges a filled sheet back into the translation files of its language.
+
+Usage: check_translations import [options] <sheet> [i18n-dir]
+
+  --dry-run                       print the changes instead of writing them
+  --backup-dir <path>             keep a copy of every file rewritten
+  --read-only                     refuse to write, unless with --dry-run
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "review",
+        "\
+Records translations as reviewed.
+
+Usage: check_translations review approve --lang <lang> --keys-from <file> [options] [i18n-dir]
+
+  --lang <lang>                   language of the reviewed translations
+  --keys-from <file>              the reviewed keys, one per line
+  --reviewer <name>               who reviewed them
+",
+    ),
+    (
+        "sample",
+        "\
+Draws keys of a language for a manual review.
+
+Usage: check_translations sample --lang <lang> [options] [i18n-dir]
+
+  --lang <lang>                   language to sample
+  --count <n>                     number of translations (50)
+  --seed <seed>                   seed of the pick, for a repeatable sample
+  --exclude-reviewed              leave out translations already reviewed
+  --format markdown|csv           format of the sample (markdown)
+  --output <path>                 write it to a file instead of stdout
+  --keys-output <path>            also write the keys drawn, for `review approve`
+  --state <path>                  state file (translation-check.state.json)
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "preview",
+        "\
+Renders the value of a key in every language with sample variables.
+
+Usage: check_translations preview [options] <key> [i18n-dir]
+
+  --lang <langs>                  languages to render, comma-separated
+  --all-langs                     render every language
+  --vars <name=value,...>         values of the placeholders
+  --format text|json              output format (text)
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "search",
+        "\
+Searches keys and values.
+
+Usage: check_translations search [options] <query> [i18n-dir]
+
+  --in keys|values                where to search, comma-separated (both)
+  --lang <lang>                   only search one language
+  --fuzzy                         also match text a few edits away
+  --limit <n>                     number of results (20)
+  --format text|json              output format (text)
+  --color auto|always|never       colored output
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "stats",
+        "\
+Counts the base keys each language translates, misses or leaves empty.
+
+Usage: check_translations stats [options] [i18n-dir]
+
+  --format text|json|markdown     output format (text)
+  --per-file                      one row per translation file
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "trend",
+        "\
+Prints the coverage history recorded in the state file.
+
+Usage: check_translations trend [options]
+
+  --state <path>                  state file (translation-check.state.json)
+  --format text|json              output format (text)
+",
+    ),
+    (
+        "format",
+        "\
+Rewrites the JSON translation files with sorted keys and one indentation.
+
+Usage: check_translations format [options] [i18n-dir]
+
+  --check                         only list the files that would change
+  --indent <width>|tab            indentation, 1 to 8 spaces or a tab (2)
+  --read-only                     refuse to write, unless with --check
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "merge-reports",
+        "\
+Merges the JSON reports of partitioned runs into one.
+
+Usage: check_translations merge-reports [options] <report>...
+
+  --output <path>                 write the merged report to a file
+  --no-rollup                     keep every row
+",
+    ),
+    (
+        "verify-bundle",
+        "\
+Compares the translations of the build output with the translation folder.
+
+Usage: check_translations verify-bundle --dist <dir> [options] [i18n-dir]
+
+  --dist <dir>                    folder of the built bundle
+  --format <format>               console report format
+  --report <format>:<path>        also write the report there, repeatable
+  --color auto|always|never       colored output
+  --i18n-dir <path>               translation folder
+  --base-lang <lang>              base language
+  --config <path>                 project settings
+",
+    ),
+    (
+        "plural-rules",
+        "\
+Prints the CLDR plural categories of a language.
+
+Usage: check_translations plural-rules <lang>
+",
+    ),
+    (
+        "schema-report",
+        "\
+Prints the JSON schema of the JSON report.
+
+Usage: check_translations schema-report [options]
+
+  --output <path>                 write it to a file instead of stdout
+",
+    ),
+    (
+        "lint-config",
+        "\
+Checks the configuration files for mistakes.
+
+Usage: check_translations lint-config [options]
+
+  --config <path>                 project settings (translation-check.toml)
+  --content-rules <path>          content rules file
+",
+    ),
+    (
+        "jsonrpc",
+        "\
+Answers JSON-RPC requests of an editor on the standard streams.
+
+Usage: check_translations jsonrpc --stdio
+",
+    ),
+    (
+        "serve",
+        "\
+Keeps the analysis in one process, answering runs over a unix socket.
+
+Usage: check_translations serve [options]
+
+  --socket <path>                 where to listen (.translation-check.sock)
+",
+    ),
+];
+
+// The usage `--help` prints after `command`, the run's when it names none.
+pub fn usage(command: Option<&str>) -> Option<&'static str> {
+    match command {
+        None | Some("check" | "unused" | "fix") => Some(RUN_USAGE),
+        Some(command) => COMMAND_USAGES
+            .iter()
+            .find(|(name, _)| *name == command)
+            .map(|(_, usage)| *usage),
+    }
+}
+
+// The command a help request is about: `help <command>`, or the first
+// argument of a run asking for `-h` or `--help`. `None` when no help is
+// asked for.
+pub fn help_request(args: &[String]) -> Option<Option<&str>> {
+    match args.first().map(String::as_str) {
+        Some("help") => Some(args.get(1).map(String::as_str)),
+        first if args.iter().any(|arg| arg == "-h" || arg == "--help") => {
+            Some(first.filter(|first| !first.starts_with('-') && usage(Some(first)).is_some()))
+        }
+        _ => None,
+    }
+}
+
+// Setting this to `1`, `true` or `yes` has the same effect as `--read-only`,
+// for CI runners that enforce it for every job.
+pub const READ_ONLY_ENV: &str = "TRANSLATION_CHECK_READ_ONLY";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Only {
+    UnusedKeys,
+    Consistency,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Human,
+    Json,
+    Junit,
+    Html,
+    Plain,
+    Sarif,
+}
+
+// Where one rendering of the report goes; `None` is stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sink {
+    pub format: ReportFormat,
+    pub path: Option<PathBuf>,
+}
+
+// What `--fix` rewrites in the translation files before checking them, in
+// the order fixes are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fix {
+    // Deletes junk files instead of rewriting translation files.
+    Hygiene,
+    Nfc,
+    TranslatedPlaceholders,
+    QuoteEscapes,
+    Eol,
+    // Applied to the report rather than before the checks.
+    SyncKeys,
+}
+
+impl Fix {
+    pub fn name(self) -> &'static str {
+        match self {
+            Fix::Hygiene => "hygiene",
+            Fix::Nfc => "nfc",
+            Fix::TranslatedPlaceholders => "translated-placeholders",
+            Fix::QuoteEscapes => "quote-escapes",
+            Fix::Eol => "eol",
+            Fix::SyncKeys => "sync-keys",
+        }
+    }
+
+    // Whether the fix is meant to rewrite the base language too, which it
+    // still only does with `--allow-base-writes`: normalizing its text or
+    // line endings, or removing its unused keys. Placeholders are named by
+    // the base.
+    pub fn writes_base(self) -> bool {
+        matches!(
+            self,
+            Fix::Nfc | Fix::QuoteEscapes | Fix::Eol | Fix::SyncKeys
+        )
+    }
+}
+
+// Conditions `--fail-on` turns into errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailOn {
+    Unowned,
+    // Runs with warnings but no error exit with `WARNINGS_EXIT_CODE`.
+    Warnings,
+}
+
+// The exit code of a run with `--fail-on warnings` that found warnings but
+// no error; 1 is for errors and 2 for invalid usage.
+pub const WARNINGS_EXIT_CODE: i32 = 3;
+
+// How `--partition` assigns base keys to shards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionBy {
+    Prefix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+// Where the base language of a run comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseLangOrigin {
+    Default,
+    Flag,
+    Config,
+    Detected,
+}
+
+impl BaseLangOrigin {
+    pub fn name(self) -> &'static str {
+        match self {
+            BaseLangOrigin::Default => "default",
+            BaseLangOrigin::Flag => "flag",
+            BaseLangOrigin::Config => "config",
+            BaseLangOrigin::Detected => "detected",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub i18n_dir: PathBuf,
+    pub base_lang: String,
+    pub base_lang_origin: BaseLangOrigin,
+    // With the default base language, the language scoring clearly higher
+    // as a base, and the default's own score.
+    pub base_lang_rival: Option<(Candidate, Candidate)>,
+    // The folders scanned for usages; `--src-dir` may be given once for
+    // each.
+    pub src_dirs: Vec<PathBuf>,
+    // The extensions of the source files scanned for usages, without the dot.
+    pub extensions: Vec<String>,
+    // The extensions of the source folders of `[[project.sources]]` that
+    // set their own.
+    pub src_dir_extensions: BTreeMap<PathBuf, Vec<String>>,
+    pub only: Option<Only>,
+    pub timings: bool,
+    pub low_memory: bool,
+    // Defaults to the process limit on open files, less some headroom.
+    pub max_open_files: Option<usize>,
+    // The placeholder syntaxes read, first the presets, then the patterns
+    // capturing the name of a placeholder of another syntax.
+    pub placeholder_styles: Vec<PlaceholderStyle>,
+    pub placeholder_patterns: Vec<Regex>,
+    pub schema_snapshot: Option<PathBuf>,
+    pub update_schema_snapshot: bool,
+    pub schema_url: Option<String>,
+    pub schema_token_env: Option<String>,
+    pub schema_cache_dir: PathBuf,
+    // Whether source files unchanged since the last run are read from the
+    // scan cache instead of scanned again.
+    pub scan_cache: bool,
+    pub schema_timeout: Duration,
+    pub require_fresh_schema: bool,
+    pub key_case_insensitive: bool,
+    pub config: Option<PathBuf>,
+    pub loader_config: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub html: Option<PathBuf>,
+    pub reports: Vec<Sink>,
+    pub color: ColorChoice,
+    pub quiet: bool,
+    // Only sets the exit code, stopping at the first failing group of
+    // checks; nothing but the summary line is printed.
+    pub check_only: bool,
+    pub max_findings: Option<usize>,
+    // Fails the run when a language translates a smaller percentage of the
+    // base keys.
+    pub min_coverage: Option<f64>,
+    // Prints the coverage table instead of the findings.
+    pub coverage_only: bool,
+    pub max_value_length: usize,
+    pub group_by: GroupBy,
+    pub junit_suites: JunitSuites,
+    pub state: Option<PathBuf>,
+    pub update_state: bool,
+    pub no_hooks: bool,
+    pub metrics_file: Option<PathBuf>,
+    pub statsd: Option<String>,
+    pub require_reviewed: Vec<String>,
+    // Sorted in the order they are applied, without repeats.
+    pub fixes: Vec<Fix>,
+    pub backup_dir: Option<PathBuf>,
+    // Lets the fixes meant to rewrite the base language do so.
+    pub allow_base_writes: bool,
+    pub dry_run: bool,
+    // Skips the confirmation of `--fix hygiene`.
+    pub yes: bool,
+    pub partition: Option<Partition>,
+    pub partition_by: Option<PartitionBy>,
+    // Limits the run to the files listed, one per line (`-` for stdin), or
+    // to the files git sees changed since a ref.
+    pub changed_files: Option<PathBuf>,
+    pub since: Option<String>,
+    // Checks the whole project but only reports the keys the translation
+    // files changed since a ref, and the changed source files.
+    pub changed_since: Option<String>,
+    // Re-runs the check on the files changed since the last run, until
+    // interrupted.
+    pub watch: bool,
+    // The socket of the `serve` server that check runs delegate to, unless
+    // `--no-daemon` checks in-process.
+    pub socket: PathBuf,
+    pub no_daemon: bool,
+    // Reviews the findings on the terminal, applying the fixes chosen
+    // once done.
+    pub interactive: bool,
+    // Files whose content is read from this JSON object instead of the disk.
+    pub buffers: Option<PathBuf>,
+    pub waivers: Option<PathBuf>,
+    pub emit_waivers: Option<PathBuf>,
+    // The findings accepted when the tool was adopted, and where to write
+    // those of this run as the new baseline.
+    pub baseline: Option<PathBuf>,
+    pub write_baseline: Option<PathBuf>,
+    pub read_only: bool,
+    pub print_config: bool,
+    pub codeowners: Option<PathBuf>,
+    pub content_rules: Option<PathBuf>,
+    pub check_untranslated: bool,
+    // Reports empty and untranslated values as errors.
+    pub strict: bool,
+    pub fail_on: Vec<FailOn>,
+    pub suggest_fixes: bool,
+    pub scan_openapi: Vec<String>,
+    pub redaction: Redaction,
+    // Findings of one check and key in more languages than this are
+    // reported as one row; `None` with `--no-rollup`.
+    pub rollup: Option<usize>,
+    // The options given on the command line, by name, which the
+    // configuration file doesn't override.
+    pub given: BTreeSet<String>,
+}
+
+// The translations a subcommand reads: `[i18n-dir] [--i18n-dir <path>]
+// [--base-lang <lang>] [--config <path>]`. What isn't given comes from the
+// configuration file as for the check run.
+#[derive(Debug, Clone, Default)]
+pub struct Target {
+    pub i18n_dir: Option<PathBuf>,
+    pub base_lang: Option<String>,
+    pub config: Option<PathBuf>,
+}
+
+impl Target {
+    // Whether `name` is one of the options of the target, which is then
+    // set to `value`.
+    pub fn option(
+        &mut self,
+        name: &str,
+        value: impl FnOnce() -> Result<String, String>,
+    ) -> Result<bool, String> {
+        match name {
+            "--i18n-dir" => self.set_i18n_dir(PathBuf::from(value()?))?,
+            "--base-lang" => self.base_lang = Some(parse_lang(&value()?, "--base-lang")?),
+            "--config" => self.config = Some(PathBuf::from(value()?)),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    // The translation directory, given as an argument or with `--i18n-dir`.
+    pub fn set_i18n_dir(&mut self, path: impl Into<PathBuf>) -> Result<(), String> {
+        if self.i18n_dir.is_some() {
+            return Err(
+                "the translation directory is given both as an argument and with `--i18n-dir`"
+                    .into(),
+            );
+        }
+        self.i18n_dir = Some(path.into());
+        Ok(())
+    }
+
+    // The options of a check run given the same target.
+    pub fn options(&self) -> Options {
+        let mut options = Options {
+            config: self.config.clone(),
+            ..Options::default()
+        };
+        if let Some(dir) = &self.i18n_dir {
+            options.i18n_dir = dir.clone();
+            options.given.insert("--i18n-dir".to_string());
+        }
+        if let Some(lang) = &self.base_lang {
+            options.base_lang = lang.clone();
+            options.base_lang_origin = BaseLangOrigin::Flag;
+            options.given.insert("--base-lang".to_string());
+        }
+        options
+    }
+}
+
+// `review approve --lang <lang> --keys-from <file> [--reviewer <name>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct ReviewOptions {
+    pub i18n_dir: PathBuf,
+    pub lang: String,
+    pub keys_from: PathBuf,
+    pub reviewer: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorklistFormat {
+    Markdown,
+    Csv,
+}
+
+// `worklist --lang <lang> [--base-lang <lang>] [--output <path>]
+// [--format markdown|csv] [--sections <list>] [--schema-snapshot <path>]
+// [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct WorklistOptions {
+    pub target: Target,
+    pub lang: String,
+    pub output: Option<PathBuf>,
+    pub format: WorklistFormat,
+    pub sections: Option<Vec<Section>>,
+    pub schema_snapshot: Option<PathBuf>,
+}
+
+// `export --lang <lang> [--base-lang <lang>] [--output <path>]
+// [--format csv|xlsx] [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub target: Target,
+    pub lang: String,
+    pub output: Option<PathBuf>,
+    pub format: SheetFormat,
+}
+
+// `import <sheet> [--base-lang <lang>] [--dry-run] [--backup-dir <path>]
+// [--read-only] [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    pub target: Target,
+    pub sheet: PathBuf,
+    pub dry_run: bool,
+    pub backup_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewFormat {
+    Text,
+    Json,
+}
+
+// `preview <key> [--lang <lang>]... [--all-langs] [--base-lang <lang>]
+// [--vars <name=value,...>] [--format text|json] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct PreviewOptions {
+    pub target: Target,
+    pub key: String,
+    // Empty for the base language, unless `all_langs` is set.
+    pub langs: Vec<String>,
+    pub all_langs: bool,
+    pub vars: BTreeMap<String, String>,
+    pub format: PreviewFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchFormat {
+    Text,
+    Json,
+}
+
+// `search <query> [--in keys|values] [--lang <lang>] [--fuzzy]
+// [--limit <n>] [--base-lang <lang>] [--format text|json]
+// [--color auto|always|never] [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub target: Target,
+    pub query: String,
+    // Keys and values by default.
+    pub fields: Vec<Field>,
+    // The language whose keys and values are searched; the base by default.
+    pub lang: Option<String>,
+    pub fuzzy: bool,
+    pub limit: usize,
+    pub format: SearchFormat,
+    pub color: ColorChoice,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    Markdown,
+    Csv,
+}
+
+// `sample --lang <lang> [--count <n>] [--seed <text>] [--exclude-reviewed]
+// [--base-lang <lang>] [--format markdown|csv] [--output <path>]
+// [--keys-output <path>] [--state <path>] [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct SampleOptions {
+    pub target: Target,
+    pub lang: String,
+    pub count: usize,
+    // Drawn from the clock and printed when not given.
+    pub seed: Option<String>,
+    pub exclude_reviewed: bool,
+    pub format: SampleFormat,
+    pub output: Option<PathBuf>,
+    // Where the drawn keys are listed for `review approve --keys-from`.
+    pub keys_output: Option<PathBuf>,
+    pub state: PathBuf,
+}
+
+// `merge-reports <report.json>... [--output <path>] [--no-rollup]`
+#[derive(Debug, Clone)]
+pub struct MergeOptions {
+    pub reports: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub rollup: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendFormat {
+    Text,
+    Json,
+}
+
+// `trend [--state <path>] [--format text|json]`
+#[derive(Debug, Clone)]
+pub struct TrendOptions {
+    pub state: PathBuf,
+    pub format: TrendFormat,
+}
+
+// `plural-rules <lang>`
+#[derive(Debug, Clone)]
+pub struct PluralRulesOptions {
+    pub lang: String,
+}
+
+// `jsonrpc --stdio`
+#[derive(Debug, Clone, Default)]
+pub struct JsonRpcOptions {
+    pub stdio: bool,
+}
+
+// `serve [--socket <path>]`
+#[derive(Debug, Clone)]
+pub struct ServeOptions {
+    pub socket: PathBuf,
+}
+
+// `lint-config [--config <path>] [--content-rules <path>]`
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    pub config: Option<PathBuf>,
+    pub content_rules: Option<PathBuf>,
+}
+
+// `verify-bundle --dist <dir> [--base-lang <lang>] [--report <format>:<path>]...
+// [--format <format>] [--color auto|always|never] [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct VerifyBundleOptions {
+    pub target: Target,
+    // The build output, such as `dist`.
+    pub dist: PathBuf,
+    // The console gets the human report unless a `--report` takes stdout.
+    pub reports: Vec<Sink>,
+    pub color: ColorChoice,
+}
+
+// `schema-report [--output <path>]`
+#[derive(Debug, Clone, Default)]
+pub struct SchemaReportOptions {
+    pub output: Option<PathBuf>,
+}
+
+// `format [--check] [--read-only] [--indent <n|tab>] [--base-lang <lang>]
+// [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    pub target: Target,
+    // Lists the files left to format instead of rewriting them.
+    pub check: bool,
+    pub indent: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Text,
+    Json,
+    Markdown,
+}
+
+// `stats [--format text|json|markdown] [--per-file] [--base-lang <lang>]
+// [--config <path>] [i18n-dir]`
+#[derive(Debug, Clone)]
+pub struct StatsOptions {
+    pub target: Target,
+    pub format: StatsFormat,
+    // Breaks each language down by base file.
+    pub per_file: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            i18n_dir: PathBuf::new(),
+            base_lang: DEFAULT_BASE_LANG.to_string(),
+            base_lang_origin: BaseLangOrigin::Default,
+            base_lang_rival: None,
+            src_dirs: vec![PathBuf::from(DEFAULT_SRC_DIR)],
+            extensions: DEFAULT_EXTENSIONS.map(str::to_string).to_vec(),
+            src_dir_extensions: BTreeMap::new(),
+            only: None,
+            timings: false,
+            low_memory: false,
+            max_open_files: None,
+            placeholder_styles: vec![PlaceholderStyle::SingleBrace],
+            placeholder_patterns: Vec::new(),
+            schema_snapshot: None,
+            update_schema_snapshot: false,
+            schema_url: None,
+            schema_token_env: None,
+            schema_cache_dir: PathBuf::from(DEFAULT_SCHEMA_CACHE_DIR),
+            scan_cache: true,
+            schema_timeout: DEFAULT_SCHEMA_TIMEOUT,
+            require_fresh_schema: false,
+            key_case_insensitive: false,
+            config: None,
+            loader_config: None,
+            output: None,
+            html: None,
+            reports: Vec::new(),
+            color: ColorChoice::Auto,
+            quiet: false,
+            check_only: false,
+            max_findings: None,
+            min_coverage: None,
+            coverage_only: false,
+            max_value_length: DEFAULT_MAX_VALUE_LENGTH,
+            group_by: GroupBy::Language,
+            junit_suites: JunitSuites::Language,
+            state: None,
+            update_state: false,
+            no_hooks: false,
+            metrics_file: None,
+            statsd: None,
+            require_reviewed: Vec::new(),
+            fixes: Vec::new(),
+            backup_dir: None,
+            allow_base_writes: false,
+            dry_run: false,
+            yes: false,
+            partition: None,
+            partition_by: None,
+            changed_files: None,
+            buffers: None,
+            since: None,
+            changed_since: None,
+            waivers: None,
+            emit_waivers: None,
+            baseline: None,
+            write_baseline: None,
+            read_only: false,
+            print_config: false,
+            watch: false,
+            socket: PathBuf::from(daemon::SOCKET_FILE),
+            no_daemon: false,
+            interactive: false,
+            codeowners: None,
+            content_rules: None,
+            check_untranslated: false,
+            strict: false,
+            fail_on: Vec::new(),
+            suggest_fixes: false,
+            scan_openapi: Vec::new(),
+            redaction: Redaction::default(),
+            rollup: Some(rollup::DEFAULT_THRESHOLD),
+            given: BTreeSet::new(),
+        }
+    }
+}
+
+impl Options {
+    // Parses the arguments following the binary name. The first positional
+    // argument is the translation directory, as it has always been. A run
+    // may be named: `check` is the default run, `unused` is short for
+    // `--only unused-keys` and `fix` for `--fix sync-keys`.
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut positional = Vec::new();
+        let mut i18n_dir = None;
+        let mut src_dirs = Vec::new();
+        let mut schema_cache_dir = false;
+        let mut placeholder_styles = Vec::new();
+        let mode = match args.first().map(String::as_str) {
+            Some(mode @ ("check" | "unused" | "fix")) => Some(mode),
+            _ => None,
+        };
+        let mut args = args[usize::from(mode.is_some())..].iter();
+
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            if name.starts_with("--") {
+                options.given.insert(name.to_string());
+            }
+            match name {
+                "--i18n-dir" => i18n_dir = Some(PathBuf::from(value()?)),
+                "--base-lang" => {
+                    options.base_lang = parse_lang(&value()?, "--base-lang")?;
+                    options.base_lang_origin = BaseLangOrigin::Flag;
+                }
+                "--src-dir" => src_dirs.push(PathBuf::from(value()?)),
+                "--ext" | "--extensions" => {
+                    let value = value()?;
+                    options.extensions = value
+                        .split(',')
+                        .map(|ext| ext.trim().trim_start_matches('.'))
+                        .filter(|ext| !ext.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    if options.extensions.is_empty() {
+                        return Err(format!(
+                            "invalid value `{}` for `--ext` (expected extensions such as `ts,js,vue`)",
+                            value
+                        ));
+                    }
+                }
+                "--only" => options.only = Some(parse_only(&value()?)?),
+                "--timings" => options.timings = true,
+                "--low-memory" => options.low_memory = true,
+                "--max-open-files" => {
+                    let value = value()?;
+                    options.max_open_files = match value.parse() {
+                        Ok(0) | Err(_) => {
+                            return Err(format!(
+                                "invalid value `{}` for `--max-open-files` (expected a positive number)",
+                                value
+                            ));
+                        }
+                        Ok(limit) => Some(limit),
+                    };
+                }
+                "--placeholder-style" => {
+                    for style in value()?.split(',') {
+                        placeholder_styles.push(parse_placeholder_style(style.trim())?);
+                    }
+                }
+                "--placeholder-pattern" => {
+                    let pattern = value()?;
+                    let regex = checks::parse_placeholder_pattern(&pattern).map_err(|err| {
+                        format!(
+                            "invalid value `{}` for `--placeholder-pattern` ({})",
+                            pattern, err
+                        )
+                    })?;
+                    options.placeholder_patterns.push(regex);
+                }
+                "--schema-snapshot" => options.schema_snapshot = Some(PathBuf::from(value()?)),
+                "--update-schema-snapshot" => options.update_schema_snapshot = true,
+                "--schema-url" => options.schema_url = Some(value()?),
+                "--schema-token-env" => options.schema_token_env = Some(value()?),
+                "--no-cache" => options.scan_cache = false,
+                "--schema-cache-dir" => {
+                    options.schema_cache_dir = PathBuf::from(value()?);
+                    schema_cache_dir = true;
+                }
+                "--schema-timeout" => {
+                    let value = value()?;
+                    let secs = value
+                        .parse()
+                        .map_err(|_| format!("invalid value `{}` for `--schema-timeout`", value))?;
+                    options.schema_timeout = Duration::from_secs(secs);
+                }
+                "--require-fresh-schema" => options.require_fresh_schema = true,
+                "--key-case-insensitive" => options.key_case_insensitive = true,
+                "--config" => options.config = Some(PathBuf::from(value()?)),
+                "--loader-config" => options.loader_config = Some(PathBuf::from(value()?)),
+                "--output" => options.output = Some(PathBuf::from(value()?)),
+                "--html" => options.html = Some(PathBuf::from(value()?)),
+                "--report" => options.reports.push(parse_report(&value()?)?),
+                // The format of the console report, as `--report <format>:-`.
+                "--format" => options.reports.push(Sink {
+                    format: parse_report_format(&value()?)?,
+                    path: None,
+                }),
+                "--color" => options.color = parse_color(&value()?)?,
+                "--quiet" | "-q" => options.quiet = true,
+                "--check-only" => options.check_only = true,
+                "--max-findings" => {
+                    let value = value()?;
+                    let max = value
+                        .parse()
+                        .map_err(|_| format!("invalid value `{}` for `--max-findings`", value))?;
+                    options.max_findings = Some(max);
+                }
+                "--min-coverage" => {
+                    let value = value()?;
+                    options.min_coverage = match value.trim_end_matches('%').parse::<f64>() {
+                        Ok(min) if (0.0..=100.0).contains(&min) => Some(min),
+                        _ => {
+                            return Err(format!(
+                                "invalid value `{}` for `--min-coverage` (expected a percentage between 0 and 100)",
+                                value
+                            ));
+                        }
+                    };
+                }
+                "--coverage-only" => options.coverage_only = true,
+                "--max-value-length" => {
+                    let value = value()?;
+                    options.max_value_length = value.parse().map_err(|_| {
+                        format!("invalid value `{}` for `--max-value-length`", value)
+                    })?;
+                }
+                "--group-by" => options.group_by = parse_group_by(&value()?)?,
+                "--junit-suites" => options.junit_suites = parse_junit_suites(&value()?)?,
+                "--state" => options.state = Some(PathBuf::from(value()?)),
+                "--update-state" => options.update_state = true,
+                "--no-hooks" => options.no_hooks = true,
+                "--metrics-file" => options.metrics_file = Some(PathBuf::from(value()?)),
+                "--statsd" => options.statsd = Some(value()?),
+                "--fix" => {
+                    for fix in value()?.split(',') {
+                        options.fixes.push(parse_fix(fix.trim())?);
+                    }
+                }
+                "--backup-dir" => options.backup_dir = Some(PathBuf::from(value()?)),
+                "--dry-run" => options.dry_run = true,
+                "--allow-base-writes" => options.allow_base_writes = true,
+                "--yes" => options.yes = true,
+                "--read-only" => options.read_only = true,
+                "--print-config" => options.print_config = true,
+                "--watch" => options.watch = true,
+                "--socket" => options.socket = PathBuf::from(value()?),
+                "--no-daemon" => options.no_daemon = true,
+                "--interactive" => options.interactive = true,
+                "--waivers" => options.waivers = Some(PathBuf::from(value()?)),
+                "--emit-waivers" => options.emit_waivers = Some(PathBuf::from(value()?)),
+                "--baseline" => options.baseline = Some(PathBuf::from(value()?)),
+                "--no-baseline" => options.baseline = None,
+                "--write-baseline" => options.write_baseline = Some(PathBuf::from(value()?)),
+                "--codeowners" => options.codeowners = Some(PathBuf::from(value()?)),
+                "--content-rules" => options.content_rules = Some(PathBuf::from(value()?)),
+                "--check-untranslated" => options.check_untranslated = true,
+                "--strict" => options.strict = true,
+                "--redact-values" => options.redaction.values = true,
+                "--redact-keys" => options.redaction.keys.push(KeyPattern::new(&value()?)?),
+                "--scan-openapi" => options.scan_openapi.push(value()?),
+                "--suggest-fixes" => options.suggest_fixes = true,
+                "--no-rollup" => options.rollup = None,
+                "--rollup-threshold" => {
+                    let value = value()?;
+                    options.rollup = Some(value.parse().map_err(|_| {
+                        format!("invalid value `{}` for `--rollup-threshold`", value)
+                    })?);
+                }
+                "--fail-on" => options.fail_on.push(parse_fail_on(&value()?)?),
+                "--partition" => options.partition = Some(Partition::parse(&value()?)?),
+                "--partition-by" => options.partition_by = Some(parse_partition_by(&value()?)?),
+                "--changed-files" => options.changed_files = Some(PathBuf::from(value()?)),
+                "--since" => options.since = Some(value()?),
+                "--changed-since" => options.changed_since = Some(value()?),
+                "--buffers" => options.buffers = Some(PathBuf::from(value()?)),
+                "--require-reviewed" => options.require_reviewed.extend(
+                    value()?
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|lang| !lang.is_empty())
+                        .map(str::to_string),
+                ),
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match mode {
+            Some("unused") => {
+                if options.only.is_some_and(|only| only != Only::UnusedKeys) {
+                    return Err("`unused` can't be combined with `--only consistency`".into());
+                }
+                options.only = Some(Only::UnusedKeys);
+            }
+            Some("fix") if !options.fixes.contains(&Fix::SyncKeys) => {
+                options.fixes.push(Fix::SyncKeys)
+            }
+            _ => {}
+        }
+
+        if !placeholder_styles.is_empty() {
+            options.placeholder_styles = placeholder_styles;
+        }
+        if !src_dirs.is_empty() {
+            options.src_dirs = src_dirs;
+        }
+
+        match (positional.as_slice(), i18n_dir) {
+            ([], None) => {}
+            ([], Some(path)) => options.i18n_dir = path,
+            ([path], None) => {
+                options.i18n_dir = PathBuf::from(path);
+                options.given.insert("--i18n-dir".to_string());
+            }
+            ([_], Some(_)) => {
+                return Err(
+                    "the translation directory is given both as an argument and with `--i18n-dir`"
+                        .into(),
+                );
+            }
+            ([_, extra, ..], _) => return Err(format!("unexpected argument `{}`", extra)),
+        }
+
+        options.fixes.sort();
+        options.fixes.dedup();
+
+        options.read_only |= read_only_from_env();
+        if options.read_only {
+            let writes = [
+                (!options.fixes.is_empty() && !options.dry_run, "--fix"),
+                (options.interactive && !options.dry_run, "--interactive"),
+                (options.update_schema_snapshot, "--update-schema-snapshot"),
+                (options.update_state, "--update-state"),
+                (options.write_baseline.is_some(), "--write-baseline"),
+                (schema_cache_dir, "--schema-cache-dir"),
+            ];
+            if let Some((_, flag)) = writes.iter().find(|(given, _)| *given) {
+                return Err(format!(
+                    "`{}` writes to the project and can't be used in read-only mode",
+                    flag
+                ));
+            }
+            options.schema_cache_dir = env::temp_dir().join(DEFAULT_SCHEMA_CACHE_DIR);
+            // The system temp directory is shared by every project.
+            options.scan_cache = false;
+        }
+
+        if options.update_schema_snapshot && options.schema_snapshot.is_none() {
+            return Err("`--update-schema-snapshot` requires `--schema-snapshot <path>`".into());
+        }
+        if options.schema_url.is_none()
+            && (options.require_fresh_schema || options.schema_token_env.is_some())
+        {
+            return Err(
+                "`--require-fresh-schema` and `--schema-token-env` require `--schema-url <url>`"
+                    .into(),
+            );
+        }
+
+        if options.backup_dir.is_some() && options.fixes.is_empty() && !options.interactive {
+            return Err("`--backup-dir` requires `--fix <fix>` or `--interactive`".into());
+        }
+        if options.dry_run && options.fixes.is_empty() && !options.interactive {
+            return Err("`--dry-run` requires `--fix <fix>` or `--interactive`".into());
+        }
+        if options.allow_base_writes && !options.fixes.iter().any(|fix| fix.writes_base()) {
+            return Err(
+                "`--allow-base-writes` requires `--fix nfc`, `--fix quote-escapes`, `--fix eol` or `--fix sync-keys`"
+                    .into(),
+            );
+        }
+        if options.yes && !options.fixes.contains(&Fix::Hygiene) {
+            return Err("`--yes` requires `--fix hygiene`".into());
+        }
+        if options.backup_dir.is_some() && options.fixes == [Fix::Hygiene] {
+            return Err("`--backup-dir` can't be combined with `--fix hygiene`, which only deletes junk files".into());
+        }
+
+        if options.strict && !options.check_untranslated {
+            return Err("`--strict` requires `--check-untranslated`".into());
+        }
+
+        if options.codeowners.is_none()
+            && (options.group_by == GroupBy::Owner || options.fail_on.contains(&FailOn::Unowned))
+        {
+            return Err(
+                "`--group-by owner` and `--fail-on unowned` require `--codeowners <path>`".into(),
+            );
+        }
+
+        if options.check_only {
+            let conflicts = [
+                ("--output", options.output.is_some()),
+                ("--html", options.html.is_some()),
+                ("--report", !options.reports.is_empty()),
+                ("--emit-waivers", options.emit_waivers.is_some()),
+                ("--write-baseline", options.write_baseline.is_some()),
+                ("--suggest-fixes", options.suggest_fixes),
+                ("--update-state", options.update_state),
+                ("--metrics-file", options.metrics_file.is_some()),
+                ("--statsd", options.statsd.is_some()),
+                ("--partition", options.partition.is_some()),
+                ("--fix", !options.fixes.is_empty()),
+            ];
+            if let Some((option, _)) = conflicts.iter().find(|(_, set)| *set) {
+                return Err(format!(
+                    "`--check-only` only sets the exit code and can't be combined with `{}`",
+                    option
+                ));
+            }
+        }
+
+        if options.only == Some(Only::UnusedKeys)
+            && let Some(option) = [
+                ("--min-coverage", options.min_coverage.is_some()),
+                ("--coverage-only", options.coverage_only),
+            ]
+            .iter()
+            .find_map(|(option, set)| set.then_some(option))
+        {
+            return Err(format!(
+                "`{}` needs the consistency checks and can't be combined with `--only unused-keys`",
+                option
+            ));
+        }
+
+        if options.partition_by.is_some() && options.partition.is_none() {
+            return Err("`--partition-by` requires `--partition <index>/<count>`".into());
+        }
+        if options.partition.is_some() {
+            if options.update_state {
+                return Err(
+                    "`--update-state` needs the whole run and can't be combined with `--partition`"
+                        .into(),
+                );
+            }
+            if !options
+                .reports
+                .iter()
+                .any(|sink| sink.format == ReportFormat::Json)
+            {
+                return Err(
+                    "`--partition` requires a `--report json:<path>` for `merge-reports`".into(),
+                );
+            }
+        }
+
+        if options.changed_files.is_some() && options.since.is_some() {
+            return Err("`--changed-files` and `--since` can't be combined".into());
+        }
+        if options.changed_since.is_some()
+            && let Some(option) = [
+                ("--changed-files", options.changed_files.is_some()),
+                ("--since", options.since.is_some()),
+                ("--write-baseline", options.write_baseline.is_some()),
+            ]
+            .iter()
+            .find_map(|(option, set)| set.then_some(option))
+        {
+            return Err(format!(
+                "`--changed-since` reports part of the findings and can't be combined with `{}`",
+                option
+            ));
+        }
+        if options.update_state
+            && let Some(option) = [
+                ("--changed-files", options.changed_files.is_some()),
+                ("--since", options.since.is_some()),
+                ("--changed-since", options.changed_since.is_some()),
+            ]
+            .iter()
+            .find_map(|(option, set)| set.then_some(option))
+        {
+            return Err(format!(
+                "`--update-state` needs the whole run and can't be combined with `{}`",
+                option
+            ));
+        }
+
+        if options.watch
+            && let Some(option) = [
+                ("--changed-files", options.changed_files.is_some()),
+                ("--since", options.since.is_some()),
+                ("--changed-since", options.changed_since.is_some()),
+                ("--update-state", options.update_state),
+                ("--update-schema-snapshot", options.update_schema_snapshot),
+                ("--fix", !options.fixes.is_empty()),
+                ("--print-config", options.print_config),
+            ]
+            .iter()
+            .find_map(|(option, set)| set.then_some(option))
+        {
+            return Err(format!(
+                "`--watch` re-runs the check on every change and can't be combined with `{}`",
+                option
+            ));
+        }
+
+        if options.interactive
+            && let Some(option) = [
+                ("--check-only", options.check_only),
+                ("--watch", options.watch),
+                ("--fix", !options.fixes.is_empty()),
+                ("--write-baseline", options.write_baseline.is_some()),
+                ("--output", options.output.is_some()),
+                ("--html", options.html.is_some()),
+                ("--report", !options.reports.is_empty()),
+                ("--emit-waivers", options.emit_waivers.is_some()),
+                ("--suggest-fixes", options.suggest_fixes),
+                ("--update-state", options.update_state),
+                ("--metrics-file", options.metrics_file.is_some()),
+                ("--statsd", options.statsd.is_some()),
+                ("--partition", options.partition.is_some()),
+            ]
+            .iter()
+            .find_map(|(option, set)| set.then_some(option))
+        {
+            return Err(format!(
+                "`--interactive` reviews the findings on the terminal and can't be combined with `{}`",
+                option
+            ));
+        }
+
+        if options.suggest_fixes
+            && !options
+                .reports
+                .iter()
+                .any(|sink| sink.format == ReportFormat::Json)
+        {
+            return Err("`--suggest-fixes` requires a `--report json:<path>`".into());
+        }
+
+        if options
+            .sinks()
+            .iter()
+            .filter(|sink| sink.path.is_none())
+            .count()
+            > 1
+        {
+            return Err("only one `--report` can be written to stdout (`-`)".into());
+        }
+
+        Ok(options)
+    }
+
+    // Every rendering of the report. The console gets the human report
+    // unless `--output` redirects it or a `--report` takes stdout.
+    pub fn sinks(&self) -> Vec<Sink> {
+        let mut sinks = self.reports.clone();
+        if let Some(path) = &self.html {
+            sinks.push(Sink {
+                format: ReportFormat::Html,
+                path: Some(path.clone()),
+            });
+        }
+        match &self.output {
+            Some(path) => sinks.push(Sink {
+                format: ReportFormat::Human,
+                path: Some(path.clone()),
+            }),
+            None if self.reports.iter().all(|sink| sink.path.is_some()) => sinks.insert(
+                0,
+                Sink {
+                    format: ReportFormat::Human,
+                    path: None,
+                },
+            ),
+            None => {}
+        }
+        sinks
+    }
+
+    // The settings that decide what a run checks and may touch, printed by
+    // `--print-config` and recorded in the JSON report for audits.
+    pub fn effective_config(&self, root: &Path, config_file: Option<&str>) -> Value {
+        json!({
+            "root": root.display().to_string(),
+            "i18n_dir": self.i18n_dir.display().to_string(),
+            "base_lang": self.base_lang,
+            "base_lang_origin": self.base_lang_origin.name(),
+            // The first source folder, as recorded before there could be
+            // several.
+            "src_dir": self.src_dirs[0].display().to_string(),
+            "src_dirs": self
+                .sources()
+                .iter()
+                .map(|root| json!({
+                    "dir": root.dir.display().to_string(),
+                    "extensions": root.extensions,
+                }))
+                .collect::<Vec<_>>(),
+            "extensions": self.extensions,
+            "placeholder_styles": self
+                .placeholder_styles
+                .iter()
+                .map(|style| style.name())
+                .collect::<Vec<_>>(),
+            "placeholder_patterns": self
+                .placeholder_patterns
+                .iter()
+                .map(Regex::as_str)
+                .collect::<Vec<_>>(),
+            "config_file": config_file,
+            "read_only": self.read_only,
+            "schema_cache_dir": self.schema_cache_dir.display().to_string(),
+            "scan_cache": self.scan_cache,
+            "key_case_insensitive": self.key_case_insensitive,
+            "redaction": self.redaction.describe(),
+        })
+    }
+
+    // The source folders with the extensions scanned in each.
+    pub fn sources(&self) -> Vec<SourceRoot> {
+        self.src_dirs
+            .iter()
+            .map(|dir| SourceRoot {
+                dir: dir.clone(),
+                extensions: self
+                    .src_dir_extensions
+                    .get(dir)
+                    .unwrap_or(&self.extensions)
+                    .clone(),
+            })
+            .collect()
+    }
+
+    pub fn remote_schema(&self) -> Option<RemoteSchema> {
+        Some(RemoteSchema {
+            url: self.schema_url.clone()?,
+            token_env: self.schema_token_env.clone(),
+            cache_dir: self.schema_cache_dir.clone(),
+            timeout: self.schema_timeout,
+            require_fresh: self.require_fresh_schema,
+        })
+    }
+
+    // The `[project]` settings of the configuration file, for the options
+    // not given on the command line.
+    pub fn apply_project(&mut self, project: &ProjectConfig) -> Result<(), String> {
+        if let Some(dir) = &project.i18n_dir
+            && !self.given.contains("--i18n-dir")
+        {
+            self.i18n_dir = dir.clone();
+        }
+        if self.i18n_dir.as_os_str().is_empty() {
+            self.i18n_dir = find_i18n_dir()?;
+        }
+        if !self.given.contains("--src-dir") {
+            if let Some(dir) = &project.src_dir {
+                self.src_dirs = vec![dir.clone()];
+            } else if !project.sources.is_empty() {
+                self.src_dirs = project
+                    .sources
+                    .iter()
+                    .map(|source| source.dir.clone())
+                    .collect();
+                self.src_dir_extensions = project
+                    .sources
+                    .iter()
+                    .filter_map(|source| Some((source.dir.clone(), source.extensions.clone()?)))
+                    .collect();
+            }
+        }
+        if let Some(extensions) = &project.extensions
+            && !self.given.contains("--ext")
+            && !self.given.contains("--extensions")
+        {
+            self.extensions = extensions.clone();
+        }
+        if let Some(styles) = &project.placeholder_styles
+            && !self.given.contains("--placeholder-style")
+        {
+            self.placeholder_styles = styles.clone();
+        }
+        if let Some(path) = &project.baseline
+            && !self.given.contains("--baseline")
+            && !self.given.contains("--no-baseline")
+        {
+            self.baseline = Some(path.clone());
+        }
+        if !self.given.contains("--placeholder-pattern") {
+            self.placeholder_patterns
+                .extend(project.placeholder_patterns.iter().cloned());
+        }
+        Ok(())
+    }
+
+    pub fn human_options(&self) -> HumanOptions {
+        HumanOptions {
+            quiet: self.quiet,
+            max_findings: self.max_findings,
+            group_by: self.group_by,
+            coverage_only: self.coverage_only,
+        }
+    }
+}
+
+impl WorklistOptions {
+    // Parses the arguments following `worklist`.
+    pub fn parse(args: &[String]) -> Result<WorklistOptions, String> {
+        let mut options = WorklistOptions {
+            target: Target::default(),
+            lang: String::new(),
+            output: None,
+            format: WorklistFormat::Markdown,
+            sections: None,
+            schema_snapshot: None,
+        };
+        let mut lang = None;
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--lang" => lang = Some(value()?),
+                "--output" => options.output = Some(PathBuf::from(value()?)),
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "markdown" => WorklistFormat::Markdown,
+                        "csv" => WorklistFormat::Csv,
+                        other => {
+                            return Err(format!(
+                                "invalid value `{}` for `--format` (expected `markdown` or `csv`)",
+                                other
+                            ));
+                        }
+                    }
+                }
+                "--sections" => {
+                    options.sections = Some(
+                        value()?
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|name| !name.is_empty())
+                            .map(|name| parse_section(name, "`--sections`"))
+                            .collect::<Result<_, _>>()?,
+                    )
+                }
+                "--schema-snapshot" => options.schema_snapshot = Some(PathBuf::from(value()?)),
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => options.target.set_i18n_dir(path)?,
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        options.lang = lang.ok_or("`worklist` requires `--lang <lang>`")?;
+        Ok(options)
+    }
+}
+
+impl ExportOptions {
+    // Parses the arguments following `export`. The format defaults to the
+    // extension of `--output`.
+    pub fn parse(args: &[String]) -> Result<ExportOptions, String> {
+        let mut options = ExportOptions {
+            target: Target::default(),
+            lang: String::new(),
+            output: None,
+            format: SheetFormat::Csv,
+        };
+        let mut lang = None;
+        let mut format = None;
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--lang" => lang = Some(parse_lang(&value()?, "--lang")?),
+                "--output" => options.output = Some(PathBuf::from(value()?)),
+                "--format" => {
+                    let value = value()?;
+                    format = Some(SheetFormat::parse(&value).ok_or_else(|| {
+                        format!(
+                            "invalid value `{}` for `--format` (expected `csv` or `xlsx`)",
+                            value
+                        )
+                    })?)
+                }
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => options.target.set_i18n_dir(path)?,
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        options.lang = lang.ok_or("`export` requires `--lang <lang>`")?;
+        options.format = format
+            .or_else(|| options.output.as_deref().map(SheetFormat::of))
+            .unwrap_or(SheetFormat::Csv);
+        if options.format == SheetFormat::Xlsx && options.output.is_none() {
+            return Err("`export --format xlsx` requires `--output <path>`".into());
+        }
+        Ok(options)
+    }
+}
+
+impl ImportOptions {
+    // Parses the arguments following `import`.
+    pub fn parse(args: &[String]) -> Result<ImportOptions, String> {
+        let mut options = ImportOptions {
+            target: Target::default(),
+            sheet: PathBuf::new(),
+            dry_run: false,
+            backup_dir: None,
+        };
+        let mut read_only = false;
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--dry-run" => options.dry_run = true,
+                "--backup-dir" => options.backup_dir = Some(PathBuf::from(value()?)),
+                "--read-only" => read_only = true,
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => return Err("`import` requires the sheet to import".into()),
+            [sheet] => options.sheet = PathBuf::from(sheet),
+            [sheet, path] => {
+                options.sheet = PathBuf::from(sheet);
+                options.target.set_i18n_dir(path)?;
+            }
+            [_, _, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        if !options.dry_run && (read_only || read_only_from_env()) {
+            return Err(
+                "`import` writes translation files and can't be used in read-only mode; `import --dry-run` only prints the changes".into(),
+            );
+        }
+        Ok(options)
+    }
+}
+
+// Removes `--cwd <path>` from the arguments, wherever it is, as it applies
+// to every command.
+pub fn take_cwd(args: &mut Vec<String>) -> Result<Option<PathBuf>, String> {
+    let mut cwd = None;
+    let mut i = 0;
+    while i < args.len() {
+        if let Some(path) = args[i].strip_prefix("--cwd=") {
+            cwd = Some(PathBuf::from(path));
+            args.remove(i);
+        } else if args[i] == "--cwd" {
+            if i + 1 == args.len() {
+                return Err("missing value for `--cwd`".into());
+            }
+            cwd = Some(PathBuf::from(args.remove(i + 1)));
+            args.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(cwd)
+}
+
+// The first of the conventional translation folders found under the
+// project root, the working directory by then.
+pub fn find_i18n_dir() -> Result<PathBuf, String> {
+    CONVENTIONAL_I18N_DIRS
+        .iter()
+        .map(PathBuf::from)
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| {
+            format!(
+                "no translation folder found in the project root (looked for {}); give it as an argument or with `--i18n-dir`, or set `i18n_dir` under `[project]` in {}",
+                CONVENTIONAL_I18N_DIRS.join(", "),
+                CONFIG_FILE
+            )
+        })
+}
+
+fn read_only_from_env() -> bool {
+    env::var(READ_ONLY_ENV).is_ok_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes"
+        )
+    })
+}
+
+impl PreviewOptions {
+    // Parses the arguments following `preview`.
+    pub fn parse(args: &[String]) -> Result<PreviewOptions, String> {
+        let mut options = PreviewOptions {
+            target: Target::default(),
+            key: String::new(),
+            langs: Vec::new(),
+            all_langs: false,
+            vars: BTreeMap::new(),
+            format: PreviewFormat::Text,
+        };
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--lang" => options.langs.extend(
+                    value()?
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|lang| !lang.is_empty())
+                        .map(str::to_string),
+                ),
+                "--all-langs" => options.all_langs = true,
+                "--vars" => {
+                    for var in value()?.split(',').filter(|var| !var.is_empty()) {
+                        let (name, sample) = var.split_once('=').ok_or_else(|| {
+                            format!(
+                                "invalid variable `{}` in `--vars` (expected `name=value`)",
+                                var
+                            )
+                        })?;
+                        options
+                            .vars
+                            .insert(name.trim().to_string(), sample.to_string());
+                    }
+                }
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "text" => PreviewFormat::Text,
+                        "json" => PreviewFormat::Json,
+                        other => {
+                            return Err(format!(
+                                "invalid value `{}` for `--format` (expected `text` or `json`)",
+                                other
+                            ));
+                        }
+                    }
+                }
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        options.key = positional.next().ok_or("`preview` requires a key")?;
+        if let Some(path) = positional.next() {
+            options.target.set_i18n_dir(path)?;
+        }
+        if let Some(extra) = positional.next() {
+            return Err(format!("unexpected argument `{}`", extra));
+        }
+        if options.all_langs && !options.langs.is_empty() {
+            return Err("`--all-langs` can't be combined with `--lang`".into());
+        }
+        Ok(options)
+    }
+}
+
+impl SearchOptions {
+    // Parses the arguments following `search`.
+    pub fn parse(args: &[String]) -> Result<SearchOptions, String> {
+        let mut options = SearchOptions {
+            target: Target::default(),
+            query: String::new(),
+            fields: Vec::new(),
+            lang: None,
+            fuzzy: false,
+            limit: DEFAULT_SEARCH_LIMIT,
+            format: SearchFormat::Text,
+            color: ColorChoice::Auto,
+        };
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--in" => {
+                    for field in value()?.split(',').map(str::trim) {
+                        options.fields.push(match field {
+                            "keys" => Field::Key,
+                            "values" => Field::Value,
+                            other => {
+                                return Err(format!(
+                                    "invalid value `{}` for `--in` (expected `keys` or `values`)",
+                                    other
+                                ));
+                            }
+                        });
+                    }
+                }
+                "--lang" => options.lang = Some(parse_lang(&value()?, "--lang")?),
+                "--fuzzy" => options.fuzzy = true,
+                "--limit" => {
+                    let value = value()?;
+                    options.limit = value
+                        .parse()
+                        .ok()
+                        .filter(|limit| *limit > 0)
+                        .ok_or_else(|| format!("invalid value `{}` for `--limit`", value))?;
+                }
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "text" => SearchFormat::Text,
+                        "json" => SearchFormat::Json,
+                        other => {
+                            return Err(format!(
+                                "invalid value `{}` for `--format` (expected `text` or `json`)",
+                                other
+                            ));
+                        }
+                    }
+                }
+                "--color" => options.color = parse_color(&value()?)?,
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        options.query = positional
+            .next()
+            .filter(|query| !query.trim().is_empty())
+            .ok_or("`search` requires a query")?;
+        if let Some(path) = positional.next() {
+            options.target.set_i18n_dir(path)?;
+        }
+        if let Some(extra) = positional.next() {
+            return Err(format!("unexpected argument `{}`", extra));
+        }
+        if options.fields.is_empty() {
+            options.fields = vec![Field::Key, Field::Value];
+        }
+        options.fields.sort();
+        options.fields.dedup();
+        Ok(options)
+    }
+}
+
+impl SampleOptions {
+    // Parses the arguments following `sample`.
+    pub fn parse(args: &[String]) -> Result<SampleOptions, String> {
+        let mut options = SampleOptions {
+            target: Target::default(),
+            lang: String::new(),
+            count: DEFAULT_SAMPLE_COUNT,
+            seed: None,
+            exclude_reviewed: false,
+            format: SampleFormat::Markdown,
+            output: None,
+            keys_output: None,
+            state: PathBuf::from(STATE_FILE),
+        };
+        let mut lang = None;
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--lang" => lang = Some(parse_lang(&value()?, "--lang")?),
+                "--count" => {
+                    let value = value()?;
+                    options.count = value
+                        .parse()
+                        .ok()
+                        .filter(|count| *count > 0)
+                        .ok_or_else(|| format!("invalid value `{}` for `--count`", value))?;
+                }
+                "--seed" => {
+                    let seed = value()?;
+                    if seed.is_empty() {
+                        return Err("`--seed` can't be empty".into());
+                    }
+                    options.seed = Some(seed);
+                }
+                "--exclude-reviewed" => options.exclude_reviewed = true,
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "markdown" => SampleFormat::Markdown,
+                        "csv" => SampleFormat::Csv,
+                        other => {
+                            return Err(format!(
+                                "invalid value `{}` for `--format` (expected `markdown` or `csv`)",
+                                other
+                            ));
+                        }
+                    }
+                }
+                "--output" => options.output = Some(PathBuf::from(value()?)),
+                "--keys-output" => options.keys_output = Some(PathBuf::from(value()?)),
+                "--state" => options.state = PathBuf::from(value()?),
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => options.target.set_i18n_dir(path)?,
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        options.lang = lang.ok_or("`sample` requires `--lang <lang>`")?;
+        Ok(options)
+    }
+}
+
+impl ReviewOptions {
+    // Parses the arguments following `review`.
+    pub fn parse(args: &[String]) -> Result<ReviewOptions, String> {
+        if read_only_from_env() || args.iter().any(|arg| arg == "--read-only") {
+            return Err(
+                "`review approve` writes to the project and can't be used in read-only mode".into(),
+            );
+        }
+        let mut args = args.iter();
+        match args.next().map(String::as_str) {
+            Some("approve") => {}
+            Some(other) => {
+                return Err(format!(
+                    "unknown review command `{}` (expected `approve`)",
+                    other
+                ));
+            }
+            None => return Err("missing review command (expected `approve`)".into()),
+        }
+
+        let mut i18n_dir = PathBuf::new();
+        let mut lang = None;
+        let mut keys_from = None;
+        let mut reviewer = None;
+        let mut positional = Vec::new();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--lang" => lang = Some(value()?),
+                "--keys-from" => keys_from = Some(PathBuf::from(value()?)),
+                "--reviewer" => reviewer = Some(value()?),
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => i18n_dir = PathBuf::from(path),
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+
+        Ok(ReviewOptions {
+            i18n_dir,
+            lang: lang.ok_or("`review approve` requires `--lang <lang>`")?,
+            keys_from: keys_from.ok_or("`review approve` requires `--keys-from <file>`")?,
+            reviewer,
+        })
+    }
+}
+
+impl MergeOptions {
+    // Parses the arguments following `merge-reports`.
+    pub fn parse(args: &[String]) -> Result<MergeOptions, String> {
+        let mut reports = Vec::new();
+        let mut output = None;
+        let mut rollup = true;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            match name {
+                "--output" => {
+                    let value = inline_value
+                        .or_else(|| args.next().cloned())
+                        .ok_or("missing value for `--output`")?;
+                    output = Some(PathBuf::from(value));
+                }
+                "--no-rollup" => rollup = false,
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => reports.push(PathBuf::from(arg)),
+            }
+        }
+
+        if reports.is_empty() {
+            return Err("`merge-reports` requires the partial JSON reports to merge".into());
+        }
+        Ok(MergeOptions {
+            reports,
+            output,
+            rollup,
+        })
+    }
+}
+
+impl TrendOptions {
+    // Parses the arguments following `trend`.
+    pub fn parse(args: &[String]) -> Result<TrendOptions, String> {
+        let mut options = TrendOptions {
+            state: PathBuf::from(STATE_FILE),
+            format: TrendFormat::Text,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--state" => options.state = PathBuf::from(value()?),
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "text" => TrendFormat::Text,
+                        "json" => TrendFormat::Json,
+                        other => {
+                            return Err(format!(
+                                "invalid value `{}` for `--format` (expected `text` or `json`)",
+                                other
+                            ));
+                        }
+                    }
+                }
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl PluralRulesOptions {
+    // Parses the arguments following `plural-rules`.
+    pub fn parse(args: &[String]) -> Result<PluralRulesOptions, String> {
+        match args {
+            [] => Err("missing language (expected `plural-rules <lang>`)".into()),
+            [arg, ..] if arg.starts_with("--") => Err(format!("unknown option `{}`", arg)),
+            [lang] => Ok(PluralRulesOptions { lang: lang.clone() }),
+            [_, extra, ..] => Err(format!("unexpected argument `{}`", extra)),
+        }
+    }
+}
+
+impl JsonRpcOptions {
+    // Parses the arguments following `jsonrpc`. Standard streams are the
+    // only transport, but editors pass `--stdio` as for language servers.
+    pub fn parse(args: &[String]) -> Result<JsonRpcOptions, String> {
+        let mut options = JsonRpcOptions::default();
+        for arg in args {
+            match arg.as_str() {
+                "--stdio" => options.stdio = true,
+                name if name.starts_with("--") => {
+                    return Err(format!("unknown option `{}`", name));
+                }
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        if !options.stdio {
+            return Err("`jsonrpc` requires `--stdio`".into());
+        }
+        Ok(options)
+    }
+}
+
+impl ServeOptions {
+    // Parses the arguments following `serve`.
+    pub fn parse(args: &[String]) -> Result<ServeOptions, String> {
+        let mut options = ServeOptions {
+            socket: PathBuf::from(daemon::SOCKET_FILE),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--socket" => options.socket = PathBuf::from(value()?),
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl SchemaReportOptions {
+    // Parses the arguments following `schema-report`.
+    pub fn parse(args: &[String]) -> Result<SchemaReportOptions, String> {
+        let mut options = SchemaReportOptions::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            match name {
+                "--output" => {
+                    let value = inline_value
+                        .or_else(|| args.next().cloned())
+                        .ok_or("missing value for `--output`")?;
+                    options.output = Some(PathBuf::from(value));
+                }
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl VerifyBundleOptions {
+    // Parses the arguments following `verify-bundle`.
+    pub fn parse(args: &[String]) -> Result<VerifyBundleOptions, String> {
+        let mut options = VerifyBundleOptions {
+            target: Target::default(),
+            dist: PathBuf::new(),
+            reports: Vec::new(),
+            color: ColorChoice::Auto,
+        };
+        let mut dist = None;
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--dist" => dist = Some(PathBuf::from(value()?)),
+                "--report" => options.reports.push(parse_report(&value()?)?),
+                "--format" => options.reports.push(Sink {
+                    format: parse_report_format(&value()?)?,
+                    path: None,
+                }),
+                "--color" => options.color = parse_color(&value()?)?,
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => options.target.set_i18n_dir(path)?,
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        options.dist = dist.ok_or("`verify-bundle` requires `--dist <dir>`")?;
+        if options
+            .reports
+            .iter()
+            .filter(|sink| sink.path.is_none())
+            .count()
+            > 1
+        {
+            return Err("only one `--report` can be written to stdout (`-`)".into());
+        }
+        if options.reports.iter().all(|sink| sink.path.is_some()) {
+            options.reports.insert(
+                0,
+                Sink {
+                    format: ReportFormat::Human,
+                    path: None,
+                },
+            );
+        }
+        Ok(options)
+    }
+}
+
+impl LintOptions {
+    // Parses the arguments following `lint-config`.
+    pub fn parse(args: &[String]) -> Result<LintOptions, String> {
+        let mut options = LintOptions::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--config" => options.config = Some(PathBuf::from(value()?)),
+                "--content-rules" => options.content_rules = Some(PathBuf::from(value()?)),
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl FormatOptions {
+    // Parses the arguments following `format`.
+    pub fn parse(args: &[String]) -> Result<FormatOptions, String> {
+        let mut options = FormatOptions {
+            target: Target::default(),
+            check: false,
+            indent: normalize::DEFAULT_INDENT.to_string(),
+        };
+        let mut read_only = false;
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--check" => options.check = true,
+                "--read-only" => read_only = true,
+                "--indent" => {
+                    let value = value()?;
+                    options.indent = match value.as_str() {
+                        "tab" => "\t".to_string(),
+                        width => match width.parse::<usize>() {
+                            Ok(width) if (1..=8).contains(&width) => " ".repeat(width),
+                            _ => {
+                                return Err(format!(
+                                    "invalid value `{}` for `--indent` (expected a width from 1 to 8, or `tab`)",
+                                    value
+                                ));
+                            }
+                        },
+                    }
+                }
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => options.target.set_i18n_dir(path)?,
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        if !options.check && (read_only || read_only_from_env()) {
+            return Err(
+                "`format` rewrites translation files and can't be used in read-only mode; `format --check` only lists them".into(),
+            );
+        }
+        Ok(options)
+    }
+}
+
+impl StatsOptions {
+    // Parses the arguments following `stats`.
+    pub fn parse(args: &[String]) -> Result<StatsOptions, String> {
+        let mut options = StatsOptions {
+            target: Target::default(),
+            format: StatsFormat::Text,
+            per_file: false,
+        };
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("missing value for `{}`", name))
+            };
+
+            match name {
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "text" => StatsFormat::Text,
+                        "json" => StatsFormat::Json,
+                        "markdown" => StatsFormat::Markdown,
+                        other => {
+                            return Err(format!(
+                                "invalid value `{}` for `--format` (expected `text`, `json` or `markdown`)",
+                                other
+                            ));
+                        }
+                    }
+                }
+                "--per-file" => options.per_file = true,
+                _ if options.target.option(name, &mut value)? => {}
+                _ if name.starts_with("--") => return Err(format!("unknown option `{}`", name)),
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        match positional.as_slice() {
+            [] => {}
+            [path] => options.target.set_i18n_dir(path)?,
+            [_, extra, ..] => return Err(format!("unexpected argument `{}`", extra)),
+        }
+        Ok(options)
+    }
+}
+
+fn parse_color(value: &str) -> Result<ColorChoice, String> {
+    match value {
+        "auto" => Ok(ColorChoice::Auto),
+        "always" => Ok(ColorChoice::Always),
+        "never" => Ok(ColorChoice::Never),
+        _ => Err(format!(
+            "invalid value `{}` for `--color` (expected `auto`, `always` or `never`)",
+            value
+        )),
+    }
+}
+
+fn parse_placeholder_style(value: &str) -> Result<PlaceholderStyle, String> {
+    PlaceholderStyle::parse(value).ok_or_else(|| {
+        format!(
+            "invalid value `{}` for `--placeholder-style` (expected `single-brace`, `double-brace`, `percent` or `all`)",
+            value
+        )
+    })
+}
+
+fn parse_fix(value: &str) -> Result<Fix, String> {
+    match value {
+        "nfc" => Ok(Fix::Nfc),
+        "translated-placeholders" => Ok(Fix::TranslatedPlaceholders),
+        "quote-escapes" => Ok(Fix::QuoteEscapes),
+        "hygiene" => Ok(Fix::Hygiene),
+        "eol" => Ok(Fix::Eol),
+        "sync-keys" => Ok(Fix::SyncKeys),
+        _ => Err(format!(
+            "invalid value `{}` for `--fix` (expected `nfc`, `translated-placeholders`, `quote-escapes`, `eol`, `hygiene` or `sync-keys`)",
+            value
+        )),
+    }
+}
+
+fn parse_partition_by(value: &str) -> Result<PartitionBy, String> {
+    match value {
+        "prefix" => Ok(PartitionBy::Prefix),
+        _ => Err(format!(
+            "invalid value `{}` for `--partition-by` (expected `prefix`)",
+            value
+        )),
+    }
+}
+
+fn parse_report_format(format: &str) -> Result<ReportFormat, String> {
+    match format {
+        "human" => Ok(ReportFormat::Human),
+        "json" => Ok(ReportFormat::Json),
+        "junit" => Ok(ReportFormat::Junit),
+        "html" => Ok(ReportFormat::Html),
+        "plain" => Ok(ReportFormat::Plain),
+        "sarif" => Ok(ReportFormat::Sarif),
+        _ => Err(format!(
+            "invalid report format `{}` (expected `human`, `json`, `junit`, `html`, `plain` or `sarif`)",
+            format
+        )),
+    }
+}
+
+fn parse_report(value: &str) -> Result<Sink, String> {
+    let (format, path) = value.split_once(':').ok_or_else(|| {
+        format!(
+            "invalid value `{}` for `--report` (expected `<format>:<path>`)",
+            value
+        )
+    })?;
+    let format = parse_report_format(format)?;
+    let path = match path {
+        "" => return Err(format!("missing path in `--report {}`", value)),
+        "-" => None,
+        path => Some(PathBuf::from(path)),
+    };
+    Ok(Sink { format, path })
+}
+
+fn parse_group_by(value: &str) -> Result<GroupBy, String> {
+    match value {
+        "language" => Ok(GroupBy::Language),
+        "file" => Ok(GroupBy::File),
+        "owner" => Ok(GroupBy::Owner),
+        _ => Err(format!(
+            "invalid value `{}` for `--group-by` (expected `language`, `file` or `owner`)",
+            value
+        )),
+    }
+}
+
+fn parse_junit_suites(value: &str) -> Result<JunitSuites, String> {
+    match value {
+        "check" => Ok(JunitSuites::Check),
+        "language" => Ok(JunitSuites::Language),
+        _ => Err(format!(
+            "invalid value `{}` for `--junit-suites` (expected `language` or `check`)",
+            value
+        )),
+    }
+}
+
+fn parse_fail_on(value: &str) -> Result<FailOn, String> {
+    match value {
+        "unowned" => Ok(FailOn::Unowned),
+        "warnings" => Ok(FailOn::Warnings),
+        _ => Err(format!(
+            "invalid value `{}` for `--fail-on` (expected `unowned` or `warnings`)",
+            value
+        )),
+    }
+}
+
+fn parse_only(value: &str) -> Result<Only, String> {
+    match value {
+        "unused-keys" => Ok(Only::UnusedKeys),
+        "consistency" => Ok(Only::Consistency),
+        _ => Err(format!(
+            "invalid value `{}` for `--only` (expected `unused-keys` or `consistency`)",
+            value
+        )),
+    }
+}
@@ -0,0 +1,220 @@
+use crate::report::{Check, Finding, Report};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+// The bucket of findings whose file matches no rule, or a rule without
+// owners.
+pub const UNOWNED: &str = "(unowned)";
+
+// One line of a CODEOWNERS file.
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+    regex: Regex,
+}
+
+impl Rule {
+    // Follows the CODEOWNERS flavor of gitignore patterns: a pattern with a
+    // slash other than a trailing one is anchored to the repository root,
+    // others match at any depth. `*` and `?` stay within a path segment and
+    // `**` spans segments. A pattern matching a directory owns everything
+    // below it, but `docs/*` only owns the files directly in `docs`.
+    pub fn new(pattern: &str, owners: Vec<String>) -> Result<Rule, String> {
+        let dir_only = pattern.ends_with('/');
+        let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+        let body = pattern.trim_start_matches('/').trim_end_matches('/');
+        if body.is_empty() {
+            return Err(format!("pattern `{}` matches nothing", pattern));
+        }
+
+        let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+        let chars: Vec<char> = body.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    if chars.get(i + 2) == Some(&'/') {
+                        regex.push_str("(?:.*/)?");
+                        i += 3;
+                    } else {
+                        regex.push_str(".*");
+                        i += 2;
+                    }
+                    continue;
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                '\\' if i + 1 < chars.len() => {
+                    i += 1;
+                    regex.push_str(&regex::escape(&chars[i].to_string()));
+                }
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+            i += 1;
+        }
+
+        let last_segment = body.rsplit('/').next().unwrap_or(body);
+        if dir_only {
+            regex.push_str("/.*");
+        } else if !last_segment.contains(['*', '?']) {
+            regex.push_str("(?:/.*)?");
+        }
+        regex.push('$');
+
+        Ok(Rule {
+            pattern: pattern.to_string(),
+            owners,
+            regex: Regex::new(&regex)
+                .map_err(|err| format!("invalid pattern `{}`: {}", pattern, err))?,
+        })
+    }
+
+    // `path` is relative to the repository root, with `/` separators.
+    pub fn matches(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+pub struct CodeOwners {
+    // The repository root the patterns are relative to.
+    pub root: PathBuf,
+    pub rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    pub fn parse(source: &str, root: PathBuf) -> Result<CodeOwners, String> {
+        let mut rules = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // Owners may be followed by a comment.
+            let line = line.split(" #").next().unwrap();
+            let mut fields = split_fields(line).into_iter();
+            let pattern = fields.next().unwrap();
+            let owners = fields.collect();
+            rules.push(
+                Rule::new(&pattern, owners)
+                    .map_err(|err| format!("line {}: {}", index + 1, err))?,
+            );
+        }
+        Ok(CodeOwners { root, rules })
+    }
+
+    // Reads a CODEOWNERS file. Its repository is the folder holding it, or
+    // the parent of `.github` and `docs`, as GitHub looks for it there.
+    pub fn load(path: &Path) -> Result<CodeOwners, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read CODEOWNERS {}: {}", path.display(), err))?;
+        let mut root = absolute(path.parent().unwrap_or(Path::new(".")));
+        if root
+            .file_name()
+            .is_some_and(|name| name == ".github" || name == "docs")
+        {
+            root.pop();
+        }
+        CodeOwners::parse(&source, root)
+            .map_err(|err| format!("invalid CODEOWNERS {}: {}", path.display(), err))
+    }
+
+    // The owners of a path relative to the repository root. The last
+    // matching rule wins, even when it has no owners.
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path))
+            .map_or(&[], |rule| &rule.owners)
+    }
+
+    // A file as shown in findings, relative to the working directory or
+    // absolute, relative to the repository root. `None` outside it.
+    pub fn relative(&self, file: &str) -> Option<String> {
+        let path = absolute(Path::new(file));
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let segments: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().to_string())
+            .collect();
+        Some(segments.join("/"))
+    }
+
+    // Records the owners of the file of every finding. With
+    // `fail_on_unowned`, each of those files nobody owns is reported too.
+    pub fn assign(&self, report: &mut Report, fail_on_unowned: bool) {
+        let mut unowned: BTreeMap<String, usize> = BTreeMap::new();
+        for finding in &mut report.findings {
+            let file = finding.file.clone();
+            let owners = file
+                .as_deref()
+                .and_then(|file| self.relative(file))
+                .map(|path| self.owners_of(&path).to_vec())
+                .unwrap_or_default();
+            if owners.is_empty()
+                && let Some(file) = file
+            {
+                *unowned.entry(file).or_default() += finding.weight;
+            }
+            finding.owners = Some(owners);
+        }
+
+        if fail_on_unowned {
+            for (file, findings) in unowned {
+                let mut finding = Finding::new(Check::UnownedFile)
+                    .detail("Findings", findings.to_string())
+                    .file(&file);
+                finding.owners = Some(Vec::new());
+                report.findings.push(finding);
+            }
+        }
+    }
+}
+
+// A pattern and its owners, keeping `\ ` escaped spaces in the pattern.
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                field.push(c);
+                field.extend(chars.next());
+            }
+            c if c.is_whitespace() => {
+                if !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() {
+        fields.push(field);
+    }
+    fields
+}
+
+// Makes `path` absolute, resolving symlinks when it exists so that it
+// compares with the repository root.
+fn absolute(path: &Path) -> PathBuf {
+    if let Ok(path) = fs::canonicalize(path) {
+        return path;
+    }
+    let path = std::env::current_dir().unwrap_or_default().join(path);
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,1469 @@
+use crate::checks::key_naming::KeyCase;
+use crate::checks::reserved::{Reserved, Rule};
+use crate::checks::scripts::Script;
+use crate::checks::style::Capitalization;
+use crate::checks::{self, PlaceholderStyle};
+use crate::model::{FileNameRule, parse_lang};
+use crate::report::{Check, Severity};
+use crate::selector::KeyPattern;
+use crate::sync;
+use crate::toml;
+use crate::usage::{self, UsageMode};
+use crate::waivers::Waiver;
+use crate::worklist::{Section, parse_section};
+use glob::Pattern;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const CONFIG_FILE: &str = "translation-check.toml";
+// The same settings as JSON, read when there is no `CONFIG_FILE`.
+pub const JSON_CONFIG_FILE: &str = ".translationcheckrc.json";
+
+// The folder relative paths are resolved against: the nearest ancestor of
+// `start` holding a git repository or a configuration file, or `start`
+// itself when there is none.
+pub fn project_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| {
+            dir.join(".git").exists()
+                || dir.join(CONFIG_FILE).exists()
+                || dir.join(JSON_CONFIG_FILE).exists()
+        })
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+// Defaults for the command-line options of the same names, which win when
+// given.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectConfig {
+    pub i18n_dir: Option<PathBuf>,
+    pub src_dir: Option<PathBuf>,
+    // `[[project.sources]]`: several source folders, each with its own
+    // extensions or those of `extensions`, instead of `src_dir`.
+    pub sources: Vec<SourceConfig>,
+    pub extensions: Option<Vec<String>>,
+    pub placeholder_styles: Option<Vec<PlaceholderStyle>>,
+    pub placeholder_patterns: Vec<Regex>,
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceConfig {
+    pub dir: PathBuf,
+    pub extensions: Option<Vec<String>>,
+}
+
+fn extensions(value: &Value, name: &str) -> Result<Option<Vec<String>>, String> {
+    match value.get(name.rsplit('.').next().unwrap()) {
+        None => Ok(None),
+        Some(_) => Ok(Some(
+            strings(value, name)?
+                .into_iter()
+                .map(|ext| ext.trim_start_matches('.').to_string())
+                .filter(|ext| !ext.is_empty())
+                .collect(),
+        )),
+    }
+}
+
+impl SourceConfig {
+    fn from_value(value: &Value) -> Result<SourceConfig, String> {
+        let dir = string(value, "project.sources.dir")?
+            .ok_or("every `[[project.sources]]` needs a `dir`")?;
+        let extensions = extensions(value, "project.sources.extensions")?;
+        if extensions.as_ref().is_some_and(Vec::is_empty) {
+            return Err(format!(
+                "`project.sources.extensions` of `{}` names no extension",
+                dir
+            ));
+        }
+        Ok(SourceConfig {
+            dir: PathBuf::from(dir),
+            extensions,
+        })
+    }
+}
+
+impl ProjectConfig {
+    fn from_value(value: &Value) -> Result<ProjectConfig, String> {
+        let extensions = extensions(value, "project.extensions")?;
+        let sources: Vec<SourceConfig> = match value.get("sources") {
+            None => Vec::new(),
+            Some(Value::Array(sources)) => sources
+                .iter()
+                .map(SourceConfig::from_value)
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`project.sources` must be an array of tables".to_string()),
+        };
+        if !sources.is_empty() && value.get("src_dir").is_some() {
+            return Err(
+                "`project.src_dir` and `[[project.sources]]` can't both be set".to_string(),
+            );
+        }
+        let placeholder_styles = match value.get("placeholder_styles") {
+            None => None,
+            Some(_) => Some(
+                strings(value, "project.placeholder_styles")?
+                    .iter()
+                    .map(|name| {
+                        PlaceholderStyle::parse(name).ok_or_else(|| {
+                            format!(
+                                "unknown placeholder style `{}` in `project.placeholder_styles`",
+                                name
+                            )
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+        let placeholder_patterns = strings(value, "project.placeholder_patterns")?
+            .iter()
+            .map(|pattern| {
+                checks::parse_placeholder_pattern(pattern).map_err(|err| {
+                    format!(
+                        "invalid pattern `{}` in `project.placeholder_patterns`: {}",
+                        pattern, err
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(ProjectConfig {
+            i18n_dir: string(value, "project.i18n_dir")?.map(PathBuf::from),
+            src_dir: string(value, "project.src_dir")?.map(PathBuf::from),
+            sources,
+            extensions,
+            placeholder_styles,
+            placeholder_patterns,
+            baseline: string(value, "project.baseline")?.map(PathBuf::from),
+        })
+    }
+}
+
+// Project settings read from `translation-check.toml`, or from
+// `.translationcheckrc.json` without one.
+#[derive(Default)]
+pub struct Config {
+    // The file the settings were read from, if any.
+    pub source: Option<PathBuf>,
+    pub project: ProjectConfig,
+    // The severity of every finding of a check, instead of its own; `None`
+    // for the checks turned `off`, whose findings are dropped.
+    pub severities: BTreeMap<Check, Option<Severity>>,
+    pub loader_config: Option<LoaderConfig>,
+    pub linked_keys: Vec<LinkedKeys>,
+    // Keys translated in the base language only, such as `dev.` strings.
+    pub base_only_namespaces: Vec<KeyPattern>,
+    // The only keys linked messages (`@:key`) may point to, when set.
+    pub linkable_keys: Option<Vec<KeyPattern>>,
+    pub hooks: Option<HooksConfig>,
+    pub scripts: ScriptsConfig,
+    pub key_constants: Option<KeyConstantsConfig>,
+    pub e2e: E2eConfig,
+    pub exclude: ExcludeConfig,
+    pub key_naming: Option<KeyNamingConfig>,
+    // Links to screenshots of the keys, shown with their findings.
+    pub context: Option<ContextConfig>,
+    // Where `verify-bundle` finds the translations in the build output.
+    pub bundle: BundleConfig,
+    // Enables the check for copy-pasted sibling values.
+    pub sibling_duplicates: Option<SiblingDuplicatesConfig>,
+    // Enables the check for plural forms of a base key disagreeing.
+    pub plural_forms: Option<PluralFormsConfig>,
+    // Enables the check for placeholders changing position.
+    pub placeholder_position: bool,
+    // Enables the checks reading values as ICU MessageFormat.
+    pub icu: bool,
+    // Enables the check for line endings and final newlines.
+    pub file_format: Option<FileFormatConfig>,
+    pub suppressions: SuppressionsConfig,
+    // Placeholder names the formatting library treats specially.
+    pub reserved_placeholders: Reserved,
+    pub unreadable_files: UnreadableFilesConfig,
+    pub untranslated: UntranslatedConfig,
+    pub usage: UsageConfig,
+    pub sync_keys: SyncKeysConfig,
+    // How file names repeating their language are compared across folders.
+    pub file_name_rules: Vec<FileNameRule>,
+    // The languages the project must have, instead of the ones recorded in
+    // the state file.
+    pub expected_languages: Option<Vec<String>>,
+    // The base language when `--base-lang` isn't given.
+    pub base_lang: Option<String>,
+    // Language folders read as part of another language, such as `zh-CN`
+    // for `zh-Hans`, by alias.
+    pub language_aliases: BTreeMap<String, String>,
+    // Folders of the translation directory that hold no language, such as
+    // `_archive`, kept there on purpose.
+    pub non_language_dirs: Vec<String>,
+    // Which sections `worklist` lists, in order.
+    pub worklist_sections: Option<Vec<Section>>,
+    // The translation files loaded for the sources of each route, when the
+    // application lazy-loads them.
+    pub routes: Vec<Route>,
+    // How the values of whole namespaces, such as `buttons.*`, are written.
+    pub styles: Vec<StyleRule>,
+    // Findings the project never wants reported, such as keys only some
+    // markets have; `[[ignore]]` entries take the fields of a waiver.
+    pub ignores: Vec<Waiver>,
+}
+
+// The context manifest mapping key globs to screenshots or design links,
+// and how many links a finding shows at most.
+pub struct ContextConfig {
+    pub manifest: PathBuf,
+    pub max_links: usize,
+}
+
+impl ContextConfig {
+    fn from_value(value: &Value) -> Result<ContextConfig, String> {
+        let manifest =
+            string(value, "context.manifest")?.ok_or("`context.manifest` is required")?;
+        let max_links = match value.get("max_links") {
+            None => 3,
+            Some(max) => max
+                .as_u64()
+                .filter(|max| *max > 0)
+                .ok_or("`context.max_links` must be a positive integer")?
+                as usize,
+        };
+        Ok(ContextConfig {
+            manifest: PathBuf::from(manifest),
+            max_links,
+        })
+    }
+}
+
+// Built files holding translations, for `verify-bundle`. `files` is a glob
+// under the build output in which `{lang}` stands for the language of a
+// file holding one language. `path` leads, dot-separated, to the
+// translations in the JSON the file holds or embeds: the messages of that
+// language, or an object of languages when `files` has no `{lang}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleAsset {
+    pub files: String,
+    pub path: Vec<String>,
+}
+
+impl BundleAsset {
+    // The glob matching the files, `{lang}` standing for any name.
+    pub fn glob(&self) -> String {
+        self.files.replace("{lang}", "*")
+    }
+}
+
+pub struct BundleConfig {
+    pub assets: Vec<BundleAsset>,
+}
+
+impl Default for BundleConfig {
+    // Translation files copied as they are, such as `locales/de.json`.
+    fn default() -> Self {
+        BundleConfig {
+            assets: vec![BundleAsset {
+                files: "**/{lang}.json".to_string(),
+                path: Vec::new(),
+            }],
+        }
+    }
+}
+
+impl BundleConfig {
+    fn from_value(value: &Value) -> Result<BundleConfig, String> {
+        let assets = match value.get("assets") {
+            None => return Ok(BundleConfig::default()),
+            Some(Value::Array(assets)) if !assets.is_empty() => assets,
+            Some(_) => {
+                return Err("`bundle.assets` must be a non-empty array of tables".to_string());
+            }
+        };
+        let assets = assets
+            .iter()
+            .map(|asset| {
+                let files = string(asset, "bundle.assets.files")?
+                    .ok_or("`bundle.assets.files` is required")?;
+                if files.matches("{lang}").count() > 1 {
+                    return Err(format!(
+                        "`bundle.assets.files` `{}` can only use `{{lang}}` once",
+                        files
+                    ));
+                }
+                let path = string(asset, "bundle.assets.path")?.unwrap_or_default();
+                let asset = BundleAsset {
+                    files,
+                    path: path
+                        .split('.')
+                        .filter(|segment| !segment.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                };
+                Pattern::new(&asset.glob()).map_err(|err| {
+                    format!(
+                        "invalid `bundle.assets.files` glob `{}`: {}",
+                        asset.files, err
+                    )
+                })?;
+                Ok(asset)
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(BundleConfig { assets })
+    }
+}
+
+// Values shorter than `min_length` characters, such as "OK", are repeated
+// legitimately and never reported.
+pub struct SiblingDuplicatesConfig {
+    pub min_length: usize,
+}
+
+impl SiblingDuplicatesConfig {
+    fn from_value(value: &Value) -> Result<SiblingDuplicatesConfig, String> {
+        let min_length = match value.get("min_length") {
+            None => 4,
+            Some(length) => length
+                .as_u64()
+                .ok_or("`sibling_duplicates.min_length` must be a positive integer")?
+                as usize,
+        };
+        Ok(SiblingDuplicatesConfig { min_length })
+    }
+}
+
+// Keys ending in `_<suffix>` are the plural forms of the key without it,
+// such as `item_one` and `item_other` for i18next.
+pub struct PluralFormsConfig {
+    pub suffixes: Vec<String>,
+}
+
+impl PluralFormsConfig {
+    fn from_value(value: &Value) -> Result<PluralFormsConfig, String> {
+        let suffixes = match value.get("suffixes") {
+            None => ["zero", "one", "two", "few", "many", "other"]
+                .map(str::to_string)
+                .to_vec(),
+            Some(_) => strings(value, "plural_forms.suffixes")?,
+        };
+        Ok(PluralFormsConfig { suffixes })
+    }
+}
+
+// The line break translation files use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eol {
+    Lf,
+    Crlf,
+}
+
+impl Eol {
+    pub fn name(self) -> &'static str {
+        match self {
+            Eol::Lf => "lf",
+            Eol::Crlf => "crlf",
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Eol::Lf => "\n",
+            Eol::Crlf => "\r\n",
+        }
+    }
+}
+
+// How translation files end their lines and whether their last line ends
+// too. A setting left out accepts anything.
+pub struct FileFormatConfig {
+    pub eol: Option<Eol>,
+    pub final_newline: Option<bool>,
+}
+
+impl FileFormatConfig {
+    fn from_value(value: &Value) -> Result<FileFormatConfig, String> {
+        let eol = match string(value, "file_format.eol")?.as_deref() {
+            None => None,
+            Some("lf") => Some(Eol::Lf),
+            Some("crlf") => Some(Eol::Crlf),
+            Some(eol) => {
+                return Err(format!(
+                    "invalid value `{}` for `file_format.eol` (expected `lf` or `crlf`)",
+                    eol
+                ));
+            }
+        };
+        let final_newline = match value.get("final_newline") {
+            None => None,
+            Some(final_newline) => Some(
+                final_newline
+                    .as_bool()
+                    .ok_or("`file_format.final_newline` must be a boolean")?,
+            ),
+        };
+        Ok(FileFormatConfig { eol, final_newline })
+    }
+}
+
+// A language more than `max_fraction` of whose files can't be read is left
+// out of the comparison, instead of having all their keys reported missing.
+pub struct UnreadableFilesConfig {
+    pub max_fraction: f64,
+}
+
+impl Default for UnreadableFilesConfig {
+    fn default() -> Self {
+        UnreadableFilesConfig { max_fraction: 0.2 }
+    }
+}
+
+impl UnreadableFilesConfig {
+    fn from_value(value: &Value) -> Result<UnreadableFilesConfig, String> {
+        let mut config = UnreadableFilesConfig::default();
+        if let Some(fraction) = value.get("max_fraction") {
+            config.max_fraction = fraction
+                .as_f64()
+                .filter(|fraction| (0.0..=1.0).contains(fraction))
+                .ok_or("`unreadable_files.max_fraction` must be a number between 0 and 1")?;
+        }
+        Ok(config)
+    }
+}
+
+// With `--check-untranslated`, translations identical to their base value
+// are only reported from `min_length` characters, so that "OK" or "PDF"
+// can stay as they are. Values listed in `allow`, such as brand names, and
+// the keys under the `allow_keys` patterns may always match the base.
+pub struct UntranslatedConfig {
+    pub min_length: usize,
+    pub allow: Vec<String>,
+    pub allow_keys: Vec<KeyPattern>,
+}
+
+impl Default for UntranslatedConfig {
+    fn default() -> Self {
+        UntranslatedConfig {
+            min_length: 4,
+            allow: Vec::new(),
+            allow_keys: Vec::new(),
+        }
+    }
+}
+
+impl UntranslatedConfig {
+    fn from_value(value: &Value) -> Result<UntranslatedConfig, String> {
+        let mut config = UntranslatedConfig::default();
+        if let Some(length) = value.get("min_length") {
+            config.min_length = length
+                .as_u64()
+                .ok_or("`untranslated.min_length` must be a positive integer")?
+                as usize;
+        }
+        config.allow = strings(value, "untranslated.allow")?;
+        config.allow_keys = strings(value, "untranslated.allow_keys")?
+            .iter()
+            .map(|pattern| KeyPattern::new(pattern))
+            .collect::<Result<_, _>>()?;
+        Ok(config)
+    }
+
+    // Whether the translation of `key` may be the base value `value`.
+    pub fn allows(&self, key: &str, value: &str) -> bool {
+        self.allow.iter().any(|allowed| allowed == value)
+            || self.allow_keys.iter().any(|pattern| pattern.covers(key))
+    }
+}
+
+// How the sources are searched for keys. In `calls` mode, only the keys
+// the `call_patterns` capture are used, and the keys they capture that the
+// base lacks are the undefined ones.
+pub struct UsageConfig {
+    pub mode: UsageMode,
+    pub call_patterns: Vec<Regex>,
+    // Keys the code builds in ways the scan can't follow, such as
+    // `t(statusKey(code))`, which are always used.
+    pub dynamic_keys: Vec<KeyPattern>,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        UsageConfig {
+            mode: UsageMode::Quoted,
+            call_patterns: usage::CALL_PATTERNS
+                .iter()
+                .map(|pattern| Regex::new(pattern).unwrap())
+                .collect(),
+            dynamic_keys: Vec::new(),
+        }
+    }
+}
+
+impl UsageConfig {
+    fn from_value(value: &Value) -> Result<UsageConfig, String> {
+        let mut config = UsageConfig::default();
+        if let Some(mode) = string(value, "usage.mode")? {
+            config.mode = UsageMode::parse(&mode).ok_or_else(|| {
+                format!(
+                    "invalid value `{}` for `usage.mode` (expected `quoted` or `calls`)",
+                    mode
+                )
+            })?;
+        }
+        if value.get("call_patterns").is_some() {
+            config.call_patterns = strings(value, "usage.call_patterns")?
+                .iter()
+                .map(|pattern| match Regex::new(pattern) {
+                    Ok(regex) if regex.captures_len() > 1 => Ok(regex),
+                    Ok(_) => Err(format!(
+                        "invalid pattern `{}` in `usage.call_patterns`: it captures no key",
+                        pattern
+                    )),
+                    Err(err) => Err(format!(
+                        "invalid pattern `{}` in `usage.call_patterns`: {}",
+                        pattern, err
+                    )),
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        config.dynamic_keys = strings(value, "usage.dynamic_keys")?
+            .iter()
+            .map(|pattern| KeyPattern::new(pattern))
+            .collect::<Result<_, _>>()?;
+        Ok(config)
+    }
+}
+
+// What `--fix sync-keys` values the keys it adds with: `template`, in which
+// `{value}` stands for the base value, as `[TODO] {value}` by default.
+pub struct SyncKeysConfig {
+    pub template: String,
+}
+
+impl Default for SyncKeysConfig {
+    fn default() -> Self {
+        SyncKeysConfig {
+            template: sync::DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+impl SyncKeysConfig {
+    fn from_value(value: &Value) -> Result<SyncKeysConfig, String> {
+        let mut config = SyncKeysConfig::default();
+        if let Some(template) = string(value, "sync_keys.template")? {
+            config.template = template;
+        }
+        Ok(config)
+    }
+}
+
+// How `i18n-check-disable` comments in the sources are checked.
+pub struct SuppressionsConfig {
+    // The severity of regions left disabled at the end of a file.
+    pub unterminated: Severity,
+}
+
+impl Default for SuppressionsConfig {
+    fn default() -> Self {
+        SuppressionsConfig {
+            unterminated: Severity::Error,
+        }
+    }
+}
+
+impl SuppressionsConfig {
+    fn from_value(value: &Value) -> Result<SuppressionsConfig, String> {
+        let unterminated = match string(value, "suppressions.unterminated")?.as_deref() {
+            None | Some("error") => Severity::Error,
+            Some("warning") => Severity::Warning,
+            Some(other) => {
+                return Err(format!(
+                    "invalid value `{}` for `suppressions.unterminated` (expected `error` or `warning`)",
+                    other
+                ));
+            }
+        };
+        Ok(SuppressionsConfig { unterminated })
+    }
+}
+
+// Generated files binding keys to constants, such as
+// `export const CHECKOUT_TITLE = 'checkout.title'`. Keys are then used
+// through the constants rather than written out in the sources.
+pub struct KeyConstantsConfig {
+    // Globs matched against paths relative to the source folder.
+    pub files: Vec<Pattern>,
+}
+
+impl KeyConstantsConfig {
+    pub fn matches(&self, relative: &Path) -> bool {
+        self.files
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+    }
+}
+
+// End-to-end tests, which select elements by their translated text or a
+// `data-testid` holding a key. Keys only they use are reported apart from
+// the unused ones, as deleting them breaks the tests.
+pub struct E2eConfig {
+    // Globs matched against paths relative to the source folder.
+    pub files: Vec<Pattern>,
+}
+
+impl Default for E2eConfig {
+    fn default() -> Self {
+        E2eConfig {
+            files: ["e2e/**", "**/*.e2e.ts", "cypress/**"]
+                .iter()
+                .map(|glob| Pattern::new(glob).unwrap())
+                .collect(),
+        }
+    }
+}
+
+impl E2eConfig {
+    pub fn matches(&self, relative: &Path) -> bool {
+        self.files
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+    }
+
+    fn from_value(value: &Value) -> Result<E2eConfig, String> {
+        if value.get("files").is_none() {
+            return Ok(E2eConfig::default());
+        }
+        let files = strings(value, "e2e.files")?
+            .iter()
+            .map(|file| {
+                Pattern::new(file)
+                    .map_err(|err| format!("invalid `e2e.files` glob `{}`: {}", file, err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(E2eConfig { files })
+    }
+}
+
+// What every check leaves out, as if it didn't exist: generated translation
+// files and vendored sources, by globs relative to the translation directory
+// and to the source folder, and keys in every language. Unlike `[[ignore]]`,
+// excluded keys aren't counted either.
+#[derive(Default)]
+pub struct ExcludeConfig {
+    pub translations: Vec<Pattern>,
+    pub sources: Vec<Pattern>,
+    pub keys: Vec<KeyPattern>,
+}
+
+impl ExcludeConfig {
+    pub fn excludes_key(&self, key: &str) -> bool {
+        self.keys.iter().any(|pattern| pattern.covers(key))
+    }
+
+    pub fn excludes_source(&self, relative: &Path) -> bool {
+        self.sources
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+    }
+
+    fn from_value(value: &Value) -> Result<ExcludeConfig, String> {
+        let globs = |name: &str| {
+            strings(value, &format!("exclude.{}", name))?
+                .iter()
+                .map(|glob| {
+                    Pattern::new(glob).map_err(|err| {
+                        format!("invalid `exclude.{}` glob `{}`: {}", name, glob, err)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(ExcludeConfig {
+            translations: globs("translations")?,
+            sources: globs("sources")?,
+            keys: strings(value, "exclude.keys")?
+                .iter()
+                .map(|pattern| KeyPattern::new(pattern))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+// The naming rules of the base keys, each left out when unset: the case
+// of their segments, a regex each segment must match, how many segments a
+// key may have, and whether the keys of a file all start with its name, as
+// `buttons.*` in `buttons.json`.
+pub struct KeyNamingConfig {
+    pub case: Option<KeyCase>,
+    pub pattern: Option<Regex>,
+    pub max_depth: Option<usize>,
+    pub file_namespace: bool,
+}
+
+impl KeyNamingConfig {
+    fn from_value(value: &Value) -> Result<KeyNamingConfig, String> {
+        let case = string(value, "key_naming.case")?
+            .map(|case| {
+                KeyCase::parse(&case).ok_or_else(|| {
+                    format!(
+                        "invalid value `{}` for `key_naming.case` (expected `camel`, `pascal`, `snake` or `kebab`)",
+                        case
+                    )
+                })
+            })
+            .transpose()?;
+        let pattern = string(value, "key_naming.pattern")?
+            .map(|pattern| {
+                Regex::new(&pattern).map_err(|err| format!("invalid `key_naming.pattern`: {}", err))
+            })
+            .transpose()?;
+        let max_depth = match value.get("max_depth") {
+            None => None,
+            Some(depth) => Some(
+                depth
+                    .as_u64()
+                    .filter(|depth| *depth > 0)
+                    .ok_or("`key_naming.max_depth` must be a positive integer")?
+                    as usize,
+            ),
+        };
+        let file_namespace = match value.get("file_namespace") {
+            None => false,
+            Some(enabled) => enabled
+                .as_bool()
+                .ok_or("`key_naming.file_namespace` must be a boolean")?,
+        };
+        if case.is_none() && pattern.is_none() && max_depth.is_none() && !file_namespace {
+            return Err(
+                "`key_naming` needs a `case`, `pattern`, `max_depth` or `file_namespace`"
+                    .to_string(),
+            );
+        }
+        Ok(KeyNamingConfig {
+            case,
+            pattern,
+            max_depth,
+            file_namespace,
+        })
+    }
+}
+
+// The translation files a route loads, as file names relative to the
+// language folder, for the sources whose path relative to the source
+// folder matches `sources`.
+pub struct Route {
+    pub sources: Pattern,
+    pub files: BTreeSet<String>,
+}
+
+// How the values of the keys matching `keys` are written: their
+// capitalization in `langs` (every language when empty), ignoring the words
+// of `acronyms`, and a regex per language they must match.
+pub struct StyleRule {
+    pub keys: KeyPattern,
+    pub capitalization: Option<Capitalization>,
+    pub langs: Vec<String>,
+    pub acronyms: Vec<String>,
+    pub patterns: BTreeMap<String, Regex>,
+    pub severity: Severity,
+}
+
+// Which scripts each language is expected to be written in, on top of the
+// built-in defaults, and the share of letters from other scripts above which
+// a value is reported.
+pub struct ScriptsConfig {
+    pub threshold: f64,
+    pub expected: BTreeMap<String, Vec<Script>>,
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        ScriptsConfig {
+            threshold: 0.5,
+            expected: BTreeMap::new(),
+        }
+    }
+}
+
+// Keys whose values must stay identical in every language. Members may use
+// wildcards, as long as they all use the same number of them.
+pub struct LinkedKeys {
+    pub members: Vec<KeyPattern>,
+}
+
+// Notifications sent when a run differs from the state file.
+pub struct HooksConfig {
+    pub timeout: Duration,
+    pub hooks: Vec<Hook>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HookEvent {
+    NewBaseKeys,
+    DeletedKeys,
+    CoverageDropped,
+}
+
+pub enum HookTarget {
+    // Run through the shell with the NDJSON payload on stdin.
+    Command(String),
+    // Receives the NDJSON payload as the body of a POST request.
+    Url(String),
+}
+
+pub struct Hook {
+    pub event: HookEvent,
+    pub target: HookTarget,
+    // Coverage percentage below which `coverage-dropped-below-threshold`
+    // fires.
+    pub threshold: Option<f64>,
+}
+
+impl HookEvent {
+    pub const ALL: [HookEvent; 3] = [
+        HookEvent::NewBaseKeys,
+        HookEvent::DeletedKeys,
+        HookEvent::CoverageDropped,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::NewBaseKeys => "new-base-keys",
+            HookEvent::DeletedKeys => "deleted-keys",
+            HookEvent::CoverageDropped => "coverage-dropped-below-threshold",
+        }
+    }
+}
+
+// How to read the list of bundled translation files out of the
+// application's i18n loader configuration.
+pub struct LoaderConfig {
+    pub path: Option<PathBuf>,
+    pub pattern: Regex,
+}
+
+impl Config {
+    // Reads the given config file, or `translation-check.toml` in the
+    // working directory when it exists.
+    pub fn discover(explicit: Option<&Path>) -> Result<Config, String> {
+        match explicit {
+            Some(path) => Config::load(path),
+            None if Path::new(CONFIG_FILE).exists() => Config::load(Path::new(CONFIG_FILE)),
+            None if Path::new(JSON_CONFIG_FILE).exists() => {
+                Config::load(Path::new(JSON_CONFIG_FILE))
+            }
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read config {}: {}", path.display(), err))?;
+        let value = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&source).map_err(|err| err.to_string())
+        } else {
+            toml::parse(&source)
+        }
+        .map_err(|err| format!("invalid config {}: {}", path.display(), err))?;
+        let mut config = Config::from_value(&value)
+            .map_err(|err| format!("invalid config {}: {}", path.display(), err))?;
+        config.source = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    fn from_value(value: &Value) -> Result<Config, String> {
+        let project = value
+            .get("project")
+            .map(ProjectConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+        let severities = match value.get("severity") {
+            None => BTreeMap::new(),
+            Some(Value::Object(table)) => table
+                .iter()
+                .map(|(id, severity)| {
+                    let check = Check::from_id(id)
+                        .ok_or_else(|| format!("unknown check `{}` in `severity`", id))?;
+                    let severity = match severity.as_str() {
+                        Some("off") => None,
+                        Some("warn") => Some(Severity::Warning),
+                        name => Some(name.and_then(Severity::from_name).ok_or_else(|| {
+                            format!(
+                                "invalid value for `severity.{}` (expected `info`, `warning`, `error` or `off`)",
+                                id
+                            )
+                        })?),
+                    };
+                    Ok((check, severity))
+                })
+                .collect::<Result<_, String>>()?,
+            Some(_) => return Err("`severity` must be a table".to_string()),
+        };
+
+        let loader_config = value
+            .get("loader_config")
+            .map(LoaderConfig::from_value)
+            .transpose()?;
+
+        let linked_keys = match value.get("linked_keys") {
+            None => Vec::new(),
+            Some(Value::Array(groups)) => groups
+                .iter()
+                .map(LinkedKeys::from_value)
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`linked_keys` must be an array of tables".to_string()),
+        };
+
+        // A trailing dot, as in `dev.`, names the namespace itself.
+        let base_only_namespaces = strings(value, "base_only_namespaces")?
+            .iter()
+            .map(|namespace| KeyPattern::new(namespace.strip_suffix('.').unwrap_or(namespace)))
+            .collect::<Result<_, _>>()?;
+
+        let linkable_keys = match value.get("linkable_keys") {
+            None => None,
+            Some(_) => Some(
+                strings(value, "linkable_keys")?
+                    .iter()
+                    .map(|key| KeyPattern::new(key))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        let hooks = value
+            .get("hooks")
+            .map(HooksConfig::from_value)
+            .transpose()?;
+
+        let scripts = value
+            .get("scripts")
+            .map(ScriptsConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let key_constants = value
+            .get("key_constants")
+            .map(KeyConstantsConfig::from_value)
+            .transpose()?;
+
+        let e2e = value
+            .get("e2e")
+            .map(E2eConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let key_naming = value
+            .get("key_naming")
+            .map(KeyNamingConfig::from_value)
+            .transpose()?;
+
+        let exclude = value
+            .get("exclude")
+            .map(ExcludeConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let context = value
+            .get("context")
+            .map(ContextConfig::from_value)
+            .transpose()?;
+
+        let bundle = value
+            .get("bundle")
+            .map(BundleConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let sibling_duplicates = value
+            .get("sibling_duplicates")
+            .map(SiblingDuplicatesConfig::from_value)
+            .transpose()?;
+
+        let plural_forms = value
+            .get("plural_forms")
+            .map(PluralFormsConfig::from_value)
+            .transpose()?;
+
+        let placeholder_position = match value.get("placeholder_position") {
+            None => false,
+            Some(section) => match section.get("enabled") {
+                None => true,
+                Some(enabled) => enabled
+                    .as_bool()
+                    .ok_or("`placeholder_position.enabled` must be a boolean")?,
+            },
+        };
+
+        let icu = match value.get("icu") {
+            None => false,
+            Some(section) => match section.get("enabled") {
+                None => true,
+                Some(enabled) => enabled.as_bool().ok_or("`icu.enabled` must be a boolean")?,
+            },
+        };
+
+        let file_format = value
+            .get("file_format")
+            .map(FileFormatConfig::from_value)
+            .transpose()?;
+
+        let suppressions = value
+            .get("suppressions")
+            .map(SuppressionsConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let reserved_placeholders = match value.get("reserved_placeholders") {
+            None => Reserved::new(),
+            Some(table @ Value::Object(names)) => names
+                .iter()
+                .map(|(name, rules)| {
+                    let setting = format!("reserved_placeholders.{}", name);
+                    let rules = match rules {
+                        Value::String(rule) => vec![rule.clone()],
+                        _ => strings(table, &setting)?,
+                    };
+                    let rules = rules
+                        .iter()
+                        .map(|rule| {
+                            Rule::parse(rule).ok_or_else(|| {
+                                format!(
+                                    "invalid rule `{}` for `{}` (expected `forbidden`, `must-match-base` or `numeric-only`)",
+                                    rule, setting
+                                )
+                            })
+                        })
+                        .collect::<Result<_, _>>()?;
+                    Ok((name.clone(), rules))
+                })
+                .collect::<Result<_, String>>()?,
+            Some(_) => return Err("`reserved_placeholders` must be a table".into()),
+        };
+
+        let unreadable_files = value
+            .get("unreadable_files")
+            .map(UnreadableFilesConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let untranslated = value
+            .get("untranslated")
+            .map(UntranslatedConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let usage = value
+            .get("usage")
+            .map(UsageConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let sync_keys = value
+            .get("sync_keys")
+            .map(SyncKeysConfig::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        let file_name_rules = match value.get("file_names") {
+            Some(file_names) if file_names.get("strip").is_some() => {
+                strings(file_names, "file_names.strip")?
+                    .iter()
+                    .map(|name| {
+                        FileNameRule::parse(name).ok_or_else(|| {
+                            format!(
+                                "invalid rule `{}` for `file_names.strip` (expected `lang-prefix` or `lang-suffix`)",
+                                name
+                            )
+                        })
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            _ => Vec::new(),
+        };
+
+        let expected_languages = match value.get("languages") {
+            Some(languages) if languages.get("expected").is_some() => {
+                Some(strings(languages, "languages.expected")?)
+            }
+            _ => None,
+        };
+        let base_lang = match value.get("languages") {
+            Some(languages) => string(languages, "languages.base")?
+                .map(|lang| parse_lang(&lang, "languages.base"))
+                .transpose()?,
+            None => None,
+        };
+        let language_aliases = match value.get("languages") {
+            Some(languages) => language_aliases(languages)?,
+            None => BTreeMap::new(),
+        };
+        let non_language_dirs = match value.get("languages") {
+            Some(languages) => strings(languages, "languages.non_language_dirs")?
+                .into_iter()
+                .map(|dir| dir.trim_end_matches('/').to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let worklist_sections = match value.get("worklist") {
+            None => None,
+            Some(worklist) if worklist.get("sections").is_none() => None,
+            Some(worklist) => Some(
+                strings(worklist, "worklist.sections")?
+                    .iter()
+                    .map(|name| parse_section(name, "`worklist.sections`"))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        let routes = match value.get("routes") {
+            None => Vec::new(),
+            Some(Value::Object(routes)) => routes
+                .iter()
+                .map(|(sources, files)| Route::from_value(sources, files))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`routes` must be a table".to_string()),
+        };
+
+        let styles = match value.get("style") {
+            None => Vec::new(),
+            Some(Value::Object(styles)) => styles
+                .iter()
+                .map(|(keys, style)| StyleRule::from_value(keys, style))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`style` must be a table".to_string()),
+        };
+
+        let ignores = match value.get("ignore") {
+            None => Vec::new(),
+            Some(Value::Array(ignores)) => ignores
+                .iter()
+                .enumerate()
+                .map(|(index, ignore)| Waiver::from_value(ignore, "ignore", index))
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`ignore` must be an array of tables".to_string()),
+        };
+
+        Ok(Config {
+            source: None,
+            project,
+            severities,
+            loader_config,
+            linked_keys,
+            base_only_namespaces,
+            linkable_keys,
+            hooks,
+            scripts,
+            key_constants,
+            e2e,
+            exclude,
+            key_naming,
+            context,
+            bundle,
+            sibling_duplicates,
+            plural_forms,
+            placeholder_position,
+            icu,
+            file_format,
+            suppressions,
+            reserved_placeholders,
+            unreadable_files,
+            untranslated,
+            usage,
+            sync_keys,
+            file_name_rules,
+            expected_languages,
+            base_lang,
+            language_aliases,
+            non_language_dirs,
+            worklist_sections,
+            routes,
+            styles,
+            ignores,
+        })
+    }
+}
+
+impl LoaderConfig {
+    fn from_value(value: &Value) -> Result<LoaderConfig, String> {
+        let pattern =
+            string(value, "loader_config.pattern")?.ok_or("`loader_config.pattern` is required")?;
+        let pattern = Regex::new(&pattern)
+            .map_err(|err| format!("invalid `loader_config.pattern`: {}", err))?;
+        if !pattern.capture_names().any(|name| name == Some("file")) {
+            return Err("`loader_config.pattern` needs a `file` capture group".to_string());
+        }
+
+        Ok(LoaderConfig {
+            path: string(value, "loader_config.path")?.map(PathBuf::from),
+            pattern,
+        })
+    }
+}
+
+impl KeyConstantsConfig {
+    fn from_value(value: &Value) -> Result<KeyConstantsConfig, String> {
+        let files = strings(value, "key_constants.files")?
+            .iter()
+            .map(|file| {
+                Pattern::new(file).map_err(|err| {
+                    format!("invalid `key_constants.files` glob `{}`: {}", file, err)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if files.is_empty() {
+            return Err("`key_constants.files` needs at least one glob".to_string());
+        }
+        Ok(KeyConstantsConfig { files })
+    }
+}
+
+impl Route {
+    fn from_value(sources: &str, files: &Value) -> Result<Route, String> {
+        let name = format!("routes.\"{}\"", sources);
+        let sources = Pattern::new(sources)
+            .map_err(|err| format!("invalid `routes` glob `{}`: {}", sources, err))?;
+        let files = match files {
+            Value::Array(files) => files
+                .iter()
+                .map(|file| file.as_str().map(str::to_string))
+                .collect::<Option<_>>(),
+            _ => None,
+        }
+        .ok_or_else(|| format!("`{}` must be an array of file names", name))?;
+        Ok(Route { sources, files })
+    }
+}
+
+impl StyleRule {
+    fn from_value(keys: &str, value: &Value) -> Result<StyleRule, String> {
+        let name = format!("style.\"{}\"", keys);
+        if !value.is_object() {
+            return Err(format!("`{}` must be a table", name));
+        }
+        let capitalization = string(value, &format!("{}.capitalization", name))?
+            .map(|capitalization| {
+                Capitalization::parse(&capitalization).ok_or_else(|| {
+                    format!(
+                        "invalid value `{}` for `{}.capitalization` (expected `sentence`, `title` or `lower`)",
+                        capitalization, name
+                    )
+                })
+            })
+            .transpose()?;
+        let patterns = match value.get("patterns") {
+            None => BTreeMap::new(),
+            Some(Value::Object(patterns)) => patterns
+                .iter()
+                .map(|(lang, pattern)| {
+                    let setting = format!("{}.patterns.{}", name, lang);
+                    let pattern = pattern
+                        .as_str()
+                        .ok_or_else(|| format!("`{}` must be a string", setting))?;
+                    let pattern = Regex::new(pattern)
+                        .map_err(|err| format!("invalid `{}`: {}", setting, err))?;
+                    Ok((lang.clone(), pattern))
+                })
+                .collect::<Result<_, String>>()?,
+            Some(_) => return Err(format!("`{}.patterns` must be a table", name)),
+        };
+        if capitalization.is_none() && patterns.is_empty() {
+            return Err(format!("`{}` needs a `capitalization` or `patterns`", name));
+        }
+        let severity = match string(value, &format!("{}.severity", name))?.as_deref() {
+            None | Some("info") => Severity::Info,
+            Some("warning") => Severity::Warning,
+            Some("error") => Severity::Error,
+            Some(other) => {
+                return Err(format!(
+                    "invalid value `{}` for `{}.severity` (expected `info`, `warning` or `error`)",
+                    other, name
+                ));
+            }
+        };
+
+        Ok(StyleRule {
+            keys: KeyPattern::new(keys)?,
+            capitalization,
+            langs: strings(value, &format!("{}.langs", name))?,
+            acronyms: strings(value, &format!("{}.acronyms", name))?,
+            patterns,
+            severity,
+        })
+    }
+}
+
+impl ScriptsConfig {
+    fn from_value(value: &Value) -> Result<ScriptsConfig, String> {
+        let mut config = ScriptsConfig::default();
+        if let Some(threshold) = value.get("threshold") {
+            config.threshold = threshold
+                .as_f64()
+                .filter(|threshold| (0.0..=1.0).contains(threshold))
+                .ok_or("`scripts.threshold` must be a number between 0 and 1")?;
+        }
+
+        let Some(expected) = value.get("expected") else {
+            return Ok(config);
+        };
+        let langs = expected
+            .as_object()
+            .ok_or("`scripts.expected` must be a table")?;
+        for lang in langs.keys() {
+            let scripts = strings(expected, &format!("scripts.expected.{}", lang))?
+                .iter()
+                .map(|name| {
+                    Script::from_name(name).ok_or_else(|| {
+                        format!("unknown script `{}` for `scripts.expected.{}`", name, lang)
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            config.expected.insert(lang.clone(), scripts);
+        }
+        Ok(config)
+    }
+}
+
+impl HooksConfig {
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn from_value(value: &Value) -> Result<HooksConfig, String> {
+        let table = value.as_object().ok_or("`hooks` must be a table")?;
+        let timeout = match table.get("timeout") {
+            None => HooksConfig::DEFAULT_TIMEOUT,
+            Some(timeout) => Duration::from_secs(
+                timeout
+                    .as_u64()
+                    .ok_or("`hooks.timeout` must be a number of seconds")?,
+            ),
+        };
+
+        let mut hooks = Vec::new();
+        for (name, hook) in table.iter().filter(|(name, _)| *name != "timeout") {
+            let event = HookEvent::ALL
+                .into_iter()
+                .find(|event| event.name() == name)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown hook event `{}` (expected `new-base-keys`, `deleted-keys` or `coverage-dropped-below-threshold`)",
+                        name
+                    )
+                })?;
+            hooks.push(Hook::from_value(event, hook)?);
+        }
+
+        Ok(HooksConfig { timeout, hooks })
+    }
+}
+
+impl Hook {
+    fn from_value(event: HookEvent, value: &Value) -> Result<Hook, String> {
+        let name = format!("hooks.{}", event.name());
+        let command = string(value, &format!("{}.command", name))?;
+        let url = string(value, &format!("{}.url", name))?;
+        let target = match (command, url) {
+            (Some(command), None) => HookTarget::Command(command),
+            (None, Some(url)) => HookTarget::Url(url),
+            _ => return Err(format!("`{}` needs either a `command` or a `url`", name)),
+        };
+
+        let threshold = match value.get("threshold") {
+            None => None,
+            Some(threshold) => Some(
+                threshold
+                    .as_f64()
+                    .ok_or_else(|| format!("`{}.threshold` must be a number", name))?,
+            ),
+        };
+        if event == HookEvent::CoverageDropped && threshold.is_none() {
+            return Err(format!("`{}` needs a `threshold`", name));
+        }
+
+        Ok(Hook {
+            event,
+            target,
+            threshold,
+        })
+    }
+}
+
+impl LinkedKeys {
+    fn from_value(value: &Value) -> Result<LinkedKeys, String> {
+        let members = strings(value, "linked_keys.keys")?
+            .iter()
+            .map(|key| KeyPattern::new(key))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if members.len() < 2 {
+            return Err("`linked_keys.keys` needs at least two keys".to_string());
+        }
+        if members
+            .iter()
+            .any(|member| member.wildcards() != members[0].wildcards())
+        {
+            return Err(format!(
+                "linked keys `{}` must all use the same number of wildcards",
+                members
+                    .iter()
+                    .map(|member| member.as_str())
+                    .collect::<Vec<_>>()
+                    .join("`, `")
+            ));
+        }
+
+        Ok(LinkedKeys { members })
+    }
+}
+
+// `aliases = { "zh-CN" = "zh-Hans" }`, from each alias to the language it
+// is read as, which can't itself be an alias.
+fn language_aliases(languages: &Value) -> Result<BTreeMap<String, String>, String> {
+    let aliases = match languages.get("aliases") {
+        None => return Ok(BTreeMap::new()),
+        Some(Value::Object(aliases)) => aliases,
+        Some(_) => return Err("`languages.aliases` must be a table".to_string()),
+    };
+    let mut parsed = BTreeMap::new();
+    for (alias, lang) in aliases {
+        let lang = lang.as_str().ok_or_else(|| {
+            format!(
+                "`languages.aliases.\"{}\"` must be the language it stands for",
+                alias
+            )
+        })?;
+        if lang == alias || aliases.contains_key(lang) {
+            return Err(format!(
+                "`languages.aliases.\"{}\"` must name a language that isn't an alias itself",
+                alias
+            ));
+        }
+        parsed.insert(alias.clone(), lang.to_string());
+    }
+    Ok(parsed)
+}
+
+fn strings(table: &Value, name: &str) -> Result<Vec<String>, String> {
+    let key = name.rsplit('.').next().unwrap();
+    match table.get(key) {
+        None => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("`{}` must be an array of strings", name))
+            })
+            .collect(),
+        Some(_) => Err(format!("`{}` must be an array of strings", name)),
+    }
+}
+
+fn string(table: &Value, name: &str) -> Result<Option<String>, String> {
+    let key = name.rsplit('.').next().unwrap();
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(format!("`{}` must be a string", name)),
+    }
+}
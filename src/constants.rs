@@ -0,0 +1,173 @@
+use crate::handles;
+use aho_corasick::AhoCorasick;
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    // `export const CHECKOUT_TITLE = 'checkout.title'`, optionally typed.
+    static ref CONST_REGEX: Regex = Regex::new(
+        r#"(?m)^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=\n]+)?=\s*(['"`])([^'"`\n]+)['"`]"#
+    )
+    .unwrap();
+    static ref ENUM_REGEX: Regex = Regex::new(r"(?:export\s+)?(?:const\s+)?enum\s+[\w$]+\s*\{([^}]*)}").unwrap();
+    // `CHECKOUT_TITLE = 'checkout.title'` inside an enum body.
+    static ref MEMBER_REGEX: Regex =
+        Regex::new(r#"([A-Za-z_$][\w$]*)\s*=\s*(['"])([^'"\n]+)['"]"#).unwrap();
+    static ref DECLARATION_REGEX: Regex =
+        Regex::new(r"\b(?:const|let|var|function|class)\s+([A-Za-z_$][\w$]*)").unwrap();
+    static ref IMPORT_REGEX: Regex =
+        Regex::new(r#"import\s+(?:type\s+)?\{[^}]*}\s*from\s*['"][^'"]+['"]"#).unwrap();
+}
+
+// A key bound to an identifier in a generated constants file.
+#[derive(Debug, Clone)]
+pub struct KeyConstant {
+    pub name: String,
+    pub key: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl KeyConstant {
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file.display(), self.line)
+    }
+}
+
+// Which keys are used through their constants, and by which files.
+#[derive(Default)]
+pub struct Resolution {
+    pub usages: BTreeMap<String, BTreeSet<PathBuf>>,
+    // Constants never referenced outside the definition files.
+    pub unused: Vec<KeyConstant>,
+    pub warnings: Vec<String>,
+}
+
+pub fn parse_constants(path: &Path, content: &str) -> Vec<KeyConstant> {
+    let constant = |name: &str, key: &str, offset: usize| KeyConstant {
+        name: name.to_string(),
+        key: key.to_string(),
+        file: path.to_path_buf(),
+        line: content[..offset].matches('\n').count() + 1,
+    };
+
+    let mut constants: Vec<KeyConstant> = CONST_REGEX
+        .captures_iter(content)
+        .filter(|caps| &caps[2] != "`" || !caps[3].contains("${"))
+        .map(|caps| constant(&caps[1], &caps[3], caps.get(1).unwrap().start()))
+        .collect();
+    for body in ENUM_REGEX.captures_iter(content) {
+        let body = body.get(1).unwrap();
+        for caps in MEMBER_REGEX.captures_iter(body.as_str()) {
+            let offset = body.start() + caps.get(1).unwrap().start();
+            constants.push(constant(&caps[1], &caps[3], offset));
+        }
+    }
+    constants.sort_by_key(|constant| constant.line);
+    constants
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+}
+
+// Resolves key usages through the constants declared in `definitions`: a key
+// counts as used by every other file referencing one of its constants, and
+// the definition files themselves never count as using anything. Files that
+// declare their own binding with the name of a constant are not counted for
+// that constant.
+pub fn resolve(definitions: &[PathBuf], sources: &[PathBuf]) -> Resolution {
+    let mut resolution = Resolution::default();
+
+    let mut by_name: BTreeMap<String, Vec<KeyConstant>> = BTreeMap::new();
+    for file in definitions {
+        let Ok(content) = handles::read_to_string(file) else {
+            continue;
+        };
+        for constant in parse_constants(file, &content) {
+            by_name
+                .entry(constant.name.clone())
+                .or_default()
+                .push(constant);
+        }
+    }
+    for (name, constants) in by_name.iter().filter(|(_, constants)| constants.len() > 1) {
+        resolution.warnings.push(format!(
+            "key constant `{}` is defined more than once ({})",
+            name,
+            constants
+                .iter()
+                .map(|constant| format!("{} -> {}", constant.location(), constant.key))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    if by_name.is_empty() {
+        return resolution;
+    }
+
+    let names: Vec<&String> = by_name.keys().collect();
+    let matcher = AhoCorasick::new(&names).expect("Failed to build constant matcher");
+
+    let references: Vec<(PathBuf, HashSet<usize>, Vec<usize>)> = sources
+        .par_iter()
+        .filter_map(|file| {
+            let content = handles::read_to_string(file).ok()?;
+            let content = IMPORT_REGEX.replace_all(&content, "");
+            let bytes = content.as_bytes();
+
+            let mut found = HashSet::new();
+            for hit in matcher.find_overlapping_iter(bytes) {
+                let before = hit.start().checked_sub(1).map(|i| bytes[i]);
+                let after = bytes.get(hit.end()).copied();
+                if !before.is_some_and(is_identifier_byte) && !after.is_some_and(is_identifier_byte)
+                {
+                    found.insert(hit.pattern().as_usize());
+                }
+            }
+
+            let declared: HashSet<&str> = DECLARATION_REGEX
+                .captures_iter(&content)
+                .map(|caps| caps.get(1).unwrap().as_str())
+                .collect();
+            let shadowed: Vec<usize> = found
+                .iter()
+                .copied()
+                .filter(|index| declared.contains(names[*index].as_str()))
+                .collect();
+            Some((file.clone(), found, shadowed))
+        })
+        .collect();
+
+    let mut referenced: HashMap<usize, usize> = HashMap::new();
+    for (file, found, shadowed) in references {
+        for index in &shadowed {
+            resolution.warnings.push(format!(
+                "key constant `{}` is shadowed by a local declaration in {}",
+                names[*index],
+                file.display()
+            ));
+        }
+        for index in found.into_iter().filter(|index| !shadowed.contains(index)) {
+            *referenced.entry(index).or_default() += 1;
+            for constant in &by_name[names[index]] {
+                resolution
+                    .usages
+                    .entry(constant.key.clone())
+                    .or_default()
+                    .insert(file.clone());
+            }
+        }
+    }
+
+    for (index, name) in names.iter().enumerate() {
+        if !referenced.contains_key(&index) {
+            resolution.unused.extend(by_name[*name].iter().cloned());
+        }
+    }
+    resolution.warnings.sort();
+    resolution
+}
@@ -0,0 +1,257 @@
+use crate::model::Project;
+use crate::report::{Check, Finding, Severity};
+use crate::selector::KeyPattern;
+use crate::toml;
+use regex::{Regex, RegexSet};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+// Words a language must never use, matched as whole words whatever their
+// case, except in the allowed keys.
+pub struct ForbiddenWords {
+    pub name: String,
+    // Every language when empty.
+    pub langs: Vec<String>,
+    pub words: Vec<String>,
+    pub allow: Vec<KeyPattern>,
+    pub severity: Severity,
+}
+
+// A phrase the values of the matching keys must contain verbatim.
+pub struct RequiredPhrase {
+    pub name: String,
+    pub langs: Vec<String>,
+    pub keys: Vec<KeyPattern>,
+    pub phrase: String,
+    pub severity: Severity,
+}
+
+// The rules of a `--content-rules` file.
+pub struct ContentRules {
+    pub source: String,
+    pub forbidden: Vec<ForbiddenWords>,
+    pub required: Vec<RequiredPhrase>,
+}
+
+fn applies(langs: &[String], lang: &str) -> bool {
+    langs.is_empty() || langs.iter().any(|applied| applied == lang)
+}
+
+// The fields every rule has, for the rule `kind` #`index`.
+fn common(
+    value: &Value,
+    kind: &str,
+    index: usize,
+) -> Result<(String, Vec<String>, Severity), String> {
+    let name = match value.get("name") {
+        Some(Value::String(name)) => name.clone(),
+        Some(_) => {
+            return Err(format!(
+                "`name` of {} #{} must be a string",
+                kind,
+                index + 1
+            ));
+        }
+        None => return Err(format!("{} #{} needs a `name`", kind, index + 1)),
+    };
+    let langs = match value.get("langs") {
+        None => Vec::new(),
+        Some(Value::Array(langs)) => langs
+            .iter()
+            .map(|lang| lang.as_str().map(str::to_string))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("`langs` of {} `{}` must be an array of strings", kind, name))?,
+        Some(_) => {
+            return Err(format!(
+                "`langs` of {} `{}` must be an array of strings",
+                kind, name
+            ));
+        }
+    };
+    let severity = match value.get("severity") {
+        None => Severity::Error,
+        Some(Value::String(severity)) => Severity::from_name(severity).ok_or_else(|| {
+            format!(
+                "invalid severity `{}` for {} `{}` (expected `error`, `warning` or `info`)",
+                severity, kind, name
+            )
+        })?,
+        Some(_) => {
+            return Err(format!(
+                "`severity` of {} `{}` must be a string",
+                kind, name
+            ));
+        }
+    };
+    Ok((name, langs, severity))
+}
+
+fn strings(value: &Value, field: &str, kind: &str, name: &str) -> Result<Vec<String>, String> {
+    match value.get(field) {
+        None => Ok(Vec::new()),
+        Some(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect::<Option<_>>()
+            .ok_or_else(|| {
+                format!(
+                    "`{}` of {} `{}` must be an array of strings",
+                    field, kind, name
+                )
+            }),
+        Some(_) => Err(format!(
+            "`{}` of {} `{}` must be an array of strings",
+            field, kind, name
+        )),
+    }
+}
+
+fn patterns(value: &Value, field: &str, kind: &str, name: &str) -> Result<Vec<KeyPattern>, String> {
+    strings(value, field, kind, name)?
+        .iter()
+        .map(|key| KeyPattern::new(key).map_err(|err| format!("{} of {} `{}`", err, kind, name)))
+        .collect()
+}
+
+fn tables<'v>(value: &'v Value, kind: &str) -> Result<&'v [Value], String> {
+    match value.get(kind) {
+        None => Ok(&[]),
+        Some(Value::Array(tables)) => Ok(tables),
+        Some(_) => Err(format!("`{}` must be an array of tables", kind)),
+    }
+}
+
+impl ContentRules {
+    pub fn load(path: &Path) -> Result<ContentRules, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read content rules {}: {}", path.display(), err))?;
+        ContentRules::parse(&source)
+            .map_err(|err| format!("invalid content rules {}: {}", path.display(), err))
+            .map(|mut rules| {
+                rules.source = path.display().to_string();
+                rules
+            })
+    }
+
+    fn parse(source: &str) -> Result<ContentRules, String> {
+        let value = toml::parse(source)?;
+
+        let mut forbidden = Vec::new();
+        for (index, rule) in tables(&value, "forbidden")?.iter().enumerate() {
+            let (name, langs, severity) = common(rule, "forbidden", index)?;
+            let words = strings(rule, "words", "forbidden", &name)?;
+            if words.is_empty() || words.iter().any(|word| word.trim().is_empty()) {
+                return Err(format!("forbidden `{}` needs non-empty `words`", name));
+            }
+            forbidden.push(ForbiddenWords {
+                allow: patterns(rule, "allow", "forbidden", &name)?,
+                name,
+                langs,
+                words,
+                severity,
+            });
+        }
+
+        let mut required = Vec::new();
+        for (index, rule) in tables(&value, "required")?.iter().enumerate() {
+            let (name, langs, severity) = common(rule, "required", index)?;
+            let keys = patterns(rule, "keys", "required", &name)?;
+            if keys.is_empty() {
+                return Err(format!("required `{}` needs `keys`", name));
+            }
+            let phrase = match rule.get("phrase") {
+                Some(Value::String(phrase)) if !phrase.is_empty() => phrase.clone(),
+                _ => return Err(format!("required `{}` needs a `phrase`", name)),
+            };
+            required.push(RequiredPhrase {
+                name,
+                langs,
+                keys,
+                phrase,
+                severity,
+            });
+        }
+
+        Ok(ContentRules {
+            source: String::new(),
+            forbidden,
+            required,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forbidden.is_empty() && self.required.is_empty()
+    }
+
+    // Reports the values using a forbidden word and the values of keys
+    // lacking their required phrase. The forbidden words that apply to a
+    // language are matched at once, so a value is scanned once however many
+    // words there are.
+    pub fn check(&self, project: &Project) -> Vec<Finding> {
+        project.flat_map_languages(|lang, data| {
+            let words: Vec<(&ForbiddenWords, &str)> = self
+                .forbidden
+                .iter()
+                .filter(|rule| applies(&rule.langs, lang))
+                .flat_map(|rule| rule.words.iter().map(move |word| (rule, word.as_str())))
+                .collect();
+            let sources: Vec<String> = words
+                .iter()
+                .map(|(_, word)| format!(r"(?i)\b{}\b", regex::escape(word)))
+                .collect();
+            let set = RegexSet::new(&sources).expect("escaped words");
+            let regexes: Vec<Regex> = sources
+                .iter()
+                .map(|source| Regex::new(source).expect("escaped word"))
+                .collect();
+            let required: Vec<&RequiredPhrase> = self
+                .required
+                .iter()
+                .filter(|rule| applies(&rule.langs, lang))
+                .collect();
+
+            let mut keys: Vec<&str> = data.keys().collect();
+            keys.sort();
+            let mut findings = Vec::new();
+            for key in keys {
+                let value = data.value(key).unwrap_or_default();
+                let file = data.file(key).unwrap_or_default();
+                for index in set.matches(value).iter() {
+                    let (rule, _) = words[index];
+                    if rule.allow.iter().any(|allowed| allowed.matches(key)) {
+                        continue;
+                    }
+                    let found = regexes[index]
+                        .find(value)
+                        .map_or("", |found| found.as_str());
+                    findings.push(
+                        Finding::new(Check::ForbiddenWord)
+                            .lang(lang)
+                            .key(key)
+                            .detail("Rule", &rule.name)
+                            .detail("Word", found)
+                            .file(file)
+                            .severity(rule.severity),
+                    );
+                }
+                for rule in &required {
+                    if rule.keys.iter().any(|keys| keys.matches(key))
+                        && !value.contains(&rule.phrase)
+                    {
+                        findings.push(
+                            Finding::new(Check::MissingRequiredPhrase)
+                                .lang(lang)
+                                .key(key)
+                                .detail("Rule", &rule.name)
+                                .detail("Phrase", &rule.phrase)
+                                .file(file)
+                                .severity(rule.severity),
+                        );
+                    }
+                }
+            }
+            findings
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use crate::report::{Check, Finding, Report};
+use crate::selector::KeyPattern;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The screenshots or design links of the keys one glob matches.
+pub struct Entry {
+    pub pattern: KeyPattern,
+    pub links: Vec<String>,
+}
+
+// A context manifest, as the design pipeline exports it: a JSON object
+// from key globs such as `checkout.*` to a link or an array of links.
+// Entries are kept in glob order.
+pub struct Manifest {
+    pub path: PathBuf,
+    pub entries: Vec<Entry>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Manifest, String> {
+        let source = fs::read_to_string(path).map_err(|err| {
+            format!(
+                "failed to read context manifest {}: {}",
+                path.display(),
+                err
+            )
+        })?;
+        let entries = parse(&source)
+            .map_err(|err| format!("invalid context manifest {}: {}", path.display(), err))?;
+        Ok(Manifest {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn link_count(&self) -> usize {
+        self.entries.iter().map(|entry| entry.links.len()).sum()
+    }
+
+    // The links of every entry matching `key`, each once, up to `max`.
+    pub fn links(&self, key: &str, max: usize) -> Vec<String> {
+        let mut links: Vec<String> = Vec::new();
+        for entry in self
+            .entries
+            .iter()
+            .filter(|entry| entry.pattern.matches(key))
+        {
+            for link in &entry.links {
+                if !links.contains(link) {
+                    links.push(link.clone());
+                }
+            }
+        }
+        links.truncate(max);
+        links
+    }
+
+    // Records the links of every key the report has findings for.
+    pub fn attach(&self, report: &mut Report, max: usize) {
+        for key in report
+            .findings
+            .iter()
+            .filter_map(|finding| finding.key.as_ref())
+        {
+            if !report.context.contains_key(key) {
+                let links = self.links(key, max);
+                if !links.is_empty() {
+                    report.context.insert(key.clone(), links);
+                }
+            }
+        }
+    }
+
+    // The entries no key of `keys` matches anymore, usually screens that
+    // were redesigned or keys that were renamed.
+    pub fn stale<'k>(&self, keys: impl IntoIterator<Item = &'k str>) -> Vec<Finding> {
+        let mut unmatched: HashSet<usize> = (0..self.entries.len()).collect();
+        for key in keys {
+            unmatched.retain(|index| !self.entries[*index].pattern.matches(key));
+            if unmatched.is_empty() {
+                break;
+            }
+        }
+        let mut unmatched: Vec<usize> = unmatched.into_iter().collect();
+        unmatched.sort();
+        unmatched
+            .into_iter()
+            .map(|index| {
+                let entry = &self.entries[index];
+                Finding::new(Check::StaleContext)
+                    .detail("Pattern", entry.pattern.as_str())
+                    .detail("Links", entry.links.len().to_string())
+                    .file(self.path.display().to_string())
+            })
+            .collect()
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Entry>, String> {
+    let Value::Object(entries) = serde_json::from_str(source).map_err(|err| err.to_string())?
+    else {
+        return Err("expected an object from key globs to links".to_string());
+    };
+    entries
+        .iter()
+        .map(|(glob, links)| {
+            let links = match links {
+                Value::String(link) => vec![link.clone()],
+                Value::Array(links) => links
+                    .iter()
+                    .map(|link| link.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| format!("`{}` must be a link or an array of links", glob))?,
+                _ => return Err(format!("`{}` must be a link or an array of links", glob)),
+            };
+            if links.is_empty() {
+                return Err(format!("`{}` has no links", glob));
+            }
+            if let Some(link) = links
+                .iter()
+                .find(|link| !link.starts_with("https://") && !link.starts_with("http://"))
+            {
+                return Err(format!(
+                    "`{}` links to `{}`, which isn't an http(s) URL",
+                    glob, link
+                ));
+            }
+            Ok(Entry {
+                pattern: KeyPattern::new(glob)?,
+                links,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+use serde_json::{Value, json};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+// `serve` keeps one process checking the project for every run of the
+// command line, the editor plugin and `--watch`: the translation files stay
+// flattened and the source files scanned between runs, so that a run only
+// reads again the files changed since. A run finding the socket delegates
+// to the server and prints what it answers; without a server, or with one
+// it can't use, it checks in-process.
+//
+// Each message is a 4-byte big-endian length followed by that many bytes
+// of JSON. A connection carries one run:
+//
+// - the client says `{ "version", "root" }`, its version and project root;
+// - the server answers `{ "accepted", "version", "message"? }`, refusing a
+//   client of another version or another project with a `message` saying
+//   why;
+// - the client sends `{ "args", "changed"? }`, the options of the check run
+//   and, for a `--watch` run, the files changed since the last one;
+// - the server answers `{ "code", "stdout", "stderr" }`, the exit code and
+//   what the run printed.
+//
+// Either side refuses the other's version: the messages of two versions
+// may not mean the same.
+
+// Where `serve` listens and check runs look for it, in the project root.
+pub const SOCKET_FILE: &str = ".translation-check.sock";
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// Larger messages are refused rather than read, whatever a peer claims to
+// send.
+const MAX_MESSAGE: usize = 256 * 1024 * 1024;
+
+// A check run delegated to the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub args: Vec<String>,
+    pub changed: Option<Vec<PathBuf>>,
+}
+
+// What a delegated run answers, to be printed by the client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reply {
+    pub code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub fn write_message(out: &mut impl Write, message: &Value) -> io::Result<()> {
+    let bytes = message.to_string().into_bytes();
+    let length = u32::try_from(bytes.len())
+        .ok()
+        .filter(|length| *length as usize <= MAX_MESSAGE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "message too large"))?;
+    out.write_all(&length.to_be_bytes())?;
+    out.write_all(&bytes)?;
+    out.flush()
+}
+
+pub fn read_message(input: &mut impl Read) -> io::Result<Value> {
+    let mut length = [0; 4];
+    input.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_MESSAGE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("message of {} bytes is too large", length),
+        ));
+    }
+    let mut bytes = vec![0; length];
+    input.read_exact(&mut bytes)?;
+    serde_json::from_slice(&bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+// The server's answer to a client of `version` checking `root`, the server
+// checking `served`.
+pub fn greet(version: &str, root: &str, served: &Path) -> Value {
+    let refusal = if version != VERSION {
+        Some(format!(
+            "this server runs version {} of check_translations, not {}; restart `check_translations serve` to use it",
+            VERSION, version
+        ))
+    } else if Path::new(root) != served {
+        Some(format!(
+            "this server checks {}, not {}",
+            served.display(),
+            root
+        ))
+    } else {
+        None
+    };
+    match refusal {
+        Some(message) => json!({ "accepted": false, "version": VERSION, "message": message }),
+        None => json!({ "accepted": true, "version": VERSION }),
+    }
+}
+
+fn request_of(message: &Value) -> Result<Request, String> {
+    let strings = |value: &Value| -> Option<Vec<String>> {
+        value
+            .as_array()?
+            .iter()
+            .map(|value| value.as_str().map(str::to_string))
+            .collect()
+    };
+    let args = strings(&message["args"]).ok_or("`args` must be an array of strings")?;
+    let changed = match &message["changed"] {
+        Value::Null => None,
+        changed => Some(
+            strings(changed)
+                .ok_or("`changed` must be an array of strings")?
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+        ),
+    };
+    Ok(Request { args, changed })
+}
+
+fn reply_of(message: &Value) -> Option<Reply> {
+    Some(Reply {
+        code: i32::try_from(message["code"].as_i64()?).ok()?,
+        stdout: message["stdout"].as_str()?.to_string(),
+        stderr: message["stderr"].as_str()?.to_string(),
+    })
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use std::fs;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::time::Duration;
+
+    const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    // Listens on `socket` for the runs of the project at `root` and answers
+    // each with `run`, one at a time, until the process ends. A socket file
+    // nothing listens on any more is left from an earlier server and
+    // replaced; one a server answers on is refused.
+    pub fn serve(
+        socket: &Path,
+        root: &Path,
+        mut run: impl FnMut(Request) -> Reply,
+    ) -> Result<(), String> {
+        if socket.exists() {
+            if UnixStream::connect(socket).is_ok() {
+                return Err(format!("a server already listens on {}", socket.display()));
+            }
+            fs::remove_file(socket)
+                .map_err(|err| format!("failed to remove {}: {}", socket.display(), err))?;
+        }
+        let listener = UnixListener::bind(socket)
+            .map_err(|err| format!("failed to listen on {}: {}", socket.display(), err))?;
+        for stream in listener.incoming() {
+            // A client gone halfway only loses its own run.
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let _ = answer(&mut stream, root, &mut run);
+        }
+        Ok(())
+    }
+
+    fn answer(
+        stream: &mut UnixStream,
+        root: &Path,
+        run: &mut impl FnMut(Request) -> Reply,
+    ) -> io::Result<()> {
+        // A client sending nothing would hold up the runs of the others.
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let hello = read_message(stream)?;
+        let greeting = greet(
+            hello["version"].as_str().unwrap_or("unknown"),
+            hello["root"].as_str().unwrap_or_default(),
+            root,
+        );
+        write_message(stream, &greeting)?;
+        if greeting["accepted"] != true {
+            return Ok(());
+        }
+        let reply = match request_of(&read_message(stream)?) {
+            Ok(request) => run(request),
+            Err(err) => Reply {
+                code: 2,
+                stdout: String::new(),
+                stderr: format!("invalid request: {}\n", err),
+            },
+        };
+        write_message(
+            stream,
+            &json!({ "code": reply.code, "stdout": reply.stdout, "stderr": reply.stderr }),
+        )
+    }
+
+    // Runs `request` on the server listening on `socket`, for the project
+    // at `root`. `None` when there is no server to ask: no socket file, or
+    // one left by a server that is gone. An error when the server refuses
+    // the run or doesn't answer it, for the caller to check in-process.
+    pub fn delegate(
+        socket: &Path,
+        root: &Path,
+        request: &Request,
+    ) -> Result<Option<Reply>, String> {
+        let Ok(mut stream) = UnixStream::connect(socket) else {
+            return Ok(None);
+        };
+        let lost = |err: io::Error| {
+            format!(
+                "lost the connection to the server on {}: {}",
+                socket.display(),
+                err
+            )
+        };
+        write_message(
+            &mut stream,
+            &json!({ "version": VERSION, "root": root.display().to_string() }),
+        )
+        .map_err(lost)?;
+        let greeting = read_message(&mut stream).map_err(lost)?;
+        let version = greeting["version"].as_str().unwrap_or("unknown");
+        if greeting["accepted"] != true {
+            return Err(format!(
+                "the server on {} refused the run: {}",
+                socket.display(),
+                greeting["message"].as_str().unwrap_or("no reason given")
+            ));
+        }
+        if version != VERSION {
+            return Err(format!(
+                "the server on {} runs version {} of check_translations, not {}; restart `check_translations serve` to use it",
+                socket.display(),
+                version,
+                VERSION
+            ));
+        }
+        let changed = request.changed.as_ref().map(|changed| {
+            changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<String>>()
+        });
+        write_message(
+            &mut stream,
+            &json!({ "args": request.args, "changed": changed }),
+        )
+        .map_err(lost)?;
+        let reply = read_message(&mut stream).map_err(lost)?;
+        reply_of(&reply)
+            .map(Some)
+            .ok_or_else(|| format!("the server on {} sent an invalid reply", socket.display()))
+    }
+}
+
+#[cfg(unix)]
+pub use unix::{delegate, serve};
+
+// Without unix sockets there is no server: every run checks in-process.
+#[cfg(not(unix))]
+pub fn serve(
+    _socket: &Path,
+    _root: &Path,
+    _run: impl FnMut(Request) -> Reply,
+) -> Result<(), String> {
+    Err("`serve` needs unix domain sockets, which this platform lacks".to_string())
+}
+
+#[cfg(not(unix))]
+pub fn delegate(_socket: &Path, _root: &Path, _request: &Request) -> Result<Option<Reply>, String> {
+    Ok(None)
+}
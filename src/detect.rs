@@ -0,0 +1,157 @@
+use crate::checks::placeholder_spans;
+use crate::model::Project;
+use std::cmp::Ordering;
+
+// How close the two best candidates may score before the base language is
+// left for the user to pick.
+pub const AMBIGUOUS_MARGIN: f64 = 0.05;
+
+// How much a language looks like the one the others are translated from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub lang: String,
+    pub keys: usize,
+    // The other languages whose keys this one all has.
+    pub supersets: usize,
+    // The share of values reading as text, rather than bare placeholders or
+    // keys copied in as values.
+    pub text: f64,
+    // The mean of the three, each scaled to [0, 1].
+    pub score: f64,
+}
+
+impl Candidate {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} (score {:.2}: {} keys, superset of {} languages, {:.0}% text)",
+            self.lang,
+            self.score,
+            self.keys,
+            self.supersets,
+            self.text * 100.0
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Detection {
+    Found(Candidate),
+    // The candidates scoring within the margin of the best one, best first.
+    Ambiguous(Vec<Candidate>),
+    NoLanguages,
+}
+
+// The language the others look translated from, when it isn't a close
+// call.
+pub fn base_language(project: &Project) -> Detection {
+    let mut candidates = candidates(project);
+    let Some(best) = candidates.first() else {
+        return Detection::NoLanguages;
+    };
+    let best_score = best.score;
+    if candidates
+        .get(1)
+        .is_some_and(|second| best_score - second.score < AMBIGUOUS_MARGIN)
+    {
+        candidates.retain(|candidate| best_score - candidate.score < AMBIGUOUS_MARGIN);
+        return Detection::Ambiguous(candidates);
+    }
+    Detection::Found(candidates.swap_remove(0))
+}
+
+// The best candidate when it scores clearly higher than `base`, the base
+// language of the run.
+pub fn rival(project: &Project, base: &str) -> Option<(Candidate, Candidate)> {
+    let mut candidates = candidates(project);
+    let base = candidates
+        .iter()
+        .position(|candidate| candidate.lang == base)?;
+    let base = candidates.remove(base);
+    let best = candidates.into_iter().next()?;
+    (best.score - base.score >= AMBIGUOUS_MARGIN).then_some((best, base))
+}
+
+// Scores every language of the project as a base, best first: the base
+// usually has the most keys, has every key the others have, and holds
+// written text where unfinished translations hold placeholders or copies of
+// the key.
+pub fn candidates(project: &Project) -> Vec<Candidate> {
+    project.load_all();
+    let languages: Vec<(&str, Vec<&str>)> = project
+        .languages()
+        .filter_map(|lang| {
+            let data = project.get(lang)?;
+            let keys = data.values.keys().map(|key| &**key).collect();
+            Some((lang, keys))
+        })
+        .collect();
+    let max_keys = languages
+        .iter()
+        .map(|(_, keys)| keys.len())
+        .max()
+        .unwrap_or(0);
+    let others = languages.len().saturating_sub(1);
+
+    let mut candidates: Vec<Candidate> = languages
+        .iter()
+        .map(|(lang, keys)| {
+            let data = project.get(lang).unwrap();
+            let supersets = languages
+                .iter()
+                .filter(|(other, other_keys)| {
+                    other != lang && other_keys.iter().all(|key| data.values.contains_key(*key))
+                })
+                .count();
+            let text = data
+                .values
+                .iter()
+                .filter(|(key, entry)| reads_as_text(key, &entry.value))
+                .count();
+            let text = if keys.is_empty() {
+                0.0
+            } else {
+                text as f64 / keys.len() as f64
+            };
+            let size = if max_keys == 0 {
+                0.0
+            } else {
+                keys.len() as f64 / max_keys as f64
+            };
+            let coverage = if others == 0 {
+                1.0
+            } else {
+                supersets as f64 / others as f64
+            };
+            Candidate {
+                lang: lang.to_string(),
+                keys: keys.len(),
+                supersets,
+                text,
+                score: (size + coverage + text) / 3.0,
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.lang.cmp(&b.lang))
+    });
+    candidates
+}
+
+// Letters are left once placeholders are taken out, and the value isn't the
+// key itself.
+fn reads_as_text(key: &str, value: &str) -> bool {
+    if value.trim() == key {
+        return false;
+    }
+    let mut end = 0;
+    for (range, _, _) in placeholder_spans(value) {
+        if value[end..range.start].chars().any(char::is_alphabetic) {
+            return true;
+        }
+        end = range.end;
+    }
+    value[end..].chars().any(char::is_alphabetic)
+}
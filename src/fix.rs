@@ -0,0 +1,392 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process;
+
+// Marks a `--backup-dir` folder as holding the copies of a previous fix, so
+// that the next one may replace them.
+const BACKUP_MARKER: &str = ".translation-check-backup";
+
+// What a fix did to one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Applied,
+    // Replaced, then restored from its backup after a later file failed.
+    RolledBack,
+    Untouched,
+    // Restoring it failed too: the file keeps the fix and its pre-fix copy
+    // stays at the given path.
+    RollbackFailed(PathBuf),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Applied => write!(f, "applied"),
+            Outcome::RolledBack => write!(f, "rolled back"),
+            Outcome::Untouched => write!(f, "untouched"),
+            Outcome::RollbackFailed(backup) => {
+                write!(f, "rollback failed, pre-fix copy at {}", backup.display())
+            }
+        }
+    }
+}
+
+// The outcome of a transaction, file by file, and the error that stopped it.
+#[derive(Debug)]
+pub struct FixReport {
+    pub files: Vec<(PathBuf, Outcome)>,
+    pub error: Option<String>,
+}
+
+impl FixReport {
+    pub fn applied(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|(_, outcome)| *outcome == Outcome::Applied)
+            .count()
+    }
+
+    // The state the tree is left in, for the error message of a failed fix.
+    pub fn state(&self) -> &'static str {
+        if self
+            .files
+            .iter()
+            .any(|(_, outcome)| matches!(outcome, Outcome::RollbackFailed(_)))
+        {
+            "the fix is partially applied, restore the files whose rollback failed from their copies"
+        } else {
+            "no translation file was changed"
+        }
+    }
+
+    pub fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        for (path, outcome) in &self.files {
+            writeln!(out, "   - {}: {}", outcome, path.display())?;
+        }
+        Ok(())
+    }
+}
+
+// The new contents of the files a fix rewrites, applied all or nothing:
+// every new content is written to a temporary file first, and only then
+// are they renamed into place one by one. When a rename fails, the files
+// already replaced are restored from the copies taken before.
+#[derive(Debug, Default)]
+pub struct Transaction {
+    changes: Vec<(PathBuf, String)>,
+}
+
+impl Transaction {
+    pub fn rewrite(&mut self, path: PathBuf, content: String) {
+        self.changes.push((path, content));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // Drops the rewrites of the `protected` files, those of the base
+    // language, unless `allowed`, and returns the files left alone. The base
+    // is what every language is compared against: a fix writing it by
+    // mistake spreads to all of them.
+    pub fn protect(&mut self, protected: &[PathBuf], allowed: bool) -> Vec<PathBuf> {
+        if allowed {
+            return Vec::new();
+        }
+        let absolute =
+            |path: &Path| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let protected: HashSet<PathBuf> = protected.iter().map(|path| absolute(path)).collect();
+        let (refused, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.changes)
+            .into_iter()
+            .partition(|(path, _)| protected.contains(&absolute(path)));
+        self.changes = kept;
+        refused.into_iter().map(|(path, _)| path).collect()
+    }
+
+    // `--dry-run`: the lines each file would change, as a unified diff
+    // without context. A line rewritten in place, as most fixes do, is shown
+    // on its own; lines added or removed are shown by run.
+    pub fn write_diff(&self, out: &mut impl Write) -> io::Result<()> {
+        for (path, content) in &self.changes {
+            let current = fs::read_to_string(path).unwrap_or_default();
+            let old: Vec<&str> = current.lines().collect();
+            let new: Vec<&str> = content.lines().collect();
+            writeln!(out, "--- {}", path.display())?;
+            writeln!(out, "+++ {}", path.display())?;
+            let mut common = common_lines(&old, &new);
+            common.push((old.len(), new.len()));
+            let (mut x, mut y) = (0, 0);
+            for (next_x, next_y) in common {
+                let (removed, added) = (&old[x..next_x], &new[y..next_y]);
+                if removed.len() == added.len() {
+                    for (index, (before, after)) in removed.iter().zip(added).enumerate() {
+                        writeln!(out, "@@ -{},1 +{},1 @@", x + index + 1, y + index + 1)?;
+                        writeln!(out, "-{}", before)?;
+                        writeln!(out, "+{}", after)?;
+                    }
+                } else {
+                    writeln!(
+                        out,
+                        "@@ -{},{} +{},{} @@",
+                        x + 1,
+                        removed.len(),
+                        y + 1,
+                        added.len()
+                    )?;
+                    for before in removed {
+                        writeln!(out, "-{}", before)?;
+                    }
+                    for after in added {
+                        writeln!(out, "+{}", after)?;
+                    }
+                }
+                (x, y) = (next_x + 1, next_y + 1);
+            }
+        }
+        Ok(())
+    }
+
+    // Applies the transaction. With `backup_dir`, the pre-fix copies are
+    // kept there, replacing those of the previous fix; otherwise they are
+    // removed once every file is replaced.
+    pub fn commit(&self, backup_dir: Option<&Path>) -> FixReport {
+        self.commit_with(backup_dir, |from, to| fs::rename(from, to))
+    }
+
+    // `commit` with the renames into place done by `rename`, which lets the
+    // tests inject a failure halfway.
+    pub fn commit_with(
+        &self,
+        backup_dir: Option<&Path>,
+        mut rename: impl FnMut(&Path, &Path) -> io::Result<()>,
+    ) -> FixReport {
+        let mut report = FixReport {
+            files: self
+                .changes
+                .iter()
+                .map(|(path, _)| (path.clone(), Outcome::Untouched))
+                .collect(),
+            error: None,
+        };
+        if self.changes.is_empty() {
+            return report;
+        }
+
+        // Files the fix creates have no copy to restore; rolling them back
+        // removes them.
+        let created: Vec<bool> = self
+            .changes
+            .iter()
+            .map(|(path, _)| !path.exists())
+            .collect();
+        let mut temporaries = Vec::new();
+        for (path, content) in &self.changes {
+            let temporary = sibling(path, "tmp");
+            let written = temporary
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| write_synced(&temporary, content));
+            if let Err(err) = written {
+                let _ = fs::remove_file(&temporary);
+                return abort(
+                    report,
+                    &temporaries,
+                    format!("failed to write {}: {}", temporary.display(), err),
+                );
+            }
+            temporaries.push(temporary);
+        }
+
+        let backups = match backup_paths(&self.changes, backup_dir) {
+            Ok(backups) => backups,
+            Err(err) => return abort(report, &temporaries, err),
+        };
+        for (index, ((path, _), backup)) in self.changes.iter().zip(&backups).enumerate() {
+            if created[index] {
+                continue;
+            }
+            let copied = backup
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::copy(path, backup));
+            if let Err(err) = copied {
+                if backup_dir.is_none() {
+                    remove_all(&backups[..index]);
+                }
+                return abort(
+                    report,
+                    &temporaries,
+                    format!(
+                        "failed to back up {} to {}: {}",
+                        path.display(),
+                        backup.display(),
+                        err
+                    ),
+                );
+            }
+        }
+
+        for index in 0..self.changes.len() {
+            let path = &self.changes[index].0;
+            if let Err(err) = rename(&temporaries[index], path) {
+                remove_all(&temporaries[index..]);
+                for restored in (0..index).rev() {
+                    let (path, outcome) = &mut report.files[restored];
+                    let restored_file = if created[restored] {
+                        fs::remove_file(&*path)
+                    } else {
+                        fs::copy(&backups[restored], &*path).map(|_| ())
+                    };
+                    *outcome = match restored_file {
+                        Ok(_) => Outcome::RolledBack,
+                        Err(_) => Outcome::RollbackFailed(backups[restored].clone()),
+                    };
+                }
+                if backup_dir.is_none() {
+                    let restored: Vec<PathBuf> = backups
+                        .iter()
+                        .zip(&report.files)
+                        .filter(|(_, (_, outcome))| !matches!(outcome, Outcome::RollbackFailed(_)))
+                        .map(|(backup, _)| backup.clone())
+                        .collect();
+                    remove_all(&restored);
+                }
+                report.error = Some(format!("failed to replace {}: {}", path.display(), err));
+                return report;
+            }
+            report.files[index].1 = Outcome::Applied;
+        }
+
+        if backup_dir.is_none() {
+            remove_all(&backups);
+        }
+        report
+    }
+}
+
+fn abort(mut report: FixReport, temporaries: &[PathBuf], error: String) -> FixReport {
+    remove_all(temporaries);
+    report.error = Some(error);
+    report
+}
+
+fn remove_all(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// A hidden file next to `path`, named like those of `write_atomically`.
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.{}.{}", name, process::id(), extension))
+}
+
+fn write_synced(path: &Path, content: &str) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()
+}
+
+// Where each file is copied before being replaced: next to it, or under
+// `backup_dir` at its path relative to the project, after emptying the
+// copies of the previous fix.
+fn backup_paths(
+    changes: &[(PathBuf, String)],
+    backup_dir: Option<&Path>,
+) -> Result<Vec<PathBuf>, String> {
+    let Some(dir) = backup_dir else {
+        return Ok(changes
+            .iter()
+            .map(|(path, _)| sibling(path, "bak"))
+            .collect());
+    };
+
+    let failed = |err: io::Error| {
+        format!(
+            "failed to prepare the backup folder {}: {}",
+            dir.display(),
+            err
+        )
+    };
+    if dir.join(BACKUP_MARKER).exists() {
+        fs::remove_dir_all(dir).map_err(failed)?;
+    } else if fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(format!(
+            "refusing to use {} as backup folder: it isn't empty and holds no previous backup",
+            dir.display()
+        ));
+    }
+    fs::create_dir_all(dir).map_err(failed)?;
+    fs::write(dir.join(BACKUP_MARKER), "").map_err(failed)?;
+
+    let root = std::env::current_dir().unwrap_or_default();
+    Ok(changes
+        .iter()
+        .map(|(path, _)| {
+            let relative = path.strip_prefix(&root).unwrap_or(path);
+            let mut backup = dir.to_path_buf();
+            for component in relative.components() {
+                if let Component::Normal(segment) = component {
+                    backup.push(segment);
+                }
+            }
+            backup
+        })
+        .collect())
+}
+
+// The lines `old` and `new` have in common, as their index on both sides,
+// along the shortest edit script between them (Myers' algorithm).
+fn common_lines(old: &[&str], new: &[&str]) -> Vec<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let offset = n + m;
+    let index = |k: isize| (k + offset) as usize;
+    // The furthest `x` reached on each diagonal `k = x - y`, before each
+    // step of the search.
+    let mut furthest = vec![0isize; 2 * (n + m) as usize + 2];
+    let mut trace = Vec::new();
+    let down = |furthest: &[isize], d: isize, k: isize| {
+        k == -d || (k != d && furthest[index(k - 1)] < furthest[index(k + 1)])
+    };
+    'search: for d in 0..=n + m {
+        trace.push(furthest.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if down(&furthest, d, k) {
+                furthest[index(k + 1)]
+            } else {
+                furthest[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            furthest[index(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut common = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, furthest) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let previous_k = if down(furthest, d, k) { k + 1 } else { k - 1 };
+        let previous_x = furthest[index(previous_k)];
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            common.push((x as usize, y as usize));
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+    common.reverse();
+    common
+}
@@ -0,0 +1,262 @@
+use memmap2::{MmapMut, MmapOptions};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::{self, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
+
+// File descriptors left to the rest of the process (standard streams,
+// sockets, the binary itself) when the limit is derived from the rlimit.
+const HEADROOM: usize = 32;
+
+// Files smaller than this are read rather than mapped, a read costing less
+// than setting up the mapping.
+const MAP_THRESHOLD: u64 = 64 * 1024;
+
+// Used when the platform doesn't report its limit.
+const FALLBACK_LIMIT: usize = 64;
+
+// How many times an open failing with "too many open files" is retried,
+// waiting twice as long each time from 1 ms.
+const RETRIES: u32 = 8;
+
+// The files the loader and the source scanners may have open at once, so
+// that reading thousands of them in parallel stays under the rlimit of
+// constrained CI containers. Zero until `set_limit` or the first open.
+static LIMIT: AtomicUsize = AtomicUsize::new(0);
+static OPEN: Mutex<usize> = Mutex::new(0);
+static RELEASED: Condvar = Condvar::new();
+
+// Contents read instead of the files on disk, by absolute path, such as the
+// unsaved changes of an editor. Streamed reads in low-memory mode still see
+// the disk.
+static BUFFERS: RwLock<BTreeMap<PathBuf, String>> = RwLock::new(BTreeMap::new());
+
+// `--max-open-files`.
+pub fn set_limit(limit: usize) {
+    LIMIT.store(limit.max(1), Ordering::SeqCst);
+}
+
+pub fn limit() -> usize {
+    match LIMIT.load(Ordering::SeqCst) {
+        0 => {
+            let limit = default_limit();
+            // Another thread may have set it meanwhile.
+            match LIMIT.compare_exchange(0, limit, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => limit,
+                Err(set) => set,
+            }
+        }
+        limit => limit,
+    }
+}
+
+// The soft limit on open files of the process, less some headroom.
+pub fn default_limit() -> usize {
+    let soft = fs::read_to_string("/proc/self/limits")
+        .ok()
+        .and_then(|limits| {
+            let line = limits
+                .lines()
+                .find(|line| line.starts_with("Max open files"))?;
+            line["Max open files".len()..]
+                .split_whitespace()
+                .next()?
+                .parse::<usize>()
+                .ok()
+        });
+    match soft {
+        Some(soft) => soft.saturating_sub(HEADROOM).max(1),
+        None => FALLBACK_LIMIT,
+    }
+}
+
+// A slot among the files that may be open, given back on drop.
+pub struct Permit(());
+
+impl Permit {
+    pub fn acquire() -> Permit {
+        let limit = limit();
+        let mut open = OPEN.lock().unwrap_or_else(|err| err.into_inner());
+        while *open >= limit {
+            open = RELEASED.wait(open).unwrap_or_else(|err| err.into_inner());
+        }
+        *open += 1;
+        Permit(())
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut open = OPEN.lock().unwrap_or_else(|err| err.into_inner());
+        *open -= 1;
+        RELEASED.notify_one();
+    }
+}
+
+// An open file holding its permit until it is closed.
+pub struct Handle {
+    file: File,
+    _permit: Permit,
+}
+
+impl Deref for Handle {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        &self.file
+    }
+}
+
+impl DerefMut for Handle {
+    fn deref_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+}
+
+pub fn open(path: &Path) -> io::Result<Handle> {
+    let permit = Permit::acquire();
+    let file = retrying(|| File::open(path))?;
+    Ok(Handle {
+        file,
+        _permit: permit,
+    })
+}
+
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    if let Some(content) = buffer(path.as_ref()) {
+        return Ok(content);
+    }
+    let _permit = Permit::acquire();
+    retrying(|| fs::read_to_string(path.as_ref()))
+}
+
+// The bytes of a file, read or mapped, that the caller may edit in place.
+pub enum Contents {
+    Read(Vec<u8>),
+    Mapped(MmapMut),
+}
+
+impl Deref for Contents {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Contents::Read(bytes) => bytes,
+            Contents::Mapped(map) => map,
+        }
+    }
+}
+
+impl DerefMut for Contents {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            Contents::Read(bytes) => bytes,
+            Contents::Mapped(map) => map,
+        }
+    }
+}
+
+// The bytes of `path` without copying large files onto the heap: they are
+// mapped copy-on-write, so that edits stay private to the process and only
+// the pages edited are copied. The descriptor is closed once mapped, the
+// mapping outliving it, so the permit isn't held for the scan. The content
+// may be in any encoding: the scans match bytes.
+pub fn map(path: &Path) -> io::Result<Contents> {
+    if let Some(content) = buffer(path) {
+        return Ok(Contents::Read(content.into_bytes()));
+    }
+    let contents = {
+        let file = open(path)?;
+        if file.metadata()?.len() < MAP_THRESHOLD {
+            let mut bytes = Vec::new();
+            io::Read::read_to_end(&mut &*file, &mut bytes)?;
+            Contents::Read(bytes)
+        } else {
+            // Safety: a file truncated by another process while mapped makes
+            // reading past its new end fault, as with any mapped scanner; the
+            // sources aren't expected to shrink during a run.
+            match unsafe { MmapOptions::new().map_copy(&*file) } {
+                Ok(map) => Contents::Mapped(map),
+                // Some file systems can't be mapped.
+                Err(_) => Contents::Read(fs::read(path)?),
+            }
+        }
+    };
+    Ok(contents)
+}
+
+// `--buffers`: replaces the previous buffers with `buffers`, by path
+// relative to the working directory or absolute.
+pub fn set_buffers(buffers: BTreeMap<PathBuf, String>) {
+    let buffers = buffers
+        .into_iter()
+        .filter_map(|(path, content)| Some((path::absolute(path).ok()?, content)))
+        .collect();
+    *BUFFERS.write().unwrap_or_else(|err| err.into_inner()) = buffers;
+}
+
+fn buffer(path: &Path) -> Option<String> {
+    let buffers = BUFFERS.read().unwrap_or_else(|err| err.into_inner());
+    if buffers.is_empty() {
+        return None;
+    }
+    buffers.get(&path::absolute(path).ok()?).cloned()
+}
+
+// Whether `path` is read from a buffer instead of the disk.
+pub fn is_buffered(path: &Path) -> bool {
+    let buffers = BUFFERS.read().unwrap_or_else(|err| err.into_inner());
+    !buffers.is_empty() && path::absolute(path).is_ok_and(|path| buffers.contains_key(&path))
+}
+
+// The modification time of `path` in nanoseconds and its size, which tell
+// whether it changed since it was last read.
+pub fn stamp(path: &Path) -> Option<(u64, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((u64::try_from(modified.as_nanos()).ok()?, metadata.len()))
+}
+
+// A JSON object from file paths to the content to read instead.
+pub fn read_buffers(path: &Path) -> Result<BTreeMap<PathBuf, String>, String> {
+    let source = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read the buffers {}: {}", path.display(), err))?;
+    let invalid = || format!("{} must map file paths to their content", path.display());
+    match serde_json::from_str(&source).map_err(|err| format!("{}: {}", invalid(), err))? {
+        Value::Object(buffers) => buffers
+            .into_iter()
+            .map(|(file, content)| match content {
+                Value::String(content) => Ok((PathBuf::from(file), content)),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+// Descriptors held by something else, such as another process sharing the
+// limit, may still run out; the open is tried again before giving up.
+fn retrying<T>(mut attempt: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let mut delay = Duration::from_millis(1);
+    for _ in 0..RETRIES {
+        match attempt() {
+            Err(err) if is_exhausted(&err) => {
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    attempt()
+}
+
+// EMFILE (the process limit) or ENFILE (the system one).
+fn is_exhausted(err: &io::Error) -> bool {
+    matches!(err.raw_os_error(), Some(23 | 24))
+}
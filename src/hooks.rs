@@ -0,0 +1,124 @@
+use crate::config::{Hook, HookEvent, HookTarget, HooksConfig};
+use crate::http;
+use crate::state::State;
+use serde_json::{Value, json};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The payload lines of `hook` for the changes between the state file and
+// the current run, empty when the event did not happen.
+pub fn payload(hook: &Hook, previous: &State, current: &State) -> Vec<Value> {
+    let event = hook.event.name();
+    match hook.event {
+        HookEvent::NewBaseKeys => current
+            .base_keys
+            .difference(&previous.base_keys)
+            .map(|key| json!({ "event": event, "key": key }))
+            .collect(),
+        HookEvent::DeletedKeys => previous
+            .base_keys
+            .difference(&current.base_keys)
+            .map(|key| json!({ "event": event, "key": key }))
+            .collect(),
+        HookEvent::CoverageDropped => {
+            let threshold = hook.threshold.unwrap();
+            current
+                .coverage
+                .iter()
+                .filter_map(|(lang, coverage)| {
+                    let before = *previous.coverage.get(lang)?;
+                    (before >= threshold && *coverage < threshold).then(|| {
+                        json!({
+                            "event": event,
+                            "lang": lang,
+                            "coverage": coverage,
+                            "previous": before,
+                            "threshold": threshold,
+                        })
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+// Invokes every hook whose event happened, at most once per event, and
+// returns a warning for each hook that failed. Hook failures never change
+// the outcome of the run.
+pub fn run(hooks: &HooksConfig, previous: &State, current: &State) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for hook in &hooks.hooks {
+        let lines = payload(hook, previous, current);
+        if lines.is_empty() {
+            continue;
+        }
+        let body: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+
+        let result = match &hook.target {
+            HookTarget::Command(command) => {
+                run_command(command, hook.event, body.as_bytes(), hooks.timeout)
+            }
+            HookTarget::Url(url) => post(url, body.as_bytes(), hooks.timeout),
+        };
+        if let Err(err) = result {
+            warnings.push(format!("{} hook failed: {}", hook.event.name(), err));
+        }
+    }
+
+    warnings
+}
+
+fn run_command(
+    command: &str,
+    event: HookEvent,
+    payload: &[u8],
+    timeout: Duration,
+) -> Result<(), String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("TRANSLATION_CHECK_EVENT", event.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("failed to run `{}`: {}", command, err))?;
+
+    // Written from another thread so that a hook which never reads its
+    // input cannot block the run past the timeout.
+    let mut stdin = child.stdin.take().unwrap();
+    let payload = payload.to_vec();
+    thread::spawn(move || stdin.write_all(&payload));
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(format!("`{}` exited with {}", command, status)),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("`{}` timed out after {:?}", command, timeout));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+}
+
+fn post(url: &str, payload: &[u8], timeout: Duration) -> Result<(), String> {
+    let headers = [("Content-Type", "application/x-ndjson".to_string())];
+    let response = http::post(url, &headers, payload, timeout)?;
+    if (200..300).contains(&response.status) {
+        Ok(())
+    } else {
+        Err(format!("{} answered with status {}", url, response.status))
+    }
+}
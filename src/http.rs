@@ -0,0 +1,188 @@
+use std::time::Duration;
+use ureq::Agent;
+
+// The largest response body read. Anything bigger is refused rather than
+// held in memory.
+pub const MAX_RESPONSE_SIZE: u64 = 32 * 1024 * 1024;
+
+// The redirects followed before a request is given up.
+pub const MAX_REDIRECTS: usize = 5;
+
+// The requests this tool makes, to download published artifacts and post
+// notifications, over ureq. `https://` URLs are fetched over TLS, the server
+// certificate checked against the Mozilla root certificates.
+pub struct Response {
+    pub status: u16,
+    headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub fn get(url: &str, headers: &[(&str, String)], timeout: Duration) -> Result<Response, String> {
+    request("GET", url, headers, &[], timeout)
+}
+
+pub fn post(
+    url: &str,
+    headers: &[(&str, String)],
+    body: &[u8],
+    timeout: Duration,
+) -> Result<Response, String> {
+    request("POST", url, headers, body, timeout)
+}
+
+// Sends the request, following up to `MAX_REDIRECTS` redirects to URLs of
+// the same scheme. Credentials only go to the host they were given for.
+fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, String)],
+    body: &[u8],
+    timeout: Duration,
+) -> Result<Response, String> {
+    let agent: Agent = Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .max_redirects(0)
+        .http_status_as_error(false)
+        .user_agent("check_translations")
+        .build()
+        .into();
+
+    let mut method = method;
+    let mut url = url.to_string();
+    let mut headers = headers.to_vec();
+    let mut body = body;
+    for _ in 0..=MAX_REDIRECTS {
+        let response = send(&agent, method, &url, &headers, body)?;
+        let location = match response.status {
+            301 | 302 | 303 | 307 | 308 => response.header("Location"),
+            _ => None,
+        };
+        let Some(location) = location else {
+            return Ok(response);
+        };
+        let next = resolve(&url, location)?;
+        let (tls, host) = split_url(&url)?;
+        let (next_tls, next_host) = split_url(&next)?;
+        if next_tls != tls {
+            return Err(format!(
+                "{} redirects to {}, which uses another scheme",
+                url, next
+            ));
+        }
+        if next_host != host {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("Authorization"));
+        }
+        // As browsers do, only 307 and 308 repeat the method and its body.
+        if !matches!(response.status, 307 | 308) {
+            method = "GET";
+            body = &[];
+        }
+        url = next;
+    }
+    Err(format!(
+        "request to {} was redirected more than {} times",
+        url, MAX_REDIRECTS
+    ))
+}
+
+fn send(
+    agent: &Agent,
+    method: &str,
+    url: &str,
+    headers: &[(&str, String)],
+    body: &[u8],
+) -> Result<Response, String> {
+    let (tls, _) = split_url(url)?;
+    if !tls
+        && headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("Authorization"))
+    {
+        return Err(format!(
+            "refusing to send credentials to {} over plain HTTP; use an https:// URL",
+            url
+        ));
+    }
+
+    let mut request = ureq::http::Request::builder().method(method).uri(url);
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    let request = request
+        .body(body)
+        .map_err(|err| format!("invalid request to {}: {}", url, err))?;
+    let mut response = agent
+        .run(request)
+        .map_err(|err| format!("request to {} failed: {}", url, err))?;
+
+    let body = response
+        .body_mut()
+        .with_config()
+        .limit(MAX_RESPONSE_SIZE)
+        .read_to_vec()
+        .map_err(|err| match err {
+            ureq::Error::BodyExceedsLimit(_) => format!(
+                "response from {} is larger than {} bytes",
+                url, MAX_RESPONSE_SIZE
+            ),
+            err => format!("request to {} failed: {}", url, err),
+        })?;
+    Ok(Response {
+        status: response.status().as_u16(),
+        headers: response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect(),
+        body,
+    })
+}
+
+// Whether the URL is fetched over TLS, and its host and port.
+fn split_url(url: &str) -> Result<(bool, &str), String> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+        (true, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (false, rest)
+    } else {
+        return Err(format!("unsupported URL `{}`", url));
+    };
+    Ok((tls, rest.split(['/', '?', '#']).next().unwrap_or_default()))
+}
+
+// The URL a `Location` header points to, from the URL that answered it.
+fn resolve(url: &str, location: &str) -> Result<String, String> {
+    if location.starts_with("https://") || location.starts_with("http://") {
+        return Ok(location.to_string());
+    }
+    let (tls, authority) = split_url(url)?;
+    let scheme = if tls { "https" } else { "http" };
+    if let Some(rest) = location.strip_prefix("//") {
+        return Ok(format!("{}://{}", scheme, rest));
+    }
+    let origin = format!("{}://{}", scheme, authority);
+    if location.starts_with('/') {
+        return Ok(format!("{}{}", origin, location));
+    }
+    // Relative to the folder of the current path.
+    let path = url[origin.len()..]
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+    let folder = path.rfind('/').map_or("/", |end| &path[..=end]);
+    Ok(format!("{}{}{}", origin, folder, location))
+}
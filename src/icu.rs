@@ -0,0 +1,317 @@
+use std::fmt;
+
+// The argument types of ICU MessageFormat.
+const FORMATS: [&str; 6] = ["number", "date", "time", "spellout", "ordinal", "duration"];
+// The plural categories a `plural` or `selectordinal` case may select.
+const CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+// How an argument of a message is formatted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Kind {
+    // `{name}`, printed as it is.
+    Simple,
+    // `{price, number, ::currency/EUR}`: the type, its style aside.
+    Format(String),
+    Plural,
+    SelectOrdinal,
+    Select,
+}
+
+impl Kind {
+    pub fn name(&self) -> &str {
+        match self {
+            Kind::Simple => "simple",
+            Kind::Format(format) => format,
+            Kind::Plural => "plural",
+            Kind::SelectOrdinal => "selectordinal",
+            Kind::Select => "select",
+        }
+    }
+}
+
+// An argument of a message, with the selectors of its cases for `plural`,
+// `selectordinal` and `select`, such as `=0`, `one` and `other`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argument {
+    pub name: String,
+    pub kind: Kind,
+    pub cases: Vec<String>,
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            Kind::Simple => write!(f, "{{{}}}", self.name),
+            _ => write!(f, "{{{}, {}}}", self.name, self.kind.name()),
+        }
+    }
+}
+
+// Where a message stops being valid, counted in characters from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at character {}", self.message, self.position)
+    }
+}
+
+// Reads an ICU MessageFormat string and returns its arguments in the order
+// they open, those nested in the cases of another included. Apostrophes
+// quote syntax characters as ICU reads them by default: `'{'` is a literal
+// brace, `''` an apostrophe, and an apostrophe before anything else, as in
+// "l'article", is just text.
+pub fn parse(text: &str) -> Result<Vec<Argument>, SyntaxError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        at: 0,
+        arguments: Vec::new(),
+    };
+    parser.message(false)?;
+    if parser.at < parser.chars.len() {
+        return Err(parser.error("unmatched `}`"));
+    }
+    Ok(parser.arguments)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    at: usize,
+    arguments: Vec<Argument>,
+}
+
+impl Parser {
+    fn error(&self, message: impl Into<String>) -> SyntaxError {
+        SyntaxError {
+            position: self.at + 1,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.at += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SyntaxError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.at += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("expected `{}`, found the end", expected))),
+        }
+    }
+
+    // Text and arguments up to a `}` closing the enclosing case, or the end.
+    // `#` is only special in the cases of a plural argument.
+    fn message(&mut self, in_plural: bool) -> Result<(), SyntaxError> {
+        while let Some(c) = self.peek() {
+            match c {
+                '}' => return Ok(()),
+                '{' => {
+                    self.at += 1;
+                    self.argument()?;
+                }
+                '\'' => self.apostrophe(in_plural)?,
+                _ => self.at += 1,
+            }
+        }
+        Ok(())
+    }
+
+    fn apostrophe(&mut self, in_plural: bool) -> Result<(), SyntaxError> {
+        let start = self.at;
+        self.at += 1;
+        match self.peek() {
+            Some('\'') => self.at += 1,
+            Some('{' | '}') => self.quoted(start)?,
+            Some('#' | '|') if in_plural => self.quoted(start)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // The quoted text after an apostrophe, up to the one closing it.
+    fn quoted(&mut self, start: usize) -> Result<(), SyntaxError> {
+        while let Some(c) = self.peek() {
+            self.at += 1;
+            if c == '\'' {
+                if self.peek() == Some('\'') {
+                    self.at += 1;
+                } else {
+                    return Ok(());
+                }
+            }
+        }
+        self.at = start;
+        Err(self.error("unterminated quote"))
+    }
+
+    fn identifier(&mut self) -> String {
+        self.skip_whitespace();
+        let start = self.at;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.at += 1;
+        }
+        self.chars[start..self.at].iter().collect()
+    }
+
+    // An argument, after its opening brace.
+    fn argument(&mut self) -> Result<(), SyntaxError> {
+        let name = self.identifier();
+        if name.is_empty() {
+            return Err(match self.peek() {
+                Some(c) if !matches!(c, ',' | '}') => {
+                    self.error(format!("invalid character `{}` in an argument name", c))
+                }
+                _ => self.error("expected an argument name"),
+            });
+        }
+        self.skip_whitespace();
+        if self.peek() != Some(',') {
+            self.expect('}')?;
+            self.arguments.push(Argument {
+                name,
+                kind: Kind::Simple,
+                cases: Vec::new(),
+            });
+            return Ok(());
+        }
+        self.at += 1;
+
+        let type_at = self.at;
+        let kind = match self.identifier().as_str() {
+            "" => return Err(self.error("expected an argument type")),
+            "plural" => Kind::Plural,
+            "selectordinal" => Kind::SelectOrdinal,
+            "select" => Kind::Select,
+            format if FORMATS.contains(&format) => Kind::Format(format.to_string()),
+            other => {
+                self.at = type_at;
+                self.skip_whitespace();
+                return Err(self.error(format!("unknown argument type `{}`", other)));
+            }
+        };
+        let index = self.arguments.len();
+        self.arguments.push(Argument {
+            name,
+            kind: kind.clone(),
+            cases: Vec::new(),
+        });
+        self.skip_whitespace();
+        match kind {
+            Kind::Format(_) => {
+                if self.peek() == Some(',') {
+                    self.at += 1;
+                    self.style()?;
+                }
+                self.expect('}')
+            }
+            _ => {
+                self.expect(',')?;
+                let cases = self.cases(&kind)?;
+                self.arguments[index].cases = cases;
+                self.expect('}')
+            }
+        }
+    }
+
+    // The style of a formatted argument, such as `short` or a skeleton,
+    // braces in it balanced.
+    fn style(&mut self) -> Result<(), SyntaxError> {
+        self.skip_whitespace();
+        let start = self.at;
+        let mut depth = 0;
+        while let Some(c) = self.peek() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                '\'' => {
+                    self.apostrophe(false)?;
+                    continue;
+                }
+                _ => {}
+            }
+            self.at += 1;
+        }
+        if self.chars[start..self.at].iter().all(|c| c.is_whitespace()) {
+            return Err(self.error("expected a style"));
+        }
+        Ok(())
+    }
+
+    // The cases of a `plural`, `selectordinal` or `select` argument, each a
+    // selector and a message in braces, one of them `other`.
+    fn cases(&mut self, kind: &Kind) -> Result<Vec<String>, SyntaxError> {
+        let plural = *kind != Kind::Select;
+        let mut cases: Vec<String> = Vec::new();
+        self.skip_whitespace();
+        if plural && self.chars[self.at..].starts_with(&['o', 'f', 'f', 's', 'e', 't', ':']) {
+            self.at += "offset:".len();
+            self.skip_whitespace();
+            let start = self.at;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.at += 1;
+            }
+            if start == self.at {
+                return Err(self.error("expected a number after `offset:`"));
+            }
+        }
+        loop {
+            self.skip_whitespace();
+            let start = self.at;
+            let selector = if plural && self.peek() == Some('=') {
+                self.at += 1;
+                let number = self.identifier();
+                if number.is_empty() || number.parse::<f64>().is_err() {
+                    self.at = start;
+                    return Err(self.error("expected a number after `=`"));
+                }
+                format!("={}", number)
+            } else {
+                self.identifier()
+            };
+            if selector.is_empty() {
+                if matches!(self.peek(), Some('}') | None) && !cases.is_empty() {
+                    break;
+                }
+                return Err(match self.peek() {
+                    Some('}') | None => self.error("expected a case"),
+                    Some(c) => self.error(format!("invalid character `{}` in a selector", c)),
+                });
+            }
+            if plural && !selector.starts_with('=') && !CATEGORIES.contains(&selector.as_str()) {
+                self.at = start;
+                return Err(self.error(format!("unknown plural category `{}`", selector)));
+            }
+            if cases.contains(&selector) {
+                self.at = start;
+                return Err(self.error(format!("duplicate case `{}`", selector)));
+            }
+            self.expect('{')?;
+            self.message(plural)?;
+            self.expect('}')?;
+            cases.push(selector);
+        }
+        if !cases.iter().any(|case| case == "other") {
+            return Err(self.error(format!(
+                "the `{}` argument has no `other` case",
+                kind.name()
+            )));
+        }
+        Ok(cases)
+    }
+}
@@ -0,0 +1,637 @@
+use crate::base::Base;
+use crate::checks;
+use crate::fix::Transaction;
+use crate::loader;
+use crate::model::{Project, ValueKind};
+use crate::report::Finding;
+use crate::sync;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+// What the review does with a finding once it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mark {
+    Accept,
+    DeleteKey,
+    CopyFromBase,
+}
+
+impl Mark {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Mark::Accept => "accept into baseline",
+            Mark::DeleteKey => "delete key",
+            Mark::CopyFromBase => "copy from base",
+        }
+    }
+}
+
+// Why `mark` can't be given to `finding`, if it can't. Keys are only
+// deleted or copied in the JSON files of the other languages: the base is
+// what they are compared against.
+fn refusal(
+    project: &Project,
+    base: &Base,
+    finding: &Finding,
+    mark: Mark,
+    accepts: bool,
+) -> Option<&'static str> {
+    if mark == Mark::Accept {
+        return (!accepts).then_some("accepting findings needs `--baseline <path>`");
+    }
+    let Some(key) = finding.key.as_deref() else {
+        return Some("the finding is about no key");
+    };
+    let Some(lang) = finding
+        .lang
+        .as_deref()
+        .filter(|lang| Some(*lang) != base.lang)
+    else {
+        return Some("only the keys of the other languages are edited");
+    };
+    let entry = project.get(lang).and_then(|data| data.values.get(key));
+    let is_json = |file: &str| Path::new(file).extension().is_some_and(|ext| ext == "json");
+    match mark {
+        Mark::DeleteKey => match entry {
+            None => Some("the language doesn't define the key"),
+            Some(entry) if !is_json(&entry.file) => Some("only keys of JSON files are deleted"),
+            Some(_) => None,
+        },
+        _ => {
+            if base.kind(key) != Some(ValueKind::String) || base.usage_key(key) != key {
+                Some("the base has no string value for the key")
+            } else if entry
+                .is_some_and(|entry| entry.kind != ValueKind::String || !is_json(&entry.file))
+            {
+                Some("only string values of JSON files are replaced")
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// The findings in the order they are reviewed, by language then file, with
+// the position of each in `findings`.
+fn review_order(findings: &[Finding]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..findings.len()).collect();
+    order.sort_by_key(|index| {
+        let finding = &findings[*index];
+        (
+            finding.lang.clone(),
+            finding.file.clone(),
+            finding.line,
+            finding.column,
+            finding.key.clone(),
+        )
+    });
+    order
+}
+
+fn group(finding: &Finding) -> String {
+    format!(
+        "{} · {}",
+        finding.lang.as_deref().unwrap_or("-").to_uppercase(),
+        finding.file.as_deref().unwrap_or("(no file)")
+    )
+}
+
+// The keys of the review, shown by `?`.
+const HELP: [(&str, &str); 12] = [
+    ("↓ j n enter", "next finding"),
+    ("↑ k p", "previous finding"),
+    ("] [", "first finding of the next or previous file"),
+    ("pgdn pgup", "a page of findings down or up"),
+    ("home end", "first or last finding"),
+    ("a", "accept into the baseline"),
+    ("d", "delete the key from the file"),
+    ("c", "copy the base value into the translation"),
+    ("u", "unmark"),
+    ("?", "show or hide these keys"),
+    ("q", "quit and apply the marks"),
+    ("x esc", "quit without writing anything"),
+];
+
+const HINTS: &str =
+    "a accept · d delete · c copy · u unmark · ↑↓ move · ] [ file · ? keys · q apply · x quit";
+
+// What a key press does to the review.
+enum Outcome {
+    Continue,
+    Apply,
+    Abandon,
+}
+
+// A screen of the review: the list of findings, the one selected and its
+// values, and a status line for the hints or what the last key did.
+struct Frame {
+    list: Vec<String>,
+    // The line of `list` selected.
+    selected: usize,
+    detail: Vec<String>,
+    status: String,
+}
+
+struct Review<'a> {
+    project: &'a Project<'a>,
+    base: &'a Base<'a>,
+    findings: &'a [Finding],
+    accepts: bool,
+    order: Vec<usize>,
+    at: usize,
+    marks: BTreeMap<usize, Mark>,
+    message: Option<String>,
+    help: bool,
+}
+
+impl<'a> Review<'a> {
+    fn new(
+        project: &'a Project<'a>,
+        base: &'a Base<'a>,
+        findings: &'a [Finding],
+        accepts: bool,
+    ) -> Review<'a> {
+        Review {
+            project,
+            base,
+            findings,
+            accepts,
+            order: review_order(findings),
+            at: 0,
+            marks: BTreeMap::new(),
+            message: None,
+            help: false,
+        }
+    }
+
+    fn handle(&mut self, key: KeyEvent, page: usize) -> Outcome {
+        self.message = None;
+        let last = self.order.len() - 1;
+        let index = self.order[self.at];
+        let mark = match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Outcome::Abandon;
+            }
+            KeyCode::Char('a') => Some(Mark::Accept),
+            KeyCode::Char('d') => Some(Mark::DeleteKey),
+            KeyCode::Char('c') => Some(Mark::CopyFromBase),
+            _ => None,
+        };
+        if let Some(mark) = mark {
+            match refusal(
+                self.project,
+                self.base,
+                &self.findings[index],
+                mark,
+                self.accepts,
+            ) {
+                Some(reason) => {
+                    self.message = Some(format!("Can't {}: {}", mark.describe(), reason))
+                }
+                None => {
+                    self.marks.insert(index, mark);
+                    self.at = (self.at + 1).min(last);
+                }
+            }
+            return Outcome::Continue;
+        }
+        match key.code {
+            KeyCode::Down | KeyCode::Enter | KeyCode::Char('j' | 'n') => {
+                if self.at == last {
+                    self.message = Some("Last finding; `q` applies the marks".to_string());
+                }
+                self.at = (self.at + 1).min(last);
+            }
+            KeyCode::Up | KeyCode::Char('k' | 'p') => self.at = self.at.saturating_sub(1),
+            KeyCode::PageDown => self.at = (self.at + page).min(last),
+            KeyCode::PageUp => self.at = self.at.saturating_sub(page),
+            KeyCode::Home => self.at = 0,
+            KeyCode::End => self.at = last,
+            KeyCode::Char(']') => {
+                let current = group(&self.findings[index]);
+                if let Some(next) =
+                    (self.at..=last).find(|at| group(&self.findings[self.order[*at]]) != current)
+                {
+                    self.at = next;
+                }
+            }
+            KeyCode::Char('[') => {
+                // The first finding of the previous file, or of this one when
+                // another of its findings is selected.
+                let first = |at: usize| {
+                    let current = group(&self.findings[self.order[at]]);
+                    (0..=at)
+                        .rev()
+                        .take_while(|at| group(&self.findings[self.order[*at]]) == current)
+                        .last()
+                        .unwrap_or(at)
+                };
+                let start = first(self.at);
+                self.at = if start < self.at || start == 0 {
+                    start
+                } else {
+                    first(start - 1)
+                };
+            }
+            KeyCode::Char('u') => {
+                self.marks.remove(&index);
+            }
+            KeyCode::Char('?') => self.help = !self.help,
+            KeyCode::Char('q') => return Outcome::Apply,
+            KeyCode::Char('x') | KeyCode::Esc => return Outcome::Abandon,
+            KeyCode::Char(other) => {
+                self.message = Some(format!("Unknown key `{}`; `?` shows them", other));
+            }
+            _ => {}
+        }
+        Outcome::Continue
+    }
+
+    fn frame(&self, width: usize) -> Frame {
+        let width = width.max(20);
+        let id_width = self
+            .findings
+            .iter()
+            .map(|finding| finding.check.info().id.len())
+            .max()
+            .unwrap_or(0);
+        let mut list = vec![format!(
+            "Review · {} findings · {} marked",
+            self.order.len(),
+            self.marks.len()
+        )];
+        let mut selected = 0;
+        let mut last = None;
+        for (at, index) in self.order.iter().enumerate() {
+            let finding = &self.findings[*index];
+            let heading = group(finding);
+            if last.as_ref() != Some(&heading) {
+                list.push(heading.clone());
+                last = Some(heading);
+            }
+            if at == self.at {
+                selected = list.len();
+            }
+            let mark = self
+                .marks
+                .get(index)
+                .map(|mark| format!("  [{}]", mark.describe()))
+                .unwrap_or_default();
+            list.push(format!(
+                "{} {:>4}  {:<7}  {:<id_width$}  {}{}",
+                if at == self.at { "▶" } else { " " },
+                at + 1,
+                finding.severity.name().to_uppercase(),
+                finding.check.info().id,
+                finding.key.as_deref().unwrap_or("-"),
+                mark,
+            ));
+        }
+
+        let detail = if self.help {
+            HELP.iter()
+                .map(|(keys, action)| format!("  {:<12}  {}", keys, action))
+                .collect()
+        } else {
+            self.detail(width)
+        };
+        Frame {
+            list: list.into_iter().map(|line| clip(&line, width)).collect(),
+            selected,
+            detail: detail.into_iter().map(|line| clip(&line, width)).collect(),
+            status: clip(self.message.as_deref().unwrap_or(HINTS), width),
+        }
+    }
+
+    // The selected finding, then the base value and the translation of its
+    // key in two columns.
+    fn detail(&self, width: usize) -> Vec<String> {
+        let index = self.order[self.at];
+        let finding = &self.findings[index];
+        let info = finding.check.info();
+        let mut lines = vec![
+            format!(
+                "[{}/{}] {} · {}",
+                self.at + 1,
+                self.order.len(),
+                finding.lang.as_deref().unwrap_or("-").to_uppercase(),
+                finding.location().as_deref().unwrap_or("(no file)")
+            ),
+            format!(
+                "{} {} {}",
+                finding.severity.name().to_uppercase(),
+                info.id,
+                finding.key.as_deref().unwrap_or("-")
+            ),
+            format!("  {}", finding.message.as_deref().unwrap_or(info.title)),
+        ];
+        for (label, value) in &finding.details {
+            lines.push(format!("  {}: {}", label, value));
+        }
+        if let Some(key) = finding.key.as_deref() {
+            let quoted = |value: Option<&str>| {
+                value.map_or_else(|| "(none)".to_string(), |value| format!("{:?}", value))
+            };
+            let mut columns = vec![(self.base.label(), quoted(self.base.value(key)))];
+            if let Some(lang) = finding
+                .lang
+                .as_deref()
+                .filter(|lang| Some(*lang) != self.base.lang)
+            {
+                let value = self.project.get(lang).and_then(|data| data.value(key));
+                columns.push((lang.to_uppercase(), quoted(value)));
+            }
+            lines.push(String::new());
+            lines.extend(side_by_side(&columns, width));
+        }
+        if let Some(mark) = self.marks.get(&index) {
+            lines.push(format!("  Marked: {}", mark.describe()));
+        }
+        lines
+    }
+}
+
+// Columns of `width` characters in all, each a label over its wrapped text.
+fn side_by_side(columns: &[(String, String)], width: usize) -> Vec<String> {
+    let column = (width - 2 - 3 * (columns.len() - 1)) / columns.len();
+    let wrapped: Vec<Vec<String>> = columns
+        .iter()
+        .map(|(label, text)| {
+            let chars: Vec<char> = text.chars().collect();
+            let mut lines = vec![label.clone()];
+            lines.extend(chars.chunks(column).map(|chunk| chunk.iter().collect()));
+            lines
+        })
+        .collect();
+    let height = wrapped.iter().map(Vec::len).max().unwrap_or(0);
+    (0..height)
+        .map(|row| {
+            let cells: Vec<String> = wrapped
+                .iter()
+                .map(|lines| {
+                    let cell = lines.get(row).map_or("", String::as_str);
+                    format!("{}{}", cell, " ".repeat(column - cell.chars().count()))
+                })
+                .collect();
+            format!("  {}", cells.join(" │ ")).trim_end().to_string()
+        })
+        .collect()
+}
+
+fn clip(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+// `--interactive` on a terminal: a full screen of the findings, driven by
+// the keyboard, until `q` returns the marks given, by position in
+// `findings`, or `x` returns `None`, for nothing to be written. Findings are
+// only accepted with a baseline to add them to, as `accepts` says.
+pub fn review(
+    project: &Project,
+    base: &Base,
+    findings: &[Finding],
+    accepts: bool,
+) -> io::Result<Option<BTreeMap<usize, Mark>>> {
+    if findings.is_empty() {
+        println!("No finding to review");
+        return Ok(Some(BTreeMap::new()));
+    }
+    let mut review = Review::new(project, base, findings, accepts);
+    let mut out = io::stdout();
+    let _screen = Screen::enter(&mut out)?;
+    loop {
+        let (width, height) = terminal::size()?;
+        let page = draw(&mut out, &review.frame(width as usize), height as usize)?;
+        let key = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            _ => continue,
+        };
+        match review.handle(key, page) {
+            Outcome::Continue => {}
+            Outcome::Apply => return Ok(Some(review.marks)),
+            Outcome::Abandon => return Ok(None),
+        }
+    }
+}
+
+// The alternate screen in raw mode, left when dropped, whatever ends the
+// review.
+struct Screen;
+
+impl Screen {
+    fn enter(out: &mut impl Write) -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, cursor::Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// Draws `frame` on a terminal of `height` lines: the list scrolled to keep
+// the selected finding in view, a rule, the detail and the status line at
+// the bottom. Returns the lines of the list shown, a page.
+fn draw(out: &mut impl Write, frame: &Frame, height: usize) -> io::Result<usize> {
+    let height = height.max(8);
+    // The list takes what the detail leaves, but at least a third.
+    let shown = frame.list.len().min(
+        (height - 2)
+            .saturating_sub(frame.detail.len())
+            .max(height / 3),
+    );
+    let offset = frame
+        .selected
+        .saturating_sub(shown / 2)
+        .min(frame.list.len() - shown);
+    queue!(out, Clear(ClearType::All))?;
+    for (row, line) in frame.list[offset..offset + shown].iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, row as u16))?;
+        if offset + row == frame.selected {
+            queue!(
+                out,
+                SetAttribute(Attribute::Reverse),
+                Print(line),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(out, Print(line))?;
+        }
+    }
+    queue!(out, cursor::MoveTo(0, shown as u16), Print("─".repeat(20)))?;
+    for (row, line) in frame.detail.iter().take(height - shown - 2).enumerate() {
+        queue!(
+            out,
+            cursor::MoveTo(0, (shown + 1 + row) as u16),
+            Print(line)
+        )?;
+    }
+    queue!(
+        out,
+        cursor::MoveTo(0, (height - 1) as u16),
+        SetAttribute(Attribute::Dim),
+        Print(&frame.status),
+        SetAttribute(Attribute::Reset)
+    )?;
+    out.flush()?;
+    Ok(shown.max(1))
+}
+
+// The review away from a terminal, for scripts and tests: each character
+// of `input` is a key press, `\n` enter and the escape sequences of the
+// arrow and page keys those keys, and each screen is written to `out` as
+// plain lines, `width` characters wide. The end of the input abandons the
+// review, as `x` does.
+pub fn review_keys(
+    project: &Project,
+    base: &Base,
+    findings: &[Finding],
+    accepts: bool,
+    input: &mut impl Read,
+    out: &mut impl Write,
+    width: usize,
+) -> io::Result<Option<BTreeMap<usize, Mark>>> {
+    if findings.is_empty() {
+        writeln!(out, "No finding to review")?;
+        return Ok(Some(BTreeMap::new()));
+    }
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let mut review = Review::new(project, base, findings, accepts);
+    let mut keys = parse_keys(&text).into_iter();
+    loop {
+        let frame = review.frame(width);
+        for line in frame
+            .list
+            .iter()
+            .chain([&"─".repeat(20)])
+            .chain(&frame.detail)
+        {
+            writeln!(out, "{}", line)?;
+        }
+        writeln!(out, "{}\n", frame.status)?;
+        let Some(key) = keys.next() else {
+            return Ok(None);
+        };
+        match review.handle(key, PIPED_PAGE) {
+            Outcome::Continue => {}
+            Outcome::Apply => return Ok(Some(review.marks)),
+            Outcome::Abandon => return Ok(None),
+        }
+    }
+}
+
+// The findings `pgdn` and `pgup` move by away from a terminal.
+const PIPED_PAGE: usize = 10;
+
+fn parse_keys(text: &str) -> Vec<KeyEvent> {
+    let mut keys = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let sequences = [
+            ("\x1b[A", KeyCode::Up),
+            ("\x1b[B", KeyCode::Down),
+            ("\x1b[5~", KeyCode::PageUp),
+            ("\x1b[6~", KeyCode::PageDown),
+            ("\x1b[H", KeyCode::Home),
+            ("\x1b[F", KeyCode::End),
+        ];
+        let (code, length) = match sequences.iter().find(|(seq, _)| rest.starts_with(seq)) {
+            Some((seq, code)) => (*code, seq.len()),
+            None => (
+                match c {
+                    '\n' | '\r' => KeyCode::Enter,
+                    '\x1b' => KeyCode::Esc,
+                    c => KeyCode::Char(c),
+                },
+                c.len_utf8(),
+            ),
+        };
+        keys.push(KeyEvent::new(code, KeyModifiers::NONE));
+        rest = &rest[length..];
+    }
+    keys
+}
+
+// The rewrite deleting and copying the keys marked so, one write per file,
+// and the findings it fixes. Keys copied into a language lacking them go to
+// the file `--fix sync-keys` would add them to; the others are rewritten
+// in place, keeping the formatting of the file.
+pub fn edits(
+    project: &Project,
+    base: &Base,
+    findings: &[Finding],
+    marks: &BTreeMap<usize, Mark>,
+) -> Result<(Transaction, BTreeSet<usize>), String> {
+    let mut files: BTreeMap<String, Vec<(usize, Mark, &str)>> = BTreeMap::new();
+    for (index, mark) in marks {
+        let finding = &findings[*index];
+        let (Some(lang), Some(key)) = (finding.lang.as_deref(), finding.key.as_deref()) else {
+            continue;
+        };
+        let defined = project
+            .get(lang)
+            .and_then(|data| data.file(key))
+            .map(str::to_string);
+        let file = match (mark, defined) {
+            (Mark::Accept, _) | (Mark::DeleteKey, None) => continue,
+            (_, Some(file)) => file,
+            (Mark::CopyFromBase, None) => checks::expected_file(project, base, lang, key),
+        };
+        files.entry(file).or_default().push((*index, *mark, key));
+    }
+
+    let mut transaction = Transaction::default();
+    let mut edited = BTreeSet::new();
+    for (file, marked) in files {
+        let original = match fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(_) if !Path::new(&file).exists() => "{}\n".to_string(),
+            Err(err) => return Err(format!("failed to read {}: {}", file, err)),
+        };
+        let mut content = original.clone();
+        // Two findings about the same key are fixed by one edit.
+        let mut done = BTreeSet::new();
+        for (index, mark, key) in marked {
+            if done.contains(&(mark, key)) {
+                edited.insert(index);
+                continue;
+            }
+            let value = base.value(key).unwrap_or_default();
+            let changed = match mark {
+                Mark::DeleteKey => sync::remove(&content, key),
+                _ => match loader::value_spans(&content)
+                    .map_err(|err| format!("failed to parse {}: {}", file, err))?
+                    .into_iter()
+                    .find(|(span_key, _)| span_key == key)
+                {
+                    Some((_, range)) => {
+                        let mut copied = content.clone();
+                        copied.replace_range(range, &serde_json::to_string(value).unwrap());
+                        Some(copied)
+                    }
+                    None => sync::insert(&content, key, value),
+                },
+            };
+            if let Some(changed) = changed {
+                content = changed;
+                edited.insert(index);
+                done.insert((mark, key));
+            }
+        }
+        if content != original {
+            transaction.rewrite(file.into(), content);
+        }
+    }
+    Ok((transaction, edited))
+}
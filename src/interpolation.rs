@@ -0,0 +1,207 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+lazy_static! {
+    static ref CALL_REGEX: Regex = Regex::new(r#"\bt\(\s*(['"`])([^'"`\n]+?)['"`]"#).unwrap();
+    static ref I18N_T_REGEX: Regex = Regex::new(r"<i18n-t\b([^>]*?)(/?)>").unwrap();
+    static ref KEYPATH_REGEX: Regex = Regex::new(r#"(?:^|\s)(:?)keypath\s*=\s*"([^"]*)""#).unwrap();
+    static ref PLURAL_REGEX: Regex = Regex::new(r"(?:^|\s):plural\s*=").unwrap();
+    static ref SLOT_REGEX: Regex = Regex::new(r"<template\s+(?:#|v-slot:)([\w-]+)").unwrap();
+    static ref IDENT_REGEX: Regex = Regex::new(r"^[\w$]+$").unwrap();
+}
+
+// A translation call site together with the variable names it supplies.
+// `supplied` is `None` when the arguments are not an object literal and the
+// variables can't be known statically.
+pub struct CallSite {
+    pub key: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub supplied: Option<HashSet<String>>,
+    // The variables given a string literal, such as `{ count: 'many' }`.
+    pub literals: HashSet<String>,
+}
+
+pub fn find_call_sites(path: &Path, content: &str) -> Vec<CallSite> {
+    let is_vue = path.extension().and_then(|ext| ext.to_str()) == Some("vue");
+    let mut sites = Vec::new();
+
+    for call in CALL_REGEX.captures_iter(content) {
+        let key = &call[2];
+        if call[1].starts_with('`') && key.contains("${") {
+            continue;
+        }
+        let end = call.get(0).unwrap().end();
+        // `t('errors.' + code)`: only a prefix of the key is known.
+        if content[end..].trim_start().starts_with('+') {
+            continue;
+        }
+        let (supplied, literals) = match call_arguments(&content[end..]) {
+            Some((supplied, literals)) => (Some(supplied), literals),
+            None => (None, HashSet::new()),
+        };
+        sites.push(CallSite {
+            key: key.to_string(),
+            file: path.to_path_buf(),
+            line: line_at(content, call.get(0).unwrap().start()),
+            supplied,
+            literals,
+        });
+    }
+
+    if let Some(template) = template_section(content).filter(|_| is_vue) {
+        sites.extend(i18n_t_components(path, content, template));
+    }
+
+    sites
+}
+
+fn line_at(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
+}
+
+// Parses what follows the key literal of a call: either the end of the call
+// (no variables supplied) or a second argument. Returns the variables
+// supplied and those given a string literal.
+fn call_arguments(rest: &str) -> Option<(HashSet<String>, HashSet<String>)> {
+    let rest = rest.trim_start();
+    if rest.starts_with(')') {
+        return Some((HashSet::new(), HashSet::new()));
+    }
+
+    let argument = rest.strip_prefix(',')?.trim_start();
+    if argument.starts_with('{') {
+        object_literal_keys(argument)
+    } else {
+        None
+    }
+}
+
+// Extracts the top-level property names of an object literal starting at the
+// beginning of `source`, and those whose value is a string literal. Spreads
+// and computed properties make it unverifiable.
+fn object_literal_keys(source: &str) -> Option<(HashSet<String>, HashSet<String>)> {
+    let mut keys = HashSet::new();
+    let mut literals = HashSet::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut segment = String::new();
+
+    for c in source.chars().skip(1) {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            segment.push(c);
+            continue;
+        }
+
+        match c {
+            '}' if depth == 0 => {
+                object_property(&segment, &mut keys, &mut literals)?;
+                return Some((keys, literals));
+            }
+            ',' if depth == 0 => {
+                object_property(&segment, &mut keys, &mut literals)?;
+                segment.clear();
+                continue;
+            }
+            '\'' | '"' | '`' => quote = Some(c),
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            _ => {}
+        }
+        segment.push(c);
+    }
+
+    None
+}
+
+fn object_property(
+    segment: &str,
+    keys: &mut HashSet<String>,
+    literals: &mut HashSet<String>,
+) -> Option<()> {
+    let segment = segment.trim();
+    if segment.is_empty() {
+        return Some(());
+    }
+    if segment.starts_with("...") || segment.starts_with('[') {
+        return None;
+    }
+
+    let (name, value) = segment.split_once(':').unwrap_or((segment, ""));
+    let name = name.trim().trim_matches(|c| c == '\'' || c == '"');
+    if !IDENT_REGEX.is_match(name) {
+        return None;
+    }
+    if value.trim_start().starts_with(['\'', '"', '`']) {
+        literals.insert(name.to_string());
+    }
+    keys.insert(name.to_string());
+    Some(())
+}
+
+// The byte range of the `<template>` block of a single-file component.
+fn template_section(content: &str) -> Option<Range<usize>> {
+    let start = content.find("<template")?;
+    let end = content.rfind("</template>")?;
+    (start < end).then_some(start..end)
+}
+
+// `<i18n-t keypath="key">` components, whose named slots provide the
+// placeholder values.
+fn i18n_t_components(path: &Path, content: &str, template: Range<usize>) -> Vec<CallSite> {
+    let section = &content[template.clone()];
+    let mut sites = Vec::new();
+
+    for component in I18N_T_REGEX.captures_iter(section) {
+        let attributes = &component[1];
+        let start = component.get(0).unwrap().start();
+        let line = line_at(content, template.start + start);
+
+        let Some(keypath) = KEYPATH_REGEX.captures(attributes) else {
+            continue;
+        };
+        let key = if keypath[1].is_empty() {
+            Some(keypath[2].to_string())
+        } else {
+            let expression = keypath[2].trim();
+            expression
+                .strip_prefix('\'')
+                .and_then(|rest| rest.strip_suffix('\''))
+                .filter(|key| !key.contains('\''))
+                .map(str::to_string)
+        };
+
+        let mut supplied = HashSet::new();
+        if PLURAL_REGEX.is_match(attributes) {
+            supplied.insert("count".to_string());
+            supplied.insert("n".to_string());
+        }
+        if component[2].is_empty() {
+            let body_start = component.get(0).unwrap().end();
+            let body_end = section[body_start..]
+                .find("</i18n-t>")
+                .map_or(section.len(), |offset| body_start + offset);
+            supplied.extend(
+                SLOT_REGEX
+                    .captures_iter(&section[body_start..body_end])
+                    .map(|slot| slot[1].to_string()),
+            );
+        }
+
+        sites.push(CallSite {
+            key: key.clone().unwrap_or_else(|| keypath[2].to_string()),
+            file: path.to_path_buf(),
+            line,
+            supplied: key.map(|_| supplied),
+            literals: HashSet::new(),
+        });
+    }
+
+    sites
+}
@@ -0,0 +1,97 @@
+use serde_json::{Map, Value};
+
+// Reads a Java `.properties` file into an object of its keys, which keep
+// their dots: `checkout.title = Checkout` is the key `checkout.title`.
+// Comments start with `#` or `!`, a line ending in a backslash goes on on
+// the next one, and keys end at the first unescaped `=`, `:` or whitespace.
+pub fn parse(source: &str) -> Result<Value, String> {
+    let mut map = Map::new();
+    let mut lines = source.lines().enumerate();
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+        let mut logical = line.to_string();
+        while ends_with_continuation(&logical) {
+            logical.pop();
+            match lines.next() {
+                Some((_, next)) => logical.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+        let error = |err: String| format!("line {}: {}", number + 1, err);
+
+        let mut key_end = logical.len();
+        let mut escaped = false;
+        for (index, c) in logical.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '=' | ':' | ' ' | '\t' | '\x0c' => {
+                    key_end = index;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let key = unescape(&logical[..key_end]).map_err(error)?;
+        let mut rest = logical[key_end..].trim_start_matches([' ', '\t', '\x0c']);
+        if let Some(value) = rest.strip_prefix(['=', ':']) {
+            rest = value.trim_start_matches([' ', '\t', '\x0c']);
+        }
+        let value = unescape(rest).map_err(error)?;
+        map.insert(key, Value::String(value));
+    }
+    Ok(Value::Object(map))
+}
+
+// Whether the line ends in an odd number of backslashes, the last one
+// escaping the line break.
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+fn unescape(text: &str) -> Result<String, String> {
+    let mut value = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('f') => value.push('\x0c'),
+            Some('u') => {
+                let mut code = hex_unit(&mut chars)?;
+                // Characters outside the BMP are written as surrogate pairs.
+                if (0xD800..0xDC00).contains(&code) && chars.as_str().starts_with("\\u") {
+                    chars.nth(1);
+                    let low = hex_unit(&mut chars)?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(format!("unpaired surrogate `\\u{:04X}`", code));
+                    }
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                }
+                let c = char::from_u32(code)
+                    .ok_or_else(|| format!("invalid character `\\u{:04X}`", code))?;
+                value.push(c);
+            }
+            Some(other) => value.push(other),
+            None => {}
+        }
+    }
+    Ok(value)
+}
+
+// The four hex digits of a `\u` escape.
+fn hex_unit(chars: &mut std::str::Chars) -> Result<u32, String> {
+    let hex: String = chars.by_ref().take(4).collect();
+    u32::from_str_radix(&hex, 16)
+        .ok()
+        .filter(|_| hex.len() == 4)
+        .ok_or_else(|| format!("malformed `\\u{}` escape", hex))
+}
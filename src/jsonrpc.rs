@@ -0,0 +1,595 @@
+use crate::base::Base;
+use crate::checks;
+use crate::cli::Options;
+use crate::config::Config;
+use crate::handles;
+use crate::model::Project;
+use crate::render::json;
+use crate::report::{Check, Finding, Report, Severity};
+use crate::setup;
+use crate::sync;
+use crate::timings::Timings;
+use crate::usage::{references_by_file, relative_to_root, source_files};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{self, Path, PathBuf};
+use std::process::{self, Command};
+
+// `jsonrpc --stdio` serves an editor plugin over newline-delimited JSON-RPC
+// 2.0: one request, notification or response per line of stdin and stdout.
+// Requests carry an `id`, which their response repeats; notifications
+// don't and get no response. The methods are:
+//
+// - `initialize` `{ "root"?, "args"?, "i18nDir"?, "srcDir"?, "baseLang"?,
+//   "config"? }` sets the project: `root` is the project root, `args` the
+//   options of a check run and the others shorthands for `--i18n-dir`,
+//   `--src-dir`, `--base-lang` and `--config`. Returns `{ "name",
+//   "version" }`. Every other method but `shutdown` needs it first.
+// - `didChangeFile` `{ "path", "content" }` reads `content` instead of the
+//   file, relative to the root or absolute, until `content` is `null`.
+// - `getFindings` `{ "file"? }` checks the project and returns
+//   `{ "findings": [Diagnostic] }`, only those of `file` when given.
+// - `explainKey` `{ "key" }` returns `{ "key", "base", "values": [{ "lang",
+//   "value", "file", "range" }], "usages": [{ "file", "range" }],
+//   "findings": [Diagnostic] }`, from the files as the session sees them.
+// - `shutdown` answers `null` and ends the session.
+//
+// A Diagnostic is `{ "check", "severity", "message", "lang", "key", "file",
+// "range" }`, where `range` is `{ "start": Position, "end": Position }` or
+// `null` for findings about no place in a file, such as a missing language
+// folder. A Position is `{ "line", "character" }`, counted from zero in
+// UTF-16 code units like the Language Server Protocol does.
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+// From the Language Server Protocol, for requests before `initialize`.
+pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+type IsSet = fn(&Options) -> bool;
+
+// Check options the session sets itself, for the child run to print one
+// JSON report and leave the project alone.
+const SESSION_OPTIONS: [(&str, IsSet); 12] = [
+    ("--format", |options| !options.reports.is_empty()),
+    ("--output", |options| options.output.is_some()),
+    ("--html", |options| options.html.is_some()),
+    ("--check-only", |options| options.check_only),
+    ("--print-config", |options| options.print_config),
+    ("--buffers", |options| options.buffers.is_some()),
+    ("--changed-files", |options| options.changed_files.is_some()),
+    ("--since", |options| options.since.is_some()),
+    ("--changed-since", |options| options.changed_since.is_some()),
+    ("--partition", |options| options.partition.is_some()),
+    ("--emit-waivers", |options| options.emit_waivers.is_some()),
+    ("--interactive", |options| options.interactive),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    // `None` for a notification.
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> RpcError {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn params(message: impl Into<String>) -> RpcError {
+        RpcError::new(INVALID_PARAMS, message)
+    }
+}
+
+impl Request {
+    pub fn parse(line: &str) -> Result<Request, RpcError> {
+        let value: Value = serde_json::from_str(line)
+            .map_err(|err| RpcError::new(PARSE_ERROR, format!("invalid JSON: {}", err)))?;
+        if !value.is_object() || value["jsonrpc"] != "2.0" {
+            return Err(RpcError::new(
+                INVALID_REQUEST,
+                "a request must be an object with `\"jsonrpc\": \"2.0\"`",
+            ));
+        }
+        let method = value["method"]
+            .as_str()
+            .ok_or_else(|| RpcError::new(INVALID_REQUEST, "missing `method`"))?;
+        Ok(Request {
+            id: value.get("id").cloned(),
+            method: method.to_string(),
+            params: value.get("params").cloned().unwrap_or(Value::Null),
+        })
+    }
+}
+
+// The response to the request `id`.
+pub fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    // In UTF-16 code units.
+    pub character: usize,
+}
+
+impl Position {
+    // The position of the byte `offset` of `content`.
+    pub fn at(content: &str, offset: usize) -> Position {
+        let before = &content[..offset];
+        let start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Position {
+            line: before.matches('\n').count(),
+            character: before[start..].encode_utf16().count(),
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({ "line": self.line, "character": self.character })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Range {
+    // The bytes `span` of `content`, up to the end of their first line.
+    pub fn of(content: &str, span: std::ops::Range<usize>) -> Range {
+        let end = content[span.clone()]
+            .find('\n')
+            .map_or(span.end, |newline| span.start + newline);
+        Range {
+            start: Position::at(content, span.start),
+            end: Position::at(content, end),
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({ "start": self.start.to_json(), "end": self.end.to_json() })
+    }
+}
+
+// A finding where an editor shows it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub check: &'static str,
+    pub severity: Severity,
+    // The title of the check, its message and details.
+    pub message: String,
+    pub lang: Option<String>,
+    pub key: Option<String>,
+    pub file: Option<String>,
+    pub range: Option<Range>,
+}
+
+impl Diagnostic {
+    // Findings on a line of a source file point at the key quoted there,
+    // or the whole line; findings about a key of a translation file, which
+    // have a column, at its member; findings about a file at its start.
+    // `content` reads a file as the session sees it.
+    pub fn from_finding(
+        finding: &Finding,
+        content: &mut impl FnMut(&str) -> Option<String>,
+    ) -> Diagnostic {
+        let info = finding.check.info();
+        let mut message = finding.message.clone().unwrap_or(info.title.to_string());
+        if let Some(key) = &finding.key {
+            message = format!("{}: {}", message, key);
+        }
+        for (name, value) in &finding.details {
+            message = format!("{}; {}: {}", message, name, value);
+        }
+        let range = finding.file.as_deref().map(|file| {
+            let start = Position {
+                line: 0,
+                character: 0,
+            };
+            let start = Range { start, end: start };
+            let Some(content) = content(file) else {
+                return start;
+            };
+            let key = finding.key.as_deref();
+            match finding.line {
+                Some(line) if finding.column.is_none() => line_range(&content, line, key),
+                _ => key.and_then(|key| member_range(&content, key)),
+            }
+            .unwrap_or(start)
+        });
+        Diagnostic {
+            check: info.id,
+            severity: finding.severity,
+            message,
+            lang: finding.lang.clone(),
+            key: finding.key.clone(),
+            file: finding.file.clone(),
+            range,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "check": self.check,
+            "severity": self.severity.name(),
+            "message": self.message,
+            "lang": self.lang,
+            "key": self.key,
+            "file": self.file,
+            "range": self.range.map(Range::to_json),
+        })
+    }
+}
+
+// The key quoted on the line `line` (from 1) of `content`, or the line.
+fn line_range(content: &str, line: usize, key: Option<&str>) -> Option<Range> {
+    let start: usize = content
+        .split_inclusive('\n')
+        .take(line.checked_sub(1)?)
+        .map(str::len)
+        .sum();
+    let text = content[start..].lines().next()?;
+    let span = key
+        .and_then(|key| text.find(key).map(|found| found..found + key.len()))
+        .unwrap_or(0..text.len());
+    Some(Range::of(content, start + span.start..start + span.end))
+}
+
+// The member of `key` in a JSON document, or of its closest parent written
+// there, such as the array holding an element.
+fn member_range(content: &str, key: &str) -> Option<Range> {
+    let mut key = key;
+    loop {
+        if let Some(span) = sync::member_span(content, key) {
+            return Some(Range::of(content, span));
+        }
+        key = &key[..key.rfind('.')?];
+    }
+}
+
+struct Setup {
+    // The options of the check runs.
+    args: Vec<String>,
+    options: Options,
+    config: Config,
+}
+
+// One editor session: the project, and the files it holds unsaved changes
+// to. Each `getFindings` runs a whole check, in a child process so that a
+// check failing can't end the session.
+pub struct Session {
+    exe: PathBuf,
+    setup: Option<Setup>,
+    buffers: BTreeMap<PathBuf, String>,
+    done: bool,
+}
+
+impl Session {
+    // `exe` is this binary, run for the checks.
+    pub fn new(exe: PathBuf) -> Session {
+        Session {
+            exe,
+            setup: None,
+            buffers: BTreeMap::new(),
+            done: false,
+        }
+    }
+
+    // Whether `shutdown` ended the session.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    // The response to one line, `None` for a notification.
+    pub fn handle(&mut self, line: &str) -> Option<Value> {
+        let request = match Request::parse(line) {
+            Ok(request) => request,
+            Err(error) => return Some(response(Value::Null, Err(error))),
+        };
+        let result = self.call(&request.method, &request.params);
+        request.id.map(|id| response(id, result))
+    }
+
+    fn call(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => self.initialize(params),
+            "shutdown" => {
+                self.done = true;
+                Ok(Value::Null)
+            }
+            "didChangeFile" | "getFindings" | "explainKey" if self.setup.is_none() => Err(
+                RpcError::new(SERVER_NOT_INITIALIZED, "`initialize` must come first"),
+            ),
+            "didChangeFile" => self.did_change_file(params),
+            "getFindings" => {
+                let file = optional_text(params, "file")?;
+                let report = self.check()?;
+                let file = file.map(absolute).transpose()?;
+                Ok(json!({
+                    "findings": self.diagnostics(&report, |finding| {
+                        file.is_none() || finding.file.as_deref().map(absolute).transpose().ok().flatten() == file
+                    }),
+                }))
+            }
+            "explainKey" => self.explain_key(params),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method `{}`", method),
+            )),
+        }
+    }
+
+    fn initialize(&mut self, params: &Value) -> Result<Value, RpcError> {
+        if let Some(root) = optional_text(params, "root")? {
+            env::set_current_dir(&root).map_err(|err| {
+                RpcError::params(format!(
+                    "failed to use {} as the project root: {}",
+                    root, err
+                ))
+            })?;
+        }
+        let mut args: Vec<String> = match &params["args"] {
+            Value::Null => Vec::new(),
+            Value::Array(args) => args
+                .iter()
+                .map(|arg| arg.as_str().map(str::to_string))
+                .collect::<Option<_>>()
+                .ok_or_else(|| RpcError::params("`args` must hold strings"))?,
+            _ => return Err(RpcError::params("`args` must be an array")),
+        };
+        for (name, option) in [
+            ("i18nDir", "--i18n-dir"),
+            ("srcDir", "--src-dir"),
+            ("baseLang", "--base-lang"),
+            ("config", "--config"),
+        ] {
+            if let Some(value) = optional_text(params, name)? {
+                args.extend([option.to_string(), value]);
+            }
+        }
+        // The project is only read, whatever the arguments.
+        args.push("--read-only".to_string());
+        let mut options = Options::parse(&args).map_err(RpcError::params)?;
+        if let Some((option, _)) = SESSION_OPTIONS.iter().find(|(_, set)| set(&options)) {
+            return Err(RpcError::params(format!(
+                "`{}` is set by the session and can't be passed to `initialize`",
+                option
+            )));
+        }
+        let config = Config::discover(options.config.as_deref()).map_err(RpcError::params)?;
+        // The session reads the project as the check run does.
+        setup::configure(&mut options, &config).map_err(RpcError::params)?;
+        self.setup = Some(Setup {
+            args,
+            options,
+            config,
+        });
+        Ok(json!({ "name": "check_translations", "version": env!("CARGO_PKG_VERSION") }))
+    }
+
+    fn did_change_file(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let path = params["path"]
+            .as_str()
+            .ok_or_else(|| RpcError::params("missing `path`"))?;
+        let path = absolute(path)?;
+        match &params["content"] {
+            Value::String(content) => {
+                self.buffers.insert(path, content.clone());
+            }
+            Value::Null => {
+                self.buffers.remove(&path);
+            }
+            _ => return Err(RpcError::params("`content` must be a string or null")),
+        }
+        // The session reads the buffers too, to place findings and explain
+        // keys.
+        handles::set_buffers(self.buffers.clone());
+        Ok(Value::Null)
+    }
+
+    // A check of the project with the buffers, as `check_translations
+    // --format json` reports it. The child run delegates to a `serve`
+    // server when one listens.
+    fn check(&self) -> Result<Report, RpcError> {
+        let setup = self.setup.as_ref().unwrap();
+        let internal = |message: String| RpcError::new(INTERNAL_ERROR, message);
+        let root = env::current_dir()
+            .map_err(|err| internal(format!("failed to read the working directory: {}", err)))?;
+        let mut command = Command::new(&self.exe);
+        command.arg("--cwd").arg(root).args(&setup.args).args([
+            "--format",
+            "json",
+            "--color",
+            "never",
+            "--quiet",
+            "--no-hooks",
+            "--no-rollup",
+        ]);
+        let buffers =
+            env::temp_dir().join(format!("check_translations-buffers-{}.json", process::id()));
+        if !self.buffers.is_empty() {
+            let contents: serde_json::Map<String, Value> = self
+                .buffers
+                .iter()
+                .map(|(path, content)| (path.display().to_string(), json!(content)))
+                .collect();
+            fs::write(&buffers, Value::Object(contents).to_string())
+                .map_err(|err| internal(format!("failed to write the buffers: {}", err)))?;
+            command.arg("--buffers").arg(&buffers);
+        }
+        let output = command
+            .output()
+            .map_err(|err| internal(format!("failed to run the check: {}", err)));
+        let _ = fs::remove_file(&buffers);
+        let output = output?;
+        if !matches!(output.status.code(), Some(0 | 1)) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(internal(format!(
+                "the check failed: {}",
+                stderr.trim().lines().last().unwrap_or("no error message")
+            )));
+        }
+        let mut report = json::parse(&String::from_utf8_lossy(&output.stdout))
+            .map_err(|err| internal(format!("failed to read the check report: {}", err)))?;
+
+        // A missing key is shown in the file it should be added to.
+        let timings = Timings::default();
+        let project = self.project(&timings);
+        if project.get(project.base_lang()).is_some() {
+            let base = Base::language(&project, project.base_lang());
+            for finding in &mut report.findings {
+                if let (Check::MissingKey, Some(lang), Some(key)) =
+                    (finding.check, &finding.lang, &finding.key)
+                {
+                    finding.file = Some(checks::expected_file(&project, &base, lang, key));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    // The project as the session sees it, buffers included.
+    fn project<'t>(&self, timings: &'t Timings) -> Project<'t> {
+        let session = self.setup.as_ref().unwrap();
+        setup::discover(&session.options, &session.config, timings)
+    }
+
+    fn diagnostics(&self, report: &Report, keep: impl Fn(&Finding) -> bool) -> Vec<Value> {
+        let mut contents: HashMap<String, Option<String>> = HashMap::new();
+        let mut content = |file: &str| {
+            contents
+                .entry(file.to_string())
+                .or_insert_with(|| handles::read_to_string(file).ok())
+                .clone()
+        };
+        report
+            .findings
+            .iter()
+            .filter(|finding| keep(finding))
+            .map(|finding| Diagnostic::from_finding(finding, &mut content).to_json())
+            .collect()
+    }
+
+    fn explain_key(&self, params: &Value) -> Result<Value, RpcError> {
+        let key = params["key"]
+            .as_str()
+            .ok_or_else(|| RpcError::params("missing `key`"))?;
+        let setup = self.setup.as_ref().unwrap();
+        let options = &setup.options;
+        let timings = Timings::default();
+        let project = self.project(&timings);
+        let values: Vec<Value> = project
+            .languages()
+            .map(|lang| {
+                let data = project.get(lang);
+                let file = data.and_then(|data| data.file(key));
+                let range =
+                    file.and_then(|file| member_range(&handles::read_to_string(file).ok()?, key));
+                json!({
+                    "lang": lang,
+                    "value": data.and_then(|data| data.value(key)),
+                    "file": file,
+                    "range": range.map(Range::to_json),
+                })
+            })
+            .collect();
+
+        let roots = options.sources();
+        let sources: Vec<PathBuf> = source_files(&roots)
+            .into_iter()
+            .filter(|file| {
+                !setup
+                    .config
+                    .exclude
+                    .excludes_source(relative_to_root(file, &roots))
+            })
+            .collect();
+        let keys = HashSet::from([key.to_string()]);
+        let usages: Vec<Value> = references_by_file(&sources, &keys, options.key_case_insensitive)
+            .into_iter()
+            .flat_map(|(file, references)| {
+                let content = handles::read_to_string(&file).unwrap_or_default();
+                let file = file.display().to_string();
+                references.into_iter().map(move |(_, line)| {
+                    json!({
+                        "file": file,
+                        "range": line_range(&content, line, Some(key)).map(Range::to_json),
+                    })
+                })
+            })
+            .collect();
+
+        let report = self.check()?;
+        Ok(json!({
+            "key": key,
+            "base": options.base_lang,
+            "values": values,
+            "usages": usages,
+            "findings": self.diagnostics(&report, |finding| {
+                finding.key.as_deref() == Some(key)
+            }),
+        }))
+    }
+}
+
+fn optional_text(params: &Value, name: &str) -> Result<Option<String>, RpcError> {
+    match &params[name] {
+        Value::Null => Ok(None),
+        Value::String(text) => Ok(Some(text.clone())),
+        _ => Err(RpcError::params(format!("`{}` must be a string", name))),
+    }
+}
+
+fn absolute(path: impl AsRef<Path>) -> Result<PathBuf, RpcError> {
+    let path = path.as_ref();
+    path::absolute(path)
+        .map_err(|err| RpcError::params(format!("invalid path {}: {}", path.display(), err)))
+}
+
+// Answers the requests of `input` on `output` until `shutdown` or the end of
+// the input.
+pub fn serve(
+    session: &mut Session,
+    input: impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = session.handle(&line) {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+        if session.is_done() {
+            break;
+        }
+    }
+    Ok(())
+}
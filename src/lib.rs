@@ -0,0 +1,79 @@
+pub mod base;
+pub mod baseline;
+pub mod bundle;
+pub mod checker;
+pub mod checks;
+pub mod codeowners;
+pub mod config;
+pub mod constants;
+pub mod content;
+pub mod context;
+pub mod detect;
+pub mod fix;
+pub mod handles;
+pub mod http;
+pub mod icu;
+pub mod interpolation;
+pub mod java_properties;
+pub mod loader;
+pub mod metrics;
+pub mod model;
+pub mod module;
+pub mod normalize;
+pub mod openapi;
+pub mod partition;
+pub mod patch;
+pub mod plural_rules;
+pub mod po;
+pub mod preview;
+pub mod redact;
+pub mod remote;
+pub mod render;
+pub mod report;
+pub mod review;
+pub mod rollup;
+pub mod sample;
+pub mod scaffold;
+pub mod schema;
+pub mod scope;
+pub mod search;
+pub mod selector;
+pub mod sha256;
+pub mod sheet;
+pub mod state;
+pub mod stats;
+pub mod suppressions;
+pub mod sync;
+pub mod timings;
+pub mod toml;
+pub mod translation_set;
+pub mod trend;
+pub mod unicode;
+pub mod usage;
+pub mod waivers;
+pub mod worklist;
+pub mod xlsx;
+pub mod yaml;
+
+// The command line, the analysis server and what only a run of the binary
+// does. They are public for `main.rs` alone and may change in any release.
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod daemon;
+#[doc(hidden)]
+pub mod hooks;
+#[doc(hidden)]
+pub mod interactive;
+#[doc(hidden)]
+pub mod jsonrpc;
+#[doc(hidden)]
+pub mod setup;
+#[doc(hidden)]
+pub mod watch;
+
+// The types embedders need most, so that they don't have to know the
+// module layout.
+pub use checker::Checker;
+pub use report::{Check, Finding, Report, Severity};
+pub use translation_set::{LoadOptions, TranslationSet};
@@ -0,0 +1,635 @@
+use crate::handles;
+use crate::java_properties;
+use crate::model::{Entry, KeyId, LanguageData, ValueKind};
+use crate::module::{self, parse_module};
+use crate::po;
+use crate::report::Finding;
+use crate::timings::Timings;
+use crate::yaml;
+use dashmap::DashMap;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+// Objects may carry a block of checker settings under this key, which is
+// never a translation itself.
+pub const METADATA_KEY: &str = "_translationCheck";
+
+// The formats translation files are read in, told apart by their extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Json,
+    Yaml,
+    Properties,
+    Gettext,
+}
+
+impl FileFormat {
+    pub const EXTENSIONS: [(&'static str, FileFormat); 6] = [
+        ("json", FileFormat::Json),
+        ("yml", FileFormat::Yaml),
+        ("yaml", FileFormat::Yaml),
+        ("properties", FileFormat::Properties),
+        ("po", FileFormat::Gettext),
+        ("pot", FileFormat::Gettext),
+    ];
+
+    pub fn of(name: &str) -> Option<FileFormat> {
+        let (_, extension) = name.rsplit_once('.')?;
+        FileFormat::EXTENSIONS
+            .iter()
+            .find(|(known, _)| *known == extension)
+            .map(|(_, format)| *format)
+    }
+}
+
+// The name of a translation file without its extension, `None` for files of
+// another format.
+pub fn translation_stem(name: &str) -> Option<&str> {
+    FileFormat::of(name)?;
+    name.rsplit_once('.').map(|(stem, _)| stem)
+}
+
+// A translation file read into the document a JSON file would hold, with
+// the keys a gettext catalog flags as fuzzy.
+#[derive(Debug, Default)]
+pub struct Document {
+    pub value: Value,
+    pub fuzzy: Vec<String>,
+}
+
+// Parses a translation file of `lang` after the format of its extension; a
+// base module is read from its exported object literal.
+pub fn parse_document(path: &Path, content: &str, lang: &str) -> Result<Document, String> {
+    let name = path.to_string_lossy();
+    let is_module = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| module::EXTENSIONS.contains(&extension));
+    let value = if is_module {
+        parse_module(content).map_err(|err| format!("unsupported module syntax: {}", err))?
+    } else {
+        match FileFormat::of(&name) {
+            Some(FileFormat::Yaml) => yaml::parse(content)
+                .map(|document| unwrap_language(lang, document))
+                .map_err(|err| format!("invalid YAML: {}", err))?,
+            Some(FileFormat::Properties) => java_properties::parse(content)
+                .map_err(|err| format!("invalid properties file: {}", err))?,
+            Some(FileFormat::Gettext) => {
+                let catalog = po::parse(content, name.ends_with(".pot"), lang)
+                    .map_err(|err| format!("invalid gettext catalog: {}", err))?;
+                return Ok(Document {
+                    value: catalog.messages,
+                    fuzzy: catalog.fuzzy,
+                });
+            }
+            _ => match serde_json::from_str(content)
+                .map_err(|err| format!("invalid JSON: {}", err))?
+            {
+                // Keys are the members of the root object; an array or a
+                // scalar has none.
+                Value::Object(map) => Value::Object(map),
+                _ => return Err("expected a JSON object".into()),
+            },
+        }
+    };
+    Ok(Document {
+        value,
+        fuzzy: Vec::new(),
+    })
+}
+
+// Rails-style YAML files nest their keys under the language, as in
+// `de: { greeting: Hallo }`; the language itself is no part of the keys.
+fn unwrap_language(lang: &str, document: Value) -> Value {
+    match document {
+        Value::Object(mut map)
+            if map.len() == 1
+                && map
+                    .keys()
+                    .next()
+                    .is_some_and(|key| key.eq_ignore_ascii_case(lang)) =>
+        {
+            map.values_mut().next().map(Value::take).unwrap_or_default()
+        }
+        document => document,
+    }
+}
+
+// A translation file parsed and flattened, before its keys are interned.
+#[derive(Debug)]
+struct FlatFile {
+    leaves: Vec<(String, Value)>,
+    arrays: Vec<(String, usize)>,
+    do_not_translate: HashSet<String>,
+    fuzzy: Vec<String>,
+    // Members a JSON file defines again in the same object, with the line
+    // and column of each definition parsing dropped.
+    repeated: Vec<(String, Position)>,
+}
+
+// A file flattened by an earlier run, with the language it was parsed for
+// and its stamp then.
+struct KeptFile {
+    lang: String,
+    stamp: (u64, u64),
+    flat: Arc<FlatFile>,
+}
+
+// The translation files flattened by the runs of a process checking more
+// than once (`--watch`, `serve`), so that a run only flattens the files
+// changed since the previous one; `None` until `keep_flattened_files`.
+static KEPT_FILES: Mutex<Option<HashMap<PathBuf, KeptFile>>> = Mutex::new(None);
+
+pub fn keep_flattened_files() {
+    KEPT_FILES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get_or_insert_with(HashMap::new);
+}
+
+// The flattened file at `path`, from an earlier run when it hasn't changed
+// since. Buffers are always flattened again.
+fn flatten_file(path: &Path, lang: &str, timings: &Timings) -> Result<Arc<FlatFile>, String> {
+    let stamp = handles::stamp(path).filter(|_| !handles::is_buffered(path));
+    {
+        let kept = KEPT_FILES.lock().unwrap_or_else(|err| err.into_inner());
+        if let (Some(kept), Some(stamp)) = (kept.as_ref().and_then(|kept| kept.get(path)), stamp)
+            && kept.lang == lang
+            && kept.stamp == stamp
+        {
+            timings.record_flattened(false);
+            return Ok(kept.flat.clone());
+        }
+    }
+
+    let content = handles::read_to_string(path).map_err(|err| err.to_string())?;
+    let document = parse_document(path, &content, lang)?;
+    let flattened = Flattened::default();
+    flatten_json(&document.value, String::new(), &flattened);
+    let mut do_not_translate = HashSet::new();
+    find_do_not_translate(&document.value, None, false, &mut do_not_translate);
+    let repeated = match FileFormat::of(&path.to_string_lossy()) {
+        Some(FileFormat::Json) => repeated_members(&content).unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    timings.record_flattened(true);
+    let flat = Arc::new(FlatFile {
+        leaves: flattened.leaves.into_iter().collect(),
+        arrays: flattened.arrays.into_iter().collect(),
+        do_not_translate,
+        fuzzy: document.fuzzy,
+        repeated,
+    });
+
+    let mut kept = KEPT_FILES.lock().unwrap_or_else(|err| err.into_inner());
+    if let (Some(kept), Some(stamp)) = (kept.as_mut(), stamp) {
+        kept.insert(
+            path.to_path_buf(),
+            KeptFile {
+                lang: lang.to_string(),
+                stamp,
+                flat: flat.clone(),
+            },
+        );
+    }
+    Ok(flat)
+}
+
+// Parses the translation files of one language into its entries. Files
+// that can't be read or parsed are recorded with their error and skipped.
+pub fn load_language(
+    lang: &str,
+    files: &[PathBuf],
+    intern: impl Fn(String) -> KeyId,
+    timings: &Timings,
+) -> LanguageData {
+    let mut data = LanguageData::default();
+
+    for path in files {
+        let flat = match flatten_file(path, lang, timings) {
+            Ok(flat) => flat,
+            Err(err) => {
+                data.unreadable.push((path.to_string_lossy().into(), err));
+                continue;
+            }
+        };
+
+        let file: Arc<str> = path.to_string_lossy().into();
+        for key in &flat.fuzzy {
+            data.fuzzy.push((intern(key.clone()), file.clone()));
+        }
+        for (key, (line, column)) in &flat.repeated {
+            let dropped = format!("{}:{}:{}", file, line, column);
+            data.duplicates
+                .push((intern(key.clone()), dropped.into(), file.clone()));
+        }
+        for (key, length) in &flat.arrays {
+            data.arrays
+                .insert(intern(key.clone()), (*length, file.clone()));
+        }
+        for (key, leaf) in &flat.leaves {
+            let do_not_translate = flat.do_not_translate.contains(key);
+            let key = intern(key.clone());
+            let (value, kind) = match leaf {
+                Value::String(text) => (text.clone(), ValueKind::String),
+                Value::Number(_) => (leaf.to_string(), ValueKind::Number),
+                Value::Bool(_) => (leaf.to_string(), ValueKind::Boolean),
+                _ => (leaf.to_string(), ValueKind::Null),
+            };
+            let previous = data.values.insert(
+                key.clone(),
+                Entry {
+                    value,
+                    kind,
+                    file: file.clone(),
+                    do_not_translate,
+                },
+            );
+            if let Some(previous) = previous {
+                data.duplicates.push((key, previous.file, file.clone()));
+            }
+        }
+    }
+
+    data
+}
+
+// A JSON document flattened to dotted keys. Array elements are keyed by
+// their index, so `{"terms": ["a", "b"]}` holds `terms.0` and `terms.1`.
+#[derive(Debug, Default)]
+pub struct Flattened {
+    // The strings, numbers, booleans and nulls of the document.
+    pub leaves: DashMap<String, Value>,
+    // The length of every array, empty ones included.
+    pub arrays: DashMap<String, usize>,
+}
+
+pub fn flatten_json(value: &Value, prefix: String, output: &Flattened) {
+    // `None` stands for the document root, so that an empty key still adds
+    // its own segment instead of being merged with its parent.
+    let root = if prefix.is_empty() {
+        None
+    } else {
+        Some(prefix)
+    };
+    let mut stack = vec![(root, value)];
+    let child = |prefix: &Option<String>, key: &str| match prefix {
+        None => key.to_string(),
+        Some(prefix) => format!("{}.{}", prefix, key),
+    };
+
+    while let Some((curr_prefix, curr_value)) = stack.pop() {
+        match curr_value {
+            Value::Object(map) => {
+                for (key, val) in map.iter().filter(|(key, _)| *key != METADATA_KEY) {
+                    stack.push((Some(child(&curr_prefix, key)), val));
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    stack.push((Some(child(&curr_prefix, &index.to_string())), item));
+                }
+                output
+                    .arrays
+                    .insert(curr_prefix.unwrap_or_default(), items.len());
+            }
+            leaf => {
+                output
+                    .leaves
+                    .insert(curr_prefix.unwrap_or_default(), leaf.clone());
+            }
+        }
+    }
+}
+
+// Collects the keys marked as not to be translated. An object marks its whole
+// subtree with `"_translationCheck": { "doNotTranslate": true }`, or only the
+// listed children with `"doNotTranslate": ["sku", "vendor"]`.
+fn find_do_not_translate(
+    value: &Value,
+    path: Option<String>,
+    marked: bool,
+    output: &mut HashSet<String>,
+) {
+    match value {
+        Value::Object(map) => {
+            let setting = map
+                .get(METADATA_KEY)
+                .and_then(|metadata| metadata.get("doNotTranslate"));
+            for (key, val) in map.iter().filter(|(key, _)| *key != METADATA_KEY) {
+                let listed = match setting {
+                    Some(Value::Bool(all)) => *all,
+                    Some(Value::Array(keys)) => keys.iter().any(|listed| listed == key.as_str()),
+                    _ => false,
+                };
+                let child = match &path {
+                    None => key.clone(),
+                    Some(path) => format!("{}.{}", path, key),
+                };
+                find_do_not_translate(val, Some(child), marked || listed, output);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                let child = match &path {
+                    None => index.to_string(),
+                    Some(path) => format!("{}.{}", path, index),
+                };
+                find_do_not_translate(item, Some(child), marked, output);
+            }
+        }
+        Value::String(_) if marked => {
+            output.insert(path.unwrap_or_default());
+        }
+        _ => {}
+    }
+}
+
+// Rebuilds the nested document of flattened translations, for writing them
+// back to disk. Fails when a key is both a value and the parent of another
+// key, e.g. `a` and `a.b`, which no JSON document can hold.
+pub fn nest_json<'a>(
+    translations: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> Result<Value, String> {
+    let mut entries: Vec<_> = translations.into_iter().collect();
+    entries.sort();
+
+    let mut root = Map::new();
+    for (key, value) in entries {
+        let segments: Vec<&str> = key.split('.').collect();
+        let (last, parents) = segments.split_last().unwrap();
+
+        let mut table = &mut root;
+        for (depth, segment) in parents.iter().enumerate() {
+            let entry = table
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            table = match entry {
+                Value::Object(map) => map,
+                _ => {
+                    return Err(format!(
+                        "key `{}` conflicts with the value of `{}`",
+                        key,
+                        segments[..=depth].join(".")
+                    ));
+                }
+            };
+        }
+
+        if table.contains_key(*last) {
+            return Err(format!("key `{}` conflicts with nested keys", key));
+        }
+        table.insert(last.to_string(), Value::String(value.clone()));
+    }
+
+    Ok(Value::Object(root))
+}
+
+// Where the string value of each flattened key sits in a JSON document, as
+// the byte range of its literal, quotes included. Fixes rewrite values in
+// place through these ranges, keeping the formatting of the file.
+pub fn value_spans(content: &str) -> Result<Vec<(String, Range<usize>)>, String> {
+    Ok(Scanner::scan(content)?.spans)
+}
+
+// Where each flattened key of a JSON document is defined, as the byte offset
+// of its name, or of the element itself for array elements. Objects and
+// arrays are keys too, for findings about a whole group.
+pub fn key_offsets(content: &str) -> Result<HashMap<String, usize>, String> {
+    Ok(Scanner::scan(content)?.keys.into_iter().collect())
+}
+
+// The flattened keys a JSON document defines more than once in the same
+// object, of which parsing keeps only the last.
+pub fn duplicate_members(content: &str) -> Result<Vec<String>, String> {
+    Ok(Scanner::scan(content)?
+        .duplicates
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect())
+}
+
+// The definitions of `duplicate_members` that parsing drops, each with the
+// line and column of its name.
+fn repeated_members(content: &str) -> Result<Vec<(String, Position)>, String> {
+    Ok(positions(content, Scanner::scan(content)?.duplicates))
+}
+
+// A line and column in a document, both from 1, columns counting characters.
+type Position = (usize, usize);
+
+// The line and column of each flattened key of a JSON document.
+fn key_positions(content: &str) -> Result<HashMap<String, Position>, String> {
+    let offsets = key_offsets(content)?.into_iter().collect();
+    Ok(positions(content, offsets).into_iter().collect())
+}
+
+// The line and column of each offset of `content`, in offset order.
+fn positions(content: &str, mut offsets: Vec<(String, usize)>) -> Vec<(String, Position)> {
+    offsets.sort_by_key(|(_, offset)| *offset);
+    let (mut line, mut line_start, mut at) = (1, 0, 0);
+    let mut positions = Vec::with_capacity(offsets.len());
+    for (key, offset) in offsets {
+        for (index, byte) in content.as_bytes()[at..offset].iter().enumerate() {
+            if *byte == b'\n' {
+                line += 1;
+                line_start = at + index + 1;
+            }
+        }
+        at = offset;
+        let column = content[line_start..offset].chars().count() + 1;
+        positions.push((key, (line, column)));
+    }
+    positions
+}
+
+// Points the findings about a key of a JSON translation file at the line
+// and column the key is defined at, reading each file once. Findings that
+// already have a line, and keys the file doesn't define, as missing keys,
+// are left as they are.
+pub fn locate_keys(findings: &mut [Finding]) {
+    let mut files = HashMap::new();
+    for finding in findings.iter_mut().filter(|finding| finding.line.is_none()) {
+        let (Some(key), Some(file)) = (&finding.key, &finding.file) else {
+            continue;
+        };
+        if FileFormat::of(file) != Some(FileFormat::Json) {
+            continue;
+        }
+        let positions = files.entry(file.clone()).or_insert_with(|| {
+            handles::read_to_string(Path::new(file))
+                .ok()
+                .and_then(|content| key_positions(&content).ok())
+        });
+        if let Some(&(line, column)) = positions.as_ref().and_then(|positions| positions.get(key)) {
+            finding.line = Some(line);
+            finding.column = Some(column);
+        }
+    }
+}
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    content: &'a str,
+    pos: usize,
+    spans: Vec<(String, Range<usize>)>,
+    keys: Vec<(String, usize)>,
+    // Members defined again in the same object, with the offset of the
+    // definition the later one replaces.
+    duplicates: Vec<(String, usize)>,
+}
+
+impl Scanner<'_> {
+    fn scan(content: &str) -> Result<Scanner<'_>, String> {
+        let mut scanner = Scanner {
+            bytes: content.as_bytes(),
+            content,
+            pos: 0,
+            spans: Vec::new(),
+            keys: Vec::new(),
+            duplicates: Vec::new(),
+        };
+        scanner.value(None)?;
+        scanner.skip_whitespace();
+        if scanner.pos < scanner.bytes.len() {
+            return Err(scanner.error("trailing characters"));
+        }
+        Ok(scanner)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at byte {}", message, self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) != Some(&byte) {
+            return Err(self.error(&format!("expected `{}`", byte as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    // The literal starting at the cursor, decoded.
+    fn string(&mut self) -> Result<(String, Range<usize>), String> {
+        self.expect(b'"')?;
+        let start = self.pos - 1;
+        loop {
+            match self.bytes.get(self.pos) {
+                None => return Err(self.error("unterminated string")),
+                Some(b'\\') => self.pos += 2,
+                Some(b'"') => break,
+                Some(_) => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        let literal = &self.content[start..self.pos];
+        let text = serde_json::from_str(literal).map_err(|err| self.error(&err.to_string()))?;
+        Ok((text, start..self.pos))
+    }
+
+    // Scans the value at the cursor, recording it under `path` when it is
+    // a string. Like `flatten_json`, array elements are keyed by index and
+    // metadata blocks are skipped.
+    fn value(&mut self, path: Option<String>) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b'}') {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                let mut members = HashMap::new();
+                loop {
+                    let (key, range) = self.string()?;
+                    self.expect(b':')?;
+                    let child = match &path {
+                        None => key.clone(),
+                        Some(path) => format!("{}.{}", path, key),
+                    };
+                    if let Some(previous) = members.insert(key.clone(), range.start) {
+                        self.duplicates.push((child.clone(), previous));
+                    }
+                    let (spans, keys) = (self.spans.len(), self.keys.len());
+                    self.keys.push((child.clone(), range.start));
+                    self.value(Some(child))?;
+                    if key == METADATA_KEY {
+                        self.spans.truncate(spans);
+                        self.keys.truncate(keys);
+                    }
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(());
+                        }
+                        _ => return Err(self.error("expected `,` or `}`")),
+                    }
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b']') {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                let mut index = 0;
+                loop {
+                    let child = match &path {
+                        None => index.to_string(),
+                        Some(path) => format!("{}.{}", path, index),
+                    };
+                    self.skip_whitespace();
+                    self.keys.push((child.clone(), self.pos));
+                    self.value(Some(child))?;
+                    index += 1;
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(());
+                        }
+                        _ => return Err(self.error("expected `,` or `]`")),
+                    }
+                }
+            }
+            Some(b'"') => {
+                let (_, range) = self.string()?;
+                self.spans.push((path.unwrap_or_default(), range));
+                Ok(())
+            }
+            Some(_) => {
+                let start = self.pos;
+                while self
+                    .bytes
+                    .get(self.pos)
+                    .is_some_and(|byte| !b",}] \t\r\n".contains(byte))
+                {
+                    self.pos += 1;
+                }
+                serde_json::from_str::<Value>(&self.content[start..self.pos])
+                    .map(|_| ())
+                    .map_err(|err| self.error(&err.to_string()))
+            }
+            None => Err(self.error("unexpected end of document")),
+        }
+    }
+}
@@ -1,277 +1,1898 @@
+use check_translations::base::Base;
+use check_translations::baseline::Baseline;
+use check_translations::bundle::Bundle;
+use check_translations::checker::{Checker, Run, exempt_from_coverage};
+use check_translations::checks::{self, check_translations, extract_variables};
+use check_translations::cli::{
+    self, BaseLangOrigin, ColorChoice, ExportOptions, FailOn, Fix, FormatOptions, ImportOptions,
+    JsonRpcOptions, LintOptions, MergeOptions, Options, PluralRulesOptions, PreviewFormat,
+    PreviewOptions, ReportFormat, ReviewOptions, SampleFormat, SampleOptions, SchemaReportOptions,
+    SearchFormat, SearchOptions, ServeOptions, Sink, StatsFormat, StatsOptions, Target,
+    TrendFormat, TrendOptions, VerifyBundleOptions, WorklistFormat, WorklistOptions,
+};
+use check_translations::codeowners::CodeOwners;
+use check_translations::config::{self, Config, FileFormatConfig};
+use check_translations::content::ContentRules;
+use check_translations::context::Manifest;
+use check_translations::daemon;
+use check_translations::fix::Transaction;
+use check_translations::handles;
+use check_translations::hooks;
+use check_translations::interactive::{self, Mark};
+use check_translations::jsonrpc;
+use check_translations::loader;
+use check_translations::metrics;
+use check_translations::model::{self, Layout, Project};
+use check_translations::normalize;
+use check_translations::partition;
+use check_translations::patch;
+use check_translations::plural_rules;
+use check_translations::preview::Preview;
+use check_translations::render::html::{HtmlRenderer, KeyValues};
+use check_translations::render::human::{GroupBy, HumanOptions, HumanRenderer, Style};
+use check_translations::render::json::{self, JsonRenderer};
+use check_translations::render::junit::{JunitRenderer, JunitSuites};
+use check_translations::render::plain::PlainRenderer;
+use check_translations::render::sarif::SarifRenderer;
+use check_translations::render::{self, Renderer};
+use check_translations::report::{Finding, Report, Severity};
+use check_translations::review::{ReviewStatus, review_file};
+use check_translations::rollup;
+use check_translations::sample::Sample;
+use check_translations::schema::Schema;
+use check_translations::scope::{self, Scope, Touched};
+use check_translations::search::{self, Index};
+use check_translations::setup::{self, Setup};
+use check_translations::sheet::{Sheet, SheetFormat};
+use check_translations::state::{STATE_FILE, State};
+use check_translations::stats::Stats;
+use check_translations::sync;
+use check_translations::timings::Timings;
+use check_translations::trend::Trend;
+use check_translations::unicode;
+use check_translations::usage::{self, describe_roots};
+use check_translations::waivers::{self, Waivers};
+use check_translations::watch::{self, Watcher};
+use check_translations::worklist::{Section, Worklist};
 use colored::*;
-use dashmap::{DashMap, DashSet};
-use glob::glob;
-use lazy_static::lazy_static;
-use rayon::prelude::*;
-use regex::Regex;
-use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::cell::{RefCell, RefMut};
+use std::collections::{BTreeMap, HashSet};
 use std::env;
+use std::fmt::Display;
 use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::process;
-use std::str;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-lazy_static! {
-    static ref TRANSLATION_VAR_REGEX: Regex = Regex::new(r"\{(\w+)}").unwrap();
+fn exit_with_error(err: impl Display) -> ! {
+    eprintln!("{} {}", "❌".red(), err);
+    process::exit(2);
 }
 
-// Extracts variables like `{productName}` format from a translation string
-fn extract_variables(text: &str) -> HashSet<String> {
-    TRANSLATION_VAR_REGEX
-        .captures_iter(text)
-        .map(|cap| cap[1].to_string())
-        .collect()
+// Where a check run prints: the streams of the process, or buffers sent
+// back to the client a `serve` server runs it for.
+enum Streams {
+    Standard,
+    Captured {
+        out: RefCell<Vec<u8>>,
+        err: RefCell<Vec<u8>>,
+    },
 }
 
-fn get_translation_file<'a>(
-    file_mapping: &'a Arc<DashMap<String, HashMap<String, String>>>,
-    lang: &str,
-    key: &str,
-) -> String {
-    file_mapping
-        .get(lang)
-        .and_then(|fm| fm.get(key).map(|s| s.clone()))
-        .unwrap_or_else(|| "Unknown file".to_string())
-}
-
-fn flatten_json(value: &Value, prefix: String, output: &DashMap<String, String>) {
-    let mut stack = vec![(prefix, value)];
-
-    while let Some((curr_prefix, curr_value)) = stack.pop() {
-        match curr_value {
-            Value::Object(map) => {
-                for (key, val) in map {
-                    let new_key = if curr_prefix.is_empty() {
-                        key.clone()
-                    } else {
-                        format!("{}.{}", curr_prefix, key)
-                    };
-                    stack.push((new_key, val));
+struct Buffer<'a>(RefMut<'a, Vec<u8>>);
+
+impl Write for Buffer<'_> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.write(bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Streams {
+    fn captured() -> Streams {
+        Streams::Captured {
+            out: RefCell::default(),
+            err: RefCell::default(),
+        }
+    }
+
+    fn out(&self) -> Box<dyn Write + '_> {
+        match self {
+            Streams::Standard => Box::new(io::stdout().lock()),
+            Streams::Captured { out, .. } => Box::new(Buffer(out.borrow_mut())),
+        }
+    }
+
+    fn err(&self) -> Box<dyn Write + '_> {
+        match self {
+            Streams::Standard => Box::new(io::stderr().lock()),
+            Streams::Captured { err, .. } => Box::new(Buffer(err.borrow_mut())),
+        }
+    }
+
+    // What a captured run printed on stdout and stderr.
+    fn taken(self) -> (String, String) {
+        match self {
+            Streams::Standard => (String::new(), String::new()),
+            Streams::Captured { out, err } => (
+                String::from_utf8_lossy(&out.into_inner()).into_owned(),
+                String::from_utf8_lossy(&err.into_inner()).into_owned(),
+            ),
+        }
+    }
+}
+
+// Says which language the base was auto-detected as, or which looks more
+// like the base than the default.
+fn announce_base_lang(options: &Options, err: &mut dyn Write) {
+    if options.base_lang_origin == BaseLangOrigin::Detected {
+        let _ = writeln!(
+            err,
+            "{} {}",
+            "🔎".yellow(),
+            format!(
+                "using '{}' as base language (auto-detected); pass --base-lang to override",
+                options.base_lang
+            )
+            .yellow()
+            .bold()
+        );
+    }
+    if let Some((rival, default)) = &options.base_lang_rival {
+        let _ = writeln!(
+            err,
+            "{} '{}' looks more like the base language than '{}': {} against {}; pass --base-lang {} or set `languages.base` to use it",
+            "⚠️".yellow(),
+            rival.lang,
+            default.lang,
+            rival.describe(),
+            default.describe(),
+            rival.lang
+        );
+    }
+}
+
+// The configuration and base language of a subcommand reading the
+// translations, as the check run would take them.
+fn load_setup(target: &Target) -> Result<Setup, String> {
+    let setup = Setup::load(target)?;
+    announce_base_lang(&setup.options, &mut io::stderr());
+    Ok(setup)
+}
+
+// `review approve`: records the current values of the listed keys as
+// approved in the language's review status file.
+fn approve_reviews(review: &ReviewOptions) -> Result<(), String> {
+    let timings = Timings::default();
+    let i18n_dir = match &review.i18n_dir {
+        dir if dir.as_os_str().is_empty() => match Config::discover(None)?.project.i18n_dir {
+            Some(dir) => dir,
+            None => cli::find_i18n_dir()?,
+        },
+        dir => dir.clone(),
+    };
+    // Only the reviewed language is read.
+    let project = Project::discover(&i18n_dir, &review.lang, false, &timings);
+    let data = project
+        .get(&review.lang)
+        .ok_or_else(|| format!("no `{}` folder in {}", review.lang, i18n_dir.display()))?;
+    let reviewer = review
+        .reviewer
+        .clone()
+        .or_else(|| env::var("USER").ok())
+        .ok_or("`review approve` requires `--reviewer <name>`")?;
+
+    let keys: Vec<String> = fs::read_to_string(&review.keys_from)
+        .map_err(|err| format!("failed to read {}: {}", review.keys_from.display(), err))?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+
+    let path = review_file(&project, &review.lang);
+    let mut status = ReviewStatus::load(&path)?.unwrap_or_default();
+    let missing = status.approve(data, &keys, &reviewer);
+    for key in &missing {
+        eprintln!(
+            "{} `{}` has no value in {}, not approved",
+            "⚠️".yellow(),
+            key,
+            review.lang
+        );
+    }
+    status.write(&path)?;
+    eprintln!(
+        "Approved {} keys in {}",
+        keys.len() - missing.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+// `--fix nfc`: decomposed text written in NFC. Only the composed
+// characters change, so the formatting is kept.
+fn normalize(content: String) -> String {
+    match unicode::nfc(&content) {
+        Cow::Owned(normalized) => normalized,
+        Cow::Borrowed(_) => content,
+    }
+}
+
+// `--fix translated-placeholders`: a value whose only mismatch with the
+// base is a renamed placeholder, like `{nombre}` for `{name}`, with the base
+// name back. Only its placeholders change. Several renamed placeholders are
+// left for a translator, as which one became which can't be told.
+fn rename_placeholder(base: &Base, key: &str, value: &str, literal: &str) -> Option<String> {
+    let base_vars = base.placeholders.get(key)?;
+    let (found, expected) = checks::renamed_placeholder(base_vars, base.value(key), value)?;
+    // The literal keeps its escapes; a placeholder spelled with escapes is
+    // left alone.
+    let literal = literal.replace(&format!("{{{}}}", found), &format!("{{{}}}", expected));
+    serde_json::from_str::<String>(&literal)
+        .is_ok_and(|value| !extract_variables(&value).contains(&found))
+        .then_some(literal)
+}
+
+// `--fix quote-escapes`: the quotes `checks::quotes` knows the spelling of.
+// The value is written back whole, keeping the escapes of the literal for
+// everything but the fixed quotes when possible.
+fn fix_quote_escapes(key: &str, value: &str, literal: &str) -> Option<String> {
+    let fixed = checks::quotes::fix_quotes(key, value)?;
+    let mut kept = literal.replace("\\\\'", "'").replace("\\\\\\\"", "\\\"");
+    for (entity, quote) in [("&apos;", "'"), ("&#39;", "'"), ("&#x27;", "'")] {
+        kept = kept.replace(entity, quote);
+    }
+    for entity in ["&quot;", "&#34;"] {
+        kept = kept.replace(entity, "\\\"");
+    }
+    if serde_json::from_str::<String>(&kept).is_ok_and(|value| value == fixed) {
+        Some(kept)
+    } else {
+        Some(serde_json::to_string(&fixed).unwrap())
+    }
+}
+
+// Rewrites the string literals of a translation file; `edit` gets the key,
+// the value and the literal, quotes and escapes included, and returns its
+// replacement or `None` to leave it.
+fn rewrite_literals(
+    content: &str,
+    edit: impl Fn(&str, &str, &str) -> Option<String>,
+) -> Result<String, String> {
+    let mut content = content.to_string();
+    for (key, range) in loader::value_spans(&content)?.into_iter().rev() {
+        let literal = &content[range.clone()];
+        let Ok(value) = serde_json::from_str::<String>(literal) else {
+            continue;
+        };
+        if let Some(replacement) = edit(&key, &value, literal) {
+            content.replace_range(range, &replacement);
+        }
+    }
+    Ok(content)
+}
+
+// The fixes rewriting translation files, applied in their order to the
+// text of each file before anything is written: a file is rewritten once
+// whatever the combination, never holds the state between two fixes, and a
+// second run finds nothing left to fix.
+fn fix_files(
+    project: &Project,
+    fixes: &[Fix],
+    file_format: Option<&FileFormatConfig>,
+) -> Result<Transaction, String> {
+    let base = Base::language(project, project.base_lang());
+    let mut transaction = Transaction::default();
+    for lang in project.languages() {
+        for path in project.files(lang) {
+            let original = fs::read_to_string(&path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+            // A base module is only normalized: its literals aren't JSON.
+            let is_json = path.extension().is_some_and(|ext| ext == "json");
+            let parse_error = |err| format!("failed to parse {}: {}", path.display(), err);
+            let mut content = original.clone();
+            for fix in fixes {
+                if base.lang == Some(lang) && !fix.writes_base() {
+                    continue;
                 }
+                content = match fix {
+                    Fix::Nfc => normalize(content),
+                    Fix::TranslatedPlaceholders if is_json => {
+                        rewrite_literals(&content, |key, value, literal| {
+                            rename_placeholder(&base, key, value, literal)
+                        })
+                        .map_err(parse_error)?
+                    }
+                    Fix::QuoteEscapes if is_json => {
+                        rewrite_literals(&content, fix_quote_escapes).map_err(parse_error)?
+                    }
+                    Fix::Eol if is_json => match file_format {
+                        Some(format) => checks::line_endings::fix_line_endings(&content, format),
+                        None => content,
+                    },
+                    _ => content,
+                };
             }
-            Value::String(text) => {
-                output.insert(curr_prefix, text.clone());
+            if content != original {
+                transaction.rewrite(path, content);
             }
-            _ => {}
         }
     }
+    Ok(transaction)
 }
 
-fn extract_keys_from_content(content: &str, base_keys: &HashSet<String>) -> HashSet<String> {
-    let used_keys: HashSet<String> = base_keys
-        .par_iter()
-        .filter(|key| content.contains(*key))
-        .cloned()
-        .collect();
+// 1 when the run found errors and, with `--fail-on warnings`, another code
+// when it found warnings only.
+fn exit_code(report: &Report, options: &Options) -> i32 {
+    if report.has_errors() {
+        1
+    } else if options.fail_on.contains(&FailOn::Warnings) && report.weight(Severity::Warning) > 0 {
+        cli::WARNINGS_EXIT_CODE
+    } else {
+        0
+    }
+}
 
-    used_keys
+fn fix_names(fixes: &[Fix]) -> String {
+    let names: Vec<&str> = fixes.iter().map(|fix| fix.name()).collect();
+    names.join(",")
 }
 
-fn get_all_files_by_extension(path: &Path, extension: &str) -> Vec<PathBuf> {
-    let mut files = Vec::new();
+// Whether `--allow-base-writes` lets one of `fixes` rewrite the base.
+fn base_writes_allowed(options: &Options, fixes: &[Fix]) -> bool {
+    options.allow_base_writes && fixes.iter().any(|fix| fix.writes_base())
+}
 
-    if let Ok(entries) = path.read_dir() {
-        files.extend(entries.filter_map(Result::ok).flat_map(|entry| {
-            let path = entry.path();
-            if path.is_dir() {
-                get_all_files_by_extension(&path, extension)
-            } else if path.extension().and_then(|ext| ext.to_str()) == Some(extension) {
-                vec![path]
-            } else {
-                vec![]
-            }
-        }));
+// Leaves the base language files out of a fix, each with a warning,
+// unless `--allow-base-writes` is given for a fix meant to write them.
+// Returns the files left alone.
+fn protect_base(
+    transaction: &mut Transaction,
+    project: &Project,
+    options: &Options,
+    fixes: &[Fix],
+) -> Vec<PathBuf> {
+    let refused = transaction.protect(
+        &project.files(project.base_lang()),
+        base_writes_allowed(options, fixes),
+    );
+    for path in &refused {
+        eprintln!(
+            "{} Left {} alone: `--fix {}` only rewrites base language files with `--allow-base-writes`",
+            "⚠️".yellow(),
+            path.display(),
+            fix_names(fixes)
+        );
     }
-    files
+    refused
 }
 
-fn process_files(files: &[PathBuf], base_keys: &HashSet<String>) -> HashSet<String> {
-    let used_keys: HashSet<String> = files
-        .par_iter()
-        .filter_map(|file_path| {
-            if let Ok(content) = fs::read_to_string(file_path) {
-                Some(extract_keys_from_content(&content, base_keys))
-            } else {
-                None
-            }
+// Prints the diff of a fix with `--dry-run`, and applies it otherwise.
+// Returns whether translation files changed.
+fn apply_fix(
+    transaction: &Transaction,
+    options: &Options,
+    fixes: &[Fix],
+    done: impl Fn(usize) -> String,
+) -> bool {
+    if options.dry_run {
+        let _ = transaction.write_diff(&mut io::stderr());
+        return false;
+    }
+    if transaction.is_empty() {
+        eprintln!("0 files modified by `--fix {}`", fix_names(fixes));
+        return false;
+    }
+    let fixed = transaction.commit(options.backup_dir.as_deref());
+    if let Some(err) = &fixed.error {
+        eprintln!("Fix report:");
+        let _ = fixed.write_text(&mut io::stderr());
+        exit_with_error(format!("{}; {}", err, fixed.state()));
+    }
+    if fixed.files.is_empty() {
+        return false;
+    }
+    eprintln!("{}", done(fixed.applied()));
+    let _ = fixed.write_text(&mut io::stderr());
+    true
+}
+
+// `--fix hygiene`: deletes the operating system metadata and editor
+// backups of the translation directory once confirmed, on stdin or by
+// `--yes`. A file that parses as translations is kept whatever its name.
+fn delete_junk(project: &Project, options: &Options) {
+    let (junk, kept): (Vec<_>, Vec<_>) = checks::hygiene::stray_files(project)
+        .into_iter()
+        .filter(|stray| stray.problem.is_junk())
+        .map(|stray| stray.path)
+        .partition(|path| !checks::hygiene::parses_as_translations(path));
+    for path in &kept {
+        eprintln!(
+            "Keeping {}: it parses as a translation file",
+            path.display().to_string().escape_debug()
+        );
+    }
+    if junk.is_empty() {
+        if !options.dry_run {
+            eprintln!("0 files modified by `--fix hygiene`");
+        }
+        return;
+    }
+    let verb = if options.dry_run {
+        "Would delete"
+    } else {
+        "Deleting"
+    };
+    eprintln!("{} {} junk files:", verb, junk.len());
+    for path in &junk {
+        eprintln!("   - {}", path.display().to_string().escape_debug());
+    }
+    if options.dry_run {
+        return;
+    }
+    if !options.yes {
+        eprint!("Delete them? [y/N] ");
+        let mut answer = String::new();
+        let _ = io::stdin().read_line(&mut answer);
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            eprintln!("No file was deleted");
+            return;
+        }
+    }
+    for path in &junk {
+        fs::remove_file(path).unwrap_or_else(|err| {
+            exit_with_error(format!("failed to delete {}: {}", path.display(), err))
+        });
+    }
+    eprintln!("Deleted {} junk files", junk.len());
+}
+
+// `--interactive`: reviews the findings on the terminal, then applies the
+// marks given. Deleted and copied keys are written in one transaction, as
+// `--fix` writes them, through `--dry-run` and `--backup-dir`; accepted
+// findings are added to the `--baseline` file. What was applied leaves the
+// report.
+fn review_findings(
+    report: &mut Report,
+    project: &Project,
+    base: &Base,
+    baseline: Option<Baseline>,
+    options: &Options,
+) {
+    let accepts = baseline.is_some();
+    let marks = if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        interactive::review(project, base, &report.findings, accepts)
+    } else {
+        interactive::review_keys(
+            project,
+            base,
+            &report.findings,
+            accepts,
+            &mut io::stdin().lock(),
+            &mut io::stdout(),
+            100,
+        )
+    }
+    .unwrap_or_else(|err| exit_with_error(format!("failed to run the review: {}", err)));
+    let Some(marks) = marks else {
+        eprintln!("Review abandoned; nothing was written");
+        return;
+    };
+    let (transaction, edited) = interactive::edits(project, base, &report.findings, &marks)
+        .unwrap_or_else(|err| exit_with_error(err));
+    for (index, mark) in &marks {
+        if *mark != Mark::Accept && !edited.contains(index) {
+            eprintln!(
+                "{} Couldn't {} `{}`; it is left as it is",
+                "⚠️".yellow(),
+                mark.describe(),
+                report.findings[*index].key.as_deref().unwrap_or_default()
+            );
+        }
+    }
+    let written = if options.dry_run || !transaction.is_empty() {
+        apply_fix(&transaction, options, &[], |count| {
+            format!("Edited {} translation files", count)
         })
-        .flatten()
+    } else {
+        false
+    };
+
+    let accepted: Vec<usize> = marks
+        .iter()
+        .filter(|(_, mark)| **mark == Mark::Accept)
+        .map(|(index, _)| *index)
         .collect();
+    // Accepting is only offered with a baseline.
+    if let (Some(mut baseline), Some(path)) = (baseline, &options.baseline)
+        && !accepted.is_empty()
+    {
+        if options.dry_run {
+            eprintln!(
+                "Would accept {} findings into the baseline {}",
+                accepted.len(),
+                path.display()
+            );
+        } else {
+            for index in &accepted {
+                baseline.add(&report.findings[*index]);
+            }
+            baseline
+                .write(path)
+                .unwrap_or_else(|err| exit_with_error(err));
+            eprintln!(
+                "{} findings accepted into the baseline {}",
+                accepted.len(),
+                path.display()
+            );
+            *report.baselined.get_or_insert(0) += accepted
+                .iter()
+                .map(|index| report.findings[*index].weight)
+                .sum::<usize>();
+        }
+    }
+    if options.dry_run {
+        return;
+    }
+    let mut index = 0;
+    report.findings.retain(|_| {
+        index += 1;
+        let applied = match marks.get(&(index - 1)) {
+            Some(Mark::Accept) => true,
+            Some(_) => written && edited.contains(&(index - 1)),
+            None => false,
+        };
+        !applied
+    });
+}
+
+// `merge-reports`: combines the partial JSON reports of a partitioned run
+// into the full report. Returns whether it has errors.
+fn merge_reports(merge: &MergeOptions) -> Result<bool, String> {
+    let fragments = merge
+        .reports
+        .iter()
+        .map(|path| {
+            let source = fs::read_to_string(path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+            let report = json::parse(&source)
+                .map_err(|err| format!("invalid report {}: {}", path.display(), err))?;
+            Ok((path.display().to_string(), report))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut report = partition::merge(fragments)?;
+    if merge.rollup
+        && let Some(rolled) = rollup::roll_up(&report, rollup::DEFAULT_THRESHOLD)
+    {
+        report = rolled;
+    }
+
+    match &merge.output {
+        Some(path) => render::write_atomically(path, |out| JsonRenderer::new(out).render(&report))
+            .map_err(|err| format!("failed to write report {}: {}", path.display(), err))?,
+        None => JsonRenderer::new(io::stdout().lock())
+            .render(&report)
+            .map_err(|err| format!("failed to write report: {}", err))?,
+    }
+    eprintln!(
+        "Merged {} partial reports: {} errors, {} warnings",
+        merge.reports.len(),
+        report.weight(Severity::Error),
+        report.weight(Severity::Warning)
+    );
+    Ok(report.has_errors())
+}
 
-    used_keys
+// Renders `report` to every sink; files are written plain and the console
+// follows `console`.
+fn write_reports(
+    report: &Report,
+    sinks: &[Sink],
+    values: &KeyValues,
+    human_options: HumanOptions,
+    junit_suites: JunitSuites,
+    console: Style,
+    streams: &Streams,
+) -> Result<(), String> {
+    for sink in sinks {
+        let render = |out: &mut dyn Write, style: Style| match sink.format {
+            ReportFormat::Human => HumanRenderer::new(out, style, human_options).render(report),
+            ReportFormat::Json => JsonRenderer::new(out).render(report),
+            ReportFormat::Junit => JunitRenderer::new(out, junit_suites).render(report),
+            ReportFormat::Html => HtmlRenderer::new(out, values).render(report),
+            ReportFormat::Plain => PlainRenderer::new(out).render(report),
+            ReportFormat::Sarif => SarifRenderer::new(out).render(report),
+        };
+        match &sink.path {
+            Some(path) => render::write_atomically(path, |out| render(out, Style::PLAIN))
+                .map_err(|err| format!("failed to write report {}: {}", path.display(), err))?,
+            None => render(&mut streams.out(), console)
+                .map_err(|err| format!("failed to write report: {}", err))?,
+        }
+    }
+    Ok(())
 }
 
-fn check_translations_usage(base_keys: &HashSet<String>, files: &[PathBuf]) -> HashSet<String> {
-    let used_keys = process_files(files, base_keys);
+// `verify-bundle`: compares the translations the build output ships with
+// the translation folder, for releases to catch a language or keys the
+// bundler dropped. Returns whether errors were found.
+fn verify_bundle(options: &VerifyBundleOptions) -> Result<bool, String> {
+    let setup = load_setup(&options.target)?;
+    let config = &setup.config;
+    if !options.dist.is_dir() {
+        return Err(format!("no build output in {}", options.dist.display()));
+    }
+    let timings = Timings::default();
+    let project = setup.project(&timings);
+    project.load_all();
+    let languages: Vec<&str> = project.languages().collect();
+    let bundle = Bundle::scan(&options.dist, &config.bundle.assets, &languages)?;
 
-    let unused_keys: HashSet<_> = base_keys.difference(&used_keys).cloned().collect();
+    let mut report = Report {
+        config: Some(serde_json::json!({
+            "i18n_dir": setup.i18n_dir().display().to_string(),
+            "base_lang": setup.base_lang(),
+            "dist": options.dist.display().to_string(),
+        })),
+        ..Report::default()
+    };
+    report.extend(bundle.compare(&project, &options.dist));
+    report.sort();
 
-    unused_keys
+    let console = Style {
+        color: colored::control::SHOULD_COLORIZE.should_colorize(),
+        emoji: true,
+    };
+    let human_options = HumanOptions {
+        quiet: false,
+        max_findings: None,
+        group_by: GroupBy::Language,
+        coverage_only: false,
+    };
+    write_reports(
+        &report,
+        &options.reports,
+        &KeyValues::new(),
+        human_options,
+        JunitSuites::Language,
+        console,
+        &Streams::Standard,
+    )?;
+    eprintln!(
+        "Read {} built files holding {} of {} languages",
+        bundle.files,
+        bundle.languages.len(),
+        languages.len()
+    );
+    Ok(report.has_errors())
 }
 
-fn check_translations(
-    base_lang: &str,
-    translations: Arc<DashMap<String, HashMap<String, String>>>,
-    file_mapping: Arc<DashMap<String, HashMap<String, String>>>,
-    unused_keys: &DashSet<String>,
-) -> bool {
-    let base_translation = translations.get("fr").unwrap();
-    let base_keys: HashSet<_> = base_translation.keys().collect();
-    let has_errors = Arc::new(AtomicBool::new(false));
+// The findings of the checks a translator works through, for `worklist` and
+// `export`.
+fn work_findings(
+    base: &Base,
+    project: &Project,
+    config: &Config,
+    schema: Option<&Schema>,
+) -> Vec<Finding> {
+    let mut findings = check_translations(base, project, &HashSet::new(), schema, false, 0);
+    // What is kept in the base only is no one's work.
+    checks::base_only::check_base_only(base, project, &config.base_only_namespaces, &mut findings);
+    findings
+}
 
-    let impacted_files = DashSet::new();
+// `worklist`: lists what a language still needs, missing and empty
+// translations first, for a translator to work through.
+fn write_worklist(options: &WorklistOptions) -> Result<(), String> {
+    let setup = load_setup(&options.target)?;
+    let config = &setup.config;
+    let timings = Timings::default();
+    let project = setup.project(&timings);
+    if project.get(&options.lang).is_none() {
+        return Err(format!(
+            "no `{}` folder in {}",
+            options.lang,
+            setup.i18n_dir().display()
+        ));
+    }
+    project.load_all();
+    let base = Base::language(&project, setup.base_lang());
+    let schema = options
+        .schema_snapshot
+        .as_ref()
+        .filter(|path| path.exists())
+        .map(|path| Schema::load(path))
+        .transpose()?;
+    let findings = work_findings(&base, &project, config, schema.as_ref());
 
-    translations.iter().par_bridge().for_each(|entry| {
-        let (lang, keys) = entry.pair();
-        if lang == base_lang {
-            return;
+    let sections = options
+        .sections
+        .as_deref()
+        .or(config.worklist_sections.as_deref())
+        .unwrap_or(&Section::ALL);
+    let mut worklist = Worklist::new(&project, &base, &options.lang, &findings, sections);
+    if let Some(context) = &config.context {
+        worklist = worklist.with_context(&Manifest::load(&context.manifest)?, context.max_links);
+    }
+    let text = match options.format {
+        WorklistFormat::Markdown => worklist.to_markdown(),
+        WorklistFormat::Csv => worklist.to_csv(),
+    };
+    match &options.output {
+        Some(path) => {
+            render::write_atomically(path, |out| out.write_all(text.as_bytes()))
+                .map_err(|err| format!("failed to write worklist {}: {}", path.display(), err))?;
+            eprintln!("{} items written to {}", worklist.len(), path.display());
         }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
 
-        let other_keys: HashSet<_> = keys.keys().collect();
-        let missing_keys: Vec<_> = base_keys.difference(&other_keys).collect();
-        let extra_keys: Vec<_> = other_keys.difference(&base_keys).collect();
-
-        let mut local_errors = false;
+// `export`: writes the missing and empty translations of a language as a
+// CSV or XLSX sheet for translators to fill in, and `import` to merge back.
+fn export_sheet(options: &ExportOptions) -> Result<(), String> {
+    let setup = load_setup(&options.target)?;
+    let config = &setup.config;
+    let timings = Timings::default();
+    let project = setup
+        .project(&timings)
+        .retain_languages(|lang| lang == options.lang);
+    if project.get(&options.lang).is_none() {
+        return Err(format!(
+            "no `{}` folder in {}",
+            options.lang,
+            setup.i18n_dir().display()
+        ));
+    }
+    let base = Base::language(&project, setup.base_lang());
+    let findings = work_findings(&base, &project, config, None);
 
-        if !missing_keys.is_empty() {
-            println!("{}", "❌ Missing keys:".bold().red());
-            for key in &missing_keys {
-                let file = get_translation_file(&file_mapping, lang, key);
-                println!("   - Key: {} | File: {}", key.red(), file.blue());
-            }
-            local_errors = true;
+    let sheet = Sheet::export(&project, &base, &options.lang, &findings);
+    let content = match options.format {
+        SheetFormat::Csv => sheet.to_csv().into_bytes(),
+        SheetFormat::Xlsx => sheet.to_xlsx(),
+    };
+    match &options.output {
+        Some(path) => {
+            render::write_atomically(path, |out| out.write_all(&content))
+                .map_err(|err| format!("failed to write sheet {}: {}", path.display(), err))?;
+            eprintln!("{} keys written to {}", sheet.rows.len(), path.display());
         }
+        None => io::stdout()
+            .write_all(&content)
+            .map_err(|err| err.to_string())?,
+    }
+    Ok(())
+}
 
-        if !extra_keys.is_empty() {
-            println!("{}", "⚠️ Extra keys:".bold().yellow());
-            for key in &extra_keys {
-                let file = get_translation_file(&file_mapping, lang, key);
-                println!("   - Key: {} | File: {}", key.yellow(), file.blue());
-            }
-            local_errors = true;
+// `import`: merges the translations of a sheet written by `export` into the
+// JSON files of its language, in one transaction as `--fix` writes them.
+fn import_sheet(options: &ImportOptions) -> Result<(), String> {
+    let setup = load_setup(&options.target)?;
+    let sheet = Sheet::read(&options.sheet)?;
+    let lang = model::parse_lang(&sheet.lang, "the language column")?;
+    if lang == setup.base_lang() {
+        return Err(format!(
+            "{} translates the base language `{}`, which `import` never writes",
+            options.sheet.display(),
+            lang
+        ));
+    }
+    let timings = Timings::default();
+    let project = setup
+        .project(&timings)
+        .retain_languages(|other| other == lang);
+    if project.get(&lang).is_none() {
+        return Err(format!(
+            "no `{}` folder in {}",
+            lang,
+            setup.i18n_dir().display()
+        ));
+    }
+    let base = Base::language(&project, setup.base_lang());
+    let import = sheet.import(&project, &base)?;
+    for (key, reason) in &import.skipped {
+        eprintln!("{} Skipped `{}`: {}", "⚠️".yellow(), key, reason);
+    }
+    if options.dry_run {
+        return import
+            .transaction
+            .write_diff(&mut io::stderr())
+            .map_err(|err| err.to_string());
+    }
+    let imported = import.transaction.commit(options.backup_dir.as_deref());
+    if let Some(err) = &imported.error {
+        eprintln!("Import report:");
+        let _ = imported.write_text(&mut io::stderr());
+        return Err(format!("{}; {}", err, imported.state()));
+    }
+    eprintln!(
+        "{} translations imported into {} files",
+        import.imported,
+        imported.applied()
+    );
+    let _ = imported.write_text(&mut io::stderr());
+    Ok(())
+}
+
+// `sample`: draws keys of a language for a manual review, the same ones
+// for the same seed, with their values and findings.
+fn sample(options: &SampleOptions) -> Result<(), String> {
+    let setup = load_setup(&options.target)?;
+    let timings = Timings::default();
+    let project = setup
+        .project(&timings)
+        .retain_languages(|lang| lang == options.lang);
+    if project.get(&options.lang).is_none() {
+        return Err(format!(
+            "no `{}` folder in {}",
+            options.lang,
+            setup.i18n_dir().display()
+        ));
+    }
+    let base = Base::language(&project, setup.base_lang());
+    let findings = check_translations(&base, &project, &HashSet::new(), None, false, 0);
+    let state = State::load(&options.state)?;
+    let reviews = if options.exclude_reviewed {
+        ReviewStatus::load(&review_file(&project, &options.lang))?
+    } else {
+        None
+    };
+    let seed = options.seed.clone().unwrap_or_else(|| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let seed = format!("{:x}", now.as_nanos());
+        eprintln!(
+            "Drawn with `--seed {}`, which draws the same sample again",
+            seed
+        );
+        seed
+    });
+
+    let sample = Sample::new(
+        &project,
+        &base,
+        &options.lang,
+        options.count,
+        &seed,
+        state.as_ref(),
+        reviews.as_ref(),
+    )
+    .with_findings(&findings);
+    let text = match options.format {
+        SampleFormat::Markdown => sample.to_markdown(),
+        SampleFormat::Csv => sample.to_csv(),
+    };
+    match &options.output {
+        Some(path) => {
+            render::write_atomically(path, |out| out.write_all(text.as_bytes()))
+                .map_err(|err| format!("failed to write sample {}: {}", path.display(), err))?;
+            eprintln!("{} keys written to {}", sample.items.len(), path.display());
         }
+        None => print!("{}", text),
+    }
+    if let Some(path) = &options.keys_output {
+        render::write_atomically(path, |out| out.write_all(sample.to_keys().as_bytes()))
+            .map_err(|err| format!("failed to write sampled keys {}: {}", path.display(), err))?;
+        eprintln!(
+            "Record the review with `review approve --lang {} --keys-from {}`",
+            options.lang,
+            path.display()
+        );
+    }
+    Ok(())
+}
 
-        for key in base_keys.intersection(&other_keys) {
-            let base_vars =
-                extract_variables(translations.get(base_lang).unwrap().get(*key).unwrap());
-            let other_vars = extract_variables(translations.get(lang).unwrap().get(*key).unwrap());
+// `preview`: renders a value with sample variables in each requested
+// language. Returns whether anything was called out.
+fn preview(options: &PreviewOptions) -> Result<bool, String> {
+    let setup = load_setup(&options.target)?;
+    let timings = Timings::default();
+    let project = setup.project(&timings);
+    let langs: Vec<String> = if options.all_langs {
+        project.languages().map(str::to_string).collect()
+    } else if options.langs.is_empty() {
+        vec![setup.base_lang().to_string()]
+    } else {
+        options.langs.clone()
+    };
+    if let Some(lang) = langs.iter().find(|lang| project.get(lang).is_none()) {
+        return Err(format!(
+            "no `{}` folder in {}",
+            lang,
+            setup.i18n_dir().display()
+        ));
+    }
 
-            if base_vars != other_vars {
-                let base_file = get_translation_file(&file_mapping, base_lang, key);
-                let other_file = get_translation_file(&file_mapping, lang, key);
+    let preview = Preview::new(&project, &options.key, &langs, options.vars.clone());
+    match options.format {
+        PreviewFormat::Text => preview
+            .write_text(&mut io::stdout().lock(), true)
+            .map_err(|err| format!("failed to write preview: {}", err))?,
+        PreviewFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&preview.to_json(&project)).unwrap()
+        ),
+    }
+    let problems = preview.problems(&project);
+    for problem in &problems {
+        eprintln!("{} {}", "⚠️".yellow(), problem);
+    }
+    Ok(!problems.is_empty())
+}
 
-                println!("{}", "🔄 Variable mismatch detected!".bold().magenta());
-                println!("   - Key: {}", key.magenta());
-                println!(
-                    "   - Expected variables ({}): {}",
-                    base_lang.to_uppercase().bold(),
-                    format!("{:?}", base_vars).green()
-                );
-                println!(
-                    "   - Found variables ({}): {}",
-                    lang.to_uppercase().bold(),
-                    format!("{:?}", other_vars).cyan()
-                );
-                println!(
-                    "   - Location: Expected in {} but found in {}",
-                    base_file.yellow(),
-                    other_file.blue()
-                );
+// `search`: the keys whose name or value holds the query, best first.
+// Returns whether anything matched.
+fn search(options: &SearchOptions) -> Result<bool, String> {
+    match options.color {
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+        ColorChoice::Auto => {}
+    }
+    let setup = load_setup(&options.target)?;
+    let timings = Timings::default();
+    let project = setup.project(&timings);
+    let lang = options.lang.as_deref().unwrap_or(setup.base_lang());
+    let data = project
+        .get(lang)
+        .ok_or_else(|| format!("no `{}` folder in {}", lang, setup.i18n_dir().display()))?;
 
-                impacted_files.insert(base_file);
-                impacted_files.insert(other_file);
-                local_errors = true;
+    let mut matches = Index::new(data).search(&options.query, &options.fields, options.fuzzy);
+    let total = matches.len();
+    matches.truncate(options.limit);
+    match options.format {
+        SearchFormat::Text => {
+            search::write_text(&mut io::stdout().lock(), lang, &matches)
+                .map_err(|err| format!("failed to write matches: {}", err))?;
+            if total > matches.len() {
+                eprintln!(
+                    "Showing the best {} of {} matches; `--limit` shows more",
+                    matches.len(),
+                    total
+                );
             }
         }
+        SearchFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&search::to_json(
+                &options.query,
+                lang,
+                &options.fields,
+                total,
+                &matches
+            ))
+            .unwrap()
+        ),
+    }
+    if total == 0 {
+        eprintln!("Nothing matches \"{}\" in {}", options.query, lang);
+    }
+    Ok(total > 0)
+}
 
-        for key in unused_keys.iter() {
-            let local_key = key.as_str();
+// `trend`: how coverage moved over the history kept in the state file.
+fn trend(options: &TrendOptions) -> Result<(), String> {
+    let state = State::load(&options.state)?
+        .ok_or_else(|| format!("no state file at {}", options.state.display()))?;
+    let trend = Trend::new(&state.history).ok_or_else(|| {
+        format!(
+            "{} has no coverage history yet; it is recorded by `--update-state`",
+            options.state.display()
+        )
+    })?;
+    match options.format {
+        TrendFormat::Text => trend
+            .write_text(&mut io::stdout().lock())
+            .map_err(|err| format!("failed to write trend: {}", err)),
+        TrendFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&trend.to_json()).unwrap()
+            );
+            Ok(())
+        }
+    }
+}
+
+// `lint-config`: whether the config file and the content rules load,
+// without checking anything.
+fn lint_config(options: &LintOptions) -> Result<(), String> {
+    let config = Config::discover(options.config.as_deref())?;
+    match &config.source {
+        Some(source) => println!("{} is valid", source.display()),
+        None => println!("No {} found; the defaults apply", config::CONFIG_FILE),
+    }
+    if let Some(context) = &config.context {
+        let manifest = Manifest::load(&context.manifest)?;
+        println!(
+            "{} is valid: {} key patterns, {} links",
+            context.manifest.display(),
+            manifest.entries.len(),
+            manifest.link_count()
+        );
+    }
+    if let Some(path) = &options.content_rules {
+        let rules = ContentRules::load(path)?;
+        println!(
+            "{} is valid: {} forbidden word rules, {} required phrase rules",
+            path.display(),
+            rules.forbidden.len(),
+            rules.required.len()
+        );
+    }
+    Ok(())
+}
 
-            if other_keys.contains(&local_key.to_string()) {
-                println!("{}", "⚠️ Unused key found in translation:".bold().yellow());
-                let file: String = get_translation_file(&file_mapping, lang, local_key);
-                println!("   - Key: {} | File: {}", key.yellow(), file.blue());
-                local_errors = true;
+// `format`: rewrites the JSON translation files of every language with
+// their keys sorted, or only lists those that aren't with `--check`. A file
+// that isn't valid JSON is reported and left alone. Returns whether a file
+// wasn't formatted or couldn't be.
+fn format(options: &FormatOptions) -> Result<bool, String> {
+    let setup = load_setup(&options.target)?;
+    let timings = Timings::default();
+    let project = setup.project(&timings);
+    let files: Vec<PathBuf> = project
+        .languages()
+        .flat_map(|lang| project.files(lang))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect();
+
+    let mut transaction = Transaction::default();
+    let mut unformatted = Vec::new();
+    let mut failed = false;
+    for path in &files {
+        let formatted = fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|content| {
+                let formatted = normalize::normalize(&content, &options.indent)?;
+                Ok((formatted != content).then_some(formatted))
+            });
+        match formatted {
+            Ok(Some(_)) if options.check => unformatted.push(path.clone()),
+            Ok(Some(formatted)) => transaction.rewrite(path.clone(), formatted),
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!(
+                    "{} Couldn't format {}: {}",
+                    "⚠️".yellow(),
+                    path.display(),
+                    err
+                );
+                failed = true;
             }
         }
+    }
 
-        if local_errors {
-            has_errors.store(true, Ordering::Relaxed);
+    if options.check {
+        for path in &unformatted {
+            println!("{}", path.display());
         }
+        if unformatted.is_empty() {
+            eprintln!("All {} translation files are formatted", files.len());
+        } else {
+            eprintln!(
+                "{} of {} translation files aren't formatted; `format` rewrites them",
+                unformatted.len(),
+                files.len()
+            );
+        }
+        return Ok(failed || !unformatted.is_empty());
+    }
+    let formatted = transaction.commit(None);
+    if let Some(err) = &formatted.error {
+        eprintln!("Format report:");
+        let _ = formatted.write_text(&mut io::stderr());
+        return Err(format!("{}; {}", err, formatted.state()));
+    }
+    eprintln!(
+        "{} of {} translation files formatted",
+        formatted.applied(),
+        files.len()
+    );
+    let _ = formatted.write_text(&mut io::stderr());
+    Ok(failed)
+}
+
+// `stats`: per language, how many base keys are translated, missing or
+// empty, as a table or JSON.
+fn stats(options: &StatsOptions) -> Result<(), String> {
+    let setup = load_setup(&options.target)?;
+    let config = &setup.config;
+    let timings = Timings::default();
+    let project = setup.project(&timings);
+    project.load_all();
+    let ignores = Waivers {
+        source: config
+            .source
+            .as_ref()
+            .map_or(config::CONFIG_FILE.to_string(), |path| {
+                path.display().to_string()
+            }),
+        waivers: config.ignores.clone(),
+    };
+    let today = waivers::today();
+    let stats = Stats::measure(&project, setup.options.key_case_insensitive, |lang, key| {
+        exempt_from_coverage(config, Some(&ignores), &today, lang, key)
     });
+    for lang in project.excluded_languages() {
+        eprintln!(
+            "{} {} isn't counted: too many of its files are unreadable",
+            "⚠️".yellow(),
+            lang
+        );
+    }
+    let mut out = io::stdout().lock();
+    match options.format {
+        StatsFormat::Text => stats.write_text(&mut out, options.per_file),
+        StatsFormat::Markdown => stats.write_markdown(&mut out, options.per_file),
+        StatsFormat::Json => writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&stats.to_json(options.per_file)).unwrap()
+        ),
+    }
+    .map_err(|err| format!("failed to write stats: {}", err))
+}
 
-    has_errors.load(Ordering::Relaxed)
+// `plural-rules`: the CLDR plural categories of a language, with sample
+// numbers, for translators.
+fn plural_rules(options: &PluralRulesOptions) -> Result<(), String> {
+    let (tag, categories) = plural_rules::categories(&options.lang)
+        .ok_or_else(|| format!("no CLDR plural rules for `{}`", options.lang))?;
+    if tag.eq_ignore_ascii_case(&options.lang) {
+        println!("Plural categories of {}:", tag);
+    } else {
+        println!("Plural categories of {} (from {}):", options.lang, tag);
+    }
+    for category in categories {
+        let optional = if category.optional { " (optional)" } else { "" };
+        println!("   - {}{}: {}", category.name, optional, category.examples);
+    }
+    Ok(())
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let base_path = args
-        .get(1)
-        .map(|s| s.as_str())
-        .unwrap_or("../../circularx/webapp/src/assets/i18n");
-
-    let lang_folders: Vec<String> = fs::read_dir(base_path)
-        .expect("Failed to read directory")
-        .filter_map(|entry| {
-            entry.ok().and_then(|entry| {
-                let path = entry.path();
-                if path.is_dir() {
-                    entry.file_name().into_string().ok()
-                } else {
-                    None
-                }
-            })
+// `schema-report`: the JSON Schema of the JSON report, for tools reading it.
+fn schema_report(options: &SchemaReportOptions) -> Result<(), String> {
+    let schema = serde_json::to_string_pretty(&json::schema()).unwrap();
+    match &options.output {
+        Some(path) => render::write_atomically(path, |out| writeln!(out, "{}", schema))
+            .map_err(|err| format!("failed to write {}: {}", path.display(), err)),
+        None => {
+            println!("{}", schema);
+            Ok(())
+        }
+    }
+}
+
+// The files other than translations and sources that change what a run
+// checks, such as `translation-check.toml`.
+fn settings(options: &Options, config: &Config) -> Vec<PathBuf> {
+    [
+        config.source.clone(),
+        options.waivers.clone(),
+        options.content_rules.clone(),
+        options.codeowners.clone(),
+        config
+            .context
+            .as_ref()
+            .map(|context| context.manifest.clone()),
+        options.loader_config.clone(),
+        options.schema_snapshot.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+// The options and configuration of a check run with `args`.
+fn configure(args: &[String]) -> Result<(Options, Config), String> {
+    let mut options = Options::parse(args)?;
+    if let Some(limit) = options.max_open_files {
+        handles::set_limit(limit);
+    }
+    // Those of an earlier run of the process are dropped.
+    handles::set_buffers(match &options.buffers {
+        Some(path) => handles::read_buffers(path)?,
+        None => BTreeMap::new(),
+    });
+
+    match options.color {
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Never => colored::control::set_override(false),
+        ColorChoice::Auto => colored::control::unset_override(),
+    }
+
+    let config = Config::discover(options.config.as_deref())?;
+    setup::configure(&mut options, &config)?;
+    Ok((options, config))
+}
+
+// A check run with `args`, of the whole project or, with `changed`, of what
+// the files listed change, as `--changed-files` scopes it. Returns the exit
+// code.
+fn check(
+    args: &[String],
+    root: &Path,
+    changed: Option<Vec<PathBuf>>,
+    streams: &Streams,
+) -> Result<i32, String> {
+    let (options, config) = configure(args)?;
+    let checker = Checker::configured(options, config);
+    let (options, config) = (checker.options(), checker.configuration());
+    let source = config
+        .source
+        .as_ref()
+        .map(|path| path.display().to_string());
+    let effective_config = options.effective_config(root, source.as_deref());
+    if options.print_config {
+        let _ = writeln!(
+            streams.out(),
+            "{}",
+            serde_json::to_string_pretty(&effective_config).unwrap()
+        );
+        return Ok(0);
+    }
+
+    if !options.quiet {
+        let _ = writeln!(streams.err(), "Project root: {}", root.display());
+    }
+    announce_base_lang(options, &mut streams.err());
+
+    // A published schema stands in for the base language folder.
+    if options.schema_url.is_none() {
+        setup::check_base_language(&options.i18n_dir, &options.base_lang)?;
+    }
+    let changed = match (changed, &options.changed_files, &options.since) {
+        (Some(changed), _, _) => Some(changed),
+        (None, Some(path), _) => Some(scope::read_changed_files(path)?),
+        (None, None, Some(git_ref)) => Some(scope::changed_since(git_ref)?),
+        (None, None, None) => None,
+    };
+    let scope = match changed {
+        Some(changed) => {
+            let scope = Scope::of(
+                &changed,
+                &scope::Paths {
+                    root,
+                    i18n_dir: &options.i18n_dir,
+                    layout: model::layout(&options.i18n_dir).unwrap_or(Layout::Folders),
+                    base_lang: &options.base_lang,
+                    sources: &options.sources(),
+                    settings: settings(options, config),
+                },
+            );
+            if !options.quiet {
+                let _ = writeln!(streams.err(), "{}", scope.describe());
+            }
+            scope
+        }
+        None => Scope::Full("every file".to_string()),
+    };
+    let touched = match &options.changed_since {
+        Some(git_ref) => {
+            let touched = scope::changed_since(git_ref).and_then(|changed| {
+                Touched::since(
+                    git_ref,
+                    &changed,
+                    &scope::Paths {
+                        root,
+                        i18n_dir: &options.i18n_dir,
+                        layout: model::layout(&options.i18n_dir).unwrap_or(Layout::Folders),
+                        base_lang: &options.base_lang,
+                        sources: &options.sources(),
+                        settings: Vec::new(),
+                    },
+                )
+            })?;
+            if !options.quiet {
+                let _ = writeln!(streams.err(), "{}", touched.describe(git_ref));
+            }
+            Some(touched)
+        }
+        None => None,
+    };
+    let timings = Timings::default();
+    let discover = || {
+        timings.time("discover languages", || {
+            setup::discover(options, config, &timings).retain_languages(|lang| scope.compares(lang))
         })
+    };
+    let mut project = discover();
+    if options.fixes.contains(&Fix::Hygiene) {
+        timings.time("delete junk files", || delete_junk(&project, options));
+    }
+    let rewrites: Vec<Fix> = options
+        .fixes
+        .iter()
+        .copied()
+        .filter(|fix| !matches!(fix, Fix::SyncKeys | Fix::Hygiene))
         .collect();
+    if rewrites.contains(&Fix::Eol) && config.file_format.is_none() {
+        return Err(
+            "`--fix eol` requires `eol` or `final_newline` under `[file_format]` in the configuration file"
+                .to_string(),
+        );
+    }
+    if !rewrites.is_empty() {
+        let mut transaction = timings.time("fix translation files", || {
+            fix_files(&project, &rewrites, config.file_format.as_ref())
+        })?;
+        let done = |count| match rewrites[..] {
+            [Fix::Nfc] => format!("Normalized {} translation files to NFC", count),
+            [Fix::QuoteEscapes] => format!("Fixed quote escapes in {} translation files", count),
+            [Fix::Eol] => format!("Fixed line endings in {} translation files", count),
+            [Fix::TranslatedPlaceholders] => format!(
+                "Renamed translated placeholders in {} translation files",
+                count
+            ),
+            _ => format!(
+                "Fixed {} translation files with `--fix {}`",
+                count,
+                fix_names(&rewrites)
+            ),
+        };
+        protect_base(&mut transaction, &project, options, &rewrites);
+        if apply_fix(&transaction, options, &rewrites, done) {
+            // The base language was parsed before the rewrite.
+            project = discover();
+        }
+    }
+    let base = match options.remote_schema() {
+        Some(remote) => {
+            let (schema, warning) = remote.fetch()?;
+            if let Some(warning) = warning {
+                let _ = writeln!(streams.err(), "{} {}", "⚠️".yellow(), warning);
+            }
+            Base::schema(schema, &remote.url)
+        }
+        None => Base::language(&project, &options.base_lang),
+    };
+    let state_path = options
+        .state
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(STATE_FILE));
+    let previous = State::load(&state_path).unwrap_or_else(|err| {
+        let _ = writeln!(streams.err(), "{} {}", "⚠️".yellow(), err);
+        None
+    });
+    // Files are written plain; the console follows `--color`.
+    let console = Style {
+        color: colored::control::SHOULD_COLORIZE.should_colorize(),
+        emoji: true,
+    };
+    let summarize = |report: &Report| {
+        let quiet = HumanOptions {
+            quiet: true,
+            ..options.human_options()
+        };
+        HumanRenderer::new(&mut streams.out(), console, quiet)
+            .render(report)
+            .map_err(|err| format!("failed to write report: {}", err))
+    };
 
-    let translations = Arc::new(DashMap::new());
-    let file_mapping = Arc::new(DashMap::new());
+    let checked = checker.run(
+        &project,
+        &base,
+        &Run {
+            scope: &scope,
+            touched: touched.as_ref(),
+            previous: previous.as_ref(),
+            timings: &timings,
+        },
+    )?;
+    for warning in &checked.warnings {
+        let _ = writeln!(streams.err(), "{} {}", "⚠️".yellow(), warning);
+    }
+    for note in &checked.notes {
+        let _ = writeln!(streams.err(), "{}", note);
+    }
+    let mut report = checked.report;
+    report.config = Some(effective_config);
+    if checked.stopped {
+        summarize(&report)?;
+        return Ok(1);
+    }
+    if let Some(path) = options
+        .schema_snapshot
+        .as_ref()
+        .filter(|_| options.update_schema_snapshot)
+    {
+        Schema::write(path, &base.name, &base.placeholders)?;
+        let _ = writeln!(
+            streams.err(),
+            "Schema snapshot written to {}",
+            path.display()
+        );
+    }
 
-    lang_folders.par_iter().for_each(|lang| {
-        let pattern = format!("{}/{}/*.json", base_path, lang);
-        let mut translations_keys_and_values = HashMap::new();
-        let mut translations_keys_and_paths = HashMap::new();
+    let baseline = checked.baseline;
+    if let (Some(path), Some(written)) = (&options.write_baseline, &baseline) {
+        written.write(path)?;
+        let _ = writeln!(
+            streams.err(),
+            "{} findings written to the baseline {}",
+            written.len(),
+            path.display()
+        );
+    }
+    if checked.fixed > 0 && !options.quiet {
+        let _ = writeln!(
+            streams.err(),
+            "{} findings of the baseline are fixed; `--write-baseline` drops them from it",
+            checked.fixed
+        );
+    }
+    let context = checked.context;
+    let languages: Vec<&str> = project.languages().collect();
+    let today = waivers::today();
+    if options.fixes.contains(&Fix::SyncKeys) {
+        let protected = if base_writes_allowed(options, &[Fix::SyncKeys]) {
+            Vec::new()
+        } else {
+            project.files(project.base_lang())
+        };
+        let (mut transaction, fixed) = timings.time("sync keys", || {
+            sync::sync_keys(
+                &report,
+                &project,
+                &base,
+                &config.sync_keys.template,
+                &protected,
+            )
+        })?;
+        if !fixed.kept.is_empty() {
+            let _ = writeln!(
+                streams.err(),
+                "{} Kept {} unused keys in every language: `--fix sync-keys` only removes them from the base language with `--allow-base-writes`",
+                "⚠️".yellow(),
+                fixed.kept.len()
+            );
+        }
+        let refused = protect_base(&mut transaction, &project, options, &[Fix::SyncKeys]);
+        if apply_fix(&transaction, options, &[Fix::SyncKeys], |count| {
+            format!(
+                "Synced keys in {} translation files",
+                count - fixed.scaffold_files
+            )
+        }) {
+            let fixed = fixed.indexes(&refused);
+            let mut index = 0;
+            report.findings.retain(|_| {
+                index += 1;
+                !fixed.contains(&(index - 1))
+            });
+        }
+    }
+    if let Some((manifest, max_links)) = &context {
+        manifest.attach(&mut report, *max_links);
+    }
+    if let Some(path) = &options.codeowners {
+        let codeowners = CodeOwners::load(path)?;
+        codeowners.assign(&mut report, options.fail_on.contains(&FailOn::Unowned));
+    }
+    timings.time("locate keys", || loader::locate_keys(&mut report.findings));
+    if options.check_only {
+        summarize(&report)?;
+        return Ok(exit_code(&report, options));
+    }
+    report.sort();
+    if options.suggest_fixes {
+        timings.time("suggest fixes", || {
+            patch::suggest_fixes(&mut report, &project, &base)
+        });
+    }
 
-        for entry in glob(&pattern).expect("Failed to read glob pattern") {
-            if let Ok(path) = entry {
-                let content = fs::read_to_string(&path).expect("Failed to read file");
-                let json: Value = serde_json::from_str(&content).expect("Invalid JSON");
+    if options.interactive {
+        review_findings(&mut report, &project, &base, baseline, options);
+        summarize(&report)?;
+        return Ok(exit_code(&report, options));
+    }
 
-                let mut flattened = DashMap::new();
-                flatten_json(&json, String::new(), &mut flattened);
+    if let Some(path) = &options.emit_waivers {
+        let emitted = waivers::emit(path, &report, &today)?;
+        let _ = writeln!(
+            streams.err(),
+            "{} waivers written to {}",
+            emitted,
+            path.display()
+        );
+    }
 
-                for (key, value) in flattened {
-                    translations_keys_and_values.insert(key.clone(), value);
-                    translations_keys_and_paths.insert(key, path.to_string_lossy().to_string());
-                }
+    // The state file, hooks and metrics still see the whole run; only what
+    // is reported and the exit code are limited to the shard.
+    let shard = options.partition.map(|partition| {
+        let mut shard = report.clone();
+        partition.apply(&mut shard, base.placeholders.keys().map(String::as_str));
+        shard
+    });
+    let shown = shard.as_ref().unwrap_or(&report);
+    let redacted = options.redaction.is_enabled().then(|| {
+        let mut redacted = shown.clone();
+        options.redaction.apply(&mut redacted);
+        redacted
+    });
+    let shown = redacted.as_ref().unwrap_or(shown);
+    let rolled = options
+        .rollup
+        .and_then(|threshold| rollup::roll_up(shown, threshold));
+    let shown = rolled.as_ref().unwrap_or(shown);
+
+    let sinks = options.sinks();
+    let mut values = KeyValues::new();
+    if sinks.iter().any(|sink| sink.format == ReportFormat::Html) {
+        let keys: HashSet<&str> = shown
+            .findings
+            .iter()
+            .filter_map(|finding| finding.key.as_deref())
+            .collect();
+        for (key, lang, value) in project.flat_map_languages(|lang, data| {
+            keys.iter()
+                .filter_map(|key| Some((*key, lang.to_string(), data.value(key)?.to_string())))
+                .collect()
+        }) {
+            values
+                .entry(key.to_string())
+                .or_default()
+                .insert(lang, value);
+        }
+        options.redaction.apply_values(&mut values);
+    }
+
+    write_reports(
+        shown,
+        &sinks,
+        &values,
+        options.human_options(),
+        options.junit_suites,
+        console,
+        streams,
+    )?;
+    // With every report in a file, the console still gets the summary line.
+    if sinks.iter().all(|sink| sink.path.is_some()) {
+        summarize(shown)?;
+    }
+
+    // Hooks compare whole runs.
+    let hooks = config
+        .hooks
+        .as_ref()
+        .filter(|_| !options.no_hooks && scope.is_full());
+    if hooks.is_some() || options.update_state {
+        let current = State::from_run(&base, &project, &report, previous.as_ref(), &today);
+
+        if let (Some(hooks), Some(previous)) = (hooks, &previous) {
+            for warning in timings.time("run hooks", || hooks::run(hooks, previous, &current)) {
+                let _ = writeln!(streams.err(), "{} {}", "⚠️".yellow(), warning);
             }
         }
+        if options.update_state {
+            current.write(&state_path)?;
+            let _ = writeln!(streams.err(), "State written to {}", state_path.display());
+        }
+    }
 
-        translations.insert(lang.to_string(), translations_keys_and_values);
-        file_mapping.insert(lang.to_string(), translations_keys_and_paths);
-    });
+    if options.metrics_file.is_some() || options.statsd.is_some() {
+        let samples = metrics::collect(&base, &languages, &report, &timings);
+        if let Some(path) = &options.metrics_file {
+            metrics::write_textfile(path, &samples)?;
+        }
+        if let Some(address) = &options.statsd
+            && let Err(err) = metrics::send_statsd(address, &samples)
+        {
+            let _ = writeln!(streams.err(), "{} {}", "⚠️".yellow(), err);
+        }
+    }
 
-    let has_errors = check_translations(
-        "fr",
-        translations.clone(),
-        file_mapping.clone(),
-        &DashSet::new(),
-    );
+    if options.timings {
+        let _ = timings.write(&mut streams.err());
+    }
+
+    Ok(exit_code(shown, options))
+}
 
-    let files: Vec<PathBuf> = ["ts", "js", "vue"]
-        .par_iter()
-        .flat_map(|ext| get_all_files_by_extension(Path::new("../../circularx/webapp/src"), ext))
+// `--watch`: checks the project, then checks the files changed since, as
+// `--changed-files` scopes them, whenever some change, until interrupted.
+// The runs share one process, which keeps the translation files flattened
+// and the source files scanned between them: a run only flattens and scans
+// again the files changed since, unless a `serve` server runs them. A run
+// stopping on an error doesn't end the watch.
+fn watch(args: &[String], root: &Path) -> ! {
+    let args: Vec<String> = args
+        .iter()
+        .filter(|arg| *arg != "--watch")
+        .cloned()
         .collect();
+    let (options, config) = configure(&args).unwrap_or_else(|err| exit_with_error(err));
+    if !options.low_memory {
+        loader::keep_flattened_files();
+        usage::keep_scans();
+    }
+    let run = |changed: Option<Vec<PathBuf>>| {
+        if delegate(&args, root, &options, changed.clone()).is_none()
+            && let Err(err) = check(&args, root, changed, &Streams::Standard)
+        {
+            eprintln!("{} {}", "❌".red(), err);
+        }
+    };
+
+    let sources = options.sources();
+    let watched = watch::Watched {
+        i18n_dir: &options.i18n_dir,
+        sources: &sources,
+        settings: settings(&options, &config),
+    };
+    let mut watcher = Watcher::new(&watched);
+    run(None);
+    loop {
+        eprintln!(
+            "{} Watching {} and {} for changes",
+            "👀".cyan(),
+            options.i18n_dir.display(),
+            describe_roots(&sources)
+        );
+        let changed: Vec<PathBuf> = watcher
+            .wait(&watched)
+            .into_iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(path)
+            })
+            .collect();
+        let names: Vec<String> = changed
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        eprintln!("\n{} {}", "Changed:".bold(), names.join(", "));
+        run(Some(changed));
+    }
+}
 
-    let base_translation = translations.get("fr").unwrap();
-    let base_keys: HashSet<String> = base_translation.keys().cloned().collect();
+// Runs the check on the `serve` server listening on the socket, printing
+// what it answers, and returns the exit code. `None` to check in-process:
+// without a server, with `--no-daemon`, for the runs reading the terminal or
+// stdin, or once the server can't be used.
+fn delegate(
+    args: &[String],
+    root: &Path,
+    options: &Options,
+    changed: Option<Vec<PathBuf>>,
+) -> Option<i32> {
+    if options.no_daemon
+        || options.interactive
+        || !options.fixes.is_empty()
+        || options.changed_files.as_deref() == Some(Path::new("-"))
+    {
+        return None;
+    }
+    // The server's own terminal and environment don't say what this run
+    // should do.
+    let mut args = args.to_vec();
+    if options.color == ColorChoice::Auto {
+        let color = colored::control::SHOULD_COLORIZE.should_colorize();
+        args.push("--color".to_string());
+        args.push(if color { "always" } else { "never" }.to_string());
+    }
+    if options.read_only {
+        args.push("--read-only".to_string());
+    }
+    let request = daemon::Request { args, changed };
+    match daemon::delegate(&options.socket, root, &request) {
+        Ok(Some(reply)) => {
+            let _ = io::stdout().write_all(reply.stdout.as_bytes());
+            let _ = io::stderr().write_all(reply.stderr.as_bytes());
+            Some(reply.code)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            eprintln!("{} {}; checking in-process", "⚠️".yellow(), err);
+            None
+        }
+    }
+}
 
-    let unused_keys = check_translations_usage(&base_keys, &files);
+// `serve`: checks the project for every run delegating to it, one at a
+// time, keeping the translation files flattened and the source files
+// scanned between runs, until interrupted.
+fn serve(options: &ServeOptions, root: &Path) -> Result<(), String> {
+    loader::keep_flattened_files();
+    usage::keep_scans();
+    eprintln!(
+        "{} Serving the checks of {} on {}",
+        "📡".cyan(),
+        root.display(),
+        options.socket.display()
+    );
+    daemon::serve(&options.socket, root, |request| {
+        let streams = Streams::captured();
+        // A run that panics fails alone; the server goes on.
+        let code = panic::catch_unwind(AssertUnwindSafe(|| {
+            check(&request.args, root, request.changed, &streams)
+        }));
+        let code = match code {
+            Ok(Ok(code)) => code,
+            Ok(Err(err)) => {
+                let _ = writeln!(streams.err(), "{} {}", "❌".red(), err);
+                2
+            }
+            Err(_) => {
+                let _ = writeln!(
+                    streams.err(),
+                    "{} the check panicked; the output of `serve` says why",
+                    "❌".red()
+                );
+                2
+            }
+        };
+        let (stdout, stderr) = streams.taken();
+        daemon::Reply {
+            code,
+            stdout,
+            stderr,
+        }
+    })
+}
 
-    println!("Unused keys: {:?}", unused_keys.len());
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    // Relative paths, given or defaults, are resolved against the project
+    // root whatever folder the tool runs from.
+    let root = match cli::take_cwd(&mut args).unwrap_or_else(|err| exit_with_error(err)) {
+        Some(root) => root,
+        None => config::project_root(&env::current_dir().unwrap_or_else(|err| {
+            exit_with_error(format!("failed to read the working directory: {}", err))
+        })),
+    };
+    env::set_current_dir(&root).unwrap_or_else(|err| {
+        exit_with_error(format!(
+            "failed to use {} as the project root: {}",
+            root.display(),
+            err
+        ))
+    });
+    let root = env::current_dir().unwrap_or(root);
 
-    process::exit(if has_errors { 1 } else { 0 });
+    if let Some(command) = cli::help_request(&args) {
+        match cli::usage(command) {
+            Some(usage) => print!("{}", usage),
+            None => exit_with_error(format!(
+                "unknown command `{}`; `--help` lists the commands",
+                command.unwrap_or_default()
+            )),
+        }
+        return;
+    }
+
+    match args.first().map(String::as_str) {
+        Some("review") => {
+            let review =
+                ReviewOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            approve_reviews(&review).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("worklist") => {
+            let worklist =
+                WorklistOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            write_worklist(&worklist).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("export") => {
+            let export =
+                ExportOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            export_sheet(&export).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("import") => {
+            let import =
+                ImportOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            import_sheet(&import).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("sample") => {
+            let options =
+                SampleOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            sample(&options).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("preview") => {
+            let options =
+                PreviewOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            let flagged = preview(&options).unwrap_or_else(|err| exit_with_error(err));
+            process::exit(if flagged { 1 } else { 0 });
+        }
+        Some("search") => {
+            let options =
+                SearchOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            let found = search(&options).unwrap_or_else(|err| exit_with_error(err));
+            process::exit(if found { 0 } else { 1 });
+        }
+        Some("merge-reports") => {
+            let merge = MergeOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            let has_errors = merge_reports(&merge).unwrap_or_else(|err| exit_with_error(err));
+            process::exit(if has_errors { 1 } else { 0 });
+        }
+        Some("format") => {
+            let options =
+                FormatOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            let failed = format(&options).unwrap_or_else(|err| exit_with_error(err));
+            process::exit(if failed { 1 } else { 0 });
+        }
+        Some("stats") => {
+            let options =
+                StatsOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            stats(&options).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("trend") => {
+            let options =
+                TrendOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            trend(&options).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("plural-rules") => {
+            let options =
+                PluralRulesOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            plural_rules(&options).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("schema-report") => {
+            let options =
+                SchemaReportOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            schema_report(&options).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("jsonrpc") => {
+            JsonRpcOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            let exe = env::current_exe().unwrap_or_else(|err| {
+                exit_with_error(format!("failed to locate this binary: {}", err))
+            });
+            let mut session = jsonrpc::Session::new(exe);
+            jsonrpc::serve(&mut session, io::stdin().lock(), &mut io::stdout().lock())
+                .unwrap_or_else(|err| exit_with_error(format!("jsonrpc session failed: {}", err)));
+            return;
+        }
+        Some("serve") => {
+            let options =
+                ServeOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            serve(&options, &root).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        Some("verify-bundle") => {
+            let options =
+                VerifyBundleOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            match options.color {
+                ColorChoice::Always => colored::control::set_override(true),
+                ColorChoice::Never => colored::control::set_override(false),
+                ColorChoice::Auto => {}
+            }
+            let has_errors = verify_bundle(&options).unwrap_or_else(|err| exit_with_error(err));
+            process::exit(if has_errors { 1 } else { 0 });
+        }
+        Some("lint-config") => {
+            let options = LintOptions::parse(&args[1..]).unwrap_or_else(|err| exit_with_error(err));
+            lint_config(&options).unwrap_or_else(|err| exit_with_error(err));
+            return;
+        }
+        _ => {}
+    }
+    let options = Options::parse(&args).unwrap_or_else(|err| exit_with_error(err));
+    if options.watch {
+        watch(&args, &root);
+    }
+    let code = delegate(&args, &root, &options, None).unwrap_or_else(|| {
+        check(&args, &root, None, &Streams::Standard).unwrap_or_else(|err| exit_with_error(err))
+    });
+    process::exit(code);
 }
@@ -0,0 +1,208 @@
+use crate::base::Base;
+use crate::render;
+use crate::report::{Check, Report, Severity};
+use crate::timings::Timings;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::path::Path;
+
+pub struct MetricInfo {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub labels: &'static [&'static str],
+}
+
+// Every exported metric. Names and label sets are relied upon by dashboards
+// and alerts, so they only ever gain entries. All of them are gauges.
+pub const FINDINGS: MetricInfo = MetricInfo {
+    name: "translation_check_findings",
+    help: "Findings of the last run by check and severity.",
+    labels: &["check", "severity"],
+};
+pub const COMPLETENESS: MetricInfo = MetricInfo {
+    name: "translation_check_completeness_ratio",
+    help: "Share of the base keys present in each language.",
+    labels: &["lang"],
+};
+pub const UNUSED_KEYS: MetricInfo = MetricInfo {
+    name: "translation_check_unused_keys",
+    help: "Base keys never referenced from the sources.",
+    labels: &[],
+};
+pub const PHASE_DURATION: MetricInfo = MetricInfo {
+    name: "translation_check_phase_duration_seconds",
+    help: "Duration of each phase of the last run.",
+    labels: &["phase"],
+};
+pub const FILES_SCANNED: MetricInfo = MetricInfo {
+    name: "translation_check_files_scanned",
+    help: "Files read by the last run, by kind.",
+    labels: &["kind"],
+};
+
+pub const METRICS: [&MetricInfo; 5] = [
+    &FINDINGS,
+    &COMPLETENESS,
+    &UNUSED_KEYS,
+    &PHASE_DURATION,
+    &FILES_SCANNED,
+];
+
+pub struct Sample {
+    pub metric: &'static MetricInfo,
+    // Values of the metric's labels, in the same order.
+    pub labels: Vec<String>,
+    pub value: f64,
+}
+
+// The metrics of a finished run. Completeness and unused keys are left out
+// when the checks producing them were skipped, rather than reported as zero.
+pub fn collect(base: &Base, languages: &[&str], report: &Report, timings: &Timings) -> Vec<Sample> {
+    let mut samples = Vec::new();
+
+    let mut findings: BTreeMap<(Check, Severity), usize> = Check::ALL
+        .into_iter()
+        .map(|check| ((check, check.info().severity), 0))
+        .collect();
+    for finding in &report.findings {
+        *findings
+            .entry((finding.check, finding.severity))
+            .or_default() += finding.weight;
+    }
+    for ((check, severity), count) in findings {
+        samples.push(Sample {
+            metric: &FINDINGS,
+            labels: vec![check.info().id.to_string(), severity.name().to_string()],
+            value: count as f64,
+        });
+    }
+
+    if report.consistency_checked {
+        for lang in languages.iter().filter(|lang| Some(**lang) != base.lang) {
+            samples.push(Sample {
+                metric: &COMPLETENESS,
+                labels: vec![lang.to_string()],
+                value: report.completeness(lang, base.placeholders.len()),
+            });
+        }
+    }
+
+    if report.usage_scanned {
+        samples.push(Sample {
+            metric: &UNUSED_KEYS,
+            labels: vec![],
+            value: report.count(Check::UnusedKey) as f64,
+        });
+    }
+
+    let mut phases: BTreeMap<String, f64> = BTreeMap::new();
+    for (phase, duration) in timings.phases() {
+        *phases.entry(phase.replace(' ', "_")).or_default() += duration.as_secs_f64();
+    }
+    for (phase, seconds) in phases {
+        samples.push(Sample {
+            metric: &PHASE_DURATION,
+            labels: vec![phase],
+            value: seconds,
+        });
+    }
+
+    for (kind, count) in [
+        ("translation", report.translation_files),
+        ("source", report.source_files),
+    ] {
+        samples.push(Sample {
+            metric: &FILES_SCANNED,
+            labels: vec![kind.to_string()],
+            value: count as f64,
+        });
+    }
+
+    samples
+}
+
+// Prometheus text exposition format, as read by the node exporter's
+// textfile collector.
+pub fn to_prometheus(samples: &[Sample]) -> String {
+    let mut output = String::new();
+    for metric in METRICS {
+        writeln!(output, "# HELP {} {}", metric.name, metric.help).unwrap();
+        writeln!(output, "# TYPE {} gauge", metric.name).unwrap();
+        for sample in samples
+            .iter()
+            .filter(|sample| sample.metric.name == metric.name)
+        {
+            output.push_str(metric.name);
+            if !metric.labels.is_empty() {
+                let labels: Vec<String> = metric
+                    .labels
+                    .iter()
+                    .zip(&sample.labels)
+                    .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+                    .collect();
+                write!(output, "{{{}}}", labels.join(",")).unwrap();
+            }
+            writeln!(output, " {}", sample.value).unwrap();
+        }
+    }
+    output
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// Written atomically, so the collector never reads a half-written file.
+pub fn write_textfile(path: &Path, samples: &[Sample]) -> Result<(), String> {
+    render::write_atomically(path, |out| out.write_all(to_prometheus(samples).as_bytes()))
+        .map_err(|err| format!("failed to write metrics {}: {}", path.display(), err))
+}
+
+// StatsD gauges, with labels sent as DogStatsD tags since plain StatsD has
+// no notion of them.
+pub fn to_statsd(samples: &[Sample]) -> Vec<String> {
+    samples
+        .iter()
+        .map(|sample| {
+            let mut line = format!("{}:{}|g", sample.metric.name, sample.value);
+            if !sample.labels.is_empty() {
+                let tags: Vec<String> = sample
+                    .metric
+                    .labels
+                    .iter()
+                    .zip(&sample.labels)
+                    .map(|(name, value)| format!("{}:{}", name, value.replace([',', '|'], "_")))
+                    .collect();
+                write!(line, "|#{}", tags.join(",")).unwrap();
+            }
+            line
+        })
+        .collect()
+}
+
+// Sends one datagram per metric, which keeps every packet well under the
+// usual MTU.
+pub fn send_statsd(address: &str, samples: &[Sample]) -> Result<(), String> {
+    let failed = |err: std::io::Error| format!("failed to send metrics to {}: {}", address, err);
+    let target = address
+        .to_socket_addrs()
+        .map_err(failed)?
+        .next()
+        .ok_or_else(|| format!("failed to send metrics to {}: no address", address))?;
+    let local: SocketAddr = if target.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+    let socket = UdpSocket::bind(local).map_err(failed)?;
+    socket.connect(target).map_err(failed)?;
+    for line in to_statsd(samples) {
+        socket.send(line.as_bytes()).map_err(failed)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,841 @@
+use crate::loader::{FileFormat, load_language, translation_stem};
+use crate::module;
+use crate::selector::KeyPattern;
+use crate::timings::Timings;
+use dashmap::{DashMap, DashSet};
+use glob::{MatchOptions, Pattern, glob_with};
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+pub type Lang = String;
+
+// The language the others are compared against unless told otherwise.
+pub const DEFAULT_BASE_LANG: &str = "fr";
+
+// A language folder name: a path would point outside the translation
+// directory.
+pub fn parse_lang(value: &str, option: &str) -> Result<String, String> {
+    if value.is_empty() || value.contains(['/', '\\']) || value.starts_with('.') {
+        return Err(format!(
+            "invalid value `{}` for `{}` (expected a language folder name such as `fr`)",
+            value, option
+        ));
+    }
+    Ok(value.to_string())
+}
+
+// Keys are interned, so that every language shares one allocation per key.
+pub type KeyId = Arc<str>;
+
+// The JSON type of a value. Only strings are translated; the other
+// scalars are only compared by key and type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    String,
+    Number,
+    Boolean,
+    Null,
+}
+
+impl ValueKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueKind::String => "string",
+            ValueKind::Number => "number",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Null => "null",
+        }
+    }
+}
+
+// One translated key: its value and the file defining it. Values other
+// than strings hold their JSON text, such as `42` or `null`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub value: String,
+    pub kind: ValueKind,
+    pub file: Arc<str>,
+    // Marked with `"doNotTranslate": true`, so that every language must keep
+    // the value unchanged.
+    pub do_not_translate: bool,
+}
+
+impl Entry {
+    // A string of only whitespace, counted as left empty rather than
+    // translated.
+    pub fn is_blank(&self) -> bool {
+        self.kind == ValueKind::String && self.value.trim().is_empty()
+    }
+}
+
+#[derive(Default)]
+pub struct LanguageData {
+    pub values: HashMap<KeyId, Entry>,
+    // The files that couldn't be read or parsed, with the error.
+    pub unreadable: Vec<(Arc<str>, String)>,
+    // Keys defined again after their first definition, with the file of the
+    // one that was dropped and of the one kept. The last definition wins. A
+    // definition dropped within the kept file is named by its
+    // `file:line:column`.
+    pub duplicates: Vec<(KeyId, Arc<str>, Arc<str>)>,
+    // Keys of gettext messages flagged `fuzzy`, with their file.
+    pub fuzzy: Vec<(KeyId, Arc<str>)>,
+    // The length and file of each array. Its elements are keyed by index,
+    // as `terms.body.0`.
+    pub arrays: HashMap<KeyId, (usize, Arc<str>)>,
+}
+
+impl LanguageData {
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(|key| &**key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|entry| entry.value.as_str())
+    }
+
+    pub fn file(&self, key: &str) -> Option<&str> {
+        match self.values.get(key) {
+            Some(entry) => Some(&*entry.file),
+            None => self.arrays.get(key).map(|(_, file)| &**file),
+        }
+    }
+
+    // The outermost array holding `key`, as `terms.body` for
+    // `terms.body.0.title`.
+    pub fn outermost_array<'k>(&self, key: &'k str) -> Option<&'k str> {
+        key.match_indices('.')
+            .map(|(end, _)| &key[..end])
+            .find(|prefix| self.arrays.contains_key(*prefix))
+    }
+}
+
+// How a file name may repeat the language of its folder, as folders
+// migrated from another layout hold `de.common.json` or `common.de.json`
+// where the others hold `common.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileNameRule {
+    LangPrefix,
+    LangSuffix,
+}
+
+impl FileNameRule {
+    pub const ALL: [FileNameRule; 2] = [FileNameRule::LangPrefix, FileNameRule::LangSuffix];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FileNameRule::LangPrefix => "lang-prefix",
+            FileNameRule::LangSuffix => "lang-suffix",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<FileNameRule> {
+        FileNameRule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == name)
+    }
+
+    // The stem without its language, when it carries it this way.
+    pub fn strip<'s>(self, lang: &str, stem: &'s str) -> Option<&'s str> {
+        let stripped = match self {
+            FileNameRule::LangPrefix => stem.strip_prefix(lang)?.strip_prefix('.'),
+            FileNameRule::LangSuffix => stem.strip_suffix(lang)?.strip_suffix('.'),
+        }?;
+        (!stripped.is_empty()).then_some(stripped)
+    }
+}
+
+// The name a file is compared by across languages: `de.common.json` reads
+// `common.json` when the language prefix is stripped.
+pub fn normalize_file_name(lang: &str, name: &str, rules: &[FileNameRule]) -> String {
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) => (stem, Some(extension)),
+        None => (name, None),
+    };
+    let stem = rules
+        .iter()
+        .find_map(|rule| rule.strip(lang, stem))
+        .unwrap_or(stem);
+    match extension {
+        Some(extension) => format!("{}.{}", stem, extension),
+        None => stem.to_string(),
+    }
+}
+
+// How the translation directory holds its languages: a folder per language
+// (`de/common.json`), or files named after their language (`de.json`, or
+// `de.common.json` for a namespace), all in the directory itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Folders,
+    Files,
+}
+
+// The layout of the translation directory. Hidden entries and entries not
+// named after a language don't count; a directory holding both language
+// folders and language files is refused, as which one holds a language
+// would be guesswork.
+pub fn layout(base_path: &Path) -> Result<Layout, String> {
+    let entries = fs::read_dir(base_path).map_err(|err| {
+        format!(
+            "failed to read the translation directory {}: {}",
+            base_path.display(),
+            err
+        )
+    })?;
+    let (mut folders, mut files) = (Vec::new(), Vec::new());
+    for entry in entries.filter_map(Result::ok) {
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if entry.path().is_dir() {
+            if is_language_tag(&name) {
+                folders.push(name);
+            }
+        } else if let Some(stem) = translation_stem(&name)
+            && is_language_tag(stem.split('.').next().unwrap_or(stem))
+        {
+            files.push(name);
+        }
+    }
+    match (folders.is_empty(), files.is_empty()) {
+        (_, true) => Ok(Layout::Folders),
+        (true, false) => Ok(Layout::Files),
+        (false, false) => {
+            folders.sort();
+            files.sort();
+            Err(format!(
+                "{} mixes language folders ({}) with language files ({}); keep one layout",
+                base_path.display(),
+                folders.join(", "),
+                files.join(", ")
+            ))
+        }
+    }
+}
+
+// Whether `name` is shaped like a BCP 47 language tag, such as `de`, `pt-BR`
+// or `zh_Hant_TW`: a language of two or three letters, then subtags of one
+// to eight letters or digits. A folder named `shared` holds a namespace
+// rather than a language.
+pub fn is_language_tag(name: &str) -> bool {
+    let mut subtags = name.split(['-', '_']);
+    let language = subtags.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+// The languages of the translation directory: its folders, or the part of
+// its file names before the first dot, when named like a language.
+pub fn language_names(base_path: &Path, layout: Layout) -> Vec<Lang> {
+    let mut languages: Vec<Lang> = fs::read_dir(base_path)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if name.starts_with('.') {
+                return None;
+            }
+            match layout {
+                Layout::Folders => entry.path().is_dir().then_some(name),
+                Layout::Files => {
+                    let stem = translation_stem(&name)?;
+                    let lang = stem.split('.').next().unwrap_or(stem);
+                    Some(lang.to_string())
+                }
+            }
+        })
+        .filter(|lang| is_language_tag(lang))
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+// A key a language folder defines with another value than the folder of
+// the language it is an alias of, whose value is kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasConflict {
+    pub kept: Arc<str>,
+    pub kept_value: String,
+    pub other: Arc<str>,
+    pub other_value: String,
+}
+
+// The files of the definitions of a duplicate key that were dropped, in
+// reading order, and of the one kept. A definition dropped within the kept
+// file is named by its `file:line:column`.
+pub type Duplicate = (Vec<Arc<str>>, Arc<str>);
+
+// Every language found under the translation directory. Values are
+// only parsed when a check first asks for a language, except for the base
+// language whose keys are needed by every mode. In low-memory mode the other
+// languages are parsed again for every check instead of being kept.
+pub struct Project<'a> {
+    base: Lang,
+    base_path: PathBuf,
+    layout: Layout,
+    languages: BTreeMap<Lang, OnceLock<LanguageData>>,
+    keys: DashSet<KeyId>,
+    // Every translation file parsed during the run, as attributed to its
+    // entries.
+    read: DashSet<Arc<str>>,
+    // The files that couldn't be read, with their language and error.
+    unreadable: DashMap<Arc<str>, (Lang, String)>,
+    // Keys of a language defined more than once, with the files of the
+    // definition that was dropped and of the one kept.
+    duplicates: DashMap<(Lang, KeyId), Duplicate>,
+    fuzzy: DashMap<(Lang, KeyId), Arc<str>>,
+    // Languages left out of the comparison as too many of their files
+    // couldn't be read, and the fraction of files that may fail before.
+    excluded: DashSet<Lang>,
+    max_unreadable: f64,
+    // Languages `retain_languages` dropped.
+    left_out: BTreeSet<Lang>,
+    // The other folders merged into a language, as spelled on disk, and
+    // the keys they disagree on.
+    aliases: BTreeMap<Lang, Vec<Lang>>,
+    conflicts: DashMap<(Lang, KeyId), AliasConflict>,
+    // Folders of the translation directory declared as holding no language.
+    non_language_dirs: BTreeSet<String>,
+    // Translation files, by their path relative to the translation
+    // directory, and keys no language reads.
+    excluded_files: Vec<Pattern>,
+    excluded_keys: Vec<KeyPattern>,
+    file_name_rules: Vec<FileNameRule>,
+    low_memory: bool,
+    timings: &'a Timings,
+}
+
+impl<'a> Project<'a> {
+    pub fn discover(
+        base_path: &Path,
+        base_lang: &str,
+        low_memory: bool,
+        timings: &'a Timings,
+    ) -> Project<'a> {
+        // The caller reports a mixed layout; folders are read meanwhile.
+        let layout = layout(base_path).unwrap_or(Layout::Folders);
+        let mut languages: BTreeMap<Lang, OnceLock<LanguageData>> =
+            language_names(base_path, layout)
+                .into_iter()
+                .map(|lang| (lang, OnceLock::new()))
+                .collect();
+        if base_module(base_path, base_lang).is_some() || base_path.join(base_lang).is_dir() {
+            languages.entry(base_lang.to_string()).or_default();
+        }
+
+        let project = Project {
+            base: base_lang.to_string(),
+            base_path: base_path.to_path_buf(),
+            layout,
+            languages,
+            keys: DashSet::new(),
+            read: DashSet::new(),
+            unreadable: DashMap::new(),
+            duplicates: DashMap::new(),
+            fuzzy: DashMap::new(),
+            excluded: DashSet::new(),
+            max_unreadable: 1.0,
+            left_out: BTreeSet::new(),
+            aliases: BTreeMap::new(),
+            conflicts: DashMap::new(),
+            non_language_dirs: BTreeSet::new(),
+            excluded_files: Vec::new(),
+            excluded_keys: Vec::new(),
+            file_name_rules: Vec::new(),
+            low_memory,
+            timings,
+        };
+        project.get(base_lang);
+        project
+    }
+
+    // Leaves a language out of the comparison when more than `fraction` of
+    // its files can't be read, rather than reporting every key of the
+    // unreadable files as missing. The base language is always compared.
+    pub fn with_max_unreadable(mut self, fraction: f64) -> Project<'a> {
+        self.max_unreadable = fraction;
+        self
+    }
+
+    // Compares file layouts across languages by normalized names; findings
+    // keep the names on disk.
+    pub fn with_file_name_rules(mut self, rules: &[FileNameRule]) -> Project<'a> {
+        self.file_name_rules = rules.to_vec();
+        self
+    }
+
+    // Reads the folders named in `aliases`, such as `zh-CN`, as part of the
+    // language they alias, such as `zh-Hans`. The base language keeps its
+    // name.
+    pub fn with_aliases(mut self, aliases: &BTreeMap<Lang, Lang>) -> Project<'a> {
+        for (alias, lang) in aliases {
+            if *alias == self.base || self.languages.remove(alias).is_none() {
+                continue;
+            }
+            self.languages.entry(lang.clone()).or_default();
+            self.aliases
+                .entry(lang.clone())
+                .or_default()
+                .push(alias.clone());
+        }
+        if self.aliases.contains_key(&self.base) {
+            self.languages.insert(self.base.clone(), OnceLock::new());
+            self.get(&self.base.clone());
+        }
+        self
+    }
+
+    // Leaves out the folders the project keeps next to its languages on
+    // purpose, such as `_archive`, which aren't unassigned files either.
+    pub fn with_non_language_dirs(mut self, dirs: &[String]) -> Project<'a> {
+        for dir in dirs {
+            if *dir != self.base {
+                self.languages.remove(dir);
+            }
+            self.non_language_dirs.insert(dir.clone());
+        }
+        self
+    }
+
+    // Leaves out the translation files matching `files`, such as generated
+    // ones, and the keys matching `keys` in every language. The base
+    // language is read again without them.
+    pub fn with_exclusions(mut self, files: &[Pattern], keys: &[KeyPattern]) -> Project<'a> {
+        if files.is_empty() && keys.is_empty() {
+            return self;
+        }
+        self.excluded_files = files.to_vec();
+        self.excluded_keys = keys.to_vec();
+        self.read.clear();
+        self.unreadable.clear();
+        self.duplicates.clear();
+        self.fuzzy.clear();
+        self.languages.insert(self.base.clone(), OnceLock::new());
+        self.get(&self.base.clone());
+        self
+    }
+
+    fn excludes_file(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.base_path).unwrap_or(path);
+        self.excluded_files
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+    }
+
+    // The folders declared as holding no language.
+    pub fn non_language_dirs(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.non_language_dirs
+            .iter()
+            .map(|dir| self.base_path.join(dir))
+    }
+
+    // The JSON files of the translation directory no language reads: those
+    // directly in it but not named after a language, and those of its
+    // folders named like a namespace rather than a language. Sorted by path.
+    pub fn unassigned_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&self.base_path)
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+        {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.starts_with('.') || name == self.base {
+                continue;
+            }
+            let path = entry.path();
+            if path.is_dir() {
+                if is_language_tag(&name)
+                    || self.languages.contains_key(&name)
+                    || self.non_language_dirs.contains(&name)
+                {
+                    continue;
+                }
+                let dir = Pattern::escape(&path.display().to_string());
+                for (extension, _) in FileFormat::EXTENSIONS {
+                    files.extend(
+                        glob_with(
+                            &format!("{}/*.{}", dir, extension),
+                            MatchOptions {
+                                require_literal_leading_dot: true,
+                                ..MatchOptions::new()
+                            },
+                        )
+                        .expect("Failed to read glob pattern")
+                        .flatten(),
+                    );
+                }
+            } else if let Some(stem) = translation_stem(&name)
+                && !is_language_tag(stem.split('.').next().unwrap_or(stem))
+            {
+                files.push(path);
+            }
+        }
+        files.retain(|file| !self.excludes_file(file));
+        files.sort();
+        files
+    }
+
+    // The folders read as `lang`: its own, then those of its aliases.
+    pub fn folders(&self, lang: &str) -> Vec<Lang> {
+        let mut folders = vec![lang.to_string()];
+        folders.extend(self.aliases.get(lang).into_iter().flatten().cloned());
+        folders
+    }
+
+    // The folder of `lang` holding `file`, as spelled on disk.
+    pub fn folder_of(&self, lang: &str, file: &Path) -> Lang {
+        self.folders(lang)
+            .into_iter()
+            .find(|folder| self.folder_files(folder).iter().any(|path| path == file))
+            .unwrap_or_else(|| lang.to_string())
+    }
+
+    // The keys merged folders define with different values so far, by
+    // language and key.
+    pub fn alias_conflicts(&self) -> BTreeMap<(Lang, KeyId), AliasConflict> {
+        self.conflicts
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    // Leaves out the languages other than the base that `keep` rejects for
+    // each of their folders, for runs limited to the files that changed.
+    pub fn retain_languages(mut self, keep: impl Fn(&str) -> bool) -> Project<'a> {
+        let base = self.base.clone();
+        let (kept, left_out) =
+            std::mem::take(&mut self.languages)
+                .into_iter()
+                .partition(|(lang, _)| {
+                    *lang == base || self.folders(lang).iter().any(|folder| keep(folder))
+                });
+        self.languages = kept;
+        self.left_out.extend(left_out.into_keys());
+        self
+    }
+
+    // Whether `lang` is a language of the translation directory the run
+    // leaves out, whose files are none of its business.
+    pub fn leaves_out(&self, lang: &str) -> bool {
+        self.left_out.contains(lang)
+    }
+
+    pub fn file_name_rules(&self) -> &[FileNameRule] {
+        &self.file_name_rules
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.languages.keys().map(|lang| lang.as_str())
+    }
+
+    pub fn get(&self, lang: &str) -> Option<&LanguageData> {
+        self.languages
+            .get(lang)
+            .map(|cell| cell.get_or_init(|| self.load(lang)))
+    }
+
+    pub fn base_lang(&self) -> &str {
+        &self.base
+    }
+
+    pub fn base(&self) -> &LanguageData {
+        self.get(&self.base)
+            .expect("Base language folder not found")
+    }
+
+    // Parses every language up front, in parallel, for the checks that
+    // compare all languages anyway. Nothing is kept in low-memory mode.
+    pub fn load_all(&self) {
+        if self.low_memory {
+            return;
+        }
+        self.languages.par_iter().for_each(|(lang, _)| {
+            self.get(lang);
+        });
+    }
+
+    // Runs `check` on every language and collects its findings. Languages
+    // are checked in parallel, or one at a time in low-memory mode, where
+    // each language is dropped once its check is done. Excluded languages
+    // are skipped.
+    pub fn flat_map_languages<T: Send>(
+        &self,
+        check: impl Fn(&str, &LanguageData) -> Vec<T> + Sync,
+    ) -> Vec<T> {
+        if !self.low_memory {
+            self.load_all();
+            return self
+                .languages
+                .par_iter()
+                .filter(|(lang, _)| !self.excluded.contains(*lang))
+                .flat_map_iter(|(lang, _)| check(lang, self.get(lang).unwrap()))
+                .collect();
+        }
+
+        self.languages
+            .keys()
+            .flat_map(|lang| {
+                if *lang == self.base {
+                    return check(lang, self.base());
+                }
+                let data = self.load(lang);
+                if self.excluded.contains(lang) {
+                    return Vec::new();
+                }
+                check(lang, &data)
+            })
+            .collect()
+    }
+
+    // The files that couldn't be read so far, with their language and
+    // error, by path.
+    pub fn unreadable_files(&self) -> BTreeMap<Arc<str>, (Lang, String)> {
+        self.unreadable
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    // The keys defined more than once so far, by language and key.
+    pub fn duplicate_keys(&self) -> BTreeMap<(Lang, KeyId), Duplicate> {
+        self.duplicates
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    // The keys of messages flagged `fuzzy` so far, by language and key,
+    // with their file.
+    pub fn fuzzy_entries(&self) -> BTreeMap<(Lang, KeyId), Arc<str>> {
+        self.fuzzy
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    pub fn excluded_languages(&self) -> BTreeSet<Lang> {
+        self.excluded.iter().map(|lang| lang.clone()).collect()
+    }
+
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    // The translation files of a language, without parsing them, including
+    // those of its aliases.
+    pub fn files(&self, lang: &str) -> Vec<PathBuf> {
+        self.folders(lang)
+            .iter()
+            .flat_map(|folder| self.folder_files(folder))
+            .collect()
+    }
+
+    // The translation files of one language folder. Hidden files such as
+    // the review status sidecar are not translations. A base language
+    // without a folder is read from its module.
+    fn folder_files(&self, lang: &str) -> Vec<PathBuf> {
+        if lang == self.base
+            && let Some(module) = base_module(&self.base_path, lang)
+        {
+            return vec![module];
+        }
+        let base_path = Pattern::escape(&self.base_path.display().to_string());
+        let lang = Pattern::escape(lang);
+        let patterns: Vec<String> = FileFormat::EXTENSIONS
+            .iter()
+            .flat_map(|(extension, format)| match self.layout {
+                // gettext keeps the catalogs of a language in `LC_MESSAGES`.
+                Layout::Folders if *format == FileFormat::Gettext => vec![
+                    format!("{}/{}/*.{}", base_path, lang, extension),
+                    format!("{}/{}/LC_MESSAGES/*.{}", base_path, lang, extension),
+                ],
+                Layout::Folders => vec![format!("{}/{}/*.{}", base_path, lang, extension)],
+                Layout::Files => vec![
+                    format!("{}/{}.{}", base_path, lang, extension),
+                    format!("{}/{}.*.{}", base_path, lang, extension),
+                ],
+            })
+            .collect();
+        let options = MatchOptions {
+            require_literal_leading_dot: true,
+            ..MatchOptions::new()
+        };
+        let mut files: Vec<PathBuf> = patterns
+            .iter()
+            .flat_map(|pattern| {
+                glob_with(pattern, options)
+                    .expect("Failed to read glob pattern")
+                    .flatten()
+            })
+            .filter(|file| !self.excludes_file(file))
+            .collect();
+        files.sort();
+        files
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    // Where the files of a language are: its folder, or the translation
+    // directory itself for files named after their language.
+    pub fn language_dir(&self, lang: &str) -> PathBuf {
+        match self.layout {
+            Layout::Folders => self.base_path.join(lang),
+            Layout::Files => self.base_path.clone(),
+        }
+    }
+
+    // The translation files parsed so far by this run.
+    pub fn read_files(&self) -> Vec<Arc<str>> {
+        self.read.iter().map(|file| file.clone()).collect()
+    }
+
+    // Entries are attributed to the exact paths globbed for this load, never
+    // to names remembered from an earlier one.
+    fn load(&self, lang: &str) -> LanguageData {
+        self.timings.record_parsed(lang);
+
+        let files = self.files(lang);
+        for file in &files {
+            self.read.insert(file.to_string_lossy().into());
+        }
+        let mut data = match self.aliases.get(lang) {
+            Some(aliases) => self.load_merged(lang, aliases),
+            None => load_language(lang, &files, |key| self.intern(key), self.timings),
+        };
+        if !self.excluded_keys.is_empty() {
+            let excluded = |key: &str| self.excluded_keys.iter().any(|pattern| pattern.covers(key));
+            data.values.retain(|key, _| !excluded(key));
+            data.arrays.retain(|key, _| !excluded(key));
+            data.duplicates.retain(|(key, _, _)| !excluded(key));
+            data.fuzzy.retain(|(key, _)| !excluded(key));
+        }
+        // A folder that can't be listed would read as a language without
+        // keys; it is recorded like a file that can't be read instead, which
+        // leaves the language out of the comparison.
+        if self.layout == Layout::Folders {
+            for folder in self.folders(lang) {
+                let dir = self.base_path.join(&folder);
+                if let Err(err) = fs::read_dir(&dir)
+                    && err.kind() != io::ErrorKind::NotFound
+                {
+                    data.unreadable.push((
+                        dir.to_string_lossy().into(),
+                        format!("failed to list the folder: {}", err),
+                    ));
+                }
+            }
+        }
+        for (file, err) in &data.unreadable {
+            self.read.remove(file);
+            self.unreadable
+                .insert(file.clone(), (lang.to_string(), err.clone()));
+        }
+        // A key defined in three files is two duplicates, the second keeping
+        // the file that replaced the first.
+        for (key, dropped, kept) in &data.duplicates {
+            let mut entry = self
+                .duplicates
+                .entry((lang.to_string(), key.clone()))
+                .or_insert_with(|| (Vec::new(), kept.clone()));
+            entry.0.push(dropped.clone());
+            entry.1 = kept.clone();
+        }
+        for (key, file) in &data.fuzzy {
+            self.fuzzy
+                .insert((lang.to_string(), key.clone()), file.clone());
+        }
+        if lang != self.base
+            && data.unreadable.len() as f64 > self.max_unreadable * files.len() as f64
+        {
+            self.excluded.insert(lang.to_string());
+        }
+        data
+    }
+
+    // Reads the folder of `lang` and then those of its aliases. A key the
+    // language's own folder already defines is kept, and recorded as a
+    // conflict when the alias has another value for it.
+    fn load_merged(&self, lang: &str, aliases: &[Lang]) -> LanguageData {
+        let mut data = load_language(
+            lang,
+            &self.folder_files(lang),
+            |key| self.intern(key),
+            self.timings,
+        );
+        for alias in aliases {
+            let other = load_language(
+                alias,
+                &self.folder_files(alias),
+                |key| self.intern(key),
+                self.timings,
+            );
+            data.unreadable.extend(other.unreadable);
+            data.duplicates.extend(other.duplicates);
+            data.fuzzy.extend(other.fuzzy);
+            for (key, array) in other.arrays {
+                data.arrays.entry(key).or_insert(array);
+            }
+            for (key, entry) in other.values {
+                match data.values.get(&key) {
+                    Some(kept) if kept.value != entry.value => {
+                        let conflict = AliasConflict {
+                            kept: kept.file.clone(),
+                            kept_value: kept.value.clone(),
+                            other: entry.file,
+                            other_value: entry.value,
+                        };
+                        self.conflicts.insert((lang.to_string(), key), conflict);
+                    }
+                    Some(_) => {}
+                    None => {
+                        data.values.insert(key, entry);
+                    }
+                }
+            }
+        }
+        data
+    }
+
+    fn intern(&self, key: String) -> KeyId {
+        if let Some(interned) = self.keys.get(key.as_str()) {
+            return interned.clone();
+        }
+        let key: KeyId = key.into();
+        self.keys.insert(key.clone());
+        key
+    }
+}
+
+// The module holding the base language, such as `i18n/en.ts`, when it has
+// no folder of its own.
+pub fn base_module(base_path: &Path, base_lang: &str) -> Option<PathBuf> {
+    if base_path.join(base_lang).is_dir() {
+        return None;
+    }
+    module::EXTENSIONS
+        .iter()
+        .map(|extension| base_path.join(format!("{}.{}", base_lang, extension)))
+        .find(|path| path.is_file())
+}
@@ -0,0 +1,252 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+// The extensions of translation modules, which may hold the base language
+// instead of a folder of JSON files.
+pub const EXTENSIONS: [&str; 2] = ["ts", "js"];
+
+lazy_static! {
+    // Where the exported object starts: `export default`, `module.exports =`
+    // or `export const en: Messages =`.
+    static ref EXPORT_REGEX: Regex = Regex::new(
+        r"(?m)^[ \t]*(?:export\s+default|module\.exports\s*=|export\s+const\s+[A-Za-z_$][\w$]*\s*(?::[^=\n]+)?=)\s*"
+    )
+    .unwrap();
+}
+
+// Reads the object literal a TypeScript or JavaScript module exports, as in
+// `export default { checkout: { title: 'Checkout' } }`, into the document a
+// JSON file would hold. The module is never run: keys must be identifiers,
+// strings or numbers and values strings, template literals or nested
+// objects. `${name}` in a template literal stands for the placeholder
+// `{name}`. Anything else fails with its position rather than losing keys.
+pub fn parse_module(content: &str) -> Result<Value, String> {
+    let start = EXPORT_REGEX
+        .find(content)
+        .ok_or("no exported object literal (expected `export default { ... }`)")?
+        .end();
+    let mut parser = Parser {
+        content,
+        bytes: content.as_bytes(),
+        pos: start,
+    };
+    if parser.peek() != Some(b'{') {
+        return Err(parser.error("the export must be an object literal"));
+    }
+    parser.value()
+}
+
+struct Parser<'a> {
+    content: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        let before = &self.content[..self.pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        format!("{} at line {} column {}", message, line, column)
+    }
+
+    // Skips whitespace and comments, and returns the next byte.
+    fn peek(&mut self) -> Option<u8> {
+        loop {
+            let rest = &self.bytes[self.pos..];
+            if rest.first().is_some_and(u8::is_ascii_whitespace) {
+                self.pos += 1;
+            } else if rest.starts_with(b"//") {
+                self.pos += rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+            } else if rest.starts_with(b"/*") {
+                match self.content[self.pos + 2..].find("*/") {
+                    Some(end) => self.pos += end + 4,
+                    None => self.pos = self.bytes.len(),
+                }
+            } else {
+                return rest.first().copied();
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(quote @ (b'\'' | b'"')) => self.string(quote).map(Value::String),
+            Some(b'`') => self.template().map(Value::String),
+            Some(b'[') => Err(self.error("arrays aren't supported")),
+            Some(_) => Err(self.error("unsupported value (only strings and objects are read)")),
+            None => Err(self.error("unexpected end of module")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.pos += 1;
+        let mut map = Map::new();
+        loop {
+            let key = match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(map));
+                }
+                Some(quote @ (b'\'' | b'"')) => self.string(quote)?,
+                Some(b'[') => return Err(self.error("computed keys aren't supported")),
+                Some(b'.') => return Err(self.error("spread properties aren't supported")),
+                Some(byte) if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' => {
+                    let start = self.pos;
+                    while self.bytes.get(self.pos).is_some_and(|&byte| {
+                        byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+                    }) {
+                        self.pos += 1;
+                    }
+                    self.content[start..self.pos].to_string()
+                }
+                Some(_) => return Err(self.error("expected a key")),
+                None => return Err(self.error("unexpected end of module")),
+            };
+            match self.peek() {
+                Some(b':') => self.pos += 1,
+                Some(b',' | b'}') => {
+                    return Err(
+                        self.error(&format!("shorthand property `{}` isn't supported", key))
+                    );
+                }
+                Some(b'(') => return Err(self.error(&format!("method `{}` isn't supported", key))),
+                _ => return Err(self.error("expected `:`")),
+            }
+            let value = self.value()?;
+            map.insert(key, value);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {}
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    // A quoted string, its escapes decoded.
+    fn string(&mut self, quote: u8) -> Result<String, String> {
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            let Some(c) = self.content[self.pos..].chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            match c {
+                '\\' => {
+                    self.pos += 1;
+                    self.escape(&mut text)?;
+                }
+                '\n' => return Err(self.error("unterminated string")),
+                _ if c as u32 == quote as u32 => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                _ => {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    // A template literal, whose `${name}` substitutions become placeholders.
+    fn template(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut text = String::new();
+        loop {
+            let Some(c) = self.content[self.pos..].chars().next() else {
+                return Err(self.error("unterminated template literal"));
+            };
+            match c {
+                '\\' => {
+                    self.pos += 1;
+                    self.escape(&mut text)?;
+                }
+                '`' => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                '$' if self.content[self.pos..].starts_with("${") => {
+                    let Some(end) = self.content[self.pos..].find('}') else {
+                        return Err(self.error("unterminated substitution"));
+                    };
+                    let expression = self.content[self.pos + 2..self.pos + end].trim();
+                    if expression.is_empty()
+                        || !expression.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    {
+                        return Err(self.error(&format!(
+                            "unsupported substitution `${{{}}}` (only `${{name}}` maps to a placeholder)",
+                            expression
+                        )));
+                    }
+                    text.push_str(&format!("{{{}}}", expression));
+                    self.pos += end + 1;
+                }
+                _ => {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    // The escape sequence after a backslash.
+    fn escape(&mut self, text: &mut String) -> Result<(), String> {
+        let Some(c) = self.content[self.pos..].chars().next() else {
+            return Err(self.error("unterminated string"));
+        };
+        self.pos += c.len_utf8();
+        let decoded = match c {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            '0' => '\0',
+            // A line continuation.
+            '\n' => return Ok(()),
+            'x' => char::from_u32(self.hex(2)?).unwrap_or(char::REPLACEMENT_CHARACTER),
+            'u' if self.bytes.get(self.pos) == Some(&b'{') => {
+                let Some(end) = self.content[self.pos..].find('}') else {
+                    return Err(self.error("invalid unicode escape"));
+                };
+                let hex = &self.content[self.pos + 1..self.pos + end];
+                self.pos += end + 1;
+                u32::from_str_radix(hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error("invalid unicode escape"))?
+            }
+            'u' => {
+                let high = self.hex(4)?;
+                // A surrogate pair spelled as two escapes.
+                if (0xD800..0xDC00).contains(&high) && self.content[self.pos..].starts_with("\\u") {
+                    self.pos += 2;
+                    let low = self.hex(4)?;
+                    char::from_u32(0x10000 + ((high - 0xD800) << 10) + low.wrapping_sub(0xDC00))
+                        .ok_or_else(|| self.error("invalid unicode escape"))?
+                } else {
+                    // A lone surrogate, which no `char` holds.
+                    char::from_u32(high).unwrap_or(char::REPLACEMENT_CHARACTER)
+                }
+            }
+            c => c,
+        };
+        text.push(decoded);
+        Ok(())
+    }
+
+    // The value of the next `digits` hex digits.
+    fn hex(&mut self, digits: usize) -> Result<u32, String> {
+        let hex = self
+            .content
+            .get(self.pos..self.pos + digits)
+            .ok_or_else(|| self.error("invalid escape"))?;
+        let value = u32::from_str_radix(hex, 16).map_err(|_| self.error("invalid escape"))?;
+        self.pos += digits;
+        Ok(value)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::loader;
+use serde_json::Value;
+
+pub const DEFAULT_INDENT: &str = "  ";
+
+// Writes `value` at `depth`, object keys sorted by code point.
+fn write(value: &Value, indent: &str, depth: usize, out: &mut String) {
+    let items: Vec<(Option<&String>, &Value)> = match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().map(|(key, value)| (Some(key), value)).collect();
+            entries.sort_by_key(|(key, _)| *key);
+            entries
+        }
+        Value::Array(items) => items.iter().map(|item| (None, item)).collect(),
+        scalar => {
+            out.push_str(&serde_json::to_string(scalar).unwrap());
+            return;
+        }
+    };
+    let (open, close) = if value.is_object() {
+        ('{', '}')
+    } else {
+        ('[', ']')
+    };
+    out.push(open);
+    if items.is_empty() {
+        out.push(close);
+        return;
+    }
+    for (index, (key, item)) in items.iter().enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        out.push('\n');
+        out.push_str(&indent.repeat(depth + 1));
+        if let Some(key) = key {
+            out.push_str(&serde_json::to_string(key).unwrap());
+            out.push_str(": ");
+        }
+        write(item, indent, depth + 1, out);
+    }
+    out.push('\n');
+    out.push_str(&indent.repeat(depth));
+    out.push(close);
+}
+
+// A JSON translation file as `format` writes it: keys sorted by code point
+// at every level, each level indented by `indent`, and a trailing newline.
+// Escapes such as `\u00e9` are written as the characters they stand for.
+// A document defining a member twice in an object is refused, as only the
+// last definition would be written.
+pub fn normalize(content: &str, indent: &str) -> Result<String, String> {
+    let duplicates = loader::duplicate_members(content)?;
+    if !duplicates.is_empty() {
+        return Err(format!(
+            "{} defined more than once; remove all but one definition",
+            duplicates
+                .iter()
+                .map(|key| format!("`{}`", key))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    let value: Value = serde_json::from_str(content).map_err(|err| err.to_string())?;
+    let mut out = String::new();
+    write(&value, indent, 0, &mut out);
+    out.push('\n');
+    Ok(out)
+}
@@ -0,0 +1,156 @@
+use crate::handles;
+use crate::yaml;
+use glob::glob;
+use rayon::prelude::*;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+// A string a spec file may return to the frontend as a translation key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub key: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl Reference {
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file.display(), self.line)
+    }
+}
+
+// The values of one `enum` (or `const`, in OpenAPI 3.1), or the value of an
+// `x-translation-key` extension field, which always names a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub references: Vec<Reference>,
+    pub extension: bool,
+}
+
+// The spec files matched by `patterns`, with the ones that can't be read
+// or parsed as warnings.
+pub fn scan(patterns: &[String]) -> (Vec<Group>, Vec<String>) {
+    let mut files = Vec::new();
+    let mut warnings = Vec::new();
+    for pattern in patterns {
+        match glob(pattern) {
+            Ok(paths) => files.extend(paths.flatten()),
+            Err(err) => warnings.push(format!(
+                "invalid `--scan-openapi` glob `{}`: {}",
+                pattern, err
+            )),
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let parsed: Vec<Result<Vec<Group>, String>> = files
+        .par_iter()
+        .map(|path| {
+            let content = handles::read_to_string(path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+            parse(path, &content)
+        })
+        .collect();
+    let mut groups = Vec::new();
+    for file in parsed {
+        match file {
+            Ok(file) => groups.extend(file),
+            Err(warning) => warnings.push(warning),
+        }
+    }
+    (groups, warnings)
+}
+
+// JSON and YAML documents are parsed whole; each reference is given the
+// line of the first occurrence of its key, and the groups are in the order
+// of the document.
+pub fn parse(path: &Path, content: &str) -> Result<Vec<Group>, String> {
+    let json =
+        path.extension().is_some_and(|ext| ext == "json") || content.trim_start().starts_with('{');
+    let document: Value = if json {
+        serde_json::from_str(content).map_err(|err| err.to_string())
+    } else {
+        yaml::parse(content)
+    }
+    .map_err(|err| format!("failed to parse {}: {}", path.display(), err))?;
+    let line = |key: &str| {
+        if json {
+            json_line(content, key)
+        } else {
+            yaml_line(content, key)
+        }
+    };
+    let mut groups = Vec::new();
+    collect_json(&document, &mut |values, extension| {
+        let references = values
+            .into_iter()
+            .map(|key| Reference {
+                line: line(&key),
+                key,
+                file: path.to_path_buf(),
+            })
+            .collect();
+        groups.push(Group {
+            references,
+            extension,
+        });
+    });
+    groups.sort_by_key(|group: &Group| group.references.first().map(|reference| reference.line));
+    Ok(groups)
+}
+
+fn collect_json(value: &Value, found: &mut impl FnMut(Vec<String>, bool)) {
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map {
+                match (name.as_str(), child) {
+                    ("enum", Value::Array(values)) => found(
+                        values
+                            .iter()
+                            .filter_map(|value| value.as_str().map(str::to_string))
+                            .collect(),
+                        false,
+                    ),
+                    ("const", Value::String(key)) => found(vec![key.clone()], false),
+                    ("x-translation-key", Value::String(key)) => found(vec![key.clone()], true),
+                    _ => collect_json(child, found),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_json(item, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+// The line of the first occurrence of `key` as a JSON string.
+fn json_line(content: &str, key: &str) -> usize {
+    let quoted = serde_json::to_string(key).unwrap();
+    content
+        .find(&quoted)
+        .map_or(1, |offset| content[..offset].matches('\n').count() + 1)
+}
+
+// The line of the first scalar `key` stands alone in, outside comments,
+// as a plain or quoted value.
+fn yaml_line(content: &str, key: &str) -> usize {
+    let escaped = key.replace('\'', "''");
+    content
+        .lines()
+        .position(|line| {
+            let text = yaml::strip_comment(line);
+            [key, &escaped].iter().any(|literal| {
+                text.match_indices(literal).any(|(offset, _)| {
+                    let before = text[..offset].chars().next_back();
+                    let after = text[offset + literal.len()..].chars().next();
+                    before.is_none_or(|c| " -:[,'\"".contains(c))
+                        && after.is_none_or(|c| " ,]'\"".contains(c))
+                })
+            })
+        })
+        .map_or(1, |index| index + 1)
+}
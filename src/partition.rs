@@ -0,0 +1,172 @@
+use crate::report::{Finding, Report};
+use std::collections::BTreeMap;
+
+// One shard of a run split across CI jobs, `index` out of `count`
+// (starting at 1). Base keys are assigned by hashing their first segment, so
+// a namespace always lands in the same shard whatever the other keys are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    pub index: usize,
+    pub count: usize,
+}
+
+// What a partial report records about the shard it covers, so that the
+// fragments can be checked against each other when merging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fragment {
+    pub partition: Partition,
+    // Base keys assigned to this shard, and in the whole run.
+    pub keys: usize,
+    pub total_keys: usize,
+}
+
+impl Partition {
+    pub fn parse(value: &str) -> Result<Partition, String> {
+        let invalid = || {
+            format!(
+                "invalid value `{}` for `--partition` (expected `<index>/<count>`, such as `2/4`)",
+                value
+            )
+        };
+        let (index, count) = value.split_once('/').ok_or_else(invalid)?;
+        let index: usize = index.trim().parse().map_err(|_| invalid())?;
+        let count: usize = count.trim().parse().map_err(|_| invalid())?;
+        if count == 0 || index == 0 || index > count {
+            return Err(invalid());
+        }
+        Ok(Partition { index, count })
+    }
+
+    // The shard owning `key`, starting at 1.
+    pub fn of(key: &str, count: usize) -> usize {
+        let prefix = key.split('.').next().unwrap_or_default();
+        // FNV-1a, stable across platforms and Rust versions unlike the
+        // standard library hashers.
+        let hash = prefix
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+        (hash % count as u64) as usize + 1
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        Partition::of(key, self.count) == self.index
+    }
+
+    // Findings about a key follow the key; the others, such as missing
+    // loader files, are reported by the first shard only.
+    pub fn owns(&self, finding: &Finding) -> bool {
+        match &finding.key {
+            Some(key) => self.contains(key),
+            None => self.index == 1,
+        }
+    }
+
+    // Keeps the findings of this shard and tags the report with it. The
+    // coverage of the whole run goes with the findings without a key.
+    pub fn apply<'a>(&self, report: &mut Report, base_keys: impl Iterator<Item = &'a str>) {
+        let (mut keys, mut total_keys) = (0, 0);
+        for key in base_keys {
+            total_keys += 1;
+            if self.contains(key) {
+                keys += 1;
+            }
+        }
+        report.findings.retain(|finding| self.owns(finding));
+        if self.index != 1 {
+            report.coverage.clear();
+        }
+        report.fragment = Some(Fragment {
+            partition: *self,
+            keys,
+            total_keys,
+        });
+    }
+}
+
+// Combines the partial reports of every shard of a run into the full report.
+// Each shard must be present exactly once, and every finding must come from
+// the shard owning it.
+pub fn merge(fragments: Vec<(String, Report)>) -> Result<Report, String> {
+    let mut shards: BTreeMap<usize, (String, Fragment)> = BTreeMap::new();
+    let mut merged = Report::default();
+    let mut expected: Option<(String, Fragment)> = None;
+
+    for (name, report) in fragments {
+        let fragment = report
+            .fragment
+            .ok_or_else(|| format!("{} is not a partial report (it has no `partition`)", name))?;
+        let partition = fragment.partition;
+
+        if let Some((first, reference)) = &expected
+            && (reference.partition.count, reference.total_keys)
+                != (partition.count, fragment.total_keys)
+        {
+            return Err(format!(
+                "{} and {} come from different runs ({} shards of {} keys, and {} shards of {} keys)",
+                first,
+                name,
+                reference.partition.count,
+                reference.total_keys,
+                partition.count,
+                fragment.total_keys
+            ));
+        }
+        expected.get_or_insert_with(|| (name.clone(), fragment));
+
+        if let Some((other, _)) = shards.get(&partition.index) {
+            return Err(format!(
+                "{} and {} both cover partition {}/{}",
+                other, name, partition.index, partition.count
+            ));
+        }
+        if let Some(finding) = report
+            .findings
+            .iter()
+            .find(|finding| !partition.owns(finding))
+        {
+            return Err(format!(
+                "{} reports `{}` (partition {}/{}) outside its own partition {}/{}",
+                name,
+                finding.key.as_deref().unwrap_or(finding.check.info().id),
+                finding
+                    .key
+                    .as_deref()
+                    .map_or(1, |key| Partition::of(key, partition.count)),
+                partition.count,
+                partition.index,
+                partition.count
+            ));
+        }
+
+        merged.config = merged.config.or(report.config);
+        merged.coverage.extend(report.coverage);
+        merged.context.extend(report.context);
+        merged.extend(report.findings);
+        shards.insert(partition.index, (name, fragment));
+    }
+
+    let Some((_, reference)) = expected else {
+        return Err("no reports to merge".to_string());
+    };
+    let count = reference.partition.count;
+    let missing: Vec<String> = (1..=count)
+        .filter(|index| !shards.contains_key(index))
+        .map(|index| format!("{}/{}", index, count))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("missing partitions {}", missing.join(", ")));
+    }
+
+    let covered: usize = shards.values().map(|(_, fragment)| fragment.keys).sum();
+    if covered != reference.total_keys {
+        return Err(format!(
+            "the partitions cover {} of {} base keys; they were not produced from the same tree",
+            covered, reference.total_keys
+        ));
+    }
+
+    merged.sort();
+    Ok(merged)
+}
@@ -0,0 +1,218 @@
+use crate::base::Base;
+use crate::checks;
+use crate::model::Project;
+use crate::report::{Check, Finding, Report};
+use crate::unicode;
+use serde_json::{Map, Value, json};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// A JSON pointer segment, `~` and `/` escaped as RFC 6901 requires.
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn pointer(segments: &[String]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("/{}", escape(segment)))
+        .collect()
+}
+
+// The object keys leading to the value of a flattened key, whose segments
+// may be nested objects or dotted keys alike. Keys are matched in NFC so
+// that a key stays found once a previous patch renamed one of its parents.
+fn locate(value: &Value, segments: &[&str]) -> Option<Vec<String>> {
+    if segments.is_empty() {
+        return value.is_string().then(Vec::new);
+    }
+    let map = value.as_object()?;
+    for end in (1..=segments.len()).rev() {
+        let name = unicode::nfc(&segments[..end].join(".")).into_owned();
+        for (key, child) in map {
+            if unicode::nfc(key) == name
+                && let Some(mut rest) = locate(child, &segments[end..])
+            {
+                rest.insert(0, key.clone());
+                return Some(rest);
+            }
+        }
+    }
+    None
+}
+
+// The patch adding `value` under `segments`, nesting the segments no
+// object of the document holds yet like `nest_json` does.
+fn add(document: &Value, segments: &[&str], value: &str) -> Option<Vec<Value>> {
+    let nest = |segments: &[&str]| {
+        segments
+            .iter()
+            .rev()
+            .fold(Value::String(value.to_string()), |value, segment| {
+                let mut map = Map::new();
+                map.insert(segment.to_string(), value);
+                Value::Object(map)
+            })
+    };
+    // The file doesn't exist yet.
+    if document.is_null() {
+        return Some(vec![
+            json!({ "op": "add", "path": "", "value": nest(segments) }),
+        ]);
+    }
+
+    let mut path = Vec::new();
+    let mut node = document.as_object()?;
+    let mut rest = segments;
+    'walk: loop {
+        for end in (1..rest.len()).rev() {
+            let name = rest[..end].join(".");
+            if let Some(Value::Object(child)) = node.get(&name) {
+                path.push(name);
+                node = child;
+                rest = &rest[end..];
+                continue 'walk;
+            }
+        }
+        break;
+    }
+    // A value already holds the first segment: the key conflicts with it.
+    if node.contains_key(rest[0]) {
+        return None;
+    }
+    path.push(rest[0].to_string());
+    Some(vec![
+        json!({ "op": "add", "path": pointer(&path), "value": nest(&rest[1..]) }),
+    ])
+}
+
+// The patch renaming every segment of `key` stored decomposed.
+fn normalize_key(document: &Value, segments: &[&str]) -> Option<Vec<Value>> {
+    let mut path = locate(document, segments)?;
+    let mut patch = Vec::new();
+    for index in 0..path.len() {
+        if let Cow::Owned(normalized) = unicode::nfc(&path[index]) {
+            let from = pointer(&path[..=index]);
+            path[index] = normalized;
+            patch.push(json!({ "op": "move", "from": from, "path": pointer(&path[..=index]) }));
+        }
+    }
+    (!patch.is_empty()).then_some(patch)
+}
+
+fn normalize_value(document: &Value, segments: &[&str]) -> Option<Vec<Value>> {
+    let path = pointer(&locate(document, segments)?);
+    match unicode::nfc(document.pointer(&path)?.as_str()?) {
+        Cow::Owned(normalized) => Some(vec![
+            json!({ "op": "replace", "path": path, "value": normalized }),
+        ]),
+        Cow::Borrowed(_) => None,
+    }
+}
+
+fn remove(document: &Value, segments: &[&str]) -> Option<Vec<Value>> {
+    let path = pointer(&locate(document, segments)?);
+    Some(vec![json!({ "op": "remove", "path": path })])
+}
+
+// The file whose document fixing `finding` patches.
+fn target(finding: &Finding, project: &Project, base: &Base) -> Option<String> {
+    match finding.check {
+        Check::MissingKey => Some(checks::expected_file(
+            project,
+            base,
+            finding.lang.as_deref()?,
+            finding.key.as_deref()?,
+        )),
+        Check::NotNfc | Check::UnusedKey | Check::ExtraKey => finding.file.clone(),
+        _ => None,
+    }
+}
+
+fn patch(finding: &Finding, base: &Base, document: &Value) -> Option<Vec<Value>> {
+    let key = finding.key.as_deref()?;
+    let segments: Vec<&str> = key.split('.').collect();
+    match (finding.check, finding.details.first()) {
+        (Check::MissingKey, _) => add(document, &segments, base.value(key)?),
+        (Check::NotNfc, Some((_, part))) if part == "key" => normalize_key(document, &segments),
+        (Check::NotNfc, _) => normalize_value(document, &segments),
+        _ => remove(document, &segments),
+    }
+}
+
+// Applies the operations this module emits, failing like RFC 6902 asks
+// when a path doesn't exist.
+fn apply(document: &mut Value, patch: &[Value]) -> Option<()> {
+    fn split(path: &str) -> Option<(&str, String)> {
+        let (parent, last) = path.rsplit_once('/')?;
+        Some((parent, last.replace("~1", "/").replace("~0", "~")))
+    }
+    fn take(document: &mut Value, path: &str) -> Option<Value> {
+        let (parent, last) = split(path)?;
+        document.pointer_mut(parent)?.as_object_mut()?.remove(&last)
+    }
+    fn put(document: &mut Value, path: &str, value: Value) -> Option<()> {
+        if path.is_empty() {
+            *document = value;
+            return Some(());
+        }
+        let (parent, last) = split(path)?;
+        document
+            .pointer_mut(parent)?
+            .as_object_mut()?
+            .insert(last, value);
+        Some(())
+    }
+
+    for operation in patch {
+        let path = operation["path"].as_str()?;
+        match operation["op"].as_str()? {
+            "add" => put(document, path, operation["value"].clone())?,
+            "remove" => {
+                take(document, path)?;
+            }
+            "replace" => *document.pointer_mut(path)? = operation["value"].clone(),
+            "move" => {
+                let value = take(document, operation["from"].as_str()?)?;
+                put(document, path, value)?;
+            }
+            _ => return None,
+        }
+    }
+    Some(())
+}
+
+// `--suggest-fixes`: attaches to each finding with a mechanical fix the
+// RFC 6902 patch applying it to the JSON document of its file: adding a
+// missing key with the base value, removing an unused or extra key, and
+// composing a decomposed key or value like `--fix nfc` does. The patches
+// of a file apply in the order of the report, each to the document the
+// previous ones left, so findings already fixed by an earlier patch, like
+// the keys of an object another key finding renamed, get none.
+pub fn suggest_fixes(report: &mut Report, project: &Project, base: &Base) {
+    // The document of each file as patched so far; `Null` for a file that
+    // doesn't exist yet, `None` for one that can't be parsed.
+    let mut documents: HashMap<String, Option<Value>> = HashMap::new();
+    for finding in &mut report.findings {
+        let Some(file) = target(finding, project, base) else {
+            continue;
+        };
+        let document = documents.entry(file.clone()).or_insert_with(|| {
+            if !Path::new(&file).exists() {
+                return Some(Value::Null);
+            }
+            let content = fs::read_to_string(&file).ok()?;
+            serde_json::from_str(&content).ok()
+        });
+        let Some(document) = document else {
+            continue;
+        };
+        if let Some(patch) = patch(finding, base, document)
+            && apply(document, &patch).is_some()
+        {
+            finding.suggested_fix = Some(json!({ "file": file, "patch": patch }));
+        }
+    }
+}
@@ -0,0 +1,48 @@
+mod cldr;
+
+use cldr::RULES;
+
+// A plural category of a language, such as `few`, with sample numbers
+// selecting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Category {
+    pub name: &'static str,
+    // Translations may leave it out and fall back to `other`.
+    pub optional: bool,
+    pub examples: &'static str,
+}
+
+const fn required(name: &'static str, examples: &'static str) -> Category {
+    Category {
+        name,
+        optional: false,
+        examples,
+    }
+}
+
+const fn optional(name: &'static str, examples: &'static str) -> Category {
+    Category {
+        name,
+        optional: true,
+        examples,
+    }
+}
+
+// The CLDR plural categories of a language tag, with the tag they were found
+// for: the tag itself or, failing that, its closest parent, `pt` for `pt-BR`.
+// Tags are compared ignoring case, with `_` standing for `-`.
+pub fn categories(tag: &str) -> Option<(&'static str, &'static [Category])> {
+    let mut tag = tag.replace('_', "-");
+    loop {
+        let found = RULES.iter().find_map(|(tags, categories)| {
+            tags.iter()
+                .find(|known| known.eq_ignore_ascii_case(&tag))
+                .map(|known| (*known, *categories))
+        });
+        if found.is_some() {
+            return found;
+        }
+        let (parent, _) = tag.rsplit_once('-')?;
+        tag = parent.to_string();
+    }
+}
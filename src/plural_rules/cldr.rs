@@ -0,0 +1,253 @@
+use super::{Category, optional, required};
+
+// The cardinal plural categories of each language, grouped by rule set as
+// in CLDR 44's `plurals.xml`, with the integer samples of each category, or
+// the decimal ones for categories only decimals select. `many` is optional
+// where CLDR only selects it for millions and compact numbers.
+pub const RULES: [(&[&str], &[Category]); 26] = [
+    (
+        &[
+            "bm", "bo", "dz", "hnj", "id", "ig", "ii", "in", "ja", "jbo", "jv", "jw", "kde", "kea",
+            "km", "ko", "lkt", "lo", "ms", "my", "nqo", "osa", "root", "sah", "ses", "sg", "su",
+            "th", "to", "tpi", "vi", "wo", "yo", "yue", "zh",
+        ],
+        &[required("other", "0~15, 100, 1000, 10000, 100000, 1000000")],
+    ),
+    (
+        &[
+            "ak", "am", "as", "bho", "bn", "doi", "fa", "ff", "gu", "guw", "hi", "hy", "kab", "kn",
+            "ln", "mg", "nso", "pa", "pcm", "si", "ti", "wa", "zu",
+        ],
+        &[
+            required("one", "0, 1"),
+            required("other", "2~17, 100, 1000, 10000, 100000, 1000000"),
+        ],
+    ),
+    (
+        &[
+            "af", "an", "asa", "ast", "az", "bal", "bem", "bez", "bg", "brx", "ce", "cgg", "chr",
+            "ckb", "da", "de", "dv", "ee", "el", "en", "eo", "et", "eu", "fi", "fo", "fur", "fy",
+            "gl", "gsw", "ha", "haw", "hu", "ia", "io", "jgo", "jmc", "ka", "kaj", "kcg", "kk",
+            "kkj", "kl", "ks", "ksb", "ku", "ky", "lb", "lg", "lij", "mas", "mgo", "ml", "mn",
+            "mr", "nah", "nb", "nd", "ne", "nl", "nn", "nnh", "no", "nr", "ny", "nyn", "om", "or",
+            "os", "pap", "ps", "rm", "rof", "rwk", "saq", "sc", "sd", "sdh", "seh", "sn", "so",
+            "sq", "ss", "ssy", "st", "sv", "sw", "syr", "ta", "te", "teo", "tig", "tk", "tn", "tr",
+            "ts", "ug", "ur", "uz", "ve", "vo", "vun", "wae", "xh", "xog", "yi",
+        ],
+        &[
+            required("one", "1"),
+            required("other", "0, 2~16, 100, 1000, 10000, 100000, 1000000"),
+        ],
+    ),
+    (
+        &["is", "mk"],
+        &[
+            required("one", "1, 21, 31, 41, 51, 61, 71, 81, 101, 1001"),
+            required("other", "0, 2~16, 100, 1000, 10000, 100000, 1000000"),
+        ],
+    ),
+    (
+        &["ceb", "fil", "tl"],
+        &[
+            required("one", "0~3, 5, 7, 8, 10~13, 15, 17, 18, 20, 21, 100, 1000"),
+            required("other", "4, 6, 9, 14, 16, 19, 24, 26, 29, 34, 36, 39"),
+        ],
+    ),
+    (
+        &["fr"],
+        &[
+            required("one", "0, 1"),
+            optional("many", "1000000, 2000000, 3000000"),
+            required("other", "2~17, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["pt"],
+        &[
+            required("one", "0, 1"),
+            optional("many", "1000000, 2000000, 3000000"),
+            required("other", "2~17, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["ca", "es", "it", "pt-PT", "vec"],
+        &[
+            required("one", "1"),
+            optional("many", "1000000, 2000000, 3000000"),
+            required("other", "0, 2~16, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["lv", "prg"],
+        &[
+            required("zero", "0, 10~20, 30, 40, 50, 60, 100, 1000"),
+            required("one", "1, 21, 31, 41, 51, 61, 71, 81, 101, 1001"),
+            required("other", "2~9, 22~29, 102, 1002"),
+        ],
+    ),
+    (
+        &["he", "iw"],
+        &[
+            required("one", "1"),
+            required("two", "2"),
+            required("other", "0, 3~17, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["iu", "naq", "sat", "se", "sma", "smi", "smj", "smn", "sms"],
+        &[
+            required("one", "1"),
+            required("two", "2"),
+            required("other", "0, 3~17, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["mo", "ro"],
+        &[
+            required("one", "1"),
+            required("few", "0, 2~16, 101, 1001"),
+            required("other", "20~35, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["bs", "hr", "sh", "sr"],
+        &[
+            required("one", "1, 21, 31, 41, 51, 61, 71, 81, 101, 1001"),
+            required("few", "2~4, 22~24, 32~34, 42~44, 52~54, 62, 102, 1002"),
+            required("other", "0, 5~19, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["gd"],
+        &[
+            required("one", "1, 11"),
+            required("two", "2, 12"),
+            required("few", "3~10, 13~19"),
+            required("other", "0, 20~34, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["dsb", "hsb", "sl"],
+        &[
+            required("one", "1, 101, 201, 301, 401, 501, 601, 701, 1001"),
+            required("two", "2, 102, 202, 302, 402, 502, 602, 702, 1002"),
+            required("few", "3, 4, 103, 104, 203, 204, 303, 304, 1003"),
+            required("other", "0, 5~19, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["cs", "sk"],
+        &[
+            required("one", "1"),
+            required("few", "2~4"),
+            required("many", "0.0~1.5, 10.0, 100.0, 1000.0"),
+            required("other", "0, 5~19, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["pl"],
+        &[
+            required("one", "1"),
+            required("few", "2~4, 22~24, 32~34, 42~44, 52~54, 62, 102, 1002"),
+            required("many", "0, 5~19, 100, 1000, 10000, 100000"),
+            required("other", "0.0~1.5, 10.0, 100.0, 1000.0"),
+        ],
+    ),
+    (
+        &["be", "ru", "uk"],
+        &[
+            required("one", "1, 21, 31, 41, 51, 61, 71, 81, 101, 1001"),
+            required("few", "2~4, 22~24, 32~34, 42~44, 52~54, 62, 102, 1002"),
+            required("many", "0, 5~19, 100, 1000, 10000, 100000"),
+            required("other", "0.0~1.5, 10.0, 100.0, 1000.0"),
+        ],
+    ),
+    (
+        &["lt"],
+        &[
+            required("one", "1, 21, 31, 41, 51, 61, 71, 81, 101, 1001"),
+            required("few", "2~9, 22~29, 102, 1002"),
+            required("many", "0.1~0.9, 1.1~1.7, 10.1, 100.1, 1000.1"),
+            required("other", "0, 10~20, 30, 40, 50, 60, 100, 1000"),
+        ],
+    ),
+    (
+        &["mt"],
+        &[
+            required("one", "1"),
+            required("two", "2"),
+            required("few", "0, 3~10, 103~109, 1003"),
+            required("many", "11~19, 111~117, 1011"),
+            required("other", "20~35, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["ga"],
+        &[
+            required("one", "1"),
+            required("two", "2"),
+            required("few", "3~6"),
+            required("many", "7~10"),
+            required("other", "0, 11~25, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["br"],
+        &[
+            required("one", "1, 21, 31, 41, 51, 61, 81, 101, 1001"),
+            required("two", "2, 22, 32, 42, 52, 62, 82, 102, 1002"),
+            required(
+                "few",
+                "3, 4, 9, 23, 24, 29, 33, 34, 39, 43, 44, 49, 103, 1003",
+            ),
+            required("many", "1000000"),
+            required("other", "0, 5~8, 10~20, 100, 1000, 10000, 100000"),
+        ],
+    ),
+    (
+        &["gv"],
+        &[
+            required("one", "1, 11, 21, 31, 41, 51, 61, 71, 101, 1001"),
+            required("two", "2, 12, 22, 32, 42, 52, 62, 72, 102, 1002"),
+            required("few", "0, 20, 40, 60, 80, 100, 120, 140, 1000, 10000"),
+            required("many", "0.0~1.5, 10.0, 100.0, 1000.0"),
+            required("other", "3~10, 13~19, 23, 103, 1003"),
+        ],
+    ),
+    (
+        &["kw"],
+        &[
+            required("zero", "0"),
+            required("one", "1"),
+            required("two", "2, 22, 42, 62, 82, 102, 122, 142, 1000, 10000"),
+            required("few", "3, 23, 43, 63, 83, 103, 123, 143, 1003"),
+            required("many", "21, 41, 61, 81, 101, 121, 141, 161, 1001"),
+            required("other", "4~19, 100, 1004, 1000000"),
+        ],
+    ),
+    (
+        &["ar", "ars"],
+        &[
+            required("zero", "0"),
+            required("one", "1"),
+            required("two", "2"),
+            required("few", "3~10, 103~110, 1003"),
+            required("many", "11~26, 111, 1011"),
+            required(
+                "other",
+                "100~102, 200~202, 300~302, 400~402, 500~502, 600, 1000",
+            ),
+        ],
+    ),
+    (
+        &["cy"],
+        &[
+            required("zero", "0"),
+            required("one", "1"),
+            required("two", "2"),
+            required("few", "3"),
+            required("many", "6"),
+            required("other", "4, 5, 7~20, 100, 1000, 10000, 100000"),
+        ],
+    ),
+];
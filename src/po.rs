@@ -0,0 +1,234 @@
+use crate::plural_rules;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+// A gettext catalog read as translations: its messages keyed by `msgid`,
+// and the keys of the entries flagged `fuzzy`, which `msgfmt` leaves out of
+// the compiled catalog until someone reviews them.
+#[derive(Debug, Default)]
+pub struct Catalog {
+    pub messages: Value,
+    pub fuzzy: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Message {
+    context: Option<String>,
+    id: Option<String>,
+    id_plural: Option<String>,
+    strs: BTreeMap<usize, String>,
+    fuzzy: bool,
+    obsolete: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+// Reads a `.po` catalog, or a `.pot` template, whose `msgid`s are the
+// values of its language. A message with a `msgctxt` is keyed
+// `<msgctxt>|<msgid>`; plural forms are keyed `<msgid>_<category>`, their
+// `msgstr[n]` taking the CLDR plural categories of `lang` in order, as
+// `[plural_forms]` reads them. Messages not translated yet, the header and
+// obsolete `#~` messages are left out.
+pub fn parse(source: &str, template: bool, lang: &str) -> Result<Catalog, String> {
+    let mut catalog = Catalog::default();
+    let mut messages = Map::new();
+    let mut message = Message::default();
+    let mut field = None;
+    for (number, line) in source.lines().enumerate() {
+        let error = |err: &str| format!("line {}: {}", number + 1, err);
+        let mut line = line.trim();
+        let mut obsolete = false;
+        if line.is_empty() {
+            finish(&mut message, template, lang, &mut messages, &mut catalog);
+            field = None;
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("#~") {
+            // Obsolete messages are only kept for translation memory.
+            if !message.obsolete && !message.strs.is_empty() {
+                finish(&mut message, template, lang, &mut messages, &mut catalog);
+            }
+            message.obsolete = true;
+            obsolete = true;
+            line = rest.trim_start();
+            if line.is_empty() {
+                continue;
+            }
+        } else if let Some(flags) = line.strip_prefix("#,") {
+            if !message.strs.is_empty() {
+                finish(&mut message, template, lang, &mut messages, &mut catalog);
+            }
+            message.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            continue;
+        } else if line.starts_with('#') {
+            if !message.strs.is_empty() {
+                finish(&mut message, template, lang, &mut messages, &mut catalog);
+            }
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let text = string(line).map_err(|err| error(&err))?;
+            let Some(field) = field else {
+                return Err(error("string outside of a message"));
+            };
+            match field {
+                Field::Context => message.context.get_or_insert_default().push_str(&text),
+                Field::Id => message.id.get_or_insert_default().push_str(&text),
+                Field::IdPlural => message.id_plural.get_or_insert_default().push_str(&text),
+                Field::Str(index) => message.strs.entry(index).or_default().push_str(&text),
+            }
+            continue;
+        }
+        let (keyword, rest) = line
+            .split_once([' ', '\t'])
+            .ok_or_else(|| error("expected a keyword and a string"))?;
+        let text = string(rest.trim()).map_err(|err| error(&err))?;
+        let next = match keyword {
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::IdPlural,
+            "msgstr" => Field::Str(0),
+            _ => match keyword
+                .strip_prefix("msgstr[")
+                .and_then(|index| index.strip_suffix(']'))
+                .and_then(|index| index.parse().ok())
+            {
+                Some(index) => Field::Str(index),
+                None => return Err(error(&format!("unknown keyword `{}`", keyword))),
+            },
+        };
+        // A new message may follow without a blank line.
+        if matches!(next, Field::Context | Field::Id) && !message.strs.is_empty() {
+            finish(&mut message, template, lang, &mut messages, &mut catalog);
+            message.obsolete = obsolete;
+        }
+        match next {
+            Field::Context => message.context = Some(text),
+            Field::Id => message.id = Some(text),
+            Field::IdPlural => message.id_plural = Some(text),
+            Field::Str(index) => {
+                message.strs.insert(index, text);
+            }
+        }
+        field = Some(next);
+    }
+    finish(&mut message, template, lang, &mut messages, &mut catalog);
+    catalog.messages = Value::Object(messages);
+    Ok(catalog)
+}
+
+// Adds the message read so far, and starts the next one.
+fn finish(
+    message: &mut Message,
+    template: bool,
+    lang: &str,
+    messages: &mut Map<String, Value>,
+    catalog: &mut Catalog,
+) {
+    let message = std::mem::take(message);
+    let Some(id) = message.id.filter(|id| !id.is_empty()) else {
+        return;
+    };
+    if message.obsolete {
+        return;
+    }
+    let key = match &message.context {
+        Some(context) => format!("{}|{}", context, id),
+        None => id.clone(),
+    };
+    let mut added = Vec::new();
+    match (message.id_plural, template) {
+        (None, true) => added.push((key, id)),
+        (None, false) => {
+            if let Some(text) = message.strs.get(&0).filter(|text| !text.is_empty()) {
+                added.push((key, text.clone()));
+            }
+        }
+        (Some(plural), true) => {
+            added.push((format!("{}_one", key), id));
+            added.push((format!("{}_other", key), plural));
+        }
+        (Some(_), false) => {
+            let forms: Vec<&String> = message.strs.values().collect();
+            for (category, form) in plural_categories(lang, forms.len()) {
+                if !forms[form].is_empty() {
+                    added.push((format!("{}_{}", key, category), forms[form].clone()));
+                }
+            }
+        }
+    }
+    for (key, text) in added {
+        if message.fuzzy {
+            catalog.fuzzy.push(key.clone());
+        }
+        messages.insert(key, Value::String(text));
+    }
+}
+
+// The categories `count` plural forms stand for, with the form of each:
+// the required categories first, the last form repeating for those left,
+// so that three forms in Russian are `one`, `few` and `many`, `many` also
+// standing for `other`. A language the table doesn't know is read like
+// English.
+fn plural_categories(lang: &str, count: usize) -> Vec<(&'static str, usize)> {
+    let categories: Vec<(&'static str, bool)> = match plural_rules::categories(lang) {
+        Some((_, categories)) => categories
+            .iter()
+            .map(|category| (category.name, category.optional))
+            .collect(),
+        None => vec![("one", false), ("other", false)],
+    };
+    if count == 0 {
+        return Vec::new();
+    }
+    let required: Vec<&'static str> = categories
+        .iter()
+        .filter(|(_, optional)| !optional)
+        .map(|(name, _)| *name)
+        .collect();
+    if count > required.len() {
+        return categories
+            .into_iter()
+            .take(count)
+            .enumerate()
+            .map(|(index, (name, _))| (name, index))
+            .collect();
+    }
+    required
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| (name, index.min(count - 1)))
+        .collect()
+}
+
+// A C string literal, as gettext writes them.
+fn string(text: &str) -> Result<String, String> {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .filter(|_| text.len() >= 2)
+        .ok_or("expected a quoted string")?;
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('r') => value.push('\r'),
+                Some(other) => value.push(other),
+                None => return Err("unterminated escape".into()),
+            },
+            '"' => return Err("unescaped quote in a string".into()),
+            c => value.push(c),
+        }
+    }
+    Ok(value)
+}
@@ -0,0 +1,168 @@
+use crate::checks::variable_spans;
+use crate::model::Project;
+use colored::*;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::ops::Range;
+
+// A value with the sample variables substituted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub text: String,
+    // Where the placeholders without a sample value sit in `text`.
+    pub unreplaced: Vec<Range<usize>>,
+}
+
+impl Rendered {
+    pub fn unreplaced_names(&self) -> BTreeSet<&str> {
+        self.unreplaced
+            .iter()
+            .map(|range| &self.text[range.start + 1..range.end - 1])
+            .collect()
+    }
+}
+
+// Replaces every `{name}` placeholder with its sample value; placeholders
+// without one are kept as written.
+pub fn render(value: &str, vars: &BTreeMap<String, String>) -> Rendered {
+    let mut text = String::new();
+    let mut unreplaced = Vec::new();
+    let mut pos = 0;
+    for (range, name) in variable_spans(value) {
+        text.push_str(&value[pos..range.start]);
+        match vars.get(name) {
+            Some(sample) => text.push_str(sample),
+            None => {
+                let start = text.len();
+                text.push_str(&value[range.clone()]);
+                unreplaced.push(start..text.len());
+            }
+        }
+        pos = range.end;
+    }
+    text.push_str(&value[pos..]);
+    Rendered { text, unreplaced }
+}
+
+// The rendered value of one key in each requested language, `None` where
+// the language has no value for it.
+pub struct Preview {
+    pub key: String,
+    pub vars: BTreeMap<String, String>,
+    pub languages: Vec<(String, Option<Rendered>)>,
+}
+
+impl Preview {
+    pub fn new(
+        project: &Project,
+        key: &str,
+        langs: &[String],
+        vars: BTreeMap<String, String>,
+    ) -> Preview {
+        let languages = langs
+            .iter()
+            .map(|lang| {
+                let value = project.get(lang).and_then(|data| data.value(key));
+                (lang.clone(), value.map(|value| render(value, &vars)))
+            })
+            .collect();
+        Preview {
+            key: key.to_string(),
+            vars,
+            languages,
+        }
+    }
+
+    // Sample variables the key uses in no language, usually a typo.
+    pub fn unknown_variables<'a>(&'a self, project: &'a Project) -> Vec<&'a str> {
+        let used: BTreeSet<&str> = project
+            .languages()
+            .filter_map(|lang| project.get(lang)?.value(&self.key))
+            .flat_map(|value| variable_spans(value).into_iter().map(|(_, name)| name))
+            .collect();
+        self.vars
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !used.contains(name))
+            .collect()
+    }
+
+    // Unknown variables, unreplaced placeholders and missing values, one
+    // line each.
+    pub fn problems(&self, project: &Project) -> Vec<String> {
+        let mut problems: Vec<String> = self
+            .unknown_variables(project)
+            .into_iter()
+            .map(|name| format!("`{}` is not a variable of `{}`", name, self.key))
+            .collect();
+        for (lang, rendered) in &self.languages {
+            match rendered {
+                None => problems.push(format!("`{}` has no value in {}", self.key, lang)),
+                Some(rendered) => problems.extend(
+                    rendered
+                        .unreplaced_names()
+                        .into_iter()
+                        .map(|name| format!("`{{{}}}` left unreplaced in {}", name, lang)),
+                ),
+            }
+        }
+        problems
+    }
+
+    pub fn write_text(&self, out: &mut dyn Write, color: bool) -> io::Result<()> {
+        let width = self
+            .languages
+            .iter()
+            .map(|(lang, _)| lang.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (lang, rendered) in &self.languages {
+            let text = match rendered {
+                None if color => "(no value)".yellow().to_string(),
+                None => "(no value)".to_string(),
+                Some(rendered) => paint(rendered, color),
+            };
+            writeln!(out, "{:<width$} │ {}", lang, text, width = width)?;
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self, project: &Project) -> Value {
+        let languages: Map<String, Value> = self
+            .languages
+            .iter()
+            .map(|(lang, rendered)| {
+                let entry = match rendered {
+                    None => json!({ "rendered": null, "unreplaced": [] }),
+                    Some(rendered) => json!({
+                        "rendered": rendered.text,
+                        "unreplaced": rendered.unreplaced_names(),
+                    }),
+                };
+                (lang.clone(), entry)
+            })
+            .collect();
+        json!({
+            "key": self.key,
+            "vars": self.vars,
+            "languages": languages,
+            "unknown_variables": self.unknown_variables(project),
+        })
+    }
+}
+
+fn paint(rendered: &Rendered, color: bool) -> String {
+    if !color {
+        return rendered.text.clone();
+    }
+    let mut painted = String::new();
+    let mut pos = 0;
+    for range in &rendered.unreplaced {
+        painted.push_str(&rendered.text[pos..range.start]);
+        painted.push_str(&rendered.text[range.clone()].red().bold().to_string());
+        pos = range.end;
+    }
+    painted.push_str(&rendered.text[pos..]);
+    painted
+}
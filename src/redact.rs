@@ -0,0 +1,156 @@
+use crate::checks::variable_spans;
+use crate::render::html::KeyValues;
+use crate::report::{Excerpt, Report};
+use crate::selector::KeyPattern;
+use crate::sha256;
+use serde_json::{Value, json};
+
+// `--redact-values` and `--redact-keys`: the translated text kept out of the
+// reports, so that they can be shared outside the team. A value is replaced
+// by a stable hash of it with its length and placeholders, e.g.
+// `[redacted 3f2a9c1b, 24 chars, {count}]`, which still tells whether two
+// values are the same and why placeholders mismatch.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    pub values: bool,
+    // The key subtrees redacted when not every value is.
+    pub keys: Vec<KeyPattern>,
+}
+
+impl Redaction {
+    pub fn is_enabled(&self) -> bool {
+        self.values || !self.keys.is_empty()
+    }
+
+    // Whether the values of `key` are redacted: under `--redact-keys`, a
+    // pattern matches the key or one of its parents.
+    pub fn covers(&self, key: Option<&str>) -> bool {
+        if self.values {
+            return true;
+        }
+        let Some(key) = key else {
+            return false;
+        };
+        self.keys.iter().any(|pattern| pattern.covers(key))
+    }
+
+    // The settings as recorded in the report's config section.
+    pub fn describe(&self) -> Value {
+        json!({
+            "values": self.values,
+            "keys": self.keys.iter().map(KeyPattern::as_str).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn apply(&self, report: &mut Report) {
+        for finding in &mut report.findings {
+            if !self.covers(finding.key.as_deref()) {
+                continue;
+            }
+            for excerpt in &mut finding.values {
+                redact_excerpt(excerpt);
+            }
+            for (_, value) in &mut finding.details {
+                if let Some(redacted) = redact_quoted(value) {
+                    *value = redacted;
+                }
+            }
+            // A patch restores the text it adds, so it can't be kept.
+            finding.suggested_fix = None;
+        }
+    }
+
+    pub fn apply_values(&self, values: &mut KeyValues) {
+        for (key, languages) in values.iter_mut() {
+            if self.covers(Some(key)) {
+                for value in languages.values_mut() {
+                    *value = redact(value, false);
+                }
+            }
+        }
+    }
+}
+
+// The redacted form of `text`; a `truncated` excerpt only hashes its start.
+pub fn redact(text: &str, truncated: bool) -> String {
+    let hash = sha256::hex_digest(text.as_bytes());
+    let mut parts = vec![
+        format!("redacted {}", &hash[..8]),
+        format!(
+            "{}{} chars",
+            text.chars().count(),
+            if truncated { "+" } else { "" }
+        ),
+    ];
+    for (_, name) in variable_spans(text) {
+        let placeholder = format!("{{{}}}", name);
+        if !parts.contains(&placeholder) {
+            parts.push(placeholder);
+        }
+    }
+    format!("[{}]", parts.join(", "))
+}
+
+// Highlighted placeholders stay highlighted in the redacted text.
+fn redact_excerpt(excerpt: &mut Excerpt) {
+    let highlighted: Vec<&str> = excerpt
+        .highlights
+        .iter()
+        .filter_map(|range| excerpt.text.get(range.clone()))
+        .collect();
+    let text = redact(&excerpt.text, excerpt.truncated);
+    let highlights = variable_spans(&text)
+        .into_iter()
+        .filter(|(range, _)| highlighted.contains(&&text[range.clone()]))
+        .map(|(range, _)| range)
+        .collect();
+    excerpt.highlights = highlights;
+    excerpt.text = text;
+    excerpt.truncated = false;
+}
+
+// Details quote values with `{:?}`, sometimes followed by their file as in
+// `"Valeur" (i18n/fr/common.json)`; the quoted part is redacted.
+fn redact_quoted(detail: &str) -> Option<String> {
+    let rest = detail.strip_prefix('"')?;
+    let mut escaped = false;
+    let end = rest.char_indices().find_map(|(index, c)| {
+        match (escaped, c) {
+            (false, '"') => return Some(index),
+            (false, '\\') => escaped = true,
+            _ => escaped = false,
+        }
+        None
+    })?;
+    let value = unescape(&rest[..end]);
+    Some(format!("{}{}", redact(&value, false), &rest[end + 1..]))
+}
+
+// Undoes the escapes `{:?}` adds to a string.
+fn unescape(quoted: &str) -> String {
+    let mut text = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('t') => text.push('\t'),
+            Some('0') => text.push('\0'),
+            Some('u') => {
+                let code: String = chars
+                    .by_ref()
+                    .skip_while(|c| *c == '{')
+                    .take_while(|c| *c != '}')
+                    .collect();
+                text.extend(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32));
+            }
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
@@ -0,0 +1,142 @@
+use crate::http;
+use crate::schema::Schema;
+use crate::sha256;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// A base schema published by another team. Every download is checked
+// against the `<url>.sha256` file published next to it, which catches
+// truncated or corrupt downloads but only proves where the schema came from
+// over `https://`. It is cached with its ETag, so that unchanged schemas are not downloaded again and the last good
+// copy can be used when the server is unreachable.
+pub struct RemoteSchema {
+    pub url: String,
+    pub token_env: Option<String>,
+    pub cache_dir: PathBuf,
+    pub timeout: Duration,
+    pub require_fresh: bool,
+}
+
+enum Download {
+    Fresh(Vec<u8>, Option<String>),
+    NotModified,
+}
+
+impl RemoteSchema {
+    // Returns the schema and, when the cached copy had to be used instead of
+    // a fresh one, a warning explaining why.
+    pub fn fetch(&self) -> Result<(Schema, Option<String>), String> {
+        let name = &sha256::hex_digest(self.url.as_bytes())[..16];
+        let cached = self.cache_dir.join(format!("schema-{}.json", name));
+        let etag_path = self.cache_dir.join(format!("schema-{}.etag", name));
+        let etag = fs::read_to_string(&etag_path)
+            .ok()
+            .filter(|_| cached.exists());
+
+        match self.download(etag.as_deref()) {
+            Ok(Download::Fresh(body, etag)) => {
+                let content = String::from_utf8(body)
+                    .map_err(|_| format!("schema {} is not valid UTF-8", self.url))?;
+                let schema = Schema::parse(&content, &self.url)?;
+                self.store(&cached, &content, &etag_path, etag.as_deref())?;
+                Ok((schema, None))
+            }
+            Ok(Download::NotModified) => Ok((load_cached(&cached, &self.url)?, None)),
+            Err(err) if self.require_fresh => Err(format!(
+                "failed to fetch schema {} (required by `--require-fresh-schema`): {}",
+                self.url, err
+            )),
+            Err(err) if cached.exists() => {
+                let warning = format!(
+                    "failed to fetch schema {}: {}; using the cached copy {}",
+                    self.url,
+                    err,
+                    cached.display()
+                );
+                Ok((load_cached(&cached, &self.url)?, Some(warning)))
+            }
+            Err(err) => Err(format!(
+                "failed to fetch schema {} and no cached copy exists: {}",
+                self.url, err
+            )),
+        }
+    }
+
+    fn download(&self, etag: Option<&str>) -> Result<Download, String> {
+        let mut headers = Vec::new();
+        if let Some(var) = &self.token_env {
+            let token =
+                env::var(var).map_err(|_| format!("environment variable `{}` is not set", var))?;
+            headers.push(("Authorization", format!("Bearer {}", token)));
+        }
+
+        let mut schema_headers = headers.clone();
+        if let Some(etag) = etag {
+            schema_headers.push(("If-None-Match", etag.to_string()));
+        }
+
+        let response = http::get(&self.url, &schema_headers, self.timeout)?;
+        match response.status {
+            304 if etag.is_some() => return Ok(Download::NotModified),
+            200 => {}
+            status => return Err(format!("server answered with status {}", status)),
+        }
+
+        let checksum_url = format!("{}.sha256", self.url);
+        let checksum = http::get(&checksum_url, &headers, self.timeout)?;
+        if checksum.status != 200 {
+            return Err(format!(
+                "failed to fetch checksum {}: server answered with status {}",
+                checksum_url, checksum.status
+            ));
+        }
+        let expected = String::from_utf8_lossy(&checksum.body)
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let actual = sha256::hex_digest(&response.body);
+        if expected != actual {
+            return Err(format!(
+                "checksum mismatch (expected {}, downloaded {})",
+                expected, actual
+            ));
+        }
+
+        let etag = response.header("ETag").map(str::to_string);
+        Ok(Download::Fresh(response.body, etag))
+    }
+
+    fn store(
+        &self,
+        cached: &Path,
+        content: &str,
+        etag_path: &Path,
+        etag: Option<&str>,
+    ) -> Result<(), String> {
+        let write_error = |err: std::io::Error| {
+            format!(
+                "failed to cache schema in {}: {}",
+                self.cache_dir.display(),
+                err
+            )
+        };
+        fs::create_dir_all(&self.cache_dir).map_err(write_error)?;
+        fs::write(cached, content).map_err(write_error)?;
+        match etag {
+            Some(etag) => fs::write(etag_path, etag).map_err(write_error),
+            None => {
+                let _ = fs::remove_file(etag_path);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn load_cached(cached: &Path, url: &str) -> Result<Schema, String> {
+    let content = fs::read_to_string(cached)
+        .map_err(|err| format!("failed to read cached schema {}: {}", cached.display(), err))?;
+    Schema::parse(&content, url)
+}
@@ -0,0 +1,42 @@
+pub mod html;
+pub mod human;
+pub mod json;
+pub mod junit;
+pub mod plain;
+pub mod sarif;
+
+use crate::report::Report;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+
+// Writes a finished report in one output format.
+pub trait Renderer {
+    fn render(&mut self, report: &Report) -> io::Result<()>;
+}
+
+// Writes `path` through a temporary file in the same folder renamed into
+// place, so readers never see a partial file and a failed run leaves the
+// previous one intact.
+pub fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temporary = path.with_file_name(format!(".{}.{}.tmp", name, process::id()));
+
+    let result = File::create(&temporary).and_then(|file| {
+        let mut out = BufWriter::new(file);
+        write(&mut out)?;
+        out.flush()?;
+        out.get_ref().sync_all()
+    });
+    match result.and_then(|_| fs::rename(&temporary, path)) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            let _ = fs::remove_file(&temporary);
+            Err(err)
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::render::Renderer;
+use crate::report::{Check, Report, Severity};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+const SCRIPT: &str = include_str!("html/report.js");
+const STYLE: &str = include_str!("html/report.css");
+
+// The values of a key in every language, shown when a finding is expanded.
+pub type KeyValues = BTreeMap<String, BTreeMap<String, String>>;
+
+// A single self-contained HTML page. Findings are embedded as compact JSON
+// and rendered by the page a chunk at a time, so large reports stay
+// responsive. Keys and their values are embedded once however many findings
+// refer to them.
+pub struct HtmlRenderer<'a, W: Write> {
+    out: W,
+    values: &'a KeyValues,
+}
+
+impl<'a, W: Write> HtmlRenderer<'a, W> {
+    pub fn new(out: W, values: &'a KeyValues) -> HtmlRenderer<'a, W> {
+        HtmlRenderer { out, values }
+    }
+
+    fn data(&self, report: &Report) -> Value {
+        let checks: Map<String, Value> = Check::ALL
+            .into_iter()
+            .map(|check| {
+                let info = check.info();
+                (
+                    info.id.to_string(),
+                    json!({
+                        "emoji": info.emoji,
+                        "title": info.title,
+                        "severity": info.severity.name(),
+                    }),
+                )
+            })
+            .collect();
+
+        let mut keys: Vec<&str> = Vec::new();
+        let mut key_index: HashMap<&str, usize> = HashMap::new();
+        let findings: Vec<Value> = report
+            .findings
+            .iter()
+            .map(|finding| {
+                let key = finding.key.as_deref().map_or(-1, |key| {
+                    *key_index.entry(key).or_insert_with(|| {
+                        keys.push(key);
+                        keys.len() - 1
+                    }) as i64
+                });
+                let details: Vec<Value> = finding
+                    .details
+                    .iter()
+                    .map(|(label, value)| json!([label, value]))
+                    .collect();
+                json!([
+                    finding.check.info().id,
+                    finding.lang,
+                    key,
+                    finding.location(),
+                    details,
+                    report.context(finding),
+                ])
+            })
+            .collect();
+        let values: Vec<Value> = keys
+            .iter()
+            .map(|key| json!(self.values.get(*key).cloned().unwrap_or_default()))
+            .collect();
+
+        json!({
+            "summary": format!(
+                "{} errors, {} warnings, {} notices",
+                report.weight(Severity::Error),
+                report.weight(Severity::Warning),
+                report.weight(Severity::Info)
+            ),
+            "checks": checks,
+            "keys": keys,
+            "values": values,
+            "findings": findings,
+        })
+    }
+}
+
+impl<W: Write> Renderer for HtmlRenderer<'_, W> {
+    fn render(&mut self, report: &Report) -> io::Result<()> {
+        // `<` only appears inside JSON strings, where the escape keeps the
+        // embedded data from closing its script element.
+        let data = self.data(report).to_string().replace('<', "\\u003c");
+        write!(
+            self.out,
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Translation check report</title>
+<style>
+{STYLE}</style>
+</head>
+<body>
+<header>
+<h1>Translation check report</h1>
+<p id="summary"></p>
+<input id="filter" type="search" placeholder="Filter by check, language, key, detail or file">
+<button id="prev" type="button">‹</button>
+<span id="page"></span>
+<button id="next" type="button">›</button>
+<p class="help">j / k: next / previous finding · enter: show the values · n / p: next / previous page · /: filter</p>
+</header>
+<table>
+<thead><tr><th>Check</th><th>Language</th><th>Key</th><th>Details</th><th>File</th></tr></thead>
+<tbody id="rows"></tbody>
+</table>
+<script type="application/json" id="report">{data}</script>
+<script>
+{SCRIPT}</script>
+</body>
+</html>
+"#
+        )?;
+        self.out.flush()
+    }
+}
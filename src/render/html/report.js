@@ -0,0 +1,141 @@
+const PAGE_SIZE = 200;
+const data = JSON.parse(document.getElementById("report").textContent);
+const rows = document.getElementById("rows");
+const filter = document.getElementById("filter");
+const expanded = new Set();
+let visible = data.findings.map((_, index) => index);
+let searchText = null;
+let selected = 0;
+
+function cell(row, text, className) {
+  const td = row.insertCell();
+  td.textContent = text;
+  if (className) td.className = className;
+  return td;
+}
+
+// The values of the finding's key in every language, built only once the
+// row is expanded.
+function comparison(finding) {
+  const row = document.createElement("tr");
+  row.className = "values";
+  const td = row.insertCell();
+  td.colSpan = 5;
+  const values = finding[2] < 0 ? {} : data.values[finding[2]];
+  const langs = Object.keys(values).sort();
+  if (!langs.length) {
+    td.textContent = "No values for this finding.";
+    return row;
+  }
+  const table = document.createElement("table");
+  for (const lang of langs) {
+    const line = table.insertRow();
+    cell(line, lang.toUpperCase(), "lang");
+    cell(line, values[lang]);
+  }
+  td.appendChild(table);
+  return row;
+}
+
+function render() {
+  const pages = Math.max(1, Math.ceil(visible.length / PAGE_SIZE));
+  const page = Math.min(Math.floor(selected / PAGE_SIZE), pages - 1);
+  const start = page * PAGE_SIZE;
+  rows.replaceChildren();
+  visible.slice(start, start + PAGE_SIZE).forEach((index, offset) => {
+    const finding = data.findings[index];
+    const check = data.checks[finding[0]];
+    const row = rows.insertRow();
+    row.className = check.severity + (start + offset === selected ? " selected" : "");
+    row.onclick = () => {
+      selected = start + offset;
+      toggle();
+    };
+    cell(row, check.emoji + " " + check.title);
+    cell(row, (finding[1] || "").toUpperCase());
+    cell(row, finding[2] < 0 ? "" : data.keys[finding[2]], "key");
+    const details = cell(row, finding[4].map(([label, value]) => label + ": " + value).join(" | "));
+    for (const url of finding[5]) {
+      const link = document.createElement("a");
+      link.className = "context";
+      link.href = url;
+      link.target = "_blank";
+      link.rel = "noopener";
+      link.textContent = "context";
+      link.onclick = (event) => event.stopPropagation();
+      details.append(" ", link);
+    }
+    cell(row, finding[3] || "", "file");
+    if (expanded.has(index)) rows.appendChild(comparison(finding));
+  });
+  document.getElementById("page").textContent =
+    "Page " + (page + 1) + " of " + pages + " · " + visible.length + " findings";
+  const current = rows.querySelector(".selected");
+  if (current) current.scrollIntoView({ block: "nearest" });
+}
+
+function move(delta) {
+  selected = Math.max(0, Math.min(visible.length - 1, selected + delta));
+  render();
+}
+
+function toggle() {
+  const index = visible[selected];
+  if (index === undefined) return;
+  if (!expanded.delete(index)) expanded.add(index);
+  render();
+}
+
+function applyFilter() {
+  const query = filter.value.trim().toLowerCase();
+  if (searchText === null) {
+    searchText = data.findings.map((finding) =>
+      [
+        data.checks[finding[0]].title,
+        finding[1] || "",
+        finding[2] < 0 ? "" : data.keys[finding[2]],
+        finding[3] || "",
+        ...finding[4].flat(),
+      ]
+        .join("\n")
+        .toLowerCase()
+    );
+  }
+  visible = data.findings
+    .map((_, index) => index)
+    .filter((index) => !query || searchText[index].includes(query));
+  selected = 0;
+  render();
+}
+
+let filterTimer;
+filter.addEventListener("input", () => {
+  clearTimeout(filterTimer);
+  filterTimer = setTimeout(applyFilter, 150);
+});
+document.getElementById("prev").onclick = () => move(-PAGE_SIZE);
+document.getElementById("next").onclick = () => move(PAGE_SIZE);
+
+document.addEventListener("keydown", (event) => {
+  if (event.target === filter) {
+    if (event.key === "Escape" || event.key === "Enter") filter.blur();
+    return;
+  }
+  if (event.ctrlKey || event.metaKey || event.altKey) return;
+  const actions = {
+    j: () => move(1),
+    k: () => move(-1),
+    n: () => move(PAGE_SIZE),
+    p: () => move(-PAGE_SIZE),
+    Enter: toggle,
+    "/": () => filter.focus(),
+  };
+  const action = actions[event.key];
+  if (action) {
+    event.preventDefault();
+    action();
+  }
+});
+
+document.getElementById("summary").textContent = data.summary;
+render();
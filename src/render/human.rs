@@ -0,0 +1,481 @@
+use crate::codeowners::UNOWNED;
+use crate::render::Renderer;
+use crate::report::{Check, Finding, Layout, Report, Severity};
+use colored::*;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Language,
+    File,
+    Owner,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HumanOptions {
+    pub quiet: bool,
+    pub max_findings: Option<usize>,
+    pub group_by: GroupBy,
+    // Leaves out the findings, for the coverage table and summary alone.
+    pub coverage_only: bool,
+}
+
+// How one rendering looks. Each sink gets its own, so that the console can
+// be colored while a file written in the same run has neither color nor
+// emoji, whatever the global `--color` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub color: bool,
+    pub emoji: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        color: false,
+        emoji: false,
+    };
+}
+
+// The colorized console report.
+pub struct HumanRenderer<W: Write> {
+    out: W,
+    style: Style,
+    options: HumanOptions,
+}
+
+impl<W: Write> HumanRenderer<W> {
+    pub fn new(out: W, style: Style, options: HumanOptions) -> HumanRenderer<W> {
+        HumanRenderer {
+            out,
+            style,
+            options,
+        }
+    }
+
+    // The escape codes are written here rather than by `colored`, whose
+    // global override would otherwise decide for every sink at once.
+    fn paint(&self, text: &str, color: Color) -> String {
+        if self.style.color {
+            format!("\x1b[{}m{}\x1b[0m", color.to_fg_str(), text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn heading(&self, text: &str, color: Color) -> String {
+        if self.style.color {
+            format!("\x1b[1;{}m{}\x1b[0m", color.to_fg_str(), text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    // `text` behind its emoji, when the style shows them.
+    fn icon(&self, emoji: &str, text: &str) -> String {
+        if self.style.emoji {
+            format!("{} {}", emoji, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    // Paints the highlighted ranges of one line of a quoted value. `offset`
+    // is where the line starts in the whole value.
+    fn highlight(&self, line: &str, offset: usize, highlights: &[Range<usize>]) -> String {
+        if !self.style.color {
+            return line.to_string();
+        }
+        let mut painted = String::new();
+        let mut pos = 0;
+        for range in highlights {
+            if range.start < offset + pos || range.end > offset + line.len() {
+                continue;
+            }
+            let (start, end) = (range.start - offset, range.end - offset);
+            painted.push_str(&line[pos..start]);
+            painted.push_str(&self.heading(&line[start..end], Color::Red));
+            pos = end;
+        }
+        painted.push_str(&line[pos..]);
+        painted
+    }
+
+    // Quoted values one above the other behind a gutter, so they can be
+    // compared at a glance. Continuation lines of multi-line values keep
+    // their own indentation.
+    fn values(&mut self, finding: &Finding) -> io::Result<()> {
+        if finding.values.is_empty() {
+            return Ok(());
+        }
+        let width = finding
+            .values
+            .iter()
+            .map(|excerpt| excerpt.label.chars().count())
+            .max()
+            .unwrap_or(0);
+        writeln!(self.out, "   - Values:")?;
+        for excerpt in &finding.values {
+            let lines: Vec<&str> = excerpt.text.split('\n').collect();
+            let mut offset = 0;
+            for (i, line) in lines.iter().enumerate() {
+                let label = if i == 0 { excerpt.label.as_str() } else { "" };
+                let trimmed = line.strip_suffix('\r').unwrap_or(line);
+                let mut text = self.highlight(trimmed, offset, &excerpt.highlights);
+                if excerpt.truncated && i + 1 == lines.len() {
+                    text.push('…');
+                }
+                writeln!(self.out, "     {:<width$} │ {}", label, text, width = width)?;
+                offset += line.len() + 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn inline(&mut self, finding: &Finding) -> io::Result<()> {
+        let color = finding.check.info().color;
+        let mut parts = Vec::new();
+        if let Some(key) = &finding.key {
+            parts.push(format!("Key: {}", self.paint(key, color)));
+        }
+        if let Some(message) = &finding.message {
+            parts.push(message.clone());
+        }
+        for (label, value) in &finding.details {
+            parts.push(format!("{}: {}", label, self.paint(value, Color::Cyan)));
+        }
+        if let Some(location) = finding.location() {
+            parts.push(format!("File: {}", self.paint(&location, Color::Blue)));
+        }
+        writeln!(self.out, "   - {}", parts.join(" | "))
+    }
+
+    // The context links of the key, dim under its finding.
+    fn context(&mut self, links: &[String]) -> io::Result<()> {
+        for link in links {
+            let line = format!("context: {}", link);
+            if self.style.color {
+                writeln!(self.out, "     \x1b[2m{}\x1b[0m", line)?;
+            } else {
+                writeln!(self.out, "     {}", line)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn block(&mut self, finding: &Finding, title: &str) -> io::Result<()> {
+        let info = finding.check.info();
+        let header = match &finding.message {
+            Some(message) => self.icon(info.emoji, message),
+            // The renames follow as a list, under a header ending with a
+            // colon as the headers of the inline sections do.
+            None if finding.check == Check::TranslatedPlaceholder => format!("{}:", title),
+            None => title.to_string(),
+        };
+        writeln!(self.out, "{}", self.heading(&header, info.color))?;
+        if let Some(key) = &finding.key {
+            writeln!(self.out, "   - Key: {}", self.paint(key, info.color))?;
+        }
+        for (label, value) in &finding.details {
+            writeln!(
+                self.out,
+                "   - {}: {}",
+                label,
+                self.paint(value, Color::Cyan)
+            )?;
+        }
+        self.values(finding)
+    }
+
+    fn findings(&mut self, report: &Report) -> io::Result<()> {
+        let mut listed: Vec<&Finding> = report
+            .findings
+            .iter()
+            .filter(|finding| finding.check.info().layout != Layout::Count)
+            .collect();
+        match self.options.group_by {
+            GroupBy::Language => {}
+            GroupBy::File => {
+                listed.sort_by(|a, b| (&a.file, &a.lang, a.check).cmp(&(&b.file, &b.lang, b.check)))
+            }
+            GroupBy::Owner => listed.sort_by(|a, b| {
+                (owners(a), &a.lang, a.check, &a.file).cmp(&(owners(b), &b.lang, b.check, &b.file))
+            }),
+        }
+
+        let limit = self.options.max_findings.unwrap_or(usize::MAX);
+        let mut section: Option<Option<String>> = None;
+        let mut group: Option<(Option<&String>, Check)> = None;
+
+        for finding in listed.iter().take(limit) {
+            let finding_section = match self.options.group_by {
+                GroupBy::Language => finding.lang.clone(),
+                GroupBy::File => finding.file.clone(),
+                GroupBy::Owner => Some(owners(finding)),
+            };
+            if section.as_ref() != Some(&finding_section) {
+                match (self.options.group_by, &finding_section) {
+                    (GroupBy::Language, Some(lang)) => {
+                        let header = self.icon("🔍", &format!("Checking {}", lang.to_uppercase()));
+                        writeln!(self.out, "{}", self.heading(&header, Color::White))?;
+                    }
+                    (GroupBy::Language, None) => {}
+                    (GroupBy::File, file) => {
+                        let header = self.icon("📄", file.as_deref().unwrap_or("(no file)"));
+                        writeln!(self.out, "{}", self.heading(&header, Color::Blue))?;
+                    }
+                    (GroupBy::Owner, owners) => {
+                        let header = self.icon("👥", owners.as_deref().unwrap_or_default());
+                        writeln!(self.out, "{}", self.heading(&header, Color::Magenta))?;
+                    }
+                }
+                section = Some(finding_section);
+                group = None;
+            }
+
+            let info = finding.check.info();
+            let title = match (self.options.group_by, &finding.lang) {
+                (GroupBy::File | GroupBy::Owner, Some(lang)) => self.icon(
+                    info.emoji,
+                    &format!("{} ({})", info.title, lang.to_uppercase()),
+                ),
+                _ => self.icon(info.emoji, info.title),
+            };
+
+            match info.layout {
+                Layout::Block => self.block(finding, &title)?,
+                _ => {
+                    if group != Some((finding.lang.as_ref(), finding.check)) {
+                        group = Some((finding.lang.as_ref(), finding.check));
+                        writeln!(
+                            self.out,
+                            "{}",
+                            self.heading(&format!("{}:", title), info.color)
+                        )?;
+                    }
+                    self.inline(finding)?;
+                }
+            }
+            self.context(report.context(finding))?;
+        }
+
+        if listed.len() > limit {
+            writeln!(
+                self.out,
+                "… {} more findings not shown (raise --max-findings to see them)",
+                listed.len() - limit
+            )?;
+        }
+        Ok(())
+    }
+
+    fn counts(&mut self, report: &Report) -> io::Result<()> {
+        for check in [Check::UnverifiableInterpolation, Check::UnusedKey] {
+            let count = report.count(check);
+            if count == 0 && !(check == Check::UnusedKey && report.usage_scanned) {
+                continue;
+            }
+            let info = check.info();
+            let label = self.icon(info.emoji, &format!("{}:", info.title));
+            // With several source folders, which ones were searched.
+            let searched = match report.source_roots.as_slice() {
+                roots if check == Check::UnusedKey && roots.len() > 1 => {
+                    format!(" (searched {})", roots.join(", "))
+                }
+                _ => String::new(),
+            };
+            writeln!(
+                self.out,
+                "{} {}{}",
+                self.heading(&label, info.color),
+                count,
+                searched
+            )?;
+        }
+        if let Some(exempted) = report.base_only {
+            let label = self.heading(
+                &self.icon("🧪", "Base-only keys exempted from translation:"),
+                Color::White,
+            );
+            writeln!(self.out, "{} {}", label, exempted)?;
+        }
+        if let Some(waived) = report.waived {
+            let label = self.heading(&self.icon("🙈", "Waived findings:"), Color::White);
+            writeln!(self.out, "{} {}", label, waived)?;
+        }
+        if let Some(baselined) = report.baselined {
+            let label = self.heading(&self.icon("📼", "Baseline findings:"), Color::White);
+            writeln!(self.out, "{} {}", label, baselined)?;
+        }
+        Ok(())
+    }
+
+    // What each language has to fix, such as `DE: 12 missing_key,
+    // 3 extra_key`. Rolled-up rows count for each of their languages.
+    fn languages(&mut self, report: &Report) -> io::Result<()> {
+        let mut totals: BTreeMap<&str, BTreeMap<Check, usize>> = BTreeMap::new();
+        let findings = report.findings.iter().flat_map(|finding| {
+            if finding.members.is_empty() {
+                std::slice::from_ref(finding)
+            } else {
+                &finding.members[..]
+            }
+        });
+        for finding in findings {
+            if let Some(lang) = &finding.lang {
+                *totals
+                    .entry(lang)
+                    .or_default()
+                    .entry(finding.check)
+                    .or_default() += finding.weight;
+            }
+        }
+        if totals.is_empty() {
+            return Ok(());
+        }
+
+        let label = self.icon("📊", "Findings per language:");
+        writeln!(self.out, "{}", self.heading(&label, Color::White))?;
+        for (lang, checks) in totals {
+            let counts: Vec<String> = checks
+                .iter()
+                .map(|(check, count)| format!("{} {}", count, check.info().id))
+                .collect();
+            writeln!(
+                self.out,
+                "   - {}: {}",
+                lang.to_uppercase(),
+                counts.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+
+    // How much of the base each language translates, one row per language.
+    fn coverage(&mut self, report: &Report) -> io::Result<()> {
+        if report.coverage.is_empty() {
+            return Ok(());
+        }
+        let label = self.icon("📈", "Coverage per language:");
+        writeln!(self.out, "{}", self.heading(&label, Color::White))?;
+        let width = report
+            .coverage
+            .iter()
+            .map(|coverage| coverage.lang.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Language".len());
+        writeln!(
+            self.out,
+            "   {:<width$}  {:>6}  {:>10}  {:>7}  {:>5}  {:>10}  {:>8}",
+            "Language", "Keys", "Translated", "Missing", "Extra", "Mismatches", "Coverage"
+        )?;
+        for coverage in &report.coverage {
+            writeln!(
+                self.out,
+                "   {:<width$}  {:>6}  {:>10}  {:>7}  {:>5}  {:>10}  {:>8}",
+                coverage.lang.to_uppercase(),
+                coverage.total,
+                coverage.translated,
+                coverage.missing,
+                coverage.extra,
+                coverage.mismatches,
+                format!("{:.1}%", coverage.percent())
+            )?;
+        }
+        for coverage in report
+            .coverage
+            .iter()
+            .filter(|coverage| !coverage.folders.is_empty())
+        {
+            writeln!(
+                self.out,
+                "   {} merges the folders {}",
+                coverage.lang.to_uppercase(),
+                coverage.folders.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+
+    fn summary(&mut self, report: &Report) -> io::Result<()> {
+        let errors = report.weight(Severity::Error);
+        let warnings = report.weight(Severity::Warning);
+
+        let line = if errors > 0 {
+            self.heading(
+                &self.icon(
+                    "❌",
+                    &format!(
+                        "Translation issues found: {} errors, {} warnings.",
+                        errors, warnings
+                    ),
+                ),
+                Color::Red,
+            )
+        } else if warnings > 0 {
+            self.heading(
+                &self.icon(
+                    "✅",
+                    &format!("No translation errors found ({} warnings).", warnings),
+                ),
+                Color::Yellow,
+            )
+        } else {
+            self.heading(
+                &self.icon("✅", "No translation issues found."),
+                Color::Green,
+            )
+        };
+        writeln!(self.out, "{}", line)?;
+
+        // Their keys are neither missing nor checked, which the counts above
+        // don't tell.
+        for finding in report
+            .findings
+            .iter()
+            .filter(|finding| finding.check == Check::LanguageExcluded)
+        {
+            let note = format!(
+                "{} was left out of the comparison: {}.",
+                finding.lang.as_deref().unwrap_or_default().to_uppercase(),
+                finding.message.as_deref().unwrap_or_default()
+            );
+            writeln!(
+                self.out,
+                "{}",
+                self.heading(&self.icon("⛔", &note), Color::Yellow)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The owners of a finding's file as listed in a section header.
+fn owners(finding: &Finding) -> String {
+    match finding.owners.as_deref() {
+        Some([]) | None => UNOWNED.to_string(),
+        Some(owners) => owners.join(" "),
+    }
+}
+
+impl<W: Write> Renderer for HumanRenderer<W> {
+    fn render(&mut self, report: &Report) -> io::Result<()> {
+        if !self.options.quiet {
+            let title = self.heading(
+                &self.icon("🌍", "Translation Consistency Check Complete"),
+                Color::White,
+            );
+            writeln!(self.out, "{}", title)?;
+            if !self.options.coverage_only {
+                self.findings(report)?;
+                self.counts(report)?;
+                self.languages(report)?;
+            }
+            self.coverage(report)?;
+        }
+        self.summary(report)?;
+        self.out.flush()
+    }
+}
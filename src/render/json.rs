@@ -0,0 +1,548 @@
+use crate::partition::{Fragment, Partition};
+use crate::render::Renderer;
+use crate::report::{Check, Coverage, Excerpt, Finding, Report, Severity};
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+// The version of the report format, as described by `schema()`. Adding a
+// field or a check bumps the minor version; removing, renaming or retyping
+// one bumps the major version, and reports of another major version are
+// not read.
+pub const SCHEMA_VERSION: &str = "1.16.0";
+
+// The arrays listing the findings of the most common checks by key,
+// language and file, beside `findings`, for scripts reading only those.
+// Placeholder mismatches include renamed placeholders and the languages a
+// change of the base placeholders left behind.
+const LISTED_CHECKS: [(&str, &[Check]); 4] = [
+    ("missing_keys", &[Check::MissingKey]),
+    ("extra_keys", &[Check::ExtraKey]),
+    (
+        "variable_mismatches",
+        &[
+            Check::VariableMismatch,
+            Check::TranslatedPlaceholder,
+            Check::BasePlaceholdersChanged,
+        ],
+    ),
+    ("unused_keys", &[Check::UnusedKey]),
+];
+
+// The whole report as one JSON document, for dashboards and other tools.
+// Checks are named by their stable ids.
+pub struct JsonRenderer<W: Write> {
+    out: W,
+}
+
+impl<W: Write> JsonRenderer<W> {
+    pub fn new(out: W) -> JsonRenderer<W> {
+        JsonRenderer { out }
+    }
+}
+
+impl<W: Write> Renderer for JsonRenderer<W> {
+    fn render(&mut self, report: &Report) -> io::Result<()> {
+        let findings: Vec<Value> = report
+            .findings
+            .iter()
+            .map(|finding| finding_json(report, finding))
+            .collect();
+
+        let mut document = json!({
+            "schema_version": SCHEMA_VERSION,
+            "summary": {
+                "errors": report.weight(Severity::Error),
+                "warnings": report.weight(Severity::Warning),
+                "notices": report.weight(Severity::Info),
+            },
+            "findings": findings,
+        });
+        for (name, checks) in LISTED_CHECKS {
+            document[name] = listed(report, checks);
+        }
+        if let Some(config) = &report.config {
+            document["config"] = config.clone();
+        }
+        if let Some(waived) = report.waived {
+            document["summary"]["waived"] = json!(waived);
+        }
+        if let Some(baselined) = report.baselined {
+            document["summary"]["baselined"] = json!(baselined);
+        }
+        if let Some(exempted) = report.base_only {
+            document["summary"]["base_only"] = json!(exempted);
+        }
+        if report.usage_scanned {
+            document["summary"]["source_roots"] = json!(report.source_roots);
+        }
+        if !report.coverage.is_empty() {
+            document["coverage"] = report
+                .coverage
+                .iter()
+                .map(|coverage| {
+                    let mut row = json!({
+                        "lang": coverage.lang,
+                        "total_keys": coverage.total,
+                        "translated": coverage.translated,
+                        "missing": coverage.missing,
+                        "extra": coverage.extra,
+                        "variable_mismatches": coverage.mismatches,
+                        "coverage": coverage.percent(),
+                    });
+                    if !coverage.folders.is_empty() {
+                        row["folders"] = json!(coverage.folders);
+                    }
+                    row
+                })
+                .collect();
+        }
+        if let Some(fragment) = &report.fragment {
+            document["partition"] = json!({
+                "index": fragment.partition.index,
+                "count": fragment.partition.count,
+                "by": "prefix",
+                "keys": fragment.keys,
+                "total_keys": fragment.total_keys,
+            });
+        }
+        serde_json::to_writer_pretty(&mut self.out, &document)?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
+
+// The key, language and file of each finding of `checks`, those of a
+// roll-up row one by one, in the order of the report. A change of the base
+// placeholders is listed once for each language it leaves behind, without
+// a file.
+fn listed(report: &Report, checks: &[Check]) -> Value {
+    let mut listed = Vec::new();
+    for finding in &report.findings {
+        if !checks.contains(&finding.check) {
+            continue;
+        }
+        if finding.check == Check::BasePlaceholdersChanged {
+            let langs = finding
+                .details
+                .iter()
+                .find(|(label, _)| label == "Languages")
+                .map(|(_, langs)| langs.split(", ").collect())
+                .unwrap_or_else(Vec::new);
+            for lang in langs {
+                listed.push(json!({ "key": finding.key, "lang": lang, "file": null }));
+            }
+            continue;
+        }
+        let findings = if finding.members.is_empty() {
+            std::slice::from_ref(finding)
+        } else {
+            &finding.members[..]
+        };
+        for finding in findings {
+            listed.push(json!({ "key": finding.key, "lang": finding.lang, "file": finding.file }));
+        }
+    }
+    Value::Array(listed)
+}
+
+// A roll-up row lists the findings it stands for in `grouped_members`.
+fn finding_json(report: &Report, finding: &Finding) -> Value {
+    let details: Vec<Value> = finding
+        .details
+        .iter()
+        .map(|(label, value)| json!({ "label": label, "value": value }))
+        .collect();
+    let values: Vec<Value> = finding
+        .values
+        .iter()
+        .map(|excerpt| {
+            let highlights: Vec<Value> = excerpt
+                .highlights
+                .iter()
+                .map(|range| json!({ "start": range.start, "end": range.end }))
+                .collect();
+            json!({
+                "label": excerpt.label,
+                "text": excerpt.text,
+                "truncated": excerpt.truncated,
+                "highlights": highlights,
+            })
+        })
+        .collect();
+    let mut entry = json!({
+        "check": finding.check.info().id,
+        "severity": finding.severity.name(),
+        "weight": finding.weight,
+        "lang": finding.lang,
+        "key": finding.key,
+        "file": finding.file,
+        "line": finding.line,
+        "column": finding.column,
+        "message": finding.message,
+        "details": details,
+        "values": values,
+    });
+    if let Some(owners) = &finding.owners {
+        entry["owners"] = json!(owners);
+    }
+    let context = report.context(finding);
+    if !context.is_empty() {
+        entry["context"] = json!(context);
+    }
+    if let Some(fix) = &finding.suggested_fix {
+        entry["suggested_fix"] = fix.clone();
+    }
+    if !finding.members.is_empty() {
+        entry["grouped_members"] = finding
+            .members
+            .iter()
+            .map(|member| finding_json(report, member))
+            .collect();
+    }
+    entry
+}
+
+// Reads back a report written by `JsonRenderer`, for `merge-reports`. The
+// summary is not trusted; it is recomputed from the findings.
+pub fn parse(source: &str) -> Result<Report, String> {
+    let document: Value = serde_json::from_str(source).map_err(|err| err.to_string())?;
+    // Reports written before the format was versioned follow version 1.
+    if let Some(version) = document.get("schema_version") {
+        let version = version
+            .as_str()
+            .ok_or("`schema_version` must be a string")?;
+        if major(version) != major(SCHEMA_VERSION) {
+            return Err(format!(
+                "report schema version {} is not supported (expected {}.x); regenerate the report with this version",
+                version,
+                major(SCHEMA_VERSION)
+            ));
+        }
+    }
+    let findings = document["findings"]
+        .as_array()
+        .ok_or("missing `findings` array")?;
+
+    let mut report = Report::default();
+    for (i, finding) in findings.iter().enumerate() {
+        let context = |err| format!("finding {}: {}", i + 1, err);
+        // Roll-up rows are read back as the findings they stand for, which
+        // are rolled up again once merged.
+        let members = match finding["grouped_members"].as_array() {
+            Some(members) => members.iter().collect(),
+            None => vec![finding],
+        };
+        for member in members {
+            let parsed = parse_finding(member).map_err(context)?;
+            if let (Some(key), Some(links)) = (&parsed.key, member["context"].as_array()) {
+                let links = links
+                    .iter()
+                    .map(|link| link.as_str().map(str::to_string))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| context("`context` must be an array of strings".into()))?;
+                report.context.insert(key.clone(), links);
+            }
+            report.findings.push(parsed);
+        }
+    }
+
+    report.config = document.get("config").cloned();
+    for (i, row) in document["coverage"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        report
+            .coverage
+            .push(parse_coverage(row).map_err(|err| format!("coverage {}: {}", i + 1, err))?);
+    }
+
+    let partition = &document["partition"];
+    if !partition.is_null() {
+        let number = |name: &str| {
+            partition[name]
+                .as_u64()
+                .map(|n| n as usize)
+                .ok_or_else(|| format!("`partition.{}` must be a number", name))
+        };
+        if partition["by"] != "prefix" {
+            return Err(format!(
+                "unsupported `partition.by` {} (expected \"prefix\")",
+                partition["by"]
+            ));
+        }
+        report.fragment = Some(Fragment {
+            partition: Partition {
+                index: number("index")?,
+                count: number("count")?,
+            },
+            keys: number("keys")?,
+            total_keys: number("total_keys")?,
+        });
+    }
+    Ok(report)
+}
+
+fn parse_coverage(value: &Value) -> Result<Coverage, String> {
+    let number = |name: &str| {
+        value[name]
+            .as_u64()
+            .map(|n| n as usize)
+            .ok_or_else(|| format!("`{}` must be a number", name))
+    };
+    Ok(Coverage {
+        lang: value["lang"].as_str().ok_or("missing `lang`")?.to_string(),
+        total: number("total_keys")?,
+        translated: number("translated")?,
+        missing: number("missing")?,
+        extra: number("extra")?,
+        mismatches: number("variable_mismatches")?,
+        folders: match &value["folders"] {
+            Value::Null => Vec::new(),
+            Value::Array(folders) => folders
+                .iter()
+                .map(|folder| folder.as_str().map(str::to_string))
+                .collect::<Option<_>>()
+                .ok_or("`folders` must hold strings")?,
+            _ => return Err("`folders` must be an array".to_string()),
+        },
+    })
+}
+
+fn parse_finding(value: &Value) -> Result<Finding, String> {
+    let text = |name: &str| match &value[name] {
+        Value::Null => Ok(None),
+        Value::String(text) => Ok(Some(text.clone())),
+        _ => Err(format!("`{}` must be a string", name)),
+    };
+
+    let id = text("check")?.ok_or("missing `check`")?;
+    let check = Check::from_id(&id).ok_or_else(|| format!("unknown check `{}`", id))?;
+    let severity = text("severity")?.ok_or("missing `severity`")?;
+    let mut finding = Finding::new(check)
+        .severity(
+            Severity::from_name(&severity)
+                .ok_or_else(|| format!("unknown severity `{}`", severity))?,
+        )
+        .weight(value["weight"].as_u64().ok_or("missing `weight`")? as usize);
+    finding.lang = text("lang")?;
+    finding.key = text("key")?;
+    finding.file = text("file")?;
+    finding.line = value["line"].as_u64().map(|line| line as usize);
+    finding.column = value["column"].as_u64().map(|column| column as usize);
+    finding.message = text("message")?;
+    for detail in value["details"].as_array().ok_or("missing `details`")? {
+        match (detail["label"].as_str(), detail["value"].as_str()) {
+            (Some(label), Some(value)) => finding = finding.detail(label, value),
+            _ => return Err("details need a `label` and a `value`".to_string()),
+        }
+    }
+    if let Some(owners) = value["owners"].as_array() {
+        finding.owners = Some(
+            owners
+                .iter()
+                .map(|owner| owner.as_str().map(str::to_string))
+                .collect::<Option<_>>()
+                .ok_or("`owners` must be an array of strings")?,
+        );
+    }
+    if !value["suggested_fix"].is_null() {
+        finding.suggested_fix = Some(value["suggested_fix"].clone());
+    }
+    // Reports written before values were quoted have none.
+    for excerpt in value["values"].as_array().into_iter().flatten() {
+        finding.values.push(parse_excerpt(excerpt)?);
+    }
+    Ok(finding)
+}
+
+fn parse_excerpt(value: &Value) -> Result<Excerpt, String> {
+    let (Some(label), Some(text)) = (value["label"].as_str(), value["text"].as_str()) else {
+        return Err("values need a `label` and a `text`".to_string());
+    };
+    let highlights = value["highlights"]
+        .as_array()
+        .ok_or("values need `highlights`")?
+        .iter()
+        .map(
+            |range| match (range["start"].as_u64(), range["end"].as_u64()) {
+                (Some(start), Some(end)) => Ok(start as usize..end as usize),
+                _ => Err("highlights need a `start` and an `end`".to_string()),
+            },
+        )
+        .collect::<Result<_, _>>()?;
+    Ok(Excerpt {
+        label: label.to_string(),
+        text: text.to_string(),
+        truncated: value["truncated"].as_bool().unwrap_or(false),
+        highlights,
+    })
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
+
+// The JSON Schema of the documents `JsonRenderer` writes, for
+// `schema-report`. Check ids and severities come from their Rust types.
+pub fn schema() -> Value {
+    let count = json!({ "type": "integer", "minimum": 0 });
+    let text = json!({ "type": "string" });
+    let optional_text = json!({ "type": ["string", "null"] });
+    let checks: Vec<&str> = Check::ALL.iter().map(|check| check.info().id).collect();
+    let severities: Vec<&str> = [Severity::Error, Severity::Warning, Severity::Info]
+        .iter()
+        .map(|severity| severity.name())
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": format!("Translation check report, version {}", SCHEMA_VERSION),
+        "type": "object",
+        "required": [
+            "schema_version", "summary", "findings", "missing_keys", "extra_keys",
+            "variable_mismatches", "unused_keys",
+        ],
+        "additionalProperties": false,
+        "properties": {
+            "schema_version": {
+                "type": "string",
+                "pattern": format!("^{}\\.[0-9]+\\.[0-9]+$", major(SCHEMA_VERSION)),
+            },
+            "summary": {
+                "type": "object",
+                "required": ["errors", "warnings", "notices"],
+                "additionalProperties": false,
+                "properties": {
+                    "errors": count,
+                    "warnings": count,
+                    "notices": count,
+                    "waived": count,
+                    "baselined": count,
+                    "base_only": count,
+                    "source_roots": { "type": "array", "items": text },
+                },
+            },
+            "findings": { "type": "array", "items": { "$ref": "#/$defs/finding" } },
+            "missing_keys": { "type": "array", "items": { "$ref": "#/$defs/listed" } },
+            "extra_keys": { "type": "array", "items": { "$ref": "#/$defs/listed" } },
+            "variable_mismatches": { "type": "array", "items": { "$ref": "#/$defs/listed" } },
+            "unused_keys": { "type": "array", "items": { "$ref": "#/$defs/listed" } },
+            "config": { "type": "object" },
+            "coverage": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": [
+                        "lang", "total_keys", "translated", "missing", "extra",
+                        "variable_mismatches", "coverage",
+                    ],
+                    "additionalProperties": false,
+                    "properties": {
+                        "lang": text,
+                        "total_keys": count,
+                        "translated": count,
+                        "missing": count,
+                        "extra": count,
+                        "variable_mismatches": count,
+                        "coverage": { "type": "number", "minimum": 0, "maximum": 100 },
+                        "folders": { "type": "array", "items": text },
+                    },
+                },
+            },
+            "partition": {
+                "type": "object",
+                "required": ["index", "count", "by", "keys", "total_keys"],
+                "additionalProperties": false,
+                "properties": {
+                    "index": { "type": "integer", "minimum": 1 },
+                    "count": { "type": "integer", "minimum": 1 },
+                    "by": { "enum": ["prefix"] },
+                    "keys": count,
+                    "total_keys": count,
+                },
+            },
+        },
+        "$defs": {
+            "finding": {
+                "type": "object",
+                "required": [
+                    "check", "severity", "weight", "lang", "key", "file", "line", "column",
+                    "message", "details", "values",
+                ],
+                "additionalProperties": false,
+                "properties": {
+                    "check": { "enum": checks },
+                    "severity": { "enum": severities },
+                    "weight": count,
+                    "lang": optional_text,
+                    "key": optional_text,
+                    "file": optional_text,
+                    "line": { "type": ["integer", "null"], "minimum": 1 },
+                    "column": { "type": ["integer", "null"], "minimum": 1 },
+                    "message": optional_text,
+                    "details": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["label", "value"],
+                            "additionalProperties": false,
+                            "properties": { "label": text, "value": text },
+                        },
+                    },
+                    "values": { "type": "array", "items": { "$ref": "#/$defs/excerpt" } },
+                    "owners": { "type": "array", "items": text },
+                    "context": { "type": "array", "items": text },
+                    "suggested_fix": {
+                        "type": "object",
+                        "required": ["file", "patch"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "file": text,
+                            "patch": { "type": "array", "items": { "$ref": "#/$defs/operation" } },
+                        },
+                    },
+                    "grouped_members": {
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/finding" },
+                    },
+                },
+            },
+            "listed": {
+                "type": "object",
+                "required": ["key", "lang", "file"],
+                "additionalProperties": false,
+                "properties": { "key": optional_text, "lang": optional_text, "file": optional_text },
+            },
+            "excerpt": {
+                "type": "object",
+                "required": ["label", "text", "truncated", "highlights"],
+                "additionalProperties": false,
+                "properties": {
+                    "label": text,
+                    "text": text,
+                    "truncated": { "type": "boolean" },
+                    "highlights": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["start", "end"],
+                            "additionalProperties": false,
+                            "properties": { "start": count, "end": count },
+                        },
+                    },
+                },
+            },
+            // A JSON Patch (RFC 6902) operation.
+            "operation": {
+                "type": "object",
+                "required": ["op", "path"],
+                "properties": {
+                    "op": { "enum": ["add", "remove", "replace", "move", "copy", "test"] },
+                    "path": text,
+                    "from": text,
+                },
+            },
+        },
+    })
+}
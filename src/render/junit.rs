@@ -0,0 +1,179 @@
+use crate::render::Renderer;
+use crate::report::{Check, Finding, Report, Severity};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+// What a test suite stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JunitSuites {
+    Check,
+    Language,
+}
+
+// JUnit XML for CI test report views: one test suite per language, or per
+// check, and one test case per finding. Errors are failures; warnings and
+// notices pass with their details as output, so they show up without failing
+// the build twice.
+pub struct JunitRenderer<W: Write> {
+    out: W,
+    suites: JunitSuites,
+}
+
+impl<W: Write> JunitRenderer<W> {
+    pub fn new(out: W, suites: JunitSuites) -> JunitRenderer<W> {
+        JunitRenderer { out, suites }
+    }
+
+    fn test_case(&mut self, finding: &Finding) -> io::Result<()> {
+        let info = finding.check.info();
+        let subject = finding
+            .key
+            .clone()
+            .or_else(|| finding.location())
+            .unwrap_or_else(|| info.id.to_string());
+        // The suite already names what the case name leaves out.
+        let name = match (self.suites, &finding.lang) {
+            (JunitSuites::Check, Some(lang)) => format!("{}: {}", lang, subject),
+            (JunitSuites::Language, _) => format!("{}: {}", info.id, subject),
+            (JunitSuites::Check, None) => subject,
+        };
+
+        let mut text = Vec::new();
+        if let Some(message) = &finding.message {
+            text.push(message.clone());
+        }
+        text.extend(
+            finding
+                .details
+                .iter()
+                .map(|(label, value)| format!("{}: {}", label, value)),
+        );
+        text.extend(finding.values.iter().map(|excerpt| {
+            let ellipsis = if excerpt.truncated { "…" } else { "" };
+            format!("Value ({}): {}{}", excerpt.label, excerpt.text, ellipsis)
+        }));
+        if let Some(location) = finding.location() {
+            text.push(format!("File: {}", location));
+        }
+        let text = escape(&text.join("\n"));
+
+        write!(
+            self.out,
+            "    <testcase name=\"{}\" classname=\"translation-check.{}\"",
+            escape(&name),
+            info.id
+        )?;
+        if let Some(file) = &finding.file {
+            write!(self.out, " file=\"{}\"", escape(file))?;
+        }
+        if finding.severity == Severity::Error {
+            writeln!(
+                self.out,
+                ">\n      <failure message=\"{}\" type=\"{}\">{}</failure>\n    </testcase>",
+                escape(info.title),
+                info.id,
+                text
+            )
+        } else {
+            writeln!(
+                self.out,
+                ">\n      <system-out>{}</system-out>\n    </testcase>",
+                text
+            )
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| matches!(c, '\t' | '\n' | '\r') || *c >= ' ')
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn failures<'a>(findings: impl IntoIterator<Item = &'a Finding>) -> usize {
+    findings
+        .into_iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .count()
+}
+
+// The findings of each suite, in the order of the checks or of the
+// languages. Roll-ups are split back into the findings of each language, and
+// findings of no language, such as those about the source files, make up a
+// `project` suite after the languages.
+fn suites(report: &Report, suites: JunitSuites) -> Vec<(String, Vec<&Finding>)> {
+    match suites {
+        JunitSuites::Check => Check::ALL
+            .into_iter()
+            .map(|check| {
+                let findings = report
+                    .findings
+                    .iter()
+                    .filter(|finding| finding.check == check)
+                    .collect();
+                (check.info().id.to_string(), findings)
+            })
+            .filter(|(_, findings): &(String, Vec<&Finding>)| !findings.is_empty())
+            .collect(),
+        JunitSuites::Language => {
+            let mut languages: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+            let mut project = Vec::new();
+            let findings = report.findings.iter().flat_map(|finding| {
+                if finding.members.is_empty() {
+                    std::slice::from_ref(finding)
+                } else {
+                    &finding.members[..]
+                }
+            });
+            for finding in findings {
+                match &finding.lang {
+                    Some(lang) => languages.entry(lang).or_default().push(finding),
+                    None => project.push(finding),
+                }
+            }
+            let mut suites: Vec<(String, Vec<&Finding>)> = languages
+                .into_iter()
+                .map(|(lang, findings)| (lang.to_string(), findings))
+                .collect();
+            if !project.is_empty() {
+                suites.push(("project".to_string(), project));
+            }
+            suites
+        }
+    }
+}
+
+impl<W: Write> Renderer for JunitRenderer<W> {
+    fn render(&mut self, report: &Report) -> io::Result<()> {
+        writeln!(self.out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            self.out,
+            "<testsuites name=\"translation-check\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+            report.findings.len(),
+            failures(&report.findings)
+        )?;
+        for (name, findings) in suites(report, self.suites) {
+            writeln!(
+                self.out,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+                escape(&name),
+                findings.len(),
+                failures(findings.iter().copied())
+            )?;
+            for finding in findings {
+                self.test_case(finding)?;
+            }
+            writeln!(self.out, "  </testsuite>")?;
+        }
+        writeln!(self.out, "</testsuites>")?;
+        self.out.flush()
+    }
+}
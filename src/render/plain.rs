@@ -0,0 +1,64 @@
+use crate::render::Renderer;
+use crate::report::{Finding, Report};
+use std::io::{self, Write};
+
+// One line per finding for grep and shell scripts, with tab-separated
+// fields: severity, check id, language, key, `file[:line]` and message.
+// Missing fields are `-`, quoted values are left out. The layout is stable.
+pub struct PlainRenderer<W: Write> {
+    out: W,
+}
+
+impl<W: Write> PlainRenderer<W> {
+    pub fn new(out: W) -> PlainRenderer<W> {
+        PlainRenderer { out }
+    }
+}
+
+impl<W: Write> Renderer for PlainRenderer<W> {
+    fn render(&mut self, report: &Report) -> io::Result<()> {
+        for finding in &report.findings {
+            let fields = [
+                finding.severity.name().to_uppercase(),
+                finding.check.info().id.to_string(),
+                field(finding.lang.as_deref()),
+                field(finding.key.as_deref()),
+                field(finding.location().as_deref()),
+                field(Some(&message(finding))),
+            ];
+            writeln!(self.out, "{}", fields.join("\t"))?;
+        }
+        Ok(())
+    }
+}
+
+// The message and details of a finding, separated by `; `.
+fn message(finding: &Finding) -> String {
+    let mut parts: Vec<String> = finding.message.iter().cloned().collect();
+    parts.extend(
+        finding
+            .details
+            .iter()
+            .map(|(label, value)| format!("{}: {}", label, value)),
+    );
+    parts.join("; ")
+}
+
+// Escapes what would break the layout: tabs, line breaks and the
+// backslashes of the escapes themselves.
+fn field(value: Option<&str>) -> String {
+    let Some(value) = value.filter(|value| !value.is_empty()) else {
+        return "-".to_string();
+    };
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
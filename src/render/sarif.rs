@@ -0,0 +1,144 @@
+use crate::render::Renderer;
+use crate::report::{Check, Finding, Report, Severity};
+use serde_json::{Map, Value, json};
+use std::io::{self, Write};
+
+pub const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// SARIF 2.1.0, as GitHub code scanning uploads it: one rule per check the
+// report has findings for and one result per finding, located in its file
+// when it has one. Grouped findings are written as their members.
+pub struct SarifRenderer<W: Write> {
+    out: W,
+}
+
+impl<W: Write> SarifRenderer<W> {
+    pub fn new(out: W) -> SarifRenderer<W> {
+        SarifRenderer { out }
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+fn rule(check: Check) -> Value {
+    let info = check.info();
+    json!({
+        "id": info.id,
+        "shortDescription": { "text": info.title },
+        "defaultConfiguration": { "level": level(info.severity) },
+    })
+}
+
+// The title of the check, then the language and key, the message and the
+// details, as code scanning shows one line of text per result.
+fn message(finding: &Finding) -> String {
+    let mut text = finding.check.info().title.to_string();
+    match (&finding.lang, &finding.key) {
+        (Some(lang), Some(key)) => {
+            text.push_str(&format!(": `{}` in {}", key, lang.to_uppercase()))
+        }
+        (None, Some(key)) => text.push_str(&format!(": `{}`", key)),
+        (Some(lang), None) => text.push_str(&format!(" in {}", lang.to_uppercase())),
+        (None, None) => {}
+    }
+    let mut parts: Vec<String> = finding.message.iter().cloned().collect();
+    parts.extend(
+        finding
+            .details
+            .iter()
+            .map(|(label, value)| format!("{}: {}", label, value)),
+    );
+    if !parts.is_empty() {
+        text.push_str(&format!(" ({})", parts.join("; ")));
+    }
+    text
+}
+
+fn result(finding: &Finding, rule_index: usize) -> Value {
+    let mut result = Map::new();
+    result.insert("ruleId".into(), json!(finding.check.info().id));
+    result.insert("ruleIndex".into(), json!(rule_index));
+    result.insert("level".into(), json!(level(finding.severity)));
+    result.insert("message".into(), json!({ "text": message(finding) }));
+    // A key missing from a base without files has no file to point to.
+    if let Some(file) = finding.file.as_ref().filter(|file| *file != "Unknown file") {
+        let mut location = json!({
+            "artifactLocation": { "uri": file.replace('\\', "/") },
+        });
+        if let Some(line) = finding.line {
+            location["region"] = json!({ "startLine": line });
+            if let Some(column) = finding.column {
+                location["region"]["startColumn"] = json!(column);
+            }
+        }
+        result.insert(
+            "locations".into(),
+            json!([{ "physicalLocation": location }]),
+        );
+    }
+    let mut properties = Map::new();
+    if let Some(lang) = &finding.lang {
+        properties.insert("lang".into(), json!(lang));
+    }
+    if let Some(key) = &finding.key {
+        properties.insert("key".into(), json!(key));
+    }
+    if !properties.is_empty() {
+        result.insert("properties".into(), Value::Object(properties));
+    }
+    Value::Object(result)
+}
+
+impl<W: Write> Renderer for SarifRenderer<W> {
+    fn render(&mut self, report: &Report) -> io::Result<()> {
+        let findings: Vec<&Finding> = report
+            .findings
+            .iter()
+            .flat_map(|finding| {
+                if finding.members.is_empty() {
+                    vec![finding]
+                } else {
+                    finding.members.iter().collect()
+                }
+            })
+            .collect();
+        let checks: Vec<Check> = Check::ALL
+            .into_iter()
+            .filter(|check| findings.iter().any(|finding| finding.check == *check))
+            .collect();
+        let results: Vec<Value> = findings
+            .iter()
+            .map(|finding| {
+                let index = checks.iter().position(|check| *check == finding.check);
+                result(finding, index.unwrap())
+            })
+            .collect();
+
+        let document = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "translation-check",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": checks.into_iter().map(rule).collect::<Vec<_>>(),
+                    }
+                },
+                "results": results,
+                // Columns count characters, as the other reports do.
+                "columnKind": "unicodeCodePoints",
+            }],
+        });
+        serde_json::to_writer_pretty(&mut self.out, &document)?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
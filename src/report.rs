@@ -0,0 +1,1183 @@
+use crate::partition::Fragment;
+use colored::Color;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Severity> {
+        [Severity::Info, Severity::Warning, Severity::Error]
+            .into_iter()
+            .find(|severity| severity.name() == name)
+    }
+}
+
+// How the human renderer lays out the findings of a check: one line per
+// finding under a shared header, a header per finding followed by one line
+// per detail, or just the number of findings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Inline,
+    Block,
+    Count,
+}
+
+pub struct CheckInfo {
+    // Stable identifier of the check, used as the `check` label of the
+    // exported metrics. Never rename one.
+    pub id: &'static str,
+    pub emoji: &'static str,
+    pub title: &'static str,
+    pub color: Color,
+    pub severity: Severity,
+    pub layout: Layout,
+}
+
+// Every kind of finding, in the order they are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Check {
+    LanguageRemoved,
+    LanguageAdded,
+    UnreadableFile,
+    LanguageExcluded,
+    MixedFileNames,
+    StrayFile,
+    UnassignedFile,
+    LineEndings,
+    KeyCaseCollision,
+    KeyNaming,
+    DuplicateKey,
+    AliasConflict,
+    FuzzyTranslation,
+    SuspiciousBaseValue,
+    PluralFormMismatch,
+    PluralRulesUnknown,
+    PluralCategoryMissing,
+    PluralCategoryUnused,
+    MissingKey,
+    ExtraKey,
+    TypeMismatch,
+    ArrayLengthMismatch,
+    EmptyValue,
+    UntranslatedValue,
+    BaseOnlyKeyTranslated,
+    KeyCaseMismatch,
+    BasePlaceholdersChanged,
+    VariableMismatch,
+    TranslatedPlaceholder,
+    ReservedPlaceholder,
+    DoNotTranslateChanged,
+    QuoteEscape,
+    MarkupMismatch,
+    UnusedKeyInTranslation,
+    LinkedKeyDrift,
+    LinkedKeyMissing,
+    LinkNotAllowed,
+    LinkCycle,
+    SiblingDuplicate,
+    PlaceholderPosition,
+    DuplicatePlaceholder,
+    IcuSyntaxError,
+    IcuArgumentMismatch,
+    StyleViolation,
+    UnexpectedScript,
+    NotNfc,
+    ForbiddenWord,
+    MissingRequiredPhrase,
+    Unreviewed,
+    ModifiedSinceReview,
+    StaleScaffold,
+    LoaderMissingFile,
+    LoaderUnreferencedFile,
+    UndefinedKey,
+    UnloadedKey,
+    MissingInterpolation,
+    UnverifiableInterpolation,
+    BackendMissingKey,
+    UnusedKey,
+    E2eOnlyUsage,
+    ExpiredWaiver,
+    UnusedWaiver,
+    MalformedSuppression,
+    UnusedSuppression,
+    StaleContext,
+    UnownedFile,
+    CoverageBelowMinimum,
+    BundleMissingLanguage,
+    BundleMissingKey,
+    BundleStaleKey,
+}
+
+impl Check {
+    pub const ALL: [Check; 70] = [
+        Check::LanguageRemoved,
+        Check::LanguageAdded,
+        Check::UnreadableFile,
+        Check::LanguageExcluded,
+        Check::MixedFileNames,
+        Check::StrayFile,
+        Check::UnassignedFile,
+        Check::LineEndings,
+        Check::KeyCaseCollision,
+        Check::KeyNaming,
+        Check::DuplicateKey,
+        Check::AliasConflict,
+        Check::FuzzyTranslation,
+        Check::SuspiciousBaseValue,
+        Check::PluralFormMismatch,
+        Check::PluralRulesUnknown,
+        Check::PluralCategoryMissing,
+        Check::PluralCategoryUnused,
+        Check::MissingKey,
+        Check::ExtraKey,
+        Check::TypeMismatch,
+        Check::ArrayLengthMismatch,
+        Check::EmptyValue,
+        Check::UntranslatedValue,
+        Check::BaseOnlyKeyTranslated,
+        Check::KeyCaseMismatch,
+        Check::BasePlaceholdersChanged,
+        Check::VariableMismatch,
+        Check::TranslatedPlaceholder,
+        Check::ReservedPlaceholder,
+        Check::DoNotTranslateChanged,
+        Check::QuoteEscape,
+        Check::MarkupMismatch,
+        Check::UnusedKeyInTranslation,
+        Check::LinkedKeyDrift,
+        Check::LinkedKeyMissing,
+        Check::LinkNotAllowed,
+        Check::LinkCycle,
+        Check::SiblingDuplicate,
+        Check::PlaceholderPosition,
+        Check::DuplicatePlaceholder,
+        Check::IcuSyntaxError,
+        Check::IcuArgumentMismatch,
+        Check::StyleViolation,
+        Check::UnexpectedScript,
+        Check::NotNfc,
+        Check::ForbiddenWord,
+        Check::MissingRequiredPhrase,
+        Check::Unreviewed,
+        Check::ModifiedSinceReview,
+        Check::StaleScaffold,
+        Check::LoaderMissingFile,
+        Check::LoaderUnreferencedFile,
+        Check::UndefinedKey,
+        Check::UnloadedKey,
+        Check::MissingInterpolation,
+        Check::UnverifiableInterpolation,
+        Check::BackendMissingKey,
+        Check::UnusedKey,
+        Check::E2eOnlyUsage,
+        Check::ExpiredWaiver,
+        Check::UnusedWaiver,
+        Check::MalformedSuppression,
+        Check::UnusedSuppression,
+        Check::StaleContext,
+        Check::UnownedFile,
+        Check::CoverageBelowMinimum,
+        Check::BundleMissingLanguage,
+        Check::BundleMissingKey,
+        Check::BundleStaleKey,
+    ];
+
+    pub fn from_id(id: &str) -> Option<Check> {
+        Check::ALL.into_iter().find(|check| check.info().id == id)
+    }
+
+    pub fn info(self) -> &'static CheckInfo {
+        match self {
+            Check::LanguageRemoved => &CheckInfo {
+                id: "language_removed",
+                emoji: "🗑️",
+                title: "Languages removed",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::LanguageAdded => &CheckInfo {
+                id: "language_added",
+                emoji: "🆕",
+                title: "Languages added",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::UnreadableFile => &CheckInfo {
+                id: "unreadable_file",
+                emoji: "🚷",
+                title: "Unreadable translation files",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::LanguageExcluded => &CheckInfo {
+                id: "language_excluded",
+                emoji: "⛔",
+                title: "Languages left out of the comparison",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::MixedFileNames => &CheckInfo {
+                id: "mixed_file_names",
+                emoji: "🗂️",
+                title: "Language folders mixing file naming conventions",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Block,
+            },
+            Check::StrayFile => &CheckInfo {
+                id: "stray_file",
+                emoji: "🚮",
+                title: "Files in the translation directory that aren't translations",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::UnassignedFile => &CheckInfo {
+                id: "unassigned_file",
+                emoji: "🧳",
+                title: "Translation files outside the language folders",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::LineEndings => &CheckInfo {
+                id: "line_endings",
+                emoji: "↩️",
+                title: "Translation files breaking the line ending policy",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::KeyCaseCollision => &CheckInfo {
+                id: "key_case_collision",
+                emoji: "❌",
+                title: "Keys differing only in case",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::KeyNaming => &CheckInfo {
+                id: "key_naming",
+                emoji: "🪪",
+                title: "Keys breaking the naming rules",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::DuplicateKey => &CheckInfo {
+                id: "duplicate_key",
+                emoji: "👯",
+                title: "Keys defined in more than one place",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::AliasConflict => &CheckInfo {
+                id: "alias_conflict",
+                emoji: "🪞",
+                title: "Keys with different values in folders of the same language",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::FuzzyTranslation => &CheckInfo {
+                id: "fuzzy_translation",
+                emoji: "🌫️",
+                title: "Fuzzy translations awaiting review",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::SuspiciousBaseValue => &CheckInfo {
+                id: "suspicious_base_value",
+                emoji: "🚩",
+                title: "Suspicious base values",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::PluralFormMismatch => &CheckInfo {
+                id: "plural_form_mismatch",
+                emoji: "🔢",
+                title: "Plural forms of a base key using different placeholders",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::PluralRulesUnknown => &CheckInfo {
+                id: "plural_rules_unknown",
+                emoji: "🧭",
+                title: "Languages without known plural rules",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::PluralCategoryMissing => &CheckInfo {
+                id: "plural_category_missing",
+                emoji: "🧮",
+                title: "Plural keys lacking a form their language requires",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Block,
+            },
+            Check::PluralCategoryUnused => &CheckInfo {
+                id: "plural_category_unused",
+                emoji: "🪦",
+                title: "Plural forms their language never uses",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::MissingKey => &CheckInfo {
+                id: "missing_key",
+                emoji: "❌",
+                title: "Missing keys",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::ExtraKey => &CheckInfo {
+                id: "extra_key",
+                emoji: "⚠️",
+                title: "Extra keys",
+                color: Color::Yellow,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::TypeMismatch => &CheckInfo {
+                id: "type_mismatch",
+                emoji: "🔀",
+                title: "Keys holding another type of value than in the base",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::ArrayLengthMismatch => &CheckInfo {
+                id: "array_length_mismatch",
+                emoji: "📏",
+                title: "Arrays with another number of elements than in the base",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::EmptyValue => &CheckInfo {
+                id: "empty_value",
+                emoji: "📭",
+                title: "Empty translations",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::UntranslatedValue => &CheckInfo {
+                id: "untranslated_value",
+                emoji: "📋",
+                title: "Translations identical to the base value",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::BaseOnlyKeyTranslated => &CheckInfo {
+                id: "base_only_key_translated",
+                emoji: "🧪",
+                title: "Base-only keys translated",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::KeyCaseMismatch => &CheckInfo {
+                id: "key_case_mismatch",
+                emoji: "🔠",
+                title: "Key case mismatch",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::BasePlaceholdersChanged => &CheckInfo {
+                id: "base_placeholders_changed",
+                emoji: "🔁",
+                title: "Base placeholders changed",
+                color: Color::Magenta,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::VariableMismatch => &CheckInfo {
+                id: "variable_mismatch",
+                emoji: "🔄",
+                title: "Variable mismatch detected!",
+                color: Color::Magenta,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::TranslatedPlaceholder => &CheckInfo {
+                id: "translated_placeholder",
+                emoji: "🏷️",
+                title: "Placeholders renamed by the translation",
+                color: Color::Magenta,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::ReservedPlaceholder => &CheckInfo {
+                id: "reserved_placeholder",
+                emoji: "🔒",
+                title: "Reserved placeholder names misused",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::DoNotTranslateChanged => &CheckInfo {
+                id: "do_not_translate_changed",
+                emoji: "🔒",
+                title: "Do-not-translate value changed",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::QuoteEscape => &CheckInfo {
+                id: "quote_escape",
+                emoji: "💬",
+                title: "Escaped or unpaired quotes",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Block,
+            },
+            Check::MarkupMismatch => &CheckInfo {
+                id: "markup_mismatch",
+                emoji: "📐",
+                title: "Markup differing from the base",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::UnusedKeyInTranslation => &CheckInfo {
+                id: "unused_key_in_translation",
+                emoji: "⚠️",
+                title: "Unused key found in translation",
+                color: Color::Yellow,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::LinkedKeyDrift => &CheckInfo {
+                id: "linked_key_drift",
+                emoji: "🔗",
+                title: "Linked keys have different values",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::LinkedKeyMissing => &CheckInfo {
+                id: "linked_key_missing",
+                emoji: "🔗",
+                title: "Linked keys missing from their group",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::LinkNotAllowed => &CheckInfo {
+                id: "link_not_allowed",
+                emoji: "⛓️",
+                title: "Link to a key outside the linkable keys",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::LinkCycle => &CheckInfo {
+                id: "link_cycle",
+                emoji: "🌀",
+                title: "Linked messages forming a cycle",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::SiblingDuplicate => &CheckInfo {
+                id: "sibling_duplicate",
+                emoji: "👯",
+                title: "Sibling keys sharing a translation",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Block,
+            },
+            Check::PlaceholderPosition => &CheckInfo {
+                id: "placeholder_position",
+                emoji: "📍",
+                title: "Placeholders moved to or from the edges of the value",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Block,
+            },
+            Check::DuplicatePlaceholder => &CheckInfo {
+                id: "duplicate_placeholder",
+                emoji: "🔂",
+                title: "Placeholders repeated more often than the base repeats them",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Block,
+            },
+            Check::IcuSyntaxError => &CheckInfo {
+                id: "icu_syntax_error",
+                emoji: "🧶",
+                title: "Values that aren't valid ICU messages",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::IcuArgumentMismatch => &CheckInfo {
+                id: "icu_argument_mismatch",
+                emoji: "🎛️",
+                title: "ICU arguments differing from the base",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Block,
+            },
+            Check::StyleViolation => &CheckInfo {
+                id: "style_violation",
+                emoji: "🎨",
+                title: "Values not written in the style of their namespace",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Block,
+            },
+            Check::UnexpectedScript => &CheckInfo {
+                id: "unexpected_script",
+                emoji: "🔤",
+                title: "Values in an unexpected script",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::NotNfc => &CheckInfo {
+                id: "not_nfc",
+                emoji: "🔣",
+                title: "Text not in Unicode NFC",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::ForbiddenWord => &CheckInfo {
+                id: "forbidden_word",
+                emoji: "🚫",
+                title: "Values using a forbidden word",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::MissingRequiredPhrase => &CheckInfo {
+                id: "missing_required_phrase",
+                emoji: "📜",
+                title: "Values lacking a required phrase",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::Unreviewed => &CheckInfo {
+                id: "unreviewed",
+                emoji: "📝",
+                title: "Translations without an approved review",
+                color: Color::Yellow,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::ModifiedSinceReview => &CheckInfo {
+                id: "modified_since_review",
+                emoji: "📝",
+                title: "Translations modified since review",
+                color: Color::Yellow,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::StaleScaffold => &CheckInfo {
+                id: "stale_scaffold",
+                emoji: "🏗️",
+                title: "Scaffolds left untranslated while their base value changed",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::LoaderMissingFile => &CheckInfo {
+                id: "loader_missing_file",
+                emoji: "📦",
+                title: "Files listed in the loader config but missing",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::LoaderUnreferencedFile => &CheckInfo {
+                id: "loader_unreferenced_file",
+                emoji: "📦",
+                title: "Files not referenced by the loader config",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::UndefinedKey => &CheckInfo {
+                id: "undefined_key",
+                emoji: "❓",
+                title: "Keys used in the code but missing from the base",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::UnloadedKey => &CheckInfo {
+                id: "unloaded_key",
+                emoji: "📦",
+                title: "Keys used by a route that doesn't load their file",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::MissingInterpolation => &CheckInfo {
+                id: "missing_interpolation",
+                emoji: "🧩",
+                title: "Missing interpolation variables",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::UnverifiableInterpolation => &CheckInfo {
+                id: "unverifiable_interpolation",
+                emoji: "🧩",
+                title: "Unverifiable interpolation call sites",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Count,
+            },
+            Check::BackendMissingKey => &CheckInfo {
+                id: "backend_missing_key",
+                emoji: "🛰️",
+                title: "Backend references missing key",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::UnusedKey => &CheckInfo {
+                id: "unused_key",
+                emoji: "🧹",
+                title: "Unused keys",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Count,
+            },
+            Check::E2eOnlyUsage => &CheckInfo {
+                id: "e2e_only_usage",
+                emoji: "🎭",
+                title: "Keys only end-to-end tests use",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Inline,
+            },
+            Check::ExpiredWaiver => &CheckInfo {
+                id: "expired_waiver",
+                emoji: "⏰",
+                title: "Expired waivers",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+            Check::UnusedWaiver => &CheckInfo {
+                id: "unused_waiver",
+                emoji: "🙈",
+                title: "Waivers matching no finding",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Inline,
+            },
+            Check::MalformedSuppression => &CheckInfo {
+                id: "malformed_suppression",
+                emoji: "🚫",
+                title: "Malformed suppression comments",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::UnusedSuppression => &CheckInfo {
+                id: "unused_suppression",
+                emoji: "💤",
+                title: "Suppression comments matching no finding",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Inline,
+            },
+            Check::StaleContext => &CheckInfo {
+                id: "stale_context",
+                emoji: "🖼️",
+                title: "Context manifest entries matching no key",
+                color: Color::Yellow,
+                severity: Severity::Info,
+                layout: Layout::Inline,
+            },
+            Check::UnownedFile => &CheckInfo {
+                id: "unowned_file",
+                emoji: "👥",
+                title: "Files with no code owner",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::CoverageBelowMinimum => &CheckInfo {
+                id: "coverage_below_minimum",
+                emoji: "📉",
+                title: "Languages below the minimum coverage",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::BundleMissingLanguage => &CheckInfo {
+                id: "bundle_missing_language",
+                emoji: "🚚",
+                title: "Languages missing from the build output",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::BundleMissingKey => &CheckInfo {
+                id: "bundle_missing_key",
+                emoji: "🕳️",
+                title: "Keys missing from the build output",
+                color: Color::Red,
+                severity: Severity::Error,
+                layout: Layout::Inline,
+            },
+            Check::BundleStaleKey => &CheckInfo {
+                id: "bundle_stale_key",
+                emoji: "📤",
+                title: "Keys the build output ships that the sources no longer have",
+                color: Color::Yellow,
+                severity: Severity::Warning,
+                layout: Layout::Inline,
+            },
+        }
+    }
+}
+
+// A value quoted in a finding, such as both sides of a variable mismatch.
+// `highlights` are byte ranges of `text` worth pointing out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Excerpt {
+    pub label: String,
+    pub text: String,
+    // Whether `text` was cut short.
+    pub truncated: bool,
+    pub highlights: Vec<Range<usize>>,
+}
+
+impl Excerpt {
+    // Quotes `text`, keeping at most `max_chars` characters (all of them
+    // when zero). A highlight crossing the cut is kept whole.
+    pub fn new(
+        label: impl Into<String>,
+        text: &str,
+        highlights: Vec<Range<usize>>,
+        max_chars: usize,
+    ) -> Excerpt {
+        let mut end = match text.char_indices().nth(max_chars) {
+            Some((end, _)) if max_chars > 0 => end,
+            _ => text.len(),
+        };
+        if let Some(crossing) = highlights.iter().find(|range| range.contains(&end)) {
+            end = crossing.end;
+        }
+        Excerpt {
+            label: label.into(),
+            text: text[..end].to_string(),
+            truncated: end < text.len(),
+            highlights: highlights
+                .into_iter()
+                .filter(|range| range.end <= end)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub check: Check,
+    pub severity: Severity,
+    // How many individual problems the finding stands for, so grouped
+    // findings weigh as much as the findings they replace.
+    pub weight: usize,
+    pub lang: Option<String>,
+    pub key: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    // The column of `line` the finding starts at, counted in characters
+    // from 1, when known.
+    pub column: Option<usize>,
+    pub message: Option<String>,
+    pub details: Vec<(String, String)>,
+    pub values: Vec<Excerpt>,
+    // The code owners of the file, when a CODEOWNERS file was given; empty
+    // when nobody owns it.
+    pub owners: Option<Vec<String>>,
+    // The target file and JSON patch fixing the finding, with
+    // `--suggest-fixes`.
+    pub suggested_fix: Option<Value>,
+    // The findings a roll-up row stands for, one per language.
+    pub members: Vec<Finding>,
+}
+
+impl Finding {
+    pub fn new(check: Check) -> Finding {
+        Finding {
+            check,
+            severity: check.info().severity,
+            weight: 1,
+            lang: None,
+            key: None,
+            file: None,
+            line: None,
+            column: None,
+            message: None,
+            details: Vec::new(),
+            values: Vec::new(),
+            owners: None,
+            suggested_fix: None,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn lang(mut self, lang: &str) -> Finding {
+        self.lang = Some(lang.to_string());
+        self
+    }
+
+    pub fn key(mut self, key: &str) -> Finding {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    pub fn file(mut self, file: impl Into<String>) -> Finding {
+        self.file = Some(file.into());
+        self
+    }
+
+    pub fn line(mut self, line: usize) -> Finding {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn column(mut self, column: usize) -> Finding {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Finding {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn detail(mut self, label: impl Into<String>, value: impl Into<String>) -> Finding {
+        self.details.push((label.into(), value.into()));
+        self
+    }
+
+    pub fn value(mut self, excerpt: Excerpt) -> Finding {
+        self.values.push(excerpt);
+        self
+    }
+
+    // Overrides the default severity of the check, for checks that only
+    // gate some languages.
+    pub fn severity(mut self, severity: Severity) -> Finding {
+        self.severity = severity;
+        self
+    }
+
+    pub fn weight(mut self, weight: usize) -> Finding {
+        self.weight = weight;
+        self
+    }
+
+    pub fn location(&self) -> Option<String> {
+        let file = self.file.as_ref()?;
+        Some(match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", file, line, column),
+            (Some(line), None) => format!("{}:{}", file, line),
+            _ => file.clone(),
+        })
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Report {
+    pub findings: Vec<Finding>,
+    // Whether the source scan ran, so that zero unused keys can be told
+    // apart from not having looked.
+    pub usage_scanned: bool,
+    // Whether the languages were compared against the base, without which
+    // missing keys and completeness are unknown.
+    pub consistency_checked: bool,
+    pub source_files: usize,
+    // The source folders the scan searched, when it ran: a key reported
+    // unused is used in none of them.
+    pub source_roots: Vec<String>,
+    pub translation_files: usize,
+    // Weight of the findings dropped by waivers, when a waivers file was
+    // given.
+    pub waived: Option<usize>,
+    // Weight of the findings dropped by the baseline, when one was given.
+    pub baselined: Option<usize>,
+    // Number of base keys exempted from translation by
+    // `base_only_namespaces`, when it is set.
+    pub base_only: Option<usize>,
+    // The settings of the run, as recorded in the JSON report.
+    pub config: Option<Value>,
+    // Set when only one shard of a partitioned run is reported.
+    pub fragment: Option<Fragment>,
+    // One row per compared language, when the consistency checks ran.
+    pub coverage: Vec<Coverage>,
+    // The screenshots or design links of the keys with findings, from the
+    // context manifest, held once however many findings a key has.
+    pub context: BTreeMap<String, Vec<String>>,
+}
+
+// How much of the base a language translates. Base-only keys and the ones
+// an `[[ignore]]` entry accepts as missing are left out of `total`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coverage {
+    pub lang: String,
+    pub total: usize,
+    pub translated: usize,
+    pub missing: usize,
+    pub extra: usize,
+    pub mismatches: usize,
+    // The folders merged into the language by `[languages] aliases`, when
+    // there is more than its own.
+    pub folders: Vec<String>,
+}
+
+impl Coverage {
+    pub fn percent(&self) -> f64 {
+        percent(self.translated, self.total)
+    }
+}
+
+// How many base keys a language translates, misses or leaves blank, a value
+// of only whitespace. The coverage table and `stats` both count them with
+// `Report::count_keys`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub translated: usize,
+    pub missing: usize,
+    pub empty: usize,
+}
+
+impl Counts {
+    pub fn total(&self) -> usize {
+        self.translated + self.missing + self.empty
+    }
+
+    pub fn percent(&self) -> f64 {
+        percent(self.translated, self.total())
+    }
+}
+
+// Translated keys per hundred base keys, rounded to one decimal. A base
+// without keys is fully covered.
+fn percent(translated: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    (translated as f64 / total as f64 * 1000.0).round() / 10.0
+}
+
+impl Report {
+    pub fn extend(&mut self, findings: impl IntoIterator<Item = Finding>) {
+        self.findings.extend(findings);
+    }
+
+    // Gives the findings of each check in `severities` that severity, as set
+    // under `[severity]` in the configuration file, and drops those of the
+    // checks turned off.
+    pub fn override_severities(&mut self, severities: &BTreeMap<Check, Option<Severity>>) {
+        if severities.is_empty() {
+            return;
+        }
+        self.findings
+            .retain(|finding| severities.get(&finding.check) != Some(&None));
+        for finding in &mut self.findings {
+            if let Some(Some(severity)) = severities.get(&finding.check) {
+                finding.severity = *severity;
+            }
+        }
+    }
+
+    // Orders findings by language, check, key and location so that two runs
+    // over the same tree render identically.
+    pub fn sort(&mut self) {
+        self.findings.sort_by(|a, b| {
+            (
+                &a.lang, a.check, &a.key, &a.file, a.line, a.column, &a.details,
+            )
+                .cmp(&(
+                    &b.lang, b.check, &b.key, &b.file, b.line, b.column, &b.details,
+                ))
+        });
+    }
+
+    pub fn weight(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .map(|finding| finding.weight)
+            .sum()
+    }
+
+    // The context links of the finding's key.
+    pub fn context(&self, finding: &Finding) -> &[String] {
+        finding
+            .key
+            .as_ref()
+            .and_then(|key| self.context.get(key))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn count(&self, check: Check) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.check == check)
+            .map(|finding| finding.weight)
+            .sum()
+    }
+
+    // Share of the base keys present in a language, between 0 and 1.
+    pub fn completeness(&self, lang: &str, base_keys: usize) -> f64 {
+        let missing = self
+            .findings
+            .iter()
+            .filter(|finding| {
+                finding.check == Check::MissingKey && finding.lang.as_deref() == Some(lang)
+            })
+            .count();
+        let total = base_keys.max(1);
+        (total - missing.min(total)) as f64 / total as f64
+    }
+
+    // The coverage of each language, from the findings before waivers
+    // apply. Base keys `exempt(lang, key)` accepts as absent from a language
+    // are left out of its total, and those `blank(lang, key)` holds with a
+    // blank value count as missing, as `stats` counts them. Languages left
+    // out of the comparison have none.
+    pub fn measure_coverage(
+        &mut self,
+        languages: &[&str],
+        base_keys: &[&str],
+        exempt: impl Fn(&str, &str) -> bool,
+        blank: impl Fn(&str, &str) -> bool,
+    ) {
+        let count = |lang: &str, checks: &[Check]| {
+            self.findings
+                .iter()
+                .filter(|finding| {
+                    checks.contains(&finding.check)
+                        && finding.lang.as_deref() == Some(lang)
+                        && !finding.key.as_deref().is_some_and(|key| exempt(lang, key))
+                })
+                .count()
+        };
+        self.coverage = languages
+            .iter()
+            .filter(|lang| count(lang, &[Check::LanguageExcluded]) == 0)
+            .map(|lang| {
+                let keys: Vec<&str> = base_keys
+                    .iter()
+                    .copied()
+                    .filter(|key| !exempt(lang, key))
+                    .collect();
+                let counts = self.count_keys(lang, &keys, |key| blank(lang, key));
+                Coverage {
+                    lang: lang.to_string(),
+                    total: counts.total(),
+                    translated: counts.translated,
+                    missing: counts.missing + counts.empty,
+                    extra: count(lang, &[Check::ExtraKey]),
+                    mismatches: count(
+                        lang,
+                        &[Check::VariableMismatch, Check::TranslatedPlaceholder],
+                    ),
+                    folders: Vec::new(),
+                }
+            })
+            .collect();
+    }
+
+    // How many of the base `keys` a language translates, misses or leaves
+    // blank, from the findings comparing it with the base. The elements of
+    // an array missing as a whole or shorter than the base are missing;
+    // `blank(key)` tells the keys it holds with a blank value.
+    pub fn count_keys(&self, lang: &str, keys: &[&str], blank: impl Fn(&str) -> bool) -> Counts {
+        let absent: HashSet<&str> = self
+            .findings
+            .iter()
+            .filter(|finding| {
+                finding.check == Check::MissingKey && finding.lang.as_deref() == Some(lang)
+            })
+            .filter_map(|finding| finding.key.as_deref())
+            .collect();
+        // The length of each array shorter than the base, whose elements
+        // past it are reported by the length mismatch only.
+        let label = format!("Length ({})", lang.to_uppercase());
+        let shortened: HashMap<&str, usize> = self
+            .findings
+            .iter()
+            .filter(|finding| {
+                finding.check == Check::ArrayLengthMismatch && finding.lang.as_deref() == Some(lang)
+            })
+            .filter_map(|finding| {
+                let (_, length) = finding.details.iter().find(|(name, _)| *name == label)?;
+                Some((finding.key.as_deref()?, length.parse().ok()?))
+            })
+            .collect();
+        let mut counts = Counts::default();
+        for key in keys {
+            if is_missing(key, &absent, &shortened) {
+                counts.missing += 1;
+            } else if blank(key) {
+                counts.empty += 1;
+            } else {
+                counts.translated += 1;
+            }
+        }
+        counts
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.weight(Severity::Error) > 0
+    }
+}
+
+// Whether a language lacks the base `key`: reported missing itself, under
+// an array or object missing as a whole, which is one finding standing for
+// each of its elements, or an element past the end of an array `shortened`
+// holds the length of.
+fn is_missing(key: &str, absent: &HashSet<&str>, shortened: &HashMap<&str, usize>) -> bool {
+    absent.contains(key)
+        || key.match_indices('.').any(|(end, _)| {
+            let parent = &key[..end];
+            absent.contains(parent)
+                || shortened.get(parent).is_some_and(|length| {
+                    key[end + 1..]
+                        .split('.')
+                        .next()
+                        .and_then(|index| index.parse::<usize>().ok())
+                        .is_some_and(|index| index >= *length)
+                })
+        })
+}
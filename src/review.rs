@@ -0,0 +1,135 @@
+use crate::model::{LanguageData, Layout, Project};
+use crate::render;
+use crate::sha256;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// Kept in each language folder, next to the translations it covers.
+pub const REVIEW_FILE: &str = ".review-status.json";
+
+pub const APPROVED: &str = "approved";
+
+// The review status file of a language: in its folder, or next to its
+// files as `.de.review-status.json` when languages are files.
+pub fn review_file(project: &Project, lang: &str) -> PathBuf {
+    match project.layout() {
+        Layout::Folders => project.language_dir(lang).join(REVIEW_FILE),
+        Layout::Files => project
+            .language_dir(lang)
+            .join(format!(".{}{}", lang, REVIEW_FILE)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Review {
+    pub status: String,
+    pub reviewer: String,
+    // SHA-256 of the value that was reviewed, so that later edits are
+    // noticed.
+    pub value_hash: String,
+}
+
+// The review status of every key of one language.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReviewStatus {
+    pub reviews: BTreeMap<String, Review>,
+}
+
+pub fn value_hash(value: &str) -> String {
+    sha256::hex_digest(value.as_bytes())
+}
+
+impl Review {
+    // Whether `value` is what the reviewer approved.
+    pub fn approves(&self, value: &str) -> bool {
+        self.status == APPROVED && self.value_hash == value_hash(value)
+    }
+}
+
+impl ReviewStatus {
+    pub fn load(path: &Path) -> Result<Option<ReviewStatus>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read review status {}: {}", path.display(), err))?;
+        let json: Value = serde_json::from_str(&content)
+            .map_err(|err| format!("invalid review status {}: {}", path.display(), err))?;
+        let entries = json
+            .as_object()
+            .ok_or_else(|| format!("invalid review status {}: not an object", path.display()))?;
+
+        let field = |review: &Value, name: &str| {
+            review
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let reviews = entries
+            .iter()
+            .map(|(key, review)| {
+                (
+                    key.clone(),
+                    Review {
+                        status: field(review, "status"),
+                        reviewer: field(review, "reviewer"),
+                        value_hash: field(review, "value_hash"),
+                    },
+                )
+            })
+            .collect();
+        Ok(Some(ReviewStatus { reviews }))
+    }
+
+    // Approves the current value of each key for `reviewer`, returning the
+    // keys the language has no value for.
+    pub fn approve<'a>(
+        &mut self,
+        data: &LanguageData,
+        keys: &'a [String],
+        reviewer: &str,
+    ) -> Vec<&'a str> {
+        let mut missing = Vec::new();
+        for key in keys {
+            let Some(value) = data.value(key) else {
+                missing.push(key.as_str());
+                continue;
+            };
+            self.reviews.insert(
+                key.clone(),
+                Review {
+                    status: APPROVED.to_string(),
+                    reviewer: reviewer.to_string(),
+                    value_hash: value_hash(value),
+                },
+            );
+        }
+        missing
+    }
+
+    // Written atomically with sorted keys and one field per line, so that it
+    // diffs cleanly in git.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let json: Map<String, Value> = self
+            .reviews
+            .iter()
+            .map(|(key, review)| {
+                (
+                    key.clone(),
+                    json!({
+                        "reviewer": review.reviewer,
+                        "status": review.status,
+                        "value_hash": review.value_hash,
+                    }),
+                )
+            })
+            .collect();
+        let content = serde_json::to_string_pretty(&Value::Object(json)).unwrap() + "\n";
+        render::write_atomically(path, |out| out.write_all(content.as_bytes()))
+            .map_err(|err| format!("failed to write review status {}: {}", path.display(), err))
+    }
+}
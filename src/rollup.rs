@@ -0,0 +1,125 @@
+use crate::report::{Check, Finding, Report};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Findings of one check and key in more than this many languages are shown
+// as one row.
+pub const DEFAULT_THRESHOLD: usize = 5;
+
+// The languages a roll-up row names before counting the rest.
+const NAMED_LANGUAGES: usize = 3;
+
+// The languages of the findings sharing a check and a key, and their indexes.
+type Groups<'a> = BTreeMap<(Check, &'a str), (BTreeSet<&'a str>, Vec<usize>)>;
+
+// A base key deleted by mistake is an extra key in every language: one event
+// reported as dozens of rows. Findings sharing a check and a key in more than
+// `threshold` languages are replaced by one finding holding them as its
+// members. It weighs as much as they do, so counts and the exit code don't
+// change, and keeps only what they all have in common. `None` when nothing
+// rolls up, sparing a copy of the report.
+pub fn roll_up(report: &Report, threshold: usize) -> Option<Report> {
+    let mut groups = Groups::new();
+    for (index, finding) in report.findings.iter().enumerate() {
+        if let (Some(lang), Some(key)) = (&finding.lang, &finding.key) {
+            let (langs, indexes) = groups.entry((finding.check, key)).or_default();
+            langs.insert(lang);
+            indexes.push(index);
+        }
+    }
+    let mut rolled: Vec<Option<usize>> = vec![None; report.findings.len()];
+    let mut count = 0;
+    for (langs, indexes) in groups.values() {
+        if langs.len() > threshold {
+            for index in indexes {
+                rolled[*index] = Some(count);
+            }
+            count += 1;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+
+    let mut members: Vec<Vec<Finding>> = vec![Vec::new(); count];
+    let mut findings = Vec::new();
+    for (finding, group) in report.findings.iter().zip(rolled) {
+        match group {
+            Some(group) => members[group].push(finding.clone()),
+            None => findings.push(finding.clone()),
+        }
+    }
+    findings.extend(members.into_iter().map(summarize));
+    let mut rolled = Report {
+        findings,
+        usage_scanned: report.usage_scanned,
+        consistency_checked: report.consistency_checked,
+        source_files: report.source_files,
+        source_roots: report.source_roots.clone(),
+        translation_files: report.translation_files,
+        waived: report.waived,
+        baselined: report.baselined,
+        base_only: report.base_only,
+        config: report.config.clone(),
+        fragment: report.fragment,
+        coverage: report.coverage.clone(),
+        context: report.context.clone(),
+    };
+    rolled.sort();
+    Some(rolled)
+}
+
+fn summarize(members: Vec<Finding>) -> Finding {
+    let first = &members[0];
+    let shared = |get: fn(&Finding) -> &Option<String>| {
+        get(first).clone().filter(|value| {
+            members
+                .iter()
+                .all(|member| get(member).as_ref() == Some(value))
+        })
+    };
+    let langs: BTreeSet<&str> = members
+        .iter()
+        .filter_map(|member| member.lang.as_deref())
+        .collect();
+    let mut named: Vec<String> = langs
+        .iter()
+        .take(NAMED_LANGUAGES)
+        .map(|lang| lang.to_string())
+        .collect();
+    if langs.len() > NAMED_LANGUAGES {
+        named.push(format!("+{} more", langs.len() - NAMED_LANGUAGES));
+    }
+
+    let mut finding = Finding::new(first.check)
+        .severity(members.iter().map(|member| member.severity).max().unwrap())
+        .weight(members.iter().map(|member| member.weight).sum());
+    finding.key = first.key.clone();
+    finding.file = shared(|member| &member.file);
+    if finding.file.is_some()
+        && members
+            .iter()
+            .all(|member| (member.line, member.column) == (first.line, first.column))
+    {
+        finding.line = first.line;
+        finding.column = first.column;
+    }
+    finding.message = shared(|member| &member.message);
+    finding.details = first
+        .details
+        .iter()
+        .filter(|detail| members.iter().all(|member| member.details.contains(detail)))
+        .cloned()
+        .collect();
+    finding = finding.detail(format!("In {} languages", langs.len()), named.join(", "));
+    finding.values = first
+        .values
+        .iter()
+        .filter(|excerpt| members.iter().all(|member| member.values.contains(excerpt)))
+        .cloned()
+        .collect();
+    if members.iter().all(|member| member.owners == first.owners) {
+        finding.owners = first.owners.clone();
+    }
+    finding.members = members;
+    finding
+}
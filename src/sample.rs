@@ -0,0 +1,189 @@
+use crate::base::Base;
+use crate::model::Project;
+use crate::report::Finding;
+use crate::review::ReviewStatus;
+use crate::sha256;
+use crate::state::State;
+use crate::worklist::csv_field;
+use std::cmp::Ordering;
+
+// How much likelier a key added since the state file is to be drawn.
+pub const RECENT_WEIGHT: f64 = 4.0;
+
+// One key drawn for review, with what the reviewer needs to judge it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub key: String,
+    pub file: String,
+    pub base_value: String,
+    pub value: String,
+    // Added to the base since the state file was last updated.
+    pub recent: bool,
+    // The findings of the key in the language, as `check (details)`.
+    pub findings: Vec<String>,
+}
+
+pub struct Sample {
+    pub lang: String,
+    pub seed: String,
+    // How many keys could have been drawn.
+    pub population: usize,
+    // Sorted by file and key.
+    pub items: Vec<Item>,
+}
+
+// Where a key falls for a seed, uniform in [0, 1): the same key always
+// draws the same number for the same seed, whatever else the language has.
+fn draw(seed: &str, key: &str) -> f64 {
+    let digest = sha256::hex_digest(format!("{}\n{}", seed, key).as_bytes());
+    let bits = u64::from_str_radix(&digest[..16], 16).unwrap();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+impl Sample {
+    // Draws `count` keys the language translates, without replacement.
+    // Each key gets `draw^(1 / weight)` and the highest ones are kept, so
+    // keys added since `state` are picked more often; keys whose current
+    // value `reviews` approves are skipped when given.
+    pub fn new(
+        project: &Project,
+        base: &Base,
+        lang: &str,
+        count: usize,
+        seed: &str,
+        state: Option<&State>,
+        reviews: Option<&ReviewStatus>,
+    ) -> Sample {
+        let data = project.get(lang);
+        let mut candidates: Vec<(f64, Item)> = base
+            .entries()
+            .filter_map(|(key, base_entry)| {
+                let entry = data?.values.get(key)?;
+                if reviews
+                    .and_then(|reviews| reviews.reviews.get(key))
+                    .is_some_and(|review| review.approves(&entry.value))
+                {
+                    return None;
+                }
+                let recent = state.is_some_and(|state| !state.base_keys.contains(key));
+                let weight = if recent { RECENT_WEIGHT } else { 1.0 };
+                let item = Item {
+                    key: key.to_string(),
+                    file: entry.file.to_string(),
+                    base_value: base_entry.value.clone(),
+                    value: entry.value.clone(),
+                    recent,
+                    findings: Vec::new(),
+                };
+                Some((draw(seed, key).powf(1.0 / weight), item))
+            })
+            .collect();
+        let population = candidates.len();
+        candidates.sort_by(|(a, a_item), (b, b_item)| {
+            b.partial_cmp(a)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a_item.key.cmp(&b_item.key))
+        });
+        candidates.truncate(count);
+        let mut items: Vec<Item> = candidates.into_iter().map(|(_, item)| item).collect();
+        items.sort_by(|a, b| (&a.file, &a.key).cmp(&(&b.file, &b.key)));
+
+        Sample {
+            lang: lang.to_string(),
+            seed: seed.to_string(),
+            population,
+            items,
+        }
+    }
+
+    // Lists the findings of the run under the drawn keys of the language.
+    pub fn with_findings(mut self, findings: &[Finding]) -> Sample {
+        for finding in findings {
+            if finding.lang.as_deref() != Some(self.lang.as_str()) {
+                continue;
+            }
+            let Some(key) = &finding.key else {
+                continue;
+            };
+            if let Some(item) = self.items.iter_mut().find(|item| item.key == *key) {
+                item.findings.push(describe(finding));
+            }
+        }
+        self
+    }
+
+    // One checklist item per key, base and translated values quoted under
+    // it, for the review sheet.
+    pub fn to_markdown(&self) -> String {
+        let mut text = format!("# Review sample: {}\n\n", self.lang.to_uppercase());
+        text.push_str(&format!(
+            "{} of {} keys, seed `{}`.\n",
+            self.items.len(),
+            self.population,
+            self.seed
+        ));
+        if !self.items.is_empty() {
+            text.push('\n');
+        }
+        for item in &self.items {
+            let recent = if item.recent { " (new)" } else { "" };
+            text.push_str(&format!(
+                "- [ ] `{}` in `{}`{}\n",
+                item.key, item.file, recent
+            ));
+            for (label, value) in [("Base", &item.base_value), ("Translation", &item.value)] {
+                text.push_str(&format!("  {}:\n", label));
+                for line in value.lines() {
+                    text.push_str(&format!("  > {}\n", line));
+                }
+            }
+            for finding in &item.findings {
+                text.push_str(&format!("  Finding: {}\n", finding));
+            }
+        }
+        text
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut text = String::from("key,file,base_value,value,recent,findings\n");
+        for item in &self.items {
+            let findings = item.findings.join("; ");
+            let fields = [
+                item.key.as_str(),
+                &item.file,
+                &item.base_value,
+                &item.value,
+                if item.recent { "true" } else { "false" },
+                &findings,
+            ];
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            text.push_str(&fields.join(","));
+            text.push('\n');
+        }
+        text
+    }
+
+    // The drawn keys, one per line as `review approve --keys-from` reads
+    // them, to record the session once reviewed.
+    pub fn to_keys(&self) -> String {
+        let mut text = format!("# Review sample of {}, seed {}\n", self.lang, self.seed);
+        for item in &self.items {
+            text.push_str(&item.key);
+            text.push('\n');
+        }
+        text
+    }
+}
+
+fn describe(finding: &Finding) -> String {
+    let details: Vec<String> = finding
+        .details
+        .iter()
+        .map(|(label, value)| format!("{}: {}", label, value))
+        .collect();
+    if details.is_empty() {
+        finding.check.info().id.to_string()
+    } else {
+        format!("{} ({})", finding.check.info().id, details.join(", "))
+    }
+}
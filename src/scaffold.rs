@@ -0,0 +1,113 @@
+use crate::model::{Layout, Project};
+use crate::review::value_hash;
+use crate::waivers;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Kept in each language folder, next to the translations scaffolded in it.
+pub const SCAFFOLD_FILE: &str = ".scaffolds.meta.json";
+
+// The scaffold provenance file of a language: in its folder, or next to
+// its files as `.de.scaffolds.meta.json` when languages are files.
+pub fn scaffold_file(project: &Project, lang: &str) -> PathBuf {
+    match project.layout() {
+        Layout::Folders => project.language_dir(lang).join(SCAFFOLD_FILE),
+        Layout::Files => project
+            .language_dir(lang)
+            .join(format!(".{}{}", lang, SCAFFOLD_FILE)),
+    }
+}
+
+// Where a value `--fix sync-keys` added comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub generated_at: String,
+    pub tool_version: String,
+    // SHA-256 of the base value the scaffold was made from, so that later
+    // edits of the base are noticed.
+    pub base_hash: String,
+    // SHA-256 of the scaffold itself, which a translation replaces.
+    pub value_hash: String,
+}
+
+impl Provenance {
+    pub fn new(base_value: &str, value: &str) -> Provenance {
+        Provenance {
+            generated_at: waivers::timestamp(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            base_hash: value_hash(base_value),
+            value_hash: value_hash(value),
+        }
+    }
+
+    // Whether `value` is still the scaffold, untranslated.
+    pub fn scaffolds(&self, value: &str) -> bool {
+        self.value_hash == value_hash(value)
+    }
+}
+
+// The provenance of the scaffolded keys of one language.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Scaffolds {
+    pub records: BTreeMap<String, Provenance>,
+}
+
+impl Scaffolds {
+    pub fn load(path: &Path) -> Result<Option<Scaffolds>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read scaffolds {}: {}", path.display(), err))?;
+        let json: Value = serde_json::from_str(&content)
+            .map_err(|err| format!("invalid scaffolds {}: {}", path.display(), err))?;
+        let entries = json
+            .as_object()
+            .ok_or_else(|| format!("invalid scaffolds {}: not an object", path.display()))?;
+
+        let field = |record: &Value, name: &str| {
+            record
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let records = entries
+            .iter()
+            .map(|(key, record)| {
+                (
+                    key.clone(),
+                    Provenance {
+                        generated_at: field(record, "generated_at"),
+                        tool_version: field(record, "tool_version"),
+                        base_hash: field(record, "base_hash"),
+                        value_hash: field(record, "value_hash"),
+                    },
+                )
+            })
+            .collect();
+        Ok(Some(Scaffolds { records }))
+    }
+
+    // Sorted keys and one field per line, so that it diffs cleanly in git.
+    pub fn to_json(&self) -> String {
+        let json: Map<String, Value> = self
+            .records
+            .iter()
+            .map(|(key, record)| {
+                (
+                    key.clone(),
+                    json!({
+                        "base_hash": record.base_hash,
+                        "generated_at": record.generated_at,
+                        "tool_version": record.tool_version,
+                        "value_hash": record.value_hash,
+                    }),
+                )
+            })
+            .collect();
+        serde_json::to_string_pretty(&Value::Object(json)).unwrap() + "\n"
+    }
+}
@@ -0,0 +1,93 @@
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// A snapshot of the base language: every key with the placeholders its value
+// had when the snapshot was written. Committed snapshots track base changes;
+// published ones can stand in for the base language altogether.
+pub struct Schema {
+    pub base_lang: Option<String>,
+    pub placeholders: HashMap<String, HashSet<String>>,
+}
+
+impl Schema {
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read schema {}: {}", path.display(), err))?;
+        Schema::parse(&content, &path.display().to_string())
+    }
+
+    // `source` names where the content came from in error messages.
+    pub fn parse(content: &str, source: &str) -> Result<Schema, String> {
+        let json: Value = serde_json::from_str(content)
+            .map_err(|err| format!("invalid schema {}: {}", source, err))?;
+
+        let keys = json
+            .get("keys")
+            .and_then(Value::as_object)
+            .ok_or_else(|| format!("schema {} has no `keys` object", source))?;
+
+        let placeholders = keys
+            .iter()
+            .map(|(key, vars)| {
+                let vars = vars
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect();
+                (key.clone(), vars)
+            })
+            .collect();
+
+        Ok(Schema {
+            base_lang: json
+                .get("base_lang")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            placeholders,
+        })
+    }
+
+    pub fn write(
+        path: &Path,
+        base_lang: &str,
+        placeholders: &HashMap<String, HashSet<String>>,
+    ) -> Result<(), String> {
+        let keys: BTreeMap<_, _> = placeholders
+            .iter()
+            .map(|(key, vars)| {
+                let vars: BTreeSet<_> = vars.iter().collect();
+                (
+                    key.clone(),
+                    Value::from(vars.into_iter().cloned().collect::<Vec<_>>()),
+                )
+            })
+            .collect();
+
+        let mut json = Map::new();
+        json.insert("base_lang".to_string(), Value::from(base_lang));
+        json.insert(
+            "keys".to_string(),
+            Value::Object(keys.into_iter().collect()),
+        );
+
+        let content = serde_json::to_string_pretty(&Value::Object(json)).unwrap() + "\n";
+        fs::write(path, content)
+            .map_err(|err| format!("failed to write schema {}: {}", path.display(), err))
+    }
+
+    // The snapshot placeholders of `key` when they differ from the current
+    // base value's placeholders.
+    pub fn changed_placeholders(
+        &self,
+        key: &str,
+        base_vars: &HashSet<String>,
+    ) -> Option<&HashSet<String>> {
+        self.placeholders
+            .get(key)
+            .filter(|snapshot| *snapshot != base_vars)
+    }
+}
@@ -0,0 +1,294 @@
+use crate::loader::{FileFormat, Flattened, flatten_json, parse_document};
+use crate::model::Layout;
+use crate::module;
+use crate::report::Finding;
+use crate::usage::SourceRoot;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+// What a run limited to the files changed since the last check looks at.
+// A change to the base language or the settings can affect every finding,
+// so the whole project is checked then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    Full(String),
+    Changed {
+        // The translated languages whose files changed; the others aren't
+        // compared.
+        languages: BTreeSet<String>,
+        // The changed source files that still exist. Unused keys can't be
+        // told from a part of the sources, so they aren't looked for.
+        sources: Vec<PathBuf>,
+    },
+}
+
+// Where a project keeps what decides the scope of a change.
+pub struct Paths<'a> {
+    pub root: &'a Path,
+    pub i18n_dir: &'a Path,
+    pub layout: Layout,
+    pub base_lang: &'a str,
+    pub sources: &'a [SourceRoot],
+    // Files changing what is checked, such as `translation-check.toml`.
+    pub settings: Vec<PathBuf>,
+}
+
+impl Scope {
+    pub fn of(changed: &[PathBuf], project: &Paths) -> Scope {
+        let i18n_dir = relative(project.i18n_dir, project.root);
+        let roots: Vec<SourceRoot> = project
+            .sources
+            .iter()
+            .map(|root| SourceRoot {
+                dir: relative(&root.dir, project.root),
+                extensions: root.extensions.clone(),
+            })
+            .collect();
+        let settings: Vec<PathBuf> = project
+            .settings
+            .iter()
+            .map(|path| relative(path, project.root))
+            .collect();
+        let mut languages = BTreeSet::new();
+        let mut sources = Vec::new();
+        for path in changed.iter().map(|path| relative(path, project.root)) {
+            if settings.contains(&path) {
+                return Scope::Full(format!("{} changed", path.display()));
+            }
+            if let Ok(inside) = path.strip_prefix(&i18n_dir) {
+                match language_of(inside, project.layout) {
+                    Some(lang) if lang == project.base_lang => {
+                        return Scope::Full("the base language changed".to_string());
+                    }
+                    Some(lang) if !lang.starts_with('.') => {
+                        languages.insert(lang.to_string());
+                    }
+                    _ => {}
+                }
+            } else if let Some(at) = roots.iter().position(|root| root.contains(&path))
+                && project.root.join(&path).is_file()
+            {
+                let inside = path.strip_prefix(&roots[at].dir).unwrap();
+                sources.push(project.sources[at].dir.join(inside));
+            }
+        }
+        sources.sort();
+        sources.dedup();
+        Scope::Changed { languages, sources }
+    }
+
+    pub fn is_full(&self) -> bool {
+        matches!(self, Scope::Full(_))
+    }
+
+    // Whether the run compares `lang`; the base language always is.
+    pub fn compares(&self, lang: &str) -> bool {
+        match self {
+            Scope::Full(_) => true,
+            Scope::Changed { languages, .. } => languages.contains(lang),
+        }
+    }
+
+    // What the run checks, printed so that nobody takes a limited run for
+    // a full one.
+    pub fn describe(&self) -> String {
+        match self {
+            Scope::Full(reason) => format!("Checking everything: {}", reason),
+            Scope::Changed { languages, sources } => {
+                let languages = match languages.len() {
+                    0 => "no languages".to_string(),
+                    _ => languages.iter().cloned().collect::<Vec<_>>().join(", "),
+                };
+                format!(
+                    "Checking changed files only: {} and {} source files; other languages and unused keys were not checked",
+                    languages,
+                    sources.len()
+                )
+            }
+        }
+    }
+}
+
+// The language of a file of the translation directory, from its folder or
+// its name after the layout.
+fn language_of(inside: &Path, layout: Layout) -> Option<&str> {
+    match layout {
+        Layout::Folders if inside.components().count() > 1 => inside
+            .components()
+            .next()
+            .and_then(|folder| folder.as_os_str().to_str()),
+        Layout::Folders => None,
+        Layout::Files => inside.to_str().and_then(|name| name.split('.').next()),
+    }
+}
+
+// What a branch changed since a ref, for `--changed-since`: the keys its
+// translation files add, remove or give another value, in any language,
+// and the other files it changed. A run keeps the findings about these
+// keys, and those of the changed files that aren't about a key.
+#[derive(Debug, Default)]
+pub struct Touched {
+    pub keys: BTreeSet<String>,
+    pub translations: BTreeSet<PathBuf>,
+    pub files: BTreeSet<PathBuf>,
+    root: PathBuf,
+}
+
+impl Touched {
+    // Compares each changed translation file with its version at `git_ref`,
+    // read with `git show`; a file missing on either side adds or removes
+    // every key it has.
+    pub fn since(git_ref: &str, changed: &[PathBuf], project: &Paths) -> Result<Touched, String> {
+        let i18n_dir = relative(project.i18n_dir, project.root);
+        let mut touched = Touched {
+            root: project.root.to_path_buf(),
+            ..Touched::default()
+        };
+        for path in changed.iter().map(|path| relative(path, project.root)) {
+            let name = path.to_string_lossy().to_string();
+            let translation = FileFormat::of(&name).is_some()
+                || path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| module::EXTENSIONS.contains(&extension));
+            let Some(inside) = path.strip_prefix(&i18n_dir).ok().filter(|_| translation) else {
+                touched.files.insert(path);
+                continue;
+            };
+            let lang = language_of(inside, project.layout).unwrap_or_default();
+            let before = Command::new("git")
+                .arg("show")
+                .arg(format!("{}:./{}", git_ref, name))
+                .current_dir(project.root)
+                .output()
+                .map_err(|err| format!("failed to run git: {}", err))?;
+            let before = match before.status.success() {
+                true => leaves(&path, &String::from_utf8_lossy(&before.stdout), lang),
+                false => BTreeMap::new(),
+            };
+            let after = match fs::read_to_string(project.root.join(&path)) {
+                Ok(content) => leaves(&path, &content, lang),
+                Err(_) => BTreeMap::new(),
+            };
+            for (key, value) in &after {
+                if before.get(key) != Some(value) {
+                    touched.keys.insert(key.clone());
+                }
+            }
+            touched
+                .keys
+                .extend(before.into_keys().filter(|key| !after.contains_key(key)));
+            touched.translations.insert(path);
+        }
+        Ok(touched)
+    }
+
+    // Whether a finding is about a touched key, one of its parents or
+    // children counted, or about a changed file without being about a key.
+    pub fn contains(&self, finding: &Finding) -> bool {
+        let file = finding
+            .file
+            .as_ref()
+            .map(|file| relative(Path::new(file), &self.root));
+        match &finding.key {
+            Some(key) => {
+                self.keys.iter().any(|touched| {
+                    key == touched
+                        || key
+                            .strip_prefix(touched.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                        || touched
+                            .strip_prefix(key.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                }) || file.is_some_and(|file| self.files.contains(&file))
+            }
+            None => file.is_some_and(|file| {
+                self.files.contains(&file) || self.translations.contains(&file)
+            }),
+        }
+    }
+
+    pub fn describe(&self, git_ref: &str) -> String {
+        format!(
+            "Reporting the changes since {} only: {} keys of {} translation files and {} other files",
+            git_ref,
+            self.keys.len(),
+            self.translations.len(),
+            self.files.len()
+        )
+    }
+}
+
+// The leaves of a translation file by key, none for a file that can't be
+// parsed.
+fn leaves(path: &Path, content: &str, lang: &str) -> BTreeMap<String, Value> {
+    let Ok(document) = parse_document(path, content, lang) else {
+        return BTreeMap::new();
+    };
+    let flattened = Flattened::default();
+    flatten_json(&document.value, String::new(), &flattened);
+    flattened.leaves.into_iter().collect()
+}
+
+// `path` relative to `root`, without `.` components, as git lists files.
+fn relative(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+// One path per line, from a file or stdin for `-`. Blank lines are skipped.
+pub fn read_changed_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    let list = if path == Path::new("-") {
+        let mut list = String::new();
+        io::stdin()
+            .read_to_string(&mut list)
+            .map_err(|err| format!("failed to read the changed files from stdin: {}", err))?;
+        list
+    } else {
+        fs::read_to_string(path).map_err(|err| {
+            format!(
+                "failed to read the changed files {}: {}",
+                path.display(),
+                err
+            )
+        })?
+    };
+    Ok(list
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+// The files changed since `git_ref`, committed or not, and the new files
+// git doesn't ignore, relative to the working directory.
+pub fn changed_since(git_ref: &str) -> Result<Vec<PathBuf>, String> {
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .output()
+            .map_err(|err| format!("failed to run git: {}", err))?;
+        if !output.status.success() {
+            return Err(format!(
+                "`git {}` failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(PathBuf::from)
+            .collect::<Vec<_>>())
+    };
+    let mut changed = git(&["diff", "--name-only", "--relative", git_ref, "--"])?;
+    changed.extend(git(&["ls-files", "--others", "--exclude-standard"])?);
+    Ok(changed)
+}
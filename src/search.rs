@@ -0,0 +1,354 @@
+use crate::model::LanguageData;
+use crate::report::Excerpt;
+use colored::*;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+// How much of a value a text match shows, as the report quotes values.
+const MAX_VALUE_CHARS: usize = 80;
+
+// What `search` looks through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Field {
+    Key,
+    Value,
+}
+
+impl Field {
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::Key => "key",
+            Field::Value => "value",
+        }
+    }
+}
+
+// How a match was found, from the closest to the loosest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    // The whole text, ignoring case.
+    Exact,
+    // A substring starting a word, like `address` in `shipping_address`.
+    Word,
+    Substring,
+    // A substring a few edits away, with `--fuzzy`.
+    Edits,
+    // The characters of the query in order, with gaps, with `--fuzzy`.
+    Subsequence,
+}
+
+impl MatchKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            MatchKind::Exact => "exact",
+            MatchKind::Word => "word",
+            MatchKind::Substring => "substring",
+            MatchKind::Edits => "edits",
+            MatchKind::Subsequence => "subsequence",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub key: String,
+    pub value: String,
+    pub file: String,
+    pub field: Field,
+    pub kind: MatchKind,
+    // The edits of an `Edits` match, the characters skipped by a
+    // `Subsequence` one, zero for the others.
+    pub distance: usize,
+    // Byte ranges of the key or value, as `field` says.
+    pub highlights: Vec<Range<usize>>,
+}
+
+// Lowercased characters, with the bytes of the text each comes from.
+struct Folded {
+    chars: Vec<char>,
+    spans: Vec<Range<usize>>,
+}
+
+impl Folded {
+    fn new(text: &str) -> Folded {
+        let mut folded = Folded {
+            chars: Vec::new(),
+            spans: Vec::new(),
+        };
+        for (start, c) in text.char_indices() {
+            for lower in c.to_lowercase() {
+                folded.chars.push(lower);
+                folded.spans.push(start..start + c.len_utf8());
+            }
+        }
+        folded
+    }
+
+    // The bytes of the text behind the characters `range`.
+    fn bytes(&self, range: Range<usize>) -> Range<usize> {
+        self.spans[range.start].start..self.spans[range.end - 1].end
+    }
+}
+
+struct Entry<'d> {
+    key: &'d str,
+    value: &'d str,
+    file: &'d str,
+    folded_key: Folded,
+    folded_value: Folded,
+}
+
+// The keys and values of one language, folded for matching the first time
+// they are searched. Values come from the loaded language; nothing is read
+// again.
+pub struct Index<'d> {
+    data: &'d LanguageData,
+    entries: OnceLock<Vec<Entry<'d>>>,
+}
+
+impl<'d> Index<'d> {
+    pub fn new(data: &'d LanguageData) -> Index<'d> {
+        Index {
+            data,
+            entries: OnceLock::new(),
+        }
+    }
+
+    fn entries(&self) -> &[Entry<'d>] {
+        self.entries.get_or_init(|| {
+            self.data
+                .values
+                .iter()
+                .map(|(key, entry)| Entry {
+                    key,
+                    value: &entry.value,
+                    file: &entry.file,
+                    folded_key: Folded::new(key),
+                    folded_value: Folded::new(&entry.value),
+                })
+                .collect()
+        })
+    }
+
+    // Every key matching `query` in one of `fields`, best first: closest
+    // kind, fewest edits, keys before values, then shortest text and key
+    // name. A key matching in both fields is listed once, for its best.
+    pub fn search(&self, query: &str, fields: &[Field], fuzzy: bool) -> Vec<Match> {
+        let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<Match> = self
+            .entries()
+            .iter()
+            .filter_map(|entry| {
+                fields
+                    .iter()
+                    .filter_map(|field| {
+                        let folded = match field {
+                            Field::Key => &entry.folded_key,
+                            Field::Value => &entry.folded_value,
+                        };
+                        let (kind, distance, ranges) = find(&query, &folded.chars, fuzzy)?;
+                        Some(Match {
+                            key: entry.key.to_string(),
+                            value: entry.value.to_string(),
+                            file: entry.file.to_string(),
+                            field: *field,
+                            kind,
+                            distance,
+                            highlights: ranges
+                                .into_iter()
+                                .map(|range| folded.bytes(range))
+                                .collect(),
+                        })
+                    })
+                    .min_by_key(|found| (found.kind, found.distance, found.field))
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            (a.kind, a.distance, a.field, a.text().len(), &a.key).cmp(&(
+                b.kind,
+                b.distance,
+                b.field,
+                b.text().len(),
+                &b.key,
+            ))
+        });
+        matches
+    }
+}
+
+impl Match {
+    // The key or the value, where the query was found.
+    pub fn text(&self) -> &str {
+        match self.field {
+            Field::Key => &self.key,
+            Field::Value => &self.value,
+        }
+    }
+}
+
+// Where `query` is in `text`, as character ranges of `text`.
+fn find(
+    query: &[char],
+    text: &[char],
+    fuzzy: bool,
+) -> Option<(MatchKind, usize, Vec<Range<usize>>)> {
+    let substring = (0..=text.len().saturating_sub(query.len()))
+        .filter(|&start| text[start..].starts_with(query))
+        .map(|start| {
+            let kind = if query.len() == text.len() {
+                MatchKind::Exact
+            } else if start == 0 || !text[start - 1].is_alphanumeric() {
+                MatchKind::Word
+            } else {
+                MatchKind::Substring
+            };
+            (kind, start)
+        })
+        .min();
+    if let Some((kind, start)) = substring {
+        let range = start..start + query.len();
+        return Some((kind, 0, vec![range]));
+    }
+    if !fuzzy {
+        return None;
+    }
+    if let Some((distance, range)) = edits(query, text) {
+        return Some((MatchKind::Edits, distance, vec![range]));
+    }
+    subsequence(query, text).map(|(skipped, ranges)| (MatchKind::Subsequence, skipped, ranges))
+}
+
+// The substring of `text` the fewest edits away from `query`, when there
+// are at most one per four characters of the query. Queries shorter than
+// four characters only match as written.
+fn edits(query: &[char], text: &[char]) -> Option<(usize, Range<usize>)> {
+    let bound = query.len() / 4;
+    if bound == 0 {
+        return None;
+    }
+    // The edits between the first `i` characters of the query and a
+    // substring of `text` ending at `j`, with where that substring starts.
+    let mut row: Vec<(usize, usize)> = (0..=text.len()).map(|j| (0, j)).collect();
+    for (i, q) in query.iter().enumerate() {
+        let mut next = vec![(i + 1, 0); text.len() + 1];
+        for (j, t) in text.iter().enumerate() {
+            let replace = (row[j].0 + usize::from(q != t), row[j].1);
+            let skip_query = (row[j + 1].0 + 1, row[j + 1].1);
+            let skip_text = (next[j].0 + 1, next[j].1);
+            next[j + 1] = replace.min(skip_query).min(skip_text);
+        }
+        row = next;
+    }
+    let (end, (distance, start)) = row
+        .iter()
+        .copied()
+        .enumerate()
+        .min_by_key(|(end, (distance, start))| (*distance, end - start))?;
+    (distance <= bound && end > start).then_some((distance, start..end))
+}
+
+// The characters of `query` but spaces, leftmost in order, merged into
+// ranges, with how many characters of `text` they skip.
+fn subsequence(query: &[char], text: &[char]) -> Option<(usize, Vec<Range<usize>>)> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut next = 0;
+    for q in query.iter().filter(|q| !q.is_whitespace()) {
+        let found = next + text[next..].iter().position(|t| t == q)?;
+        match ranges.last_mut() {
+            Some(last) if last.end == found => last.end += 1,
+            _ => ranges.push(found..found + 1),
+        }
+        next = found + 1;
+    }
+    let first = ranges.first()?.start;
+    let matched: usize = ranges.iter().map(|range| range.len()).sum();
+    Some((next - first - matched, ranges))
+}
+
+// Text with its ranges in bold yellow.
+fn paint(text: &str, highlights: &[Range<usize>]) -> String {
+    let mut painted = String::new();
+    let mut pos = 0;
+    for range in highlights {
+        painted.push_str(&text[pos..range.start]);
+        painted.push_str(&text[range.clone()].yellow().bold().to_string());
+        pos = range.end;
+    }
+    painted.push_str(&text[pos..]);
+    painted
+}
+
+// One line per match: key, language, value and file, the matched text
+// highlighted and long values cut.
+pub fn write_text(out: &mut dyn Write, lang: &str, matches: &[Match]) -> io::Result<()> {
+    let width = matches
+        .iter()
+        .map(|found| found.key.chars().count())
+        .max()
+        .unwrap_or(0);
+    for found in matches {
+        let key = match found.field {
+            Field::Key => paint(&found.key, &found.highlights),
+            Field::Value => found.key.clone(),
+        };
+        let padding = " ".repeat(width - found.key.chars().count());
+        let highlights = match found.field {
+            Field::Key => Vec::new(),
+            Field::Value => found.highlights.clone(),
+        };
+        let excerpt = Excerpt::new("", &found.value, highlights, MAX_VALUE_CHARS);
+        let ellipsis = if excerpt.truncated { "…" } else { "" };
+        writeln!(
+            out,
+            "{}{} │ {} │ {}{} │ {}",
+            key,
+            padding,
+            lang,
+            paint(&excerpt.text, &excerpt.highlights),
+            ellipsis,
+            found.file
+        )?;
+    }
+    Ok(())
+}
+
+pub fn to_json(
+    query: &str,
+    lang: &str,
+    fields: &[Field],
+    total: usize,
+    matches: &[Match],
+) -> Value {
+    let matches: Vec<Value> = matches
+        .iter()
+        .map(|found| {
+            let highlights: Vec<Value> = found
+                .highlights
+                .iter()
+                .map(|range| json!({ "start": range.start, "end": range.end }))
+                .collect();
+            json!({
+                "key": found.key,
+                "lang": lang,
+                "value": found.value,
+                "file": found.file,
+                "field": found.field.name(),
+                "kind": found.kind.name(),
+                "distance": found.distance,
+                "highlights": highlights,
+            })
+        })
+        .collect();
+    json!({
+        "query": query,
+        "lang": lang,
+        "in": fields.iter().map(|field| format!("{}s", field.name())).collect::<Vec<_>>(),
+        "total": total,
+        "matches": matches,
+    })
+}
@@ -0,0 +1,129 @@
+use regex::Regex;
+
+// A key glob as written in the configuration: `*` matches within one key
+// segment and `**` matches across segments. `\*` and `\\` stand for a literal
+// asterisk and backslash; everything else is literal.
+#[derive(Debug, Clone)]
+pub struct KeyPattern {
+    source: String,
+    parts: Vec<Part>,
+    regex: Regex,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Literal(String),
+    Segment,
+    AnySegments,
+}
+
+impl KeyPattern {
+    pub fn new(source: &str) -> Result<KeyPattern, String> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if matches!(chars.peek(), Some('*' | '\\')) => {
+                    literal.push(chars.next().unwrap())
+                }
+                '*' => {
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(if chars.next_if_eq(&'*').is_some() {
+                        Part::AnySegments
+                    } else {
+                        Part::Segment
+                    });
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        let mut pattern = String::from("^");
+        for part in &parts {
+            match part {
+                Part::Literal(text) => pattern.push_str(&regex::escape(text)),
+                Part::Segment => pattern.push_str("([^.]*)"),
+                Part::AnySegments => pattern.push_str("(.*)"),
+            }
+        }
+        pattern.push('$');
+
+        let regex = Regex::new(&pattern)
+            .map_err(|err| format!("invalid key pattern `{}`: {}", source, err))?;
+        Ok(KeyPattern {
+            source: source.to_string(),
+            parts,
+            regex,
+        })
+    }
+
+    // The pattern matching exactly `key`, its asterisks and backslashes
+    // escaped.
+    pub fn literal(key: &str) -> KeyPattern {
+        let escaped = key.replace('\\', "\\\\").replace('*', "\\*");
+        KeyPattern::new(&escaped).expect("escaped key pattern")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn wildcards(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| !matches!(part, Part::Literal(_)))
+            .count()
+    }
+
+    // The literal text every matching key starts with.
+    pub fn prefix(&self) -> &str {
+        match self.parts.first() {
+            Some(Part::Literal(text)) => text,
+            _ => "",
+        }
+    }
+
+    // What each wildcard matched, in order.
+    pub fn captures(&self, key: &str) -> Option<Vec<String>> {
+        let caps = self.regex.captures(key)?;
+        Some(
+            caps.iter()
+                .skip(1)
+                .map(|cap| cap.map_or(String::new(), |m| m.as_str().to_string()))
+                .collect(),
+        )
+    }
+
+    pub fn matches(&self, key: &str) -> bool {
+        self.regex.is_match(key)
+    }
+
+    // Whether the pattern matches `key` or one of its parents, so that
+    // `legal` stands for the whole `legal.*` subtree.
+    pub fn covers(&self, key: &str) -> bool {
+        key.match_indices('.')
+            .map(|(index, _)| &key[..index])
+            .chain([key])
+            .any(|prefix| self.regex.is_match(prefix))
+    }
+
+    // The key this pattern stands for once its wildcards are replaced by
+    // `captures`.
+    pub fn expand(&self, captures: &[String]) -> String {
+        let mut captures = captures.iter();
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => text.as_str(),
+                Part::Segment => captures.next().map_or("*", String::as_str),
+                Part::AnySegments => captures.next().map_or("**", String::as_str),
+            })
+            .collect()
+    }
+}
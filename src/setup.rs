@@ -0,0 +1,147 @@
+use crate::checks;
+use crate::cli::{BaseLangOrigin, Options, Target};
+use crate::config::Config;
+use crate::detect::{self, Candidate, Detection};
+use crate::model::{self, Layout, Project};
+use crate::timings::Timings;
+use std::fs;
+use std::path::Path;
+
+// What every command reading the translations starts from: the
+// configuration file, and the options of the check run with its
+// `[project]` defaults and base language applied.
+pub struct Setup {
+    pub options: Options,
+    pub config: Config,
+}
+
+impl Setup {
+    // The setup a subcommand reads `target` with, as the check run would.
+    pub fn load(target: &Target) -> Result<Setup, String> {
+        let config = Config::discover(target.config.as_deref())?;
+        let mut options = target.options();
+        configure(&mut options, &config)?;
+        check_base_language(&options.i18n_dir, &options.base_lang)?;
+        Ok(Setup { options, config })
+    }
+
+    pub fn i18n_dir(&self) -> &Path {
+        &self.options.i18n_dir
+    }
+
+    pub fn base_lang(&self) -> &str {
+        &self.options.base_lang
+    }
+
+    pub fn project<'a>(&self, timings: &'a Timings) -> Project<'a> {
+        discover(&self.options, &self.config, timings)
+    }
+}
+
+// Applies the `[project]` defaults of `config` not overridden by a flag,
+// the placeholder syntaxes, and resolves the base language.
+pub fn configure(options: &mut Options, config: &Config) -> Result<(), String> {
+    options.apply_project(&config.project)?;
+    checks::set_placeholder_syntaxes(&options.placeholder_styles, &options.placeholder_patterns);
+    resolve_base_lang(options, config)
+}
+
+// The project with the file names, aliases, folders, exclusions and
+// unreadable-file limit of `config`.
+pub fn discover<'a>(options: &Options, config: &Config, timings: &'a Timings) -> Project<'a> {
+    Project::discover(
+        &options.i18n_dir,
+        &options.base_lang,
+        options.low_memory,
+        timings,
+    )
+    .with_max_unreadable(config.unreadable_files.max_fraction)
+    .with_file_name_rules(&config.file_name_rules)
+    .with_aliases(&config.language_aliases)
+    .with_non_language_dirs(&config.non_language_dirs)
+    .with_exclusions(&config.exclude.translations, &config.exclude.keys)
+}
+
+// Without its base language folder every key would be reported missing, so
+// the run stops and lists the folders found, which usually shows the typo.
+pub fn check_base_language(i18n_dir: &Path, base_lang: &str) -> Result<(), String> {
+    let layout = model::layout(i18n_dir)?;
+    // Every key would be compared with an empty base.
+    let folder = i18n_dir.join(base_lang);
+    if layout == Layout::Folders
+        && folder.is_dir()
+        && let Err(err) = fs::read_dir(&folder)
+    {
+        return Err(format!(
+            "failed to read the base language folder {}: {}",
+            folder.display(),
+            err
+        ));
+    }
+    let languages = model::language_names(i18n_dir, layout);
+    if languages.iter().any(|lang| lang == base_lang)
+        || i18n_dir.join(base_lang).is_dir()
+        || model::base_module(i18n_dir, base_lang).is_some()
+    {
+        return Ok(());
+    }
+    let (what, none) = match layout {
+        Layout::Folders => ("folder", "no language folders"),
+        Layout::Files => ("file", "no language files"),
+    };
+    Err(format!(
+        "no `{}` base language {} in {} (found: {})",
+        base_lang,
+        what,
+        i18n_dir.display(),
+        if languages.is_empty() {
+            none.to_string()
+        } else {
+            languages.join(", ")
+        }
+    ))
+}
+
+// Without `--base-lang`, the base is `languages.base` from the
+// configuration, else `fr` when the project has it, else the language the
+// others look translated from. A close call is left to the user, and so is
+// a `fr` another language looks more like the base than.
+fn resolve_base_lang(options: &mut Options, config: &Config) -> Result<(), String> {
+    if options.base_lang_origin != BaseLangOrigin::Default {
+        return Ok(());
+    }
+    if let Some(base) = &config.base_lang {
+        options.base_lang = base.clone();
+        options.base_lang_origin = BaseLangOrigin::Config;
+        return Ok(());
+    }
+    // A published schema stands in for the base language folder.
+    if options.schema_url.is_some() {
+        return Ok(());
+    }
+    let timings = Timings::default();
+    let project = discover(options, config, &timings);
+    if check_base_language(&options.i18n_dir, &options.base_lang).is_ok() {
+        // Scoring holds every language at once.
+        if !options.low_memory {
+            options.base_lang_rival = detect::rival(&project, &options.base_lang);
+        }
+        return Ok(());
+    }
+    match detect::base_language(&project) {
+        Detection::Found(candidate) => {
+            options.base_lang = candidate.lang;
+            options.base_lang_origin = BaseLangOrigin::Detected;
+            Ok(())
+        }
+        Detection::Ambiguous(candidates) => {
+            let candidates: Vec<String> = candidates.iter().map(Candidate::describe).collect();
+            Err(format!(
+                "can't tell which language is the base, candidates: {}; pass --base-lang",
+                candidates.join(", ")
+            ))
+        }
+        // `check_base_language` then lists what was found.
+        Detection::NoLanguages => Ok(()),
+    }
+}
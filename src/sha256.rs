@@ -0,0 +1,10 @@
+use sha2::{Digest, Sha256};
+
+// SHA-256, used to verify downloaded artifacts against their published
+// checksum and to name cache entries.
+pub fn hex_digest(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
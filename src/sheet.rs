@@ -0,0 +1,318 @@
+use crate::base::Base;
+use crate::checks::expected_file;
+use crate::fix::Transaction;
+use crate::loader;
+use crate::model::{Project, ValueKind};
+use crate::report::Finding;
+use crate::sync;
+use crate::worklist::{Section, Worklist, csv_field};
+use crate::xlsx;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SheetFormat {
+    pub fn parse(name: &str) -> Option<SheetFormat> {
+        match name {
+            "csv" => Some(SheetFormat::Csv),
+            "xlsx" => Some(SheetFormat::Xlsx),
+            _ => None,
+        }
+    }
+
+    // The format a sheet is written in, from the extension of its path.
+    pub fn of(path: &Path) -> SheetFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("xlsx") => SheetFormat::Xlsx,
+            _ => SheetFormat::Csv,
+        }
+    }
+}
+
+// One key for a translator: its base text, the translation they fill in,
+// and the file it goes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub key: String,
+    pub base_value: String,
+    pub value: String,
+    pub file: String,
+}
+
+// The keys a language lacks or leaves empty, as a sheet for translators who
+// don't read JSON: a `key` column, the base text under the base language,
+// an empty column under the language to fill in, and the `file` column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    pub base_lang: String,
+    pub lang: String,
+    pub rows: Vec<Row>,
+}
+
+// What importing a sheet changes: the rewrite of the language's files, how
+// many values it writes, and the rows left out with the reason.
+#[derive(Debug, Default)]
+pub struct Import {
+    pub transaction: Transaction,
+    pub imported: usize,
+    pub skipped: Vec<(String, String)>,
+}
+
+impl Sheet {
+    // The missing and empty translations of `lang`, from the findings of a
+    // consistency run. Only base values that are strings are offered; arrays
+    // and other values are left to edit by hand.
+    pub fn export(project: &Project, base: &Base, lang: &str, findings: &[Finding]) -> Sheet {
+        let worklist = Worklist::new(
+            project,
+            base,
+            lang,
+            findings,
+            &[Section::Missing, Section::Empty],
+        );
+        let mut rows: Vec<Row> = worklist
+            .sections
+            .into_iter()
+            .flat_map(|(_, items)| items)
+            .filter(|item| {
+                base.kind(&item.key) == Some(ValueKind::String)
+                    && base.usage_key(&item.key) == item.key
+            })
+            .map(|item| Row {
+                key: item.key,
+                base_value: item.base_value.unwrap_or_default(),
+                value: String::new(),
+                file: item.file,
+            })
+            .collect();
+        rows.sort_by(|a, b| (&a.file, &a.key).cmp(&(&b.file, &b.key)));
+        Sheet {
+            base_lang: base.name.clone(),
+            lang: lang.to_string(),
+            rows,
+        }
+    }
+
+    fn cells(&self) -> Vec<Vec<String>> {
+        let header = vec![
+            "key".to_string(),
+            self.base_lang.clone(),
+            self.lang.clone(),
+            "file".to_string(),
+        ];
+        std::iter::once(header)
+            .chain(self.rows.iter().map(|row| {
+                vec![
+                    row.key.clone(),
+                    row.base_value.clone(),
+                    row.value.clone(),
+                    row.file.clone(),
+                ]
+            }))
+            .collect()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut text = String::new();
+        for cells in self.cells() {
+            let fields: Vec<String> = cells.iter().map(|cell| csv_field(cell)).collect();
+            text.push_str(&fields.join(","));
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn to_xlsx(&self) -> Vec<u8> {
+        xlsx::write(&self.cells())
+    }
+
+    // Reads a sheet written by `export` once filled, in the format its
+    // extension names. The languages are read from the header.
+    pub fn read(path: &Path) -> Result<Sheet, String> {
+        let invalid = |err: String| format!("invalid sheet {}: {}", path.display(), err);
+        let data = fs::read(path)
+            .map_err(|err| format!("failed to read sheet {}: {}", path.display(), err))?;
+        let cells = match SheetFormat::of(path) {
+            SheetFormat::Csv => parse_csv(&String::from_utf8_lossy(&data)).map_err(invalid)?,
+            SheetFormat::Xlsx => xlsx::read(&data).map_err(invalid)?,
+        };
+        let mut cells = cells.into_iter();
+        let header = cells.next().unwrap_or_default();
+        let header: Vec<&str> = header.iter().map(|cell| cell.trim()).collect();
+        let (base_lang, lang) = match header.as_slice() {
+            ["key", base_lang, lang, "file", ..] if !base_lang.is_empty() && !lang.is_empty() => {
+                (base_lang.to_string(), lang.to_string())
+            }
+            _ => {
+                return Err(invalid(
+                    "the first row must be `key`, the base language, the language and `file`"
+                        .into(),
+                ));
+            }
+        };
+        let cell = |cells: &[String], index: usize| cells.get(index).cloned().unwrap_or_default();
+        let rows = cells
+            .filter(|cells| !cell(cells, 0).trim().is_empty())
+            .map(|cells| Row {
+                key: cell(&cells, 0).trim().to_string(),
+                base_value: cell(&cells, 1),
+                value: cell(&cells, 2),
+                file: cell(&cells, 3).trim().to_string(),
+            })
+            .collect();
+        Ok(Sheet {
+            base_lang,
+            lang,
+            rows,
+        })
+    }
+
+    // The rewrite merging the filled rows into the language's JSON files:
+    // a translation the language has is replaced in place, another is added
+    // to the file of its row, as `--fix sync-keys` adds it. Rows left empty
+    // are skipped, as are keys the base doesn't hold as a string and files
+    // that aren't the language's.
+    pub fn import(&self, project: &Project, base: &Base) -> Result<Import, String> {
+        let data = project.get(&self.lang);
+        let files = project.files(&self.lang);
+        let mut import = Import::default();
+        let mut edits: BTreeMap<String, Vec<(&str, &str)>> = BTreeMap::new();
+        for row in self.rows.iter().filter(|row| !row.value.trim().is_empty()) {
+            let mut skip = |reason: String| import.skipped.push((row.key.clone(), reason));
+            if base.kind(&row.key) != Some(ValueKind::String) || base.usage_key(&row.key) != row.key
+            {
+                skip("not a string of the base".into());
+                continue;
+            }
+            let file = match data.and_then(|data| data.values.get(row.key.as_str())) {
+                Some(entry) if entry.value == row.value => continue,
+                Some(entry) if entry.kind != ValueKind::String => {
+                    skip(format!("not a string in {}", entry.file));
+                    continue;
+                }
+                Some(entry) => entry.file.to_string(),
+                None => {
+                    let expected = expected_file(project, base, &self.lang, &row.key);
+                    let file = if row.file.is_empty() {
+                        expected.clone()
+                    } else {
+                        row.file.clone()
+                    };
+                    let known = Path::new(&file) == Path::new(&expected)
+                        || files.iter().any(|known| known == Path::new(&file));
+                    if !known {
+                        skip(format!(
+                            "{} isn't a {} translation file",
+                            file,
+                            self.lang.to_uppercase()
+                        ));
+                        continue;
+                    }
+                    file
+                }
+            };
+            if Path::new(&file).extension().is_none_or(|ext| ext != "json") {
+                skip(format!("{} isn't a JSON file", file));
+                continue;
+            }
+            edits.entry(file).or_default().push((&row.key, &row.value));
+        }
+
+        for (file, edits) in edits {
+            let original = match fs::read_to_string(&file) {
+                Ok(content) => content,
+                Err(_) if !Path::new(&file).exists() => "{}\n".to_string(),
+                Err(err) => return Err(format!("failed to read {}: {}", file, err)),
+            };
+            let mut content = original.clone();
+            for (key, value) in edits {
+                let span = loader::value_spans(&content)
+                    .map_err(|err| format!("failed to parse {}: {}", file, err))?
+                    .into_iter()
+                    .find(|(span_key, _)| span_key == key);
+                let edited = match span {
+                    Some((_, range)) => {
+                        let mut edited = content.clone();
+                        edited.replace_range(range, &serde_json::to_string(value).unwrap());
+                        Some(edited)
+                    }
+                    None => sync::insert(&content, key, value),
+                };
+                match edited {
+                    Some(edited) => {
+                        content = edited;
+                        import.imported += 1;
+                    }
+                    None => import.skipped.push((
+                        key.to_string(),
+                        format!("a value of {} stands where its object would go", file),
+                    )),
+                }
+            }
+            if content != original {
+                import.transaction.rewrite(file.into(), content);
+            }
+        }
+        Ok(import)
+    }
+}
+
+// The rows of a CSV document, fields separated by commas or, as
+// spreadsheets save them in many locales, by semicolons when the first line
+// has no comma. A byte order mark is skipped.
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let first = text.lines().next().unwrap_or_default();
+    let separator = if !first.contains(',') && first.contains(';') {
+        ';'
+    } else {
+        ','
+    };
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => {
+                let start = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(format!("unterminated quoted field on line {}", start)),
+                    }
+                }
+            }
+            c if c == separator => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
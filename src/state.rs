@@ -0,0 +1,209 @@
+use crate::base::Base;
+use crate::model::Project;
+use crate::report::{Report, Severity};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+pub const STATE_FILE: &str = "translation-check.state.json";
+
+// The number of daily snapshots kept in the history, a year of them.
+pub const HISTORY_LIMIT: usize = 365;
+
+// What the last acknowledged run looked like, committed next to the
+// translations so that later runs can tell what changed since. It is only
+// rewritten by `--update-state`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct State {
+    pub base_keys: BTreeSet<String>,
+    // Percentage of the base keys present in each language.
+    pub coverage: BTreeMap<String, f64>,
+    // Number of keys of every language, the base included.
+    pub languages: BTreeMap<String, usize>,
+    // One snapshot per day `--update-state` ran, oldest first.
+    pub history: Vec<Snapshot>,
+}
+
+// The coverage and finding totals recorded on one day, for `trend`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Snapshot {
+    // `YYYY-MM-DD`, in UTC.
+    pub date: String,
+    pub coverage: BTreeMap<String, f64>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl Snapshot {
+    fn from_value(value: &Value) -> Option<Snapshot> {
+        Some(Snapshot {
+            date: value.get("date")?.as_str()?.to_string(),
+            coverage: value
+                .get("coverage")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .filter_map(|(lang, coverage)| Some((lang.clone(), coverage.as_f64()?)))
+                .collect(),
+            errors: value.get("errors").and_then(Value::as_u64).unwrap_or(0) as usize,
+            warnings: value.get("warnings").and_then(Value::as_u64).unwrap_or(0) as usize,
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        let mut json = Map::new();
+        json.insert("coverage".to_string(), coverage_value(&self.coverage));
+        json.insert("date".to_string(), Value::from(self.date.clone()));
+        json.insert("errors".to_string(), Value::from(self.errors));
+        json.insert("warnings".to_string(), Value::from(self.warnings));
+        Value::Object(json)
+    }
+}
+
+impl State {
+    pub fn load(path: &Path) -> Result<Option<State>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read state {}: {}", path.display(), err))?;
+        let json: Value = serde_json::from_str(&content)
+            .map_err(|err| format!("invalid state {}: {}", path.display(), err))?;
+
+        let base_keys = json
+            .get("base_keys")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+        let coverage = json
+            .get("coverage")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(lang, coverage)| Some((lang.clone(), coverage.as_f64()?)))
+            .collect();
+        let languages = json
+            .get("languages")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(lang, keys)| Some((lang.clone(), keys.as_u64()? as usize)))
+            .collect();
+        let history = json
+            .get("history")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Snapshot::from_value)
+            .collect();
+
+        Ok(Some(State {
+            base_keys,
+            coverage,
+            languages,
+            history,
+        }))
+    }
+
+    // The state of the current run. Coverage is only known when the
+    // consistency checks ran; otherwise the previous figures are kept, and
+    // no snapshot is added to the history. A second run on the same day
+    // replaces that day's snapshot.
+    pub fn from_run(
+        base: &Base,
+        project: &Project,
+        report: &Report,
+        previous: Option<&State>,
+        today: &str,
+    ) -> State {
+        let base_keys: BTreeSet<String> = base.placeholders.keys().cloned().collect();
+        let languages: Vec<&str> = project.languages().collect();
+
+        let coverage = if report.consistency_checked {
+            languages
+                .iter()
+                .filter(|lang| Some(**lang) != base.lang)
+                .map(|lang| {
+                    let coverage = report.completeness(lang, base_keys.len()) * 100.0;
+                    (lang.to_string(), (coverage * 10.0).round() / 10.0)
+                })
+                .collect()
+        } else {
+            previous
+                .map(|state| state.coverage.clone())
+                .unwrap_or_default()
+        };
+
+        let languages = languages
+            .iter()
+            .map(|lang| {
+                let keys = project.get(lang).map_or(0, |data| data.keys().count());
+                (lang.to_string(), keys)
+            })
+            .collect();
+
+        let mut history = previous
+            .map(|state| state.history.clone())
+            .unwrap_or_default();
+        if report.consistency_checked {
+            history.retain(|snapshot| snapshot.date.as_str() < today);
+            history.push(Snapshot {
+                date: today.to_string(),
+                coverage: coverage.clone(),
+                errors: report.weight(Severity::Error),
+                warnings: report.weight(Severity::Warning),
+            });
+        }
+        if history.len() > HISTORY_LIMIT {
+            history.drain(..history.len() - HISTORY_LIMIT);
+        }
+
+        State {
+            base_keys,
+            coverage,
+            languages,
+            history,
+        }
+    }
+
+    // Writes the state with sorted keys and one entry per line, so that it
+    // diffs and merges well in git.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let mut json = Map::new();
+        json.insert(
+            "base_keys".to_string(),
+            Value::from(self.base_keys.iter().cloned().collect::<Vec<_>>()),
+        );
+        json.insert("coverage".to_string(), coverage_value(&self.coverage));
+        json.insert(
+            "history".to_string(),
+            Value::Array(self.history.iter().map(Snapshot::to_value).collect()),
+        );
+        json.insert(
+            "languages".to_string(),
+            Value::Object(
+                self.languages
+                    .iter()
+                    .map(|(lang, keys)| (lang.clone(), Value::from(*keys)))
+                    .collect(),
+            ),
+        );
+
+        let content = serde_json::to_string_pretty(&Value::Object(json)).unwrap() + "\n";
+        fs::write(path, content)
+            .map_err(|err| format!("failed to write state {}: {}", path.display(), err))
+    }
+}
+
+fn coverage_value(coverage: &BTreeMap<String, f64>) -> Value {
+    Value::Object(
+        coverage
+            .iter()
+            .map(|(lang, coverage)| (lang.clone(), Value::from(*coverage)))
+            .collect(),
+    )
+}
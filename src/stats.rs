@@ -0,0 +1,216 @@
+use crate::base::Base;
+use crate::checks;
+use crate::model::Project;
+use crate::report::{Counts, Report};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+use std::path::Path;
+
+fn counts_json(counts: Counts) -> Value {
+    json!({
+        "translated": counts.translated,
+        "missing": counts.missing,
+        "empty": counts.empty,
+        "completion": counts.percent(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageStats {
+    pub lang: String,
+    pub counts: Counts,
+    // The counts of the base keys of each base file, by its path in the
+    // base language folder.
+    pub files: BTreeMap<String, Counts>,
+}
+
+// The `stats` of a project: each translated language against the base keys.
+// Keys a language has that the base lacks aren't counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub base_lang: String,
+    pub base_keys: usize,
+    pub languages: Vec<LanguageStats>,
+}
+
+impl Stats {
+    // Measures the languages of a loaded project, counting as the coverage
+    // of a check run does: from the comparison of each language with the
+    // base, the elements of a shorter array missing. Base keys
+    // `exempt(lang, key)` accepts as absent from a language aren't counted
+    // for it, nor in `base_keys` when it accepts them for every language,
+    // as base-only ones. Languages left out for their unreadable files
+    // aren't measured.
+    pub fn measure(
+        project: &Project,
+        case_insensitive: bool,
+        exempt: impl Fn(&str, &str) -> bool,
+    ) -> Stats {
+        let base_lang = project.base_lang();
+        let base = Base::language(project, base_lang);
+        let mut report = Report::default();
+        report.extend(checks::check_translations(
+            &base,
+            project,
+            &HashSet::new(),
+            None,
+            case_insensitive,
+            0,
+        ));
+        let base_keys = base.counted_keys();
+        let base_data = project.base();
+        let base_dir = project.language_dir(base_lang);
+        let excluded = project.excluded_languages();
+        let mut languages: Vec<LanguageStats> = project
+            .languages()
+            .filter(|lang| *lang != base_lang && !excluded.contains(*lang))
+            .map(|lang| {
+                let data = project.get(lang);
+                let blank = |key: &str| {
+                    data.and_then(|data| data.values.get(key))
+                        .is_some_and(|entry| entry.is_blank())
+                };
+                let mut files: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+                for key in base_keys.iter().filter(|key| !exempt(lang, key)) {
+                    let file = Path::new(base_data.file(key).unwrap_or_default());
+                    let file = file
+                        .strip_prefix(&base_dir)
+                        .unwrap_or(file)
+                        .to_string_lossy()
+                        .to_string();
+                    files.entry(file).or_default().push(key);
+                }
+                let files: BTreeMap<String, Counts> = files
+                    .into_iter()
+                    .map(|(file, keys)| (file, report.count_keys(lang, &keys, blank)))
+                    .collect();
+                let mut counts = Counts::default();
+                for file in files.values() {
+                    counts.translated += file.translated;
+                    counts.missing += file.missing;
+                    counts.empty += file.empty;
+                }
+                LanguageStats {
+                    lang: lang.to_string(),
+                    counts,
+                    files,
+                }
+            })
+            .collect();
+        languages.sort_by(|a, b| a.lang.cmp(&b.lang));
+        Stats {
+            base_lang: base_lang.to_string(),
+            base_keys: base_keys
+                .iter()
+                .filter(|key| !project.languages().all(|lang| exempt(lang, key)))
+                .count(),
+            languages,
+        }
+    }
+
+    // The rows of the tables: each language, followed by its files with
+    // `per_file`.
+    fn rows(&self, per_file: bool) -> Vec<(&str, Option<&str>, Counts)> {
+        let mut rows = Vec::new();
+        for language in &self.languages {
+            rows.push((language.lang.as_str(), None, language.counts));
+            if per_file {
+                for (file, counts) in &language.files {
+                    rows.push((language.lang.as_str(), Some(file.as_str()), *counts));
+                }
+            }
+        }
+        rows
+    }
+
+    pub fn write_text(&self, out: &mut impl Write, per_file: bool) -> io::Result<()> {
+        writeln!(
+            out,
+            "Translation stats against {} ({} keys)",
+            self.base_lang, self.base_keys
+        )?;
+        let rows = self.rows(per_file);
+        let label = |lang: &str, file: Option<&str>| match file {
+            Some(file) => format!("  {}", file),
+            None => lang.to_string(),
+        };
+        let width = rows
+            .iter()
+            .map(|(lang, file, _)| label(lang, *file).chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Language".len());
+        writeln!(
+            out,
+            "{:<width$}  {:>10}  {:>7}  {:>5}  {:>10}",
+            "Language", "Translated", "Missing", "Empty", "Completion"
+        )?;
+        for (lang, file, counts) in rows {
+            writeln!(
+                out,
+                "{:<width$}  {:>10}  {:>7}  {:>5}  {:>10}",
+                label(lang, file),
+                counts.translated,
+                counts.missing,
+                counts.empty,
+                format!("{:.1}%", counts.percent())
+            )?;
+        }
+        Ok(())
+    }
+
+    // A Markdown table, for release readiness reports. With `per_file` the
+    // files of each language follow its total row.
+    pub fn write_markdown(&self, out: &mut impl Write, per_file: bool) -> io::Result<()> {
+        let file_column = if per_file { " File |" } else { "" };
+        let file_rule = if per_file { " --- |" } else { "" };
+        writeln!(
+            out,
+            "| Language |{} Translated | Missing | Empty | Completion |",
+            file_column
+        )?;
+        writeln!(out, "| --- |{} ---: | ---: | ---: | ---: |", file_rule)?;
+        for (lang, file, counts) in self.rows(per_file) {
+            let file = match (per_file, file) {
+                (false, _) => String::new(),
+                (true, Some(file)) => format!(" `{}` |", file.replace('|', "\\|")),
+                (true, None) => " **all** |".to_string(),
+            };
+            writeln!(
+                out,
+                "| {} |{} {} | {} | {} | {:.1}% |",
+                lang,
+                file,
+                counts.translated,
+                counts.missing,
+                counts.empty,
+                counts.percent()
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self, per_file: bool) -> Value {
+        json!({
+            "base_lang": self.base_lang,
+            "base_keys": self.base_keys,
+            "languages": self.languages.iter().map(|language| {
+                let mut stats = counts_json(language.counts);
+                stats["lang"] = json!(language.lang);
+                if per_file {
+                    stats["files"] = language
+                        .files
+                        .iter()
+                        .map(|(file, counts)| {
+                            let mut stats = counts_json(*counts);
+                            stats["file"] = json!(file);
+                            stats
+                        })
+                        .collect();
+                }
+                stats
+            }).collect::<Vec<_>>(),
+        })
+    }
+}
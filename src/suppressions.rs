@@ -0,0 +1,265 @@
+use crate::handles;
+use crate::report::{Check, Finding, Severity};
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::Regex;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+lazy_static! {
+    // A directive in a `//`, `/* */` or `<!-- -->` comment, such as
+    // `// i18n-check-disable-next-line missing_interpolation`.
+    static ref DIRECTIVE_REGEX: Regex = Regex::new(
+        r"(?://|/\*|<!--)\s*i18n-check-(disable-next-line|disable|enable)\b([^\n]*)"
+    )
+    .unwrap();
+}
+
+// The checks reporting findings on source lines, the only ones a comment
+// can suppress.
+const SOURCE_CHECKS: [Check; 4] = [
+    Check::UndefinedKey,
+    Check::UnloadedKey,
+    Check::MissingInterpolation,
+    Check::UnverifiableInterpolation,
+];
+
+// One `i18n-check-disable` or `i18n-check-disable-next-line` comment.
+#[derive(Debug, Clone)]
+pub struct Directive {
+    pub file: String,
+    pub line: usize,
+    // The directive as written, without the comment markers.
+    pub text: String,
+    // How many findings it suppressed.
+    pub hits: usize,
+    // The lines it covers per check, `None` standing for every check.
+    covers: Vec<(Option<Check>, RangeInclusive<usize>)>,
+}
+
+impl Directive {
+    fn covers(&self, finding: &Finding) -> bool {
+        finding.file.as_deref() == Some(self.file.as_str())
+            && finding.line.is_some_and(|line| {
+                self.covers.iter().any(|(check, lines)| {
+                    check.is_none_or(|check| check == finding.check) && lines.contains(&line)
+                })
+            })
+    }
+}
+
+// A directive that can't be applied as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+    // A region still disabled at the end of the file, which may be
+    // reported as a warning only.
+    pub unterminated: bool,
+}
+
+// The suppression comments of the source files.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    pub directives: Vec<Directive>,
+    pub problems: Vec<Problem>,
+}
+
+impl Suppressions {
+    pub fn scan(files: &[PathBuf]) -> Suppressions {
+        let parsed: Vec<Suppressions> = files
+            .par_iter()
+            .filter_map(|path| {
+                let content = handles::read_to_string(path).ok()?;
+                content
+                    .contains("i18n-check-")
+                    .then(|| Suppressions::parse(&path.display().to_string(), &content))
+            })
+            .collect();
+        let mut suppressions = Suppressions::default();
+        for file in parsed {
+            suppressions.directives.extend(file.directives);
+            suppressions.problems.extend(file.problems);
+        }
+        suppressions
+    }
+
+    // Regions nest: `i18n-check-enable` closes the innermost disabled
+    // region, and `i18n-check-enable <checks>` the innermost one of each
+    // check, so regions of different checks may overlap.
+    pub fn parse(file: &str, content: &str) -> Suppressions {
+        let mut suppressions = Suppressions::default();
+        // The directive, check and first line of each open region.
+        let mut open: Vec<(usize, Option<Check>, usize)> = Vec::new();
+        let mut last_line = 0;
+
+        for (index, text) in content.lines().enumerate() {
+            let line = index + 1;
+            last_line = line;
+            let Some(caps) = DIRECTIVE_REGEX.captures(text) else {
+                continue;
+            };
+            let mut problem = |message: String| {
+                suppressions.problems.push(Problem {
+                    file: file.to_string(),
+                    line,
+                    message,
+                    unterminated: false,
+                });
+            };
+
+            let kind = &caps[1];
+            let arguments = caps[2].split("*/").next().unwrap();
+            let arguments = arguments.split("-->").next().unwrap();
+            let mut checks = Vec::new();
+            let mut unknown = false;
+            for id in arguments
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|id| !id.is_empty())
+            {
+                match Check::from_id(id) {
+                    Some(check) if SOURCE_CHECKS.contains(&check) => checks.push(Some(check)),
+                    Some(_) => {
+                        problem(format!("`{}` isn't reported on source lines", id));
+                        unknown = true;
+                    }
+                    None => {
+                        problem(format!("unknown check `{}` in `i18n-check-{}`", id, kind));
+                        unknown = true;
+                    }
+                }
+            }
+            if unknown {
+                continue;
+            }
+            let written = arguments.split_whitespace().collect::<Vec<_>>().join(" ");
+            let directive_text = if written.is_empty() {
+                format!("i18n-check-{}", kind)
+            } else {
+                format!("i18n-check-{} {}", kind, written)
+            };
+
+            match kind {
+                "disable-next-line" | "disable" => {
+                    if checks.is_empty() {
+                        checks.push(None);
+                    }
+                    let directive = suppressions.directives.len();
+                    let mut covers = Vec::new();
+                    for check in checks {
+                        if kind == "disable" {
+                            open.push((directive, check, line + 1));
+                        } else {
+                            covers.push((check, line + 1..=line + 1));
+                        }
+                    }
+                    suppressions.directives.push(Directive {
+                        file: file.to_string(),
+                        line,
+                        text: directive_text,
+                        hits: 0,
+                        covers,
+                    });
+                }
+                _ if checks.is_empty() => match open.last() {
+                    None => problem("`i18n-check-enable` without a disabled region".to_string()),
+                    Some(&(directive, _, _)) => {
+                        for (_, check, start) in open.extract_if(.., |(d, _, _)| *d == directive) {
+                            suppressions.directives[directive]
+                                .covers
+                                .push((check, start..=line));
+                        }
+                    }
+                },
+                _ => {
+                    for check in checks {
+                        if let Some(position) = open.iter().rposition(|(_, open, _)| *open == check)
+                        {
+                            let (directive, check, start) = open.remove(position);
+                            suppressions.directives[directive]
+                                .covers
+                                .push((check, start..=line));
+                        } else if open.iter().any(|(_, open, _)| open.is_none()) {
+                            problem(format!(
+                                "`{}` can't be enabled inside a region disabling every check",
+                                check.unwrap().info().id
+                            ));
+                        } else {
+                            problem(format!(
+                                "`{}` is enabled but wasn't disabled",
+                                check.unwrap().info().id
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (directive, check, start) in open {
+            let directive = &mut suppressions.directives[directive];
+            directive.covers.push((check, start..=last_line.max(start)));
+            suppressions.problems.push(Problem {
+                file: file.to_string(),
+                line: directive.line,
+                message: format!(
+                    "`{}` is never re-enabled{}",
+                    directive.text,
+                    check.map_or(String::new(), |check| format!(" for `{}`", check.info().id))
+                ),
+                unterminated: true,
+            });
+        }
+        suppressions.problems.dedup();
+        suppressions
+    }
+
+    // Drops the findings covered by a directive, counting its hits.
+    pub fn apply(&mut self, findings: &mut Vec<Finding>) {
+        findings.retain(|finding| {
+            match self
+                .directives
+                .iter_mut()
+                .find(|directive| directive.covers(finding))
+            {
+                Some(directive) => {
+                    directive.hits += 1;
+                    false
+                }
+                None => true,
+            }
+        });
+    }
+
+    // The malformed directives, with unterminated regions at `unterminated`
+    // severity, and the directives that suppressed nothing.
+    pub fn findings(&self, unterminated: Severity) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = self
+            .problems
+            .iter()
+            .map(|problem| {
+                let finding = Finding::new(Check::MalformedSuppression)
+                    .detail("Problem", &problem.message)
+                    .file(&problem.file)
+                    .line(problem.line);
+                if problem.unterminated {
+                    finding.severity(unterminated)
+                } else {
+                    finding
+                }
+            })
+            .collect();
+        findings.extend(
+            self.directives
+                .iter()
+                .filter(|directive| directive.hits == 0)
+                .map(|directive| {
+                    Finding::new(Check::UnusedSuppression)
+                        .detail("Directive", &directive.text)
+                        .file(&directive.file)
+                        .line(directive.line)
+                }),
+        );
+        findings
+    }
+}
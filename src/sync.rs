@@ -0,0 +1,589 @@
+use crate::base::Base;
+use crate::checks;
+use crate::fix::Transaction;
+use crate::loader;
+use crate::model::{Project, ValueKind};
+use crate::report::{Check, Report};
+use crate::scaffold::{Provenance, Scaffolds, scaffold_file};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{self, Path, PathBuf};
+
+// The value of a key added by `--fix sync-keys` unless `[sync_keys]` says
+// otherwise, `{value}` standing for the base value: marked, so that
+// translators find the keys left to translate.
+pub const DEFAULT_TEMPLATE: &str = "[TODO] {value}";
+
+// Used when a file has no indented line to learn the indentation from.
+const DEFAULT_INDENT: &str = "  ";
+
+// A JSON value with where it sits in the document. Only objects are told
+// apart; everything else is left as written.
+enum Node {
+    Object {
+        // The byte ranges of the braces.
+        open: usize,
+        close: usize,
+        members: Vec<Member>,
+    },
+    Other,
+}
+
+struct Member {
+    key: String,
+    // From the opening quote of the key to the end of the value.
+    span: Range<usize>,
+    value: Node,
+}
+
+struct Parser<'a> {
+    content: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.bytes.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        serde_json::from_str(&self.content[start..self.pos]).ok()
+    }
+
+    fn value(&mut self) -> Option<Node> {
+        match self.next()? {
+            b'{' => {
+                let open = self.pos;
+                self.pos += 1;
+                let mut members = Vec::new();
+                if self.next()? == b'}' {
+                    self.pos += 1;
+                    return Some(Node::Object {
+                        open,
+                        close: self.pos - 1,
+                        members,
+                    });
+                }
+                loop {
+                    if self.next()? != b'"' {
+                        return None;
+                    }
+                    let start = self.pos;
+                    let key = self.string()?;
+                    if self.next()? != b':' {
+                        return None;
+                    }
+                    self.pos += 1;
+                    let value = self.value()?;
+                    members.push(Member {
+                        key,
+                        span: start..self.pos,
+                        value,
+                    });
+                    match self.next()? {
+                        b',' => self.pos += 1,
+                        b'}' => {
+                            self.pos += 1;
+                            return Some(Node::Object {
+                                open,
+                                close: self.pos - 1,
+                                members,
+                            });
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                if self.next()? == b']' {
+                    self.pos += 1;
+                    return Some(Node::Other);
+                }
+                loop {
+                    self.value()?;
+                    match self.next()? {
+                        b',' => self.pos += 1,
+                        b']' => {
+                            self.pos += 1;
+                            return Some(Node::Other);
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            b'"' => self.string().map(|_| Node::Other),
+            _ => {
+                let start = self.pos;
+                while self
+                    .bytes
+                    .get(self.pos)
+                    .is_some_and(|byte| !b",}] \t\r\n".contains(byte))
+                {
+                    self.pos += 1;
+                }
+                (self.pos > start).then_some(Node::Other)
+            }
+        }
+    }
+}
+
+fn parse(content: &str) -> Option<Node> {
+    let mut parser = Parser {
+        content,
+        bytes: content.as_bytes(),
+        pos: 0,
+    };
+    let root = parser.value()?;
+    parser.skip_whitespace();
+    (parser.pos == content.len()).then_some(root)
+}
+
+// The whitespace a line starts with, for the line holding `pos`.
+fn line_indent(content: &str, pos: usize) -> &str {
+    let start = content[..pos].rfind('\n').map_or(0, |newline| newline + 1);
+    let line = &content[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+// What one level of indentation is in this file: the indentation of its
+// first indented line.
+fn indent_unit(content: &str) -> &str {
+    content
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or(DEFAULT_INDENT)
+}
+
+// The member for `segments` under `value`, as nested objects or keys with
+// dots alike, with the objects leading to it: `(object, member index)`.
+fn locate<'n>(value: &'n Node, segments: &[&str]) -> Option<Vec<(&'n Node, usize)>> {
+    let Node::Object { members, .. } = value else {
+        return None;
+    };
+    for end in (1..=segments.len()).rev() {
+        let name = segments[..end].join(".");
+        for (index, member) in members.iter().enumerate() {
+            if member.key != name {
+                continue;
+            }
+            if end == segments.len() {
+                return Some(vec![(value, index)]);
+            }
+            if let Some(mut path) = locate(&member.value, &segments[end..]) {
+                path.insert(0, (value, index));
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+// Where the member of `key` is written, from the opening quote of its name
+// to the end of its value.
+pub fn member_span(content: &str, key: &str) -> Option<Range<usize>> {
+    let root = parse(content)?;
+    let segments: Vec<&str> = key.split('.').collect();
+    let (object, index) = *locate(&root, &segments)?.last()?;
+    let Node::Object { members, .. } = object else {
+        return None;
+    };
+    Some(members[index].span.clone())
+}
+
+// The document without the value of `key`. An object left empty goes with
+// it, up to the root.
+pub fn remove(content: &str, key: &str) -> Option<String> {
+    let root = parse(content)?;
+    let segments: Vec<&str> = key.split('.').collect();
+    let path = locate(&root, &segments)?;
+    let (object, index) = path
+        .iter()
+        .rev()
+        .copied()
+        .find(|(object, _)| {
+            let Node::Object { members, .. } = object else {
+                return true;
+            };
+            members.len() > 1
+        })
+        .unwrap_or(path[0]);
+    let Node::Object {
+        open,
+        close,
+        members,
+    } = object
+    else {
+        return None;
+    };
+    let range = if members.len() == 1 {
+        open + 1..*close
+    } else if index > 0 {
+        members[index - 1].span.end..members[index].span.end
+    } else {
+        members[0].span.start..members[1].span.start
+    };
+    let mut content = content.to_string();
+    content.replace_range(range, "");
+    Some(content)
+}
+
+// `"key": value` for the last segment, nested in objects for the others.
+fn render(segments: &[&str], value: &str, indent: &str, unit: &str) -> String {
+    let key = serde_json::to_string(segments[0]).unwrap();
+    if segments.len() == 1 {
+        return format!("{}: {}", key, serde_json::to_string(value).unwrap());
+    }
+    let inner = format!("{}{}", indent, unit);
+    format!(
+        "{}: {{\n{}{}\n{}}}",
+        key,
+        inner,
+        render(&segments[1..], value, &inner, unit),
+        indent
+    )
+}
+
+fn render_inline(segments: &[&str], value: &str) -> String {
+    let key = serde_json::to_string(segments[0]).unwrap();
+    if segments.len() == 1 {
+        return format!("{}: {}", key, serde_json::to_string(value).unwrap());
+    }
+    format!("{}: {{ {} }}", key, render_inline(&segments[1..], value))
+}
+
+// The document with `key` added last in the deepest object already leading
+// to it, indented like its siblings. `None` when a value stands where an
+// object would go.
+pub fn insert(content: &str, key: &str, value: &str) -> Option<String> {
+    insert_in_order(content, key, value, &HashMap::new())
+}
+
+// `insert`, with `key` going before the first sibling that `order`, the
+// offsets of the keys in the base file, puts after it. Siblings the base
+// doesn't define are skipped over.
+fn insert_in_order(
+    content: &str,
+    key: &str,
+    value: &str,
+    order: &HashMap<String, usize>,
+) -> Option<String> {
+    let root = parse(content)?;
+    let unit = indent_unit(content);
+    let mut node = &root;
+    let mut rest: Vec<&str> = key.split('.').collect();
+    'walk: loop {
+        let Node::Object { members, .. } = node else {
+            return None;
+        };
+        for end in (1..rest.len()).rev() {
+            let name = rest[..end].join(".");
+            if let Some(member) = members
+                .iter()
+                .find(|member| member.key == name && matches!(member.value, Node::Object { .. }))
+            {
+                node = &member.value;
+                rest.drain(..end);
+                continue 'walk;
+            }
+        }
+        break;
+    }
+    let Node::Object {
+        open,
+        close,
+        members,
+    } = node
+    else {
+        return None;
+    };
+    if members.iter().any(|member| member.key == rest[0]) {
+        return None;
+    }
+    let prefix = &key[..key.len() - rest.join(".").len()];
+    let next = order.get(key).and_then(|offset| {
+        members.iter().find(|member| {
+            order
+                .get(&format!("{}{}", prefix, member.key))
+                .is_some_and(|sibling| sibling > offset)
+        })
+    });
+
+    let mut content = content.to_string();
+    match (next, members.last()) {
+        (Some(next), Some(last)) if content[*open..last.span.start].contains('\n') => {
+            let indent = line_indent(&content, next.span.start).to_string();
+            let member = render(&rest, value, &indent, unit);
+            content.insert_str(next.span.start, &format!("{},\n{}", member, indent));
+        }
+        (Some(next), _) => {
+            let member = render_inline(&rest, value);
+            content.insert_str(next.span.start, &format!("{}, ", member));
+        }
+        (None, None) => {
+            let outer = line_indent(&content, *open).to_string();
+            let indent = format!("{}{}", outer, unit);
+            let member = render(&rest, value, &indent, unit);
+            content.replace_range(
+                open + 1..*close,
+                &format!("\n{}{}\n{}", indent, member, outer),
+            );
+        }
+        (None, Some(last)) if content[*open..last.span.start].contains('\n') => {
+            let indent = line_indent(&content, last.span.start).to_string();
+            let member = render(&rest, value, &indent, unit);
+            content.insert_str(last.span.end, &format!(",\n{}{}", indent, member));
+        }
+        (None, Some(last)) => {
+            let member = render_inline(&rest, value);
+            content.insert_str(last.span.end, &format!(", {}", member));
+        }
+    }
+    Some(content)
+}
+
+enum Edit {
+    Insert(String, String),
+    Remove(String),
+}
+
+impl Edit {
+    fn key(&self) -> &str {
+        match self {
+            Edit::Insert(key, _) | Edit::Remove(key) => key,
+        }
+    }
+}
+
+// The findings `--fix sync-keys` fixes, with the files their edits go to.
+#[derive(Debug, Default)]
+pub struct Fixed {
+    files: HashMap<usize, HashSet<PathBuf>>,
+    // Missing key findings going with the removal of an unused key, by
+    // index of both.
+    removed_with: Vec<(usize, usize)>,
+    // The unused keys kept in every language, since a protected file
+    // defines them.
+    pub kept: BTreeSet<String>,
+    // How many of the files rewritten are scaffold files.
+    pub scaffold_files: usize,
+}
+
+impl Fixed {
+    // The indexes of the findings fixed once the files `refused` are left
+    // alone: an unused key stays reported while a file still defines it.
+    pub fn indexes(&self, refused: &[PathBuf]) -> HashSet<usize> {
+        let mut fixed: HashSet<usize> = self
+            .files
+            .iter()
+            .filter(|(_, files)| files.iter().all(|file| !refused.contains(file)))
+            .map(|(index, _)| *index)
+            .collect();
+        for (index, unused) in &self.removed_with {
+            if fixed.contains(unused) {
+                fixed.insert(*index);
+            }
+        }
+        fixed
+    }
+}
+
+// `--fix sync-keys`: the rewrite adding each missing key to the file the
+// base language defines it in, valued with `template` filled with the base
+// value, and removing each unused key from every language. Keys are added
+// in the order of the base file; existing keys keep their order and
+// formatting. An unused key one of the `protected` files
+// defines is kept everywhere, as removing it elsewhere only would report it
+// missing next. The provenance of each key added is recorded in the
+// scaffold file of its language, which forgets the keys translated or
+// removed since. Returns the findings fixed.
+pub fn sync_keys(
+    report: &Report,
+    project: &Project,
+    base: &Base,
+    template: &str,
+    protected: &[PathBuf],
+) -> Result<(Transaction, Fixed), String> {
+    let mut edits: BTreeMap<String, Vec<(usize, Edit)>> = BTreeMap::new();
+    // Each unused key with the index of its finding. Without a source file
+    // scanned, every key would look unused: none is removed.
+    let unused: HashMap<&str, usize> = report
+        .findings
+        .iter()
+        .enumerate()
+        .filter(|_| report.source_files > 0)
+        .filter(|(_, finding)| finding.check == Check::UnusedKey)
+        .filter_map(|(index, finding)| Some((finding.key.as_deref()?, index)))
+        .collect();
+    // A key about to be removed isn't added; its missing key findings go
+    // with its removal.
+    let mut removed_with = Vec::new();
+    // The language and base value of each key added, by index of its
+    // finding.
+    let mut sources: HashMap<usize, (&str, &str)> = HashMap::new();
+    // The base file the keys added to each file come from, whose order they
+    // follow.
+    let mut base_files: HashMap<String, String> = HashMap::new();
+    for (index, finding) in report.findings.iter().enumerate() {
+        let (Check::MissingKey, Some(lang), Some(key)) = (
+            finding.check,
+            finding.lang.as_deref(),
+            finding.key.as_deref(),
+        ) else {
+            continue;
+        };
+        if let Some(unused) = unused.get(base.usage_key(key)) {
+            removed_with.push((index, *unused));
+        } else if let Some(value) = base.value(key)
+            // Array elements and values other than strings are left to fix
+            // by hand.
+            && base.kind(key) == Some(ValueKind::String)
+            && base.usage_key(key) == key
+        {
+            let file = checks::expected_file(project, base, lang, key);
+            base_files
+                .entry(file.clone())
+                .or_insert_with(|| base.file(key));
+            sources.insert(index, (lang, value));
+            let value = template.replace("{value}", value);
+            edits
+                .entry(file)
+                .or_default()
+                .push((index, Edit::Insert(key.to_string(), value)));
+        }
+    }
+    // An unused array goes as a whole.
+    let removals = project.flat_map_languages(|_, data| {
+        let values = data.values.iter().map(|(key, entry)| (key, &entry.file));
+        let arrays = data.arrays.iter().map(|(key, (_, file))| (key, file));
+        values
+            .chain(arrays)
+            .filter_map(|(key, file)| {
+                let index = unused.get(&**key)?;
+                Some((file.to_string(), key.to_string(), *index))
+            })
+            .collect()
+    });
+    let absolute = |path: &Path| path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let protected: HashSet<PathBuf> = protected.iter().map(|path| absolute(path)).collect();
+    let kept: BTreeSet<String> = removals
+        .iter()
+        .filter(|(file, _, _)| protected.contains(&absolute(Path::new(file))))
+        .map(|(_, key, _)| key.clone())
+        .collect();
+    let mut removed: HashSet<String> = HashSet::new();
+    for (file, key, index) in removals {
+        if !kept.contains(&key) {
+            removed.insert(key.clone());
+            edits
+                .entry(file)
+                .or_default()
+                .push((index, Edit::Remove(key)));
+        }
+    }
+
+    let mut transaction = Transaction::default();
+    let mut scaffolded: BTreeMap<&str, Vec<(String, Provenance)>> = BTreeMap::new();
+    let mut fixed = Fixed {
+        files: HashMap::new(),
+        removed_with,
+        kept,
+        scaffold_files: 0,
+    };
+    for (file, edits) in edits {
+        // Modules and files of other formats are left alone.
+        if Path::new(&file).extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let original = match fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(_) if !Path::new(&file).exists() => "{}\n".to_string(),
+            Err(err) => return Err(format!("failed to read {}: {}", file, err)),
+        };
+        let mut content = original.clone();
+        // Keys are added in the order of the base file, whatever the order
+        // of the report.
+        let order = base_files
+            .get(&file)
+            .and_then(|base_file| fs::read_to_string(base_file).ok())
+            .and_then(|content| loader::key_offsets(&content).ok())
+            .unwrap_or_default();
+        let mut edits = edits;
+        edits.sort_by(|(_, a), (_, b)| {
+            let offset = |edit: &Edit| order.get(edit.key()).copied().unwrap_or(usize::MAX);
+            (offset(a), a.key()).cmp(&(offset(b), b.key()))
+        });
+        for (index, edit) in edits {
+            let edited = match &edit {
+                Edit::Insert(key, value) => insert_in_order(&content, key, value, &order),
+                Edit::Remove(key) => remove(&content, key),
+            };
+            if let Some(edited) = edited {
+                content = edited;
+                if let (Edit::Insert(key, value), Some((lang, base_value))) =
+                    (&edit, sources.get(&index))
+                {
+                    scaffolded
+                        .entry(lang)
+                        .or_default()
+                        .push((key.clone(), Provenance::new(base_value, value)));
+                }
+                fixed
+                    .files
+                    .entry(index)
+                    .or_default()
+                    .insert(PathBuf::from(&file));
+            }
+        }
+        if content != original {
+            transaction.rewrite(PathBuf::from(file), content);
+        }
+    }
+
+    for lang in project.languages() {
+        let path = scaffold_file(project, lang);
+        let recorded = Scaffolds::load(&path)?;
+        let added = scaffolded.remove(lang).unwrap_or_default();
+        if recorded.is_none() && added.is_empty() {
+            continue;
+        }
+        let mut scaffolds = recorded.clone().unwrap_or_default();
+        let data = project.get(lang);
+        scaffolds.records.retain(|key, record| {
+            !removed.contains(key)
+                && data
+                    .and_then(|data| data.value(key))
+                    .is_some_and(|value| record.scaffolds(value))
+        });
+        scaffolds.records.extend(added);
+        if recorded.as_ref() != Some(&scaffolds) {
+            transaction.rewrite(path, scaffolds.to_json());
+            fixed.scaffold_files += 1;
+        }
+    }
+    Ok((transaction, fixed))
+}
@@ -0,0 +1,84 @@
+use colored::*;
+use std::fs;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Collects phase durations and the languages whose values were actually
+// parsed, so the lazy loading can be verified from `--timings`, with the
+// translation files flattened and those an earlier run of the process had
+// flattened already.
+#[derive(Default)]
+pub struct Timings {
+    phases: Mutex<Vec<(String, Duration)>>,
+    parsed_languages: Mutex<Vec<String>>,
+    files: Mutex<(usize, usize)>,
+}
+
+impl Timings {
+    pub fn time<T>(&self, phase: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.phases
+            .lock()
+            .unwrap()
+            .push((phase.to_string(), start.elapsed()));
+        result
+    }
+
+    pub fn phases(&self) -> Vec<(String, Duration)> {
+        self.phases.lock().unwrap().clone()
+    }
+
+    pub fn record_parsed(&self, lang: &str) {
+        self.parsed_languages.lock().unwrap().push(lang.to_string());
+    }
+
+    // One translation file read, `flattened` again or kept from an earlier
+    // run.
+    pub fn record_flattened(&self, flattened: bool) {
+        let mut files = self.files.lock().unwrap();
+        if flattened {
+            files.0 += 1;
+        } else {
+            files.1 += 1;
+        }
+    }
+
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{}", "⏱️ Timings:".bold())?;
+        for (phase, duration) in self.phases.lock().unwrap().iter() {
+            writeln!(out, "   - {}: {:.2?}", phase, duration)?;
+        }
+
+        let mut parsed = self.parsed_languages.lock().unwrap().clone();
+        parsed.sort();
+        parsed.dedup();
+        writeln!(out, "   - languages parsed: {}", parsed.join(","))?;
+        let (flattened, kept) = *self.files.lock().unwrap();
+        writeln!(
+            out,
+            "   - translation files flattened: {} of {}",
+            flattened,
+            flattened + kept
+        )?;
+
+        if let Some(peak) = peak_memory() {
+            writeln!(
+                out,
+                "   - peak memory: {:.1} MiB",
+                peak as f64 / (1024.0 * 1024.0)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The peak resident set size of the process in bytes, where the platform
+// reports it.
+fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
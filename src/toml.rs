@@ -0,0 +1,439 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+// A small TOML reader covering what the configuration files need: tables,
+// arrays of tables, dotted keys, strings, numbers, booleans, arrays and
+// inline tables. Dates are kept as plain strings.
+pub fn parse(source: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    parser
+        .document()
+        .map_err(|err| format!("line {}: {}", parser.line, err))
+}
+
+// Writes `text` as a TOML basic string, for the files this tool generates.
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.bump() {
+            Some(found) if found == c => Ok(()),
+            Some(found) => Err(format!("expected `{}`, found `{}`", c, found)),
+            None => Err(format!("expected `{}`, found end of file", c)),
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.bump();
+        }
+    }
+
+    // Skips whitespace, newlines and comments.
+    fn skip_blank(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\r' | '\n') => {
+                    self.bump();
+                }
+                Some('#') => self.skip_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while !matches!(self.peek(), None | Some('\n')) {
+            self.bump();
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.skip_spaces();
+        if self.peek() == Some('#') {
+            self.skip_comment();
+        }
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => {
+                self.bump();
+                Ok(())
+            }
+            Some('\r') if self.peek_at(1) == Some('\n') => {
+                self.bump();
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(format!("unexpected `{}` after value", c)),
+        }
+    }
+
+    fn document(&mut self) -> Result<Value, String> {
+        let mut root = Map::new();
+        let mut current: Vec<String> = Vec::new();
+        // The tables given a `[header]`, which may be given only once.
+        let mut defined: BTreeSet<Vec<String>> = BTreeSet::new();
+
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None => return Ok(Value::Object(root)),
+                Some('[') => {
+                    self.bump();
+                    let array = self.peek() == Some('[');
+                    if array {
+                        self.bump();
+                    }
+                    self.skip_spaces();
+                    let path = self.key_path()?;
+                    self.skip_spaces();
+                    self.expect(']')?;
+                    if array {
+                        self.expect(']')?;
+                        push_table(&mut root, &path)?;
+                        // The tables under the previous element are those
+                        // of another table.
+                        defined.retain(|table| !table.starts_with(&path));
+                    } else {
+                        if !defined.insert(path.clone()) {
+                            return Err(format!("table `{}` is defined twice", path.join(".")));
+                        }
+                        table_at(&mut root, &path)?;
+                    }
+                    self.end_of_line()?;
+                    current = path;
+                }
+                Some(_) => {
+                    let line = self.line;
+                    let path = self.key_path()?;
+                    self.skip_spaces();
+                    self.expect('=')?;
+                    self.skip_spaces();
+                    let value = self.value()?;
+                    self.end_of_line()?;
+
+                    let table = table_at(&mut root, &current)?;
+                    // A duplicate key is reported at its own line, not at
+                    // the one after its value.
+                    if let Err(err) = insert(table, &path, value) {
+                        self.line = line;
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    fn key_path(&mut self) -> Result<Vec<String>, String> {
+        let mut path = vec![self.key()?];
+        loop {
+            self.skip_spaces();
+            if self.peek() != Some('.') {
+                return Ok(path);
+            }
+            self.bump();
+            self.skip_spaces();
+            path.push(self.key()?);
+        }
+    }
+
+    fn key(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some('"') => self.basic_string(),
+            Some('\'') => self.literal_string(),
+            _ => {
+                let mut key = String::new();
+                while let Some(c) = self.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                        key.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                if key.is_empty() {
+                    return Err("expected a key".to_string());
+                }
+                Ok(key)
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('"') if self.starts_with("\"\"\"") => {
+                self.multiline_basic_string().map(Value::from)
+            }
+            Some('\'') if self.starts_with("'''") => {
+                self.multiline_literal_string().map(Value::from)
+            }
+            Some('"') => self.basic_string().map(Value::from),
+            Some('\'') => self.literal_string().map(Value::from),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some(_) => self.scalar(),
+            None => Err("expected a value".to_string()),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(text),
+                Some('\\') => text.push(self.escape()?),
+                Some('\n') | None => return Err("unterminated string".to_string()),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn multiline_basic_string(&mut self) -> Result<String, String> {
+        self.pos += 3;
+        if self.peek() == Some('\n') {
+            self.bump();
+        }
+        let mut text = String::new();
+        loop {
+            if self.starts_with("\"\"\"") {
+                self.pos += 3;
+                return Ok(text);
+            }
+            match self.bump() {
+                Some('\\') if matches!(self.peek(), Some('\n' | ' ' | '\t' | '\r')) => {
+                    while matches!(self.peek(), Some('\n' | ' ' | '\t' | '\r')) {
+                        self.bump();
+                    }
+                }
+                Some('\\') => text.push(self.escape()?),
+                Some(c) => text.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, String> {
+        match self.bump() {
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some(c @ ('u' | 'U')) => {
+                let len = if c == 'u' { 4 } else { 8 };
+                let hex: String = (0..len).filter_map(|_| self.bump()).collect();
+                u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid unicode escape `\\{}{}`", c, hex))
+            }
+            Some(c) => Err(format!("invalid escape `\\{}`", c)),
+            None => Err("unterminated string".to_string()),
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        self.expect('\'')?;
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('\'') => return Ok(text),
+                Some('\n') | None => return Err("unterminated string".to_string()),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn multiline_literal_string(&mut self) -> Result<String, String> {
+        self.pos += 3;
+        if self.peek() == Some('\n') {
+            self.bump();
+        }
+        let mut text = String::new();
+        loop {
+            if self.starts_with("'''") {
+                self.pos += 3;
+                return Ok(text);
+            }
+            match self.bump() {
+                Some(c) => text.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(Value::Array(items));
+            }
+            items.push(self.value()?);
+            self.skip_blank();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(items)),
+                _ => return Err("expected `,` or `]` in array".to_string()),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut table = Map::new();
+        loop {
+            self.skip_spaces();
+            if self.peek() == Some('}') {
+                self.bump();
+                return Ok(Value::Object(table));
+            }
+            let path = self.key_path()?;
+            self.skip_spaces();
+            self.expect('=')?;
+            self.skip_spaces();
+            let value = self.value()?;
+            insert(&mut table, &path, value)?;
+            self.skip_spaces();
+            match self.bump() {
+                Some(',') => {}
+                Some('}') => return Ok(Value::Object(table)),
+                _ => return Err("expected `,` or `}` in inline table".to_string()),
+            }
+        }
+    }
+
+    fn scalar(&mut self) -> Result<Value, String> {
+        let mut token = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_' | '.' | ':') {
+                token.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        match token.as_str() {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            "" => return Err("expected a value".to_string()),
+            _ => {}
+        }
+
+        let number = token.replace('_', "");
+        if let Ok(int) = number.parse::<i64>() {
+            return Ok(Value::from(int));
+        }
+        if let Some(float) = number
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+        {
+            return Ok(Value::Number(float));
+        }
+        if token.chars().next().unwrap().is_ascii_digit() && token.contains('-') {
+            return Ok(Value::String(token));
+        }
+        Err(format!("invalid value `{}`", token))
+    }
+}
+
+fn table_at<'a>(
+    root: &'a mut Map<String, Value>,
+    path: &[String],
+) -> Result<&'a mut Map<String, Value>, String> {
+    let mut table = root;
+    for segment in path {
+        let entry = table
+            .entry(segment.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        table = match entry {
+            Value::Object(map) => map,
+            Value::Array(items) => match items.last_mut() {
+                Some(Value::Object(map)) => map,
+                _ => return Err(format!("`{}` is not a table", segment)),
+            },
+            _ => return Err(format!("`{}` is not a table", segment)),
+        };
+    }
+    Ok(table)
+}
+
+fn push_table(root: &mut Map<String, Value>, path: &[String]) -> Result<(), String> {
+    let (last, parent) = path.split_last().unwrap();
+    let parent = table_at(root, parent)?;
+    match parent
+        .entry(last.clone())
+        .or_insert_with(|| Value::Array(vec![]))
+    {
+        Value::Array(items) => {
+            items.push(Value::Object(Map::new()));
+            Ok(())
+        }
+        _ => Err(format!("`{}` is not an array of tables", last)),
+    }
+}
+
+fn insert(table: &mut Map<String, Value>, path: &[String], value: Value) -> Result<(), String> {
+    let (last, parent) = path.split_last().unwrap();
+    let table = table_at(table, parent)?;
+    if table.contains_key(last) {
+        return Err(format!("duplicate key `{}`", last));
+    }
+    table.insert(last.clone(), value);
+    Ok(())
+}
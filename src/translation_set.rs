@@ -0,0 +1,324 @@
+use crate::base::Base;
+use crate::checks::{self, extract_variables};
+use crate::model::{self, DEFAULT_BASE_LANG, Project};
+use crate::report::{Check, Finding};
+use crate::timings::Timings;
+use crate::usage::{DEFAULT_EXTENSIONS, check_translations_usage, files_to_scan};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+// The entry point for tools embedding the checks instead of running the
+// binary: `TranslationSet::load` reads a translation directory, `compare`
+// and `find_unused` return plain reports. Languages are still read in
+// parallel, but nothing shared between threads is exposed.
+
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    // The language `find_unused` takes the keys from.
+    pub base_lang: String,
+    // Source files with these extensions are scanned when `find_unused` is
+    // given a folder.
+    pub extensions: Vec<String>,
+    // Keys differing only in case are the same key.
+    pub key_case_insensitive: bool,
+    // Reads one language at a time.
+    pub low_memory: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            base_lang: DEFAULT_BASE_LANG.to_string(),
+            extensions: DEFAULT_EXTENSIONS.map(str::to_string).to_vec(),
+            key_case_insensitive: false,
+            low_memory: false,
+        }
+    }
+}
+
+// One translated value and the file defining it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    pub value: String,
+    pub file: PathBuf,
+}
+
+// A key of one language, as reported missing or extra. The file of a
+// missing key is the one it should be added to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyFinding {
+    pub lang: String,
+    pub key: String,
+    pub file: PathBuf,
+}
+
+// A translation whose placeholders differ from the base value's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub lang: String,
+    pub key: String,
+    pub file: PathBuf,
+    pub expected: BTreeSet<String>,
+    pub found: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnreadableFile {
+    pub lang: String,
+    pub file: PathBuf,
+    pub error: String,
+}
+
+// What `compare` found, sorted by language and key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompareReport {
+    pub base_lang: String,
+    pub missing: Vec<KeyFinding>,
+    pub extra: Vec<KeyFinding>,
+    pub mismatches: Vec<Mismatch>,
+    pub unreadable: Vec<UnreadableFile>,
+}
+
+// A base key no scanned source uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedKey {
+    pub key: String,
+    pub file: PathBuf,
+}
+
+// What `find_unused` found, sorted by key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageReport {
+    pub base_lang: String,
+    pub scanned_files: usize,
+    pub unused: Vec<UnusedKey>,
+}
+
+pub struct TranslationSet {
+    path: PathBuf,
+    options: LoadOptions,
+    languages: BTreeMap<String, BTreeMap<String, Translation>>,
+}
+
+impl TranslationSet {
+    // Reads every language of the translation directory. Fails when the
+    // directory can't be read or mixes language folders with language files;
+    // files that can't be parsed are reported by `compare` instead.
+    pub fn load(path: &Path, options: LoadOptions) -> Result<TranslationSet, String> {
+        model::layout(path)?;
+        let timings = Timings::default();
+        let project = Project::discover(path, &options.base_lang, options.low_memory, &timings);
+        let languages = project
+            .languages()
+            .map(|lang| {
+                let translations = project
+                    .get(lang)
+                    .map(|data| {
+                        data.values
+                            .iter()
+                            .map(|(key, entry)| {
+                                let translation = Translation {
+                                    value: entry.value.clone(),
+                                    file: PathBuf::from(&*entry.file),
+                                };
+                                (key.to_string(), translation)
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (lang.to_string(), translations)
+            })
+            .collect();
+        Ok(TranslationSet {
+            path: path.to_path_buf(),
+            options,
+            languages,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.languages.keys().map(String::as_str)
+    }
+
+    // The translations of a language by key.
+    pub fn get(&self, lang: &str) -> Option<&BTreeMap<String, Translation>> {
+        self.languages.get(lang)
+    }
+
+    // Runs the consistency checks against `base_lang`: keys missing from or
+    // extra in each language and placeholders that don't match.
+    pub fn compare(&self, base_lang: &str) -> Result<CompareReport, String> {
+        if !self.languages.contains_key(base_lang) {
+            return Err(format!(
+                "no base language `{}` in {}",
+                base_lang,
+                self.path.display()
+            ));
+        }
+        let timings = Timings::default();
+        let project = Project::discover(&self.path, base_lang, self.options.low_memory, &timings);
+        let base = Base::language(&project, base_lang);
+        let findings = checks::check_translations(
+            &base,
+            &project,
+            &HashSet::new(),
+            None,
+            self.options.key_case_insensitive,
+            0,
+        );
+
+        let mut report = CompareReport {
+            base_lang: base_lang.to_string(),
+            ..CompareReport::default()
+        };
+        for finding in &findings {
+            let (Some(lang), Some(key)) = (&finding.lang, &finding.key) else {
+                continue;
+            };
+            let found = || KeyFinding {
+                lang: lang.clone(),
+                key: key.clone(),
+                file: PathBuf::from(finding.file.as_deref().unwrap_or_default()),
+            };
+            match finding.check {
+                Check::MissingKey => report.missing.push(KeyFinding {
+                    file: PathBuf::from(checks::expected_file(&project, &base, lang, key)),
+                    ..found()
+                }),
+                Check::ExtraKey => report.extra.push(found()),
+                Check::VariableMismatch | Check::TranslatedPlaceholder => {
+                    report.mismatches.push(self.mismatch(finding, base_lang))
+                }
+                _ => {}
+            }
+        }
+        for (file, (lang, error)) in project.unreadable_files() {
+            report.unreadable.push(UnreadableFile {
+                lang,
+                file: PathBuf::from(&*file),
+                error,
+            });
+        }
+
+        report
+            .missing
+            .sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+        report
+            .extra
+            .sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+        report
+            .mismatches
+            .sort_by(|a, b| (&a.lang, &a.key).cmp(&(&b.lang, &b.key)));
+        Ok(report)
+    }
+
+    fn mismatch(&self, finding: &Finding, base_lang: &str) -> Mismatch {
+        let lang = finding.lang.clone().unwrap_or_default();
+        let key = finding.key.clone().unwrap_or_default();
+        let variables = |lang: &str| {
+            self.languages
+                .get(lang)
+                .and_then(|translations| translations.get(&key))
+                .map(|translation| extract_variables(&translation.value).into_iter().collect())
+                .unwrap_or_default()
+        };
+        Mismatch {
+            expected: variables(base_lang),
+            found: variables(&lang),
+            file: PathBuf::from(finding.file.as_deref().unwrap_or_default()),
+            lang,
+            key,
+        }
+    }
+
+    // The keys of the base language that no source uses. `sources` are
+    // files or folders, whose files with one of the extensions are scanned.
+    pub fn find_unused(&self, sources: &[PathBuf]) -> Result<UsageReport, String> {
+        let base_lang = &self.options.base_lang;
+        let base = self.languages.get(base_lang).ok_or_else(|| {
+            format!(
+                "no base language `{}` in {}",
+                base_lang,
+                self.path.display()
+            )
+        })?;
+        let files = files_to_scan(sources, &self.options.extensions);
+
+        let keys: HashSet<String> = base.keys().cloned().collect();
+        let unused = check_translations_usage(
+            &keys,
+            &files,
+            self.options.key_case_insensitive,
+            self.options.low_memory,
+            None,
+        )?;
+        let mut unused: Vec<UnusedKey> = unused
+            .into_iter()
+            .map(|key| UnusedKey {
+                file: base[&key].file.clone(),
+                key,
+            })
+            .collect();
+        unused.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(UsageReport {
+            base_lang: base_lang.clone(),
+            scanned_files: files.len(),
+            unused,
+        })
+    }
+}
+
+fn key_finding_json(finding: &KeyFinding) -> Value {
+    json!({
+        "lang": finding.lang,
+        "key": finding.key,
+        "file": finding.file.display().to_string(),
+    })
+}
+
+impl CompareReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.mismatches.is_empty()
+            && self.unreadable.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "base_lang": self.base_lang,
+            "missing": self.missing.iter().map(key_finding_json).collect::<Vec<_>>(),
+            "extra": self.extra.iter().map(key_finding_json).collect::<Vec<_>>(),
+            "mismatches": self.mismatches.iter().map(|mismatch| json!({
+                "lang": mismatch.lang,
+                "key": mismatch.key,
+                "file": mismatch.file.display().to_string(),
+                "expected": mismatch.expected,
+                "found": mismatch.found,
+            })).collect::<Vec<_>>(),
+            "unreadable": self.unreadable.iter().map(|unreadable| json!({
+                "lang": unreadable.lang,
+                "file": unreadable.file.display().to_string(),
+                "error": unreadable.error,
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl UsageReport {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "base_lang": self.base_lang,
+            "scanned_files": self.scanned_files,
+            "unused": self.unused.iter().map(|unused| json!({
+                "key": unused.key,
+                "file": unused.file.display().to_string(),
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use crate::state::Snapshot;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+
+// The number of most recent snapshots drawn in a sparkline.
+pub const SPARKLINE_WIDTH: usize = 30;
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// How one language's coverage moved over the recorded history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageTrend {
+    pub lang: String,
+    pub coverage: f64,
+    // Percentage points gained since the snapshots 30 and 90 days before the
+    // latest one, `None` when the history doesn't go back that far.
+    pub delta_30: Option<f64>,
+    pub delta_90: Option<f64>,
+    // `(date, coverage)` for every snapshot recording the language.
+    pub history: Vec<(String, f64)>,
+}
+
+// The `trend` of a state file's history, measured from its latest snapshot
+// rather than from today, so that a history that stopped being updated still
+// reads the same.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend<'a> {
+    pub history: &'a [Snapshot],
+    pub languages: Vec<LanguageTrend>,
+}
+
+impl<'a> Trend<'a> {
+    pub fn new(history: &'a [Snapshot]) -> Option<Trend<'a>> {
+        let latest = history.last()?;
+        let languages = latest
+            .coverage
+            .iter()
+            .map(|(lang, coverage)| {
+                let delta = |days_ago| {
+                    snapshot_before(history, &latest.date, days_ago)
+                        .and_then(|snapshot| snapshot.coverage.get(lang))
+                        .map(|before| round(coverage - before))
+                };
+                LanguageTrend {
+                    lang: lang.clone(),
+                    coverage: *coverage,
+                    delta_30: delta(30),
+                    delta_90: delta(90),
+                    history: history
+                        .iter()
+                        .filter_map(|snapshot| {
+                            Some((snapshot.date.clone(), *snapshot.coverage.get(lang)?))
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        Some(Trend { history, languages })
+    }
+
+    pub fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        let (first, latest) = (&self.history[0], &self.history[self.history.len() - 1]);
+        writeln!(
+            out,
+            "Coverage trend ({} snapshots, {} to {})",
+            self.history.len(),
+            first.date,
+            latest.date
+        )?;
+        let width = self
+            .languages
+            .iter()
+            .map(|language| language.lang.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Language".len());
+        writeln!(
+            out,
+            "{:<width$}  {:>8}  {:>7}  {:>7}  History",
+            "Language", "Coverage", "30 days", "90 days"
+        )?;
+        for language in &self.languages {
+            let values: Vec<f64> = language.history.iter().map(|(_, value)| *value).collect();
+            writeln!(
+                out,
+                "{:<width$}  {:>8}  {:>7}  {:>7}  {}",
+                language.lang,
+                format!("{:.1}%", language.coverage),
+                format_delta(language.delta_30),
+                format_delta(language.delta_90),
+                sparkline(&values[values.len().saturating_sub(SPARKLINE_WIDTH)..])
+            )?;
+        }
+        writeln!(
+            out,
+            "Findings: {} errors, {} warnings",
+            latest.errors, latest.warnings
+        )
+    }
+
+    pub fn to_json(&self) -> Value {
+        let latest = &self.history[self.history.len() - 1];
+        json!({
+            "snapshots": self.history.len(),
+            "from": self.history[0].date,
+            "to": latest.date,
+            "languages": self.languages.iter().map(|language| json!({
+                "lang": language.lang,
+                "coverage": language.coverage,
+                "delta_30d": language.delta_30,
+                "delta_90d": language.delta_90,
+                "history": language.history.iter().map(|(date, coverage)| json!({
+                    "date": date,
+                    "coverage": coverage,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+            "findings": {
+                "errors": latest.errors,
+                "warnings": latest.warnings,
+                "history": self.history.iter().map(|snapshot| json!({
+                    "date": snapshot.date,
+                    "errors": snapshot.errors,
+                    "warnings": snapshot.warnings,
+                })).collect::<Vec<_>>(),
+            },
+        })
+    }
+}
+
+// One bar per value, scaled between the lowest and highest of them; a flat
+// line sits at the height of its value out of 100%.
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            let level = if max > min {
+                (value - min) / (max - min)
+            } else {
+                value / 100.0
+            };
+            BARS[((level * (BARS.len() - 1) as f64).round() as usize).min(BARS.len() - 1)]
+        })
+        .collect()
+}
+
+// The number of days since 1970-01-01 of a `YYYY-MM-DD` date.
+pub fn days(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-').map(str::parse::<i64>);
+    let (year, month, day) = (
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+        parts.next()?.ok()?,
+    );
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Howard Hinnant's civil-to-days conversion, the inverse of
+    // `waivers::today`.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+// The latest snapshot taken at least `days_ago` days before `date`.
+fn snapshot_before<'a>(history: &'a [Snapshot], date: &str, days_ago: i64) -> Option<&'a Snapshot> {
+    let cutoff = days(date)? - days_ago;
+    history
+        .iter()
+        .rev()
+        .find(|snapshot| days(&snapshot.date).is_some_and(|day| day <= cutoff))
+}
+
+fn format_delta(delta: Option<f64>) -> String {
+    match delta {
+        Some(delta) => format!("{:+.1}", delta),
+        None => "—".to_string(),
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
@@ -0,0 +1,71 @@
+use std::borrow::Cow;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc, is_nfc_quick};
+
+// A run of characters that NFC writes differently, such as `e` followed by
+// U+0301 COMBINING ACUTE ACCENT for `é`, or marks in the wrong canonical
+// order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decomposed {
+    pub original: String,
+    pub composed: String,
+}
+
+impl Decomposed {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} should be {}",
+            code_points(&self.original),
+            code_points(&self.composed)
+        )
+    }
+}
+
+pub fn code_points(text: &str) -> String {
+    text.chars()
+        .map(|c| format!("U+{:04X}", c as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Whether NFC never combines `c` with what comes before it, so the text can
+// be normalized separately on either side of it.
+fn starts_segment(c: char) -> bool {
+    canonical_combining_class(c) == 0 && is_nfc_quick(std::iter::once(c)) != IsNormalized::Maybe
+}
+
+pub fn nfc(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() || is_nfc(text) {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(text.nfc().collect())
+    }
+}
+
+// The runs of `text` that are not in NFC, empty when it is already in NFC.
+pub fn decomposed(text: &str) -> Vec<Decomposed> {
+    if text.is_ascii() || is_nfc(text) {
+        return Vec::new();
+    }
+
+    let mut starts: Vec<usize> = text
+        .char_indices()
+        .filter(|(index, c)| *index == 0 || starts_segment(*c))
+        .map(|(index, _)| index)
+        .collect();
+    starts.push(text.len());
+
+    starts
+        .windows(2)
+        .filter_map(|bounds| {
+            let segment = &text[bounds[0]..bounds[1]];
+            match nfc(segment) {
+                Cow::Borrowed(_) => None,
+                Cow::Owned(composed) => Some(Decomposed {
+                    original: segment.to_string(),
+                    composed,
+                }),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,788 @@
+use crate::handles;
+use crate::sha256;
+use aho_corasick::AhoCorasick;
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use regex::bytes::Regex;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// How much of a source file the low-memory scan holds at a time.
+const STREAM_BUFFER_SIZE: usize = 64 * 1024;
+
+lazy_static! {
+    // The static start of a key built at runtime, as in `t(`errors.${code}`)`
+    // or `t('errors.' + code)`.
+    static ref PREFIX_REGEX: Regex =
+        Regex::new(r#"`([^`$\\\n]+)\$\{|'([^'\\\n]+)'\s*\+|"([^"\\\n]+)"\s*\+"#).unwrap();
+}
+
+// The extensions of the source files scanned in a folder unless told
+// otherwise.
+pub const DEFAULT_EXTENSIONS: [&str; 3] = ["ts", "js", "vue"];
+
+// The translation calls a key counts as used in with `[usage] mode =
+// "calls"`, each capturing the key: `t('key')`, `$t('key')`, `tc` and `te`,
+// `i18n.translate('key')` and `translate.instant('key')`, Angular's
+// `'key' | translate` pipes and the `keypath` of `<i18n-t>`.
+pub const CALL_PATTERNS: [&str; 4] = [
+    r#"\bt[ce]?\(\s*['"`]([^'"`\n]+)['"`]"#,
+    r#"\btranslate(?:\.instant|\.get|\.stream)?\(\s*['"`]([^'"`\n]+)['"`]"#,
+    r#"['"]([^'"\n]+)['"]\s*\|\s*translate\b"#,
+    r#"(?:^|\s)keypath\s*=\s*"([^"\n]+)""#,
+];
+
+// How the sources are searched for the keys they use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageMode {
+    // Any string literal spelling a key exactly uses it.
+    Quoted,
+    // Only the keys translation calls name are used.
+    Calls,
+}
+
+impl UsageMode {
+    pub fn parse(name: &str) -> Option<UsageMode> {
+        match name {
+            "quoted" => Some(UsageMode::Quoted),
+            "calls" => Some(UsageMode::Calls),
+            _ => None,
+        }
+    }
+}
+
+// A key a translation call names: the whole key, or the static start of
+// one built at runtime, as in `t(`errors.${code}`)` or `t('errors.' + code)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallKey {
+    pub key: String,
+    pub prefix: bool,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+// The keys the translation calls of the sources name, comments left out.
+pub fn find_call_keys(files: &[PathBuf], patterns: &[regex::Regex]) -> Vec<CallKey> {
+    let mut keys: Vec<CallKey> = files
+        .par_iter()
+        .filter_map(|file_path| {
+            let mut content = handles::map(file_path).ok()?;
+            CommentStripper::new().strip(&mut content);
+            let content = String::from_utf8_lossy(&content);
+            let mut keys = Vec::new();
+            for pattern in patterns {
+                for call in pattern.captures_iter(&content) {
+                    let (Some(whole), Some(key)) = (call.get(0), call.get(1)) else {
+                        continue;
+                    };
+                    let (key, prefix) = match key.as_str().split_once("${") {
+                        Some((start, _)) => (start, true),
+                        None => (
+                            key.as_str(),
+                            content[whole.end()..].trim_start().starts_with('+'),
+                        ),
+                    };
+                    if key.is_empty() {
+                        continue;
+                    }
+                    keys.push(CallKey {
+                        key: key.to_string(),
+                        prefix,
+                        file: file_path.clone(),
+                        line: content[..whole.start()].matches('\n').count() + 1,
+                    });
+                }
+            }
+            Some(keys)
+        })
+        .flatten()
+        .collect();
+    keys.sort_by(|a, b| (&a.file, a.line, &a.key).cmp(&(&b.file, b.line, &b.key)));
+    keys
+}
+
+// A folder scanned for usages, such as each app of a monorepo, and the
+// extensions of the files scanned in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRoot {
+    pub dir: PathBuf,
+    pub extensions: Vec<String>,
+}
+
+impl SourceRoot {
+    // Whether `path`, relative to the same folder as `dir`, is a source file
+    // of this root.
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.dir)
+            && path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| self.extensions.iter().any(|known| known == ext))
+    }
+}
+
+// The source files of every root, once each when roots are nested.
+pub fn source_files(roots: &[SourceRoot]) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = roots
+        .par_iter()
+        .flat_map(|root| {
+            // A root may be a single file, scanned whatever its extension.
+            if root.dir.is_file() {
+                return vec![root.dir.clone()];
+            }
+            root.extensions
+                .par_iter()
+                .flat_map(|ext| get_all_files_by_extension(&root.dir, ext))
+                .collect()
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+// `file` relative to the first root holding it, for the patterns written
+// relative to the source folder.
+pub fn relative_to_root<'a>(file: &'a Path, roots: &[SourceRoot]) -> &'a Path {
+    roots
+        .iter()
+        .find_map(|root| file.strip_prefix(&root.dir).ok())
+        .unwrap_or(file)
+}
+
+// The folders of `roots`, as listed to say where a run looked.
+pub fn describe_roots(roots: &[SourceRoot]) -> String {
+    roots
+        .iter()
+        .map(|root| root.dir.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// The files of `sources` to scan: files as given, and the files of folders
+// with one of `extensions`.
+pub fn files_to_scan(sources: &[PathBuf], extensions: &[String]) -> Vec<PathBuf> {
+    sources
+        .iter()
+        .flat_map(|source| {
+            if source.is_dir() {
+                extensions
+                    .iter()
+                    .flat_map(|ext| get_all_files_by_extension(source, ext))
+                    .collect()
+            } else {
+                vec![source.clone()]
+            }
+        })
+        .collect()
+}
+
+pub fn get_all_files_by_extension(path: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    if let Ok(entries) = path.read_dir() {
+        files.extend(entries.filter_map(Result::ok).flat_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                get_all_files_by_extension(&path, extension)
+            } else if path.extension().and_then(|ext| ext.to_str()) == Some(extension) {
+                vec![path]
+            } else {
+                vec![]
+            }
+        }));
+    }
+    files
+}
+
+#[derive(Clone, Copy)]
+enum Lexed {
+    Code,
+    Quoted(u8),
+    Escaped(u8),
+    LineComment,
+    BlockComment,
+    HtmlComment,
+}
+
+// Blanks out `//`, `/* */` and `<!-- -->` comments, so that keys left in
+// commented-out code don't count as used. Source is fed chunk by chunk in
+// low-memory mode, so where the last chunk ended is remembered.
+struct CommentStripper {
+    state: Lexed,
+    // The last three bytes as read, before blanking.
+    recent: [u8; 3],
+}
+
+impl CommentStripper {
+    fn new() -> CommentStripper {
+        CommentStripper {
+            state: Lexed::Code,
+            recent: [0; 3],
+        }
+    }
+
+    fn strip(&mut self, chunk: &mut [u8]) {
+        for byte in chunk.iter_mut() {
+            let c = *byte;
+            let [_, second, last] = self.recent;
+            let blank = !matches!(
+                self.state,
+                Lexed::Code | Lexed::Quoted(_) | Lexed::Escaped(_)
+            );
+            self.state = match self.state {
+                Lexed::Code => match c {
+                    b'\'' | b'"' | b'`' => Lexed::Quoted(c),
+                    b'/' if last == b'/' => Lexed::LineComment,
+                    b'*' if last == b'/' => Lexed::BlockComment,
+                    b'-' if self.recent == *b"<!-" => Lexed::HtmlComment,
+                    _ => Lexed::Code,
+                },
+                Lexed::Quoted(quote) => match c {
+                    b'\\' => Lexed::Escaped(quote),
+                    b'\n' if quote != b'`' => Lexed::Code,
+                    _ if c == quote => Lexed::Code,
+                    _ => Lexed::Quoted(quote),
+                },
+                Lexed::Escaped(quote) => Lexed::Quoted(quote),
+                Lexed::LineComment if c == b'\n' => Lexed::Code,
+                Lexed::BlockComment if c == b'/' && last == b'*' => Lexed::Code,
+                Lexed::HtmlComment if c == b'>' && second == b'-' && last == b'-' => Lexed::Code,
+                state => state,
+            };
+            self.recent = [second, last, c];
+            if blank && c != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+}
+
+// What the sources reference: keys quoted exactly, as in `'common.save'`,
+// so that `'common.saveAndExit'` doesn't count for `common.save`, and the
+// prefixes of keys built at runtime.
+#[derive(Default)]
+struct References {
+    // Indexes into the keys the matcher was built from.
+    exact: HashSet<usize>,
+    prefixes: HashSet<String>,
+}
+
+impl References {
+    // Matches touching the end of a chunk that isn't the last one are left
+    // for the next one, which starts with the end of this one.
+    fn find(&mut self, text: &[u8], matcher: &AhoCorasick, last: bool) {
+        for found in matcher.find_overlapping_iter(text) {
+            let (Some(before), Some(after)) = (
+                found.start().checked_sub(1).map(|index| text[index]),
+                text.get(found.end()).copied(),
+            ) else {
+                continue;
+            };
+            if matches!(before, b'\'' | b'"' | b'`') && after == before {
+                self.exact.insert(found.pattern().as_usize());
+            }
+        }
+
+        for caps in PREFIX_REGEX.captures_iter(text) {
+            let whole = caps.get(0).unwrap();
+            if !last && whole.end() == text.len() {
+                continue;
+            }
+            let prefix = caps.get(1).or(caps.get(2)).or(caps.get(3)).unwrap();
+            self.prefixes
+                .insert(String::from_utf8_lossy(prefix.as_bytes()).to_string());
+        }
+    }
+
+    fn merge(mut self, other: References) -> References {
+        self.exact.extend(other.exact);
+        self.prefixes.extend(other.prefixes);
+        self
+    }
+
+    // The keys referenced exactly or starting with a referenced prefix.
+    fn used(self, keys: &[&String], case_insensitive: bool) -> HashSet<String> {
+        let fold = |text: &str| {
+            if case_insensitive {
+                text.to_lowercase()
+            } else {
+                text.to_string()
+            }
+        };
+        let mut sorted: Vec<(String, &String)> = keys.iter().map(|key| (fold(key), *key)).collect();
+        sorted.sort();
+
+        let mut used: HashSet<String> = self
+            .exact
+            .into_iter()
+            .map(|index| keys[index].clone())
+            .collect();
+        for prefix in self.prefixes.iter().map(|prefix| fold(prefix)) {
+            let start = sorted.partition_point(|(folded, _)| *folded < prefix);
+            used.extend(
+                sorted[start..]
+                    .iter()
+                    .take_while(|(folded, _)| folded.starts_with(&prefix))
+                    .map(|(_, key)| (*key).clone()),
+            );
+        }
+        used
+    }
+}
+
+// The references of each file, or why it couldn't be read. All keys are
+// searched for at once, in one pass over each file, mapped rather than read
+// when large.
+fn process_files(
+    files: &[PathBuf],
+    keys: &[&String],
+    case_insensitive: bool,
+) -> Vec<Result<References, String>> {
+    let needles: Vec<String> = keys
+        .iter()
+        .map(|key| {
+            if case_insensitive {
+                key.to_lowercase()
+            } else {
+                (*key).clone()
+            }
+        })
+        .collect();
+    let matcher = AhoCorasick::new(&needles).expect("Failed to build key matcher");
+
+    files
+        .par_iter()
+        .map(|file_path| {
+            let mut content = handles::map(file_path).map_err(|err| unreadable(file_path, err))?;
+            if case_insensitive {
+                match std::str::from_utf8(&content) {
+                    // Lowercasing may change the length, so it takes a copy.
+                    Ok(text) => content = handles::Contents::Read(text.to_lowercase().into_bytes()),
+                    // Other encodings are searched as their bytes, as
+                    // `--low-memory` searches every file.
+                    Err(_) => content.make_ascii_lowercase(),
+                }
+            }
+            CommentStripper::new().strip(&mut content);
+            let mut references = References::default();
+            references.find(&content, &matcher, true);
+            Ok(references)
+        })
+        .collect()
+}
+
+// Low-memory variant of `process_files`: every file is read through a
+// fixed-size buffer and searched for all keys at once. The end of each chunk
+// is kept in front of the next one so that keys spanning two reads are still
+// found, along with the quotes around them. Case-insensitive matching only
+// folds ASCII letters here.
+fn stream_files(
+    files: &[PathBuf],
+    keys: &[&String],
+    case_insensitive: bool,
+) -> Vec<Result<References, String>> {
+    let matcher = AhoCorasick::builder()
+        .ascii_case_insensitive(case_insensitive)
+        .build(keys)
+        .expect("Failed to build key matcher");
+    let overlap = keys.iter().map(|key| key.len()).max().unwrap_or(0) + 2;
+
+    files
+        .par_iter()
+        .map(|file_path| {
+            let mut file = handles::open(file_path).map_err(|err| unreadable(file_path, err))?;
+            let mut references = References::default();
+            let mut stripper = CommentStripper::new();
+            let mut buffer = vec![0; overlap + STREAM_BUFFER_SIZE];
+            let mut filled = 0;
+
+            loop {
+                let read = match file.read(&mut buffer[filled..]) {
+                    Ok(0) => break,
+                    Ok(read) => read,
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(unreadable(file_path, err)),
+                };
+                stripper.strip(&mut buffer[filled..filled + read]);
+                filled += read;
+                references.find(&buffer[..filled], &matcher, false);
+                let keep = overlap.min(filled);
+                buffer.copy_within(filled - keep..filled, 0);
+                filled = keep;
+            }
+            references.find(&buffer[..filled], &matcher, true);
+            Ok(references)
+        })
+        .collect()
+}
+
+// Where each source file quotes a base key exactly, as `(key, line)`, for
+// the checks that need to know which file uses a key rather than whether
+// any file does. Keys built at runtime aren't attributed.
+pub fn references_by_file(
+    files: &[PathBuf],
+    base_keys: &HashSet<String>,
+    case_insensitive: bool,
+) -> Vec<(PathBuf, Vec<(String, usize)>)> {
+    let keys: Vec<&String> = base_keys.iter().collect();
+    let matcher = AhoCorasick::builder()
+        .ascii_case_insensitive(case_insensitive)
+        .build(&keys)
+        .expect("Failed to build key matcher");
+
+    let mut references: Vec<(PathBuf, Vec<(String, usize)>)> = files
+        .par_iter()
+        .filter_map(|file_path| {
+            let mut content = handles::map(file_path).ok()?;
+            CommentStripper::new().strip(&mut content);
+            let mut found = Vec::new();
+            let (mut line, mut counted) = (1, 0);
+            for usage in matcher.find_overlapping_iter(&*content) {
+                let (Some(before), Some(after)) = (
+                    usage.start().checked_sub(1).map(|index| content[index]),
+                    content.get(usage.end()).copied(),
+                ) else {
+                    continue;
+                };
+                if !matches!(before, b'\'' | b'"' | b'`') || after != before {
+                    continue;
+                }
+                // Overlapping matches may start before the previous one.
+                if usage.start() > counted {
+                    line += content[counted..usage.start()]
+                        .iter()
+                        .filter(|&&byte| byte == b'\n')
+                        .count();
+                    counted = usage.start();
+                }
+                found.push((keys[usage.pattern().as_usize()].clone(), line));
+            }
+            (!found.is_empty()).then(|| (file_path.clone(), found))
+        })
+        .collect();
+    references.sort();
+    references
+}
+
+// The base keys no translation call names, nor starts when the call builds
+// its key at runtime.
+pub fn unused_by_calls(
+    base_keys: &HashSet<String>,
+    calls: &[CallKey],
+    case_insensitive: bool,
+) -> HashSet<String> {
+    let fold = |text: &str| {
+        if case_insensitive {
+            text.to_lowercase()
+        } else {
+            text.to_string()
+        }
+    };
+    let keys: Vec<&String> = base_keys.iter().collect();
+    let indexes: HashMap<String, usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (fold(key), index))
+        .collect();
+    let mut references = References::default();
+    for call in calls {
+        if call.prefix {
+            references.prefixes.insert(call.key.clone());
+        } else if let Some(index) = indexes.get(&fold(&call.key)) {
+            references.exact.insert(*index);
+        }
+    }
+    let used = references.used(&keys, case_insensitive);
+    base_keys.difference(&used).cloned().collect()
+}
+
+// The base keys no source file quotes, nor starts when building a key at
+// runtime. With a `cache`, the files it holds unchanged aren't read again,
+// and those scanned are recorded in it. A file that can't be read is an
+// error: the keys only it uses would be reported unused.
+pub fn check_translations_usage(
+    base_keys: &HashSet<String>,
+    files: &[PathBuf],
+    case_insensitive: bool,
+    low_memory: bool,
+    mut cache: Option<&mut ScanCache>,
+) -> Result<HashSet<String>, String> {
+    let keys: Vec<&String> = base_keys.iter().collect();
+    let indexes: HashMap<&str, usize> = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (key.as_str(), index))
+        .collect();
+    let mut references = References::default();
+    let mut stale = Vec::new();
+    for file in files {
+        match cache.as_deref_mut().and_then(|cache| cache.get(file)) {
+            Some(cached) => {
+                let exact = cached
+                    .exact
+                    .iter()
+                    .filter_map(|key| indexes.get(key.as_str()));
+                references.exact.extend(exact);
+                references.prefixes.extend(cached.prefixes.iter().cloned());
+            }
+            None => stale.push(file.clone()),
+        }
+    }
+
+    let scanned = if low_memory {
+        stream_files(&stale, &keys, case_insensitive)
+    } else {
+        process_files(&stale, &keys, case_insensitive)
+    };
+    for (file, found) in stale.into_iter().zip(scanned) {
+        let found = found?;
+        if let Some(cache) = cache.as_deref_mut() {
+            cache.insert(file, &found, &keys);
+        }
+        references = references.merge(found);
+    }
+
+    let used_keys = references.used(&keys, case_insensitive);
+    Ok(base_keys.difference(&used_keys).cloned().collect())
+}
+
+fn unreadable(path: &Path, err: io::Error) -> String {
+    format!("failed to read source file {}: {}", path.display(), err)
+}
+
+// Raised when the scan changes what it finds, for the caches written before
+// to be scanned again.
+const SCAN_CACHE_VERSION: u64 = 1;
+
+// Where the scan cache is kept, under the cache directory.
+pub const SCAN_CACHE_FILE: &str = "usage-scan.json";
+
+// What a source file referenced when it was last scanned, as it was then.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedScan {
+    // Nanoseconds since the Unix epoch.
+    pub modified: u64,
+    pub size: u64,
+    pub exact: Vec<String>,
+    pub prefixes: Vec<String>,
+}
+
+// The keys each source file referenced when it was last scanned, so that
+// repeat runs only read the files modified since. What a file references
+// depends on the keys searched for, so the cache is dropped whole when the
+// base keys, or how they are matched, change.
+#[derive(Debug)]
+pub struct ScanCache {
+    path: PathBuf,
+    fingerprint: String,
+    files: BTreeMap<PathBuf, CachedScan>,
+    hits: usize,
+}
+
+// Whether the scan cache of a run is kept in memory for the next one, by a
+// process checking more than once (`--watch`, `serve`), and the cache kept.
+static KEEP_SCANS: AtomicBool = AtomicBool::new(false);
+static KEPT_SCANS: Mutex<Option<ScanCache>> = Mutex::new(None);
+
+pub fn keep_scans() {
+    KEEP_SCANS.store(true, Ordering::SeqCst);
+}
+
+pub fn keeps_scans() -> bool {
+    KEEP_SCANS.load(Ordering::SeqCst)
+}
+
+impl ScanCache {
+    // The cache at `path`, empty when it is missing, unreadable or was
+    // written for other keys.
+    pub fn load(
+        path: &Path,
+        base_keys: &HashSet<String>,
+        case_insensitive: bool,
+        low_memory: bool,
+    ) -> ScanCache {
+        let mut cache = ScanCache::empty(path, base_keys, case_insensitive, low_memory);
+        let Some(json) = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        else {
+            return cache;
+        };
+        if json.get("fingerprint").and_then(Value::as_str) != Some(&cache.fingerprint) {
+            return cache;
+        }
+        let strings = |value: &Value, name: &str| -> Vec<String> {
+            value
+                .get(name)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        };
+        for (file, scan) in json
+            .get("files")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let (Some(modified), Some(size)) = (
+                scan.get("modified").and_then(Value::as_u64),
+                scan.get("size").and_then(Value::as_u64),
+            ) else {
+                continue;
+            };
+            cache.files.insert(
+                PathBuf::from(file),
+                CachedScan {
+                    modified,
+                    size,
+                    exact: strings(scan, "exact"),
+                    prefixes: strings(scan, "prefixes"),
+                },
+            );
+        }
+        cache
+    }
+
+    // The cache the previous run of this process kept for the same keys,
+    // or else the one at `path` when `on_disk`, or an empty one.
+    pub fn resume(
+        path: &Path,
+        base_keys: &HashSet<String>,
+        case_insensitive: bool,
+        low_memory: bool,
+        on_disk: bool,
+    ) -> ScanCache {
+        let empty = ScanCache::empty(path, base_keys, case_insensitive, low_memory);
+        let kept = KEPT_SCANS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take();
+        match kept {
+            Some(mut kept) if kept.fingerprint == empty.fingerprint && kept.path == empty.path => {
+                kept.hits = 0;
+                kept
+            }
+            _ if on_disk => ScanCache::load(path, base_keys, case_insensitive, low_memory),
+            _ => empty,
+        }
+    }
+
+    // Keeps the scans of `files` for the next run of this process, when
+    // `keep_scans` was called.
+    pub fn keep(mut self, files: &[PathBuf]) {
+        if !keeps_scans() {
+            return;
+        }
+        let files: HashSet<&PathBuf> = files.iter().collect();
+        self.files.retain(|file, _| files.contains(file));
+        *KEPT_SCANS.lock().unwrap_or_else(|err| err.into_inner()) = Some(self);
+    }
+
+    fn empty(
+        path: &Path,
+        base_keys: &HashSet<String>,
+        case_insensitive: bool,
+        low_memory: bool,
+    ) -> ScanCache {
+        let mut keys: Vec<&String> = base_keys.iter().collect();
+        keys.sort();
+        let mut identity = format!(
+            "{}\n{}\n{}\n",
+            SCAN_CACHE_VERSION, case_insensitive, low_memory
+        );
+        for key in keys {
+            identity.push_str(key);
+            identity.push('\n');
+        }
+        ScanCache {
+            path: path.to_path_buf(),
+            fingerprint: sha256::hex_digest(identity.as_bytes()),
+            files: BTreeMap::new(),
+            hits: 0,
+        }
+    }
+
+    // What `file` referenced, if it hasn't changed since it was scanned.
+    pub fn get(&mut self, file: &Path) -> Option<&CachedScan> {
+        let (modified, size) = handles::stamp(file)?;
+        let cached = self
+            .files
+            .get(file)
+            .filter(|cached| cached.modified == modified && cached.size == size)?;
+        self.hits += 1;
+        Some(cached)
+    }
+
+    fn insert(&mut self, file: PathBuf, references: &References, keys: &[&String]) {
+        let Some((modified, size)) = handles::stamp(&file) else {
+            return;
+        };
+        let mut exact: Vec<String> = references
+            .exact
+            .iter()
+            .map(|index| keys[*index].clone())
+            .collect();
+        exact.sort();
+        let mut prefixes: Vec<String> = references.prefixes.iter().cloned().collect();
+        prefixes.sort();
+        self.files.insert(
+            file,
+            CachedScan {
+                modified,
+                size,
+                exact,
+                prefixes,
+            },
+        );
+    }
+
+    // How many files were read from the cache instead of scanned.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    // Writes the files of `files` back, those no longer scanned dropped.
+    pub fn save(&mut self, files: &[PathBuf]) -> Result<(), String> {
+        let files: HashSet<&PathBuf> = files.iter().collect();
+        self.files.retain(|file, _| files.contains(file));
+        let scans: Map<String, Value> = self
+            .files
+            .iter()
+            .map(|(file, scan)| {
+                (
+                    file.display().to_string(),
+                    json!({
+                        "modified": scan.modified,
+                        "size": scan.size,
+                        "exact": scan.exact,
+                        "prefixes": scan.prefixes,
+                    }),
+                )
+            })
+            .collect();
+        let content = json!({
+            "fingerprint": self.fingerprint,
+            "files": scans,
+        });
+        let write_error = |err: std::io::Error| {
+            format!(
+                "failed to write the scan cache {}: {}",
+                self.path.display(),
+                err
+            )
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(write_error)?;
+        }
+        fs::write(&self.path, content.to_string()).map_err(write_error)
+    }
+}
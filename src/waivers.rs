@@ -0,0 +1,309 @@
+use crate::render;
+use crate::report::{Check, Finding, Report};
+use crate::selector::KeyPattern;
+use crate::toml;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// One accepted finding. A waiver matches the findings of its check whose
+// language and file equal the ones it gives and whose key matches its key
+// pattern, as in `legal.**`; the fields it leaves out match anything.
+#[derive(Debug, Clone)]
+pub struct Waiver {
+    pub check: Option<Check>,
+    pub lang: Option<String>,
+    pub key: Option<KeyPattern>,
+    pub file: Option<String>,
+    // `YYYY-MM-DD`; the waiver stops applying on that day.
+    pub expires: Option<String>,
+}
+
+// The `[[waiver]]` entries of a waivers file, or the `[[ignore]]` ones of
+// the config file, in file order.
+#[derive(Debug, Default)]
+pub struct Waivers {
+    pub source: String,
+    pub waivers: Vec<Waiver>,
+}
+
+impl Waiver {
+    // Waives exactly this finding.
+    pub fn of(finding: &Finding) -> Waiver {
+        Waiver {
+            check: Some(finding.check),
+            lang: finding.lang.clone(),
+            key: finding.key.as_deref().map(KeyPattern::literal),
+            // The key already pins the finding down; the file is kept for
+            // findings without one, such as missing loader files.
+            file: finding.file.clone().filter(|_| finding.key.is_none()),
+            expires: None,
+        }
+    }
+
+    pub fn matches(&self, finding: &Finding) -> bool {
+        let field =
+            |waived: &Option<String>, found: &Option<String>| waived.is_none() || waived == found;
+        self.check.is_none_or(|check| check == finding.check)
+            && field(&self.lang, &finding.lang)
+            && self.key.as_ref().is_none_or(|key| {
+                finding
+                    .key
+                    .as_deref()
+                    .is_some_and(|found| key.matches(found))
+            })
+            && field(&self.file, &finding.file)
+    }
+
+    pub fn expired(&self, today: &str) -> bool {
+        self.expires
+            .as_deref()
+            .is_some_and(|expires| expires <= today)
+    }
+
+    // What the waiver matches, as listed in expired and unused findings.
+    fn describe(&self) -> String {
+        let mut parts: Vec<String> = self
+            .check
+            .iter()
+            .map(|check| check.info().id.to_string())
+            .collect();
+        parts.extend(self.lang.clone());
+        parts.extend(self.key.as_ref().map(|key| key.as_str().to_string()));
+        parts.extend(self.file.clone());
+        parts.join(" ")
+    }
+
+    // `kind` names the entry in errors: `waiver` or `ignore`. A waiver
+    // needs a check or a key, so that it can't accept every finding.
+    pub fn from_value(value: &Value, kind: &str, index: usize) -> Result<Waiver, String> {
+        let name = format!("{} #{}", kind, index + 1);
+        let string = |field: &str| match value.get(field) {
+            None => Ok(None),
+            Some(Value::String(text)) => Ok(Some(text.clone())),
+            Some(_) => Err(format!("`{}` of {} must be a string", field, name)),
+        };
+
+        let check = string("check")?
+            .map(|id| {
+                Check::from_id(&id)
+                    .filter(|check| !matches!(check, Check::ExpiredWaiver | Check::UnusedWaiver))
+                    .ok_or_else(|| format!("unknown check `{}` in {}", id, name))
+            })
+            .transpose()?;
+        let key = string("key")?
+            .map(|key| KeyPattern::new(&key).map_err(|err| format!("{} in {}", err, name)))
+            .transpose()?;
+        if check.is_none() && key.is_none() {
+            return Err(format!("{} needs a `check` or a `key`", name));
+        }
+        let expires = string("expires")?;
+        if let Some(expires) = &expires
+            && !is_date(expires)
+        {
+            return Err(format!(
+                "`expires` of {} must be a `YYYY-MM-DD` date, found `{}`",
+                name, expires
+            ));
+        }
+
+        Ok(Waiver {
+            check,
+            lang: string("lang")?,
+            key,
+            file: string("file")?,
+            expires,
+        })
+    }
+}
+
+impl Waivers {
+    pub fn load(path: &Path) -> Result<Waivers, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read waivers {}: {}", path.display(), err))?;
+        let value = toml::parse(&source)
+            .map_err(|err| format!("invalid waivers {}: {}", path.display(), err))?;
+        let waivers = match value.get("waiver") {
+            None => Vec::new(),
+            Some(Value::Array(waivers)) => waivers
+                .iter()
+                .enumerate()
+                .map(|(index, waiver)| Waiver::from_value(waiver, "waiver", index))
+                .collect::<Result<_, _>>()
+                .map_err(|err| format!("invalid waivers {}: {}", path.display(), err))?,
+            Some(_) => {
+                return Err(format!(
+                    "invalid waivers {}: `waiver` must be an array of tables",
+                    path.display()
+                ));
+            }
+        };
+        Ok(Waivers {
+            source: path.display().to_string(),
+            waivers,
+        })
+    }
+
+    // Whether a waiver that hasn't expired matches the finding.
+    pub fn accepts(&self, finding: &Finding, today: &str) -> bool {
+        self.waivers
+            .iter()
+            .any(|waiver| !waiver.expired(today) && waiver.matches(finding))
+    }
+
+    // Drops the findings matched by a waiver that hasn't expired, counting
+    // them in the report, and reports the expired waivers and the ones that
+    // matched nothing.
+    pub fn apply(&self, report: &mut Report, today: &str) {
+        let mut used = vec![false; self.waivers.len()];
+        let mut waived = 0;
+        report.findings.retain(|finding| {
+            let waiver = self
+                .waivers
+                .iter()
+                .position(|waiver| !waiver.expired(today) && waiver.matches(finding));
+            match waiver {
+                Some(index) => {
+                    used[index] = true;
+                    waived += finding.weight;
+                    false
+                }
+                None => true,
+            }
+        });
+        *report.waived.get_or_insert(0) += waived;
+
+        for (index, waiver) in self.waivers.iter().enumerate() {
+            let finding = if waiver.expired(today) {
+                Finding::new(Check::ExpiredWaiver)
+                    .detail("Expired", waiver.expires.clone().unwrap())
+            } else if !used[index] {
+                Finding::new(Check::UnusedWaiver)
+            } else {
+                continue;
+            };
+            report.findings.push(
+                finding
+                    .detail("Waiver", format!("#{} {}", index + 1, waiver.describe()))
+                    .file(&*self.source),
+            );
+        }
+    }
+}
+
+// Writes a `[[waiver]]` stanza for every finding of the report, grouped by
+// check, for a reviewer to prune and append to the waivers file.
+pub fn emit(path: &Path, report: &Report, today: &str) -> Result<usize, String> {
+    let mut by_check: BTreeMap<Check, Vec<&Finding>> = BTreeMap::new();
+    for finding in report
+        .findings
+        .iter()
+        .filter(|finding| !matches!(finding.check, Check::ExpiredWaiver | Check::UnusedWaiver))
+    {
+        by_check.entry(finding.check).or_default().push(finding);
+    }
+
+    let mut text = String::new();
+    for (check, findings) in &by_check {
+        let info = check.info();
+        text.push_str(&format!(
+            "# {} {} ({})\n\n",
+            info.emoji,
+            info.title,
+            findings.len()
+        ));
+        for finding in findings {
+            let waiver = Waiver::of(finding);
+            text.push_str(&format!("# {} (found {})\n", summary(finding), today));
+            text.push_str("[[waiver]]\n");
+            text.push_str(&format!("check = {}\n", toml::quote(info.id)));
+            let key = waiver.key.as_ref().map(|key| key.as_str().to_string());
+            for (field, value) in [
+                ("lang", &waiver.lang),
+                ("key", &key),
+                ("file", &waiver.file),
+            ] {
+                if let Some(value) = value {
+                    text.push_str(&format!("{} = {}\n", field, toml::quote(value)));
+                }
+            }
+            text.push('\n');
+        }
+    }
+
+    render::write_atomically(path, |out| out.write_all(text.as_bytes()))
+        .map_err(|err| format!("failed to write waivers {}: {}", path.display(), err))?;
+    Ok(by_check.values().map(Vec::len).sum())
+}
+
+// A one-line description for the comment above an emitted waiver.
+fn summary(finding: &Finding) -> String {
+    let mut parts: Vec<String> = finding.message.iter().cloned().collect();
+    parts.extend(
+        finding
+            .details
+            .iter()
+            .map(|(label, value)| format!("{}: {}", label, value)),
+    );
+    if parts.is_empty() {
+        let title = finding.check.info().title;
+        parts.push(match finding.location() {
+            Some(location) => format!("{} in {}", title, location),
+            None => title.to_string(),
+        });
+    }
+    // Keep the comment on one line.
+    parts.join(" | ").replace(['\n', '\r'], " ")
+}
+
+fn is_date(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, byte)| match i {
+            4 | 7 => *byte == b'-',
+            _ => byte.is_ascii_digit(),
+        })
+}
+
+// The current UTC date as `YYYY-MM-DD`.
+pub fn today() -> String {
+    date(now() / 86_400)
+}
+
+// The current UTC time as `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn timestamp() -> String {
+    let seconds = now();
+    let time = seconds % 86_400;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        date(seconds / 86_400),
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs()) as i64
+}
+
+// The date `days` after 1970-01-01.
+fn date(days: i64) -> String {
+    // Howard Hinnant's days-to-civil conversion.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
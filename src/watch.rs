@@ -0,0 +1,319 @@
+use crate::usage::{SourceRoot, source_files};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+// How often `--watch` looks at the files again where the platform can't
+// notify it of changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+// How long `--watch` waits after a notification before looking at the files,
+// for an editor saving a file in several steps to be done.
+const SETTLE_TIME: Duration = Duration::from_millis(50);
+
+// What `--watch` looks at: every file of the translation directory, the
+// source files scanned for usages and the settings of the run.
+pub struct Watched<'a> {
+    pub i18n_dir: &'a Path,
+    pub sources: &'a [SourceRoot],
+    pub settings: Vec<PathBuf>,
+}
+
+impl Watched<'_> {
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = all_files(self.i18n_dir);
+        files.extend(source_files(self.sources));
+        files.extend(self.settings.iter().cloned());
+        files
+    }
+
+    // The folders to be notified of changes in: those of the translation
+    // directory, the source roots and the folders of the watched files.
+    fn folders(&self, files: &[PathBuf]) -> BTreeSet<PathBuf> {
+        let mut folders = all_folders(self.i18n_dir);
+        folders.extend(self.sources.iter().map(|root| root.dir.clone()));
+        // A file of the working directory has an empty parent.
+        folders.extend(files.iter().filter_map(|file| file.parent()).map(|folder| {
+            if folder.as_os_str().is_empty() {
+                PathBuf::from(".")
+            } else {
+                folder.to_path_buf()
+            }
+        }));
+        folders
+    }
+}
+
+fn all_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = dir.read_dir() else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .flat_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                all_files(&path)
+            } else {
+                vec![path]
+            }
+        })
+        .collect()
+}
+
+fn all_folders(dir: &Path) -> BTreeSet<PathBuf> {
+    let mut folders = BTreeSet::new();
+    if !dir.is_dir() {
+        return folders;
+    }
+    folders.insert(dir.to_path_buf());
+    for entry in dir.read_dir().into_iter().flatten().filter_map(Result::ok) {
+        if entry.path().is_dir() {
+            folders.extend(all_folders(&entry.path()));
+        }
+    }
+    folders
+}
+
+// The modification time and size of each watched file when it was taken,
+// compared after each notification to tell which files changed.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, (Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    pub fn take(watched: &Watched) -> Snapshot {
+        Snapshot::of(watched.files())
+    }
+
+    fn of(files: Vec<PathBuf>) -> Snapshot {
+        Snapshot(
+            files
+                .into_iter()
+                .filter_map(|path| {
+                    let metadata = fs::metadata(&path).ok()?;
+                    Some((path, (metadata.modified().ok(), metadata.len())))
+                })
+                .collect(),
+        )
+    }
+
+    // The files created, modified or deleted since `self`, sorted.
+    pub fn changed(&self, newer: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = newer
+            .0
+            .iter()
+            .filter(|(path, stamp)| self.0.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.0
+                .keys()
+                .filter(|path| !newer.0.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+// Waits for the watched files to change. Where the platform notifies of
+// changes, inotify on Linux, nothing is read until a watched folder
+// changes; elsewhere, or once the notifications can't be set up, such as
+// past the limit of watches, the files are polled every `POLL_INTERVAL`.
+pub struct Watcher {
+    notifications: Option<notify::Notifications>,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(watched: &Watched) -> Watcher {
+        let mut watcher = Watcher {
+            notifications: notify::Notifications::new(),
+            snapshot: Snapshot::default(),
+        };
+        let files = watched.files();
+        watcher.follow(watched, &files);
+        watcher.snapshot = Snapshot::of(files);
+        watcher
+    }
+
+    // Blocks until some of the watched files are created, modified or
+    // deleted, and returns them sorted.
+    pub fn wait(&mut self, watched: &Watched) -> Vec<PathBuf> {
+        loop {
+            match &mut self.notifications {
+                Some(notifications) => match notifications.wait() {
+                    Ok(created) => {
+                        for folder in created {
+                            if !notifications.watch(&folder) {
+                                self.notifications = None;
+                                break;
+                            }
+                        }
+                        thread::sleep(SETTLE_TIME);
+                    }
+                    Err(_) => self.notifications = None,
+                },
+                None => thread::sleep(POLL_INTERVAL),
+            }
+            let files = watched.files();
+            self.follow(watched, &files);
+            let next = Snapshot::of(files);
+            let changed = self.snapshot.changed(&next);
+            self.snapshot = next;
+            if !changed.is_empty() {
+                return changed;
+            }
+        }
+    }
+
+    // Watches the folders of `files`, which a run may change along with the
+    // settings.
+    fn follow(&mut self, watched: &Watched, files: &[PathBuf]) {
+        if let Some(notifications) = &mut self.notifications
+            && !watched
+                .folders(files)
+                .iter()
+                .all(|folder| notifications.watch(folder))
+        {
+            self.notifications = None;
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod notify {
+    use std::collections::{HashMap, HashSet};
+    use std::ffi::{CString, c_char, c_int};
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::fd::{AsRawFd, FromRawFd};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+
+    const IN_CLOEXEC: c_int = 0o2000000;
+    const IN_MODIFY: u32 = 0x2;
+    const IN_ATTRIB: u32 = 0x4;
+    const IN_CLOSE_WRITE: u32 = 0x8;
+    const IN_MOVED_FROM: u32 = 0x40;
+    const IN_MOVED_TO: u32 = 0x80;
+    const IN_CREATE: u32 = 0x100;
+    const IN_DELETE: u32 = 0x200;
+    const IN_ONLYDIR: u32 = 0x0100_0000;
+    const IN_ISDIR: u32 = 0x4000_0000;
+    const EVENTS: u32 = IN_MODIFY
+        | IN_ATTRIB
+        | IN_CLOSE_WRITE
+        | IN_MOVED_FROM
+        | IN_MOVED_TO
+        | IN_CREATE
+        | IN_DELETE
+        | IN_ONLYDIR;
+    // `struct inotify_event` up to the name that follows it.
+    const EVENT_HEADER: usize = 16;
+
+    unsafe extern "C" {
+        fn inotify_init1(flags: c_int) -> c_int;
+        fn inotify_add_watch(fd: c_int, path: *const c_char, mask: u32) -> c_int;
+    }
+
+    // An inotify instance and the folders it watches, by watch descriptor.
+    pub struct Notifications {
+        file: File,
+        folders: HashMap<c_int, PathBuf>,
+        watched: HashSet<PathBuf>,
+    }
+
+    impl Notifications {
+        pub fn new() -> Option<Notifications> {
+            // Safety: takes no pointer; a valid descriptor is owned by the
+            // file from then on, and closed with it.
+            let fd = unsafe { inotify_init1(IN_CLOEXEC) };
+            if fd < 0 {
+                return None;
+            }
+            Some(Notifications {
+                file: unsafe { File::from_raw_fd(fd) },
+                folders: HashMap::new(),
+                watched: HashSet::new(),
+            })
+        }
+
+        // Watches the entries of `folder`; false when it can't be watched
+        // for another reason than being gone.
+        pub fn watch(&mut self, folder: &Path) -> bool {
+            if self.watched.contains(folder) {
+                return true;
+            }
+            let Ok(path) = CString::new(folder.as_os_str().as_bytes()) else {
+                return false;
+            };
+            // Safety: `path` is a NUL-terminated string outliving the call.
+            let wd = unsafe { inotify_add_watch(self.file.as_raw_fd(), path.as_ptr(), EVENTS) };
+            if wd < 0 {
+                return io::Error::last_os_error().kind() == io::ErrorKind::NotFound;
+            }
+            self.folders.insert(wd, folder.to_path_buf());
+            self.watched.insert(folder.to_path_buf());
+            true
+        }
+
+        // Blocks until something changes in the watched folders, and returns
+        // the folders created in them, to be watched too.
+        pub fn wait(&mut self) -> io::Result<Vec<PathBuf>> {
+            let mut buffer = vec![0; 64 * 1024];
+            let read = self.file.read(&mut buffer)?;
+            let field = |at: usize| u32::from_ne_bytes(buffer[at..at + 4].try_into().unwrap());
+            let mut created = Vec::new();
+            let mut at = 0;
+            while at + EVENT_HEADER <= read {
+                let wd = field(at) as c_int;
+                let mask = field(at + 4);
+                let length = field(at + 12) as usize;
+                let name = &buffer[at + EVENT_HEADER..(at + EVENT_HEADER + length).min(read)];
+                let name = &name[..name
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .unwrap_or(name.len())];
+                if mask & IN_ISDIR != 0
+                    && mask & (IN_CREATE | IN_MOVED_TO) != 0
+                    && let Some(folder) = self.folders.get(&wd)
+                {
+                    created.push(folder.join(std::ffi::OsStr::from_bytes(name)));
+                }
+                at += EVENT_HEADER + length;
+            }
+            // A folder moved in may hold folders of its own.
+            let mut nested = Vec::new();
+            for folder in &created {
+                nested.extend(super::all_folders(folder));
+            }
+            Ok(nested)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod notify {
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    // No notifications: the files are polled.
+    pub enum Notifications {}
+
+    impl Notifications {
+        pub fn new() -> Option<Notifications> {
+            None
+        }
+
+        pub fn watch(&mut self, _folder: &Path) -> bool {
+            match *self {}
+        }
+
+        pub fn wait(&mut self) -> io::Result<Vec<PathBuf>> {
+            match *self {}
+        }
+    }
+}
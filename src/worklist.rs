@@ -0,0 +1,267 @@
+use crate::base::Base;
+use crate::checks::expected_file;
+use crate::context::Manifest;
+use crate::model::Project;
+use crate::report::{Check, Finding};
+use std::collections::BTreeMap;
+
+// The kinds of work a translator gets, in their default order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Missing,
+    Empty,
+    Stale,
+    Mismatch,
+}
+
+impl Section {
+    pub const ALL: [Section; 4] = [
+        Section::Missing,
+        Section::Empty,
+        Section::Stale,
+        Section::Mismatch,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Missing => "missing",
+            Section::Empty => "empty",
+            Section::Stale => "stale",
+            Section::Mismatch => "mismatch",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Section> {
+        Section::ALL
+            .into_iter()
+            .find(|section| section.name() == name)
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Section::Missing => "Missing translations",
+            Section::Empty => "Empty translations",
+            Section::Stale => "Translations to update after a base change",
+            Section::Mismatch => "Variable mismatches",
+        }
+    }
+}
+
+// `setting` names where the section was listed, for the error.
+pub fn parse_section(name: &str, setting: &str) -> Result<Section, String> {
+    Section::from_name(name).ok_or_else(|| {
+        format!(
+            "unknown worklist section `{}` in {} (expected `missing`, `empty`, `stale` or `mismatch`)",
+            name, setting
+        )
+    })
+}
+
+// One thing to translate or fix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub key: String,
+    // The file the translation belongs in.
+    pub file: String,
+    pub base_value: Option<String>,
+    pub value: Option<String>,
+    pub note: Option<String>,
+    // Screenshots or design links of the key, from the context manifest.
+    pub context: Vec<String>,
+}
+
+pub struct Worklist {
+    pub lang: String,
+    pub sections: Vec<(Section, Vec<Item>)>,
+}
+
+impl Worklist {
+    // Builds the worklist of `lang` from the findings of a consistency run,
+    // keeping the listed sections in the listed order. Empty values have no
+    // check of their own and are read from the language directly.
+    pub fn new(
+        project: &Project,
+        base: &Base,
+        lang: &str,
+        findings: &[Finding],
+        sections: &[Section],
+    ) -> Worklist {
+        let data = project.get(lang);
+        let mut items: BTreeMap<Section, Vec<Item>> = BTreeMap::new();
+        let item = |key: &str, file: String| Item {
+            key: key.to_string(),
+            file,
+            base_value: base.value(key).map(str::to_string),
+            value: data.and_then(|data| data.value(key)).map(str::to_string),
+            note: None,
+            context: Vec::new(),
+        };
+
+        for finding in findings {
+            let Some(key) = &finding.key else {
+                continue;
+            };
+            let entry = match finding.check {
+                Check::MissingKey if finding.lang.as_deref() == Some(lang) => (
+                    Section::Missing,
+                    item(key, expected_file(project, base, lang, key)),
+                ),
+                Check::VariableMismatch | Check::TranslatedPlaceholder
+                    if finding.lang.as_deref() == Some(lang) =>
+                {
+                    let file = finding.file.clone().unwrap_or_default();
+                    (
+                        Section::Mismatch,
+                        Item {
+                            note: Some(variables(finding)),
+                            ..item(key, file)
+                        },
+                    )
+                }
+                // Reported once for every language that needs updating.
+                Check::BasePlaceholdersChanged if stale_in(finding, lang) => {
+                    let file = data
+                        .and_then(|data| data.file(key))
+                        .map(str::to_string)
+                        .unwrap_or_else(|| expected_file(project, base, lang, key));
+                    (
+                        Section::Stale,
+                        Item {
+                            note: Some(variables(finding)),
+                            ..item(key, file)
+                        },
+                    )
+                }
+                _ => continue,
+            };
+            items.entry(entry.0).or_default().push(entry.1);
+        }
+
+        if let Some(data) = data {
+            for (key, entry) in &data.values {
+                let base_value = base.value(key).unwrap_or_default();
+                if entry.value.trim().is_empty() && !base_value.trim().is_empty() {
+                    items
+                        .entry(Section::Empty)
+                        .or_default()
+                        .push(item(key, entry.file.to_string()));
+                }
+            }
+        }
+
+        Worklist {
+            lang: lang.to_string(),
+            sections: sections
+                .iter()
+                .map(|section| {
+                    let mut items = items.remove(section).unwrap_or_default();
+                    items.sort_by(|a, b| (&a.file, &a.key).cmp(&(&b.file, &b.key)));
+                    (*section, items)
+                })
+                .collect(),
+        }
+    }
+
+    // Lists the screenshots or design links of each item's key.
+    pub fn with_context(mut self, manifest: &Manifest, max: usize) -> Worklist {
+        for (_, items) in &mut self.sections {
+            for item in items {
+                item.context = manifest.links(&item.key, max);
+            }
+        }
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.sections.iter().map(|(_, items)| items.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // A checklist per section, with each base value quoted under its item.
+    pub fn to_markdown(&self) -> String {
+        let mut text = format!("# Translation worklist: {}\n\n", self.lang.to_uppercase());
+        text.push_str(&match self.len() {
+            0 => "Nothing left to do.\n".to_string(),
+            1 => "1 item.\n".to_string(),
+            n => format!("{} items.\n", n),
+        });
+
+        for (section, items) in &self.sections {
+            if items.is_empty() {
+                continue;
+            }
+            text.push_str(&format!("\n## {} ({})\n\n", section.title(), items.len()));
+            for item in items {
+                text.push_str(&format!("- [ ] `{}` in `{}`\n", item.key, item.file));
+                if let Some(note) = &item.note {
+                    text.push_str(&format!("  {}\n", note));
+                }
+                for link in &item.context {
+                    text.push_str(&format!("  Context: {}\n", link));
+                }
+                for (label, value) in [("Base", &item.base_value), ("Current", &item.value)] {
+                    let Some(value) = value.as_ref().filter(|value| !value.is_empty()) else {
+                        continue;
+                    };
+                    text.push_str(&format!("  {}:\n", label));
+                    for line in value.lines() {
+                        text.push_str(&format!("  > {}\n", line));
+                    }
+                }
+            }
+        }
+        text
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut text = String::from("section,key,file,base_value,value,note,context\n");
+        for (section, items) in &self.sections {
+            for item in items {
+                let context = item.context.join(" ");
+                let fields = [
+                    section.name(),
+                    &item.key,
+                    &item.file,
+                    item.base_value.as_deref().unwrap_or_default(),
+                    item.value.as_deref().unwrap_or_default(),
+                    item.note.as_deref().unwrap_or_default(),
+                    &context,
+                ];
+                let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                text.push_str(&fields.join(","));
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+// The expected and found variables, the first two details of both checks.
+fn variables(finding: &Finding) -> String {
+    finding
+        .details
+        .iter()
+        .take(2)
+        .map(|(label, value)| format!("{}: {}", label, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn stale_in(finding: &Finding, lang: &str) -> bool {
+    finding
+        .details
+        .iter()
+        .find(|(label, _)| label == "Languages")
+        .is_some_and(|(_, langs)| langs.split(", ").any(|listed| listed == lang))
+}
+
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
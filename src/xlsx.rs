@@ -0,0 +1,334 @@
+// Just enough of the Office Open XML spreadsheet format to hand a sheet of
+// strings to translators and read it back once filled: a workbook of one
+// worksheet of inline strings, stored in a zip archive without compression,
+// and the first worksheet of any workbook, as spreadsheet programs save it.
+
+use std::io::{Cursor, Read, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+// The largest part read out of a workbook once inflated. Sheets come back
+// from outside translators, and a few kilobytes of deflated zeros would
+// otherwise fill the memory.
+const MAX_PART_SIZE: u64 = 64 * 1024 * 1024;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+
+const ROOT_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // Control characters other than tabs and line breaks can't be
+            // written in XML 1.0.
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match c {
+            Some(c) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+// The name of column `index`, from 0: `A`, …, `Z`, `AA`.
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+// The column of a cell reference such as `AB12`, from 0.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference
+        .bytes()
+        .take_while(u8::is_ascii_uppercase)
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    Some(
+        letters
+            .iter()
+            .fold(0, |index, letter| index * 26 + (letter - b'A') as usize + 1)
+            - 1,
+    )
+}
+
+// A workbook of one sheet holding `rows`, every cell a string.
+pub fn write(rows: &[Vec<String>]) -> Vec<u8> {
+    let mut sheet = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (row, cells) in rows.iter().enumerate() {
+        sheet.push_str(&format!("<row r=\"{}\">", row + 1));
+        for (column, cell) in cells.iter().enumerate() {
+            if cell.is_empty() {
+                continue;
+            }
+            sheet.push_str(&format!(
+                "<c r=\"{}{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                column_name(column),
+                row + 1,
+                escape(cell)
+            ));
+        }
+        sheet.push_str("</row>");
+    }
+    sheet.push_str("</sheetData></worksheet>");
+    let workbook = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Translations" sheetId="1" r:id="rId1"/></sheets></workbook>"#;
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    // Stored, and dated 1980-01-01 so the same sheet is the same archive.
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .last_modified_time(zip::DateTime::default());
+    for (name, data) in [
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+        ("_rels/.rels", ROOT_RELATIONSHIPS.as_bytes()),
+        ("xl/workbook.xml", workbook.as_bytes()),
+        (
+            "xl/_rels/workbook.xml.rels",
+            WORKBOOK_RELATIONSHIPS.as_bytes(),
+        ),
+        ("xl/worksheets/sheet1.xml", sheet.as_bytes()),
+    ] {
+        archive
+            .start_file(name, options)
+            .and_then(|_| Ok(archive.write_all(data)?))
+            .expect("Failed to write the workbook in memory");
+    }
+    archive
+        .finish()
+        .expect("Failed to write the workbook in memory")
+        .into_inner()
+}
+
+// The part `name` of a workbook as text, inflated up to `MAX_PART_SIZE` and
+// checked against its CRC.
+fn part(archive: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<String, String> {
+    let entry = archive
+        .by_name(name)
+        .map_err(|err| format!("`{}`: {}", name, err))?;
+    if entry.size() > MAX_PART_SIZE {
+        return Err(format!("`{}` is larger than {} bytes", name, MAX_PART_SIZE));
+    }
+    let mut content = Vec::new();
+    entry
+        .take(MAX_PART_SIZE + 1)
+        .read_to_end(&mut content)
+        .map_err(|err| format!("`{}`: {}", name, err))?;
+    if content.len() as u64 > MAX_PART_SIZE {
+        return Err(format!("`{}` is larger than {} bytes", name, MAX_PART_SIZE));
+    }
+    Ok(String::from_utf8_lossy(&content).to_string())
+}
+
+// A tag of an XML document: its name, without namespace prefix, its
+// attributes, and whether it closes an element.
+struct Tag<'x> {
+    name: &'x str,
+    attributes: &'x str,
+    closing: bool,
+    empty: bool,
+}
+
+impl<'x> Tag<'x> {
+    fn attribute(&self, name: &str) -> Option<String> {
+        let mut rest = self.attributes;
+        while let Some(equals) = rest.find('=') {
+            let attribute = rest[..equals].trim();
+            let value = rest[equals + 1..].trim_start();
+            let quote = value.chars().next()?;
+            let end = value[1..].find(quote)? + 1;
+            if attribute.rsplit(':').next() == Some(name) {
+                return Some(unescape(&value[1..end]));
+            }
+            rest = &value[end + 1..];
+        }
+        None
+    }
+}
+
+// The tags of `xml` in order, each with the text following it up to the
+// next tag. Declarations, comments and processing instructions are left out.
+fn tags(xml: &str) -> Vec<(Tag<'_>, &str)> {
+    let mut tags = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let inside = &rest[..end];
+        rest = &rest[end + 1..];
+        let text = &rest[..rest.find('<').unwrap_or(rest.len())];
+        if inside.starts_with(['?', '!']) {
+            continue;
+        }
+        let closing = inside.starts_with('/');
+        let inside = inside.trim_start_matches('/');
+        let empty = inside.ends_with('/');
+        let inside = inside.trim_end_matches('/');
+        let (name, attributes) = inside
+            .split_once(char::is_whitespace)
+            .unwrap_or((inside, ""));
+        tags.push((
+            Tag {
+                name: name.rsplit(':').next().unwrap_or(name),
+                attributes,
+                closing,
+                empty,
+            },
+            text,
+        ));
+    }
+    tags
+}
+
+// The strings shared by the cells of a workbook, each the text of its runs
+// without their phonetic guides.
+fn shared_strings(xml: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current: Option<String> = None;
+    let mut phonetic = false;
+    for (tag, text) in tags(xml) {
+        match (tag.name, tag.closing) {
+            ("si", false) if tag.empty => strings.push(String::new()),
+            ("si", false) => current = Some(String::new()),
+            ("si", true) => strings.extend(current.take()),
+            ("rPh", closing) => phonetic = !closing && !tag.empty,
+            ("t", false) if !phonetic && !tag.empty => {
+                if let Some(current) = &mut current {
+                    current.push_str(&unescape(text));
+                }
+            }
+            _ => {}
+        }
+    }
+    strings
+}
+
+// The first worksheet of an `.xlsx` workbook as rows of cell texts, the
+// missing cells empty. Numbers and booleans are read as written.
+pub fn read(data: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let mut archive =
+        ZipArchive::new(Cursor::new(data)).map_err(|err| format!("not a zip archive: {}", err))?;
+    let strings = match archive.index_for_name("xl/sharedStrings.xml") {
+        Some(_) => shared_strings(&part(&mut archive, "xl/sharedStrings.xml")?),
+        None => Vec::new(),
+    };
+    let mut sheets: Vec<String> = archive
+        .file_names()
+        .filter_map(|name| name.ok())
+        .filter(|name| name.starts_with("xl/worksheets/") && name.ends_with(".xml"))
+        .map(|name| name.to_string())
+        .collect();
+    sheets.sort_by_key(|name| (name.len(), name.clone()));
+    let sheet = sheets.first().ok_or("no worksheet in the workbook")?;
+    let sheet = part(&mut archive, sheet)?;
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut cell: Option<(usize, Option<String>)> = None;
+    let mut value = String::new();
+    for (tag, text) in tags(&sheet) {
+        match (tag.name, tag.closing) {
+            ("row", false) => {
+                let row = tag
+                    .attribute("r")
+                    .and_then(|row| row.parse::<usize>().ok())
+                    .unwrap_or(rows.len() + 1);
+                while rows.len() < row {
+                    rows.push(Vec::new());
+                }
+            }
+            ("c", false) => {
+                let row = rows.last_mut().ok_or("cell outside of a row")?;
+                let column = tag
+                    .attribute("r")
+                    .and_then(|reference| column_index(&reference))
+                    .unwrap_or(row.len());
+                cell = (!tag.empty).then(|| (column, tag.attribute("t")));
+                value.clear();
+            }
+            // The runs of a rich text string are read as one text.
+            ("v" | "t", false) if cell.is_some() && !tag.empty => {
+                value.push_str(&unescape(text));
+            }
+            ("c", true) => {
+                let Some((column, kind)) = cell.take() else {
+                    continue;
+                };
+                let text = match kind.as_deref() {
+                    Some("s") => value
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|index| strings.get(index).cloned())
+                        .ok_or_else(|| format!("invalid shared string `{}`", value))?,
+                    _ => value.clone(),
+                };
+                let row = rows.last_mut().ok_or("cell outside of a row")?;
+                if row.len() <= column {
+                    row.resize(column + 1, String::new());
+                }
+                row[column] = text;
+            }
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
@@ -0,0 +1,462 @@
+use serde_json::{Map, Number, Value};
+
+// A small YAML reader covering what locale files hold: block mappings and
+// sequences, plain, quoted and block scalars, flow collections on one line
+// and comments. Anchors, aliases, tags and several documents per file are
+// refused rather than read wrongly.
+pub fn parse(source: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        lines: source.lines().map(str::to_string).collect(),
+        pos: 0,
+    };
+    if let Some((_, text)) = parser.peek()
+        && text == "---"
+    {
+        parser.pos += 1;
+    }
+    let value = parser.node(0)?;
+    match parser.peek() {
+        None => Ok(value),
+        Some((_, text)) if text == "..." => Ok(value),
+        Some((_, text)) if text == "---" => Err(parser.error("several documents aren't supported")),
+        Some(_) => Err(parser.error("unexpected indentation")),
+    }
+}
+
+struct Parser {
+    lines: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &str) -> String {
+        format!("line {}: {}", self.pos + 1, message)
+    }
+
+    // The indentation and text, without its comment, of the next line that
+    // holds anything.
+    fn peek(&mut self) -> Option<(usize, String)> {
+        while let Some(line) = self.lines.get(self.pos) {
+            let text = strip_comment(line).trim_end();
+            if !text.trim_start().is_empty() {
+                let indent = text.len() - text.trim_start().len();
+                return Some((indent, text.trim_start().to_string()));
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    // The node starting on the next line, when it is indented at least by
+    // `min_indent`; null otherwise.
+    fn node(&mut self, min_indent: usize) -> Result<Value, String> {
+        let Some((indent, text)) = self.peek() else {
+            return Ok(Value::Null);
+        };
+        if indent < min_indent {
+            return Ok(Value::Null);
+        }
+        if self.lines[self.pos][..indent].contains('\t') {
+            return Err(self.error("tabs can't indent YAML"));
+        }
+        if is_sequence_item(&text) {
+            self.sequence(indent)
+        } else if mapping_key(&text).is_some() {
+            self.mapping(indent)
+        } else {
+            self.pos += 1;
+            inline(&text).map_err(|err| format!("line {}: {}", self.pos, err))
+        }
+    }
+
+    fn mapping(&mut self, indent: usize) -> Result<Value, String> {
+        let mut map = Map::new();
+        while let Some((line_indent, text)) = self.peek() {
+            if line_indent < indent {
+                break;
+            }
+            if line_indent > indent {
+                return Err(self.error("unexpected indentation"));
+            }
+            let Some((key, rest)) = mapping_key(&text) else {
+                break;
+            };
+            let key = key.map_err(|err| self.error(&err))?;
+            let rest = rest.trim().to_string();
+            self.pos += 1;
+            let value = if rest.is_empty() {
+                // A sequence may sit at the indentation of its key.
+                match self.peek() {
+                    Some((next, text)) if next == indent && is_sequence_item(&text) => {
+                        self.sequence(indent)?
+                    }
+                    _ => self.node(indent + 1)?,
+                }
+            } else if rest.starts_with(['|', '>']) {
+                self.block_scalar(&rest, indent)?
+            } else {
+                inline(&rest).map_err(|err| format!("line {}: {}", self.pos, err))?
+            };
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn sequence(&mut self, indent: usize) -> Result<Value, String> {
+        let mut items = Vec::new();
+        while let Some((line_indent, text)) = self.peek() {
+            if line_indent != indent || !is_sequence_item(&text) {
+                break;
+            }
+            let rest = text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.node(indent + 1)?);
+            } else if rest.starts_with(['|', '>']) {
+                self.pos += 1;
+                items.push(self.block_scalar(&rest, indent)?);
+            } else if is_sequence_item(&rest) || mapping_key(&rest).is_some() {
+                // `- key: value` opens a mapping indented like its key: the
+                // dash is read as indentation.
+                let line = &mut self.lines[self.pos];
+                let dash = line.find('-').unwrap();
+                line.replace_range(dash..dash + 1, " ");
+                let (item_indent, _) = self.peek().unwrap();
+                items.push(self.node(item_indent)?);
+            } else {
+                self.pos += 1;
+                items.push(inline(&rest).map_err(|err| format!("line {}: {}", self.pos, err))?);
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    // A `|` literal or `>` folded scalar, whose lines are those indented
+    // more than its parent. `-` strips the final line break, `+` keeps the
+    // trailing blank lines.
+    fn block_scalar(&mut self, header: &str, parent: usize) -> Result<Value, String> {
+        let folded = header.starts_with('>');
+        let mut chomping = None;
+        let mut explicit_indent = None;
+        for c in header[1..].trim_end().chars() {
+            match c {
+                '-' | '+' if chomping.is_none() => chomping = Some(c),
+                '1'..='9' if explicit_indent.is_none() => {
+                    explicit_indent = Some(parent + c.to_digit(10).unwrap() as usize)
+                }
+                _ => return Err(format!("line {}: invalid block scalar header", self.pos)),
+            }
+        }
+        let mut lines = Vec::new();
+        let mut content_indent = explicit_indent;
+        while let Some(line) = self.lines.get(self.pos) {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            if line.trim().is_empty() {
+                lines.push(String::new());
+                self.pos += 1;
+                continue;
+            }
+            let content = *content_indent.get_or_insert(indent);
+            if indent <= parent || indent < content {
+                break;
+            }
+            lines.push(line[content..].to_string());
+            self.pos += 1;
+        }
+        let trailing = lines
+            .iter()
+            .rev()
+            .take_while(|line| line.is_empty())
+            .count();
+        lines.truncate(lines.len() - trailing);
+        let mut text = if folded {
+            let mut text = String::new();
+            for (index, line) in lines.iter().enumerate() {
+                if index > 0 {
+                    // A line break between lines of text is read as a
+                    // space, except around more indented lines; blank lines
+                    // stand for the line breaks they hold.
+                    let previous = &lines[index - 1];
+                    let indented = |line: &str| line.starts_with([' ', '\t']);
+                    if line.is_empty() || indented(line) || indented(previous) {
+                        text.push('\n');
+                    } else if !previous.is_empty() {
+                        text.push(' ');
+                    }
+                }
+                text.push_str(line);
+            }
+            text
+        } else {
+            lines.join("\n")
+        };
+        match chomping {
+            Some('-') => {}
+            Some('+') => text.push_str(&"\n".repeat(trailing + 1)),
+            _ if !text.is_empty() => text.push('\n'),
+            _ => {}
+        }
+        Ok(Value::String(text))
+    }
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+// The key of a `key: value` line and what follows the colon, or `None`
+// when the line isn't a mapping entry.
+fn mapping_key(text: &str) -> Option<(Result<String, String>, &str)> {
+    if text.starts_with(['"', '\'']) {
+        let (key, length) = match quoted(text) {
+            Ok(quoted) => quoted,
+            Err(err) => return Some((Err(err), "")),
+        };
+        let rest = text[length..].trim_start();
+        return rest.strip_prefix(':').map(|rest| (Ok(key), rest));
+    }
+    if text.starts_with(['[', '{']) {
+        return None;
+    }
+    let end = text
+        .match_indices(':')
+        .map(|(index, _)| index)
+        .find(|&index| {
+            text[index + 1..].is_empty() || text[index + 1..].starts_with([' ', '\t'])
+        })?;
+    let key = text[..end].trim_end();
+    if key.starts_with(['&', '*', '!', '?']) {
+        return Some((
+            Err("anchors, aliases, tags and complex keys aren't supported".into()),
+            "",
+        ));
+    }
+    Some((Ok(key.to_string()), &text[end + 1..]))
+}
+
+// A value on the line of its key or dash.
+fn inline(text: &str) -> Result<Value, String> {
+    if text.starts_with(['[', '{']) {
+        let mut flow = Flow {
+            chars: text.chars().collect(),
+            pos: 0,
+        };
+        let value = flow.value()?;
+        flow.skip_whitespace();
+        if flow.pos < flow.chars.len() {
+            return Err("unexpected text after the flow collection".into());
+        }
+        return Ok(value);
+    }
+    if text.starts_with(['"', '\'']) {
+        let (value, length) = quoted(text)?;
+        if !text[length..].trim().is_empty() {
+            return Err("unexpected text after the quoted string".into());
+        }
+        return Ok(Value::String(value));
+    }
+    if text.starts_with(['&', '*', '!']) {
+        return Err("anchors, aliases and tags aren't supported".into());
+    }
+    Ok(plain(text))
+}
+
+// A plain scalar, typed as the YAML core schema reads it.
+fn plain(text: &str) -> Value {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => Value::Null,
+        "true" | "True" | "TRUE" => Value::Bool(true),
+        "false" | "False" | "FALSE" => Value::Bool(false),
+        _ => {
+            let number = if text
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '-' || c == '+')
+            {
+                text.parse::<i64>().ok().map(Number::from)
+            } else if text.contains(['.', 'e', 'E'])
+                && text.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+            {
+                text.parse::<f64>().ok().and_then(Number::from_f64)
+            } else {
+                None
+            };
+            number.map_or_else(|| Value::String(text.to_string()), Value::Number)
+        }
+    }
+}
+
+// A single or double-quoted string opening `text`, and the length of it
+// with its quotes.
+fn quoted(text: &str) -> Result<(String, usize), String> {
+    let quote = text.chars().next().unwrap();
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\'' if quote == '\'' => {
+                if chars.peek().is_some_and(|(_, next)| *next == '\'') {
+                    chars.next();
+                    value.push('\'');
+                } else {
+                    return Ok((value, index + 1));
+                }
+            }
+            '"' if quote == '"' => return Ok((value, index + 1)),
+            '\\' if quote == '"' => {
+                let (_, escaped) = chars.next().ok_or("unterminated string")?;
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    '0' => value.push('\0'),
+                    'u' | 'x' | 'U' => {
+                        let digits = match escaped {
+                            'x' => 2,
+                            'u' => 4,
+                            _ => 8,
+                        };
+                        let hex: String = (0..digits)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid escape `\\{}{}`", escaped, hex))?;
+                        value.push(c);
+                    }
+                    other => value.push(other),
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    Err("unterminated string".into())
+}
+
+// `[a, b]` and `{ key: value }`, nested or not, on one line.
+struct Flow {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Flow {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.chars.get(self.pos) == Some(&']') {
+                        self.pos += 1;
+                        return Ok(Value::Array(items));
+                    }
+                    items.push(self.value()?);
+                    self.separator(']')?;
+                }
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut map = Map::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.chars.get(self.pos) == Some(&'}') {
+                        self.pos += 1;
+                        return Ok(Value::Object(map));
+                    }
+                    let key = match self.scalar(true)? {
+                        Value::String(key) => key,
+                        other => other.to_string(),
+                    };
+                    self.skip_whitespace();
+                    if self.chars.get(self.pos) != Some(&':') {
+                        return Err(format!("expected `:` after `{}`", key));
+                    }
+                    self.pos += 1;
+                    let value = self.value()?;
+                    map.insert(key, value);
+                    self.separator('}')?;
+                }
+            }
+            _ => self.scalar(false),
+        }
+    }
+
+    // Skips the comma between items; the closing bracket is left for the
+    // caller.
+    fn separator(&mut self, close: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.get(self.pos) {
+            Some(',') => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) if *c == close => Ok(()),
+            _ => Err(format!("expected `,` or `{}`", close)),
+        }
+    }
+
+    fn scalar(&mut self, key: bool) -> Result<Value, String> {
+        self.skip_whitespace();
+        let rest: String = self.chars[self.pos..].iter().collect();
+        if rest.starts_with(['"', '\'']) {
+            let (value, length) = quoted(&rest)?;
+            self.pos += rest[..length].chars().count();
+            return Ok(Value::String(value));
+        }
+        let start = self.pos;
+        while let Some(&c) = self.chars.get(self.pos) {
+            if matches!(c, ',' | ']' | '}') || (key && c == ':') {
+                break;
+            }
+            if c == ':'
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_none_or(|next| next.is_whitespace())
+            {
+                break;
+            }
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        inline(text.trim())
+    }
+}
+
+// The line without its comment, a `#` starting one outside quotes. Inside
+// single quotes `''` is an escaped quote, inside double quotes a backslash
+// escapes the next character.
+pub fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (None, '"' | '\'')
+                if index == 0 || line[..index].ends_with([' ', ':', '-', '[', '{', ',']) =>
+            {
+                quote = Some(c)
+            }
+            (Some('\''), '\'') if chars.peek().is_some_and(|(_, next)| *next == '\'') => {
+                chars.next();
+            }
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (None, '#') if index == 0 || line[..index].ends_with([' ', '\t']) => {
+                return &line[..index];
+            }
+            _ => {}
+        }
+    }
+    line
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn check(dir: &TempDir) -> String {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn arrays_of_objects_are_compared_per_element() {
+    let dir = TempDir::new("arrays-nested");
+    dir.write(
+        "i18n/fr/legal.json",
+        r#"{
+            "terms": { "body": ["Bonjour {name}", "Deux", "Trois", "Quatre"] },
+            "faq": [
+                { "q": "Pourquoi ?", "a": "Parce que." },
+                { "q": "Comment ?", "a": "Ainsi." }
+            ]
+        }"#,
+    );
+    dir.write(
+        "i18n/de/legal.json",
+        r#"{
+            "terms": { "body": ["Hallo {vorname}", "Zwei", "Drei"] },
+            "faq": [
+                { "q": "Warum?", "a": "Darum." },
+                { "q": "Wie?" }
+            ]
+        }"#,
+    );
+    let stdout = check(&dir);
+    assert!(
+        stdout.contains(
+            "Arrays with another number of elements than in the base:\n   - Key: terms.body | Length (FR): 4 | Length (DE): 3 | File: i18n/de/legal.json:2:24\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Key: faq.1.a"), "{}", stdout);
+    assert!(stdout.contains("Key: terms.body.0"), "{}", stdout);
+    assert!(!stdout.contains("terms.body.3"), "{}", stdout);
+}
+
+#[test]
+fn empty_arrays_are_keys() {
+    let dir = TempDir::new("arrays-empty");
+    dir.write("i18n/fr/app.json", r#"{ "title": "Titre", "tags": [] }"#);
+    dir.write("i18n/de/app.json", r#"{ "title": "Titel" }"#);
+    dir.write(
+        "i18n/es/app.json",
+        r#"{ "title": "Título", "tags": [], "labels": [] }"#,
+    );
+    let stdout = check(&dir);
+    assert!(
+        stdout.contains("Missing keys:\n   - Key: tags | File: i18n/de/app.json\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Key: labels | File: i18n/es/app.json"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   DE             2           1        1      0           0     50.0%\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn a_string_where_the_base_has_an_array_is_a_type_mismatch() {
+    let dir = TempDir::new("arrays-type");
+    dir.write(
+        "i18n/fr/app.json",
+        r#"{ "title": "Conditions", "steps": ["Un", "Deux"], "limit": 5, "enabled": true }"#,
+    );
+    dir.write(
+        "i18n/de/app.json",
+        r#"{ "title": ["Bedingungen", "Mehr"], "steps": "Eins, zwei", "limit": "5" }"#,
+    );
+    let stdout = check(&dir);
+    for expected in [
+        "Key: title | Expected (FR): string | Found (DE): array | File: i18n/de/app.json",
+        "Key: steps | Expected (FR): array | Found (DE): string | File: i18n/de/app.json",
+        "Key: limit | Expected (FR): number | Found (DE): string | File: i18n/de/app.json",
+    ] {
+        assert!(stdout.contains(expected), "{}", stdout);
+    }
+    // Non-string values are keys like any other.
+    assert!(stdout.contains("Key: enabled"), "{}", stdout);
+    assert!(!stdout.contains("title.0"), "{}", stdout);
+    assert!(!stdout.contains("steps.0"), "{}", stdout);
+}
+
+#[test]
+fn elements_of_string_arrays_are_compared_by_type() {
+    let dir = TempDir::new("arrays-elements");
+    dir.write(
+        "i18n/fr/app.json",
+        r#"{ "weekdays": ["Lundi", "Mardi"], "sizes": [38, 40], "flags": [true, false] }"#,
+    );
+    dir.write(
+        "i18n/de/app.json",
+        r#"{ "weekdays": ["Montag", 2], "sizes": [38, "40"], "flags": [true, false] }"#,
+    );
+    let stdout = check(&dir);
+    for expected in [
+        "Key: weekdays.1 | Expected (FR): string | Found (DE): number",
+        "Key: sizes.1 | Expected (FR): number | Found (DE): string",
+    ] {
+        assert!(stdout.contains(expected), "{}", stdout);
+    }
+    assert!(!stdout.contains("flags"), "{}", stdout);
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+}
+
+// Sources look an array up whole, so its key is defined by its elements.
+#[test]
+fn call_sites_may_name_a_whole_array() {
+    let dir = TempDir::new("arrays-call-sites");
+    dir.write(
+        "i18n/fr/app.json",
+        r#"{ "steps": ["Un", "Deux"], "faq": [{ "q": "Pourquoi ?" }] }"#,
+    );
+    dir.write(
+        "src/app.ts",
+        "t('steps');\nt('faq');\nt('faq.0.q');\nt('step');\n",
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "json",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let undefined: Vec<&str> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "undefined_key")
+        .map(|finding| finding["key"].as_str().unwrap())
+        .collect();
+    assert_eq!(undefined, ["step"]);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+
+fn check(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--color",
+        "never",
+    ];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+// English has every key; German misses one and left another as its key.
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/en/common.json",
+        r#"{ "title": "Welcome", "total": "Total: {amount}", "logout": "Log out" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "title": "Willkommen", "total": "total" }"#,
+    ),
+];
+
+#[test]
+fn the_language_the_others_translate_is_picked() {
+    let dir = fixture("base-detection", &PROJECT);
+    let (code, stdout, stderr) = check(&dir, &["--format", "json"]);
+
+    assert_eq!(code, Some(1), "{}", stderr);
+    assert!(
+        stderr
+            .contains("using 'en' as base language (auto-detected); pass --base-lang to override"),
+        "{}",
+        stderr
+    );
+    let report: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["config"]["base_lang"], "en");
+    assert_eq!(report["config"]["base_lang_origin"], "detected");
+    assert!(
+        report["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|finding| finding["check"] == "missing_key"
+                && finding["key"] == "logout"
+                && finding["lang"] == "de"),
+        "{}",
+        stdout
+    );
+
+    // The flag and `languages.base` win over the guess.
+    let (_, stdout, stderr) = check(&dir, &["--base-lang", "de", "--print-config"]);
+    assert!(!stderr.contains("auto-detected"), "{}", stderr);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["base_lang"], "de");
+    assert_eq!(config["base_lang_origin"], "flag");
+
+    dir.write("translation-check.toml", "[languages]\nbase = \"de\"\n");
+    let (_, stdout, _) = check(&dir, &["--print-config"]);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["base_lang"], "de");
+    assert_eq!(config["base_lang_origin"], "config");
+}
+
+#[test]
+fn fr_stays_the_default_when_present() {
+    let dir = fixture("base-detection-fr", &PROJECT);
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Bienvenue", "total": "Total : {amount}" }"#,
+    );
+    let (_, stdout, stderr) = check(&dir, &["--print-config"]);
+    assert!(!stderr.contains("auto-detected"), "{}", stderr);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["base_lang"], "fr");
+    assert_eq!(config["base_lang_origin"], "default");
+}
+
+// A `fr` the others don't look translated from stays the base, but the
+// language they do is pointed out.
+#[test]
+fn a_default_base_scoring_lower_than_another_is_reported() {
+    let dir = fixture("base-detection-fr-rival", &PROJECT);
+    dir.write("i18n/fr/common.json", r#"{ "title": "title" }"#);
+    let (_, stdout, stderr) = check(&dir, &["--format", "json"]);
+    let report: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["config"]["base_lang"], "fr");
+    assert_eq!(report["config"]["base_lang_origin"], "default");
+    assert!(
+        stderr.contains(
+            "'en' looks more like the base language than 'fr': en (score 1.00: 3 keys, superset of 2 languages, 100% text) against fr (score 0.11: 1 keys, superset of 0 languages, 0% text); pass --base-lang en or set `languages.base` to use it"
+        ),
+        "{}",
+        stderr
+    );
+
+    let (_, _, stderr) = check(&dir, &["--base-lang", "fr", "--format", "json"]);
+    assert!(!stderr.contains("looks more like"), "{}", stderr);
+}
+
+#[test]
+fn a_close_call_lists_the_candidates() {
+    let dir = TempDir::new("base-detection-tie");
+    dir.write("i18n/en/common.json", r#"{ "title": "Welcome" }"#);
+    dir.write("i18n/de/common.json", r#"{ "title": "Willkommen" }"#);
+    let (code, _, stderr) = check(&dir, &[]);
+
+    assert_eq!(code, Some(2), "{}", stderr);
+    assert!(
+        stderr.contains(
+            "can't tell which language is the base, candidates: de (score 1.00: 1 keys, superset of 1 languages, 100% text), en (score 1.00: 1 keys, superset of 1 languages, 100% text); pass --base-lang"
+        ),
+        "{}",
+        stderr
+    );
+
+    dir.write("translation-check.toml", "[languages]\nbase = \"../en\"\n");
+    let (code, _, stderr) = check(&dir, &[]);
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("invalid value `../en` for `languages.base`"),
+        "{}",
+        stderr
+    );
+}
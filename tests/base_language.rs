@@ -0,0 +1,94 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "locales/en/common.json",
+        r#"{ "title": "Title", "greeting": "Hello {name}" }"#,
+    ),
+    (
+        "locales/fr/common.json",
+        r#"{ "title": "Titre", "greeting": "Bonjour" }"#,
+    ),
+    ("web/app.tsx", "t('title'); t('greeting', { name });\n"),
+    ("web/legacy.ts", "t('gone');\n"),
+];
+
+fn check(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec!["--cwd", dir.path().to_str().unwrap(), "--color", "never"];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn the_base_language_and_the_folders_are_configurable() {
+    let dir = fixture("base-language", &PROJECT);
+    let (code, stdout, _) = check(
+        &dir,
+        &[
+            "--i18n-dir",
+            "locales",
+            "--base-lang",
+            "en",
+            "--src-dir",
+            "web",
+            "--ext",
+            "tsx",
+        ],
+    );
+
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains(
+            "   - Key: greeting\n   - Expected variables (EN): {\"name\"}\n   - Found variables (FR): {}\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: gone"), "{}", stdout);
+    assert!(stdout.contains("🧹 Unused keys: 0\n"), "{}", stdout);
+
+    // The positional directory still works.
+    let (_, positional, _) = check(
+        &dir,
+        &[
+            "locales",
+            "--base-lang",
+            "en",
+            "--src-dir",
+            "web",
+            "--ext",
+            "tsx",
+        ],
+    );
+    assert_eq!(positional, stdout);
+}
+
+#[test]
+fn a_missing_base_language_folder_lists_the_languages_found() {
+    let dir = fixture("base-language-missing", &PROJECT);
+    let (code, _, stderr) = check(&dir, &["locales", "--base-lang", "de"]);
+
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("no `de` base language folder in locales (found: en, fr)"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn the_directory_is_given_once() {
+    let (code, _, stderr) = check(
+        &fixture("base-language-twice", &PROJECT),
+        &["locales", "--i18n-dir", "locales"],
+    );
+    assert_eq!(code, Some(2));
+    assert!(stderr.contains("`--i18n-dir`"), "{}", stderr);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{TempDir, run};
+
+const MODULE: &str = r#"import type { Messages } from './types';
+
+// The source of truth for every other language.
+export default {
+  checkout: {
+    title: 'Checkout',
+    total: `Total: ${amount}`,
+    'legal-notice': "Terms é",
+  },
+  greeting: `Hello ${name},
+welcome`,
+} satisfies Messages;
+"#;
+
+fn fixture(name: &str, module: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write("i18n/en.ts", module);
+    dir.write(
+        "i18n/de/de.json",
+        r#"{ "checkout": { "title": "Kasse", "total": "Summe" }, "greeting": "Hallo {name},\nwillkommen" }"#,
+    );
+    dir
+}
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--base-lang",
+        "en",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn a_typescript_module_serves_as_the_base() {
+    let (code, stdout) = check(&fixture("base-module", MODULE));
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("❌ Missing keys:\n   - Key: checkout.legal-notice"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "   - Key: checkout.total\n   - Expected variables (EN): {\"amount\"}\n   - Found variables (DE): {}\n   - Location: Expected in i18n/en.ts but found in i18n/de/de.json\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: greeting"), "{}", stdout);
+    assert!(
+        stdout.contains("Translation issues found: 2 errors"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn unsupported_syntax_is_reported_with_its_position() {
+    let dir = fixture(
+        "base-module-computed",
+        "const shared = 'x';\nexport default {\n  checkout: {\n    title: shared,\n  },\n};\n",
+    );
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🚷 Unreadable translation files:\n   - Error: unsupported module syntax: unsupported value (only strings and objects are read) at line 4 column 12 | File: i18n/en.ts"
+        ),
+        "{}",
+        stdout
+    );
+
+    dir.write(
+        "i18n/en.ts",
+        "export default { total: `Total: ${amount + 1}` };\n",
+    );
+    let (_, stdout) = check(&dir);
+    assert!(
+        stdout.contains("unsupported substitution `${amount + 1}`"),
+        "{}",
+        stdout
+    );
+}
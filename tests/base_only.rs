@@ -0,0 +1,93 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::{Value, json};
+
+const PROJECT: [(&str, &str); 5] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+            "title": "Titre",
+            "dev": { "debugPanel": { "open": "{panel}", "close": "Fermer" } },
+            "tools": { "dev": { "reset": "Réinitialiser" } }
+        }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "dev": { "debugPanel": { "close": "Schließen" } } }"#,
+    ),
+    ("i18n/it/common.json", r#"{ "title": "Titolo" }"#),
+    ("src/app.ts", "t('title'); t('dev.debugPanel.open');\n"),
+    (
+        "translation-check.toml",
+        "base_only_namespaces = [\"dev.\", \"tools.*.reset\"]\n",
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> String {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn base_only_keys_are_not_missing_but_must_not_be_translated() {
+    let dir = fixture("base-only", &PROJECT);
+    let stdout = check(&dir, &[]);
+
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🧪 Base-only keys translated:\n   - Key: dev.debugPanel.close | File: i18n/de/common.json:1:46\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("🧪 Base-only keys exempted from translation: 3\n"),
+        "{}",
+        stdout
+    );
+    // Still checked in the base, and for usage.
+    assert!(
+        stdout.contains("   - Key: dev.debugPanel.open | Problem: only placeholders"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("🧹 Unused keys: 2\n"), "{}", stdout);
+}
+
+#[test]
+fn the_exempted_count_is_in_the_json_summary() {
+    let dir = fixture("base-only-json", &PROJECT);
+    let report: Value = serde_json::from_str(&check(&dir, &["--format", "json"])).unwrap();
+
+    assert_eq!(report["summary"]["base_only"], json!(3));
+    let checks: Vec<(&str, &str, &str)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["lang"].is_string() && finding["lang"] != "fr")
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap(),
+                finding["lang"].as_str().unwrap(),
+                finding["key"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        checks,
+        [("base_only_key_translated", "de", "dev.debugPanel.close")]
+    );
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{TempDir, run};
+
+#[test]
+fn bad_base_values_replace_their_derived_findings() {
+    let dir = TempDir::new("base-quality");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "greeting": "{name}", "empty": "", "shop.title": "shop.title", "ok": "Bonjour {name}" }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "greeting": "Hallo", "empty": "Leer {count}", "shop.title": "Laden", "ok": "Hallo" }"#,
+    );
+    dir.write(
+        "i18n/es/common.json",
+        r#"{ "greeting": "Hola", "empty": "Vacío", "shop.title": "Tienda", "ok": "Hola {name}" }"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🚩 Suspicious base values:\n   - Key: empty | Problem: empty value | Suppressed: 1 findings in de | File: "
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains(
+        "   - Key: greeting | Problem: only placeholders | Suppressed: 2 findings in de, es | File: "
+    ));
+    assert!(stdout.contains("   - Key: shop.title | Problem: value is the key | File: "));
+    // Only the genuine mismatch is left.
+    assert_eq!(
+        stdout.matches("Variable mismatch detected!").count(),
+        1,
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("   - Key: ok\n"));
+    assert!(
+        stdout.contains("Translation issues found: 4 errors"),
+        "{}",
+        stdout
+    );
+}
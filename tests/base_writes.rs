@@ -0,0 +1,171 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::fs;
+
+// A base every fixer could find something to rewrite in: decomposed text,
+// an escaped quote and an unused key, with a German translation renaming
+// a placeholder.
+const FRENCH: &str = "{\n  \"title\": \"R\u{e9}sume\u{301}\",\n  \"address\": \"L\\\\'adresse\",\n  \"pay\": \"Payer {amount}\",\n  \"legacy\": \"Ancien\"\n}\n";
+
+const PROJECT: [(&str, &str); 3] = [
+    ("i18n/fr/common.json", FRENCH),
+    (
+        "i18n/de/common.json",
+        "{\n  \"title\": \"Zusammenfassung\",\n  \"address\": \"Die Adresse\",\n  \"pay\": \"Zahlen {betrag}\",\n  \"legacy\": \"Alt\"\n}\n",
+    ),
+    ("src/app.ts", "t('title'); t('address'); t('pay');\n"),
+];
+
+fn fix(dir: &TempDir, extra: &[&str]) -> String {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    String::from_utf8_lossy(&run(&args).stderr).to_string()
+}
+
+#[test]
+fn no_fixer_writes_the_base_by_default() {
+    for name in [
+        "nfc",
+        "translated-placeholders",
+        "quote-escapes",
+        "sync-keys",
+    ] {
+        let dir = fixture(&format!("base-writes-{}", name), &PROJECT);
+        let stderr = fix(&dir, &["--fix", name]);
+        assert_eq!(
+            fs::read(dir.path().join("i18n/fr/common.json")).unwrap(),
+            FRENCH.as_bytes(),
+            "--fix {}: {}",
+            name,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn left_alone_files_are_reported_one_by_one() {
+    let dir = fixture("base-writes-refused", &PROJECT);
+    let message = "⚠️ Left i18n/fr/common.json alone: `--fix quote-escapes` only rewrites base language files with `--allow-base-writes`\n";
+
+    let stderr = fix(&dir, &["--fix", "quote-escapes", "--dry-run"]);
+    assert!(stderr.contains(message), "{}", stderr);
+    assert!(!stderr.contains("--- i18n/fr/common.json"), "{}", stderr);
+
+    let stderr = fix(&dir, &["--fix", "quote-escapes"]);
+    assert!(stderr.contains(message), "{}", stderr);
+    assert!(stderr.contains("0 files modified"), "{}", stderr);
+}
+
+#[test]
+fn the_base_is_rewritten_when_allowed() {
+    let dir = fixture("base-writes-allowed", &PROJECT);
+    let stderr = fix(&dir, &["--fix", "nfc,sync-keys", "--allow-base-writes"]);
+    assert!(!stderr.contains("Left"), "{}", stderr);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/fr/common.json")).unwrap(),
+        "{\n  \"title\": \"R\u{e9}sum\u{e9}\",\n  \"address\": \"L\\\\'adresse\",\n  \"pay\": \"Payer {amount}\"\n}\n"
+    );
+
+    // Placeholders are named by the base, which no flag lets the fix rename.
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--fix",
+        "translated-placeholders",
+        "--allow-base-writes",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "`--allow-base-writes` requires `--fix nfc`, `--fix quote-escapes`, `--fix eol` or `--fix sync-keys`"
+    ));
+}
+
+#[test]
+fn an_unused_key_left_in_the_base_stays_reported() {
+    let dir = fixture("base-writes-unused", &PROJECT);
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--fix",
+        "sync-keys",
+        "--color",
+        "never",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json"))
+            .unwrap()
+            .contains("legacy")
+    );
+    assert!(stdout.contains("🧹 Unused keys: 1\n"), "{}", stdout);
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("⚠️ Kept 1 unused keys in every language"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// The `fix` run keeps an unused key the base defines in every language,
+// instead of removing it from the others only and reporting it missing in
+// them next.
+#[test]
+fn the_fix_run_removes_an_unused_key_everywhere_or_nowhere() {
+    let dir = fixture("base-writes-fix-run", &PROJECT);
+    let cwd = dir.path().to_str().unwrap();
+    let output = run(&["fix", "--cwd", cwd, "i18n", "--src-dir", "src"]);
+    assert!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json"))
+            .unwrap()
+            .contains("legacy"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read(dir.path().join("i18n/fr/common.json")).unwrap(),
+        FRENCH.as_bytes()
+    );
+
+    let output = run(&["--cwd", cwd, "i18n", "--src-dir", "src", "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        !report["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|finding| finding["check"] == "missing_key"),
+        "{}",
+        report
+    );
+
+    run(&[
+        "fix",
+        "--cwd",
+        cwd,
+        "i18n",
+        "--src-dir",
+        "src",
+        "--allow-base-writes",
+    ]);
+    for lang in ["fr", "de"] {
+        assert!(
+            !fs::read_to_string(dir.path().join(format!("i18n/{}/common.json", lang)))
+                .unwrap()
+                .contains("legacy"),
+            "{}",
+            lang
+        );
+    }
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+use std::fs;
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, Value, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "json",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    let report = serde_json::from_slice(&output.stdout).unwrap_or(Value::Null);
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    (output.status.code(), report, stderr)
+}
+
+fn found(report: &Value) -> Vec<(String, String)> {
+    report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap().to_string(),
+                finding["key"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn only_findings_missing_from_the_baseline_fail_the_run() {
+    let dir = TempDir::new("baseline");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{"title": "Titre", "save": "Enregistrer", "cancel": "Annuler"}"#,
+    );
+    dir.write("i18n/de/common.json", r#"{"title": "Titel"}"#);
+    dir.write("src/app.js", "t('title'); t('save'); t('cancel')");
+
+    let (code, report, stderr) = check(&dir, &["--write-baseline", "baseline.json"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert!(stderr.contains("2 findings written to the baseline baseline.json"));
+    assert_eq!(report["summary"]["baselined"], 2);
+    let baseline: Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("baseline.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        baseline["findings"][0],
+        serde_json::json!({
+            "check": "missing_key",
+            "lang": "de",
+            "key": "cancel",
+            "count": 1,
+        })
+    );
+
+    // A new finding fails the run, alone; a fixed one is pointed out.
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{"title": "Titre", "save": "Enregistrer", "cancel": "Annuler", "close": "Fermer"}"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{"title": "Titel", "save": "Speichern"}"#,
+    );
+    dir.write(
+        "src/app.js",
+        "t('title'); t('save'); t('cancel'); t('close')",
+    );
+    let (code, report, stderr) = check(&dir, &["--baseline", "baseline.json"]);
+    assert_eq!(code, Some(1), "{}", stderr);
+    assert_eq!(found(&report), [("missing_key".into(), "close".into())]);
+    assert_eq!(report["summary"]["baselined"], 1);
+    assert!(
+        stderr.contains("1 findings of the baseline are fixed"),
+        "{}",
+        stderr
+    );
+
+    // `[project] baseline` applies it by default.
+    dir.write(
+        "translation-check.toml",
+        "[project]\nbaseline = \"baseline.json\"\n",
+    );
+    let (_, report, _) = check(&dir, &[]);
+    assert_eq!(report["summary"]["baselined"], 1);
+    let (_, report, _) = check(&dir, &["--no-baseline"]);
+    assert_eq!(found(&report).len(), 2);
+
+    let (code, _, stderr) = check(&dir, &["--baseline", "missing.json"]);
+    assert_eq!(code, Some(2));
+    assert!(stderr.contains("`--write-baseline missing.json` writes it"));
+}
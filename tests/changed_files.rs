@@ -0,0 +1,204 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+// German and Spanish each miss a key, and both sources use a key the base
+// doesn't define.
+const PROJECT: [(&str, &str); 5] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "save": "Enregistrer", "legacy": "Ancien" }"#,
+    ),
+    ("i18n/de/common.json", r#"{ "title": "Titel" }"#),
+    ("i18n/es/common.json", r#"{ "title": "Título" }"#),
+    ("src/app.ts", "t('title');\nt('app.missing');\n"),
+    ("src/page.ts", "t('save');\nt('page.missing');\n"),
+];
+
+fn args<'a>(dir: &'a TempDir, extra: &[&'a str]) -> Vec<&'a str> {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "json",
+    ];
+    args.extend_from_slice(extra);
+    args
+}
+
+// The findings of a run, as `check lang key`.
+fn findings(output: &Output) -> Vec<String> {
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut findings: Vec<String> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            format!(
+                "{} {} {}",
+                finding["check"].as_str().unwrap(),
+                finding["lang"].as_str().unwrap_or("-"),
+                finding["key"].as_str().unwrap_or("-")
+            )
+        })
+        .collect();
+    findings.sort();
+    findings
+}
+
+fn changed(dir: &TempDir, files: &str) -> Output {
+    let list = dir.write("changed.txt", files);
+    run(&args(dir, &["--changed-files", list.to_str().unwrap()]))
+}
+
+#[test]
+fn changed_translations_only_compare_their_languages() {
+    let dir = fixture("changed-translations", &PROJECT);
+    let full = findings(&run(&args(&dir, &[])));
+
+    let output = changed(&dir, "i18n/de/common.json\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(
+            "Checking changed files only: de and 0 source files; other languages and unused keys were not checked"
+        ),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let de: Vec<String> = full
+        .iter()
+        .filter(|finding| finding.contains(" de "))
+        .cloned()
+        .collect();
+    assert_eq!(findings(&output), de);
+    assert_eq!(de, ["missing_key de legacy", "missing_key de save"]);
+}
+
+#[test]
+fn changed_sources_are_scanned_alone() {
+    let dir = fixture("changed-sources", &PROJECT);
+    let output = changed(&dir, "./src/app.ts\nREADME.md\n");
+    assert_eq!(findings(&output), ["undefined_key - app.missing"]);
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Checking changed files only: no languages and 1 source files"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn a_changed_base_checks_everything() {
+    let dir = fixture("changed-base", &PROJECT);
+    let full = findings(&run(&args(&dir, &[])));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args(args(&dir, &["--changed-files", "-"]))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"src/app.ts\ni18n/fr/common.json\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Checking everything: the base language changed"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(findings(&output), full);
+    assert!(full.contains(&"unused_key fr legacy".to_string()));
+}
+
+// Commits the fixture in a new repository.
+fn commit(dir: &TempDir) {
+    for args in [
+        &["init", "-q"][..],
+        &["add", "."],
+        &["commit", "-q", "-m", "Translations"],
+    ] {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .stdout(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+}
+
+#[test]
+fn since_asks_git_what_changed() {
+    let dir = fixture("since", &PROJECT);
+    commit(&dir);
+    dir.write(
+        "i18n/es/common.json",
+        r#"{ "title": "Título", "save": "Guardar" }"#,
+    );
+
+    let output = run(&args(&dir, &["--since", "HEAD"]));
+    assert_eq!(findings(&output), ["missing_key es legacy"]);
+}
+
+#[test]
+fn changed_since_reports_the_changed_keys_only() {
+    let dir = fixture("changed-since", &PROJECT);
+    commit(&dir);
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "save": "Sauvegarder", "legacy": "Ancien", "help": "Aide" }"#,
+    );
+    dir.write("i18n/es/common.json", r#"{ "save": "Guardar" }"#);
+    dir.write(
+        "src/page.ts",
+        "t('save');\nt('page.missing');\nt('help');\n",
+    );
+
+    let output = run(&args(&dir, &["--changed-since", "HEAD"]));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(
+            "Reporting the changes since HEAD only: 3 keys of 2 translation files and 1 other files"
+        ),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // The keys German misses since before stay out, as does the key the
+    // unchanged `src/app.ts` uses.
+    assert_eq!(
+        findings(&output),
+        [
+            "missing_key de help",
+            "missing_key de save",
+            "missing_key es help",
+            "missing_key es title",
+            "undefined_key - page.missing",
+        ]
+    );
+
+    let output = run(&args(&dir, &["--changed-since", "HEAD", "--since", "HEAD"]));
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn limited_runs_cannot_update_the_state() {
+    let output = run(&["--since", "HEAD", "--changed-files", "changed.txt"]);
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(&["--since", "HEAD", "--update-state"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("`--update-state` needs the whole run and can't be combined with `--since`")
+    );
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{TempDir, large_fixture, run};
+use std::time::{Duration, Instant};
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+fn last_line(text: &str) -> &str {
+    text.lines().last().unwrap_or_default()
+}
+
+#[test]
+fn only_the_summary_line_is_printed() {
+    let dir = TempDir::new("check-only");
+    dir.write("i18n/fr/common.json", r#"{ "a": "A", "b": "B" }"#);
+    dir.write("i18n/de/common.json", r#"{ "a": "A", "c": "C" }"#);
+    dir.write("src/app.ts", "t('a'); t('b');\n");
+
+    let (code, stdout, _) = check(&dir, &[]);
+    let (fast_code, fast_stdout, _) = check(&dir, &["--check-only"]);
+    assert_eq!(fast_code, code);
+    assert_eq!(fast_code, Some(1));
+    assert_eq!(fast_stdout, format!("{}\n", last_line(&stdout)));
+
+    dir.write("i18n/de/common.json", r#"{ "a": "A", "b": "B" }"#);
+    let (code, stdout, _) = check(&dir, &["--check-only"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(stdout, "✅ No translation issues found.\n");
+}
+
+#[test]
+fn waived_errors_dont_stop_the_run() {
+    let dir = TempDir::new("check-only-waived");
+    dir.write("i18n/fr/common.json", r#"{ "a": "A", "b": "B" }"#);
+    dir.write("i18n/de/common.json", r#"{ "a": "A" }"#);
+    dir.write("src/app.ts", "t('a'); t('b'); t('undefined.key');\n");
+    let waivers = dir.write(
+        "waivers.toml",
+        "[[waiver]]\ncheck = \"missing_key\"\nkey = \"b\"\n",
+    );
+
+    let (code, stdout, _) = check(
+        &dir,
+        &["--check-only", "--waivers", waivers.to_str().unwrap()],
+    );
+
+    // The undefined key, found by the source scan, still fails the run.
+    assert_eq!(code, Some(1));
+    assert_eq!(
+        stdout,
+        "❌ Translation issues found: 1 errors, 0 warnings.\n"
+    );
+}
+
+#[test]
+fn reports_cant_be_requested() {
+    let output = run(&["i18n", "--check-only", "--report", "json:report.json"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(
+            "`--check-only` only sets the exit code and can't be combined with `--report`"
+        )
+    );
+}
+
+// Keeps the fast path honest on the large fixture: with an error in the
+// translations, `--check-only` never scans the sources.
+#[test]
+fn the_first_failing_check_ends_the_run() {
+    let dir = TempDir::new("check-only-bench");
+    large_fixture(&dir, 20, 10, 200);
+    // Read last, the namespace defines the value of `key1`.
+    dir.write("i18n/en-007/ns9.json", r#"{ "key1": "Drift {other}" }"#);
+    for file in 0..50 {
+        dir.write(
+            &format!("src/module{}.ts", file),
+            &"t('key1'); t('key2'); // key3\n".repeat(200),
+        );
+    }
+
+    let time = |extra: &[&str]| {
+        let mut best = Duration::MAX;
+        let mut result = None;
+        for _ in 0..3 {
+            let start = Instant::now();
+            result = Some(check(&dir, extra));
+            best = best.min(start.elapsed());
+        }
+        (best, result.unwrap())
+    };
+    let (full, (code, stdout, _)) = time(&[]);
+    let (fast, (fast_code, fast_stdout, _)) = time(&["--check-only"]);
+
+    assert_eq!(code, Some(1));
+    assert_eq!(fast_code, Some(1));
+    assert_eq!(fast_stdout.lines().count(), 1, "{}", fast_stdout);
+    assert!(stdout.len() > fast_stdout.len());
+    assert!(
+        fast < full,
+        "--check-only took {:?}, the full run {:?}",
+        fast,
+        full
+    );
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use check_translations::codeowners::CodeOwners;
+use common::{TempDir, fixture, run};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::PathBuf;
+
+const RULES: &str = r"
+# Everything else
+*                     @org/all
+*.json                @org/json
+/docs/                @org/docs
+docs/*                @org/flat-docs
+apps/                 @org/apps
+**/logs               @org/logs
+/build/logs/          @org/build
+/i18n/fr/**           @org/fr @alice
+/i18n/de/legacy.json
+/src/?.ts             @org/single  # one-letter files
+/src/my\ file.ts      @org/spaces
+";
+
+fn owners<'a>(codeowners: &'a CodeOwners, path: &str) -> Vec<&'a str> {
+    codeowners
+        .owners_of(path)
+        .iter()
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn the_last_matching_rule_wins() {
+    let codeowners = CodeOwners::parse(RULES, PathBuf::from("/repo")).unwrap();
+
+    for (path, expected) in [
+        ("README.md", vec!["@org/all"]),
+        ("a/b/c.json", vec!["@org/json"]),
+        // `docs/*` only owns the files directly in `docs`.
+        ("docs/intro.md", vec!["@org/flat-docs"]),
+        ("docs/guides/setup.md", vec!["@org/docs"]),
+        ("nested/docs/intro.md", vec!["@org/all"]),
+        // Unanchored directories match at any depth, but only directories.
+        ("web/apps/shop/main.ts", vec!["@org/apps"]),
+        ("apps", vec!["@org/all"]),
+        ("deep/tree/logs/today.txt", vec!["@org/logs"]),
+        ("build/logs/today.txt", vec!["@org/build"]),
+        ("i18n/fr/shop/cart.json", vec!["@org/fr", "@alice"]),
+        ("i18n/de/common.json", vec!["@org/json"]),
+        // A rule without owners leaves the file unowned.
+        ("i18n/de/legacy.json", vec![]),
+        ("src/a.ts", vec!["@org/single"]),
+        ("src/ab.ts", vec!["@org/all"]),
+        ("src/my file.ts", vec!["@org/spaces"]),
+    ] {
+        assert_eq!(owners(&codeowners, path), expected, "{}", path);
+    }
+}
+
+#[test]
+fn paths_outside_the_repository_have_no_owner() {
+    let codeowners = CodeOwners::parse("* @org/all\n", PathBuf::from("/repo")).unwrap();
+
+    assert_eq!(
+        codeowners.relative("/repo/i18n/fr/common.json").as_deref(),
+        Some("i18n/fr/common.json")
+    );
+    assert_eq!(codeowners.relative("/elsewhere/common.json"), None);
+}
+
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {name}", "save": "Enregistrer" }"#,
+    ),
+    ("i18n/de/common.json", r#"{ "title": "Hallo {name}" }"#),
+    (
+        "i18n/it/common.json",
+        r#"{ "title": "Ciao", "save": "Salva" }"#,
+    ),
+    (
+        ".github/CODEOWNERS",
+        "/i18n/ @org/i18n\n/i18n/de/ @org/de\n/i18n/it/\n",
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String, Value) {
+    let i18n = dir.path().join("i18n");
+    let codeowners = dir.path().join(".github/CODEOWNERS");
+    let report = dir.path().join("report.json");
+    let sink = format!("json:{}", report.display());
+    let mut args = vec![
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--codeowners",
+        codeowners.to_str().unwrap(),
+        "--report",
+        "human:-",
+        "--report",
+        &sink,
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    let report = serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        report,
+    )
+}
+
+#[test]
+fn findings_are_routed_to_their_owners() {
+    let dir = fixture("codeowners", &PROJECT);
+
+    let (code, stdout, report) = check(&dir, &["--group-by", "owner"]);
+
+    assert_eq!(code, Some(1));
+    let owners: Vec<(&str, &Value)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| (finding["lang"].as_str().unwrap(), &finding["owners"]))
+        .collect();
+    assert_eq!(owners, [("de", &json!(["@org/de"])), ("it", &json!([]))]);
+    assert!(
+        stdout.contains("👥 @org/de\n❌ Missing keys (DE):\n   - Key: save"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("👥 (unowned)\n"), "{}", stdout);
+    assert!(!stdout.contains("Files with no code owner"), "{}", stdout);
+}
+
+#[test]
+fn unowned_files_can_fail_the_run() {
+    let dir = fixture("codeowners-fail-on", &PROJECT);
+    let it = dir.path().join("i18n/it/common.json");
+
+    let (_, stdout, report) = check(&dir, &["--fail-on", "unowned"]);
+
+    assert!(
+        stdout.contains(&format!(
+            "👥 Files with no code owner:\n   - Findings: 1 | File: {}",
+            it.display()
+        )),
+        "{}",
+        stdout
+    );
+    assert!(
+        report["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|finding| finding["check"] == "unowned_file")
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--fail-on",
+        "unowned",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+// A scratch directory under the system temp dir, removed on drop.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!(
+            "check_translations-{}-{}-{}",
+            name,
+            std::process::id(),
+            NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn write(&self, relative: &str, content: &str) -> PathBuf {
+        let path = self.0.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// A project holding `files`, each a path relative to its root and the
+// content written there.
+pub fn fixture(name: &str, files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new(name);
+    for (path, content) in files {
+        dir.write(path, content);
+    }
+    dir
+}
+
+// Generates a large synthetic i18n tree: `languages` folders with `files`
+// namespaces of `keys` entries each, the first language being `fr`.
+pub fn large_fixture(dir: &TempDir, languages: usize, files: usize, keys: usize) {
+    for lang in 0..languages {
+        let lang = if lang == 0 {
+            "fr".to_string()
+        } else {
+            format!("en-{:03}", lang)
+        };
+        for file in 0..files {
+            let entries: Vec<String> = (0..keys)
+                .map(|key| format!("  \"key{}\": \"Value {} {{name}}\"", key, key))
+                .collect();
+            dir.write(
+                &format!("i18n/{}/ns{}.json", lang, file),
+                &format!("{{\n{}\n}}\n", entries.join(",\n")),
+            );
+        }
+    }
+}
+
+pub fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args(args)
+        .output()
+        .expect("failed to run binary")
+}
+
+// Compares `actual` with `tests/snapshots/<name>.txt`. Run the tests with
+// `UPDATE_SNAPSHOTS=1` to accept new output.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing snapshot {}, run with UPDATE_SNAPSHOTS=1", name));
+    assert!(
+        expected == actual,
+        "snapshot {} differs\n--- expected\n{}\n--- actual\n{}",
+        name,
+        expected,
+        actual
+    );
+}
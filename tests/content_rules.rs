@@ -0,0 +1,144 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "promo": "Offre gratuite", "legal": { "terms": "Conditions" }, "name": "Gratuit" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "promo": "Jetzt GRATIS testen", "legal": { "terms": "AGB gelten" }, "name": "Gratis" }"#,
+    ),
+    (
+        "rules.toml",
+        r#"
+[[forbidden]]
+name = "no-free"
+langs = ["de"]
+words = ["gratis", "kostenlos"]
+allow = ["name"]
+
+[[required]]
+name = "terms-reference"
+keys = ["legal.**"]
+phrase = "AGB"
+severity = "warning"
+
+[[required]]
+name = "german-only"
+langs = ["de"]
+keys = ["promo"]
+phrase = "testen"
+"#,
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn forbidden_words_and_required_phrases_are_reported() {
+    let dir = fixture("content-rules", &PROJECT);
+    let (code, stdout, _) = check(&dir, &["--content-rules", "rules.toml"]);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("Key: promo | Rule: no-free | Word: GRATIS"),
+        "{}",
+        stdout
+    );
+    // `name` is allowed to say it, and `fr` isn't covered by the rule.
+    assert_eq!(stdout.matches("Rule: no-free").count(), 1, "{}", stdout);
+    // The French terms lack the phrase; only German must say `testen`.
+    assert!(
+        stdout.contains("Key: legal.terms | Rule: terms-reference | Phrase: AGB"),
+        "{}",
+        stdout
+    );
+    assert_eq!(
+        stdout.matches("Rule: terms-reference").count(),
+        1,
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("german-only"), "{}", stdout);
+    assert!(
+        stdout.contains("Translation issues found: 1 errors, 1 warnings."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn words_only_match_whole_words() {
+    let dir = fixture("content-rules-boundaries", &PROJECT);
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "promo": "Gratisproben testen", "legal": { "terms": "AGB" }, "name": "Gratis" }"#,
+    );
+    let (code, stdout, _) = check(&dir, &["--content-rules", "rules.toml"]);
+
+    assert!(!stdout.contains("no-free"), "{}", stdout);
+    assert_eq!(code, Some(0), "{}", stdout);
+}
+
+#[test]
+fn lint_config_validates_the_rules() {
+    let dir = fixture("content-rules-lint", &PROJECT);
+    let root = dir.path().to_str().unwrap();
+    let output = run(&[
+        "--cwd",
+        root,
+        "lint-config",
+        "--content-rules",
+        "rules.toml",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(
+        stdout.contains("rules.toml is valid: 1 forbidden word rules, 2 required phrase rules"),
+        "{}",
+        stdout
+    );
+
+    dir.write(
+        "rules.toml",
+        "[[forbidden]]\nname = \"loud\"\nwords = [\"FREE\"]\nseverity = \"fatal\"\n",
+    );
+    let output = run(&[
+        "--cwd",
+        root,
+        "lint-config",
+        "--content-rules",
+        "rules.toml",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid severity `fatal` for forbidden `loud`"),
+        "{}",
+        stderr
+    );
+
+    let (code, _, stderr) = check(&dir, &["--content-rules", "rules.toml"]);
+    assert_eq!(code, Some(2));
+    assert!(stderr.contains("invalid content rules"), "{}", stderr);
+}
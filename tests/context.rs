@@ -0,0 +1,168 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::{Value, json};
+use std::fs;
+
+// German lost the placeholder of `checkout.total` and misses
+// `checkout.title`; the manifest still pictures a `profile` screen that is
+// gone.
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "translation-check.toml",
+        "[context]\nmanifest = \"design/context.json\"\nmax_links = 2\n",
+    ),
+    (
+        "design/context.json",
+        r#"{
+  "checkout.*": "https://design.example.com/file/checkout",
+  "checkout.total": ["https://shots.example.com/total.png", "https://shots.example.com/total-mobile.png"],
+  "profile.**": "https://shots.example.com/profile.png"
+}"#,
+    ),
+    (
+        "i18n/fr/common.json",
+        r#"{ "checkout": { "total": "Total : {amount}", "title": "Paiement" }, "home": "Accueil" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "checkout": { "total": "Summe" }, "home": "Start" }"#,
+    ),
+];
+
+fn check(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--color",
+        "never",
+    ];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn findings_show_the_context_of_their_key() {
+    let dir = fixture("context-human", &PROJECT);
+    let (code, stdout, stderr) = check(&dir, &[]);
+
+    assert_eq!(code, Some(1), "{}", stderr);
+    assert!(
+        stdout.contains(
+            "   - Key: checkout.title | File: i18n/de/common.json\n     context: https://design.example.com/file/checkout\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "     DE │ Summe\n     context: https://design.example.com/file/checkout\n     context: https://shots.example.com/total.png\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("total-mobile.png"), "{}", stdout);
+    assert!(
+        stdout.contains("   - Pattern: profile.** | Links: 1 | File: design/context.json\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("context: https://shots.example.com/profile.png"));
+}
+
+#[test]
+fn json_html_and_worklists_carry_the_links() {
+    let dir = fixture("context-outputs", &PROJECT);
+    let (_, stdout, _) = check(&dir, &["--format", "json"]);
+    let report: Value = serde_json::from_str(&stdout).unwrap();
+    let findings = report["findings"].as_array().unwrap();
+    let linked = |check: &str| {
+        findings
+            .iter()
+            .find(|finding| finding["check"] == check)
+            .map(|finding| finding["context"].clone())
+            .unwrap()
+    };
+    assert_eq!(
+        linked("variable_mismatch"),
+        json!([
+            "https://design.example.com/file/checkout",
+            "https://shots.example.com/total.png"
+        ])
+    );
+    assert_eq!(
+        linked("missing_key"),
+        json!(["https://design.example.com/file/checkout"])
+    );
+    assert_eq!(linked("stale_context"), Value::Null);
+
+    let html = dir.path().join("report.html");
+    check(&dir, &["--html", html.to_str().unwrap()]);
+    let html = fs::read_to_string(html).unwrap();
+    assert!(
+        html.contains(
+            "[\"https://design.example.com/file/checkout\",\"https://shots.example.com/total.png\"]"
+        ),
+        "{}",
+        html
+    );
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "worklist",
+        "--lang",
+        "de",
+        "i18n",
+    ]);
+    let worklist = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        worklist.contains(
+            "- [ ] `checkout.total` in `i18n/de/common.json`\n  Expected variables (FR): {\"amount\"}, Found variables (DE): {}\n  Context: https://design.example.com/file/checkout\n  Context: https://shots.example.com/total.png\n"
+        ),
+        "{}",
+        worklist
+    );
+}
+
+#[test]
+fn lint_config_validates_the_manifest() {
+    let dir = fixture("context-lint", &PROJECT);
+    let lint = || {
+        let output = run(&["--cwd", dir.path().to_str().unwrap(), "lint-config"]);
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )
+    };
+    let (code, stdout, _) = lint();
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(
+        stdout.contains("design/context.json is valid: 3 key patterns, 4 links"),
+        "{}",
+        stdout
+    );
+
+    dir.write(
+        "design/context.json",
+        r#"{ "checkout.*": ["https://design.example.com/file/checkout", "checkout.png"] }"#,
+    );
+    let (code, _, stderr) = lint();
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains(
+            "invalid context manifest design/context.json: `checkout.*` links to `checkout.png`, which isn't an http(s) URL"
+        ),
+        "{}",
+        stderr
+    );
+    let (code, _, _) = check(&dir, &[]);
+    assert_eq!(code, Some(2));
+}
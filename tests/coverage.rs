@@ -0,0 +1,330 @@
+mod common;
+
+use check_translations::report::{Check, Coverage, Finding, Report};
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+use std::fs;
+
+// German lacks `checkout.pay` and the ignored legal text, renames a
+// placeholder and keeps a key the base dropped.
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "i18n/fr/app.json",
+        r#"{
+            "greeting": "Bonjour {name}",
+            "checkout": { "title": "Paiement", "pay": "Payer" },
+            "legal": { "terms": "Conditions" }
+        }"#,
+    ),
+    (
+        "i18n/de/app.json",
+        r#"{
+            "greeting": "Hallo {vorname}",
+            "checkout": { "title": "Kasse" },
+            "old": "Alt"
+        }"#,
+    ),
+    (
+        "i18n/es/app.json",
+        r#"{
+            "greeting": "Hola {name}",
+            "checkout": { "title": "Pago", "pay": "Pagar" },
+            "legal": { "terms": "Condiciones" }
+        }"#,
+    ),
+    ("translation-check.toml", "[[ignore]]\nkey = \"legal.**\"\n"),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn coverage_leaves_out_ignored_keys() {
+    let dir = fixture("coverage", &PROJECT);
+    let report = dir.path().join("report.json");
+    let (_, stdout) = check(
+        &dir,
+        &[
+            "--report",
+            &format!("json:{}", report.display()),
+            "--report",
+            "human:-",
+        ],
+    );
+    assert!(
+        stdout.contains(
+            "📈 Coverage per language:\n   Language    Keys  Translated  Missing  Extra  Mismatches  Coverage\n   DE             3           2        1      1           1     66.7%\n   ES             3           3        0      0           0    100.0%\n"
+        ),
+        "{}",
+        stdout
+    );
+
+    let report: Value = serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(
+        report["coverage"][0],
+        serde_json::json!({
+            "lang": "de",
+            "total_keys": 3,
+            "translated": 2,
+            "missing": 1,
+            "extra": 1,
+            "variable_mismatches": 1,
+            "coverage": 66.7,
+        })
+    );
+}
+
+#[test]
+fn min_coverage_fails_languages_below_it() {
+    let dir = fixture("coverage-minimum", &PROJECT);
+    // Every finding of the run is waived, so only the threshold can fail it.
+    let waived: String = ["missing_key", "extra_key", "translated_placeholder"]
+        .iter()
+        .map(|check| format!("[[waiver]]\ncheck = \"{}\"\nlang = \"de\"\n", check))
+        .collect();
+    dir.write("waivers.toml", &waived);
+    let waivers = dir.path().join("waivers.toml");
+    let waivers = waivers.to_str().unwrap();
+
+    let (code, stdout) = check(&dir, &["--waivers", waivers, "--min-coverage", "60"]);
+    assert_eq!(code, Some(0), "{}", stdout);
+
+    let (code, stdout) = check(&dir, &["--waivers", waivers, "--min-coverage", "95"]);
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "Languages below the minimum coverage:\n   - Coverage: 66.7% | Minimum: 95%\n"
+        ),
+        "{}",
+        stdout
+    );
+    // A waived missing key still counts as missing.
+    assert!(
+        stdout.contains("   DE             3           2"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: checkout.pay"), "{}", stdout);
+
+    let output = run(&["--only", "unused-keys", "--min-coverage", "95"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn coverage_only_prints_the_table() {
+    let dir = fixture("coverage-only", &PROJECT);
+    let (code, stdout) = check(&dir, &["--coverage-only"]);
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert_eq!(
+        stdout,
+        "🌍 Translation Consistency Check Complete\n📈 Coverage per language:\n   Language    Keys  Translated  Missing  Extra  Mismatches  Coverage\n   DE             3           2        1      1           1     66.7%\n   ES             3           3        0      0           0    100.0%\n❌ Translation issues found: 3 errors, 0 warnings.\n"
+    );
+}
+
+#[test]
+fn named_runs_are_short_for_their_options() {
+    let dir = fixture("coverage-named-runs", &PROJECT);
+    let run_with = |args: &[&str]| {
+        let mut all = vec!["--cwd", dir.path().to_str().unwrap()];
+        all.extend(args);
+        let output = run(&all);
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )
+    };
+    let plain = ["i18n", "--only", "consistency", "--color", "never"];
+
+    let (_, check, _) = run_with(&[&["check"], &plain[..]].concat());
+    let (_, default, _) = run_with(&plain);
+    assert_eq!(check, default);
+
+    let (code, _, stderr) = run_with(&["unused", "i18n", "--only", "consistency"]);
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("`unused` can't be combined with `--only consistency`"),
+        "{}",
+        stderr
+    );
+}
+
+// `stats` counts as the coverage table does: ignored and base-only keys left
+// out, the elements of a shorter array missing and blank values untranslated.
+#[test]
+fn stats_agree_with_the_coverage() {
+    let dir = fixture("coverage-stats", &PROJECT);
+    dir.write(
+        "i18n/fr/list.json",
+        r#"{ "steps": ["un", "deux"], "note": "Note" }"#,
+    );
+    dir.write("i18n/de/list.json", r#"{ "steps": ["eins"], "note": " " }"#);
+    let report = dir.path().join("report.json");
+    let (code, _) = check(
+        &dir,
+        &[
+            "--coverage-only",
+            "--report",
+            &format!("json:{}", report.display()),
+        ],
+    );
+    assert_eq!(code, Some(1));
+    let report: Value = serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    let coverage_only: Vec<(String, u64, u64, f64)> = report["coverage"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["lang"].as_str().unwrap().to_string(),
+                row["translated"].as_u64().unwrap(),
+                row["missing"].as_u64().unwrap(),
+                row["coverage"].as_f64().unwrap(),
+            )
+        })
+        .collect();
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "stats",
+        "--format",
+        "json",
+        "i18n",
+    ]);
+    let stats: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let stats: Vec<(String, u64, u64, f64)> = stats["languages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["lang"].as_str().unwrap().to_string(),
+                row["translated"].as_u64().unwrap(),
+                row["missing"].as_u64().unwrap() + row["empty"].as_u64().unwrap(),
+                row["completion"].as_f64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(stats, coverage_only);
+    assert_eq!(stats[0], ("de".to_string(), 3, 3, 50.0));
+}
+
+#[test]
+fn missing_elements_of_shorter_arrays_count_as_missing() {
+    let dir = TempDir::new("coverage-arrays");
+    dir.write(
+        "i18n/fr/app.json",
+        r#"{ "title": "Titre", "steps": ["un", "deux"] }"#,
+    );
+    dir.write(
+        "i18n/de/app.json",
+        r#"{ "title": "Titel", "steps": ["eins"] }"#,
+    );
+    let (code, stdout) = check(&dir, &["--min-coverage", "90"]);
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("   DE             3           2        1      0           0     66.7%\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "Languages below the minimum coverage:\n   - Coverage: 66.7% | Minimum: 90%\n"
+        ),
+        "{}",
+        stdout
+    );
+}
+
+fn measure(findings: Vec<Finding>, base_keys: &[&str]) -> Coverage {
+    let mut report = Report::default();
+    report.extend(findings);
+    report.measure_coverage(
+        &["de"],
+        base_keys,
+        |_, key| key.starts_with("dev."),
+        |_, key| key == "note",
+    );
+    report.coverage.remove(0)
+}
+
+#[test]
+fn coverage_counts_each_base_key_the_findings_leave_absent() {
+    let length_mismatch = |key: &str, base: usize, found: usize| {
+        Finding::new(Check::ArrayLengthMismatch)
+            .lang("de")
+            .key(key)
+            .detail("Length (FR)", base.to_string())
+            .detail("Length (DE)", found.to_string())
+    };
+
+    // A missing key, and an array missing as a whole, which one finding
+    // stands for.
+    let coverage = measure(
+        vec![
+            Finding::new(Check::MissingKey).lang("de").key("title"),
+            Finding::new(Check::MissingKey).lang("de").key("steps"),
+            Finding::new(Check::MissingKey).lang("fr").key("help"),
+        ],
+        &["title", "help", "steps.0", "steps.1", "stepsTitle"],
+    );
+    assert_eq!(
+        (coverage.total, coverage.translated, coverage.missing),
+        (5, 2, 3)
+    );
+    assert_eq!(coverage.percent(), 40.0);
+
+    // The elements past the end of a shorter array, however deep.
+    let coverage = measure(
+        vec![
+            length_mismatch("steps", 3, 1),
+            length_mismatch("faq.0.answers", 2, 0),
+        ],
+        &[
+            "steps.0",
+            "steps.1",
+            "steps.2.title",
+            "faq.0.answers.0",
+            "faq.0.answers.1",
+            "faq.0.question",
+        ],
+    );
+    assert_eq!((coverage.translated, coverage.missing), (2, 4));
+
+    // A blank value isn't translated.
+    let coverage = measure(Vec::new(), &["title", "note"]);
+    assert_eq!((coverage.translated, coverage.missing), (1, 1));
+
+    // A longer array misses nothing, and exempt keys aren't counted.
+    let coverage = measure(
+        vec![
+            length_mismatch("steps", 1, 2),
+            Finding::new(Check::MissingKey).lang("de").key("dev.debug"),
+        ],
+        &["steps.0", "dev.debug"],
+    );
+    assert_eq!(
+        (coverage.total, coverage.translated, coverage.missing),
+        (1, 1, 0)
+    );
+    assert_eq!(coverage.percent(), 100.0);
+
+    // A base without keys is fully covered.
+    assert_eq!(measure(Vec::new(), &[]).percent(), 100.0);
+}
@@ -0,0 +1,222 @@
+#![cfg(unix)]
+
+mod common;
+
+use check_translations::daemon::{self, SOCKET_FILE, read_message, write_message};
+use common::TempDir;
+use serde_json::json;
+use std::io;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::process::{Child, Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// A `serve` run for `dir`, killed on drop.
+struct Serving(Child);
+
+impl Serving {
+    fn start(dir: &TempDir) -> Serving {
+        let child = Command::new(env!("CARGO_BIN_EXE_check_translations"))
+            .args(["--cwd", dir.path().to_str().unwrap(), "serve"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let socket = dir.path().join(SOCKET_FILE);
+        let started = Instant::now();
+        while UnixStream::connect(&socket).is_err() {
+            assert!(
+                started.elapsed() < Duration::from_secs(20),
+                "the server never listened"
+            );
+            thread::sleep(Duration::from_millis(20));
+        }
+        Serving(child)
+    }
+}
+
+impl Drop for Serving {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn project(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{"title": "Titre", "extra": "En trop"}"#,
+    );
+    dir.write("i18n/de/common.json", r#"{"title": "Titel"}"#);
+    dir.write("src/app.js", "t('title')");
+    dir
+}
+
+fn check(dir: &TempDir, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args(["--cwd", dir.path().to_str().unwrap(), "--color", "never"])
+        .args(["--src-dir", "src", "--base-lang", "fr"])
+        .args(args)
+        .args(["i18n"])
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn delegated_runs_share_the_server_state() {
+    let dir = project("daemon_shared");
+    let _serving = Serving::start(&dir);
+
+    let first = check(&dir, &["--timings"]);
+    assert!(
+        stderr(&first).contains("translation files flattened: 2 of 2"),
+        "{}",
+        stderr(&first)
+    );
+    // The server kept what the first run flattened.
+    let second = check(&dir, &["--timings"]);
+    assert!(
+        stderr(&second).contains("translation files flattened: 0 of 2"),
+        "{}",
+        stderr(&second)
+    );
+
+    let delegated = check(&dir, &[]);
+    let in_process = check(&dir, &["--no-daemon"]);
+    assert_eq!(delegated.status.code(), Some(1));
+    assert_eq!(delegated.status.code(), in_process.status.code());
+    assert_eq!(delegated.stdout, in_process.stdout);
+    assert_eq!(stderr(&delegated), stderr(&in_process));
+}
+
+#[test]
+fn server_refuses_an_older_client_politely() {
+    let dir = project("daemon_old_client");
+    let _serving = Serving::start(&dir);
+
+    let mut stream = UnixStream::connect(dir.path().join(SOCKET_FILE)).unwrap();
+    let root = std::fs::canonicalize(dir.path()).unwrap();
+    write_message(
+        &mut stream,
+        &json!({ "version": "0.0.1", "root": root.display().to_string() }),
+    )
+    .unwrap();
+    let greeting = read_message(&mut stream).unwrap();
+    assert_eq!(greeting["accepted"], false);
+    assert_eq!(greeting["version"], daemon::VERSION);
+    assert!(
+        greeting["message"]
+            .as_str()
+            .unwrap()
+            .contains("restart `check_translations serve`"),
+        "{}",
+        greeting
+    );
+    // Nothing more is read from a refused client.
+    assert!(read_message(&mut stream).is_err());
+}
+
+#[test]
+fn client_falls_back_from_a_stale_server() {
+    let dir = project("daemon_stale_server");
+    // A server of an older binary, refusing this one.
+    let listener = UnixListener::bind(dir.path().join(SOCKET_FILE)).unwrap();
+    let old = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let hello = read_message(&mut stream).unwrap();
+        write_message(
+            &mut stream,
+            &json!({
+                "accepted": false,
+                "version": "0.0.1",
+                "message": format!(
+                    "this server runs version 0.0.1, not {}",
+                    hello["version"].as_str().unwrap()
+                ),
+            }),
+        )
+        .unwrap();
+    });
+
+    let output = check(&dir, &[]);
+    old.join().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains(&format!(
+            "the server on .translation-check.sock refused the run: this server runs version 0.0.1, not {}; checking in-process",
+            daemon::VERSION
+        )),
+        "{}",
+        stderr(&output)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Key: extra"));
+}
+
+#[test]
+fn client_refuses_a_server_of_another_version() {
+    let dir = project("daemon_other_version");
+    // A server accepting any client, as old ones may.
+    let listener = UnixListener::bind(dir.path().join(SOCKET_FILE)).unwrap();
+    let old = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        read_message(&mut stream).unwrap();
+        write_message(
+            &mut stream,
+            &json!({ "accepted": true, "version": "0.0.1" }),
+        )
+        .unwrap();
+    });
+
+    let output = check(&dir, &[]);
+    old.join().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains(&format!(
+            "runs version 0.0.1 of check_translations, not {}; restart `check_translations serve` to use it; checking in-process",
+            daemon::VERSION
+        )),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn no_daemon_checks_in_process() {
+    let dir = project("daemon_no_daemon");
+    let listener = UnixListener::bind(dir.path().join(SOCKET_FILE)).unwrap();
+    listener.set_nonblocking(true).unwrap();
+
+    let output = check(&dir, &["--no-daemon"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!stderr(&output).contains("checking in-process"));
+    assert_eq!(
+        listener.accept().unwrap_err().kind(),
+        io::ErrorKind::WouldBlock
+    );
+}
+
+#[test]
+fn stale_socket_file_is_ignored() {
+    let dir = project("daemon_stale_socket");
+    // The socket of a server gone without removing it.
+    drop(UnixListener::bind(dir.path().join(SOCKET_FILE)).unwrap());
+
+    let output = check(&dir, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!stderr(&output).contains("⚠️"), "{}", stderr(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Key: extra"));
+
+    // A new server replaces it.
+    let _serving = Serving::start(&dir);
+    let delegated = check(&dir, &["--timings"]);
+    assert!(
+        stderr(&delegated).contains("translation files flattened: 2 of 2"),
+        "{}",
+        stderr(&delegated)
+    );
+}
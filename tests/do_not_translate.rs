@@ -0,0 +1,64 @@
+mod common;
+
+use common::{TempDir, run};
+
+#[test]
+fn marked_values_must_stay_identical() {
+    let dir = TempDir::new("do-not-translate");
+    dir.write(
+        "i18n/fr/legal.json",
+        r#"{
+  "legal": {
+    "_translationCheck": { "doNotTranslate": true },
+    "entity": "ACME Industries GmbH",
+    "sku": { "basic": "SKU-001" }
+  },
+  "errors": {
+    "_translationCheck": { "doNotTranslate": ["code"] },
+    "code": "E_PAYMENT_DECLINED",
+    "message": "Paiement refusé"
+  }
+}"#,
+    );
+    dir.write(
+        "i18n/ru/legal.json",
+        r#"{
+  "legal": { "entity": "АКМЕ Индастриз", "sku": { "basic": "SKU-001" } },
+  "errors": { "code": "E_PAYMENT_DECLINED", "message": "Платёж отклонён" }
+}"#,
+    );
+    dir.write(
+        "i18n/de/legal.json",
+        r#"{
+  "legal": { "entity": "ACME Industries GmbH", "sku": { "basic": "SKU-001 " } },
+  "errors": { "code": "E_PAYMENT_DECLINED", "message": "Zahlung abgelehnt" }
+}"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🔒 Do-not-translate value changed\n   - Key: legal.entity\n   - Expected (FR): \"ACME Industries GmbH\"\n   - Found (RU): \"АКМЕ Индастриз\"\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("   - Key: legal.sku.basic\n   - Expected (FR): \"SKU-001\"\n   - Found (DE): \"SKU-001 \"\n"));
+    assert_eq!(
+        stdout.matches("Do-not-translate value changed").count(),
+        2,
+        "{}",
+        stdout
+    );
+    // The marker block is not a key, and the scripts check leaves marked
+    // values alone.
+    assert!(!stdout.contains("_translationCheck"), "{}", stdout);
+    assert!(!stdout.contains("unexpected script"), "{}", stdout);
+}
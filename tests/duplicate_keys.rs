@@ -0,0 +1,76 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+#[test]
+fn keys_defined_in_several_files_list_every_ignored_definition() {
+    let dir = TempDir::new("duplicate-keys");
+    dir.write(
+        "i18n/fr/a.json",
+        r#"{ "title": "Titre", "menu": { "save": "Enregistrer" } }"#,
+    );
+    dir.write("i18n/fr/b.json", r#"{ "menu": { "save": "Sauver" } }"#);
+    dir.write("i18n/fr/c.json", r#"{ "menu.save": "Sauvegarder" }"#);
+    dir.write(
+        "i18n/de/a.json",
+        r#"{ "title": "Titel", "menu": { "save": "Speichern" } }"#,
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let duplicates: Vec<&Value> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "duplicate_key")
+        .collect();
+    assert_eq!(duplicates.len(), 1, "{}", report);
+    assert_eq!(duplicates[0]["lang"], "fr");
+    assert_eq!(duplicates[0]["key"], "menu.save");
+    assert_eq!(duplicates[0]["file"], "i18n/fr/c.json");
+    assert_eq!(
+        duplicates[0]["details"][0]["value"],
+        "i18n/fr/a.json, i18n/fr/b.json"
+    );
+}
+
+#[test]
+fn members_repeated_within_a_file_are_reported_with_both_locations() {
+    let dir = TempDir::new("duplicate-keys-same-file");
+    dir.write("i18n/fr/one.json", r#"{ "a": "Bonjour {name}" }"#);
+    dir.write(
+        "i18n/es/one.json",
+        "{\n  \"a\": \"Hola {name}\",\n  \"b\": { \"c\": \"x\" },\n  \"a\": \"Hola {nombre}\"\n}\n",
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let duplicates: Vec<&Value> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "duplicate_key")
+        .collect();
+    assert_eq!(duplicates.len(), 1, "{}", report);
+    assert_eq!(duplicates[0]["lang"], "es");
+    assert_eq!(duplicates[0]["key"], "a");
+    assert_eq!(duplicates[0]["file"], "i18n/es/one.json");
+    assert_eq!(duplicates[0]["line"], 4);
+    assert_eq!(duplicates[0]["column"], 3);
+    assert_eq!(duplicates[0]["details"][0]["value"], "i18n/es/one.json:2:3");
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+
+// The app uses `checkout.pay`; the end-to-end tests also select elements by
+// `checkout.title` and `checkout.coupon`, which nothing else uses.
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "checkout": { "title": "Paiement", "pay": "Payer", "coupon": "Code promo" }, "legacy": "Ancien" }"#,
+    ),
+    ("webapp/src/checkout.ts", "t('checkout.pay');\n"),
+    (
+        "webapp/e2e/checkout.spec.ts",
+        "test('pays', async ({ page }) => {\n  await page.getByText(t('checkout.title')).click();\n  await page.getByTestId('checkout.pay').click();\n});\n",
+    ),
+    (
+        "webapp/cypress/coupon.cy.js",
+        "cy.get('[data-testid=\"checkout.coupon\"]');\n",
+    ),
+];
+
+fn findings(dir: &TempDir, extra: &[&str]) -> Vec<(String, String, String)> {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "webapp",
+        "--only",
+        "unused-keys",
+        "--format",
+        "json",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap().to_string(),
+                finding["key"].as_str().unwrap_or_default().to_string(),
+                finding["details"][0]["value"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn keys_only_end_to_end_tests_use_are_not_unused() {
+    let dir = fixture("e2e-usage", &PROJECT);
+    let finding = |check: &str, key: &str, detail: &str| {
+        (check.to_string(), key.to_string(), detail.to_string())
+    };
+    assert_eq!(
+        findings(&dir, &[]),
+        [
+            finding("unused_key", "legacy", ""),
+            finding(
+                "e2e_only_usage",
+                "checkout.coupon",
+                "webapp/cypress/coupon.cy.js:1"
+            ),
+            finding(
+                "e2e_only_usage",
+                "checkout.title",
+                "webapp/e2e/checkout.spec.ts:2"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn end_to_end_globs_can_be_configured() {
+    let dir = fixture("e2e-usage-config", &PROJECT);
+    // The Cypress spec now counts as a source like any other.
+    dir.write("translation-check.toml", "[e2e]\nfiles = [\"e2e/**\"]\n");
+    let checks: Vec<(String, String)> = findings(&dir, &[])
+        .into_iter()
+        .map(|(check, key, _)| (check, key))
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            ("unused_key".to_string(), "legacy".to_string()),
+            ("e2e_only_usage".to_string(), "checkout.title".to_string()),
+        ]
+    );
+}
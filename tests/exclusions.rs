@@ -0,0 +1,85 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture(config: &str) -> TempDir {
+    let dir = TempDir::new("exclusions");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "debug": { "panel": "Panneau", "reset": "Réinitialiser" } }"#,
+    );
+    dir.write("i18n/de/common.json", r#"{ "title": "Titel" }"#);
+    dir.write(
+        "i18n/de/common.generated.json",
+        r#"{ "title": "Titel", "build": "1234" }"#,
+    );
+    dir.write("src/app.ts", "t('title');\nt('debug.panel');\n");
+    dir.write(
+        "src/vendor/widget.ts",
+        "t('widget.close');\nt('debug.panel');\n",
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> String {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "plain",
+    ]);
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn excluded_files_and_keys_are_left_out_of_every_check() {
+    let dir = fixture("");
+    let stdout = check(&dir);
+    assert!(
+        stdout.contains("\tduplicate_key\tde\ttitle\t"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("\textra_key\tde\tbuild\t"), "{}", stdout);
+    assert!(
+        stdout.contains("\tmissing_key\tde\tdebug.reset\t"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("\tundefined_key\t-\twidget.close\t"),
+        "{}",
+        stdout
+    );
+
+    let dir = fixture(
+        r#"
+[exclude]
+translations = ["*/*.generated.json"]
+sources = ["vendor/**"]
+keys = ["debug"]
+"#,
+    );
+    let stdout = check(&dir);
+    assert!(!stdout.contains("\tde\ttitle\t"), "{}", stdout);
+    assert!(!stdout.contains("build"), "{}", stdout);
+    assert!(!stdout.contains("widget.close"), "{}", stdout);
+    assert!(!stdout.contains("debug."), "{}", stdout);
+}
+
+#[test]
+fn invalid_globs_are_reported() {
+    let dir = fixture("[exclude]\nsources = [\"vendor/[\"]\n");
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid `exclude.sources` glob `vendor/[`"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use check_translations::base::Base;
+use check_translations::checks::{MOVED_FILE, check_translations, verify_file_attributions};
+use check_translations::model::Project;
+use check_translations::report::{Check, Report};
+use check_translations::timings::Timings;
+use common::{TempDir, fixture};
+use std::collections::HashSet;
+use std::fs;
+
+const PROJECT: [(&str, &str); 2] = [
+    ("i18n/fr/Common.json", r#"{ "title": "Bonjour {name}" }"#),
+    ("i18n/de/Common.json", r#"{ "title": "Hallo" }"#),
+];
+
+// Loads and checks the fixture, then lets `between` change the tree before
+// the findings are attributed for the report.
+fn check(dir: &TempDir, between: impl FnOnce()) -> Report {
+    let timings = Timings::default();
+    let project = Project::discover(&dir.path().join("i18n"), "fr", false, &timings);
+    let base = Base::language(&project, "fr");
+    let mut report = Report::default();
+    report.extend(check_translations(
+        &base,
+        &project,
+        &HashSet::new(),
+        None,
+        false,
+        80,
+    ));
+
+    between();
+    verify_file_attributions(&project, &mut report);
+    report
+}
+
+#[test]
+fn files_renamed_by_case_during_the_run_are_not_attributed() {
+    let dir = fixture("attribution-rename", &PROJECT);
+    let de = dir.path().join("i18n/de");
+
+    let report = check(&dir, || {
+        fs::rename(de.join("Common.json"), de.join("common.json")).unwrap();
+    });
+
+    let finding = &report.findings[0];
+    assert_eq!(finding.check, Check::VariableMismatch);
+    assert_eq!(finding.file.as_deref(), Some(MOVED_FILE));
+    let location = &finding.details[2].1;
+    assert!(
+        location.ends_with(&format!("found in {}", MOVED_FILE)),
+        "{}",
+        location
+    );
+    // The base file is untouched and still named.
+    assert!(location.contains("fr/Common.json"), "{}", location);
+}
+
+#[test]
+fn files_deleted_during_the_run_are_not_attributed() {
+    let dir = fixture("attribution-delete", &PROJECT);
+    let fr = dir.path().join("i18n/fr/Common.json");
+    let de = dir.path().join("i18n/de/Common.json");
+
+    let report = check(&dir, || fs::remove_file(&fr).unwrap());
+
+    let finding = &report.findings[0];
+    assert_eq!(finding.file.as_deref(), Some(de.to_str().unwrap()));
+    assert_eq!(
+        finding.details[2].1,
+        format!("Expected in {} but found in {}", MOVED_FILE, de.display())
+    );
+}
+
+#[test]
+fn unchanged_files_keep_their_paths() {
+    let dir = fixture("attribution-unchanged", &PROJECT);
+    let de = dir.path().join("i18n/de/Common.json");
+
+    let report = check(&dir, || {});
+
+    assert_eq!(
+        report.findings[0].file.as_deref(),
+        Some(de.to_str().unwrap())
+    );
+}
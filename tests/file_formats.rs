@@ -0,0 +1,168 @@
+mod common;
+
+use check_translations::{java_properties, yaml};
+use common::{TempDir, run};
+use serde_json::json;
+
+fn check(dir: &TempDir, i18n: &str) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        i18n,
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn yaml_reads_what_locale_files_hold() {
+    let document = yaml::parse(
+        r#"---
+# Comments are left out.
+fr:
+  greeting: "Bonjour {name}\t!"  # and so are trailing ones
+  quote: 'l''été'
+  plain: Note: lire # ceci
+  count: 3
+  enabled: true
+  empty:
+  days: [lundi, "mardi, mercredi"]
+  steps:
+  - Un
+  - title: Deux
+    done: false
+  literal: |
+    Ligne un
+      indentée
+
+  folded: >-
+    Ligne un
+    ligne deux
+
+    Paragraphe
+  inline: { a: 1, b: [x] }
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        document,
+        json!({ "fr": {
+            "greeting": "Bonjour {name}\t!",
+            "quote": "l'été",
+            "plain": "Note: lire",
+            "count": 3,
+            "enabled": true,
+            "empty": null,
+            "days": ["lundi", "mardi, mercredi"],
+            "steps": ["Un", { "title": "Deux", "done": false }],
+            "literal": "Ligne un\n  indentée\n",
+            "folded": "Ligne un ligne deux\nParagraphe",
+            "inline": { "a": 1, "b": ["x"] },
+        } })
+    );
+
+    assert_eq!(
+        yaml::parse("a: 1\n  b: 2\n").unwrap_err(),
+        "line 2: unexpected indentation"
+    );
+    assert!(yaml::parse("a: &anchor 1\n").is_err());
+    assert!(yaml::parse("a: 1\n---\nb: 2\n").is_err());
+}
+
+// A `#` inside quotes is text, past escaped quotes too.
+#[test]
+fn yaml_comments_start_outside_quotes() {
+    let document = yaml::parse(
+        r#"msg: 'It''s # not a comment'  # but this is
+quote: "Say \"hi\" # twice"
+path: "C:\\" # a comment after an escaped backslash
+list: ['a # b', "c"]  # trailing
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        document,
+        json!({
+            "msg": "It's # not a comment",
+            "quote": "Say \"hi\" # twice",
+            "path": "C:\\",
+            "list": ["a # b", "c"],
+        })
+    );
+    assert_eq!(
+        yaml::strip_comment("msg: 'It''s # not a comment' # note"),
+        "msg: 'It''s # not a comment' "
+    );
+}
+
+#[test]
+fn properties_keep_their_dotted_keys() {
+    let document = java_properties::parse(
+        "# Comments\n! too\ncheckout.title = Commande\ncheckout.total:Total {0}\nlong = un \\\n    deux\nescaped\\ key=caf\\u00e9 \\ud83d\\ude00\\n\n",
+    )
+    .unwrap();
+    assert_eq!(
+        document,
+        json!({
+            "checkout.title": "Commande",
+            "checkout.total": "Total {0}",
+            "long": "un deux",
+            "escaped key": "café 😀\n",
+        })
+    );
+    assert!(java_properties::parse("a = \\u00g1\n").is_err());
+}
+
+// Rails keeps one file per language, its keys under the language.
+#[test]
+fn rails_locale_files_are_checked_like_json() {
+    let dir = TempDir::new("file-formats-rails");
+    dir.write(
+        "config/locales/fr.yml",
+        "fr:\n  greeting: Bonjour {name}\n  cart:\n    empty: Panier vide\n    total: Total\n",
+    );
+    dir.write(
+        "config/locales/de.yml",
+        "de:\n  greeting: Hallo\n  cart:\n    empty: Leer\n",
+    );
+    dir.write("config/locales/it.yml", "it:\n  greeting: [Ciao\n");
+
+    let (code, stdout) = check(&dir, "config/locales");
+    assert_eq!(code, Some(1), "{}", stdout);
+    for line in [
+        "   - Key: cart.total | File: config/locales/de.yml\n",
+        "   - Key: greeting\n",
+        "   - Error: invalid YAML: line 2: expected `,` or `]` | File: config/locales/it.yml\n",
+    ] {
+        assert!(stdout.contains(line), "{}", stdout);
+    }
+    assert!(!stdout.contains("Key: de."), "{}", stdout);
+}
+
+#[test]
+fn properties_folders_are_checked_like_json() {
+    let dir = TempDir::new("file-formats-properties");
+    dir.write(
+        "i18n/fr/messages.properties",
+        "checkout.title = Commande\ncheckout.total = Total {amount}\n",
+    );
+    dir.write(
+        "i18n/de/messages.properties",
+        "checkout.title = Bestellung\nlegacy = Alt\n",
+    );
+
+    let (code, stdout) = check(&dir, "i18n");
+    assert_eq!(code, Some(1), "{}", stdout);
+    for line in [
+        "   - Key: checkout.total | File: i18n/de/messages.properties\n",
+        "   - Key: legacy | File: i18n/de/messages.properties\n",
+    ] {
+        assert!(stdout.contains(line), "{}", stdout);
+    }
+}
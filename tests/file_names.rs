@@ -0,0 +1,82 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 6] = [
+    ("i18n/fr/common.json", r#"{ "title": "Titre" }"#),
+    (
+        "i18n/fr/checkout.json",
+        r#"{ "pay": "Payer", "total": "Total" }"#,
+    ),
+    ("i18n/de/de.checkout.json", r#"{ "pay": "Bezahlen" }"#),
+    ("i18n/de/common.json", r#"{ "title": "Titel" }"#),
+    ("i18n/it/checkout.it.json", r#"{ "pay": "Paga" }"#),
+    ("i18n/it/common.it.json", r#"{ "title": "Titolo" }"#),
+];
+
+fn run_in(dir: &TempDir, args: &[&str]) -> String {
+    let mut all = vec!["--cwd", dir.path().to_str().unwrap()];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    assert_ne!(
+        output.status.code(),
+        Some(2),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn folders_mixing_conventions_are_reported() {
+    let dir = fixture("file-names-mixed", &PROJECT);
+    let stdout = run_in(&dir, &["i18n", "--only", "consistency", "--color", "never"]);
+
+    assert!(
+        stdout.contains(
+            "🗂️ Language folders mixing file naming conventions\n   - Folder: i18n/de\n   - Plain names: common.json\n   - Language prefix: de.checkout.json\n"
+        ),
+        "{}",
+        stdout
+    );
+    // A folder following one convention throughout is fine.
+    assert!(!stdout.contains("Folder: i18n/it"), "{}", stdout);
+}
+
+#[test]
+fn missing_keys_belong_to_the_file_named_by_the_rules() {
+    let dir = fixture("file-names-rules", &PROJECT);
+    let expected = |lang: &str, file: &str| format!("- [ ] `total` in `i18n/{}/{}`\n", lang, file);
+
+    let worklist = run_in(&dir, &["worklist", "--lang", "it", "i18n"]);
+    assert!(
+        worklist.contains(&expected("it", "checkout.json")),
+        "{}",
+        worklist
+    );
+
+    dir.write(
+        "translation-check.toml",
+        "[file_names]\nstrip = [\"lang-prefix\", \"lang-suffix\"]\n",
+    );
+    for (lang, file) in [("it", "checkout.it.json"), ("de", "de.checkout.json")] {
+        let worklist = run_in(&dir, &["worklist", "--lang", lang, "i18n"]);
+        assert!(worklist.contains(&expected(lang, file)), "{}", worklist);
+    }
+}
+
+#[test]
+fn unknown_rules_are_rejected() {
+    let dir = fixture("file-names-invalid", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[file_names]\nstrip = [\"locale\"]\n",
+    );
+
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("invalid rule `locale` for `file_names.strip`"),
+    );
+}
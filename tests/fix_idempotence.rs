@@ -0,0 +1,117 @@
+mod common;
+
+use common::{TempDir, run};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// A translation folder every fixer has something to do in, with one value
+// needing NFC, its placeholder renamed back and its quotes unescaped.
+fn messy(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{
+  "greeting": "Café pour {name}",
+  "quote": "L'été",
+  "title": "Titre",
+  "checkout": { "total": "Total" }
+}
+"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        "{\n  \"greeting\": \"Cafe\u{301} f\\u00fcr {vorname}, it\\\\'s\",\n  \"quote\": \"It\\\\'s\",\n  \"title\": \"Titel\"\n}\n",
+    );
+    dir.write("i18n/de/.DS_Store", "\0\0\0\x01Bud1");
+    dir
+}
+
+fn contents(dir: &Path, files: &mut BTreeMap<String, Vec<u8>>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            contents(&path, files);
+        } else {
+            files.insert(path.display().to_string(), fs::read(&path).unwrap());
+        }
+    }
+}
+
+fn fix(dir: &TempDir, fixes: &str) -> (String, BTreeMap<String, Vec<u8>>) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+        "--fix",
+        fixes,
+    ];
+    if fixes.contains("hygiene") {
+        args.push("--yes");
+    }
+    let output = run(&args);
+    let mut files = BTreeMap::new();
+    contents(&dir.path().join("i18n"), &mut files);
+    (String::from_utf8_lossy(&output.stderr).to_string(), files)
+}
+
+#[test]
+fn every_fix_is_idempotent() {
+    for fixes in [
+        "hygiene",
+        "nfc",
+        "translated-placeholders",
+        "quote-escapes",
+        "sync-keys",
+    ] {
+        let dir = messy(&format!("idempotence-{}", fixes));
+        let (first, fixed) = fix(&dir, fixes);
+        assert!(!first.contains("0 files modified"), "{}: {}", fixes, first);
+
+        let (second, again) = fix(&dir, fixes);
+        assert!(
+            second.contains(&format!("0 files modified by `--fix {}`", fixes)),
+            "{}: {}",
+            fixes,
+            second
+        );
+        assert_eq!(fixed, again, "{}", fixes);
+    }
+}
+
+#[test]
+fn fixes_applied_together_are_idempotent() {
+    let dir = messy("idempotence-all");
+    let all = "sync-keys,quote-escapes,translated-placeholders,nfc,hygiene";
+    let (first, fixed) = fix(&dir, all);
+    assert!(
+        first.contains(
+            "Fixed 1 translation files with `--fix nfc,translated-placeholders,quote-escapes`"
+        ),
+        "{}",
+        first
+    );
+    let de = dir.path().join("i18n/de/common.json");
+    assert_eq!(
+        fs::read_to_string(&de).unwrap(),
+        "{\n  \"greeting\": \"Caf\u{e9} f\\u00fcr {name}, it's\",\n  \"quote\": \"It's\",\n  \"title\": \"Titel\",\n  \"checkout\": {\n    \"total\": \"[TODO] Total\"\n  }\n}\n"
+    );
+
+    let (second, again) = fix(&dir, all);
+    for fixes in [
+        "hygiene",
+        "nfc,translated-placeholders,quote-escapes",
+        "sync-keys",
+    ] {
+        assert!(
+            second.contains(&format!("0 files modified by `--fix {}`", fixes)),
+            "{}",
+            second
+        );
+    }
+    assert_eq!(fixed, again);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use check_translations::fix::{Outcome, Transaction};
+use common::{TempDir, run};
+use std::fs;
+use std::io;
+
+#[test]
+fn a_failed_write_rolls_back_the_files_already_replaced() {
+    let dir = TempDir::new("fix-rollback");
+    let mut transaction = Transaction::default();
+    let mut paths = Vec::new();
+    for lang in ["de", "it", "nl", "pl"] {
+        let path = dir.write(&format!("i18n/{}/common.json", lang), "before");
+        transaction.rewrite(path.clone(), format!("after {}", lang));
+        paths.push(path);
+    }
+
+    let mut renames = 0;
+    let report = transaction.commit_with(None, |from, to| {
+        renames += 1;
+        if renames == 3 {
+            return Err(io::Error::other("disk full"));
+        }
+        fs::rename(from, to)
+    });
+
+    let outcomes: Vec<&Outcome> = report.files.iter().map(|(_, outcome)| outcome).collect();
+    assert_eq!(
+        outcomes,
+        [
+            &Outcome::RolledBack,
+            &Outcome::RolledBack,
+            &Outcome::Untouched,
+            &Outcome::Untouched
+        ]
+    );
+    assert_eq!(
+        report.error.as_deref(),
+        Some(format!("failed to replace {}: disk full", paths[2].display()).as_str())
+    );
+    assert_eq!(report.state(), "no translation file was changed");
+    for path in &paths {
+        assert_eq!(fs::read_to_string(path).unwrap(), "before");
+        // Neither temporary files nor backups are left behind.
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+}
+
+#[test]
+fn files_created_by_a_failed_fix_are_removed() {
+    let dir = TempDir::new("fix-rollback-created");
+    let existing = dir.write("i18n/de/common.json", "before");
+    let created = dir.path().join("i18n/de/admin/users.json");
+    let mut transaction = Transaction::default();
+    transaction.rewrite(created.clone(), "{}\n".to_string());
+    transaction.rewrite(existing.clone(), "after".to_string());
+
+    let mut renames = 0;
+    let report = transaction.commit_with(None, |from, to| {
+        renames += 1;
+        if renames == 2 {
+            return Err(io::Error::other("disk full"));
+        }
+        fs::rename(from, to)
+    });
+
+    assert_eq!(report.files[0].1, Outcome::RolledBack);
+    assert!(!created.exists());
+    assert_eq!(fs::read_to_string(&existing).unwrap(), "before");
+}
+
+#[test]
+fn backup_dir_keeps_the_copies_of_the_last_fix() {
+    let dir = TempDir::new("fix-backup-dir");
+    dir.write("i18n/fr/common.json", "{ \"title\": \"R\u{e9}sum\u{e9}\" }");
+    let de = dir.write("i18n/de/common.json", "{ \"title\": \"Cafe\u{301}\" }");
+    let backups = dir.path().join("backups");
+    let fix = |backups: &str| {
+        run(&[
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "i18n",
+            "--only",
+            "consistency",
+            "--fix",
+            "nfc",
+            "--backup-dir",
+            backups,
+        ])
+    };
+
+    let output = fix(backups.to_str().unwrap());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    assert!(
+        stderr
+            .contains("Normalized 1 translation files to NFC\n   - applied: i18n/de/common.json\n"),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(backups.join("i18n/de/common.json")).unwrap(),
+        "{ \"title\": \"Cafe\u{301}\" }"
+    );
+
+    // The next fix replaces the copies of this one.
+    fs::write(&de, "{ \"title\": \"Tha\u{308}t\" }").unwrap();
+    fix(backups.to_str().unwrap());
+    assert_eq!(
+        fs::read_to_string(backups.join("i18n/de/common.json")).unwrap(),
+        "{ \"title\": \"Tha\u{308}t\" }"
+    );
+
+    // A folder holding anything else is left alone.
+    fs::write(&de, "{ \"title\": \"Cafe\u{301}\" }").unwrap();
+    let output = fix("i18n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr);
+    assert!(
+        stderr.contains("refusing to use i18n as backup folder"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("   - untouched: i18n/de/common.json\n"));
+    assert!(stderr.contains("; no translation file was changed"));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{TempDir, assert_snapshot, run};
+use std::fs;
+use std::path::Path;
+
+// Runs the check over `tests/fixtures/<name>` and returns its exit code and
+// JSON report, with the fixture path replaced by a stable placeholder. The
+// fixtures document the layouts the check supports; new checks should
+// extend them.
+fn check(name: &str) -> (Option<i32>, String) {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    let out = TempDir::new(&format!("fixture-{}", name));
+    let report = out.path().join("report.json");
+    let target = format!("json:{}", report.display());
+
+    let output = run(&[
+        "--cwd",
+        fixture.to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+        "--report",
+        &target,
+    ]);
+    let json = fs::read_to_string(&report)
+        .unwrap_or_else(|err| panic!("{}: {}", err, String::from_utf8_lossy(&output.stderr)));
+    (
+        output.status.code(),
+        json.replace(fixture.to_str().unwrap(), "[DIR]"),
+    )
+}
+
+#[test]
+fn clean_project() {
+    let (code, json) = check("clean");
+    assert_eq!(code, Some(0));
+    assert_snapshot("fixture_clean", &json);
+}
+
+#[test]
+fn project_with_every_kind_of_finding() {
+    let (code, json) = check("all-findings");
+    assert_eq!(code, Some(1));
+    assert_snapshot("fixture_all_findings", &json);
+}
+
+#[test]
+fn vue_i18n_project_with_namespaces_and_plurals() {
+    let (code, json) = check("vue-i18n");
+    assert_eq!(code, Some(1));
+    assert_snapshot("fixture_vue_i18n", &json);
+}
@@ -0,0 +1,5 @@
+export const greet = (name: string) => t('greeting', { name });
+export const bye = (name: string) => t('farewell', { name });
+export const total = () => t('total');
+export const address = () => t('address');
+export const missing = () => t('checkout.title');
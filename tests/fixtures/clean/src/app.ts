@@ -0,0 +1,2 @@
+export const greet = (name: string) => t('greeting', { name });
+export const buttons = [t('actions.save'), t('actions.cancel')];
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::fs;
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr.json",
+        r#"{ "title": "Titre", "save": "Enregistrer" }"#,
+    ),
+    (
+        "i18n/fr.admin.json",
+        r#"{ "admin": { "users": "Utilisateurs" } }"#,
+    ),
+    ("i18n/de.json", r#"{ "title": "Titel" }"#),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn files_named_after_their_language_are_languages() {
+    let dir = fixture("flat-layout", &PROJECT);
+
+    let (code, stdout, _) = check(&dir, &[]);
+
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains("🔍 Checking DE\n❌ Missing keys:\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("   - Key: save |"), "{}", stdout);
+    assert!(stdout.contains("   - Key: admin.users |"), "{}", stdout);
+    assert!(!stdout.contains("Checking ADMIN"), "{}", stdout);
+}
+
+#[test]
+fn missing_keys_belong_to_the_file_of_the_same_namespace() {
+    let dir = fixture("flat-layout-fix", &PROJECT);
+
+    let (code, _, stderr) = check(&dir, &["--fix", "sync-keys"]);
+
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de.admin.json")).unwrap(),
+        "{\n  \"admin\": {\n    \"users\": \"[TODO] Utilisateurs\"\n  }\n}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de.json")).unwrap(),
+        r#"{ "title": "Titel", "save": "[TODO] Enregistrer" }"#
+    );
+}
+
+#[test]
+fn mixing_both_layouts_is_refused() {
+    let dir = fixture("flat-layout-mixed", &PROJECT);
+    dir.write("i18n/it/common.json", r#"{ "title": "Titolo" }"#);
+
+    let (code, _, stderr) = check(&dir, &[]);
+
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains(
+            "mixes language folders (it) with language files (de.json, fr.admin.json, fr.json); keep one layout"
+        ),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+
+#[test]
+fn format_sorts_keys_and_check_lists_files_left_to_format() {
+    let dir = TempDir::new("format");
+    let unsorted = dir.write(
+        "i18n/fr/common.json",
+        "{\"title\": \"Titre\",\n    \"menu\": {\"save\": \"Enregistrer\", \"open\": \"Ouvrir\"}, \"empty\": {}}",
+    );
+    let formatted = "{\n  \"a\": \"A\",\n  \"list\": [\n    \"un\",\n    \"deux\"\n  ]\n}\n";
+    let clean = dir.write("i18n/de/common.json", formatted);
+    dir.write("i18n/de/broken.json", "{\"a\": ");
+    dir.write("i18n/de/notes.yaml", "b: B\na: A\n");
+    let cwd = dir.path().to_str().unwrap();
+
+    let output = run(&["--cwd", cwd, "format", "--check", "i18n"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), ["i18n/fr/common.json"]);
+    assert!(
+        stderr.contains("Couldn't format i18n/de/broken.json"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("1 of 3 translation files aren't formatted"),
+        "{}",
+        stderr
+    );
+
+    let output = run(&["--cwd", cwd, "format", "i18n"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        fs::read_to_string(&unsorted).unwrap(),
+        "{\n  \"empty\": {},\n  \"menu\": {\n    \"open\": \"Ouvrir\",\n    \"save\": \"Enregistrer\"\n  },\n  \"title\": \"Titre\"\n}\n"
+    );
+    assert_eq!(fs::read_to_string(&clean).unwrap(), formatted);
+
+    fs::remove_file(dir.path().join("i18n/de/broken.json")).unwrap();
+    let output = run(&["--cwd", cwd, "format", "--check", "i18n"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    assert!(
+        stderr.contains("All 2 translation files are formatted"),
+        "{}",
+        stderr
+    );
+
+    let output = run(&[
+        "--cwd",
+        cwd,
+        "format",
+        "--indent",
+        "tab",
+        "--read-only",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+// Parsing keeps the last of the members defined twice, so formatting would
+// silently drop the others.
+#[test]
+fn files_defining_a_member_twice_are_left_alone() {
+    let dir = TempDir::new("format-duplicates");
+    dir.write("i18n/fr/common.json", "{ \"a\": \"A\" }");
+    let content = "{ \"title\": \"Titre\", \"menu\": { \"save\": \"Enregistrer\", \"save\": \"Sauver\" }, \"title\": \"Accueil\" }";
+    let path = dir.write("i18n/de/common.json", content);
+    let cwd = dir.path().to_str().unwrap();
+
+    let output = run(&["--cwd", cwd, "format", "i18n"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(
+        stderr.contains(
+            "Couldn't format i18n/de/common.json: `menu.save`, `title` defined more than once"
+        ),
+        "{}",
+        stderr
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+}
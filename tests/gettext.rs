@@ -0,0 +1,129 @@
+mod common;
+
+use check_translations::po;
+use common::{TempDir, run};
+use serde_json::json;
+
+const TEMPLATE: &str = r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+#: src/app.py:10
+msgid "Hello {name}"
+msgstr ""
+
+msgctxt "menu"
+msgid "Open"
+msgstr ""
+
+msgid "{count} file"
+msgid_plural "{count} files"
+msgstr[0] ""
+msgstr[1] ""
+"#;
+
+#[test]
+fn catalogs_are_keyed_by_msgid() {
+    let catalog = po::parse(
+        r#"# Translator comment
+msgid ""
+msgstr "Plural-Forms: nplurals=3;\n"
+
+#, fuzzy, python-format
+#| msgid "Hi {name}"
+msgid "Hello {name}"
+msgstr "Привет, "
+"{name}"
+msgctxt "menu"
+msgid "Open"
+msgstr ""
+
+msgid "{count} file"
+msgid_plural "{count} files"
+msgstr[0] "{count} файл"
+msgstr[1] "{count} файла"
+msgstr[2] "{count} \"файлов\""
+
+#~ msgid "Old"
+#~ msgstr "Старый"
+"#,
+        false,
+        "ru",
+    )
+    .unwrap();
+    assert_eq!(
+        catalog.messages,
+        json!({
+            "Hello {name}": "Привет, {name}",
+            "{count} file_one": "{count} файл",
+            "{count} file_few": "{count} файла",
+            "{count} file_many": "{count} \"файлов\"",
+            "{count} file_other": "{count} \"файлов\"",
+        })
+    );
+    assert_eq!(catalog.fuzzy, ["Hello {name}"]);
+
+    let template = po::parse(TEMPLATE, true, "en").unwrap();
+    assert_eq!(
+        template.messages,
+        json!({
+            "Hello {name}": "Hello {name}",
+            "menu|Open": "Open",
+            "{count} file_one": "{count} file",
+            "{count} file_other": "{count} files",
+        })
+    );
+    assert_eq!(
+        po::parse("msgid \"a\"\nmsgstr \"b\n", false, "de").unwrap_err(),
+        "line 2: expected a quoted string"
+    );
+}
+
+// The template stands for English in `LC_MESSAGES`, as gettext lays catalogs
+// out. German left `menu|Open` untranslated and `Hello {name}` is a fuzzy
+// guess dropping the placeholder.
+#[test]
+fn catalogs_are_compared_with_their_template() {
+    let dir = TempDir::new("gettext");
+    dir.write("locale/en/LC_MESSAGES/messages.pot", TEMPLATE);
+    dir.write(
+        "locale/de/LC_MESSAGES/messages.po",
+        r#"#, fuzzy
+msgid "Hello {name}"
+msgstr "Hallo"
+
+msgctxt "menu"
+msgid "Open"
+msgstr ""
+
+msgid "{count} file"
+msgid_plural "{count} files"
+msgstr[0] "{count} Datei"
+msgstr[1] "{count} Dateien"
+"#,
+    );
+    dir.write("translation-check.toml", "[plural_forms]\n");
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "locale",
+        "--base-lang",
+        "en",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    for line in [
+        "🌫️ Fuzzy translations awaiting review:\n   - Key: Hello {name} | File: locale/de/LC_MESSAGES/messages.po\n",
+        "   - Key: menu|Open | File: locale/de/LC_MESSAGES/messages.po\n",
+        "   - Key: Hello {name}\n   - Expected variables (EN): {\"name\"}\n",
+    ] {
+        assert!(stdout.contains(line), "{}", stdout);
+    }
+    assert!(!stdout.contains("Suspicious base values"), "{}", stdout);
+    assert!(!stdout.contains("{count} file"), "{}", stdout);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::run;
+
+const COMMANDS: [&str; 17] = [
+    "worklist",
+    "export",
+    "import",
+    "review",
+    "sample",
+    "preview",
+    "search",
+    "stats",
+    "trend",
+    "format",
+    "merge-reports",
+    "verify-bundle",
+    "plural-rules",
+    "schema-report",
+    "lint-config",
+    "jsonrpc",
+    "serve",
+];
+
+#[test]
+fn help_prints_the_usage_of_the_run() {
+    for args in [
+        &["--help"][..],
+        &["-h"],
+        &["check", "--help"],
+        &["i18n", "-h"],
+        &["help"],
+    ] {
+        let output = run(args);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert_eq!(output.status.code(), Some(0), "{:?}", args);
+        assert!(
+            stdout.contains("Usage: check_translations [check|unused|fix] [options] [i18n-dir]"),
+            "{}",
+            stdout
+        );
+        assert!(stdout.contains("  --base-lang <lang>"), "{}", stdout);
+        for command in COMMANDS {
+            assert!(
+                stdout.contains(command),
+                "{} not listed:\n{}",
+                command,
+                stdout
+            );
+        }
+    }
+}
+
+#[test]
+fn help_prints_the_usage_of_each_command() {
+    for command in COMMANDS {
+        for args in [[command, "--help"], ["help", command]] {
+            let output = run(&args);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            assert_eq!(output.status.code(), Some(0), "{:?}", args);
+            assert!(
+                stdout.contains(&format!("Usage: check_translations {}", command)),
+                "{}",
+                stdout
+            );
+            assert!(!stdout.contains("Runs:"), "{}", stdout);
+        }
+    }
+
+    let output = run(&["help", "translate"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("unknown command `translate`"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
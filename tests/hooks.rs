@@ -0,0 +1,104 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+
+fn check(dir: &TempDir, extra: &[&str]) -> std::process::Output {
+    let mut args = vec![
+        dir.path().join("i18n").to_str().unwrap().to_string(),
+        "--only".to_string(),
+        "consistency".to_string(),
+        "--config".to_string(),
+        dir.path()
+            .join("translation-check.toml")
+            .to_str()
+            .unwrap()
+            .to_string(),
+        "--state".to_string(),
+        dir.path().join("state.json").to_str().unwrap().to_string(),
+    ];
+    args.extend(extra.iter().map(|arg| arg.to_string()));
+    run(&args.iter().map(String::as_str).collect::<Vec<_>>())
+}
+
+fn fixture(name: &str, hooks: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write("i18n/fr/common.json", r#"{ "a": "A", "b": "B", "c": "C" }"#);
+    dir.write("i18n/de/common.json", r#"{ "a": "A", "b": "B", "c": "C" }"#);
+    let hooks = hooks.replace("{dir}", dir.path().to_str().unwrap());
+    dir.write("translation-check.toml", &format!("[hooks]\n{}", hooks));
+    dir
+}
+
+#[test]
+fn hooks_receive_the_changes_since_the_state_file() {
+    let dir = fixture(
+        "hooks",
+        r#"
+new-base-keys = { command = "cat >> '{dir}/new.ndjson'" }
+deleted-keys = { command = "cat >> '{dir}/deleted.ndjson'" }
+coverage-dropped-below-threshold = { command = "cat >> '{dir}/coverage.ndjson'", threshold = 90 }
+"#,
+    );
+
+    let output = check(&dir, &["--update-state"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(dir.path().join("state.json").exists());
+    assert!(!dir.path().join("new.ndjson").exists());
+
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "a": "A", "b": "B", "d": "D", "e": "E" }"#,
+    );
+    let output = check(&dir, &[]);
+    assert_eq!(output.status.code(), Some(1));
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("new.ndjson")).unwrap(),
+        "{\"event\":\"new-base-keys\",\"key\":\"d\"}\n{\"event\":\"new-base-keys\",\"key\":\"e\"}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("deleted.ndjson")).unwrap(),
+        "{\"event\":\"deleted-keys\",\"key\":\"c\"}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("coverage.ndjson")).unwrap(),
+        "{\"coverage\":50.0,\"event\":\"coverage-dropped-below-threshold\",\"lang\":\"de\",\"previous\":100.0,\"threshold\":90.0}\n"
+    );
+
+    fs::remove_file(dir.path().join("new.ndjson")).unwrap();
+    check(&dir, &["--no-hooks"]);
+    assert!(!dir.path().join("new.ndjson").exists());
+}
+
+#[test]
+fn failing_and_slow_hooks_do_not_change_the_exit_code() {
+    let dir = fixture(
+        "hooks-failing",
+        r#"
+timeout = 1
+new-base-keys = { command = "exit 3" }
+deleted-keys = { command = "exec sleep 5" }
+"#,
+    );
+    check(&dir, &["--update-state"]);
+    dir.write("i18n/fr/common.json", r#"{ "a": "A", "b": "B", "d": "D" }"#);
+    dir.write("i18n/de/common.json", r#"{ "a": "A", "b": "B", "d": "D" }"#);
+
+    let output = check(&dir, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    assert!(stderr.contains("new-base-keys hook failed: `exit 3` exited with"));
+    assert!(stderr.contains("deleted-keys hook failed: `exec sleep 5` timed out after 1s"));
+}
+
+#[test]
+fn unknown_hook_events_are_rejected() {
+    let dir = fixture("hooks-unknown", "renamed-keys = { command = \"true\" }\n");
+
+    let output = check(&dir, &[]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown hook event `renamed-keys`"));
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+
+const SIZE_BUDGET: u64 = 15 * 1024 * 1024;
+
+#[test]
+fn large_reports_embed_each_value_once() {
+    let dir = TempDir::new("html-report");
+    // 5,000 keys missing from two languages: 10,000 findings sharing 5,000
+    // values.
+    let entries: Vec<String> = (0..5000)
+        .map(|key| {
+            format!(
+                "  \"section{}.key{}\": \"Une valeur assez longue pour peser dans le rapport, numéro {} {{name}}\"",
+                key / 100,
+                key,
+                key
+            )
+        })
+        .collect();
+    dir.write(
+        "i18n/fr/common.json",
+        &format!("{{\n{}\n}}\n", entries.join(",\n")),
+    );
+    dir.write("i18n/de/common.json", "{}");
+    dir.write("i18n/es/common.json", "{}");
+    let html = dir.path().join("report.html");
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--quiet",
+        "--html",
+        html.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let size = fs::metadata(&html).unwrap().len();
+    assert!(size < SIZE_BUDGET, "report is {} bytes", size);
+
+    let content = fs::read_to_string(&html).unwrap();
+    assert_eq!(content.matches("numéro 4321 {name}").count(), 1);
+    assert_eq!(content.matches("\"section43.key4321\"").count(), 1);
+    assert_eq!(content.matches("[\"missing_key\",").count(), 10000);
+    assert!(!content.contains("<link") && !content.contains("src=\""));
+}
+
+#[test]
+fn values_cannot_break_out_of_the_embedded_data() {
+    let dir = TempDir::new("html-report-escaping");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "</script><script>alert(1)</script>", "other": "Autre" }"#,
+    );
+    dir.write("i18n/de/common.json", r#"{ "other": "Andere" }"#);
+    let html = dir.path().join("report.html");
+
+    run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--html",
+        html.to_str().unwrap(),
+    ]);
+
+    let content = fs::read_to_string(&html).unwrap();
+    assert_eq!(content.matches("</script>").count(), 2, "{}", content);
+    assert!(content.contains(r"\u003c/script>\u003cscript>alert(1)"));
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::{TempDir, assert_snapshot, run};
+
+fn mixed_fixture() -> TempDir {
+    let dir = TempDir::new("human");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "a": "A {x}", "b": "B", "c": "C" }"#,
+    );
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{ "cart": { "title": "Panier {n}" } }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "a": "A {y}", "b": "B", "z": "Z" }"#,
+    );
+    dir.write(
+        "i18n/de/shop.json",
+        r#"{ "cart": { "title": "Warenkorb" } }"#,
+    );
+    dir.write("src/app.ts", "t('a', { x }); t('b'); t('cart.title');\n");
+    dir
+}
+
+// Runs the binary with color disabled and the fixture path replaced by a
+// stable placeholder.
+fn render(dir: &TempDir, extra: &[&str]) -> String {
+    let i18n = dir.path().join("i18n");
+    let src = dir.path().join("src");
+    let mut args = vec![
+        i18n.to_str().unwrap(),
+        "--src-dir",
+        src.to_str().unwrap(),
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+
+    let output = run(&args);
+    String::from_utf8_lossy(&output.stdout).replace(dir.path().to_str().unwrap(), "[DIR]")
+}
+
+#[test]
+fn missing_keys_only() {
+    let dir = TempDir::new("human-missing");
+    dir.write("i18n/fr/common.json", r#"{ "a": "A", "b": "B", "c": "C" }"#);
+    dir.write("i18n/de/common.json", r#"{ "b": "B" }"#);
+    dir.write("src/app.ts", "t('a'); t('b'); t('c');\n");
+
+    assert_snapshot("missing_keys_only", &render(&dir, &[]));
+}
+
+#[test]
+fn mixed_categories() {
+    assert_snapshot("mixed_categories", &render(&mixed_fixture(), &[]));
+}
+
+#[test]
+fn grouped_by_file() {
+    assert_snapshot(
+        "grouped_by_file",
+        &render(&mixed_fixture(), &["--group-by", "file"]),
+    );
+}
+
+#[test]
+fn truncated_with_max_findings() {
+    assert_snapshot(
+        "truncated_with_max_findings",
+        &render(&mixed_fixture(), &["--max-findings", "2"]),
+    );
+}
+
+#[test]
+fn quiet_mode() {
+    assert_snapshot("quiet_mode", &render(&mixed_fixture(), &["--quiet"]));
+}
+
+#[test]
+fn clean_run() {
+    let dir = TempDir::new("human-clean");
+    dir.write("i18n/fr/common.json", r#"{ "a": "A {x}" }"#);
+    dir.write("i18n/de/common.json", r#"{ "a": "B {x}" }"#);
+    dir.write("src/app.ts", "t('a', { x });\n");
+
+    assert_snapshot("clean_run", &render(&dir, &[]));
+}
+
+#[test]
+fn output_file_receives_the_report_without_color_or_emoji() {
+    let dir = mixed_fixture();
+    let report = dir.path().join("report.txt");
+    let stdout = render(
+        &dir,
+        &["--color", "always", "--output", report.to_str().unwrap()],
+    );
+
+    // The console keeps the colored summary line.
+    assert_eq!(
+        stdout,
+        "\u{1b}[1;31m❌ Translation issues found: 5 errors, 0 warnings.\u{1b}[0m\n"
+    );
+    let written = std::fs::read_to_string(report).unwrap();
+    assert!(!written.contains('\u{1b}'), "{}", written);
+    assert!(
+        !written.contains('❌') && !written.contains('🔍'),
+        "{}",
+        written
+    );
+    assert!(
+        written.starts_with(
+            "Translation Consistency Check Complete\nMissing interpolation variables:\n"
+        ),
+        "{}",
+        written
+    );
+    assert!(
+        written.ends_with("\nTranslation issues found: 5 errors, 0 warnings.\n"),
+        "{}",
+        written
+    );
+}
+
+#[test]
+fn languages_are_listed_in_order_and_runs_are_identical() {
+    let dir = TempDir::new("human-order");
+    let base: Vec<String> = (0..40)
+        .map(|key| format!(r#""k{:02}": "V""#, key))
+        .collect();
+    dir.write("i18n/fr/common.json", &format!("{{ {} }}", base.join(", ")));
+    let langs = ["cs", "de", "es", "it", "ja", "nl", "pl", "pt"];
+    for (index, lang) in langs.iter().enumerate() {
+        // Every language lacks a different share of the keys and adds one.
+        let mut entries: Vec<String> = (0..40)
+            .filter(|key| key % (index + 2) != 0)
+            .map(|key| format!(r#""k{:02}": "V""#, key))
+            .collect();
+        entries.push(format!(r#""extra_{}": "X""#, lang));
+        dir.write(
+            &format!("i18n/{}/common.json", lang),
+            &format!("{{ {} }}", entries.join(", ")),
+        );
+    }
+    dir.write("src/app.ts", "");
+
+    let first = render(&dir, &[]);
+    assert_eq!(render(&dir, &[]), first);
+
+    let headers: Vec<&str> = first
+        .lines()
+        .filter_map(|line| line.strip_prefix("🔍 Checking "))
+        .collect();
+    assert_eq!(headers, ["CS", "DE", "ES", "IT", "JA", "NL", "PL", "PT"]);
+    assert!(
+        first.contains("📊 Findings per language:\n   - CS: 20 missing_key, 1 extra_key\n   - DE: 14 missing_key, 1 extra_key\n"),
+        "{}",
+        first
+    );
+}
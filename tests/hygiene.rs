@@ -0,0 +1,136 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const PROJECT: [(&str, &str); 7] = [
+    ("i18n/fr/common.json", r#"{ "title": "Titre" }"#),
+    ("i18n/en/common.json", r#"{ "title": "Title" }"#),
+    ("i18n/en/.DS_Store", "\0\0\0\x01Bud1"),
+    ("i18n/en/common.json~", r#"{ "title": "Tit"#),
+    ("i18n/en/notes.txt", "Ask the agency about the tone."),
+    ("i18n/en/draft:old.txt", "draft"),
+    ("i18n/en/.gitkeep", ""),
+];
+
+fn args<'a>(dir: &'a TempDir, extra: &[&'a str]) -> Vec<&'a str> {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    args
+}
+
+#[test]
+fn stray_files_are_warnings_with_an_action() {
+    let dir = fixture("hygiene", &PROJECT);
+    dir.write("i18n/fr/empty.json", "");
+    let output = run(&args(&dir, &[]));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Files in the translation directory"),
+        "{}",
+        stdout
+    );
+    for (file, problem, action) in [
+        (
+            ".DS_Store",
+            "operating system metadata",
+            "delete it and add",
+        ),
+        (
+            "common.json~",
+            "editor backup or merge leftover",
+            "delete it",
+        ),
+        ("notes.txt", "not a translation file", "move it out"),
+        (
+            "draft:old.txt",
+            "control or reserved characters",
+            "rename it",
+        ),
+        (
+            "empty.json",
+            "empty file",
+            "add its translations or delete it",
+        ),
+    ] {
+        let line = stdout
+            .lines()
+            .find(|line| line.contains("Problem: ") && line.ends_with(file))
+            .unwrap_or_else(|| panic!("no finding for {} in {}", file, stdout));
+        assert!(line.contains(problem), "{}", line);
+        assert!(line.contains(&format!("Action: {}", action)), "{}", line);
+    }
+    assert!(!stdout.contains(".gitkeep"), "{}", stdout);
+}
+
+#[test]
+fn fix_hygiene_only_deletes_junk() {
+    let dir = fixture("hygiene-fix", &PROJECT);
+    // Named like a backup, but the only copy of these translations.
+    dir.write("i18n/en/common.json.orig", r#"{ "title": "Title" }"#);
+    let en = dir.path().join("i18n/en");
+
+    let output = run(&args(&dir, &["--fix", "hygiene", "--dry-run"]));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Would delete 2 junk files"), "{}", stderr);
+    assert!(en.join(".DS_Store").exists());
+
+    let output = run(&args(&dir, &["--fix", "hygiene", "--yes"]));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Deleted 2 junk files"), "{}", stderr);
+    assert!(
+        stderr.contains("Keeping ") && stderr.contains("common.json.orig: it parses"),
+        "{}",
+        stderr
+    );
+    assert!(!en.join(".DS_Store").exists());
+    assert!(!en.join("common.json~").exists());
+    for kept in ["common.json", "common.json.orig", "notes.txt", ".gitkeep"] {
+        assert!(en.join(kept).exists(), "{}", kept);
+    }
+
+    let output = run(&args(&dir, &["--yes"]));
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn fix_hygiene_asks_before_deleting() {
+    let dir = fixture("hygiene-confirm", &PROJECT);
+    let en = dir.path().join("i18n/en");
+    let confirm = |answer: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_check_translations"))
+            .args(args(&dir, &["--fix", "hygiene"]))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(answer.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    let stderr = confirm("n\n");
+    assert!(stderr.contains("Delete them? [y/N]"), "{}", stderr);
+    assert!(stderr.contains("No file was deleted"), "{}", stderr);
+    assert!(en.join(".DS_Store").exists());
+
+    let stderr = confirm("y\n");
+    assert!(stderr.contains("Deleted 2 junk files"), "{}", stderr);
+    assert!(!en.join(".DS_Store").exists());
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use check_translations::icu::{self, Kind};
+use common::{TempDir, run};
+use serde_json::Value;
+
+#[test]
+fn messages_are_parsed_into_their_arguments() {
+    let arguments = icu::parse(
+        "{name} a {count, plural, offset:1 =0 {aucun '{'message'}'} one {# message de {sender}} other {# messages}} le {day, date, short}, l'article",
+    )
+    .unwrap();
+    let found: Vec<(&str, &str, Vec<&str>)> = arguments
+        .iter()
+        .map(|argument| {
+            let cases = argument.cases.iter().map(String::as_str).collect();
+            (argument.name.as_str(), argument.kind.name(), cases)
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("name", "simple", vec![]),
+            ("count", "plural", vec!["=0", "one", "other"]),
+            ("sender", "simple", vec![]),
+            ("day", "date", vec![]),
+        ]
+    );
+    assert_eq!(arguments[1].kind, Kind::Plural);
+
+    let error = |text: &str| icu::parse(text).unwrap_err().to_string();
+    assert_eq!(
+        error("{count, plural, one {#}}"),
+        "the `plural` argument has no `other` case at character 24"
+    );
+    assert_eq!(
+        error("{count, plural, single {#} other {#}}"),
+        "unknown plural category `single` at character 17"
+    );
+    assert_eq!(
+        error("{count, plurall, other {#}}"),
+        "unknown argument type `plurall` at character 9"
+    );
+    assert_eq!(
+        error("Bonjour {name"),
+        "expected `}`, found the end at character 14"
+    );
+    assert_eq!(error("Bonjour name}"), "unmatched `}` at character 13");
+    assert_eq!(error("Prix : '{0}"), "unterminated quote at character 8");
+    assert_eq!(
+        error("{gender, select, male {il} male {lui} other {iel}}"),
+        "duplicate case `male` at character 28"
+    );
+}
+
+fn findings(stdout: &str, check: &str) -> Vec<Value> {
+    let report: Value = serde_json::from_str(stdout).unwrap();
+    report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == check)
+        .cloned()
+        .collect()
+}
+
+fn detail<'a>(finding: &'a Value, label: &str) -> Option<&'a str> {
+    finding["details"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|detail| detail["label"] == label)
+        .and_then(|detail| detail["value"].as_str())
+}
+
+#[test]
+fn icu_messages_are_compared_with_the_base() {
+    let dir = TempDir::new("icu-messages");
+    dir.write("translation-check.toml", "[icu]\n");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{
+  "inbox": "{count, plural, =0 {Aucun message} one {# message} other {# messages}}",
+  "invite": "{gender, select, female {Elle vous invite} other {Il vous invite}} à {event}",
+  "total": "Total : {amount, number}"
+}"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{
+  "inbox": "{count, plural, =0 {Keine Nachrichten} one {# Nachricht} other {# Nachrichten}}",
+  "invite": "{gender, select, other {Sie sind zu {event} eingeladen}}",
+  "total": "Summe: {amount}"
+}"#,
+    );
+    dir.write(
+        "i18n/pl/common.json",
+        r#"{
+  "inbox": "{count, plural, =0 {Brak wiadomości} one {# wiadomość} other {# wiadomości}}",
+  "invite": "{gender, select, female {Zaprasza cię na {event}} other {Zaprasza cię na {event}}",
+  "total": "Razem: {amount, number}"
+}"#,
+    );
+    dir.write("src/app.js", "t('inbox'); t('invite'); t('total')");
+    let args = [
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "json",
+    ];
+    let output = run(&args);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+
+    let syntax = findings(&stdout, "icu_syntax_error");
+    assert_eq!(syntax.len(), 1, "{}", stdout);
+    assert_eq!(syntax[0]["lang"], "pl");
+    assert_eq!(syntax[0]["key"], "invite");
+    assert_eq!(
+        detail(&syntax[0], "Error").unwrap(),
+        "expected `}`, found the end at character 82"
+    );
+
+    let mismatches = findings(&stdout, "icu_argument_mismatch");
+    let found: Vec<(&str, &Value)> = mismatches
+        .iter()
+        .map(|finding| (finding["key"].as_str().unwrap(), finding))
+        .collect();
+    assert_eq!(found.len(), 2, "{}", stdout);
+    assert_eq!(found[0].0, "invite");
+    assert_eq!(
+        detail(found[0].1, "Missing cases").unwrap(),
+        "gender: female"
+    );
+    assert!(detail(found[0].1, "Missing").is_none(), "{}", stdout);
+    assert_eq!(found[1].0, "total");
+    assert_eq!(detail(found[1].1, "Missing").unwrap(), "{amount, number}");
+    assert_eq!(detail(found[1].1, "Unexpected").unwrap(), "{amount}");
+    // The ICU finding says it better.
+    assert!(
+        findings(&stdout, "variable_mismatch").is_empty(),
+        "{}",
+        stdout
+    );
+
+    let plurals = findings(&stdout, "plural_category_missing");
+    assert_eq!(plurals.len(), 1, "{}", stdout);
+    assert_eq!(plurals[0]["lang"], "pl");
+    assert_eq!(plurals[0]["key"], "inbox");
+    assert_eq!(detail(&plurals[0], "Argument").unwrap(), "{count}");
+    assert_eq!(detail(&plurals[0], "Missing").unwrap(), "few, many");
+}
+
+// The words of each case aren't placeholders: two messages with the same
+// arguments match, whatever their cases say.
+#[test]
+fn matching_messages_raise_no_variable_mismatch() {
+    let dir = TempDir::new("icu-select-words");
+    dir.write("translation-check.toml", "[icu]\n");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "who": "{g, select, male {He} other {They}}" }"#,
+    );
+    dir.write(
+        "i18n/pl/common.json",
+        r#"{ "who": "{g, select, male {On} other {Oni}}" }"#,
+    );
+    dir.write("src/app.js", "t('who', { g: gender })");
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "json",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(
+        findings(&stdout, "variable_mismatch").is_empty(),
+        "{}",
+        stdout
+    );
+    assert!(
+        findings(&stdout, "icu_argument_mismatch").is_empty(),
+        "{}",
+        stdout
+    );
+}
+
+// A call supplying the argument of a `select` doesn't miss its cases.
+#[test]
+fn select_cases_are_not_interpolation_variables() {
+    let dir = TempDir::new("icu-select-call");
+    dir.write("translation-check.toml", "[icu]\n");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "who": "{g, select, male {He} other {They}}" }"#,
+    );
+    dir.write("src/app.js", "t('who', { g: gender })");
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "json",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(
+        findings(&stdout, "missing_interpolation").is_empty(),
+        "{}",
+        stdout
+    );
+}
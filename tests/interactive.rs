@@ -0,0 +1,119 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn project() -> TempDir {
+    let dir = TempDir::new("interactive");
+    dir.write(
+        "i18n/fr/common.json",
+        "{\n  \"cart\": {\n    \"empty\": \"Vide\",\n    \"total\": \"Total {amount}\"\n  },\n  \"title\": \"Titre\"\n}\n",
+    );
+    dir.write(
+        "i18n/de/common.json",
+        "{\n  \"cart\": {\n    \"empty\": \"Leer\"\n  },\n  \"legacy\": \"Alt\",\n  \"title\": \"Titel {name}\"\n}\n",
+    );
+    dir.write("src/app.ts", "t('cart.total', { amount: 1 }); t('title');");
+    dir
+}
+
+// Runs the review with `keys` pressed, one character of stdin each.
+fn review(dir: &TempDir, extra: &[&str], keys: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args([
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "i18n",
+            "--interactive",
+        ])
+        .args(extra)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run binary");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(keys.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// The findings are listed by language and file: DE's missing `cart.total`,
+// extra `legacy` and mismatched `title`.
+#[test]
+fn marks_are_applied_once_the_review_ends() {
+    let dir = project();
+    let output = review(&dir, &["--baseline", "baseline.json"], "cdaq");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}\n{}", stdout, stderr);
+    assert!(stdout.contains("DE · i18n/de/common.json"), "{}", stdout);
+    assert!(
+        stdout.contains(&format!(
+            "  {:47} │ DE\n  {:47} │ \"Titel {{name}}\"",
+            "FR", "\"Titre\""
+        )),
+        "{}",
+        stdout
+    );
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        "{\n  \"cart\": {\n    \"empty\": \"Leer\",\n    \"total\": \"Total {amount}\"\n  },\n  \"title\": \"Titel {name}\"\n}\n"
+    );
+    let baseline: Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("baseline.json")).unwrap())
+            .unwrap();
+    assert_eq!(baseline["findings"][0]["key"], "title", "{}", baseline);
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--baseline",
+        "baseline.json",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn nothing_is_written_when_the_review_is_abandoned_or_dry() {
+    let dir = project();
+    let original = fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap();
+
+    let output = review(&dir, &[], "dcx");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("nothing was written"));
+
+    let output = review(&dir, &["--dry-run"], "cdq");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("+    \"total\": \"Total {amount}\""),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        original
+    );
+
+    // Accepting needs a baseline, and base files are never edited: `]`
+    // moves past DE's file to FR's.
+    let output = review(&dir, &[], "]daq");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Can't delete key: only the keys of the other languages are edited"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Can't accept into baseline: accepting findings needs `--baseline <path>`"),
+        "{}",
+        stdout
+    );
+}
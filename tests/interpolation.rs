@@ -0,0 +1,54 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture() -> TempDir {
+    let dir = TempDir::new("interpolation");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "greeting": "Bonjour {name}", "items": "{count} articles", "plain": "Salut" }"#,
+    );
+    dir.write(
+        "src/App.vue",
+        r#"<template>
+  <p>{{ $t('greeting', { name: user.name }) }}</p>
+  <p>{{ $t('greeting') }}</p>
+  <p>{{ $t('greeting', params) }}</p>
+  <i18n-t keypath="items" tag="p">
+    <template #count><b>{{ n }}</b></template>
+  </i18n-t>
+  <i18n-t keypath="greeting" tag="p"></i18n-t>
+  <p>{{ $t('plain') }}</p>
+</template>
+
+<script setup>
+const title = t('items', { total: 3, ...rest });
+</script>
+"#,
+    );
+    dir.write(
+        "src/api.ts",
+        "export const label = t('items', { total: 3 });\n",
+    );
+    dir
+}
+
+#[test]
+fn reports_call_sites_missing_placeholders() {
+    let dir = fixture();
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--src-dir",
+        dir.path().join("src").to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("Key: greeting | Missing: [\"name\"] | File: "));
+    assert!(stdout.contains("App.vue:3"));
+    assert!(stdout.contains("App.vue:8"));
+    assert!(stdout.contains("api.ts:1"));
+    assert!(!stdout.contains("App.vue:2"));
+    assert!(!stdout.contains("App.vue:5"));
+    assert!(stdout.contains("Unverifiable interpolation call sites: 2"));
+}
@@ -0,0 +1,206 @@
+mod common;
+
+use common::{TempDir, fixture};
+use serde_json::{Value, json};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+// German misses `save` and has `old` the base doesn't; the sources use a
+// key nobody defines.
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        "{\n  \"title\": \"Titre\",\n  \"save\": \"Enregistrer\"\n}\n",
+    ),
+    (
+        "i18n/de/common.json",
+        "{\n  \"title\": \"Titel\",\n  \"old\": \"Alt\"\n}\n",
+    ),
+    ("src/app.ts", "t('save');\nt('title');\nt('app.missing');\n"),
+];
+
+// A session with `jsonrpc --stdio`, one line per message.
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start(dir: &TempDir) -> Client {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_check_translations"))
+            .args(["--cwd", dir.path().to_str().unwrap(), "jsonrpc", "--stdio"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        Client {
+            stdin: child.stdin.take().unwrap(),
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+            next_id: 1,
+        }
+    }
+
+    fn send(&mut self, message: &Value) {
+        writeln!(self.stdin, "{}", message).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    // The response to `method`, result or error.
+    fn call(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        let response = self.receive();
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+}
+
+// The findings of a `getFindings` result, as `check key file line:character`.
+fn findings(response: &Value) -> Vec<String> {
+    response["result"]["findings"]
+        .as_array()
+        .unwrap_or_else(|| panic!("{}", response))
+        .iter()
+        .map(|finding| {
+            let start = &finding["range"]["start"];
+            format!(
+                "{} {} {} {}:{}",
+                finding["check"].as_str().unwrap(),
+                finding["key"].as_str().unwrap(),
+                finding["file"].as_str().unwrap(),
+                start["line"],
+                start["character"]
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn a_session_follows_unsaved_changes() {
+    let dir = fixture("jsonrpc-session", &PROJECT);
+    let mut client = Client::start(&dir);
+
+    let early = client.call("getFindings", json!({}));
+    assert_eq!(early["error"]["code"], -32002);
+
+    let initialized = client.call(
+        "initialize",
+        json!({ "args": ["i18n", "--src-dir", "src"] }),
+    );
+    assert_eq!(initialized["result"]["name"], "check_translations");
+
+    let mut all = findings(&client.call("getFindings", json!({})));
+    all.sort();
+    assert_eq!(
+        all,
+        [
+            "extra_key old i18n/de/common.json 2:2",
+            "missing_key save i18n/de/common.json 0:0",
+            "undefined_key app.missing src/app.ts 2:3",
+        ]
+    );
+
+    // The editor adds the missing key without saving; the extra key stays.
+    let content = "{\n  \"title\": \"Titel\",\n  \"save\": \"Speichern\",\n  \"old\": \"Alt\"\n}\n";
+    client.notify(
+        "didChangeFile",
+        json!({ "path": "i18n/de/common.json", "content": content }),
+    );
+    let german = client.call("getFindings", json!({ "file": "i18n/de/common.json" }));
+    assert_eq!(findings(&german), ["extra_key old i18n/de/common.json 3:2"]);
+    assert!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json"))
+            .unwrap()
+            .contains("\"old\"")
+    );
+    assert!(
+        !fs::read_to_string(dir.path().join("i18n/de/common.json"))
+            .unwrap()
+            .contains("Speichern")
+    );
+
+    let explained = client.call("explainKey", json!({ "key": "save" }));
+    let result = &explained["result"];
+    assert_eq!(result["base"], "fr");
+    assert_eq!(
+        result["values"],
+        json!([
+            {
+                "lang": "de",
+                "value": "Speichern",
+                "file": "i18n/de/common.json",
+                "range": { "start": { "line": 2, "character": 2 }, "end": { "line": 2, "character": 21 } },
+            },
+            {
+                "lang": "fr",
+                "value": "Enregistrer",
+                "file": "i18n/fr/common.json",
+                "range": { "start": { "line": 2, "character": 2 }, "end": { "line": 2, "character": 23 } },
+            },
+        ])
+    );
+    assert_eq!(
+        result["usages"],
+        json!([{
+            "file": "src/app.ts",
+            "range": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 7 } },
+        }])
+    );
+    assert_eq!(result["findings"], json!([]));
+
+    // Dropping the buffer goes back to the file on disk.
+    client.notify(
+        "didChangeFile",
+        json!({ "path": "i18n/de/common.json", "content": null }),
+    );
+    let german = client.call("getFindings", json!({ "file": "i18n/de/common.json" }));
+    assert_eq!(findings(&german).len(), 2);
+
+    assert_eq!(client.call("shutdown", Value::Null)["result"], Value::Null);
+    assert!(client.child.wait().unwrap().success());
+}
+
+#[test]
+fn bad_requests_get_errors() {
+    let dir = fixture("jsonrpc-errors", &PROJECT);
+    let mut client = Client::start(&dir);
+
+    client.stdin.write_all(b"not json\n").unwrap();
+    let response = client.receive();
+    assert_eq!(response["error"]["code"], -32700);
+    assert_eq!(response["id"], Value::Null);
+
+    assert_eq!(client.call("format", json!({}))["error"]["code"], -32601);
+    let response = client.call("initialize", json!({ "args": ["--format", "html"] }));
+    assert_eq!(response["error"]["code"], -32602);
+    assert_eq!(
+        response["error"]["message"],
+        "`--format` is set by the session and can't be passed to `initialize`"
+    );
+    let response = client.call("initialize", json!({ "args": ["--fix", "nfc"] }));
+    assert_eq!(
+        response["error"]["message"],
+        "`--fix` writes to the project and can't be used in read-only mode"
+    );
+
+    // The session goes on after errors, and ends with its input.
+    let response = client.call("initialize", json!({ "i18nDir": "i18n" }));
+    assert!(response["result"].is_object(), "{}", response);
+    drop(client.stdin);
+    assert!(client.child.wait().unwrap().success());
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture() -> TempDir {
+    let dir = TempDir::new("key-case");
+    dir.write(
+        "i18n/fr/checkout.json",
+        r#"{ "checkout": { "title": "Paiement", "total": "Total {amount}" } }"#,
+    );
+    dir.write(
+        "i18n/de/checkout.json",
+        r#"{ "Checkout": { "Title": "Kasse", "Total": "Summe {amount}" } }"#,
+    );
+    dir.write(
+        "src/app.ts",
+        "t('Checkout.Title'); t('CHECKOUT.TOTAL', { amount });\n",
+    );
+    dir
+}
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String) {
+    let i18n = dir.path().join("i18n");
+    let src = dir.path().join("src");
+    let mut args = vec![i18n.to_str().unwrap(), "--src-dir", src.to_str().unwrap()];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    )
+}
+
+#[test]
+fn keys_differing_in_case_are_missing_by_default() {
+    let dir = fixture();
+    let (code, stdout) = check(&dir, &[]);
+
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("Missing keys"));
+    assert!(stdout.contains("Unused keys: 2"));
+}
+
+#[test]
+fn case_insensitive_mode_only_warns_about_case() {
+    let dir = fixture();
+    let (code, stdout) = check(&dir, &["--key-case-insensitive"]);
+
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(!stdout.contains("Missing keys"));
+    assert_eq!(stdout.matches("Key case mismatch").count(), 1);
+    assert_eq!(stdout.matches("| Found (DE): ").count(), 2);
+    assert!(stdout.contains("Key: checkout.title | Found (DE): Checkout.Title"));
+    assert!(stdout.contains("Unused keys: 0"));
+}
+
+#[test]
+fn base_keys_colliding_by_case_are_errors() {
+    let dir = fixture();
+    dir.write(
+        "i18n/fr/extra.json",
+        r#"{ "Checkout": { "Title": "Caisse" } }"#,
+    );
+    let (code, stdout) = check(&dir, &["--key-case-insensitive"]);
+
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains(
+        "🔍 Checking FR\n❌ Keys differing only in case:\n   - Keys: Checkout.Title, checkout.title\n"
+    ));
+}
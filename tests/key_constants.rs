@@ -0,0 +1,103 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 6] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "checkout": { "title": "Paiement", "total": "Total", "legacy": "Ancien" }, "home": { "title": "Accueil" } }"#,
+    ),
+    (
+        "src/generated/translationKeys.ts",
+        "export const CHECKOUT_TITLE = 'checkout.title';\n\
+         export const CHECKOUT_TOTAL: string = \"checkout.total\";\n\
+         export const CHECKOUT_LEGACY = 'checkout.legacy';\n\
+         export enum HomeKeys {\n  HOME_TITLE = 'home.title',\n}\n",
+    ),
+    (
+        "src/checkout.ts",
+        "import { CHECKOUT_TITLE, CHECKOUT_LEGACY } from './generated/translationKeys';\n\
+         export const title = t(CHECKOUT_TITLE);\n",
+    ),
+    (
+        "src/home.ts",
+        "import { HomeKeys } from './generated/translationKeys';\nt(HomeKeys.HOME_TITLE);\n",
+    ),
+    (
+        "src/summary.ts",
+        "const CHECKOUT_TOTAL = 'Total:';\nconsole.log(CHECKOUT_TOTAL);\n",
+    ),
+    (
+        "translation-check.toml",
+        "[key_constants]\nfiles = [\"**/translationKeys.ts\"]\n",
+    ),
+];
+
+fn check(dir: &TempDir, config: bool) -> (String, String) {
+    let mut args = vec![
+        dir.path().join("i18n").to_str().unwrap().to_string(),
+        "--src-dir".to_string(),
+        dir.path().join("src").to_str().unwrap().to_string(),
+        "--only".to_string(),
+        "unused-keys".to_string(),
+    ];
+    if config {
+        args.push("--config".to_string());
+        args.push(
+            dir.path()
+                .join("translation-check.toml")
+                .display()
+                .to_string(),
+        );
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run(&args);
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn resolves_usages_through_key_constants() {
+    let dir = fixture("key-constants", &PROJECT);
+
+    let (stdout, stderr) = check(&dir, true);
+
+    // Only the constants file spells out the keys; the legacy constant is
+    // imported but never used and the total constant is shadowed.
+    assert!(stdout.contains("Unused keys: 2"), "{}", stdout);
+    assert!(
+        stderr.contains("key constant `CHECKOUT_TOTAL` is shadowed by a local declaration in "),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("summary.ts"), "{}", stderr);
+}
+
+#[test]
+fn constants_files_count_as_usages_without_the_config() {
+    let dir = fixture("key-constants-off", &PROJECT);
+
+    let (stdout, _) = check(&dir, false);
+
+    assert!(stdout.contains("Unused keys: 0"), "{}", stdout);
+}
+
+#[test]
+fn warns_about_duplicate_constants() {
+    let dir = fixture("key-constants-duplicates", &PROJECT);
+    dir.write(
+        "src/legacy/translationKeys.ts",
+        "export const CHECKOUT_TITLE = 'checkout.old_title';\n",
+    );
+
+    let (_, stderr) = check(&dir, true);
+
+    assert!(
+        stderr.contains("key constant `CHECKOUT_TITLE` is defined more than once ("),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains(":1 -> checkout.old_title"), "{}", stderr);
+}
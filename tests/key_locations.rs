@@ -0,0 +1,77 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+fn project() -> TempDir {
+    let dir = TempDir::new("key-locations");
+    dir.write(
+        "i18n/fr/common.json",
+        "{\n  \"title\": \"Titre\",\n  \"cart\": { \"empty\": \"Vide\" }\n}\n",
+    );
+    dir.write(
+        "i18n/de/common.json",
+        "{\n  \"title\": \"Titel\",\n  \"cart\": {\n    \"empty\": \"Leer\",\n    \"größe\": \"Größe\"\n  },\n  \"_translationCheck\": { \"extra\": \"x\" },\n  \"extra\": [\"a\"]\n}\n",
+    );
+    dir
+}
+
+// Keys of a JSON file are located at their name, or at the element for
+// arrays, columns counting characters.
+#[test]
+fn findings_about_keys_point_at_their_line_and_column() {
+    let dir = project();
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "plain",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("\textra_key\tde\tcart.größe\ti18n/de/common.json:5:5\t"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("\textra_key\tde\textra\ti18n/de/common.json:8:3\t"),
+        "{}",
+        stdout
+    );
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let extra = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|finding| finding["key"] == "extra")
+        .unwrap();
+    assert_eq!((&extra["line"], &extra["column"]), (&8.into(), &3.into()));
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "sarif",
+    ]);
+    let sarif: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let result = sarif["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|result| result["properties"]["key"] == "cart.größe")
+        .unwrap();
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["region"],
+        serde_json::json!({ "startLine": 5, "startColumn": 5 })
+    );
+}
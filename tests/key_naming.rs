@@ -0,0 +1,88 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture(config: &str) -> TempDir {
+    let dir = TempDir::new("key-naming");
+    dir.write(
+        "i18n/fr/buttons.json",
+        r#"{
+            "buttons": {
+                "save": "Enregistrer",
+                "Cancel_now": "Annuler",
+                "item_one": "{count} élément",
+                "item_other": "{count} éléments",
+                "steps": ["Un", "Deux"],
+                "menu": { "file": { "recent": { "clear": "Vider" } } }
+            },
+            "title": "Boutons"
+        }"#,
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> (Option<i32>, Vec<String>) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--format",
+        "plain",
+    ]);
+    let naming = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.contains("\tkey_naming\t"))
+        .map(str::to_string)
+        .collect();
+    (output.status.code(), naming)
+}
+
+#[test]
+fn keys_are_checked_against_each_rule_set() {
+    let (code, naming) = check(&fixture(
+        "[key_naming]\ncase = \"camel\"\nmax_depth = 4\nfile_namespace = true\n",
+    ));
+    assert_eq!(code, Some(0));
+    let fields: Vec<(&str, &str)> = naming
+        .iter()
+        .map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            (fields[3], fields[5])
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (
+                "buttons.Cancel_now",
+                "Rule: case; Expected: lowerCamelCase; Segments: Cancel_now"
+            ),
+            (
+                "buttons.menu.file.recent.clear",
+                "Rule: max_depth; Depth: 5 (at most 4)"
+            ),
+            ("title", "Rule: file_namespace; Expected: buttons.*"),
+        ],
+        "{:?}",
+        naming
+    );
+}
+
+#[test]
+fn naming_is_only_checked_when_configured() {
+    let (_, naming) = check(&fixture(""));
+    assert!(naming.is_empty(), "{:?}", naming);
+
+    let dir = fixture("[key_naming]\n");
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("`key_naming` needs a `case`, `pattern`, `max_depth` or `file_namespace`"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
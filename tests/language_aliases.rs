@@ -0,0 +1,132 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+
+// Chinese is split between `zh-Hans` and the `zh-CN` folder a vendor
+// delivers, which disagree on `save`.
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "save": "Enregistrer", "greeting": "Bonjour {name}" }"#,
+    ),
+    (
+        "i18n/zh-Hans/common.json",
+        r#"{ "title": "标题", "save": "保存" }"#,
+    ),
+    (
+        "i18n/zh-CN/common.json",
+        r#"{ "save": "存储", "greeting": "你好" }"#,
+    ),
+    (
+        "translation-check.toml",
+        "[languages]\naliases = { \"zh-CN\" = \"zh-Hans\" }\n",
+    ),
+];
+
+fn report(dir: &TempDir) -> Value {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--format",
+        "json",
+    ]);
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn aliased_folders_are_one_language() {
+    let dir = fixture("aliases-merge", &PROJECT);
+    let report = report(&dir);
+    let checks: Vec<(&str, &str, &str)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap(),
+                finding["lang"].as_str().unwrap(),
+                finding["key"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            ("alias_conflict", "zh-Hans", "save"),
+            ("variable_mismatch", "zh-Hans", "greeting"),
+        ]
+    );
+
+    // A key is attributed to the folder defining it.
+    let mismatch = &report["findings"][1];
+    assert_eq!(mismatch["file"], "i18n/zh-CN/common.json");
+
+    let coverage = &report["coverage"][0];
+    assert_eq!(coverage["lang"], "zh-Hans");
+    assert_eq!(coverage["missing"], 0);
+    assert_eq!(coverage["folders"], serde_json::json!(["zh-Hans", "zh-CN"]));
+}
+
+#[test]
+fn conflicts_name_both_files() {
+    let dir = fixture("aliases-conflict", &PROJECT);
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "   - Key: save | Other value in: i18n/zh-CN/common.json | File: i18n/zh-Hans/common.json"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   ZH-HANS merges the folders zh-Hans, zh-CN\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("ZH-CN"), "{}", stdout);
+
+    let conflict = &report(&dir)["findings"][0];
+    let values: Vec<(&str, &str)> = conflict["values"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| {
+            (
+                value["label"].as_str().unwrap(),
+                value["text"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(values, [("zh-Hans", "保存"), ("zh-CN", "存储")]);
+}
+
+#[test]
+fn an_alias_cannot_stand_for_another_alias() {
+    let dir = fixture("aliases-chained", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[languages]\naliases = { \"zh-CN\" = \"zh-Hans\", \"zh-SG\" = \"zh-CN\" }\n",
+    );
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(
+            "`languages.aliases.\"zh-SG\"` must name a language that isn't an alias itself"
+        ),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+use std::process::Output;
+
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    let entries: Vec<String> = (0..1240)
+        .map(|key| format!("\"key{}\": \"Value {}\"", key, key))
+        .collect();
+    let values = format!("{{ {} }}", entries.join(", "));
+    for lang in ["fr", "de", "nl"] {
+        dir.write(&format!("i18n/{}/common.json", lang), &values);
+    }
+    dir
+}
+
+fn check(dir: &TempDir, extra: &[&str]) -> Output {
+    let i18n = dir.path().join("i18n");
+    let state = dir.path().join("state.json");
+    let mut args = vec![
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--state",
+        state.to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra);
+    run(&args)
+}
+
+#[test]
+fn languages_changed_since_the_state_fail_until_acknowledged() {
+    let dir = fixture("languages-state");
+    assert_eq!(check(&dir, &["--update-state"]).status.code(), Some(0));
+    let state = fs::read_to_string(dir.path().join("state.json")).unwrap();
+    assert!(state.contains("\"nl\": 1240"), "{}", state);
+
+    fs::remove_dir_all(dir.path().join("i18n/nl")).unwrap();
+    dir.write("i18n/pt/common.json", r#"{ "key0": "Valor" }"#);
+    let output = check(&dir, &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🗑️ Language nl removed since the last acknowledged state — 1,240 keys gone\n   - Acknowledge with: --update-state\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("🆕 Language pt added since the last acknowledged state — 1 key\n"),
+        "{}",
+        stdout
+    );
+
+    fs::remove_dir_all(dir.path().join("i18n/pt")).unwrap();
+    assert_eq!(check(&dir, &["--update-state"]).status.code(), Some(0));
+    let output = check(&dir, &[]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn expected_languages_in_the_config_replace_the_state() {
+    let dir = fixture("languages-config");
+    let config = dir.write(
+        "translation-check.toml",
+        "[languages]\nexpected = [\"fr\", \"de\", \"it\"]\n",
+    );
+    let config = config.to_str().unwrap();
+
+    // Acknowledging the state doesn't help: the list is the reference.
+    let output = check(&dir, &["--config", config, "--update-state"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("🗑️ Language it is listed in `languages.expected` but has no folder\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("🆕 Language nl is not listed in `languages.expected` — 1,240 keys\n"),
+        "{}",
+        stdout
+    );
+
+    dir.write(
+        "translation-check.toml",
+        "[languages]\nexpected = [\"fr\", \"de\", \"nl\"]\n",
+    );
+    assert_eq!(check(&dir, &["--config", config]).status.code(), Some(0));
+}
+
+#[test]
+fn states_without_languages_acknowledge_any_language() {
+    let dir = fixture("languages-old-state");
+    dir.write(
+        "state.json",
+        r#"{ "base_keys": [], "coverage": { "de": 100.0 } }"#,
+    );
+
+    assert_eq!(check(&dir, &[]).status.code(), Some(0));
+}
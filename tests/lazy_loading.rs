@@ -0,0 +1,39 @@
+mod common;
+
+use common::{TempDir, large_fixture, run};
+
+fn parsed_languages(stderr: &str) -> Vec<String> {
+    stderr
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("- languages parsed: "))
+        .expect("no parsed languages in timings")
+        .split(',')
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn unused_keys_only_parses_the_base_language() {
+    let dir = TempDir::new("lazy");
+    large_fixture(&dir, 50, 10, 50);
+    let i18n = dir.path().join("i18n");
+
+    let output = run(&[i18n.to_str().unwrap(), "--only", "unused-keys", "--timings"]);
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(parsed_languages(&stderr), vec!["fr"]);
+}
+
+#[test]
+fn full_run_parses_every_language() {
+    let dir = TempDir::new("eager");
+    large_fixture(&dir, 5, 2, 5);
+    let i18n = dir.path().join("i18n");
+
+    let output = run(&[i18n.to_str().unwrap(), "--timings"]);
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(parsed_languages(&stderr).len(), 5);
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::fs;
+
+const POLICY: &str = "[file_format]\neol = \"lf\"\nfinal_newline = true\n";
+
+// French follows the policy; the other languages were saved by other
+// editors. Values spell line breaks as `\r\n` escapes, which are text.
+const PROJECT: [(&str, &str); 5] = [
+    ("translation-check.toml", POLICY),
+    (
+        "i18n/fr/common.json",
+        "{\n  \"title\": \"Titre\",\n  \"body\": \"Ligne\\r\\nSuite\"\n}\n",
+    ),
+    (
+        "i18n/de/common.json",
+        "{\r\n  \"title\": \"Titel\",\r\n  \"body\": \"Zeile\\r\\nWeiter\"\r\n}\r\n",
+    ),
+    (
+        "i18n/es/common.json",
+        "{\n  \"title\": \"Título\",\r\n  \"body\": \"Línea\\r\\nSigue\"\n}",
+    ),
+    (
+        "i18n/it/common.json",
+        "{\r  \"title\": \"Titolo\",\r  \"body\": \"Riga\\r\\nSegue\"\r}\r",
+    ),
+];
+
+fn check(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--color",
+        "never",
+    ];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn line_breaks_are_read_from_the_bytes() {
+    let dir = fixture("line-endings", &PROJECT);
+    let (code, stdout, _) = check(&dir, &[]);
+
+    assert_eq!(code, Some(0), "{}", stdout);
+    for line in [
+        "Line endings: crlf | Expected: lf | File: i18n/de/common.json\n",
+        "Line endings: mixed (2 LF, 1 CRLF) | Expected: lf | Final newline: missing | File: i18n/es/common.json\n",
+        "Line endings: cr | Expected: lf | File: i18n/it/common.json\n",
+    ] {
+        assert!(stdout.contains(line), "{}", stdout);
+    }
+    assert!(!stdout.contains("i18n/fr/common.json"), "{}", stdout);
+
+    dir.write(
+        "translation-check.toml",
+        "[file_format]\nfinal_newline = true\n",
+    );
+    let (_, stdout, _) = check(&dir, &[]);
+    assert!(!stdout.contains("Line endings:"), "{}", stdout);
+    assert!(
+        stdout.contains("Final newline: missing | File: i18n/es/common.json\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("i18n/de/common.json"), "{}", stdout);
+}
+
+#[test]
+fn fix_eol_only_rewrites_line_breaks() {
+    let dir = fixture("line-endings-fix", &PROJECT);
+    let (code, _, stderr) = check(&dir, &["--fix", "eol"]);
+
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert!(
+        stderr.contains("Fixed line endings in 3 translation files"),
+        "{}",
+        stderr
+    );
+    let read =
+        |lang: &str| fs::read(dir.path().join(format!("i18n/{}/common.json", lang))).unwrap();
+    assert_eq!(
+        read("de"),
+        b"{\n  \"title\": \"Titel\",\n  \"body\": \"Zeile\\r\\nWeiter\"\n}\n"
+    );
+    assert_eq!(
+        read("es"),
+        "{\n  \"title\": \"Título\",\n  \"body\": \"Línea\\r\\nSigue\"\n}\n".as_bytes()
+    );
+    assert_eq!(
+        read("it"),
+        b"{\n  \"title\": \"Titolo\",\n  \"body\": \"Riga\\r\\nSegue\"\n}\n"
+    );
+
+    let (_, stdout, _) = check(&dir, &[]);
+    assert!(!stdout.contains("line ending policy"), "{}", stdout);
+    let (_, _, stderr) = check(&dir, &["--fix", "eol"]);
+    assert!(
+        stderr.contains("0 files modified by `--fix eol`"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn fix_eol_needs_a_policy_and_leaves_the_base_alone() {
+    let dir = fixture("line-endings-base", &PROJECT);
+    dir.write("translation-check.toml", "[file_format]\neol = \"crlf\"\n");
+    let (code, _, stderr) = check(&dir, &["--fix", "eol"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert!(
+        stderr.contains("Left i18n/fr/common.json alone"),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read(dir.path().join("i18n/fr/common.json")).unwrap(),
+        b"{\n  \"title\": \"Titre\",\n  \"body\": \"Ligne\\r\\nSuite\"\n}\n"
+    );
+    // Without `final_newline`, none is added or removed.
+    assert_eq!(
+        fs::read(dir.path().join("i18n/es/common.json")).unwrap(),
+        "{\r\n  \"title\": \"Título\",\r\n  \"body\": \"Línea\\r\\nSigue\"\r\n}".as_bytes()
+    );
+
+    fs::remove_file(dir.path().join("translation-check.toml")).unwrap();
+    let (code, _, stderr) = check(&dir, &["--fix", "eol"]);
+    assert_eq!(code, Some(2), "{}", stderr);
+    assert!(
+        stderr.contains("`--fix eol` requires `eol` or `final_newline` under `[file_format]`"),
+        "{}",
+        stderr
+    );
+}
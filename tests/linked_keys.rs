@@ -0,0 +1,76 @@
+mod common;
+
+use common::{TempDir, run};
+
+#[test]
+fn reports_drifting_and_missing_linked_keys() {
+    let dir = TempDir::new("linked-keys");
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{ "shop": { "buttons": { "save": "Enregistrer", "cancel": "Annuler" } } }"#,
+    );
+    dir.write(
+        "i18n/fr/admin.json",
+        r#"{ "admin": { "buttons": { "save": "Sauver", "cancel": "Annuler" } } }"#,
+    );
+    dir.write(
+        "i18n/de/shop.json",
+        r#"{ "shop": { "buttons": { "save": "Speichern", "cancel": "Abbrechen" } } }"#,
+    );
+    dir.write(
+        "i18n/de/admin.json",
+        r#"{ "admin": { "buttons": { "save": "Speichern" } } }"#,
+    );
+    dir.write(
+        "translation-check.toml",
+        r#"
+[[linked_keys]]
+keys = ["shop.buttons.*", "admin.buttons.*"]
+"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("🔗 Linked keys have different values\n   - Key: shop.buttons.save\n"));
+    assert!(stdout.contains("   - shop.buttons.save: \"Enregistrer\" ("));
+    assert!(stdout.contains("   - admin.buttons.save: \"Sauver\" ("));
+    assert!(stdout.contains(
+        "🔗 Linked keys missing from their group:\n   - Key: admin.buttons.cancel | Group: shop.buttons.cancel, admin.buttons.cancel"
+    ));
+    assert!(!stdout.contains("Key: shop.buttons.cancel\n"));
+}
+
+#[test]
+fn linked_keys_need_matching_wildcards() {
+    let dir = TempDir::new("linked-keys-wildcards");
+    dir.write("i18n/fr/common.json", "{}");
+    dir.write(
+        "translation-check.toml",
+        r#"
+[[linked_keys]]
+keys = ["shop.*.*", "admin.buttons.*"]
+"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("must all use the same number of wildcards"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use check_translations::checks::links::links;
+use common::{TempDir, run};
+
+fn check(config: &str) -> String {
+    let dir = TempDir::new("links");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{
+            "common": { "appName": "Acme", "legal": { "terms": "Conditions" } },
+            "welcome": "Bienvenue sur @:common.appName.",
+            "footer": "Lire les @.lower:common.legal.terms",
+            "promo": "Voir @:welcome",
+            "checkout": {
+                "title": "Paiement @:checkout.summary",
+                "summary": "Résumé de @:(checkout.title)"
+            },
+            "loop": "@:{'loop'} encore"
+        }"#,
+    );
+    dir.write("translation-check.toml", config);
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn link_syntaxes_are_recognized() {
+    assert_eq!(
+        links("@:a.b, @.upper:c_d, @:(e.f) @:{'g h'} user@example.com see @:i.j."),
+        ["a.b", "c_d", "e.f", "g h", "i.j"]
+    );
+}
+
+#[test]
+fn two_step_and_self_referential_cycles_report_their_chain() {
+    let stdout = check("");
+
+    assert!(
+        stdout.contains(
+            "🌀 Linked messages forming a cycle\n   - Key: checkout.summary\n   - Chain: checkout.summary → checkout.title → checkout.summary\n   - Files: i18n/fr/common.json\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Key: loop\n   - Chain: loop → loop\n"),
+        "{}",
+        stdout
+    );
+    assert_eq!(stdout.matches("🌀").count(), 2, "{}", stdout);
+    assert!(!stdout.contains("⛓️"), "{}", stdout);
+}
+
+#[test]
+fn links_outside_the_allowlist_are_errors() {
+    let stdout = check("linkable_keys = [\"common.appName\", \"common.legal.*\"]\n");
+
+    assert!(
+        stdout.contains(
+            "⛓️ Link to a key outside the linkable keys\n   - Key: promo\n   - Link: @:welcome\n   - Linkable keys: common.appName, common.legal.*\n   - File: i18n/fr/common.json\n"
+        ),
+        "{}",
+        stdout
+    );
+    for key in ["checkout.title", "checkout.summary", "loop"] {
+        assert!(
+            stdout.contains(&format!(
+                "⛓️ Link to a key outside the linkable keys\n   - Key: {}\n",
+                key
+            )),
+            "{}",
+            stdout
+        );
+    }
+    for key in ["welcome", "footer"] {
+        assert!(
+            !stdout.contains(&format!("   - Key: {}\n   - Link", key)),
+            "{}",
+            stdout
+        );
+    }
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::{TempDir, run};
+
+#[test]
+fn reports_unbundled_and_missing_translation_files() {
+    let dir = TempDir::new("loader-config");
+    for lang in ["fr", "de"] {
+        dir.write(&format!("i18n/{}/common.json", lang), r#"{ "a": "A" }"#);
+    }
+    dir.write("i18n/de/legacy.json", "{}");
+    dir.write(
+        "src/i18n.config.ts",
+        r#"const loaders = [
+  () => import('./i18n/fr/common.json'),
+  () => import('./i18n/fr/checkout.json'),
+  () => import('./i18n/de/common.json'),
+];
+"#,
+    );
+    dir.write(
+        "translation-check.toml",
+        r#"
+[loader_config]
+pattern = '''import\('\./i18n/(?P<lang>[\w-]+)/(?P<file>[\w.-]+\.json)'\)'''
+"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+        "--loader-config",
+        dir.path().join("src/i18n.config.ts").to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout
+            .contains("🔍 Checking DE\n📦 Files not referenced by the loader config:\n   - File: ")
+    );
+    assert!(stdout.contains("de/legacy.json"));
+    assert!(stdout.contains(
+        "🔍 Checking FR\n📦 Files listed in the loader config but missing:\n   - File: "
+    ));
+    assert!(stdout.contains("fr/checkout.json"));
+    assert!(!stdout.contains("fr/common.json"));
+}
+
+#[test]
+fn loader_config_requires_a_pattern() {
+    let dir = TempDir::new("loader-config-missing");
+    dir.write("i18n/fr/common.json", "{}");
+    dir.write("loader.ts", "");
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--loader-config",
+        dir.path().join("loader.ts").to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("[loader_config]"));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn peak_memory(stderr: &str) -> Option<f64> {
+    stderr
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("- peak memory: "))
+        .and_then(|peak| peak.trim_end_matches(" MiB").parse().ok())
+}
+
+#[test]
+fn low_memory_mode_halves_peak_memory_with_the_same_report() {
+    let dir = TempDir::new("low-memory");
+    for lang in 0..30 {
+        let lang = if lang == 0 {
+            "fr".to_string()
+        } else {
+            format!("en-{:03}", lang)
+        };
+        for file in 0..10 {
+            let entries: Vec<String> = (0..1000)
+                .map(|key| format!("\"key{}\": \"Value {} {{name}}\"", key, key))
+                .collect();
+            dir.write(
+                &format!("i18n/{}/ns{}.json", lang, file),
+                &format!("{{ \"ns{}\": {{ {} }} }}", file, entries.join(", ")),
+            );
+        }
+    }
+    dir.write("src/app.ts", &"t('ns1.key1')\n".repeat(10_000));
+    dir.write(
+        "i18n/en-007/ns3.json",
+        r#"{ "ns3": { "key1": "Drift {other}" } }"#,
+    );
+
+    let args = |low_memory: bool| {
+        let mut args = vec![
+            dir.path().join("i18n").to_str().unwrap().to_string(),
+            "--src-dir".to_string(),
+            dir.path().join("src").to_str().unwrap().to_string(),
+            "--timings".to_string(),
+        ];
+        if low_memory {
+            args.push("--low-memory".to_string());
+        }
+        args
+    };
+    let normal = run(&args(false).iter().map(String::as_str).collect::<Vec<_>>());
+    let low = run(&args(true).iter().map(String::as_str).collect::<Vec<_>>());
+
+    assert_eq!(normal.status.code(), Some(1));
+    assert_eq!(low.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&normal.stdout),
+        String::from_utf8_lossy(&low.stdout)
+    );
+
+    // What a run of a single small file peaks at, the binary itself, is
+    // left out of both, so that the binary growing doesn't eat the margin.
+    let idle = TempDir::new("low-memory-idle");
+    idle.write("i18n/fr/common.json", r#"{ "title": "Titre" }"#);
+    let idle = run(&[idle.path().join("i18n").to_str().unwrap(), "--timings"]);
+
+    let normal_peak = peak_memory(&String::from_utf8_lossy(&normal.stderr));
+    let low_peak = peak_memory(&String::from_utf8_lossy(&low.stderr));
+    let idle_peak = peak_memory(&String::from_utf8_lossy(&idle.stderr));
+    if let (Some(normal_peak), Some(low_peak), Some(idle_peak)) = (normal_peak, low_peak, idle_peak)
+    {
+        assert!(
+            low_peak - idle_peak <= (normal_peak - idle_peak) / 2.0,
+            "peak memory {} MiB with --low-memory, {} MiB without, {} MiB idle",
+            low_peak,
+            normal_peak,
+            idle_peak
+        );
+    }
+}
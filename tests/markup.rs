@@ -0,0 +1,109 @@
+mod common;
+
+use check_translations::checks::markup::{self, mismatch};
+use common::{TempDir, run};
+use serde_json::Value;
+
+#[test]
+fn tags_are_read_with_their_parents() {
+    let found = markup::markup(
+        "<p>Bonjour <strong>{name}</strong>,<br> lisez <a href=\"/cgu?a>b\">les <0>CGU</0></a> <!-- <i> --> si a < b</p>",
+    );
+    let elements: Vec<(Option<&str>, &str, usize)> = found
+        .elements
+        .iter()
+        .map(|((parent, name), count)| (parent.as_deref(), name.as_str(), *count))
+        .collect();
+    assert_eq!(
+        elements,
+        [
+            (None, "p", 1),
+            (Some("a"), "0", 1),
+            (Some("p"), "a", 1),
+            (Some("p"), "br", 1),
+            (Some("p"), "strong", 1),
+        ]
+    );
+    assert_eq!(found.unbalanced, None);
+
+    let unbalanced = |value: &str| markup::markup(value).unbalanced.unwrap();
+    assert_eq!(unbalanced("<b>gras"), "<b> is never closed");
+    assert_eq!(unbalanced("gras</b>"), "</b> closes no tag");
+    assert_eq!(unbalanced("<b><i>gras</b></i>"), "</b> closes <i>");
+
+    // Siblings may swap places; elements may not move into one another.
+    let base = markup::markup("<b>un</b> et <i>deux</i>");
+    assert!(mismatch(&base, &markup::markup("<i>two</i> and <b>one</b>")).is_empty());
+    assert_eq!(
+        mismatch(&base, &markup::markup("<b>one and <i>two</i></b>")),
+        [("Nesting", "<i> in <b>".to_string())]
+    );
+}
+
+#[test]
+fn translations_dropping_or_renaming_tags_are_errors() {
+    let dir = TempDir::new("markup");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{
+  "welcome": "Bonjour <strong>{name}</strong>",
+  "terms": "Acceptez les <a href=\"/cgu\">conditions</a>",
+  "lines": "Un<br>deux",
+  "plain": "Texte"
+}"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{
+  "welcome": "Hallo <b>{name}</b>",
+  "terms": "Akzeptieren Sie die <a href=\"/agb\">Bedingungen",
+  "lines": "Eins<br/>zwei",
+  "plain": "Text <em>kursiv</em>"
+}"#,
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--format",
+        "json",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut found: Vec<(String, String)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "markup_mismatch")
+        .map(|finding| {
+            let details: Vec<String> = finding["details"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|detail| !detail["label"].as_str().unwrap().contains('('))
+                .map(|detail| format!("{}: {}", detail["label"], detail["value"]))
+                .collect();
+            (
+                finding["key"].as_str().unwrap().to_string(),
+                details.join(" | "),
+            )
+        })
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            ("plain".to_string(), r#""Unexpected": "<em>""#.to_string()),
+            (
+                "terms".to_string(),
+                r#""Unbalanced": "<a> is never closed""#.to_string()
+            ),
+            (
+                "welcome".to_string(),
+                r#""Missing": "<strong>" | "Unexpected": "<b>""#.to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::{Value, json};
+use std::fs;
+
+// Keys inherited from an older system, holding every ASCII character regex
+// and glob engines treat specially.
+const KEYS: [&str; 8] = [
+    "legacy.price(+tax)",
+    "legacy.[beta]",
+    "legacy.a*b",
+    "legacy.^start$",
+    "legacy.what?",
+    "legacy.pipe|or{x}",
+    r"legacy.back\slash/",
+    "legacy.<b>&amp;#!@%~`",
+];
+
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    let values: serde_json::Map<String, Value> = KEYS
+        .iter()
+        .map(|key| (key.to_string(), json!("Valeur")))
+        .collect();
+    dir.write("i18n/fr/legacy.json", &Value::Object(values).to_string());
+    // `de` lacks the first key and gives `legacy.a*b` another value.
+    let values: serde_json::Map<String, Value> = KEYS[1..]
+        .iter()
+        .map(|key| (key.to_string(), json!("Wert")))
+        .chain([("legacy.a*b".to_string(), json!("Anders"))])
+        .collect();
+    dir.write("i18n/de/legacy.json", &Value::Object(values).to_string());
+    dir.write(
+        "src/app.ts",
+        "t('legacy.price(+tax)'); t(\"legacy.[beta]\"); t(`legacy.^start$`);",
+    );
+    dir
+}
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, Value) {
+    let report = dir.path().join("report.json");
+    let i18n = dir.path().join("i18n");
+    let src = dir.path().join("src");
+    let sink = format!("json:{}", report.display());
+    let mut args = vec![
+        i18n.to_str().unwrap(),
+        "--src-dir",
+        src.to_str().unwrap(),
+        "--report",
+        &sink,
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    let report = serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    (output.status.code(), report)
+}
+
+fn keys_of(report: &Value, check: &str) -> Vec<String> {
+    let mut keys: Vec<String> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == check)
+        .map(|finding| finding["key"].as_str().unwrap().to_string())
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn keys_are_loaded_matched_and_reported_literally() {
+    let dir = fixture("metacharacters");
+    let junit = dir.path().join("report.xml");
+    let html = dir.path().join("report.html");
+
+    let (code, report) = check(
+        &dir,
+        &[
+            "--report",
+            &format!("junit:{}", junit.display()),
+            "--html",
+            html.to_str().unwrap(),
+        ],
+    );
+
+    assert_eq!(code, Some(1));
+    assert_eq!(keys_of(&report, "missing_key"), ["legacy.price(+tax)"]);
+    assert!(keys_of(&report, "extra_key").is_empty());
+    let mut unused: Vec<&str> = KEYS
+        .iter()
+        .copied()
+        .filter(|key| !["legacy.price(+tax)", "legacy.[beta]", "legacy.^start$"].contains(key))
+        .collect();
+    unused.sort();
+    assert_eq!(keys_of(&report, "unused_key"), unused);
+
+    let junit = fs::read_to_string(&junit).unwrap();
+    assert!(
+        junit.contains("legacy.&lt;b&gt;&amp;amp;#!@%~`"),
+        "{}",
+        junit
+    );
+    let html = fs::read_to_string(&html).unwrap();
+    assert!(!html.contains("<b>&amp;"), "{}", html);
+}
+
+#[test]
+fn waivers_and_linked_keys_take_keys_literally() {
+    let dir = fixture("metacharacters-config");
+    let waivers = dir.write(
+        "waivers.toml",
+        "[[waiver]]\ncheck = \"missing_key\"\nkey = \"legacy.price(+tax)\"\n\n\
+         [[waiver]]\ncheck = \"missing_key\"\nkey = \"legacy.price.+tax.\"\n",
+    );
+    // `\*` is a literal asterisk, so the second group names a key that
+    // doesn't exist rather than every `legacy` key.
+    let config = dir.write(
+        "translation-check.toml",
+        r#"
+[[linked_keys]]
+keys = ["legacy.a\\*b", "legacy.what?"]
+
+[[linked_keys]]
+keys = ["legacy.\\*", "legacy.[beta]"]
+"#,
+    );
+
+    let (_, report) = check(
+        &dir,
+        &[
+            "--only",
+            "consistency",
+            "--waivers",
+            waivers.to_str().unwrap(),
+            "--config",
+            config.to_str().unwrap(),
+        ],
+    );
+
+    assert!(keys_of(&report, "missing_key").is_empty());
+    // The regex-like key matched no finding.
+    let unused = &report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|finding| finding["check"] == "unused_waiver")
+        .unwrap()["details"];
+    assert!(
+        unused.to_string().contains("legacy.price.+tax."),
+        "{}",
+        unused
+    );
+    assert_eq!(keys_of(&report, "linked_key_drift"), ["legacy.a*b"]);
+    assert_eq!(
+        keys_of(&report, "linked_key_missing"),
+        ["legacy.*", "legacy.*"]
+    );
+}
@@ -0,0 +1,272 @@
+mod common;
+
+use common::{fixture, run};
+use std::collections::{BTreeMap, HashSet};
+use std::net::UdpSocket;
+use std::time::Duration;
+
+type Series = BTreeMap<(String, Vec<(String, String)>), f64>;
+
+fn is_name(name: &str, colons: bool) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || (colons && c == ':'))
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || (colons && c == ':'))
+}
+
+// A parser for the Prometheus text exposition format, strict enough to reject
+// anything the textfile collector would.
+fn parse_prometheus(text: &str) -> Result<Series, String> {
+    let mut types = HashSet::new();
+    let mut series = Series::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let fail = |reason: &str| Err(format!("line {}: {}: {:?}", number + 1, reason, line));
+        if let Some(comment) = line.strip_prefix('#') {
+            let parts: Vec<&str> = comment.trim_start().splitn(3, ' ').collect();
+            match parts.as_slice() {
+                ["HELP", name, _] if is_name(name, true) => {}
+                ["TYPE", name, kind] if is_name(name, true) => {
+                    if !["counter", "gauge", "histogram", "summary", "untyped"].contains(kind) {
+                        return fail("unknown type");
+                    }
+                    if series.keys().any(|(series, _)| series == name) || !types.insert(*name) {
+                        return fail("TYPE after samples or repeated");
+                    }
+                }
+                ["HELP" | "TYPE", ..] => return fail("malformed metadata"),
+                _ => {}
+            }
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let name_end = line.find(['{', ' ']).ok_or("no value")?;
+        let name = &line[..name_end];
+        if !is_name(name, true) {
+            return fail("invalid metric name");
+        }
+
+        let mut rest = &line[name_end..];
+        let mut labels = Vec::new();
+        if let Some(inner) = rest.strip_prefix('{') {
+            let mut chars = inner.char_indices().peekable();
+            loop {
+                let start = chars.peek().map(|(i, _)| *i).ok_or("unclosed labels")?;
+                if inner[start..].starts_with('}') {
+                    rest = &inner[start + 1..];
+                    break;
+                }
+                let eq = inner[start..].find('=').ok_or("label without value")? + start;
+                let label = &inner[start..eq];
+                if !is_name(label, false) {
+                    return fail("invalid label name");
+                }
+                if !inner[eq + 1..].starts_with('"') {
+                    return fail("unquoted label value");
+                }
+                while chars.next_if(|(i, _)| *i <= eq + 1).is_some() {}
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, 'n')) => value.push('\n'),
+                            Some((_, c @ ('\\' | '"'))) => value.push(c),
+                            _ => return fail("invalid escape"),
+                        },
+                        Some((_, '\n')) | None => return fail("unterminated label value"),
+                        Some((_, c)) => value.push(c),
+                    }
+                }
+                labels.push((label.to_string(), value));
+                match chars.peek() {
+                    Some((_, ',')) => {
+                        chars.next();
+                    }
+                    Some((_, '}')) => {}
+                    _ => return fail("expected `,` or `}`"),
+                }
+            }
+        }
+
+        let mut fields = rest.split_whitespace();
+        let value = fields.next().ok_or("missing value")?;
+        let value: f64 = match value {
+            "+Inf" => f64::INFINITY,
+            "-Inf" => f64::NEG_INFINITY,
+            "NaN" => f64::NAN,
+            _ => value
+                .parse()
+                .map_err(|_| format!("invalid value {:?}", value))?,
+        };
+        if let Some(timestamp) = fields.next()
+            && timestamp.parse::<i64>().is_err()
+        {
+            return fail("invalid timestamp");
+        }
+        if fields.next().is_some() || !rest.starts_with(' ') {
+            return fail("trailing text");
+        }
+
+        labels.sort();
+        if series.insert((name.to_string(), labels), value).is_some() {
+            return fail("duplicate series");
+        }
+    }
+
+    Ok(series)
+}
+
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour", "save": "Enregistrer", "cancel": "Annuler", "unused": "Rien" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo", "save": "Speichern", "cancel": "Abbrechen", "unused": "Nichts" }"#,
+    ),
+    ("i18n/es/common.json", r#"{ "title": "Hola" }"#),
+    ("src/app.ts", "t('title'); t('save');\nt(\"cancel\");\n"),
+];
+
+fn label(name: &str, value: &str) -> (String, String) {
+    (name.to_string(), value.to_string())
+}
+
+#[test]
+fn writes_a_valid_prometheus_textfile() {
+    let dir = fixture("metrics-textfile", &PROJECT);
+    let metrics = dir.path().join("metrics.prom");
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--src-dir",
+        dir.path().join("src").to_str().unwrap(),
+        "--metrics-file",
+        metrics.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let text = std::fs::read_to_string(&metrics).unwrap();
+    let series = parse_prometheus(&text).unwrap_or_else(|err| panic!("{}\n{}", err, text));
+
+    let get = |name: &str, labels: &[(String, String)]| {
+        let mut labels = labels.to_vec();
+        labels.sort();
+        series.get(&(name.to_string(), labels)).copied()
+    };
+    assert_eq!(
+        get(
+            "translation_check_findings",
+            &[label("check", "missing_key"), label("severity", "error")]
+        ),
+        Some(3.0)
+    );
+    assert_eq!(
+        get(
+            "translation_check_findings",
+            &[
+                label("check", "variable_mismatch"),
+                label("severity", "error")
+            ]
+        ),
+        Some(0.0)
+    );
+    assert_eq!(
+        get(
+            "translation_check_completeness_ratio",
+            &[label("lang", "es")]
+        ),
+        Some(0.25)
+    );
+    assert_eq!(
+        get(
+            "translation_check_completeness_ratio",
+            &[label("lang", "de")]
+        ),
+        Some(1.0)
+    );
+    assert_eq!(get("translation_check_unused_keys", &[]), Some(1.0));
+    assert_eq!(
+        get(
+            "translation_check_files_scanned",
+            &[label("kind", "source")]
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        get(
+            "translation_check_files_scanned",
+            &[label("kind", "translation")]
+        ),
+        Some(3.0)
+    );
+    assert!(
+        get(
+            "translation_check_phase_duration_seconds",
+            &[label("phase", "scan_sources")]
+        )
+        .is_some()
+    );
+    assert!(!dir.path().join("metrics.prom.tmp").exists());
+}
+
+#[test]
+fn skipped_checks_are_left_out_of_the_metrics() {
+    let dir = fixture("metrics-skipped", &PROJECT);
+    let metrics = dir.path().join("metrics.prom");
+
+    run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--metrics-file",
+        metrics.to_str().unwrap(),
+    ]);
+
+    let text = std::fs::read_to_string(&metrics).unwrap();
+    parse_prometheus(&text).unwrap_or_else(|err| panic!("{}\n{}", err, text));
+    assert!(text.contains("# TYPE translation_check_unused_keys gauge\n"));
+    assert!(!text.contains("\ntranslation_check_unused_keys "));
+    assert!(text.contains("translation_check_completeness_ratio{lang=\"es\"} 0.25\n"));
+}
+
+#[test]
+fn pushes_the_same_metrics_to_statsd() {
+    let dir = fixture("metrics-statsd", &PROJECT);
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let address = socket.local_addr().unwrap().to_string();
+
+    run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--src-dir",
+        dir.path().join("src").to_str().unwrap(),
+        "--statsd",
+        &address,
+    ]);
+
+    let mut lines = Vec::new();
+    let mut buffer = [0; 1500];
+    socket
+        .set_read_timeout(Some(Duration::from_millis(200)))
+        .unwrap();
+    while let Ok(len) = socket.recv(&mut buffer) {
+        lines.push(String::from_utf8_lossy(&buffer[..len]).to_string());
+    }
+
+    assert!(
+        lines.contains(
+            &"translation_check_findings:3|g|#check:missing_key,severity:error".to_string()
+        ),
+        "{:?}",
+        lines
+    );
+    assert!(lines.contains(&"translation_check_completeness_ratio:0.25|g|#lang:es".to_string()));
+    assert!(lines.contains(&"translation_check_unused_keys:1|g".to_string()));
+    assert!(lines.iter().all(|line| line.contains("|g")));
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+use std::fs;
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+  "greeting": "Bonjour {name}, vous avez {count} messages",
+  "notice": "Cher {name},\n  merci pour votre commande.\n  À bientôt",
+  "long": "Un texte assez long pour être coupé avant la fin {tail}"
+}"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{
+  "greeting": "Hallo { name }, du hast {total} Nachrichten",
+  "notice": "Liebe Kundin,\n  danke für Ihre Bestellung.\n  Bis bald",
+  "long": "Ein Text, lang genug, um vor dem Ende gekürzt zu werden"
+}"#,
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> String {
+    let i18n = dir.path().join("i18n");
+    let mut args = vec![i18n.to_str().unwrap(), "--only", "consistency"];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn both_values_are_quoted_one_above_the_other() {
+    let dir = fixture("mismatch-values", &PROJECT);
+    let stdout = check(&dir, &["--color", "never"]);
+
+    assert!(
+        stdout.contains(
+            "   - Values:\n     FR │ Bonjour {name}, vous avez {count} messages\n     DE │ Hallo { name }, du hast {total} Nachrichten\n"
+        ),
+        "{}",
+        stdout
+    );
+    // Continuation lines keep their indentation behind the gutter.
+    assert!(
+        stdout.contains(
+            "     FR │ Cher {name},\n        │   merci pour votre commande.\n        │   À bientôt\n     DE │ Liebe Kundin,\n"
+        ),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn long_values_are_cut_outside_placeholders() {
+    let dir = fixture("mismatch-values-cut", &PROJECT);
+    let stdout = check(&dir, &["--color", "never", "--max-value-length", "30"]);
+
+    assert!(
+        stdout.contains("     FR │ Un texte assez long pour être …\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("     FR │ Bonjour {name}, vous avez {count}…\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn differing_placeholders_are_highlighted() {
+    let dir = fixture("mismatch-values-color", &PROJECT);
+    let stdout = check(&dir, &["--color", "always"]);
+    let highlighted = |text: &str| format!("\u{1b}[1;31m{}\u{1b}[0m", text);
+
+    assert!(
+        stdout.contains(&format!(
+            "DE │ Hallo {{ name }}, du hast {} Nachrichten",
+            highlighted("{total}")
+        )),
+        "{}",
+        stdout
+    );
+    // `{ name }` is not a placeholder, so `{name}` is missing from DE.
+    assert!(
+        stdout.contains(&format!(
+            "FR │ Bonjour {}, vous avez {} messages",
+            highlighted("{name}"),
+            highlighted("{count}")
+        )),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn json_marks_highlights_with_offsets() {
+    let dir = fixture("mismatch-values-json", &PROJECT);
+    let report = dir.path().join("report.json");
+    check(&dir, &["--report", &format!("json:{}", report.display())]);
+
+    let report: Value = serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    let finding = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|finding| finding["key"] == "greeting")
+        .unwrap();
+    let highlighted = |side: usize| -> Vec<String> {
+        let value = &finding["values"][side];
+        let text = value["text"].as_str().unwrap();
+        value["highlights"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|range| {
+                let start = range["start"].as_u64().unwrap() as usize;
+                let end = range["end"].as_u64().unwrap() as usize;
+                text[start..end].to_string()
+            })
+            .collect()
+    };
+
+    assert_eq!(finding["values"][0]["label"], "FR");
+    assert_eq!(finding["values"][0]["truncated"], false);
+    assert_eq!(highlighted(0), ["{name}", "{count}"]);
+    assert_eq!(highlighted(1), ["{total}"]);
+}
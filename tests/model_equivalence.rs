@@ -0,0 +1,87 @@
+mod common;
+
+use common::{TempDir, assert_snapshot, run};
+
+// Findings of every per-language check over one fixture. The snapshots were
+// recorded before the translations were moved to the `Project` model and
+// must keep matching it.
+fn fixture() -> TempDir {
+    let dir = TempDir::new("equivalence");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "Title": "Titre", "greeting": "Bonjour {name}", "bye": "Au revoir", "nested": { "deep": { "key": "Profond {a} {b}" } } }"#,
+    );
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{ "shop": { "buttons": { "save": "Enregistrer", "cancel": "Annuler" } }, "admin": { "buttons": { "save": "Enregistrer", "cancel": "Fermer" } } }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "greeting": "Hallo {vorname}", "extra": "Extra", "nested": { "deep": { "key": "Tief {a}" } } }"#,
+    );
+    dir.write(
+        "i18n/de/shop.json",
+        r#"{ "shop": { "buttons": { "save": "Speichern" } }, "admin": { "buttons": { "save": "Sichern", "cancel": "Abbrechen" } } }"#,
+    );
+    dir.write(
+        "i18n/es/common.json",
+        r#"{ "Title": "Título", "TITLE": "Título", "greeting": "Hola {name}", "bye": "Adiós" }"#,
+    );
+    dir.write(
+        "src/app.ts",
+        "t('Title'); t('greeting', { name }); t('nested.deep.key', { a }); t('shop.buttons.save');\n",
+    );
+    dir.write(
+        "translation-check.toml",
+        "[[linked_keys]]\nkeys = [\"shop.buttons.*\", \"admin.buttons.*\"]\n",
+    );
+    dir
+}
+
+fn render(dir: &TempDir, extra: &[&str]) -> String {
+    let i18n = dir.path().join("i18n");
+    let src = dir.path().join("src");
+    let config = dir.path().join("translation-check.toml");
+    let mut args = vec![
+        i18n.to_str().unwrap(),
+        "--src-dir",
+        src.to_str().unwrap(),
+        "--config",
+        config.to_str().unwrap(),
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+
+    let output = run(&args);
+    String::from_utf8_lossy(&output.stdout).replace(dir.path().to_str().unwrap(), "[DIR]")
+}
+
+#[test]
+fn findings_by_language() {
+    assert_snapshot("equivalence_by_language", &render(&fixture(), &[]));
+}
+
+#[test]
+fn findings_by_file() {
+    assert_snapshot(
+        "equivalence_by_file",
+        &render(&fixture(), &["--group-by", "file"]),
+    );
+}
+
+#[test]
+fn findings_with_case_insensitive_keys() {
+    assert_snapshot(
+        "equivalence_case_insensitive",
+        &render(&fixture(), &["--key-case-insensitive"]),
+    );
+}
+
+#[test]
+fn low_memory_findings_match() {
+    assert_eq!(
+        render(&fixture(), &["--low-memory"]),
+        render(&fixture(), &[])
+    );
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+
+fn fixture(dir: &TempDir) {
+    dir.write(
+        "i18n/fr/common.json",
+        "{ \"caf\u{e9}\": \"Caf\u{e9}\", \"title\": \"R\u{e9}sum\u{e9}\" }",
+    );
+    dir.write(
+        "i18n/de/common.json",
+        "{\n  \"cafe\u{301}\": \"Cafe\u{301}\",\n  \"title\": \"Zusammenfassung\"\n}\n",
+    );
+}
+
+#[test]
+fn decomposed_text_is_reported_with_its_code_points() {
+    let dir = TempDir::new("normalization");
+    fixture(&dir);
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("🔣 Text not in Unicode NFC"), "{}", stdout);
+    assert!(
+        stdout.contains("In: key | Code points: U+0065 U+0301 should be U+00E9"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("In: value | Code points: U+0065 U+0301 should be U+00E9"),
+        "{}",
+        stdout
+    );
+    // The decomposed key is the same key as the base one.
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+    assert!(!stdout.contains("Extra keys"), "{}", stdout);
+    assert!(!stdout.contains("Key case mismatch"), "{}", stdout);
+}
+
+#[test]
+fn fix_nfc_rewrites_files_in_place() {
+    let dir = TempDir::new("normalization-fix");
+    fixture(&dir);
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--fix",
+        "nfc",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(
+        stderr.contains("Normalized 1 translation files to NFC"),
+        "{}",
+        stderr
+    );
+    assert!(!stdout.contains("Text not in Unicode NFC"), "{}", stdout);
+    // Only the text changed, not the layout of the file.
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        "{\n  \"caf\u{e9}\": \"Caf\u{e9}\",\n  \"title\": \"Zusammenfassung\"\n}\n"
+    );
+}
+
+#[test]
+fn marks_out_of_canonical_order_are_reported_and_fixed() {
+    let dir = TempDir::new("normalization-reorder");
+    dir.write("i18n/fr/common.json", "{ \"title\": \"Titre\" }");
+    // `â` followed by U+0323 COMBINING DOT BELOW composes to `ậ`.
+    dir.write("i18n/vi/common.json", "{ \"title\": \"Ti\u{e2}\u{323}u\" }");
+    let i18n = dir.path().join("i18n");
+
+    let output = run(&[i18n.to_str().unwrap(), "--only", "consistency"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("In: value | Code points: U+00E2 U+0323 should be U+1EAD"),
+        "{}",
+        stdout
+    );
+
+    run(&[
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--fix",
+        "nfc",
+    ]);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/vi/common.json")).unwrap(),
+        "{ \"title\": \"Ti\u{1EAD}u\" }"
+    );
+
+    let output = run(&[i18n.to_str().unwrap(), "--only", "consistency"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Text not in Unicode NFC"), "{}", stdout);
+}
+
+#[test]
+fn decomposed_kana_is_reported() {
+    let dir = TempDir::new("normalization-kana");
+    dir.write("i18n/fr/common.json", "{ \"title\": \"Titre\" }");
+    // `か` followed by U+3099 COMBINING VOICED SOUND MARK is `が`.
+    dir.write(
+        "i18n/ja/common.json",
+        "{ \"title\": \"\u{304B}\u{3099}\u{304F}\" }",
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("🔣 Text not in Unicode NFC"), "{}", stdout);
+    assert!(
+        stdout.contains("In: value | Code points: U+304B U+3099 should be U+304C"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::{TempDir, large_fixture, run};
+use std::process::{Command, Output};
+
+// Runs the binary under `ulimit -n <limit>`, as in a constrained container.
+fn run_with_ulimit(limit: usize, args: &[&str]) -> Output {
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("ulimit -n {} && exec \"$0\" \"$@\"", limit))
+        .arg(env!("CARGO_BIN_EXE_check_translations"))
+        .args(args)
+        .output()
+        .expect("failed to run binary")
+}
+
+#[test]
+fn a_low_descriptor_limit_gives_the_same_report() {
+    let dir = TempDir::new("open-files");
+    large_fixture(&dir, 30, 40, 5);
+    for file in 0..200 {
+        dir.write(
+            &format!("src/module{}.ts", file),
+            &format!("t('key{}');\n", file % 4),
+        );
+    }
+    let args = [
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ];
+
+    let expected = run(&args);
+    assert_eq!(expected.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&expected.stdout).contains("🧹 Unused keys: 1"));
+
+    for max_open_files in [None, Some("1"), Some("4")] {
+        let mut constrained = args.to_vec();
+        if let Some(limit) = max_open_files {
+            constrained.extend(["--max-open-files", limit]);
+        }
+        let output = run_with_ulimit(48, &constrained);
+        assert_eq!(
+            output.status.code(),
+            expected.status.code(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&expected.stdout)
+        );
+    }
+}
+
+#[test]
+fn the_limit_must_be_positive() {
+    let output = run(&["i18n", "--max-open-files", "0"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`--max-open-files`"));
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use check_translations::openapi;
+use common::{TempDir, run};
+use serde_json::Value;
+use std::path::Path;
+
+const SPEC: &str = "\
+openapi: 3.0.3
+components:
+  schemas:
+    PaymentError:
+      type: object
+      properties:
+        errorKey:
+          type: string
+          enum:
+            - errors.payment.declined
+            - \"errors.payment.timeout\"  # added by the payments team
+        status:
+          type: string
+          enum: [active, 'paused']
+        hint:
+          type: string
+          x-translation-key: errors.payment.retry
+";
+
+#[test]
+fn yaml_enums_extensions_and_flow_sequences_are_read() {
+    let groups = openapi::parse(Path::new("api/payments.yaml"), SPEC).unwrap();
+
+    let keys: Vec<(Vec<(&str, usize)>, bool)> = groups
+        .iter()
+        .map(|group| {
+            (
+                group
+                    .references
+                    .iter()
+                    .map(|reference| (reference.key.as_str(), reference.line))
+                    .collect(),
+                group.extension,
+            )
+        })
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            (
+                vec![
+                    ("errors.payment.declined", 10),
+                    ("errors.payment.timeout", 11)
+                ],
+                false
+            ),
+            (vec![("active", 14), ("paused", 14)], false),
+            (vec![("errors.payment.retry", 17)], true),
+        ]
+    );
+}
+
+// YAML specs are read by the YAML parser: quotes, escapes and comments as
+// locale files hold them, and what it refuses is a warning.
+#[test]
+fn yaml_specs_are_parsed_whole() {
+    let spec = "\
+components:
+  schemas:
+    Step:
+      # 'pending' isn't a value
+      enum: ['don''t # stop', \"go # now\"]  # two values
+";
+    let groups = openapi::parse(Path::new("api/steps.yaml"), spec).unwrap();
+    let references: Vec<(&str, usize)> = groups[0]
+        .references
+        .iter()
+        .map(|reference| (reference.key.as_str(), reference.line))
+        .collect();
+    assert_eq!(references, [("don't # stop", 5), ("go # now", 5)]);
+
+    let err = openapi::parse(Path::new("api/steps.yaml"), "enum: &values [a]\n").unwrap_err();
+    assert!(
+        err.starts_with("failed to parse api/steps.yaml: "),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn spec_references_count_as_used_and_unknown_ones_are_reported() {
+    let dir = TempDir::new("openapi");
+    dir.write(
+        "i18n/fr/errors.json",
+        r#"{
+  "errors": {
+    "payment": { "declined": "Paiement refusé", "expired": "Carte expirée" },
+    "unused": "Jamais affiché"
+  }
+}"#,
+    );
+    dir.write("src/app.ts", "export {};\n");
+    dir.write("api/payments.yaml", SPEC);
+    // OpenAPI 3.1 schemas may use `const` and type arrays.
+    dir.write(
+        "api/cards.json",
+        r#"{
+  "openapi": "3.1.0",
+  "components": {
+    "schemas": {
+      "CardExpired": { "type": ["string", "null"], "const": "errors.payment.expired" }
+    }
+  }
+}"#,
+    );
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--scan-openapi",
+        "api/*.yaml",
+        "--scan-openapi=api/*.json",
+        "--format",
+        "json",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings: Vec<(&str, &str, Option<&str>, Option<u64>)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap(),
+                finding["key"].as_str().unwrap(),
+                finding["file"].as_str(),
+                finding["line"].as_u64(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        findings,
+        [
+            (
+                "backend_missing_key",
+                "errors.payment.retry",
+                Some("api/payments.yaml"),
+                Some(17)
+            ),
+            (
+                "backend_missing_key",
+                "errors.payment.timeout",
+                Some("api/payments.yaml"),
+                Some(11)
+            ),
+            (
+                "unused_key",
+                "errors.unused",
+                Some("i18n/fr/errors.json"),
+                Some(4)
+            ),
+        ]
+    );
+}
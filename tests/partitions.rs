@@ -0,0 +1,157 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PREFIXES: [&str; 8] = [
+    "account", "billing", "cart", "checkout", "common", "errors", "profile", "search",
+];
+
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    let base: Vec<String> = PREFIXES
+        .iter()
+        .flat_map(|prefix| {
+            [
+                format!(r#""{}.title": "Titre {{name}}""#, prefix),
+                format!(r#""{}.save": "Enregistrer""#, prefix),
+            ]
+        })
+        .collect();
+    // Every other namespace misses a key and lost the title's variable.
+    let de: Vec<String> = PREFIXES
+        .iter()
+        .enumerate()
+        .map(|(i, prefix)| match i % 2 {
+            0 => format!(
+                r#""{0}.title": "Titel {{name}}", "{0}.save": "Speichern""#,
+                prefix
+            ),
+            _ => format!(r#""{}.title": "Titel""#, prefix),
+        })
+        .collect();
+    dir.write("i18n/fr/common.json", &format!("{{ {} }}", base.join(", ")));
+    dir.write("i18n/de/common.json", &format!("{{ {} }}", de.join(", ")));
+    dir
+}
+
+fn check(dir: &TempDir, extra: &[&str], report: &Path) -> Option<i32> {
+    let i18n = dir.path().join("i18n");
+    let report = format!("json:{}", report.display());
+    let mut args = vec![i18n.to_str().unwrap(), "--only", "consistency"];
+    args.extend_from_slice(extra);
+    args.extend(["--report", &report]);
+    run(&args).status.code()
+}
+
+fn read(path: &Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+// Runs the four shards, returning their reports and exit codes.
+fn shards(dir: &TempDir) -> Vec<(PathBuf, Option<i32>)> {
+    (1..=4)
+        .map(|index| {
+            let path = dir.path().join(format!("part-{}.json", index));
+            let partition = format!("{}/4", index);
+            let code = check(
+                dir,
+                &["--partition", &partition, "--partition-by", "prefix"],
+                &path,
+            );
+            (path, code)
+        })
+        .collect()
+}
+
+#[test]
+fn merged_shards_match_the_unpartitioned_run() {
+    let dir = fixture("partitions");
+    let full = dir.path().join("full.json");
+    assert_eq!(check(&dir, &[], &full), Some(1));
+    let full = read(&full);
+
+    let parts: Vec<PathBuf> = shards(&dir).into_iter().map(|(part, _)| part).collect();
+    let mut sizes = Vec::new();
+    for part in &parts {
+        let part = read(part);
+        assert_eq!(part["partition"]["count"], 4);
+        sizes.push(part["findings"].as_array().unwrap().len());
+    }
+    assert_eq!(
+        sizes.iter().sum::<usize>(),
+        full["findings"].as_array().unwrap().len()
+    );
+    assert!(
+        sizes.iter().filter(|size| **size > 0).count() > 1,
+        "{:?}",
+        sizes
+    );
+
+    let merged = dir.path().join("merged.json");
+    let mut args = vec!["merge-reports".to_string()];
+    args.extend(parts.iter().rev().map(|part| part.display().to_string()));
+    args.extend(["--output".to_string(), merged.display().to_string()]);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run(&args);
+
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(read(&merged), full);
+}
+
+#[test]
+fn each_shard_only_fails_on_its_own_keys() {
+    let dir = fixture("partitions-exit");
+    let shards = shards(&dir);
+    for (part, code) in &shards {
+        let report = read(part);
+        let errors = report["summary"]["errors"].as_u64().unwrap();
+        assert_eq!(*code, Some(if errors == 0 { 0 } else { 1 }), "{}", report);
+    }
+    assert!(shards.iter().any(|(_, code)| *code == Some(0)));
+}
+
+#[test]
+fn gaps_and_overlaps_are_rejected() {
+    let dir = fixture("partitions-invalid");
+    let parts: Vec<PathBuf> = shards(&dir).into_iter().map(|(part, _)| part).collect();
+    let part = |index: usize| parts[index].to_str().unwrap();
+
+    let output = run(&["merge-reports", part(0), part(1), part(3)]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("missing partitions 3/4"));
+
+    let output = run(&["merge-reports", part(0), part(1), part(2), part(3), part(1)]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("both cover partition 2/4"));
+
+    // A finding moved into the wrong shard overlaps with its owner.
+    let (from, to) = (0..4)
+        .flat_map(|from| (0..4).map(move |to| (from, to)))
+        .find(|(from, to)| {
+            from != to
+                && !read(&parts[*from])["findings"]
+                    .as_array()
+                    .unwrap()
+                    .is_empty()
+        })
+        .unwrap();
+    let mut source = read(&parts[from]);
+    let mut target = read(&parts[to]);
+    let finding = source["findings"].as_array_mut().unwrap().pop().unwrap();
+    target["findings"].as_array_mut().unwrap().push(finding);
+    fs::write(&parts[from], source.to_string()).unwrap();
+    fs::write(&parts[to], target.to_string()).unwrap();
+
+    let output = run(&["merge-reports", part(0), part(1), part(2), part(3)]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr);
+    assert!(stderr.contains("outside its own partition"), "{}", stderr);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+// German repeats `{email}` where French uses two placeholders, and once
+// more where French names `{name}` twice on purpose; Dutch leaves out
+// `{phone}` altogether.
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+  "send": "Envoyer à {email} ou {phone}",
+  "greeting": "{name}, bonjour {name} !",
+  "welcome": "Bienvenue {name}",
+  "stars": "Note : {star}{star}{star}"
+}"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{
+  "send": "An {email} oder {email} {phone} senden",
+  "greeting": "{name}, hallo {name} {name}!",
+  "welcome": "Willkommen {name}",
+  "stars": "Note: {star}{star}{star}"
+}"#,
+    ),
+    (
+        "i18n/nl/common.json",
+        r#"{
+  "send": "Naar {email} of {email} sturen",
+  "greeting": "{name}, hallo {name}!",
+  "welcome": "Welkom {name}",
+  "stars": "Score: {star}{star}{star}"
+}"#,
+    ),
+];
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn repeated_placeholders_are_counted() {
+    let dir = fixture("placeholder-counts", &PROJECT);
+    let (_, stdout) = check(&dir);
+
+    for line in [
+        "   - Key: send\n   - Counts: {email}: 2 (DE), 1 (FR)\n   - Base (FR): \"Envoyer à {email} ou {phone}\"\n",
+        "   - Key: greeting\n   - Counts: {name}: 3 (DE), 2 (FR)\n",
+        "   - Key: stars\n   - Counts: {star}: 3 (FR)\n   - Base (FR): \"Note : {star}{star}{star}\"\n",
+    ] {
+        assert!(stdout.contains(line), "{}", stdout);
+    }
+    // The sets differ in Dutch, so the counts go with the mismatch.
+    assert!(
+        stdout.contains("Counts: {email}: 2 (NL), 1 (FR)"),
+        "{}",
+        stdout
+    );
+    assert_eq!(stdout.matches("Counts: {email}").count(), 2, "{}", stdout);
+    assert!(!stdout.contains("Willkommen"), "{}", stdout);
+    assert!(!stdout.contains("{star}: 3 (DE)"), "{}", stdout);
+}
+
+#[test]
+fn intentional_repetition_can_be_ignored_per_key() {
+    let dir = fixture("placeholder-counts-ignore", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[[ignore]]\ncheck = \"duplicate_placeholder\"\nkey = \"greeting\"\n\n[[ignore]]\ncheck = \"duplicate_placeholder\"\nkey = \"stars\"\n",
+    );
+    let (_, stdout) = check(&dir);
+
+    assert!(
+        stdout.contains("Counts: {email}: 2 (DE), 1 (FR)"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Counts: {name}"), "{}", stdout);
+    assert!(!stdout.contains("Counts: {star}"), "{}", stdout);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/requests.json",
+        r#"{
+            "refused": "Votre demande a été refusée par {name}.",
+            "sent": "{count} demandes envoyées",
+            "owner": "Par {name}",
+            "range": "De {from} à {to}"
+        }"#,
+    );
+    dir.write(
+        "i18n/de/requests.json",
+        r#"{
+            "refused": "{name} hat Ihre Anfrage abgelehnt.",
+            "sent": "{count} Anfragen gesendet",
+            "owner": "{name}",
+            "range": "{from} bis {to}"
+        }"#,
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> String {
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn moved_placeholders_are_reported_with_both_values() {
+    let dir = fixture("placeholder-position", "[placeholder_position]\n");
+
+    let stdout = check(&dir);
+
+    assert!(
+        stdout.contains(
+            "📍 Placeholders moved to or from the edges of the value\n   - Key: refused\n   - Moved: {name} trailing in FR, leading in DE\n   - Base (FR): \"Votre demande a été refusée par {name}.\"\n   - Value (DE): \"{name} hat Ihre Anfrage abgelehnt.\"\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Moved: {from} medial in FR, leading in DE\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: sent"), "{}", stdout);
+    // A value made of a placeholder alone has no structure to compare.
+    assert!(!stdout.contains("Key: owner"), "{}", stdout);
+    assert!(
+        stdout.contains("✅ No translation issues found."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn the_check_is_opt_in() {
+    for (name, config) in [
+        ("placeholder-position-off", ""),
+        (
+            "placeholder-position-disabled",
+            "[placeholder_position]\nenabled = false\n",
+        ),
+    ] {
+        let stdout = check(&fixture(name, config));
+        assert!(!stdout.contains("📍"), "{}", stdout);
+    }
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn check(name: &str, fr: &str, de: &str, extra: &[&str]) -> (Option<i32>, String) {
+    let dir = TempDir::new(name);
+    dir.write("i18n/fr/common.json", fr);
+    dir.write("i18n/de/common.json", de);
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn double_braces_are_one_placeholder() {
+    let fr = r#"{ "items": "{{count}} articles", "both": "{n} sur {{n}}" }"#;
+    let de = r#"{ "items": "Artikel", "both": "{{n}} von {n}" }"#;
+
+    let (code, stdout) = check(
+        "placeholders-double",
+        fr,
+        de,
+        &["--placeholder-style", "double-brace"],
+    );
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("   - Key: items\n   - Expected variables (FR): {\"count\"}\n   - Found variables (DE): {}\n"), "{}", stdout);
+    assert!(!stdout.contains("Key: both"), "{}", stdout);
+
+    // Read as single braces, `{{count}}` is no placeholder at all.
+    let (code, stdout) = check("placeholders-single", fr, de, &[]);
+    assert_eq!(code, Some(0), "{}", stdout);
+}
+
+#[test]
+fn the_mismatch_names_the_syntax() {
+    let fr = r#"{ "greeting": "Bonjour %{name}, %s messages et %d alertes" }"#;
+    let de = r#"{ "greeting": "Hallo %{name}, %s Nachrichten" }"#;
+
+    let (code, stdout) = check(
+        "placeholders-percent",
+        fr,
+        de,
+        &["--placeholder-style", "percent"],
+    );
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains("   - Syntax: %d (positional)\n"),
+        "{}",
+        stdout
+    );
+
+    let de = r#"{ "greeting": "Hallo {{name}}, %s Nachrichten et %d Warnungen" }"#;
+    let (_, stdout) = check("placeholders-all", fr, de, &["--placeholder-style", "all"]);
+    assert!(!stdout.contains("Variable mismatch"), "{}", stdout);
+}
+
+#[test]
+fn icu_escaped_braces_are_literal_text() {
+    let fr = r#"{ "help": "Écrivez '{name}' pour {user}", "quote": "L''{count}'' de {user}" }"#;
+    let de = r#"{ "help": "Schreibe name für {user}", "quote": "Die {user}" }"#;
+
+    let (code, stdout) = check("placeholders-icu", fr, de, &[]);
+    assert_eq!(code, Some(1));
+    assert!(!stdout.contains("Key: help"), "{}", stdout);
+    assert!(
+        stdout.contains("   - Key: quote\n   - Expected variables (FR): {\"count\", \"user\"}\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn an_unknown_style_is_rejected() {
+    let output = run(&["i18n", "--placeholder-style", "angle"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`--placeholder-style`"));
+}
+
+#[test]
+fn styles_combine_with_custom_patterns() {
+    let fr = r#"{ "greeting": "Bonjour {{name}}, %s messages pour ${team}" }"#;
+    let de = r#"{ "greeting": "Hallo {{name}}, %s Nachrichten für ${group}" }"#;
+
+    let (code, stdout) = check(
+        "placeholders-custom",
+        fr,
+        de,
+        &[
+            "--placeholder-style",
+            "double-brace,percent",
+            "--placeholder-pattern",
+            r"\$\{(\w+)\}",
+        ],
+    );
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains("   - Expected variables (FR): {\"%s\", \"name\", \"team\"}\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Syntax: ${group} (custom), ${team} (custom)\n"),
+        "{}",
+        stdout
+    );
+
+    // The same settings, as `[project]` defaults.
+    let dir = TempDir::new("placeholders-custom-config");
+    dir.write("i18n/fr/common.json", fr);
+    dir.write("i18n/de/common.json", de);
+    dir.write(
+        "translation-check.toml",
+        "[project]\nplaceholder_styles = [\"double-brace\", \"percent\"]\nplaceholder_patterns = ['\\$\\{(\\w+)\\}']\n",
+    );
+    let dir_arg = dir.path().to_str().unwrap();
+    let output = run(&["--cwd", dir_arg, "i18n", "--print-config"]);
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        config["placeholder_styles"],
+        serde_json::json!(["double-brace", "percent"])
+    );
+    let output = run(&[
+        "--cwd",
+        dir_arg,
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("${team} (custom)"), "{}", stdout);
+
+    let output = run(&["i18n", "--placeholder-pattern", r"\$\{\w+\}"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("it captures no placeholder name"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// The subcommands read the placeholder syntaxes of `[project]` as the check
+// run does.
+#[test]
+fn subcommands_read_the_configured_styles() {
+    let dir = TempDir::new("placeholders-subcommands");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "items": "{{count}} articles" }"#,
+    );
+    dir.write("i18n/de/common.json", r#"{ "items": "Artikel" }"#);
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"i18n\"\nplaceholder_styles = [\"double-brace\"]\n",
+    );
+    let subcommand = |args: &[&str]| {
+        let mut all = vec!["--cwd", dir.path().to_str().unwrap()];
+        all.extend_from_slice(args);
+        let output = run(&all);
+        assert_eq!(output.status.code(), Some(0), "{:?}", args);
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let worklist = subcommand(&["worklist", "--lang", "de"]);
+    assert!(
+        worklist.contains("## Variable mismatches (1)\n"),
+        "{}",
+        worklist
+    );
+    let sample = subcommand(&["sample", "--lang", "de", "--seed", "1"]);
+    assert!(sample.contains("Finding: variable_mismatch"), "{}", sample);
+    let preview = subcommand(&["preview", "items", "--vars", "count=3"]);
+    assert_eq!(preview, "fr │ 3 articles\n");
+}
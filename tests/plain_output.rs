@@ -0,0 +1,73 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture() -> TempDir {
+    let dir = TempDir::new("plain-output");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {name}", "save": "Enregistrer", "unused": "Inutile" }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo", "extra": "Extra", "line\nbreak": "Zeile", "save": "Speichern {n}" }"#,
+    );
+    dir.write("src/app.ts", "t('title');\nt('save');\n");
+    dir
+}
+
+#[test]
+fn every_finding_is_one_line_of_six_fields() {
+    let dir = fixture();
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "plain",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1));
+    let lines: Vec<&str> = stdout.lines().collect();
+    for line in &lines {
+        assert_eq!(line.split('\t').count(), 6, "{:?}", line);
+    }
+    let mut checks: Vec<&str> = lines
+        .iter()
+        .map(|line| line.split('\t').nth(1).unwrap())
+        .collect();
+    checks.dedup();
+    assert_eq!(
+        checks,
+        [
+            "missing_interpolation",
+            "missing_key",
+            "extra_key",
+            "variable_mismatch",
+            "unused_key"
+        ],
+        "{}",
+        stdout
+    );
+    assert!(
+        lines
+            .contains(&"ERROR\tmissing_interpolation\t-\ttitle\tsrc/app.ts:1\tMissing: [\"name\"]"),
+        "{}",
+        stdout
+    );
+    assert!(
+        lines.contains(&"ERROR\textra_key\tde\tline\\nbreak\ti18n/de/common.json:1:39\t-"),
+        "{}",
+        stdout
+    );
+    assert!(
+        lines.contains(&"INFO\tunused_key\tfr\tunused\ti18n/fr/common.json:1:53\t-"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains('\u{1b}'), "{}", stdout);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{
+            "item_one": "{count} article",
+            "item_other": "Articles",
+            "cart_one": "{count} panier",
+            "cart_other": "{count} paniers"
+        }"#,
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn forms_are_compared_within_the_base_language() {
+    let dir = fixture("plural-forms", "[plural_forms]\n");
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🔢 Plural forms of a base key using different placeholders\n   - Key: item\n   - item_one: {count}\n   - item_other: none\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: cart"), "{}", stdout);
+}
+
+#[test]
+fn mismatches_caused_by_the_base_are_counted_on_it() {
+    let dir = fixture("plural-forms-derived", "[plural_forms]\n");
+    dir.write(
+        "i18n/de/shop.json",
+        r#"{
+            "item_one": "{count} Artikel",
+            "item_other": "{count} Artikel",
+            "cart_one": "{count} Korb",
+            "cart_other": "{count} Körbe"
+        }"#,
+    );
+
+    let (_, stdout) = check(&dir);
+    assert!(
+        stdout.contains("   - Suppressed: 1 findings in de\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: item_other"), "{}", stdout);
+
+    // Fixed in the base, the forms agree with the translation again.
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{
+            "item_one": "{count} article",
+            "item_other": "{count} articles",
+            "cart_one": "{count} panier",
+            "cart_other": "{count} paniers"
+        }"#,
+    );
+    let (code, stdout) = check(&dir);
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(!stdout.contains("🔢"), "{}", stdout);
+}
+
+#[test]
+fn the_check_is_opt_in() {
+    let (code, stdout) = check(&fixture("plural-forms-off", ""));
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(!stdout.contains("🔢"), "{}", stdout);
+
+    let (_, stdout) = check(&fixture(
+        "plural-forms-suffixes",
+        "[plural_forms]\nsuffixes = [\"one\"]\n",
+    ));
+    assert!(!stdout.contains("🔢"), "{}", stdout);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/shop.json",
+        r#"{ "item_one": "{count} article", "item_other": "{count} articles" }"#,
+    ),
+    ("translation-check.toml", "[plural_forms]\n"),
+];
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn each_language_needs_its_own_plural_forms() {
+    let dir = fixture("plural-rules", &PROJECT);
+    dir.write(
+        "i18n/ru/shop.json",
+        r#"{ "item_one": "{count} товар", "item_few": "{count} товара", "item_other": "{count} товара" }"#,
+    );
+    dir.write("i18n/ja/shop.json", r#"{ "item_other": "{count} 個" }"#);
+
+    let (code, stdout) = check(&dir);
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🧮 Plural keys lacking a form their language requires\n   - Key: item\n   - Missing: many\n   - Examples: many: 0, 5~19, 100, 1000, 10000, 100000\n"
+        ),
+        "{}",
+        stdout
+    );
+    // `item_few` is a Russian form, and Japanese has no `one`.
+    assert!(!stdout.contains("Key: item_few"), "{}", stdout);
+    assert!(!stdout.contains("Key: item_one"), "{}", stdout);
+    assert!(stdout.contains("(1 warnings)"), "{}", stdout);
+}
+
+#[test]
+fn forms_are_compared_across_the_plural_key() {
+    let dir = fixture("plural-rules-forms", &PROJECT);
+    dir.write(
+        "i18n/pl/shop.json",
+        r#"{
+            "item_one": "{count} produkt",
+            "item_few": "{n} produkty",
+            "item_many": "{count} produktów",
+            "item_other": "{count} produktu"
+        }"#,
+    );
+    dir.write(
+        "i18n/de/shop.json",
+        r#"{ "item_one": "{count} Artikel", "item_few": "{count} Artikel", "item_other": "{count} Artikel" }"#,
+    );
+
+    let (code, stdout) = check(&dir);
+    assert_eq!(code, Some(1), "{}", stdout);
+    // The Polish forms the base lacks are compared with `item_other`.
+    assert!(stdout.contains("Key: item_few"), "{}", stdout);
+    assert!(
+        stdout.contains("Found variables (PL): {\"n\"}"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: item_many"), "{}", stdout);
+    assert!(
+        stdout.contains("Plural forms their language never uses:\n   - Key: item | Unused: few"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Extra keys"), "{}", stdout);
+}
+
+#[test]
+fn regional_tags_fall_back_to_their_language() {
+    let dir = fixture("plural-rules-fallback", &PROJECT);
+    dir.write(
+        "i18n/pt-BR/shop.json",
+        r#"{ "item_one": "{count} item", "item_other": "{count} itens" }"#,
+    );
+    dir.write(
+        "i18n/xx/shop.json",
+        r#"{ "item_one": "{count} x", "item_other": "{count} xs" }"#,
+    );
+
+    let (_, stdout) = check(&dir);
+    assert!(!stdout.contains("🧮"), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "Languages without known plural rules:\n   - Tag: xx | Skipped: 1 plural keys"
+        ),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn plural_rules_lists_the_categories_of_a_language() {
+    let output = run(&["plural-rules", "pt_BR"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout,
+        "Plural categories of pt_BR (from pt):\n   - one: 0, 1\n   - many (optional): 1000000, 2000000, 3000000\n   - other: 2~17, 100, 1000, 10000, 100000\n"
+    );
+
+    let output = run(&["plural-rules", "ar"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 7, "{}", stdout);
+
+    let output = run(&["plural-rules", "xx"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no CLDR plural rules for `xx`"));
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::{Value, json};
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/checkout.json",
+        r#"{ "checkout": { "summary": "{count} articles pour {name}" } }"#,
+    ),
+    (
+        "i18n/de/checkout.json",
+        r#"{ "checkout": { "summary": "{count} Artikel für {name}" } }"#,
+    ),
+    (
+        "i18n/it/checkout.json",
+        r#"{ "checkout": { "summary": "{count} articoli per {nome}" } }"#,
+    ),
+];
+
+fn preview(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String, String) {
+    let i18n = dir.path().join("i18n");
+    let mut args = vec!["preview", "checkout.summary", i18n.to_str().unwrap()];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn renders_the_value_with_the_sample_variables() {
+    let dir = fixture("preview", &PROJECT);
+
+    let (code, stdout, stderr) = preview(&dir, &["--lang", "de", "--vars", "count=3,name=Anna"]);
+
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert_eq!(stdout, "de │ 3 Artikel für Anna\n");
+    assert!(stderr.is_empty(), "{}", stderr);
+}
+
+#[test]
+fn unknown_variables_and_unreplaced_placeholders_are_called_out() {
+    let dir = fixture("preview-problems", &PROJECT);
+
+    let (code, stdout, stderr) = preview(
+        &dir,
+        &["--all-langs", "--vars", "count=3,name=Anna,cuont=4"],
+    );
+
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("fr │ 3 articles pour Anna\n"), "{}", stdout);
+    assert!(
+        stdout.contains("it │ 3 articoli per {nome}\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stderr.contains("`cuont` is not a variable of `checkout.summary`"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("`{nome}` left unreplaced in it"),
+        "{}",
+        stderr
+    );
+    // `name` is only missing from `it`, it's still a known variable.
+    assert!(!stderr.contains("`name` is not a variable"), "{}", stderr);
+}
+
+#[test]
+fn json_lists_the_rendered_strings() {
+    let dir = fixture("preview-json", &PROJECT);
+
+    let (code, stdout, _) = preview(
+        &dir,
+        &["--lang", "fr,it", "--vars", "count=1", "--format", "json"],
+    );
+
+    assert_eq!(code, Some(1));
+    let preview: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        preview,
+        json!({
+            "key": "checkout.summary",
+            "vars": { "count": "1" },
+            "languages": {
+                "fr": { "rendered": "1 articles pour {name}", "unreplaced": ["name"] },
+                "it": { "rendered": "1 articoli per {nome}", "unreplaced": ["nome"] },
+            },
+            "unknown_variables": [],
+        })
+    );
+}
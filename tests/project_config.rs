@@ -0,0 +1,211 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+
+// The translations live in `locales`, which only the configuration file
+// names. German lacks `cart`.
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "locales/fr/common.json",
+        r#"{ "title": "Titre", "cart": "Panier" }"#,
+    ),
+    ("locales/de/common.json", r#"{ "title": "Titel" }"#),
+    ("web/app.vue", "t('title'); t('cart')"),
+];
+
+fn check(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec!["--cwd", dir.path().to_str().unwrap(), "--color", "never"];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+// Subcommands come first, before the options of the check run.
+fn subcommand(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec!["--cwd", dir.path().to_str().unwrap()];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn project_settings_are_defaults_for_the_flags() {
+    let dir = fixture("project-config", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"locales\"\nsrc_dir = \"web\"\nextensions = [\".vue\"]\n",
+    );
+    let (_, stdout, _) = check(&dir, &["--print-config"]);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["i18n_dir"], "locales");
+    assert_eq!(config["src_dir"], "web");
+    assert_eq!(config["extensions"], serde_json::json!(["vue"]));
+
+    let (code, stdout, stderr) = check(&dir, &[]);
+    assert_eq!(code, Some(1), "{}", stderr);
+    assert!(
+        stdout.contains("   - Key: cart | File: locales/de/common.json\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("🧹 Unused keys: 0\n"), "{}", stdout);
+
+    // Flags win.
+    dir.write("other/fr/common.json", r#"{ "title": "Titre" }"#);
+    let (_, stdout, _) = check(&dir, &["other", "--ext", "ts", "--print-config"]);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["i18n_dir"], "other");
+    assert_eq!(config["src_dir"], "web");
+    assert_eq!(config["extensions"], serde_json::json!(["ts"]));
+}
+
+#[test]
+fn severities_can_be_overridden_per_check() {
+    let dir = fixture("project-config-severity", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"locales\"\nsrc_dir = \"web\"\n\n[severity]\nmissing_key = \"warning\"\n",
+    );
+    let (code, stdout, stderr) = check(&dir, &["--format", "json"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    let report: Value = serde_json::from_str(&stdout).unwrap();
+    let missing = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|finding| finding["check"] == "missing_key")
+        .unwrap();
+    assert_eq!(missing["severity"], "warning");
+
+    dir.write(
+        "translation-check.toml",
+        "[severity]\nmissing_keys = \"warning\"\n",
+    );
+    let (code, _, stderr) = check(&dir, &[]);
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("unknown check `missing_keys` in `severity`"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn checks_can_be_turned_off_and_warnings_fail_with_their_own_code() {
+    let dir = fixture("project-config-exit-codes", &PROJECT);
+    let config = |severity: &str| {
+        format!(
+            "[project]\ni18n_dir = \"locales\"\nsrc_dir = \"web\"\n\n[severity]\nmissing_key = \"{}\"\n",
+            severity
+        )
+    };
+    dir.write("translation-check.toml", &config("warn"));
+    let (code, _, stderr) = check(&dir, &[]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    let (code, _, stderr) = check(&dir, &["--fail-on", "warnings"]);
+    assert_eq!(code, Some(3), "{}", stderr);
+
+    dir.write("translation-check.toml", &config("off"));
+    let (code, stdout, stderr) = check(&dir, &["--fail-on", "warnings", "--format", "json"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    let report: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["findings"], serde_json::json!([]));
+
+    dir.write("translation-check.toml", &config("none"));
+    let (code, _, stderr) = check(&dir, &[]);
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("expected `info`, `warning`, `error` or `off`"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn a_json_configuration_file_is_read_without_a_toml_one() {
+    let dir = fixture("project-config-json", &PROJECT);
+    dir.write(
+        ".translationcheckrc.json",
+        r#"{ "project": { "i18n_dir": "locales", "src_dir": "web" }, "severity": { "missing_key": "info" } }"#,
+    );
+    let (code, stdout, stderr) = check(&dir, &["--print-config"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["i18n_dir"], "locales");
+    assert_eq!(config["config_file"], ".translationcheckrc.json");
+
+    let (code, _, stderr) = check(&dir, &[]);
+    assert_eq!(code, Some(0), "{}", stderr);
+
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"missing\"\n",
+    );
+    let (_, stdout, _) = check(&dir, &["--print-config"]);
+    let config: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(config["config_file"], "translation-check.toml");
+}
+
+// The subcommands take the translation folder and base language from the
+// configuration file as the check run does.
+#[test]
+fn subcommands_read_the_project_settings() {
+    let dir = TempDir::new("project-config-subcommands");
+    dir.write(
+        "locales/en/common.json",
+        r#"{ "title": "Title", "cart": "Cart" }"#,
+    );
+    dir.write("locales/de/common.json", r#"{ "title": "Titel" }"#);
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"locales\"\n\n[languages]\nbase = \"en\"\n",
+    );
+    let (code, stdout, stderr) = subcommand(&dir, &["stats", "--format", "json"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    let stats: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(stats["base_lang"], "en");
+    assert_eq!(stats["languages"][0]["missing"], 1);
+
+    for args in [
+        &["worklist", "--lang", "de"][..],
+        &["export", "--lang", "de"],
+        &["search", "Cart"],
+        &["preview", "cart"],
+    ] {
+        let (code, stdout, stderr) = subcommand(&dir, args);
+        assert_eq!(code, Some(0), "{:?}: {}", args, stderr);
+        assert!(stdout.contains("Cart"), "{:?}: {}", args, stdout);
+    }
+
+    // Without `languages.base`, the base is detected as for the check run.
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"locales\"\n",
+    );
+    let (code, stdout, stderr) = subcommand(&dir, &["stats"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert!(
+        stdout.starts_with("Translation stats against en (2 keys)\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stderr.contains("using 'en' as base language (auto-detected)"),
+        "{}",
+        stderr
+    );
+
+    // The folder given on the command line wins, once.
+    let (code, _, stderr) = subcommand(&dir, &["stats", "locales", "--i18n-dir", "locales"]);
+    assert_eq!(code, Some(2));
+    assert!(stderr.contains("given both as an argument"), "{}", stderr);
+}
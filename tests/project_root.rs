@@ -0,0 +1,117 @@
+mod common;
+
+use common::TempDir;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir_all(dir.path().join(".git")).unwrap();
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {name}", "save": "Enregistrer" }"#,
+    );
+    dir.write("i18n/de/common.json", r#"{ "title": "Hallo" }"#);
+    dir.write("packages/web/src/app.ts", "t('title');");
+    dir.write(
+        "translation-check.toml",
+        "[languages]\nexpected = [\"fr\", \"de\"]\n",
+    );
+    dir
+}
+
+fn run_in(cwd: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .expect("failed to run binary")
+}
+
+const ARGS: [&str; 6] = [
+    "i18n",
+    "--src-dir",
+    "packages/web/src",
+    "--report",
+    "json:report.json",
+    "--color=never",
+];
+
+#[test]
+fn reports_are_identical_from_any_working_directory() {
+    let dir = fixture("project-root");
+    let root = fs::canonicalize(dir.path()).unwrap();
+
+    let mut runs = Vec::new();
+    for cwd in ["", "packages/web", "i18n/fr"] {
+        let output = run_in(&dir.path().join(cwd), &ARGS);
+        assert_eq!(output.status.code(), Some(1), "{}", cwd);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains(&format!("Project root: {}\n", root.display())),
+            "{}",
+            stderr
+        );
+        let report = fs::read_to_string(dir.path().join("report.json")).unwrap();
+        runs.push((String::from_utf8_lossy(&output.stdout).to_string(), report));
+    }
+
+    assert!(runs[0].0.contains("Key: save"), "{}", runs[0].0);
+    assert!(runs[0].1.contains("\"unused_key\""), "{}", runs[0].1);
+    assert_eq!(runs[0], runs[1]);
+    assert_eq!(runs[0], runs[2]);
+}
+
+#[test]
+fn cwd_overrides_the_detected_root() {
+    let dir = fixture("project-root-cwd");
+    let elsewhere = TempDir::new("project-root-elsewhere");
+    let root = fs::canonicalize(dir.path()).unwrap();
+
+    let mut args = vec!["--cwd", dir.path().to_str().unwrap()];
+    args.extend_from_slice(&ARGS);
+    let output = run_in(elsewhere.path(), &args);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(dir.path().join("report.json").exists());
+    assert!(!elsewhere.path().join("report.json").exists());
+
+    let output = run_in(
+        elsewhere.path(),
+        &["--print-config", "--cwd", dir.path().to_str().unwrap()],
+    );
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["root"], root.to_str().unwrap());
+    assert_eq!(
+        config["config_file"], "translation-check.toml",
+        "{}",
+        config
+    );
+}
+
+#[test]
+fn paths_default_to_conventional_folders_of_the_root() {
+    let dir = TempDir::new("project-root-conventional");
+    fs::create_dir_all(dir.path().join(".git")).unwrap();
+    dir.write(
+        "src/assets/i18n/fr/common.json",
+        r#"{ "title": "Bonjour" }"#,
+    );
+    dir.write("src/app.ts", "t('title');");
+
+    let output = run_in(dir.path(), &["--print-config"]);
+    let config: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["i18n_dir"], "src/assets/i18n");
+    assert_eq!(config["src_dir"], "src");
+
+    fs::remove_dir_all(dir.path().join("src/assets")).unwrap();
+    let output = run_in(dir.path(), &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("no translation folder found in the project root"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,242 @@
+use check_translations::checks::extract_variables;
+use check_translations::interpolation::find_call_sites;
+use check_translations::loader::{Flattened, flatten_json, nest_json};
+use check_translations::toml;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+// Randomized properties of the parsers, checked with proptest, which shrinks
+// a failing input before reporting it. Every `cargo test` runs a reduced
+// number of cases; set `PROPTEST_CASES` for longer runs.
+fn config() -> ProptestConfig {
+    match std::env::var_os("PROPTEST_CASES") {
+        Some(_) => ProptestConfig::default(),
+        None => ProptestConfig::with_cases(64),
+    }
+}
+
+// Up to `max_len` pieces of the alphabet, end to end.
+fn text(alphabet: &'static [&'static str], max_len: usize) -> impl Strategy<Value = String> {
+    vec(select(alphabet), 0..=max_len).prop_map(|pieces| pieces.concat())
+}
+
+const KEY_ALPHABET: &[&str] = &["a", "b", "Z", "_", "-", " ", "é", "日", "{", "}"];
+const VALUE_ALPHABET: &[&str] = &[
+    "a", " ", "{", "}", "{name}", "{count}", "'", "\"", "`", "\\", "\n", "é", "😀",
+];
+
+fn key(dots: bool) -> BoxedStrategy<String> {
+    if !dots {
+        return text(KEY_ALPHABET, 4).boxed();
+    }
+    prop_oneof![
+        3 => text(KEY_ALPHABET, 4),
+        1 => (text(KEY_ALPHABET, 3), text(KEY_ALPHABET, 3))
+            .prop_map(|(parent, child)| format!("{}.{}", parent, child)),
+    ]
+    .boxed()
+}
+
+// An object tree whose leaves are all strings. Nested objects are never
+// empty, since flattening has nothing to keep for them.
+fn string_tree(depth: usize, dots: bool) -> BoxedStrategy<Value> {
+    let leaf = text(VALUE_ALPHABET, 6).prop_map(Value::String);
+    let value = if depth < 4 {
+        prop_oneof![2 => leaf, 1 => string_tree(depth + 1, dots)].boxed()
+    } else {
+        leaf.boxed()
+    };
+    let min = if depth == 0 { 0 } else { 1 };
+    vec((key(dots), value), min..min + 4)
+        .prop_map(|entries| Value::Object(entries.into_iter().collect()))
+        .boxed()
+}
+
+// Any JSON value, mixing arrays, numbers and nulls into the objects.
+fn mixed_tree(depth: usize) -> BoxedStrategy<Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<i64>().prop_map(Value::from),
+        any::<bool>().prop_map(Value::Bool),
+        text(VALUE_ALPHABET, 6).prop_map(Value::String),
+    ];
+    if depth >= 4 {
+        return leaf.boxed();
+    }
+    prop_oneof![
+        4 => leaf,
+        1 => vec(mixed_tree(depth + 1), 0..4).prop_map(Value::Array),
+        2 => vec((key(false), mixed_tree(depth + 1)), 0..5)
+            .prop_map(|entries| Value::Object(entries.into_iter().collect())),
+    ]
+    .boxed()
+}
+
+fn flatten(value: &Value) -> HashMap<String, String> {
+    let output = Flattened::default();
+    flatten_json(value, String::new(), &output);
+    output
+        .leaves
+        .into_iter()
+        .filter_map(|(key, leaf)| match leaf {
+            Value::String(text) => Some((key, text)),
+            _ => None,
+        })
+        .collect()
+}
+
+// What flattening should produce, written as plainly as possible: the
+// leaves and the array lengths.
+fn reference_flatten(
+    value: &Value,
+    path: Option<String>,
+    output: &mut (HashMap<String, Value>, HashMap<String, usize>),
+) {
+    let child = |key: &str| match &path {
+        None => key.to_string(),
+        Some(path) => format!("{}.{}", path, key),
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                reference_flatten(value, Some(child(key)), output);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                reference_flatten(item, Some(child(&index.to_string())), output);
+            }
+            output.1.insert(path.unwrap_or_default(), items.len());
+        }
+        leaf => {
+            output.0.insert(path.unwrap_or_default(), leaf.clone());
+        }
+    }
+}
+
+fn string_leaves(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => map.values().map(string_leaves).sum(),
+        Value::String(_) => 1,
+        _ => 0,
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn flattening_keeps_every_string_leaf(tree in string_tree(0, false)) {
+        let flat = flatten(&tree);
+        prop_assert_eq!(flat.len(), string_leaves(&tree), "{}", tree);
+        prop_assert_eq!(nest_json(&flat), Ok(tree.clone()), "{}", tree);
+    }
+
+    #[test]
+    fn flatten_nest_flatten_is_identity(tree in string_tree(0, true)) {
+        let flat = flatten(&tree);
+        // Keys containing dots can make a value and a parent share a path,
+        // which nesting has to refuse rather than silently drop.
+        if let Ok(nested) = nest_json(&flat) {
+            prop_assert_eq!(flatten(&nested), flat, "{}", tree);
+        }
+    }
+
+    #[test]
+    fn flattening_mixed_documents_matches_the_reference(tree in mixed_tree(0)) {
+        let mut expected = (HashMap::new(), HashMap::new());
+        reference_flatten(&tree, None, &mut expected);
+        let output = Flattened::default();
+        flatten_json(&tree, String::new(), &output);
+        let flat = (
+            output.leaves.into_iter().collect(),
+            output.arrays.into_iter().collect(),
+        );
+        prop_assert_eq!(flat, expected, "{}", tree);
+    }
+}
+
+// Inputs built from the characters the parsers care about, so random text
+// reaches their interesting branches.
+const SOURCE_ALPHABET: &[&str] = &[
+    "t(",
+    "$t(",
+    "'",
+    "\"",
+    "`",
+    "${",
+    "}",
+    "{",
+    "{ ",
+    "name",
+    ":",
+    ",",
+    "...",
+    "[",
+    "]",
+    ")",
+    "\n",
+    "<template>",
+    "</template>",
+    "<i18n-t keypath=\"",
+    ":keypath=\"'",
+    ">",
+    "</i18n-t>",
+    "#",
+    "v-slot:",
+    ":plural",
+    "=",
+    " ",
+    "a.b",
+    "é",
+];
+
+fn assert_fast(input: &str, f: impl FnOnce()) {
+    let start = Instant::now();
+    f();
+    assert!(
+        start.elapsed() < Duration::from_secs(2),
+        "took {:?} on {:?}",
+        start.elapsed(),
+        input
+    );
+}
+
+const TOML_ALPHABET: &[&str] = &[
+    "[", "]", "[[", "]]", "a", ".", "=", " ", "\"", "'", "'''", "\"\"\"", "\\", "\\u00e9", "1",
+    "-", "_", "true", "{", "}", ",", "#", "\n", "é",
+];
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn placeholder_extraction_never_panics(text in text(VALUE_ALPHABET, 64)) {
+        assert_fast(&text, || {
+            for var in extract_variables(&text) {
+                assert!(text.contains(&format!("{{{}}}", var)));
+            }
+        });
+    }
+
+    #[test]
+    fn call_site_parsing_never_panics(source in text(SOURCE_ALPHABET, 200)) {
+        assert_fast(&source, || {
+            for path in ["a.ts", "a.vue"] {
+                find_call_sites(Path::new(path), &source);
+            }
+        });
+    }
+
+    #[test]
+    fn config_parsing_never_panics(source in text(TOML_ALPHABET, 80)) {
+        assert_fast(&source, || {
+            let _ = toml::parse(&source);
+        });
+    }
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::fs;
+
+const FRENCH: &str = r#"{
+  "address": "L\\'adresse de \u00e9quipe",
+  "today": "Aujourd&apos;hui",
+  "todayHtml": "<b>Aujourd&apos;hui</b>",
+  "confirm": "Cliquez sur “Valider",
+  "lines": "Ligne 1\\nLigne 2",
+  "hello": "« Bonjour",
+  "said": "Il a dit \\\"oui\\\""
+}
+"#;
+
+const PROJECT: [(&str, &str); 1] = [("i18n/fr/common.json", FRENCH)];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (String, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    assert_eq!(output.status.code(), Some(0));
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn escapes_entities_and_unpaired_quotes_are_reported() {
+    let (stdout, _) = check(&fixture("quote-escapes", &PROJECT), &[]);
+
+    assert!(
+        stdout.contains(
+            "💬 Escaped or unpaired quotes\n   - Key: address\n   - Found: \\' (backslash-escaped apostrophe)\n   - Fix: --fix quote-escapes\n   - Values:\n     FR │ L\\'adresse de équipe\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Key: today\n   - Found: &apos; (apostrophe entity outside HTML)\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Key: confirm\n   - Found: “ (unpaired smart quote)\n   - Values:\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Key: lines\n   - Found: \\n (literal \\n)\n   - Values:\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Key: hello\n   - Found: « (unpaired guillemet)\n"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("Key: todayHtml"), "{}", stdout);
+    assert!(
+        stdout.contains("✅ No translation errors found (6 warnings)."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn the_fix_only_rewrites_the_unambiguous_ones() {
+    let dir = fixture("quote-escapes-fix", &PROJECT);
+    let path = dir.path().join("i18n/fr/common.json");
+
+    let (_, stderr) = check(&dir, &["--fix", "quote-escapes", "--allow-base-writes"]);
+    assert!(
+        stderr.contains("Fixed quote escapes in 1 translation files"),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        FRENCH
+            .replace(r"L\\'adresse", "L'adresse")
+            .replace("Aujourd&apos;hui\",", "Aujourd'hui\",")
+            .replace(r#"\\\"oui\\\""#, r#"\"oui\""#)
+    );
+
+    let (stdout, _) = check(&dir, &[]);
+    let keys: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("   - Key: "))
+        .collect();
+    assert_eq!(keys, ["confirm", "hello", "lines"]);
+}
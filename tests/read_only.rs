@@ -0,0 +1,98 @@
+mod common;
+
+use common::{fixture, run};
+use serde_json::Value;
+use std::fs;
+use std::process::{Command, Output};
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/common.json",
+        "{ \"title\": \"Re\u{301}sume\u{301}\" }",
+    ),
+    ("i18n/de/common.json", "{ \"title\": \"Zusammenfassung\" }"),
+];
+
+fn run_with_env(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args(args)
+        .env("TRANSLATION_CHECK_READ_ONLY", "true")
+        .output()
+        .expect("failed to run binary")
+}
+
+#[test]
+fn write_paths_are_refused_before_any_analysis() {
+    let dir = fixture("read-only", &PROJECT);
+    let i18n = dir.path().join("i18n");
+    let i18n = i18n.to_str().unwrap();
+    let before = fs::read_to_string(dir.path().join("i18n/fr/common.json")).unwrap();
+
+    let output = run(&[i18n, "--read-only", "--fix", "nfc"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr);
+    assert!(
+        stderr.contains("`--fix` writes to the project and can't be used in read-only mode"),
+        "{}",
+        stderr
+    );
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/fr/common.json")).unwrap(),
+        before
+    );
+
+    let output = run_with_env(&[i18n, "--update-state"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`--update-state`"));
+
+    let baseline = dir.path().join("baseline.json");
+    let output = run_with_env(&[i18n, "--write-baseline", baseline.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`--write-baseline`"));
+    assert!(!baseline.exists());
+
+    let output = run_with_env(&[
+        "review",
+        "approve",
+        "--lang",
+        "de",
+        "--keys-from",
+        "k",
+        i18n,
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("`review approve`"));
+}
+
+#[test]
+fn read_only_runs_are_recorded() {
+    let dir = fixture("read-only-config", &PROJECT);
+    let i18n = dir.path().join("i18n");
+    let report = dir.path().join("report.json");
+
+    let output = run_with_env(&[i18n.to_str().unwrap(), "--print-config"]);
+    assert_eq!(output.status.code(), Some(0));
+    let config: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(config["read_only"], true);
+    // The schema cache moves out of the project.
+    let cache = config["schema_cache_dir"].as_str().unwrap();
+    assert!(
+        cache.starts_with(std::env::temp_dir().to_str().unwrap()),
+        "{}",
+        cache
+    );
+
+    let output = run(&[
+        i18n.to_str().unwrap(),
+        "--read-only",
+        "--only",
+        "consistency",
+        "--report",
+        &format!("json:{}", report.display()),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let report: Value = serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(report["config"]["read_only"], true);
+    assert_eq!(report["config"]["i18n_dir"], i18n.to_str().unwrap());
+}
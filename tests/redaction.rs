@@ -0,0 +1,99 @@
+mod common;
+
+use check_translations::redact::redact;
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/app.json",
+        r#"{
+            "legal": { "terms": "Conditions {company} secrètes" },
+            "title": "Bonjour {name}",
+            "refused": "Refusée par {name}."
+        }"#,
+    ),
+    (
+        "i18n/de/app.json",
+        r#"{
+            "legal": { "terms": "Geheime Bedingungen" },
+            "title": "Hallo",
+            "refused": "{name} hat abgelehnt."
+        }"#,
+    ),
+    ("translation-check.toml", "[placeholder_position]\n"),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> String {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn values_are_replaced_by_a_stable_hash_with_their_placeholders() {
+    assert_eq!(
+        redact("Bonjour {name}", false),
+        redact("Bonjour {name}", false)
+    );
+    assert!(redact("Bonjour {name}", false).ends_with(", 14 chars, {name}]"));
+    assert!(redact("Bonjour", true).ends_with(", 7+ chars]"));
+
+    let dir = fixture("redaction", &PROJECT);
+    for format in ["human", "json", "plain", "junit"] {
+        let stdout = check(&dir, &["--redact-values", "--format", format]);
+        for value in ["secrètes", "Bonjour", "Hallo", "Refusée", "abgelehnt"] {
+            assert!(!stdout.contains(value), "{}: {}", format, stdout);
+        }
+    }
+
+    let stdout = check(&dir, &["--redact-values"]);
+    assert!(
+        stdout.contains(&format!(
+            "     FR │ {}\n     DE │ {}\n",
+            redact("Bonjour {name}", false),
+            redact("Hallo", false)
+        )),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!(
+            "   - Value (DE): {}\n",
+            redact("{name} hat abgelehnt.", false)
+        )),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn key_subtrees_are_redacted_and_recorded_in_the_config() {
+    let dir = fixture("redaction-keys", &PROJECT);
+    let stdout = check(&dir, &["--redact-keys", "legal", "--html", "report.html"]);
+    assert!(!stdout.contains("secrètes"), "{}", stdout);
+    assert!(stdout.contains("Bonjour {name}"), "{}", stdout);
+    let html = std::fs::read_to_string(dir.path().join("report.html")).unwrap();
+    assert!(!html.contains("secr"), "{}", html);
+    assert!(html.contains("Bonjour"), "{}", html);
+
+    let report: Value = serde_json::from_str(&check(
+        &dir,
+        &["--redact-keys", "legal", "--format", "json"],
+    ))
+    .unwrap();
+    assert_eq!(
+        report["config"]["redaction"],
+        serde_json::json!({ "values": false, "keys": ["legal"] })
+    );
+}
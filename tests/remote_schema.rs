@@ -0,0 +1,282 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+const SCHEMA: &str = r#"{ "base_lang": "fr", "keys": { "hello": ["name"], "bye": [] } }"#;
+const SCHEMA_SHA256: &str = "da4ac4a159002baf15405b57ddde06a5a3f3ae597738b4b306d27a377720be14";
+
+// Answers one request per entry of `responses`, in order, and returns the
+// requests it received.
+fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            requests.push(String::from_utf8(request).unwrap());
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
+
+fn ok(body: &str, etag: Option<&str>) -> String {
+    let etag = etag.map_or(String::new(), |etag| format!("ETag: {}\r\n", etag));
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n{}\r\n{}",
+        body.len(),
+        etag,
+        body
+    )
+}
+
+const PROJECT: [(&str, &str); 1] = [(
+    "i18n/de/common.json",
+    r#"{ "hello": "Hallo {vorname}", "extra": "Extra" }"#,
+)];
+
+fn check(dir: &TempDir, url: &str, extra: &[&str]) -> std::process::Output {
+    let i18n = dir.path().join("i18n");
+    let cache = dir.path().join("cache");
+    let mut args = vec![
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--schema-url",
+        url,
+        "--schema-cache-dir",
+        cache.to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra);
+    run(&args)
+}
+
+#[test]
+fn compares_languages_against_the_downloaded_schema() {
+    let dir = fixture("remote-schema", &PROJECT);
+    let (url, server) = serve(vec![
+        ok(SCHEMA, Some("\"v1\"")),
+        ok(&format!("{}  schema.json\n", SCHEMA_SHA256), None),
+    ]);
+
+    let output = check(&dir, &format!("{}/schema.json", url), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("❌ Missing keys:\n   - Key: bye"));
+    assert!(stdout.contains("⚠️ Extra keys:\n   - Key: extra"));
+    assert!(stdout.contains("   - Expected variables (FR): {\"name\"}"));
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /schema.json HTTP/1.1"));
+    assert!(requests[1].starts_with("GET /schema.json.sha256 HTTP/1.1"));
+    assert!(dir.path().join("cache").read_dir().unwrap().count() == 2);
+}
+
+#[test]
+fn revalidates_the_cached_schema_with_its_etag() {
+    let dir = fixture("remote-schema-etag", &PROJECT);
+    let (url, server) = serve(vec![
+        ok(SCHEMA, Some("\"v1\"")),
+        ok(SCHEMA_SHA256, None),
+        "HTTP/1.1 304 Not Modified\r\n\r\n".to_string(),
+    ]);
+    let url = format!("{}/schema.json", url);
+
+    check(&dir, &url, &[]);
+    let output = check(&dir, &url, &["--require-fresh-schema"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Missing keys"));
+
+    let requests = server.join().unwrap();
+    // Header names are case-insensitive.
+    assert!(
+        requests[2]
+            .to_lowercase()
+            .contains("if-none-match: \"v1\"\r\n")
+    );
+}
+
+#[test]
+fn refuses_to_send_the_token_over_plain_http() {
+    let dir = fixture("remote-schema-token", &PROJECT);
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_check_translations"))
+        .args([
+            dir.path().join("i18n").to_str().unwrap(),
+            "--only",
+            "consistency",
+            "--schema-url",
+            "http://127.0.0.1:9/schema.json",
+            "--schema-cache-dir",
+            dir.path().join("cache").to_str().unwrap(),
+            "--schema-token-env",
+            "SCHEMA_TOKEN",
+        ])
+        .env("SCHEMA_TOKEN", "secret")
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("refusing to send credentials to http://127.0.0.1:9/schema.json"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn follows_redirects_of_the_same_scheme() {
+    let dir = fixture("remote-schema-redirect", &PROJECT);
+    let (url, server) = serve(vec![
+        "HTTP/1.1 302 Found\r\nLocation: /v2/schema.json\r\nContent-Length: 0\r\n\r\n".to_string(),
+        ok(SCHEMA, None),
+        ok(SCHEMA_SHA256, None),
+    ]);
+
+    let output = check(&dir, &format!("{}/schema.json", url), &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Missing keys"));
+
+    let requests = server.join().unwrap();
+    assert!(requests[1].starts_with("GET /v2/schema.json "));
+    assert!(requests[2].starts_with("GET /schema.json.sha256 "));
+}
+
+#[test]
+fn refuses_redirects_to_another_scheme() {
+    let dir = fixture("remote-schema-downgrade", &PROJECT);
+    let (url, server) = serve(vec![
+        "HTTP/1.1 301 Moved Permanently\r\nLocation: https://localhost/schema.json\r\nContent-Length: 0\r\n\r\n".to_string(),
+    ]);
+
+    let output = check(&dir, &format!("{}/schema.json", url), &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("which uses another scheme"), "{}", stderr);
+    server.join().unwrap();
+}
+
+#[test]
+fn falls_back_to_the_cache_when_the_server_is_unreachable() {
+    let dir = fixture("remote-schema-offline", &PROJECT);
+    let (url, server) = serve(vec![ok(SCHEMA, None), ok(SCHEMA_SHA256, None)]);
+    let url = format!("{}/schema.json", url);
+    check(&dir, &url, &[]);
+    server.join().unwrap();
+
+    let output = check(&dir, &url, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("using the cached copy"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Missing keys"));
+
+    let output = check(&dir, &url, &["--require-fresh-schema"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--require-fresh-schema"));
+}
+
+#[test]
+fn rejects_a_schema_with_the_wrong_checksum() {
+    let dir = fixture("remote-schema-checksum", &PROJECT);
+    let (url, server) = serve(vec![ok(SCHEMA, None), ok(&"0".repeat(64), None)]);
+
+    let output = check(&dir, &format!("{}/schema.json", url), &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("checksum mismatch"), "{}", stderr);
+    assert!(stderr.contains("no cached copy exists"));
+    assert!(!dir.path().join("cache").exists());
+    server.join().unwrap();
+}
+
+#[test]
+fn refuses_responses_over_the_size_limit() {
+    let dir = fixture("remote-schema-oversized", &PROJECT);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/schema.json", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..read]);
+        }
+        let size = 33 * 1024 * 1024;
+        let header = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", size);
+        // The client hangs up once it has read past the limit.
+        let _ = stream
+            .write_all(header.as_bytes())
+            .and_then(|_| stream.write_all(&vec![b' '; size]));
+    });
+
+    let output = check(&dir, &url, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("is larger than 33554432 bytes"),
+        "{}",
+        stderr
+    );
+    server.join().unwrap();
+}
+
+#[test]
+fn checks_the_certificate_of_https_servers() {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use rustls::{ServerConfig, ServerConnection};
+    use std::sync::Arc;
+
+    let dir = fixture("remote-schema-https", &PROJECT);
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+    let cert = CertificateDer::from_pem_file(fixtures.join("cert.pem")).unwrap();
+    let key = PrivateKeyDer::from_pem_file(fixtures.join("key.pem")).unwrap();
+    let config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert], key)
+            .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "https://localhost:{}/schema.json",
+        listener.local_addr().unwrap().port()
+    );
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut connection = ServerConnection::new(Arc::new(config)).unwrap();
+        while connection.is_handshaking() {
+            if connection.complete_io(&mut stream).is_err() {
+                break;
+            }
+        }
+    });
+
+    let output = check(&dir, &url, &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The self-signed certificate is not issued by a trusted root.
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("invalid peer certificate: UnknownIssuer"),
+        "{}",
+        stderr
+    );
+    server.join().unwrap();
+}
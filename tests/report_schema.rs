@@ -0,0 +1,254 @@
+mod common;
+
+use check_translations::partition::{Fragment, Partition};
+use check_translations::render::Renderer;
+use check_translations::render::json::{self, JsonRenderer, SCHEMA_VERSION};
+use check_translations::report::{Check, Coverage, Excerpt, Finding, Report};
+use common::{TempDir, assert_snapshot, run};
+use regex::Regex;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::fs;
+
+// A report setting every field the JSON renderer writes.
+fn populated() -> Report {
+    let mut finding = Finding::new(Check::VariableMismatch)
+        .lang("de")
+        .key("greeting")
+        .file("i18n/de/common.json")
+        .line(3)
+        .detail("Expected variables (FR)", "{\"name\"}")
+        .value(Excerpt::new("FR", "Bonjour {name}", vec![0..7, 8..14], 80));
+    finding.message = Some("Variables differ".to_string());
+    finding.owners = Some(vec!["@team-de".to_string()]);
+    finding.suggested_fix = Some(json!({
+        "file": "i18n/de/common.json",
+        "patch": [{ "op": "replace", "path": "/greeting", "value": "Hallo {name}" }],
+    }));
+    finding.values.push(Excerpt {
+        label: "DE".to_string(),
+        text: "Hallo {vorname}".to_string(),
+        truncated: true,
+        highlights: vec![0..5, 6..15],
+    });
+    let mut rolled = Finding::new(Check::MissingKey).key("checkout.total");
+    rolled.members = vec![
+        Finding::new(Check::MissingKey)
+            .lang("it")
+            .key("checkout.total"),
+    ];
+
+    Report {
+        findings: vec![finding, rolled],
+        waived: Some(1),
+        base_only: Some(2),
+        config: Some(json!({ "base_lang": "fr" })),
+        fragment: Some(Fragment {
+            partition: Partition { index: 1, count: 2 },
+            keys: 3,
+            total_keys: 5,
+        }),
+        coverage: vec![Coverage {
+            lang: "de".to_string(),
+            total: 3,
+            translated: 2,
+            missing: 1,
+            extra: 0,
+            mismatches: 1,
+            folders: vec!["de".to_string(), "de-DE".to_string()],
+        }],
+        ..Report::default()
+    }
+}
+
+// Validates `value` against the subset of JSON Schema `json::schema()`
+// uses, recording the properties it saw under their schema path.
+fn validate(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    seen: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.strip_prefix("#/$defs/").unwrap();
+        return validate(root, &root["$defs"][name], value, path, seen);
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::String(name) => vec![name],
+            _ => types
+                .as_array()
+                .unwrap()
+                .iter()
+                .flat_map(Value::as_str)
+                .collect(),
+        };
+        let matches = types.iter().any(|name| match *name {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            other => panic!("unsupported type {}", other),
+        });
+        if !matches {
+            return Err(format!("{}: {} is not {:?}", path, value, types));
+        }
+    }
+    if let Some(allowed) = schema["enum"].as_array()
+        && !allowed.contains(value)
+    {
+        return Err(format!("{}: {} is not one of {:?}", path, value, allowed));
+    }
+    if let (Some(pattern), Some(text)) = (schema["pattern"].as_str(), value.as_str())
+        && !Regex::new(pattern).unwrap().is_match(text)
+    {
+        return Err(format!("{}: {} doesn't match {}", path, text, pattern));
+    }
+    if let Some(number) = value.as_f64() {
+        if schema["minimum"]
+            .as_f64()
+            .is_some_and(|minimum| number < minimum)
+        {
+            return Err(format!("{}: {} is below the minimum", path, number));
+        }
+        if schema["maximum"]
+            .as_f64()
+            .is_some_and(|maximum| number > maximum)
+        {
+            return Err(format!("{}: {} is above the maximum", path, number));
+        }
+    }
+    if let Some(object) = value.as_object() {
+        for required in schema["required"].as_array().into_iter().flatten() {
+            if !object.contains_key(required.as_str().unwrap()) {
+                return Err(format!("{}: missing {}", path, required));
+            }
+        }
+        let properties = schema["properties"].as_object();
+        for (name, field) in object {
+            match properties.and_then(|properties| properties.get(name)) {
+                Some(property) => {
+                    seen.insert(format!("{}.{}", path, name));
+                    validate(root, property, field, &format!("{}.{}", path, name), seen)?;
+                }
+                None if schema["additionalProperties"] == false => {
+                    return Err(format!("{}: unexpected {}", path, name));
+                }
+                None => {}
+            }
+        }
+    }
+    if let (Some(items), Some(elements)) = (schema.get("items"), value.as_array()) {
+        for element in elements {
+            validate(root, items, element, &format!("{}[]", path), seen)?;
+        }
+    }
+    Ok(())
+}
+
+fn render(report: &Report) -> Value {
+    let mut out = Vec::new();
+    JsonRenderer::new(&mut out).render(report).unwrap();
+    serde_json::from_slice(&out).unwrap()
+}
+
+#[test]
+fn a_populated_report_follows_the_schema() {
+    let schema = json::schema();
+    let document = render(&populated());
+    assert_eq!(document["schema_version"], SCHEMA_VERSION);
+    let mut seen = BTreeSet::new();
+    validate(&schema, &schema, &document, "$", &mut seen).unwrap();
+
+    // The optional fields were written and checked too.
+    for field in [
+        "$.partition",
+        "$.config",
+        "$.summary.waived",
+        "$.summary.base_only",
+        "$.coverage[].variable_mismatches",
+        "$.coverage[].folders",
+        "$.findings[].owners",
+        "$.findings[].suggested_fix.patch",
+        "$.findings[].values[].highlights[].end",
+        "$.findings[].grouped_members[].values",
+        "$.missing_keys[].lang",
+        "$.variable_mismatches[].file",
+    ] {
+        assert!(seen.contains(field), "{} not written: {:?}", field, seen);
+    }
+    // Roll-ups are listed by language.
+    assert_eq!(
+        document["missing_keys"],
+        json!([{ "key": "checkout.total", "lang": "it", "file": null }])
+    );
+    assert_eq!(
+        document["variable_mismatches"],
+        json!([{ "key": "greeting", "lang": "de", "file": "i18n/de/common.json" }])
+    );
+    assert_eq!(document["unused_keys"], json!([]));
+
+    let mut broken = document.clone();
+    broken["findings"][0]["check"] = json!("no_such_check");
+    assert!(validate(&schema, &schema, &broken, "$", &mut seen).is_err());
+    broken = document;
+    broken["schema_version"] = json!("2.0.0");
+    assert!(validate(&schema, &schema, &broken, "$", &mut seen).is_err());
+}
+
+// The schema is reviewed with every change to it; a change other than a new
+// optional field must bump the major version of `SCHEMA_VERSION`.
+#[test]
+fn schema_report_writes_the_schema() {
+    let dir = TempDir::new("schema-report");
+    let path = dir.path().join("report.schema.json");
+    let output = run(&["schema-report", "--output", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    let written = fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(&written).unwrap(),
+        json::schema()
+    );
+    assert_snapshot("report_schema", &written);
+
+    let output = run(&["schema-report"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), written);
+}
+
+#[test]
+fn merging_refuses_another_major_version() {
+    let dir = TempDir::new("schema-report-merge");
+    let mut report = populated();
+    report.fragment = Some(Fragment {
+        partition: Partition { index: 1, count: 1 },
+        keys: 3,
+        total_keys: 3,
+    });
+    let mut document = render(&report);
+    document["schema_version"] = json!("2.1.0");
+    let path = dir.write("part-1.json", &document.to_string());
+
+    let output = run(&["merge-reports", path.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("report schema version 2.1.0 is not supported (expected 1.x)"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Reports written before the version was recorded are read as version 1.
+    document.as_object_mut().unwrap().remove("schema_version");
+    dir.write("part-1.json", &document.to_string());
+    let output = run(&["merge-reports", path.to_str().unwrap()]);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
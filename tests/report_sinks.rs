@@ -0,0 +1,238 @@
+mod common;
+
+use common::{fixture, run};
+use regex::Regex;
+use serde_json::Value;
+use std::fs;
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {name}", "save": "Enregistrer", "legacy": "Ancien", "Title": "Titre" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo", "extra": "Extra", "TITLE": "Titel" }"#,
+    ),
+    ("src/app.ts", "t('title'); t('save'); t('Title');\n"),
+];
+
+fn number(text: &str, pattern: &str) -> usize {
+    Regex::new(pattern).unwrap().captures(text).unwrap()[1]
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn every_sink_renders_the_same_report() {
+    let dir = fixture("report-sinks", &PROJECT);
+    let junit = dir.path().join("report.xml");
+    let json = dir.path().join("report.json");
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--src-dir",
+        dir.path().join("src").to_str().unwrap(),
+        "--report",
+        &format!("junit:{}", junit.display()),
+        "--report",
+        &format!("json:{}", json.display()),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+
+    let json: Value = serde_json::from_str(&fs::read_to_string(&json).unwrap()).unwrap();
+    let findings = json["findings"].as_array().unwrap();
+    let errors = findings
+        .iter()
+        .filter(|finding| finding["severity"] == "error")
+        .count();
+    let junit = fs::read_to_string(&junit).unwrap();
+
+    assert!(findings.len() > 3);
+    assert_eq!(
+        number(&junit, r#"<testsuites [^>]*tests="(\d+)""#),
+        findings.len()
+    );
+    assert_eq!(junit.matches("<testcase ").count(), findings.len());
+    assert_eq!(
+        number(&junit, r#"<testsuites [^>]*failures="(\d+)""#),
+        errors
+    );
+    assert_eq!(junit.matches("<failure ").count(), errors);
+    assert_eq!(
+        number(&stdout, r"Translation issues found: (\d+) errors"),
+        json["summary"]["errors"].as_u64().unwrap() as usize
+    );
+    assert_eq!(
+        number(&stdout, r"(\d+) warnings"),
+        json["summary"]["warnings"].as_u64().unwrap() as usize
+    );
+    assert_eq!(
+        number(&stdout, r"Unused keys: (\d+)"),
+        findings
+            .iter()
+            .filter(|finding| finding["check"] == "unused_key")
+            .count()
+    );
+    assert_eq!(
+        fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|entry| entry
+                .as_ref()
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .ends_with(".tmp"))
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn a_report_can_take_over_stdout() {
+    let dir = fixture("report-stdout", &PROJECT);
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--report",
+        "json:-",
+    ]);
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["summary"]["errors"].as_u64().unwrap() > 0);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn only_one_report_can_use_stdout() {
+    let dir = fixture("report-two-stdout", &PROJECT);
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--report",
+        "json:-",
+        "--report",
+        "junit:-",
+    ]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("only one `--report` can be written to stdout")
+    );
+}
+
+#[test]
+fn junit_suites_are_the_languages_by_default() {
+    let dir = fixture("report-junit-languages", &PROJECT);
+    let junit = |extra: &[&str]| {
+        let mut args = vec![
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "i18n",
+            "--format",
+            "junit",
+        ];
+        args.extend_from_slice(extra);
+        String::from_utf8_lossy(&run(&args).stdout).to_string()
+    };
+    let suites = |junit: &str| -> Vec<String> {
+        Regex::new(r#"<testsuite name="([^"]+)""#)
+            .unwrap()
+            .captures_iter(junit)
+            .map(|cap| cap[1].to_string())
+            .collect()
+    };
+
+    let by_language = junit(&[]);
+    let names = suites(&by_language);
+    assert_eq!(
+        names.first().map(String::as_str),
+        Some("de"),
+        "{}",
+        by_language
+    );
+    assert!(
+        names.windows(2).all(|pair| pair[0] < pair[1]),
+        "{}",
+        by_language
+    );
+    assert!(
+        by_language.contains(
+            r#"<testcase name="missing_key: save" classname="translation-check.missing_key""#
+        ),
+        "{}",
+        by_language
+    );
+    assert_eq!(junit(&["--junit-suites", "language"]), by_language);
+
+    let by_check = junit(&["--junit-suites", "check"]);
+    assert!(
+        suites(&by_check).contains(&"missing_key".to_string()),
+        "{}",
+        by_check
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--junit-suites",
+        "lang",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn the_json_report_lists_the_common_findings_by_key() {
+    let dir = fixture("report-sinks-listed", &PROJECT);
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--src-dir",
+        dir.path().join("src").to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let listed = |name: &str| -> Vec<String> {
+        json[name]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| format!("{} {} {}", entry["lang"], entry["key"], entry["file"]))
+            .collect()
+    };
+    let file = |lang: &str| {
+        format!(
+            "\"{}\"",
+            dir.path()
+                .join(format!("i18n/{}/common.json", lang))
+                .display()
+        )
+    };
+    assert_eq!(
+        listed("variable_mismatches"),
+        [format!("\"de\" \"title\" {}", file("de"))]
+    );
+    assert_eq!(
+        listed("extra_keys"),
+        [
+            format!("\"de\" \"TITLE\" {}", file("de")),
+            format!("\"de\" \"extra\" {}", file("de")),
+        ]
+    );
+    assert!(
+        listed("missing_keys")
+            .iter()
+            .any(|entry| entry.starts_with("\"de\" \"save\""))
+    );
+    assert!(
+        listed("unused_keys")
+            .iter()
+            .any(|entry| entry.contains("\"legacy\""))
+    );
+}
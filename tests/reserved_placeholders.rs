@@ -0,0 +1,76 @@
+mod common;
+
+use common::{TempDir, run};
+
+const CONFIG: &str = r#"
+[reserved_placeholders]
+count = ["must-match-base", "numeric-only"]
+locale = "forbidden"
+"#;
+
+fn fixture(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/cart.json",
+        r#"{ "items": "{total} articles", "lang": "Langue : {locale}", "summary": "{count} produits" }"#,
+    );
+    dir.write(
+        "i18n/de/cart.json",
+        r#"{ "items": "{count} Artikel", "lang": "Sprache", "summary": "{count} Produkte" }"#,
+    );
+    dir.write(
+        "src/cart.ts",
+        "t('summary', { count: 'viele' });\nt('summary', { count: items.length });\n",
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "plain",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn reserved_names_are_checked_against_their_rules() {
+    let (code, stdout) = check(&fixture("reserved-placeholders", CONFIG));
+
+    assert_eq!(code, Some(1));
+    let reserved: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.contains("\treserved_placeholder\t"))
+        .collect();
+    assert_eq!(
+        reserved,
+        [
+            "ERROR\treserved_placeholder\t-\tsummary\tsrc/cart.ts:1\tsupplied a string literal; Placeholder: {count}; Rule: numeric-only",
+            "ERROR\treserved_placeholder\tde\titems\ti18n/de/cart.json:1:3\tnot used by the base (FR); Placeholder: {count}; Rule: must-match-base",
+            "ERROR\treserved_placeholder\tfr\tlang\ti18n/fr/cart.json:1:32\treserved by the formatter; Placeholder: {locale}; Rule: forbidden",
+        ],
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn nothing_is_reserved_by_default() {
+    let (_, stdout) = check(&fixture("reserved-placeholders-default", ""));
+    assert!(!stdout.contains("reserved_placeholder"), "{}", stdout);
+
+    let (code, _) = check(&fixture(
+        "reserved-placeholders-invalid",
+        "[reserved_placeholders]\ncount = \"plural\"\n",
+    ));
+    assert_eq!(code, Some(2));
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+
+#[test]
+fn approved_reviews_gate_only_the_required_languages() {
+    let dir = TempDir::new("review");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour", "save": "Enregistrer", "cancel": "Annuler" }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo", "save": "Speichern", "cancel": "Abbrechen" }"#,
+    );
+    let keys = dir.write("reviewed.txt", "# session 12\nsave\ntitle\n\nmissing\n");
+    let i18n = dir.path().join("i18n");
+
+    let output = run(&[
+        "review",
+        "approve",
+        "--lang",
+        "de",
+        "--keys-from",
+        keys.to_str().unwrap(),
+        "--reviewer",
+        "alice",
+        i18n.to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    assert!(stderr.contains("`missing` has no value in de, not approved"));
+    assert!(stderr.contains("Approved 2 keys in "));
+
+    let status = fs::read_to_string(dir.path().join("i18n/de/.review-status.json")).unwrap();
+    assert!(status.starts_with("{\n  \"save\": {\n    \"reviewer\": \"alice\",\n    \"status\": \"approved\",\n    \"value_hash\": \""));
+    assert!(status.find("\"save\"").unwrap() < status.find("\"title\"").unwrap());
+    assert!(!status.contains("cancel"));
+
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo!", "save": "Speichern", "cancel": "Abbrechen" }"#,
+    );
+    let output = run(&[
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--require-reviewed",
+        "de",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("📝 Translations without an approved review:\n   - Key: cancel | File: "),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains(
+        "📝 Translations modified since review:\n   - Key: title | Reviewer: alice | File: "
+    ));
+    assert!(stdout.contains("2 errors"), "{}", stdout);
+    assert!(!stdout.contains("Extra keys"), "{}", stdout);
+
+    // Without the gate the same findings are only warnings.
+    let output = run(&[i18n.to_str().unwrap(), "--only", "consistency"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("(2 warnings)"), "{}", stdout);
+}
+
+#[test]
+fn required_languages_without_reviews_are_entirely_unreviewed() {
+    let dir = TempDir::new("review-none");
+    dir.write("i18n/fr/common.json", r#"{ "title": "Bonjour" }"#);
+    dir.write("i18n/de/common.json", r#"{ "title": "Hallo" }"#);
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--require-reviewed",
+        "de,xx",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("lists `xx`, which has no language folder")
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--require-reviewed",
+        "de",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Translations without an approved review:\n   - Key: title"));
+}
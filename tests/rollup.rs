@@ -0,0 +1,159 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+use std::fs;
+
+const LANGUAGES: [&str; 6] = ["de", "es", "it", "nl", "pl", "pt"];
+
+// Every language lacks `b` and the first one also lacks `c`.
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "a": "Un", "b": "Deux", "c": "Trois" }"#,
+    );
+    for (index, lang) in LANGUAGES.iter().enumerate() {
+        let data = if index == 0 {
+            r#"{ "a": "One" }"#
+        } else {
+            r#"{ "a": "One", "c": "Three" }"#
+        };
+        dir.write(&format!("i18n/{}/common.json", lang), data);
+    }
+    dir
+}
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+fn read(path: &std::path::Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn a_key_missing_everywhere_is_one_row() {
+    let (code, stdout) = check(&fixture("rollup"), &[]);
+
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains("   - Key: b | In 6 languages: de, es, it, +3 more\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "🔍 Checking DE\n❌ Missing keys:\n   - Key: c | File: i18n/de/common.json\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Translation issues found: 7 errors, 0 warnings."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn the_rows_can_be_kept_apart() {
+    let dir = fixture("rollup-disabled");
+    let (_, rolled) = check(&dir, &[]);
+
+    let (code, stdout) = check(&dir, &["--no-rollup"]);
+    assert_eq!(code, Some(1));
+    assert_eq!(stdout.matches("   - Key: b |").count(), 6, "{}", stdout);
+    assert!(!stdout.contains("In 6 languages"), "{}", stdout);
+
+    let (_, stdout) = check(&dir, &["--rollup-threshold", "6"]);
+    assert_eq!(stdout.matches("   - Key: b |").count(), 6, "{}", stdout);
+
+    let (_, stdout) = check(&dir, &["--rollup-threshold", "5"]);
+    assert_eq!(stdout, rolled);
+}
+
+#[test]
+fn the_json_report_lists_the_members() {
+    let dir = fixture("rollup-json");
+    let report = dir.path().join("report.json");
+    let target = format!("json:{}", report.display());
+    check(&dir, &["--report", &target]);
+
+    let report = read(&report);
+    let findings = report["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 2);
+    let rolled = findings
+        .iter()
+        .find(|finding| finding["key"] == "b")
+        .unwrap();
+    assert_eq!(rolled["lang"], Value::Null);
+    assert_eq!(rolled["weight"], 6);
+    let langs: Vec<&str> = rolled["grouped_members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member["lang"].as_str().unwrap())
+        .collect();
+    assert_eq!(langs, LANGUAGES);
+
+    // The missing keys are listed language by language.
+    let listed: Vec<String> = report["missing_keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| format!("{}:{}", entry["lang"].as_str().unwrap(), entry["key"]))
+        .collect();
+    assert_eq!(
+        listed,
+        [
+            "de:\"b\"", "es:\"b\"", "it:\"b\"", "nl:\"b\"", "pl:\"b\"", "pt:\"b\"", "de:\"c\"",
+        ]
+    );
+}
+
+#[test]
+fn merged_reports_are_rolled_up_again() {
+    let dir = fixture("rollup-merge");
+    let mut parts = Vec::new();
+    for index in 1..=2 {
+        let part = dir.path().join(format!("part-{}.json", index));
+        let target = format!("json:{}", part.display());
+        let partition = format!("{}/2", index);
+        check(&dir, &["--partition", &partition, "--report", &target]);
+        parts.push(part.display().to_string());
+    }
+
+    let merged = dir.path().join("merged.json");
+    let mut args = vec!["merge-reports"];
+    args.extend(parts.iter().map(String::as_str));
+    let merged_path = merged.display().to_string();
+    args.extend(["--output", &merged_path]);
+    assert_eq!(run(&args).status.code(), Some(1));
+    let findings = read(&merged)["findings"].as_array().unwrap().clone();
+    assert_eq!(findings.len(), 2);
+
+    args.push("--no-rollup");
+    run(&args);
+    let findings = read(&merged)["findings"].as_array().unwrap().clone();
+    assert_eq!(findings.len(), 7);
+    assert!(
+        findings
+            .iter()
+            .all(|finding| finding["grouped_members"].is_null())
+    );
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "translation-check.toml",
+        r#"
+[routes]
+"pages/admin/**" = ["admin.json", "common.json"]
+"pages/shop/**" = ["shop.json", "common.json"]
+"components/**" = ["common.json"]
+"pages/*/shared/**" = ["admin.json", "shop.json", "common.json"]
+"#,
+    ),
+    ("i18n/fr/common.json", r#"{ "save": "Enregistrer" }"#),
+    ("i18n/fr/admin.json", r#"{ "admin": { "title": "Admin" } }"#),
+    (
+        "i18n/fr/shop.json",
+        r#"{ "shop": { "title": "Boutique" } }"#,
+    ),
+];
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn keys_must_be_defined_in_a_file_the_route_loads() {
+    let dir = fixture("routes", &PROJECT);
+    dir.write(
+        "src/pages/admin/Users.vue",
+        "t('admin.title')\nt('save')\n\nt('shop.title')\n",
+    );
+    dir.write("src/pages/shop/Cart.vue", "t('shop.title')\n");
+    dir.write("src/components/Button.vue", "t('save')\n");
+    dir.write("src/App.vue", "t('admin.title')\n");
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains(
+            "📦 Keys used by a route that doesn't load their file:\n   - Key: shop.title | Defined in: i18n/fr/shop.json | Loaded: admin.json, common.json | File: src/pages/admin/Users.vue:4\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert_eq!(stdout.matches("Defined in:").count(), 1, "{}", stdout);
+}
+
+#[test]
+fn shared_sources_only_use_the_files_every_route_loads() {
+    let dir = fixture("routes-shared", &PROJECT);
+    // Matches both `pages/admin/**` and `pages/*/shared/**`.
+    dir.write(
+        "src/pages/admin/shared/Header.vue",
+        "t('save')\nt('admin.title')\nt('shop.title')\n",
+    );
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1));
+    assert!(
+        stdout.contains("   - Key: shop.title | Defined in: i18n/fr/shop.json | Loaded: admin.json, common.json | File: src/pages/admin/shared/Header.vue:3\n"),
+        "{}",
+        stdout
+    );
+    assert_eq!(stdout.matches("Defined in:").count(), 1, "{}", stdout);
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+
+// Ten German translations to draw from, one with a translated placeholder.
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    let (mut fr, mut de) = (Vec::new(), Vec::new());
+    for index in 0..9 {
+        fr.push(format!("\"key{}\": \"Valeur {}\"", index, index));
+        de.push(format!("\"key{}\": \"Wert {}\"", index, index));
+    }
+    fr.push("\"total\": \"Total {amount}\"".to_string());
+    de.push("\"total\": \"Summe {betrag}\"".to_string());
+    dir.write("i18n/fr/common.json", &format!("{{ {} }}", fr.join(", ")));
+    dir.write("i18n/de/common.json", &format!("{{ {} }}", de.join(", ")));
+    dir
+}
+
+fn sample(dir: &TempDir, args: &[&str]) -> String {
+    let mut all = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "sample",
+        "--lang",
+        "de",
+    ];
+    all.extend_from_slice(args);
+    all.push("i18n");
+    let output = run(&all);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+// The keys of a CSV sample.
+fn keys(csv: &str) -> Vec<String> {
+    csv.lines()
+        .skip(1)
+        .map(|line| line.split(',').next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn a_seed_draws_the_same_sample() {
+    let dir = fixture("sample-seed");
+    let args = ["--count", "4", "--seed", "sprint-42", "--format", "csv"];
+    let first = sample(&dir, &args);
+    assert_eq!(first, sample(&dir, &args));
+    assert_eq!(keys(&first).len(), 4);
+
+    let others: Vec<Vec<String>> = ["sprint-43", "sprint-44", "sprint-45"]
+        .iter()
+        .map(|seed| {
+            keys(&sample(
+                &dir,
+                &["--count", "4", "--seed", seed, "--format", "csv"],
+            ))
+        })
+        .collect();
+    assert!(others.iter().any(|other| *other != keys(&first)));
+
+    // Every key drawn, with its values and findings.
+    let markdown = sample(&dir, &["--count", "10", "--seed", "sprint-42"]);
+    assert!(markdown.starts_with("# Review sample: DE\n\n10 of 10 keys, seed `sprint-42`.\n"));
+    assert!(
+        markdown.contains(
+            "- [ ] `total` in `i18n/de/common.json`\n  Base:\n  > Total {amount}\n  Translation:\n  > Summe {betrag}\n  Finding: translated_placeholder ("
+        ),
+        "{}",
+        markdown
+    );
+}
+
+#[test]
+fn keys_added_since_the_state_are_marked() {
+    let dir = fixture("sample-recent");
+    let known: Vec<String> = (0..9).map(|index| format!("\"key{}\"", index)).collect();
+    dir.write(
+        "translation-check.state.json",
+        &format!("{{ \"base_keys\": [{}] }}", known.join(", ")),
+    );
+    let csv = sample(&dir, &["--count", "10", "--seed", "s", "--format", "csv"]);
+    assert!(
+        csv.lines().any(|line| line
+            .starts_with("total,i18n/de/common.json,Total {amount},Summe {betrag},true,")),
+        "{}",
+        csv
+    );
+    assert_eq!(csv.matches(",true,").count(), 1, "{}", csv);
+}
+
+#[test]
+fn reviewed_keys_are_recorded_and_skipped() {
+    let dir = fixture("sample-reviewed");
+    sample(
+        &dir,
+        &[
+            "--count",
+            "3",
+            "--seed",
+            "a",
+            "--keys-output",
+            "sampled.txt",
+        ],
+    );
+    let sampled = fs::read_to_string(dir.path().join("sampled.txt")).unwrap();
+    let sampled: Vec<&str> = sampled
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    assert_eq!(sampled.len(), 3);
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "review",
+        "approve",
+        "--lang",
+        "de",
+        "--keys-from",
+        "sampled.txt",
+        "--reviewer",
+        "qa",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let rest = sample(
+        &dir,
+        &[
+            "--count",
+            "10",
+            "--seed",
+            "a",
+            "--exclude-reviewed",
+            "--format",
+            "csv",
+        ],
+    );
+    let rest = keys(&rest);
+    assert_eq!(rest.len(), 7);
+    assert!(sampled.iter().all(|key| !rest.contains(&key.to_string())));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+// German lacks `cart`, the whole checkout file, and leaves out the `{name}`
+// of `welcome`.
+#[test]
+fn findings_are_written_as_sarif_results() {
+    let dir = TempDir::new("sarif");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "cart": "Panier", "welcome": "Bienvenue {name}" }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "welcome": "Willkommen" }"#,
+    );
+    dir.write("i18n/fr/checkout.json", r#"{ "pay": "Payer" }"#);
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "sarif",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let sarif: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "translation-check");
+    let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect();
+    let results = run["results"].as_array().unwrap();
+    for result in results {
+        let index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(result["ruleId"], rules[index]);
+    }
+
+    let variable = results
+        .iter()
+        .find(|result| result["properties"]["key"] == "welcome")
+        .unwrap();
+    assert_eq!(variable["level"], "error");
+    assert_eq!(variable["properties"]["lang"], "de");
+    let location = &variable["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "i18n/de/common.json");
+    assert!(
+        variable["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("`welcome` in DE"),
+        "{}",
+        variable
+    );
+
+    // Missing keys point to the file they should be added to, created or
+    // not.
+    let missing = |key: &str| {
+        let result = results
+            .iter()
+            .find(|result| result["ruleId"] == "missing_key" && result["properties"]["key"] == key)
+            .unwrap();
+        result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"].clone()
+    };
+    assert_eq!(missing("cart"), "i18n/de/common.json");
+    assert_eq!(missing("pay"), "i18n/de/checkout.json");
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::{TempDir, run};
+
+#[test]
+fn base_placeholder_changes_are_grouped_into_one_finding() {
+    let dir = TempDir::new("schema");
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{ "items": "{count} articles pour {total}", "hello": "Bonjour {name}" }"#,
+    );
+    for lang in ["de", "es", "it"] {
+        dir.write(
+            &format!("i18n/{}/shop.json", lang),
+            r#"{ "items": "{total} items", "hello": "Hello {name}" }"#,
+        );
+    }
+    dir.write(
+        "i18n/it/shop.json",
+        r#"{ "items": "{total} items", "hello": "Ciao {nome}" }"#,
+    );
+    let i18n = dir.path().join("i18n");
+    let schema = dir.path().join("schema.json");
+
+    let output = run(&[
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--schema-snapshot",
+        schema.to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("Variable mismatch").count(), 3);
+    // `{nome}` is `{name}` translated.
+    assert_eq!(
+        stdout
+            .matches("Placeholders renamed by the translation")
+            .count(),
+        1
+    );
+    assert!(!schema.exists());
+
+    dir.write(
+        "schema.json",
+        r#"{ "base_lang": "fr", "keys": { "items": ["total"], "hello": ["name"] } }"#,
+    );
+    let output = run(&[
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--schema-snapshot",
+        schema.to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("Base placeholders changed for items; 3 languages need updating"));
+    assert!(stdout.contains("Languages: de, es, it"));
+    assert_eq!(stdout.matches("Variable mismatch").count(), 0);
+    assert!(stdout.contains("Renamed: {nome} should be {name}"));
+
+    // The JSON report lists the change once for every language it leaves
+    // behind, beside the renamed placeholder.
+    let output = run(&[
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--schema-snapshot",
+        schema.to_str().unwrap(),
+        "--format",
+        "json",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut listed: Vec<String> = report["variable_mismatches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| format!("{} {}", entry["lang"], entry["key"]))
+        .collect();
+    listed.sort();
+    assert_eq!(
+        listed,
+        [
+            "\"de\" \"items\"",
+            "\"es\" \"items\"",
+            "\"it\" \"hello\"",
+            "\"it\" \"items\"",
+        ]
+    );
+}
+
+#[test]
+fn update_writes_current_base_placeholders() {
+    let dir = TempDir::new("schema-write");
+    dir.write(
+        "i18n/fr/shop.json",
+        r#"{ "b": "{y} et {x}", "a": "plain" }"#,
+    );
+    let schema = dir.path().join("schema.json");
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--schema-snapshot",
+        schema.to_str().unwrap(),
+        "--update-schema-snapshot",
+    ]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(schema).unwrap(),
+        "{\n  \"base_lang\": \"fr\",\n  \"keys\": {\n    \"a\": [],\n    \"b\": [\n      \"x\",\n      \"y\"\n    ]\n  }\n}\n"
+    );
+}
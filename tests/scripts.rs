@@ -0,0 +1,74 @@
+mod common;
+
+use common::{TempDir, run};
+
+#[test]
+fn reports_values_written_in_another_script() {
+    let dir = TempDir::new("scripts");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "buy": "Acheter {product}", "title": "Bienvenue", "brand": "Acheter un iPhone" }"#,
+    );
+    dir.write(
+        "i18n/ru/common.json",
+        r#"{ "buy": "Купить {product}", "title": "Welcome", "brand": "Купить iPhone" }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "buy": "Kaufen {product}", "title": "Добро пожаловать", "brand": "iPhone kaufen" }"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🔤 Values in an unexpected script:\n   - Key: title | Script: Cyrillic (100%) | File: "
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("   - Key: title | Script: Latin (100%) | File: "));
+    assert!(!stdout.contains("Key: buy |"), "{}", stdout);
+    assert!(!stdout.contains("Key: brand |"), "{}", stdout);
+}
+
+#[test]
+fn expected_scripts_can_be_configured() {
+    let dir = TempDir::new("scripts-config");
+    dir.write("i18n/fr/common.json", r#"{ "title": "Bienvenue" }"#);
+    dir.write("i18n/sr/common.json", r#"{ "title": "Dobrodošli" }"#);
+    dir.write("i18n/xx/common.json", r#"{ "title": "Welcome" }"#);
+    dir.write(
+        "translation-check.toml",
+        r#"
+[scripts]
+threshold = 0.2
+
+[scripts.expected]
+sr = ["Cyrillic"]
+"#,
+    );
+
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("Key: title | Script: Latin (100%)"),
+        "{}",
+        stdout
+    );
+    assert_eq!(stdout.matches("Script: ").count(), 1, "{}", stdout);
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/checkout.json",
+        r#"{
+  "checkout": {
+    "address": "Adresse",
+    "shipping_address": "Adresse de livraison",
+    "billingAddress": "Adresse de facturation",
+    "step": "Étape suivante"
+  }
+}"#,
+    ),
+    (
+        "i18n/de/checkout.json",
+        r#"{
+  "checkout": {
+    "address": "Adresse",
+    "shipping_address": "Lieferadresse",
+    "billingAddress": "Rechnungsadresse"
+  }
+}"#,
+    ),
+];
+
+fn search(dir: &TempDir, args: &[&str]) -> (Option<i32>, Value) {
+    let mut all = vec!["--cwd", dir.path().to_str().unwrap(), "search"];
+    all.extend_from_slice(args);
+    all.extend(["i18n", "--format", "json"]);
+    let output = run(&all);
+    let matches = serde_json::from_slice(&output.stdout).unwrap_or(Value::Null);
+    (output.status.code(), matches)
+}
+
+// The matches as `key field kind highlighted`.
+fn matches(report: &Value) -> Vec<String> {
+    report["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|found| {
+            let text = match found["field"].as_str().unwrap() {
+                "key" => found["key"].as_str().unwrap(),
+                _ => found["value"].as_str().unwrap(),
+            };
+            let highlighted: Vec<&str> = found["highlights"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|range| {
+                    &text[range["start"].as_u64().unwrap() as usize
+                        ..range["end"].as_u64().unwrap() as usize]
+                })
+                .collect();
+            format!(
+                "{} {} {} {}",
+                found["key"].as_str().unwrap(),
+                found["field"].as_str().unwrap(),
+                found["kind"].as_str().unwrap(),
+                highlighted.join("+")
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn matches_are_ranked_from_the_closest() {
+    let dir = fixture("search-ranked", &PROJECT);
+    let (code, report) = search(&dir, &["address"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(
+        matches(&report),
+        [
+            "checkout.address key word address",
+            "checkout.shipping_address key word address",
+            "checkout.billingAddress key substring Address",
+        ]
+    );
+
+    let (_, report) = search(&dir, &["ADRESSE", "--in", "values"]);
+    assert_eq!(
+        matches(&report),
+        [
+            "checkout.address value exact Adresse",
+            "checkout.shipping_address value word Adresse",
+            "checkout.billingAddress value word Adresse",
+        ]
+    );
+    let (_, report) = search(&dir, &["étape", "--in", "values"]);
+    assert_eq!(matches(&report), ["checkout.step value word Étape"]);
+}
+
+#[test]
+fn values_of_another_language() {
+    let dir = fixture("search-lang", &PROJECT);
+    let (_, report) = search(&dir, &["adresse", "--in", "values", "--lang", "de"]);
+    assert_eq!(report["lang"], "de");
+    assert_eq!(
+        matches(&report),
+        [
+            "checkout.address value exact Adresse",
+            "checkout.shipping_address value substring adresse",
+            "checkout.billingAddress value substring adresse",
+        ]
+    );
+    assert_eq!(report["matches"][1]["file"], "i18n/de/checkout.json");
+}
+
+#[test]
+fn fuzzy_matches_allow_typos_and_gaps() {
+    let dir = fixture("search-fuzzy", &PROJECT);
+    let (code, _) = search(&dir, &["adresse de livriason", "--in", "values"]);
+    assert_eq!(code, Some(1));
+
+    let (_, report) = search(&dir, &["adresse de livriason", "--in", "values", "--fuzzy"]);
+    assert_eq!(
+        matches(&report),
+        ["checkout.shipping_address value edits Adresse de livraison"]
+    );
+    assert_eq!(report["matches"][0]["distance"], 2);
+
+    let (_, report) = search(&dir, &["ship addr", "--in", "keys", "--fuzzy"]);
+    assert_eq!(
+        matches(&report),
+        ["checkout.shipping_address key subsequence ship+addr"]
+    );
+}
+
+#[test]
+fn the_top_matches_and_a_scriptable_exit_code() {
+    let dir = fixture("search-limit", &PROJECT);
+    let (_, report) = search(&dir, &["adresse", "--limit", "1"]);
+    assert_eq!(report["total"], 3);
+    assert_eq!(report["matches"].as_array().unwrap().len(), 1);
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "search",
+        "nowhere",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Nothing matches \"nowhere\" in fr"));
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "search",
+        "livraison",
+        "i18n",
+        "--color",
+        "never",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "checkout.shipping_address │ fr │ Adresse de livraison │ i18n/fr/checkout.json\n"
+    );
+}
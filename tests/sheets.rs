@@ -0,0 +1,196 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+use std::path::Path;
+
+fn project() -> TempDir {
+    let dir = TempDir::new("sheets");
+    dir.write(
+        "i18n/fr/common.json",
+        "{\n  \"title\": \"Titre\",\n  \"cart\": {\n    \"empty\": \"Vide\",\n    \"note\": \"Remarque, \\\"libre\\\"\",\n    \"total\": \"Total {amount}\"\n  },\n  \"steps\": [\"Un\"]\n}\n",
+    );
+    dir.write(
+        "i18n/de/common.json",
+        "{\n  \"title\": \"\",\n  \"cart\": {\n    \"empty\": \"Leer\"\n  }\n}\n",
+    );
+    dir
+}
+
+fn translations(dir: &TempDir) -> String {
+    fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap()
+}
+
+const IMPORTED: &str = "{\n  \"title\": \"Titel & mehr\",\n  \"cart\": {\n    \"empty\": \"Leer\",\n    \"note\": \"Hinweis, \\\"frei\\\"\",\n    \"total\": \"Gesamt {amount}\"\n  }\n}\n";
+
+// Arrays are left to edit by hand.
+#[test]
+fn missing_and_empty_translations_are_exported() {
+    let dir = project();
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "export",
+        "--lang",
+        "de",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "key,fr,de,file\n\
+         cart.note,\"Remarque, \"\"libre\"\"\",,i18n/de/common.json\n\
+         cart.total,Total {amount},,i18n/de/common.json\n\
+         title,Titre,,i18n/de/common.json\n"
+    );
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "export",
+        "--lang",
+        "de",
+        "--output",
+        "de.xlsx",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let sheet = fs::read(dir.path().join("de.xlsx")).unwrap();
+    assert!(sheet.starts_with(b"PK\x03\x04"));
+    // An untouched sheet imports nothing.
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "import",
+        "de.xlsx",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("0 translations imported"));
+}
+
+#[test]
+fn filled_sheets_are_merged_into_the_language_files() {
+    let dir = project();
+    dir.write(
+        "de.csv",
+        "\u{feff}key;fr;de;file\r\n\
+         cart.note;\"Remarque, \"\"libre\"\"\";\"Hinweis, \"\"frei\"\"\";i18n/de/common.json\r\n\
+         cart.total;Total {amount};Gesamt {amount};i18n/de/common.json\r\n\
+         title;Titre;Titel & mehr;i18n/de/common.json\r\n\
+         cart.empty;Vide;;i18n/de/common.json\r\n\
+         legacy;;Alt;i18n/de/common.json\r\n\
+         steps.0;Un;Eins;i18n/de/common.json\r\n",
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "import",
+        "de.csv",
+        "i18n",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr);
+    assert!(
+        stderr.contains("Skipped `legacy`: not a string of the base"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Skipped `steps.0`: not a string of the base"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("3 translations imported"), "{}", stderr);
+    assert_eq!(translations(&dir), IMPORTED);
+
+    // Spreadsheet programs save shared strings, rich text and deflated
+    // entries.
+    let dir = project();
+    fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sheets/filled-de.xlsx"),
+        dir.path().join("de.xlsx"),
+    )
+    .unwrap();
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "import",
+        "de.xlsx",
+        "i18n",
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(translations(&dir), IMPORTED);
+}
+
+#[test]
+fn sheets_only_write_the_files_of_their_language() {
+    let dir = project();
+    dir.write(
+        "de.csv",
+        "key,fr,de,file\ncart.total,Total {amount},Gesamt,i18n/fr/common.json\n",
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "import",
+        "de.csv",
+        "i18n",
+    ]);
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("Skipped `cart.total`: i18n/fr/common.json isn't a DE translation file"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    dir.write("fr.csv", "key,de,fr,file\ntitle,Titel,Titre!,\n");
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "import",
+        "fr.csv",
+        "i18n",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("which `import` never writes"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn corrupt_or_oversized_workbooks_are_refused() {
+    for (fixture, error) in [
+        ("bad-crc.xlsx", "checksum"),
+        ("oversized.xlsx", "is larger than 67108864 bytes"),
+    ] {
+        let dir = project();
+        fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/sheets")
+                .join(fixture),
+            dir.path().join("de.xlsx"),
+        )
+        .unwrap();
+        let output = run(&[
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "import",
+            "de.xlsx",
+            "i18n",
+        ]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert_eq!(output.status.code(), Some(2), "{}", stderr);
+        assert!(stderr.contains("invalid sheet"), "{}", stderr);
+        assert!(stderr.contains(error), "{}", stderr);
+        assert_eq!(translations(&dir), translations(&project()));
+    }
+}
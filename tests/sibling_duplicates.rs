@@ -0,0 +1,82 @@
+mod common;
+
+use common::{TempDir, run};
+
+fn fixture(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/checkout.json",
+        r#"{ "checkout": {
+            "shipping": { "title": "Adresse de livraison" },
+            "billing": { "title": "Adresse de facturation" },
+            "actions": { "next": "Continuer", "confirm": "Continuer", "back": "Retour", "close": "Fermer" }
+        } }"#,
+    );
+    dir.write(
+        "i18n/de/checkout.json",
+        r#"{ "checkout": {
+            "shipping": { "title": "Lieferadresse" },
+            "billing": { "title": "Lieferadresse" },
+            "actions": { "next": "Weiter", "confirm": "Weiter", "back": "OK", "close": "OK" }
+        } }"#,
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> String {
+    let output = run(&[
+        dir.path().join("i18n").to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--config",
+        dir.path().join("translation-check.toml").to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn copy_pasted_sibling_values_are_reported() {
+    let dir = fixture("siblings", "[sibling_duplicates]\n");
+    let de = dir.path().join("i18n/de/checkout.json");
+
+    let stdout = check(&dir);
+
+    assert!(
+        stdout.contains(&format!(
+            "👯 Sibling keys sharing a translation\n   - Key: checkout.billing.title\n   - Sibling: checkout.shipping.title\n   - Base (FR) of checkout.billing.title: \"Adresse de facturation\"\n   - Base (FR) of checkout.shipping.title: \"Adresse de livraison\"\n   - Shared value (DE): \"Lieferadresse\"\n   - Files: {}\n",
+            de.display()
+        )),
+        "{}",
+        stdout
+    );
+    // `next` and `confirm` repeat the base, and "OK" is too short.
+    assert!(!stdout.contains("checkout.actions"), "{}", stdout);
+    assert!(
+        stdout.contains("✅ No translation errors found (1 warnings)."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn the_minimum_length_is_configurable() {
+    let stdout = check(&fixture(
+        "siblings-short",
+        "[sibling_duplicates]\nmin_length = 2\n",
+    ));
+    assert!(
+        stdout.contains("   - Key: checkout.actions.back\n   - Sibling: checkout.actions.close\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains("Key: checkout.actions.confirm"),
+        "{}",
+        stdout
+    );
+
+    let stdout = check(&fixture("siblings-off", ""));
+    assert!(!stdout.contains("👯"), "{}", stdout);
+}
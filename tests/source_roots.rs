@@ -0,0 +1,117 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+// Three apps of a monorepo sharing the translations, each using some keys.
+fn monorepo() -> TempDir {
+    let dir = TempDir::new("source-roots");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "web": "Web", "admin": "Admin", "shared": "Partagé", "template": "Gabarit", "unused": "Rien" }"#,
+    );
+    dir.write("apps/web/src/app.ts", "t('web');\n");
+    dir.write("apps/admin/src/app.ts", "t('admin');\n");
+    dir.write(
+        "apps/admin/src/page.html",
+        "<p>{{ 'template' | translate }}</p>\n",
+    );
+    dir.write("packages/shared/src/index.ts", "t('shared');\n");
+    dir
+}
+
+fn unused(report: &Value) -> Vec<&str> {
+    let mut keys: Vec<&str> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "unused_key")
+        .map(|finding| finding["key"].as_str().unwrap())
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn usages_are_aggregated_across_every_source_root() {
+    let dir = monorepo();
+    dir.write(
+        "translation-check.toml",
+        "[project]\nextensions = [\"ts\"]\n\n\
+         [[project.sources]]\ndir = \"apps/web/src\"\n\n\
+         [[project.sources]]\ndir = \"apps/admin/src\"\nextensions = [\"ts\", \"html\"]\n\n\
+         [[project.sources]]\ndir = \"packages/shared/src\"\n",
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(unused(&report), ["unused"]);
+    assert_eq!(
+        report["summary"]["source_roots"],
+        serde_json::json!(["apps/web/src", "apps/admin/src", "packages/shared/src"])
+    );
+    assert_eq!(
+        report["config"]["src_dirs"][1]["extensions"],
+        serde_json::json!(["ts", "html"])
+    );
+    assert_eq!(
+        report["config"]["src_dirs"][2]["extensions"],
+        serde_json::json!(["ts"])
+    );
+
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "Unused keys: 1 (searched apps/web/src, apps/admin/src, packages/shared/src)\n"
+        ),
+        "{}",
+        stdout
+    );
+
+    dir.write(
+        "translation-check.toml",
+        "[project]\nsrc_dir = \"src\"\n\n[[project.sources]]\ndir = \"apps/web/src\"\n",
+    );
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("can't both be set"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// `--src-dir` given several times replaces the configured roots.
+#[test]
+fn src_dir_may_be_given_once_per_root() {
+    let dir = monorepo();
+    dir.write(
+        "translation-check.toml",
+        "[[project.sources]]\ndir = \"packages/shared/src\"\n",
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "apps/web/src",
+        "--src-dir",
+        "apps/admin/src",
+        "--ext",
+        "ts",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(unused(&report), ["shared", "template", "unused"]);
+    assert_eq!(
+        report["summary"]["source_roots"],
+        serde_json::json!(["apps/web/src", "apps/admin/src"])
+    );
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+fn fixture() -> TempDir {
+    let dir = TempDir::new("stats");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "save": "Enregistrer", "legacy": "Ancien" }"#,
+    );
+    dir.write(
+        "i18n/fr/help.json",
+        r#"{ "help": "Aide", "dev": { "debug": "Déboguer" } }"#,
+    );
+    dir.write(
+        "translation-check.toml",
+        "base_only_namespaces = [\"dev.\"]\n",
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "save": " ", "extra": "Extra" }"#,
+    );
+    dir.write(
+        "i18n/es/common.json",
+        r#"{ "title": "Título", "save": "Guardar", "legacy": "Antiguo" }"#,
+    );
+    dir.write("i18n/es/help.json", r#"{ "help": "Ayuda" }"#);
+    dir
+}
+
+#[test]
+fn stats_count_translated_missing_and_empty_keys() {
+    let dir = fixture();
+    let cwd = dir.path().to_str().unwrap();
+
+    let output = run(&["--cwd", cwd, "stats", "i18n"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        [
+            "Translation stats against fr (4 keys)",
+            "Language  Translated  Missing  Empty  Completion",
+            "de                 1        2      1       25.0%",
+            "es                 4        0      0      100.0%",
+        ]
+    );
+
+    let output = run(&[
+        "--cwd",
+        cwd,
+        "stats",
+        "--per-file",
+        "--format=markdown",
+        "i18n",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.lines().take(5).collect::<Vec<_>>(),
+        [
+            "| Language | File | Translated | Missing | Empty | Completion |",
+            "| --- | --- | ---: | ---: | ---: | ---: |",
+            "| de | **all** | 1 | 2 | 1 | 25.0% |",
+            "| de | `common.json` | 1 | 1 | 1 | 33.3% |",
+            "| de | `help.json` | 0 | 1 | 0 | 0.0% |",
+        ]
+    );
+
+    let output = run(&[
+        "--cwd",
+        cwd,
+        "stats",
+        "--per-file",
+        "--format",
+        "json",
+        "i18n",
+    ]);
+    let stats: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["base_keys"], 4);
+    let de = &stats["languages"][0];
+    assert_eq!(de["lang"], "de");
+    assert_eq!(de["completion"], 25.0);
+    assert_eq!(de["files"][1]["file"], "help.json");
+    assert_eq!(de["files"][1]["missing"], 1);
+
+    let output = run(&["--cwd", cwd, "stats", "--format", "csv", "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+// `stats` reads the folder and file limits of the configuration file.
+#[test]
+fn stats_follow_the_project_configuration() {
+    let dir = TempDir::new("stats-config");
+    dir.write(
+        "locales/en/common.json",
+        r#"{ "title": "Title", "save": "Save" }"#,
+    );
+    dir.write("locales/en/help.json", r#"{ "help": "Help" }"#);
+    dir.write("locales/de/common.json", r#"{ "title": "Titel" }"#);
+    dir.write("locales/es/common.json", r#"{ "title": "Título" }"#);
+    // Fails to read like a file without permissions would.
+    std::fs::create_dir_all(dir.path().join("locales/es/help.json")).unwrap();
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"locales\"\n\n[languages]\nbase = \"en\"\n",
+    );
+    let cwd = dir.path().to_str().unwrap();
+
+    let output = run(&["--cwd", cwd, "stats", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stats: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["base_lang"], "en");
+    assert_eq!(stats["base_keys"], 3);
+    // Half the Spanish files are unreadable, over the default limit.
+    assert_eq!(stats["languages"].as_array().unwrap().len(), 1);
+    assert_eq!(stats["languages"][0]["lang"], "de");
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("es isn't counted: too many of its files are unreadable"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    dir.write(
+        "translation-check.toml",
+        "[project]\ni18n_dir = \"locales\"\n\n[languages]\nbase = \"en\"\n\n\
+         [unreadable_files]\nmax_fraction = 0.5\n",
+    );
+    let output = run(&["--cwd", cwd, "stats", "--format", "json"]);
+    let stats: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["languages"][1]["lang"], "es");
+    assert_eq!(stats["languages"][1]["missing"], 2);
+
+    let output = run(&[
+        "--cwd",
+        cwd,
+        "stats",
+        "--i18n-dir",
+        "locales",
+        "--base-lang",
+        "de",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("Translation stats against de (1 keys)\n"),
+        "{}",
+        stdout
+    );
+}
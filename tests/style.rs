@@ -0,0 +1,104 @@
+mod common;
+
+use common::{TempDir, run};
+
+const CONFIG: &str = r#"
+[style."buttons.*"]
+capitalization = "sentence"
+langs = ["en"]
+acronyms = ["PDF"]
+
+[style."buttons.*".patterns]
+fr = '^\p{L}+(er|ir|re)\b'
+"#;
+
+fn fixture(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "buttons": { "save": "Enregistrer", "export": "Exporter en PDF", "retry": "Réessayer {icon}", "close": "Fermé" } }"#,
+    );
+    dir.write(
+        "i18n/en/common.json",
+        r#"{ "buttons": { "save": "save Changes", "export": "PDF export", "retry": "{icon} Try again. Then wait", "close": "Close" } }"#,
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn values_straying_from_their_namespace_style_are_listed() {
+    let (code, stdout) = check(&fixture("style", CONFIG));
+
+    // Informational by default.
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "   - Key: buttons.save\n   - Namespace: buttons.*\n   - Expected: sentence case\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "   - Key: buttons.close\n   - Namespace: buttons.*\n   - Expected: matching `^\\p{L}+(er|ir|re)\\b`\n"
+        ),
+        "{}",
+        stdout
+    );
+    // An acronym may open a sentence, and a placeholder opening the value
+    // doesn't count as its first word.
+    assert!(!stdout.contains("buttons.export"), "{}", stdout);
+    assert!(!stdout.contains("buttons.retry"), "{}", stdout);
+    assert_eq!(
+        stdout.matches("Namespace: buttons.*").count(),
+        2,
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn the_severity_can_be_raised() {
+    let config = "[style.\"buttons.*\"]\ncapitalization = \"sentence\"\nlangs = [\"en\"]\nseverity = \"error\"\n";
+    let (code, stdout) = check(&fixture("style-severity", config));
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("Translation issues found: 1 errors, 0 warnings."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn invalid_styles_are_rejected() {
+    let dir = fixture(
+        "style-invalid",
+        "[style.\"buttons.*\"]\ncapitalization = \"camel\"\n",
+    );
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(
+            "invalid value `camel` for `style.\"buttons.*\".capitalization` (expected `sentence`, `title` or `lower`)"
+        )
+    );
+}
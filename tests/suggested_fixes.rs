@@ -0,0 +1,228 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use serde_json::{Value, json};
+use std::fs;
+
+// A JSON Patch (RFC 6902) applier for object documents, standing in for a
+// JSON Patch library as the report's consumers would use.
+fn apply(document: &mut Value, patch: &Value) {
+    fn tokens(pointer: &str) -> Vec<String> {
+        assert!(
+            pointer.is_empty() || pointer.starts_with('/'),
+            "{}",
+            pointer
+        );
+        pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect()
+    }
+    fn parent<'a>(
+        document: &'a mut Value,
+        tokens: &[String],
+    ) -> &'a mut serde_json::Map<String, Value> {
+        tokens[..tokens.len() - 1]
+            .iter()
+            .fold(document, |node, token| {
+                node.get_mut(token)
+                    .unwrap_or_else(|| panic!("no `{}` in the document", token))
+            })
+            .as_object_mut()
+            .expect("the parent of a path is an object")
+    }
+    fn add(document: &mut Value, path: &[String], value: Value) {
+        match path.last() {
+            None => *document = value,
+            Some(last) => {
+                parent(document, path).insert(last.clone(), value);
+            }
+        }
+    }
+    fn remove(document: &mut Value, path: &[String]) -> Value {
+        parent(document, path)
+            .remove(path.last().unwrap())
+            .expect("the removed path exists")
+    }
+
+    for operation in patch.as_array().unwrap() {
+        let path = tokens(operation["path"].as_str().unwrap());
+        match operation["op"].as_str().unwrap() {
+            "add" => add(document, &path, operation["value"].clone()),
+            "remove" => {
+                remove(document, &path);
+            }
+            "replace" => {
+                remove(document, &path);
+                add(document, &path, operation["value"].clone());
+            }
+            "move" => {
+                let value = remove(document, &tokens(operation["from"].as_str().unwrap()));
+                add(document, &path, value);
+            }
+            op => panic!("unexpected operation `{}`", op),
+        }
+    }
+}
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+  "café": { "menu": "Menu", "carte": "Carte" },
+  "title": "Titre",
+  "profile": { "name": "Nom", "edit/save": "Enregistrer" },
+  "unused": "Inutilisé"
+}
+"#,
+    ),
+    (
+        "i18n/de/common.json",
+        "{\n  \"cafe\u{301}\": { \"menu\": \"Me\u{301}nu\", \"carte\": \"Karte\" },\n  \"title\": \"Titel\",\n  \"profile\": { \"name\": \"Name\" },\n  \"unused\": \"Unbenutzt\",\n  \"legacy\": \"Alt\"\n}\n",
+    ),
+    (
+        "src/app.ts",
+        "t('café.menu'); t('café.carte'); t('title'); t('profile.name'); t('profile.edit/save');\n",
+    ),
+];
+
+fn suggested_fixes(dir: &TempDir) -> Vec<Value> {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--suggest-fixes",
+        "--report",
+        "json:report.json",
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("report.json")).unwrap()).unwrap();
+    report["findings"].as_array().unwrap().clone()
+}
+
+fn document(path: &std::path::Path) -> Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn nfc_patches_yield_the_document_fix_nfc_writes() {
+    let dir = fixture("suggested-fixes-nfc", &PROJECT);
+    let findings = suggested_fixes(&dir);
+    let path = dir.path().join("i18n/de/common.json");
+    let mut patched = document(&path);
+
+    let nfc: Vec<&Value> = findings
+        .iter()
+        .filter(|finding| finding["check"] == "not_nfc")
+        .collect();
+    assert_eq!(nfc.len(), 3);
+    for finding in &nfc {
+        let fix = &finding["suggested_fix"];
+        if fix.is_null() {
+            continue;
+        }
+        assert_eq!(fix["file"], "i18n/de/common.json");
+        apply(&mut patched, &fix["patch"]);
+    }
+    // Both keys live in the renamed object, so a single move fixes them.
+    let moves: Vec<&Value> = nfc
+        .iter()
+        .flat_map(|finding| finding["suggested_fix"]["patch"].as_array())
+        .flatten()
+        .filter(|operation| operation["op"] == "move")
+        .collect();
+    assert_eq!(
+        moves,
+        [&json!({ "op": "move", "from": "/cafe\u{301}", "path": "/caf\u{e9}" })]
+    );
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--fix",
+        "nfc",
+        "--only",
+        "consistency",
+    ]);
+    assert!(output.status.code().is_some(), "{:?}", output);
+    assert_eq!(patched, document(&path));
+}
+
+#[test]
+fn key_patches_scaffold_missing_keys_and_remove_unused_ones() {
+    let dir = fixture("suggested-fixes-keys", &PROJECT);
+    let findings = suggested_fixes(&dir);
+    let fixes = |check: &str| -> Vec<Value> {
+        findings
+            .iter()
+            .filter(|finding| finding["check"] == check)
+            .map(|finding| finding["suggested_fix"].clone())
+            .collect()
+    };
+
+    assert_eq!(
+        fixes("missing_key"),
+        [json!({
+            "file": "i18n/de/common.json",
+            "patch": [{ "op": "add", "path": "/profile/edit~1save", "value": "Enregistrer" }],
+        })]
+    );
+    assert_eq!(
+        fixes("extra_key"),
+        [json!({
+            "file": "i18n/de/common.json",
+            "patch": [{ "op": "remove", "path": "/legacy" }],
+        })]
+    );
+    assert_eq!(
+        fixes("unused_key"),
+        [json!({
+            "file": "i18n/fr/common.json",
+            "patch": [{ "op": "remove", "path": "/unused" }],
+        })]
+    );
+    // Only findings with a mechanical fix carry one.
+    assert!(
+        findings
+            .iter()
+            .filter(|finding| finding.get("suggested_fix").is_some())
+            .all(|finding| {
+                ["missing_key", "extra_key", "unused_key", "not_nfc"]
+                    .contains(&finding["check"].as_str().unwrap())
+            })
+    );
+
+    let mut patched = document(&dir.path().join("i18n/de/common.json"));
+    for fix in fixes("missing_key").iter().chain(&fixes("extra_key")) {
+        apply(&mut patched, &fix["patch"]);
+    }
+    assert_eq!(patched["profile"]["edit/save"], "Enregistrer");
+    assert!(patched.get("legacy").is_none());
+}
+
+#[test]
+fn requires_a_json_report() {
+    let dir = fixture("suggested-fixes-sink", &PROJECT);
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--suggest-fixes",
+    ]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("`--suggest-fixes` requires a `--report json:<path>`")
+    );
+}
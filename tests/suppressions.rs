@@ -0,0 +1,193 @@
+mod common;
+
+use check_translations::report::{Check, Finding, Severity};
+use check_translations::suppressions::Suppressions;
+use common::{TempDir, run};
+
+fn finding(check: Check, line: usize) -> Finding {
+    Finding::new(check).file("src/app.ts").line(line)
+}
+
+// The lines of `1..=max_line` whose findings of `check` are suppressed.
+fn suppressed(suppressions: &mut Suppressions, check: Check, max_line: usize) -> Vec<usize> {
+    let mut findings: Vec<Finding> = (1..=max_line).map(|line| finding(check, line)).collect();
+    suppressions.apply(&mut findings);
+    let kept: Vec<usize> = findings.iter().filter_map(|finding| finding.line).collect();
+    (1..=max_line).filter(|line| !kept.contains(line)).collect()
+}
+
+#[test]
+fn regions_nest_and_overlap() {
+    let source = "\
+// i18n-check-disable
+t('a');
+// i18n-check-disable-next-line
+t('b');
+// i18n-check-enable
+t('c');
+/* i18n-check-disable missing_interpolation */
+t('d');
+/* i18n-check-disable unverifiable_interpolation */
+t('e');
+/* i18n-check-enable missing_interpolation */
+t('f');
+/* i18n-check-enable unverifiable_interpolation */
+t('g');
+";
+    let mut suppressions = Suppressions::parse("src/app.ts", source);
+
+    assert_eq!(suppressions.problems, []);
+    assert_eq!(
+        suppressed(&mut suppressions, Check::MissingInterpolation, 14),
+        [2, 3, 4, 5, 8, 9, 10, 11]
+    );
+    assert_eq!(
+        suppressed(&mut suppressions, Check::UnverifiableInterpolation, 14),
+        [2, 3, 4, 5, 10, 11, 12, 13]
+    );
+    let hits: Vec<(usize, usize)> = suppressions
+        .directives
+        .iter()
+        .map(|directive| (directive.line, directive.hits))
+        .collect();
+    // The outer region takes the lines the inner directive covers too.
+    assert_eq!(hits, [(1, 8), (3, 0), (7, 4), (9, 4)]);
+}
+
+#[test]
+fn malformed_directives_are_reported() {
+    let source = "\
+// i18n-check-disable hardcoded-strings
+// i18n-check-disable-next-line unused_key
+// i18n-check-enable
+// i18n-check-disable
+// i18n-check-enable missing_interpolation
+t('a');
+";
+    let suppressions = Suppressions::parse("src/app.ts", source);
+
+    let problems: Vec<(usize, &str)> = suppressions
+        .problems
+        .iter()
+        .map(|problem| (problem.line, problem.message.as_str()))
+        .collect();
+    assert_eq!(
+        problems,
+        [
+            (
+                1,
+                "unknown check `hardcoded-strings` in `i18n-check-disable`"
+            ),
+            (2, "`unused_key` isn't reported on source lines"),
+            (3, "`i18n-check-enable` without a disabled region"),
+            (
+                5,
+                "`missing_interpolation` can't be enabled inside a region disabling every check"
+            ),
+            (4, "`i18n-check-disable` is never re-enabled"),
+        ]
+    );
+
+    let findings = suppressions.findings(Severity::Warning);
+    let severities: Vec<(Check, Severity)> = findings
+        .iter()
+        .map(|finding| (finding.check, finding.severity))
+        .collect();
+    assert_eq!(
+        severities[3..],
+        [
+            (Check::MalformedSuppression, Severity::Error),
+            (Check::MalformedSuppression, Severity::Warning),
+            (Check::UnusedSuppression, Severity::Info),
+        ]
+    );
+}
+
+fn fixture(name: &str, config: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    dir.write("i18n/fr/common.json", r#"{ "greeting": "Bonjour {name}" }"#);
+    dir.write(
+        "src/App.vue",
+        r#"<template>
+  <!-- i18n-check-disable-next-line missing_interpolation -->
+  <p>{{ $t('greeting') }}</p>
+  <p>{{ $t('greeting') }}</p>
+  <!-- i18n-check-disable-next-line -->
+  <p>{{ $t('greeting', { name }) }}</p>
+</template>
+
+<script setup>
+// i18n-check-disable
+const title = t('greeting');
+</script>
+"#,
+    );
+    dir.write("translation-check.toml", config);
+    dir
+}
+
+#[test]
+fn suppressed_call_sites_and_stale_comments_in_vue_templates() {
+    let dir = fixture("suppressions", "");
+    let app = dir.path().join("src/App.vue");
+    let args = |dir: &TempDir| {
+        run(&[
+            dir.path().join("i18n").to_str().unwrap(),
+            "--src-dir",
+            dir.path().join("src").to_str().unwrap(),
+            "--config",
+            dir.path().join("translation-check.toml").to_str().unwrap(),
+        ])
+    };
+
+    let output = args(&dir);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(
+        stdout.contains(&format!("{}:4", app.display())),
+        "{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains(&format!("{}:3", app.display())),
+        "{}",
+        stdout
+    );
+    assert!(
+        !stdout.contains(&format!("{}:11", app.display())),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(&format!(
+            "💤 Suppression comments matching no finding:\n   - Directive: i18n-check-disable-next-line | File: {}:5",
+            app.display()
+        )),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Problem: `i18n-check-disable` is never re-enabled"),
+        "{}",
+        stdout
+    );
+
+    // Left open, the region only warns when configured so.
+    let dir = fixture(
+        "suppressions-warning",
+        "[suppressions]\nunterminated = \"warning\"\n",
+    );
+    dir.write(
+        "src/App.vue",
+        "<script setup>\n// i18n-check-disable\nconst title = t('greeting');\n</script>\n",
+    );
+    let output = args(&dir);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(
+        stdout.contains("🚫 Malformed suppression comments"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,343 @@
+mod common;
+
+use check_translations::review;
+use common::{TempDir, fixture, run};
+use serde_json::Value;
+use std::fs;
+
+const GERMAN: &str = r#"{
+  "title": "Titel",
+  "legacy": {
+    "banner": "Alt"
+  }
+}
+"#;
+
+const PROJECT: [(&str, &str); 4] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+  "title": "Titre",
+  "checkout": {
+    "total": "Total",
+    "pay": "Payer {amount}"
+  },
+  "legacy": {
+    "banner": "Ancien"
+  }
+}
+"#,
+    ),
+    ("i18n/de/common.json", GERMAN),
+    ("i18n/it/common.json", r#"{ "title": "Titolo" }"#),
+    (
+        "src/app.ts",
+        "t('title'); t('checkout.total'); t('checkout.pay', { amount });\n",
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn missing_keys_are_added_and_unused_ones_removed() {
+    let dir = fixture("sync-keys", &PROJECT);
+    let read = |path: &str| fs::read_to_string(dir.path().join(path)).unwrap();
+
+    let (code, stderr) = check(&dir, &["--fix", "sync-keys", "--allow-base-writes"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert!(
+        stderr.contains("Synced keys in 3 translation files"),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        read("i18n/de/common.json"),
+        r#"{
+  "title": "Titel",
+  "checkout": {
+    "total": "[TODO] Total",
+    "pay": "[TODO] Payer {amount}"
+  }
+}
+"#
+    );
+    assert_eq!(
+        read("i18n/fr/common.json"),
+        r#"{
+  "title": "Titre",
+  "checkout": {
+    "total": "Total",
+    "pay": "Payer {amount}"
+  }
+}
+"#
+    );
+    assert_eq!(
+        read("i18n/it/common.json"),
+        r#"{ "title": "Titolo", "checkout": { "total": "[TODO] Total", "pay": "[TODO] Payer {amount}" } }"#
+    );
+
+    // Nothing is left to fix.
+    let (code, stderr) = check(&dir, &["--fix", "sync-keys"]);
+    assert_eq!(code, Some(0), "{}", stderr);
+    assert!(!stderr.contains("Synced keys"), "{}", stderr);
+}
+
+#[test]
+fn the_dry_run_prints_the_diff_only() {
+    let dir = fixture("sync-keys-dry-run", &PROJECT);
+
+    let (code, stderr) = check(
+        &dir,
+        &["--fix", "sync-keys", "--dry-run", "--allow-base-writes"],
+    );
+    assert_eq!(code, Some(1));
+    assert!(
+        stderr.contains(
+            "--- i18n/de/common.json\n+++ i18n/de/common.json\n@@ -3,2 +3,3 @@\n-  \"legacy\": {\n-    \"banner\": \"Alt\"\n+  \"checkout\": {\n+    \"total\": \"[TODO] Total\",\n+    \"pay\": \"[TODO] Payer {amount}\"\n"
+        ),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        GERMAN
+    );
+}
+
+// Every key would look unused: a source folder that is missing or holds
+// no source file is refused, and without one nothing is removed.
+#[test]
+fn keys_are_only_removed_once_sources_were_scanned() {
+    let dir = fixture("sync-keys-no-sources", &PROJECT);
+    let fix = |src_dir: &str| {
+        let output = run(&[
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "i18n",
+            "--src-dir",
+            src_dir,
+            "--fix",
+            "sync-keys",
+            "--allow-base-writes",
+        ]);
+        (
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )
+    };
+
+    let (code, stderr) = fix("nope");
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("source folder nope doesn't exist"),
+        "{}",
+        stderr
+    );
+
+    dir.write("assets/logo.svg", "<svg/>");
+    let (code, stderr) = fix("assets");
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("source folder assets holds no .ts, .js, .vue file"),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        GERMAN
+    );
+
+    // Without a source folder given, a project lacking `src` adds the
+    // missing keys and keeps the others.
+    fs::remove_dir_all(dir.path().join("src")).unwrap();
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--fix",
+        "sync-keys",
+        "--allow-base-writes",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let german = fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap();
+    assert!(german.contains("\"banner\": \"Alt\""), "{}", german);
+    assert!(german.contains("[TODO] Total"), "{}", german);
+    assert!(
+        fs::read_to_string(dir.path().join("i18n/fr/common.json"))
+            .unwrap()
+            .contains("Ancien")
+    );
+}
+
+#[test]
+fn the_fix_run_syncs_keys_with_the_configured_template() {
+    let dir = fixture("sync-keys-run", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[sync_keys]\ntemplate = \"⟦{value}⟧\"\n",
+    );
+
+    let output = run(&[
+        "fix",
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--dry-run",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(
+        stderr.contains(
+            "@@ -3,0 +3,4 @@\n+  \"checkout\": {\n+    \"total\": \"⟦Total⟧\",\n+    \"pay\": \"⟦Payer {amount}⟧\"\n+  },\n"
+        ),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        GERMAN
+    );
+}
+
+#[test]
+fn missing_keys_follow_the_order_of_the_base_file() {
+    let dir = TempDir::new("sync-keys-order");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{
+  "zeta": "Zêta",
+  "beta": "Bêta",
+  "nav": { "next": "Suivant", "back": "Retour", "home": "Accueil" },
+  "alpha": "Alpha"
+}
+"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{
+  "zeta": "Zeta",
+  "extra": "Extra",
+  "alpha": "Alpha",
+  "nav": { "next": "Weiter", "home": "Start" }
+}
+"#,
+    );
+
+    let (code, stderr) = check(&dir, &["--only", "consistency", "--fix", "sync-keys"]);
+    assert_eq!(code, Some(1), "{}", stderr);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("i18n/de/common.json")).unwrap(),
+        r#"{
+  "zeta": "Zeta",
+  "extra": "Extra",
+  "beta": "[TODO] Bêta",
+  "alpha": "Alpha",
+  "nav": { "next": "Weiter", "back": "[TODO] Retour", "home": "Start" }
+}
+"#
+    );
+}
+
+// Every key added records where its value comes from; one still holding
+// its scaffold once the base value changes is stale, and the record goes
+// once the key is translated.
+#[test]
+fn scaffolds_record_their_provenance_and_go_stale_with_the_base() {
+    let dir = fixture("sync-keys-scaffolds", &PROJECT);
+    let scaffolds = |lang: &str| -> Value {
+        let path = dir
+            .path()
+            .join(format!("i18n/{}/.scaffolds.meta.json", lang));
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    };
+
+    let (code, stderr) = check(&dir, &["--fix", "sync-keys"]);
+    assert_eq!(code, Some(1), "{}", stderr);
+    assert!(
+        stderr.contains("Synced keys in 2 translation files"),
+        "{}",
+        stderr
+    );
+    let german = scaffolds("de");
+    assert_eq!(
+        german.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["checkout.pay", "checkout.total"]
+    );
+    let total = &german["checkout.total"];
+    assert_eq!(total["base_hash"], review::value_hash("Total"));
+    assert_eq!(total["value_hash"], review::value_hash("[TODO] Total"));
+    assert_eq!(total["tool_version"], env!("CARGO_PKG_VERSION"));
+    assert!(total["generated_at"].as_str().unwrap().ends_with('Z'));
+
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "checkout": { "total": "Total TTC", "pay": "Payer {amount}" }, "legacy": { "banner": "Ancien" } }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "checkout": { "pay": "[TODO] Payer {amount}", "total": "Summe" }, "legacy": { "banner": "Alt" } }"#,
+    );
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let stale: Vec<(&str, &str, &str)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "stale_scaffold")
+        .map(|finding| {
+            (
+                finding["lang"].as_str().unwrap(),
+                finding["key"].as_str().unwrap(),
+                finding["severity"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(stale, [("it", "checkout.total", "warning")]);
+
+    // German translated `checkout.total`, so its record is dropped.
+    let (_, stderr) = check(&dir, &["--fix", "sync-keys", "--allow-base-writes"]);
+    assert!(stderr.contains("Synced keys"), "{}", stderr);
+    assert_eq!(
+        scaffolds("de")
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        ["checkout.pay"]
+    );
+    assert_eq!(
+        scaffolds("it")
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        ["checkout.pay", "checkout.total"]
+    );
+}
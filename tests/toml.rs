@@ -0,0 +1,46 @@
+use check_translations::toml;
+use serde_json::json;
+
+#[test]
+fn tables_and_arrays_of_tables_are_read() {
+    let value = toml::parse(
+        "title = \"x\"\n\n[project]\ni18n_dir = \"i18n\"\n\n[[project.sources]]\ndir = \"src\"\n\n[[project.sources]]\ndir = \"lib\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "title": "x",
+            "project": {
+                "i18n_dir": "i18n",
+                "sources": [{ "dir": "src" }, { "dir": "lib" }],
+            },
+        })
+    );
+}
+
+#[test]
+fn a_table_header_may_be_given_only_once() {
+    let err = toml::parse("[project]\nsrc_dir = \"src\"\n\n[project]\ni18n_dir = \"i18n\"\n")
+        .unwrap_err();
+    assert_eq!(err, "line 4: table `project` is defined twice");
+
+    // A table under an element of an array of tables is another table in
+    // each element.
+    let value = toml::parse("[[a]]\n[a.b]\nx = 1\n[[a]]\n[a.b]\nx = 2\n").unwrap();
+    assert_eq!(
+        value,
+        json!({ "a": [{ "b": { "x": 1 } }, { "b": { "x": 2 } }] })
+    );
+}
+
+#[test]
+fn a_duplicate_key_is_reported_at_its_own_line() {
+    let err = toml::parse("[project]\nsrc_dir = \"src\"\nsrc_dir = \"lib\"\n\nbase = \"fr\"\n")
+        .unwrap_err();
+    assert_eq!(err, "line 3: duplicate key `src_dir`");
+
+    // Also when its value spans lines.
+    let err = toml::parse("a = 1\na = [\n  1,\n  2,\n]\n").unwrap_err();
+    assert_eq!(err, "line 2: duplicate key `a`");
+}
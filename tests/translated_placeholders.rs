@@ -0,0 +1,119 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::fs;
+
+const SPANISH: &str = r#"{
+    "greeting": "Hola {nombre}",
+  "items":"{cantidad} artículos para {name}, {cantidad}",
+    "total": "{x} de {y}",
+    "title": "Título"
+}
+"#;
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+            "greeting": "Bonjour {name}",
+            "items": "{count} articles pour {name}, {count}",
+            "total": "{a} sur {b}",
+            "title": "Titre"
+        }"#,
+    ),
+    ("i18n/es/common.json", SPANISH),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (String, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn renamed_placeholders_are_told_apart_from_mismatches() {
+    let dir = fixture("translated-placeholders", &PROJECT);
+
+    let (stdout, _) = check(&dir, &[]);
+
+    assert!(
+        stdout.contains(
+            "🏷️ Placeholders renamed by the translation:\n   - Key: greeting\n   - Expected variables (FR): {\"name\"}\n   - Found variables (ES): {\"nombre\"}\n   - Renamed: {nombre} should be {name}\n"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Renamed: {cantidad} should be {count}\n"),
+        "{}",
+        stdout
+    );
+    // With two placeholders renamed, which became which is unclear.
+    assert!(
+        stdout.contains("🔄 Variable mismatch detected!\n   - Key: total\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn the_fix_renames_them_back_in_place() {
+    let dir = fixture("translated-placeholders-fix", &PROJECT);
+    let path = dir.path().join("i18n/es/common.json");
+
+    let (_, stderr) = check(&dir, &["--fix", "translated-placeholders", "--dry-run"]);
+    assert_eq!(fs::read_to_string(&path).unwrap(), SPANISH);
+    assert!(
+        stderr.contains(
+            "--- i18n/es/common.json\n+++ i18n/es/common.json\n@@ -2,1 +2,1 @@\n-    \"greeting\": \"Hola {nombre}\",\n+    \"greeting\": \"Hola {name}\",\n@@ -3,1 +3,1 @@\n"
+        ),
+        "{}",
+        stderr
+    );
+
+    let (_, stderr) = check(&dir, &["--fix", "translated-placeholders"]);
+    assert!(
+        stderr.contains("Renamed translated placeholders in 1 translation files"),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        SPANISH
+            .replace("{nombre}", "{name}")
+            .replace("{cantidad}", "{count}")
+    );
+
+    let (stdout, _) = check(&dir, &[]);
+    assert!(!stdout.contains("🏷️"), "{}", stdout);
+    assert!(stdout.contains("Key: total"), "{}", stdout);
+}
+
+#[test]
+fn renamed_placeholders_are_listed_as_variable_mismatches() {
+    let dir = TempDir::new("translated-placeholders-listed");
+    dir.write("i18n/fr/common.json", r#"{ "greeting": "Bonjour {name}" }"#);
+    dir.write("i18n/de/common.json", r#"{ "greeting": "Hallo {nom}" }"#);
+
+    let (stdout, _) = check(&dir, &["--format", "json"]);
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+
+    assert_eq!(
+        report["variable_mismatches"],
+        serde_json::json!([
+            { "key": "greeting", "lang": "de", "file": "i18n/de/common.json" }
+        ])
+    );
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use check_translations::config::Config;
+use check_translations::render::Renderer;
+use check_translations::render::plain::PlainRenderer;
+use check_translations::{Check, Checker, LoadOptions, TranslationSet};
+use common::{fixture, run};
+use serde_json::Value;
+use std::path::PathBuf;
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "greeting": "Bonjour {name}", "legacy": "Ancien" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "greeting": "Hallo {vorname}", "extra": "Extra", "legacy": "Alt" }"#,
+    ),
+    ("src/app.ts", "t('title');\nt('greeting');\n"),
+];
+
+#[test]
+fn languages_are_compared_to_the_base() {
+    let dir = fixture("translation-set", &PROJECT);
+    let i18n = dir.path().join("i18n");
+    let set = TranslationSet::load(&i18n, LoadOptions::default()).unwrap();
+
+    assert_eq!(set.languages().collect::<Vec<_>>(), ["de", "fr"]);
+    assert_eq!(set.get("de").unwrap()["legacy"].value, "Alt");
+
+    let report = set.compare("fr").unwrap();
+    let common = i18n.join("de/common.json");
+    assert_eq!(report.missing.len(), 1);
+    assert_eq!(report.missing[0].key, "title");
+    assert_eq!(report.missing[0].file, common);
+    assert_eq!(report.extra.len(), 1);
+    assert_eq!(
+        (report.extra[0].lang.as_str(), report.extra[0].key.as_str()),
+        ("de", "extra")
+    );
+    assert_eq!(report.mismatches.len(), 1);
+    let mismatch = &report.mismatches[0];
+    assert_eq!(mismatch.key, "greeting");
+    assert_eq!(mismatch.expected.iter().collect::<Vec<_>>(), ["name"]);
+    assert_eq!(mismatch.found.iter().collect::<Vec<_>>(), ["vorname"]);
+    assert!(!report.is_clean());
+
+    let json = report.to_json();
+    assert_eq!(json["missing"][0]["file"], common.display().to_string());
+    assert_eq!(json["mismatches"][0]["found"][0], "vorname");
+
+    assert!(set.compare("it").is_err());
+}
+
+#[test]
+fn unused_keys_are_found_in_folders_and_files() {
+    let dir = fixture("translation-set-usage", &PROJECT);
+    let set = TranslationSet::load(&dir.path().join("i18n"), LoadOptions::default()).unwrap();
+
+    let report = set.find_unused(&[dir.path().join("src")]).unwrap();
+    assert_eq!(report.scanned_files, 1);
+    let unused: Vec<&str> = report
+        .unused
+        .iter()
+        .map(|unused| unused.key.as_str())
+        .collect();
+    assert_eq!(unused, ["legacy"]);
+    assert_eq!(
+        report.unused[0].file,
+        dir.path().join("i18n/fr/common.json")
+    );
+
+    assert_eq!(report.to_json()["unused"][0]["key"], "legacy");
+
+    // A source that can't be read doesn't leave its keys reported unused.
+    let err = set.find_unused(&[PathBuf::from("missing.ts")]).unwrap_err();
+    assert!(err.starts_with("failed to read source file missing.ts: "));
+}
+
+#[test]
+fn a_mixed_layout_fails_to_load() {
+    let dir = fixture("translation-set-layout", &PROJECT);
+    dir.write("i18n/it.json", r#"{ "title": "Titolo" }"#);
+
+    let err = TranslationSet::load(&dir.path().join("i18n"), LoadOptions::default())
+        .err()
+        .unwrap();
+    assert!(err.contains("keep one layout"), "{}", err);
+}
+
+#[test]
+fn the_checker_returns_the_findings_of_a_run() {
+    let dir = fixture("translation-set-checker", &PROJECT);
+    let i18n = dir.path().join("i18n");
+
+    let report = Checker::new(LoadOptions::default()).check(&i18n).unwrap();
+    let checks: Vec<(Check, &str)> = report
+        .findings
+        .iter()
+        .map(|finding| (finding.check, finding.key.as_deref().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            (Check::MissingKey, "title"),
+            (Check::ExtraKey, "extra"),
+            (Check::TranslatedPlaceholder, "greeting"),
+        ]
+    );
+    assert_eq!(report.translation_files, 2);
+    assert!(!report.usage_scanned);
+
+    let report = Checker::new(LoadOptions::default())
+        .sources(&[dir.path().join("src")])
+        .check(&i18n)
+        .unwrap();
+    let unused: Vec<_> = report
+        .findings
+        .iter()
+        .filter(|finding| finding.check == Check::UnusedKey)
+        .collect();
+    assert_eq!(unused.len(), 1);
+    assert_eq!(unused[0].key.as_deref(), Some("legacy"));
+    assert_eq!(report.source_files, 1);
+    let mut plain = Vec::new();
+    PlainRenderer::new(&mut plain).render(&report).unwrap();
+    assert!(
+        String::from_utf8(plain)
+            .unwrap()
+            .contains("\tunused_key\tfr\tlegacy\t")
+    );
+
+    let options = LoadOptions {
+        base_lang: "it".to_string(),
+        ..LoadOptions::default()
+    };
+    assert!(Checker::new(options).check(&i18n).is_err());
+}
+
+// With the configuration file, the checker reports what the binary does.
+#[test]
+fn the_checker_runs_the_checks_of_the_configuration() {
+    let dir = fixture("translation-set-configured", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[severity]\nextra_key = \"off\"\n\n[key_naming]\ncase = \"snake\"\n",
+    );
+    let config = Config::load(&dir.path().join("translation-check.toml")).unwrap();
+    let report = Checker::new(LoadOptions::default())
+        .sources(&[dir.path().join("src")])
+        .config(config)
+        .check(&dir.path().join("i18n"))
+        .unwrap();
+    let checks: Vec<(String, String)> = report
+        .findings
+        .iter()
+        .map(|finding| {
+            (
+                finding.check.info().id.to_string(),
+                finding.key.clone().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert!(!checks.iter().any(|(check, _)| check == "extra_key"));
+    assert!(checks.contains(&("unused_key".to_string(), "legacy".to_string())));
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--format",
+        "json",
+        "--no-cache",
+    ]);
+    let cli: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let expected: Vec<(String, String)> = cli["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap().to_string(),
+                finding["key"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(checks, expected);
+}
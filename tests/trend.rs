@@ -0,0 +1,102 @@
+mod common;
+
+use check_translations::trend::{days, sparkline};
+use common::{TempDir, run};
+use serde_json::{Value, json};
+use std::fs;
+
+const STATE: &str = r#"{
+  "base_keys": ["title"],
+  "coverage": { "de": 90.0, "it": 50.0 },
+  "history": [
+    { "coverage": { "de": 60.0 }, "date": "2026-06-01", "errors": 12, "warnings": 3 },
+    { "coverage": { "de": 75.0 }, "date": "2026-08-01", "errors": 8, "warnings": 3 },
+    { "coverage": { "de": 80.0 }, "date": "2026-09-10", "errors": 5, "warnings": 2 },
+    { "coverage": { "de": 90.0, "it": 50.0 }, "date": "2026-10-14", "errors": 4, "warnings": 1 }
+  ],
+  "languages": { "de": 1, "fr": 1, "it": 1 }
+}"#;
+
+fn trend(dir: &TempDir, extra: &[&str]) -> String {
+    let mut args = vec!["--cwd", dir.path().to_str().unwrap(), "trend"];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn coverage_is_compared_to_30_and_90_days_before_the_latest_snapshot() {
+    assert_eq!(days("1970-01-01"), Some(0));
+    assert_eq!(
+        days("2024-03-01").zip(days("2024-02-28")),
+        Some((19783, 19781))
+    );
+    assert_eq!(sparkline(&[60.0, 75.0, 80.0, 90.0]), "▁▅▆█");
+    assert_eq!(sparkline(&[100.0, 100.0]), "██");
+
+    let dir = TempDir::new("trend");
+    dir.write("translation-check.state.json", STATE);
+
+    assert_eq!(
+        trend(&dir, &[]),
+        "Coverage trend (4 snapshots, 2026-06-01 to 2026-10-14)\n\
+         Language  Coverage  30 days  90 days  History\n\
+         de           90.0%    +10.0    +30.0  ▁▅▆█\n\
+         it           50.0%        —        —  ▅\n\
+         Findings: 4 errors, 1 warnings\n"
+    );
+
+    let report: Value = serde_json::from_str(&trend(&dir, &["--format", "json"])).unwrap();
+    assert_eq!(report["languages"][0]["delta_30d"], json!(10.0));
+    assert_eq!(report["languages"][1]["delta_90d"], Value::Null);
+    assert_eq!(
+        report["findings"]["history"][0],
+        json!({ "date": "2026-06-01", "errors": 12, "warnings": 3 })
+    );
+}
+
+#[test]
+fn update_state_records_one_snapshot_per_day() {
+    let dir = TempDir::new("trend-update");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "body": "Texte" }"#,
+    );
+    dir.write("i18n/de/common.json", r#"{ "title": "Titel" }"#);
+    let check = || {
+        run(&[
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "i18n",
+            "--only",
+            "consistency",
+            "--update-state",
+        ])
+    };
+    check();
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "body": "Text" }"#,
+    );
+    check();
+
+    let content = fs::read_to_string(dir.path().join("translation-check.state.json")).unwrap();
+    let state: Value = serde_json::from_str(&content).unwrap();
+    let history = state["history"].as_array().unwrap();
+    assert_eq!(history.len(), 1, "{}", content);
+    assert_eq!(history[0]["coverage"], json!({ "de": 100.0 }));
+    assert_eq!(history[0]["errors"], json!(0));
+
+    let stdout = trend(&dir, &[]);
+    assert!(
+        stdout.contains("de          100.0%        —        —  █\n"),
+        "{}",
+        stdout
+    );
+}
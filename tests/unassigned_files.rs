@@ -0,0 +1,84 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+// A `common.json` saved next to the language folders, and a `shared`
+// folder holding a namespace rather than a language.
+const PROJECT: [(&str, &str); 5] = [
+    ("i18n/fr/common.json", r#"{ "title": "Titre" }"#),
+    ("i18n/de/common.json", r#"{ "title": "Titel" }"#),
+    (
+        "i18n/common.json",
+        r#"{ "title": "Titre", "banner": { "sale": "Soldes" } }"#,
+    ),
+    ("i18n/shared/footer.json", r#"{ "footer": "Pied de page" }"#),
+    ("i18n/_archive/old.json", r#"{ "old": "Ancien" }"#),
+];
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--color",
+        "never",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn files_no_language_reads_are_reported_with_their_keys() {
+    let dir = fixture("unassigned-files", &PROJECT);
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "   - Keys: 2 (1 not in fr) | Action: move its keys into the language files | File: i18n/common.json\n"
+        ),
+        "{}",
+        stdout
+    );
+    for file in ["i18n/shared/footer.json", "i18n/_archive/old.json"] {
+        assert!(
+            stdout.contains(&format!(
+                "   - Keys: 1 (1 not in fr) | Action: move its keys into the language files, or list its folder in `languages.non_language_dirs` | File: {}\n",
+                file
+            )),
+            "{}",
+            stdout
+        );
+    }
+    // Namespaces aren't languages, and the stray file doesn't make the
+    // layout ambiguous.
+    assert!(!stdout.contains("SHARED"), "{}", stdout);
+    assert!(
+        !stdout.contains("Files in the translation directory"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn declared_folders_are_left_alone() {
+    let dir = fixture("unassigned-declared", &PROJECT);
+    dir.write(
+        "translation-check.toml",
+        "[languages]\nnon_language_dirs = [\"_archive/\", \"old\"]\n",
+    );
+    dir.write("i18n/old/common.json", r#"{ "legacy": "Héritage" }"#);
+    let (_, stdout) = check(&dir);
+
+    assert!(stdout.contains("File: i18n/common.json"), "{}", stdout);
+    assert!(
+        stdout.contains("File: i18n/shared/footer.json"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("_archive"), "{}", stdout);
+    assert!(!stdout.contains("i18n/old"), "{}", stdout);
+    assert!(!stdout.contains("OLD"), "{}", stdout);
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 3] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "errors": { "notFound": "Introuvable" } }"#,
+    ),
+    (
+        "src/app.ts",
+        "t('title');\n\
+         $t('errors.notFound');\n\
+         i18n.t(\"errors.timeout\");\n\
+         t(`errors.${code}`);\n\
+         t('errors.' + code);\n\
+         // i18n-check-disable-next-line undefined_key\n\
+         t('legacy.banner');\n",
+    ),
+    (
+        "src/Page.vue",
+        "<template>\n  <p>{{ $t('page.heading') }}</p>\n</template>\n",
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn keys_missing_from_the_base_are_reported_where_they_are_used() {
+    let (code, stdout) = check(&fixture("undefined-keys", &PROJECT), &[]);
+
+    assert_eq!(code, Some(1));
+    let keys: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("❓ Keys used in the code but missing from the base"))
+        .skip(1)
+        .take_while(|line| line.starts_with("   - "))
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "   - Key: errors.timeout | File: src/app.ts:3",
+            "   - Key: page.heading | File: src/Page.vue:2",
+        ],
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn keys_of_another_case_resolve_when_matching_case_insensitively() {
+    let dir = fixture("undefined-keys-case", &PROJECT);
+    dir.write("src/Page.vue", "<template>{{ $t('Title') }}</template>\n");
+
+    let (_, stdout) = check(&dir, &[]);
+    assert!(
+        stdout.contains("   - Key: Title | File: src/Page.vue:1"),
+        "{}",
+        stdout
+    );
+
+    let (_, stdout) = check(&dir, &["--key-case-insensitive"]);
+    assert!(!stdout.contains("Key: Title"), "{}", stdout);
+}
+
+#[test]
+fn languages_still_defining_an_undefined_key_are_named() {
+    let dir = fixture("undefined-keys-elsewhere", &PROJECT);
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "errors": { "notFound": "Nicht gefunden", "timeout": "Zeitüberschreitung" } }"#,
+    );
+
+    let (_, stdout) = check(&dir, &[]);
+    assert!(
+        stdout.contains("   - Key: errors.timeout | Defined in: DE | File: src/app.ts:3\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("   - Key: page.heading | File: src/Page.vue:2\n"),
+        "{}",
+        stdout
+    );
+}
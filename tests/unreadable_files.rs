@@ -0,0 +1,181 @@
+mod common;
+
+use common::{TempDir, run};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+// A folder named like a translation file fails to read like a file without
+// permissions would, even when the tests run as root.
+fn unreadable(dir: &TempDir, path: &str) {
+    fs::create_dir_all(dir.path().join(path)).unwrap();
+}
+
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    for file in ["common", "checkout", "account", "legal", "help"] {
+        dir.write(
+            &format!("i18n/fr/{}.json", file),
+            &format!(r#"{{ "{0}.title": "Titre {0}" }}"#, file),
+        );
+        dir.write(
+            &format!("i18n/de/{}.json", file),
+            &format!(r#"{{ "{0}.title": "Titel {0}" }}"#, file),
+        );
+        dir.write(
+            &format!("i18n/it/{}.json", file),
+            &format!(r#"{{ "{0}.title": "Titolo {0}" }}"#, file),
+        );
+    }
+    dir
+}
+
+fn check(dir: &TempDir) -> (Option<i32>, String) {
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+    ]);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn unreadable_files_are_reported_and_the_rest_is_checked() {
+    let dir = fixture("unreadable-files");
+    fs::remove_file(dir.path().join("i18n/it/legal.json")).unwrap();
+    unreadable(&dir, "i18n/it/legal.json");
+    dir.write("i18n/de/help.json", "{}");
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("🚷 Unreadable translation files:\n   - Error: Is a directory (os error 21) | File: i18n/it/legal.json"),
+        "{}",
+        stdout
+    );
+    // One file out of five stays under the threshold: its keys are missing,
+    // the other languages are compared as usual.
+    assert!(stdout.contains("Key: legal.title"), "{}", stdout);
+    assert!(stdout.contains("Key: help.title"), "{}", stdout);
+    assert!(!stdout.contains("left out"), "{}", stdout);
+}
+
+#[test]
+fn languages_with_too_many_unreadable_files_are_left_out() {
+    let dir = fixture("unreadable-files-excluded");
+    for file in ["legal", "help"] {
+        let path = format!("i18n/it/{}.json", file);
+        fs::remove_file(dir.path().join(&path)).unwrap();
+        unreadable(&dir, &path);
+    }
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+    assert!(
+        stdout.contains("⛔ Languages left out of the comparison:\n   - 2 of 5 files unreadable\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("⛔ IT was left out of the comparison: 2 of 5 files unreadable.\n"),
+        "{}",
+        stdout
+    );
+
+    dir.write(
+        "translation-check.toml",
+        "[unreadable_files]\nmax_fraction = 0.5\n",
+    );
+    let (_, stdout) = check(&dir);
+    assert!(stdout.contains("Key: legal.title"), "{}", stdout);
+    assert!(!stdout.contains("left out"), "{}", stdout);
+}
+
+#[test]
+fn unreadable_language_folders_are_left_out() {
+    let dir = fixture("unreadable-folder");
+    let folder = dir.path().join("i18n/it");
+    fs::set_permissions(&folder, fs::Permissions::from_mode(0o000)).unwrap();
+    // Root reads the folder anyway.
+    let listed = fs::read_dir(&folder).is_ok();
+    let (code, stdout) = check(&dir);
+    fs::set_permissions(&folder, fs::Permissions::from_mode(0o755)).unwrap();
+    if listed {
+        return;
+    }
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+    assert!(stdout.contains("failed to list the folder"), "{}", stdout);
+    assert!(
+        stdout.contains("⛔ IT was left out of the comparison: its folder is unreadable.\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn malformed_json_is_reported_with_its_position() {
+    let dir = fixture("malformed-json");
+    dir.write(
+        "i18n/de/checkout.json",
+        "{\n  \"checkout.title\": \"Kasse\"\n  \"x\": 1\n}\n",
+    );
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("   - Error: invalid JSON: expected `,` or `}` at line 3 column 3 | File: i18n/de/checkout.json"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("❌ Missing keys:\n   - Key: checkout.title"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn keys_defined_in_two_files_are_reported() {
+    let dir = fixture("duplicate-keys");
+    dir.write(
+        "i18n/de/help.json",
+        r#"{ "help.title": "Titel help", "common.title": "Doppelt" }"#,
+    );
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(
+        stdout.contains("👯 Keys defined in more than one place:\n   - Key: common.title | Ignored definition in: i18n/de/common.json | File: i18n/de/help.json"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn json_files_without_a_root_object_are_unreadable() {
+    let dir = fixture("unreadable-json-root");
+    dir.write("i18n/de/legal.json", "[1, 2]");
+    dir.write("i18n/it/legal.json", r#""Titolo legal""#);
+
+    let (code, stdout) = check(&dir);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("   - Error: expected a JSON object | File: i18n/de/legal.json"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("   - Error: expected a JSON object | File: i18n/it/legal.json"));
+    assert!(!stdout.contains("Key: \n"));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/common.json",
+        r#"{
+            "title": "Tableau de bord",
+            "ok": "OK",
+            "total": "{count} × 100",
+            "brand": { "_translationCheck": { "doNotTranslate": true }, "name": "Acme Studio" },
+            "empty": "Vide",
+            "hint": "Astuce"
+        }"#,
+    ),
+    (
+        "i18n/en/common.json",
+        r#"{
+            "title": "Tableau de bord",
+            "ok": "OK",
+            "total": "{count} × 100",
+            "brand": { "name": "Acme Studio" },
+            "empty": "  ",
+            "hint": "Tip"
+        }"#,
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String) {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--only",
+        "consistency",
+        "--color",
+        "never",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn empty_and_untranslated_values_are_warnings() {
+    let dir = fixture("untranslated", &PROJECT);
+    let (code, stdout) = check(&dir, &[]);
+    assert!(!stdout.contains("Empty translations"), "{}", stdout);
+
+    let (code_checked, stdout) = check(&dir, &["--check-untranslated"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(code_checked, Some(0), "{}", stdout);
+    assert!(stdout.contains("Key: empty | File: "), "{}", stdout);
+    assert!(
+        stdout.contains("Key: title | Value: \"Tableau de bord\""),
+        "{}",
+        stdout
+    );
+    // Short values, placeholders without words and do-not-translate values
+    // may match the base.
+    for key in ["Key: ok", "Key: total", "Key: brand.name"] {
+        assert!(!stdout.contains(key), "{}", stdout);
+    }
+    assert!(
+        stdout.contains("No translation errors found (2 warnings)."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn strict_mode_fails_the_run() {
+    let dir = fixture("untranslated-strict", &PROJECT);
+    let (code, stdout) = check(&dir, &["--check-untranslated", "--strict"]);
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(stdout.contains("2 errors, 0 warnings"), "{}", stdout);
+
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n", "--strict"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn the_minimum_length_is_configurable() {
+    let dir = fixture("untranslated-length", &PROJECT);
+    dir.write("translation-check.toml", "[untranslated]\nmin_length = 2\n");
+    let (_, stdout) = check(&dir, &["--check-untranslated"]);
+
+    assert!(stdout.contains("Key: ok | Value: \"OK\""), "{}", stdout);
+    assert!(!stdout.contains("Key: total"), "{}", stdout);
+}
+
+#[test]
+fn allowed_values_and_keys_may_match_the_base() {
+    let dir = fixture("untranslated-allowed", &PROJECT);
+    dir.write(
+        "i18n/fr/extra.json",
+        r#"{ "product": "Acme Cloud", "social": { "github": "GitHub" } }"#,
+    );
+    dir.write(
+        "i18n/en/extra.json",
+        r#"{ "product": "Acme Cloud", "social": { "github": "GitHub" } }"#,
+    );
+    dir.write(
+        "translation-check.toml",
+        "[untranslated]\nallow = [\"Acme Cloud\"]\nallow_keys = [\"social\"]\n",
+    );
+    let (_, stdout) = check(&dir, &["--check-untranslated"]);
+
+    for key in ["Key: product", "Key: social.github"] {
+        assert!(!stdout.contains(key), "{}", stdout);
+    }
+    assert!(stdout.contains("Key: title"), "{}", stdout);
+}
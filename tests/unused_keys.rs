@@ -0,0 +1,257 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+const KEYS: [&str; 10] = [
+    "common.save",
+    "common.saveAndExit",
+    "common.cancel",
+    "common.close",
+    "errors.notFound",
+    "errors.timeout",
+    "status.active",
+    "legacy.banner",
+    "legacy.footer",
+    "a.b",
+];
+
+fn fixture(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    let values: serde_json::Map<String, Value> = KEYS
+        .iter()
+        .map(|key| (key.to_string(), Value::from("Valeur")))
+        .collect();
+    dir.write("i18n/fr/common.json", &Value::Object(values).to_string());
+    dir.write(
+        "src/app.ts",
+        "t('common.saveAndExit');\n\
+         t(\"common.cancel\");\n\
+         const close = `common.close`;\n\
+         t(`errors.${code}`);\n\
+         t('status.' + state);\n\
+         // t('legacy.banner');\n\
+         /* t('legacy.footer'); */\n\
+         log('a.bc', 'not a.b');\n",
+    );
+    dir.write(
+        "src/Banner.vue",
+        "<template>\n  <!-- {{ $t('legacy.banner') }} -->\n  <p title=\"Don't\">{{ $t('a.b') }}</p>\n</template>\n",
+    );
+    dir
+}
+
+fn unused(dir: &TempDir, extra: &[&str]) -> Vec<String> {
+    let mut args = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--only",
+        "unused-keys",
+        "--format",
+        "json",
+    ];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut keys: Vec<String> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "unused_key")
+        .map(|finding| finding["key"].as_str().unwrap().to_string())
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn keys_are_used_when_quoted_exactly_or_covered_by_a_dynamic_prefix() {
+    let dir = fixture("unused-keys");
+    let expected = ["common.save", "legacy.banner", "legacy.footer"];
+
+    assert_eq!(unused(&dir, &[]), expected);
+    assert_eq!(unused(&dir, &["--low-memory"]), expected);
+}
+
+#[test]
+fn case_insensitive_matching_keeps_exact_references() {
+    let dir = fixture("unused-keys-case");
+    dir.write("src/extra.ts", "t('COMMON.SAVE'); t('Legacy.Banner2');\n");
+    let expected = ["legacy.banner", "legacy.footer"];
+
+    assert_eq!(unused(&dir, &["--key-case-insensitive"]), expected);
+    assert_eq!(
+        unused(&dir, &["--key-case-insensitive", "--low-memory"]),
+        expected
+    );
+}
+
+#[test]
+fn sources_in_other_encodings_are_scanned_as_bytes() {
+    let dir = fixture("unused-keys-latin1");
+    // `é` in Latin-1, which isn't UTF-8.
+    std::fs::write(
+        dir.path().join("src/legacy.ts"),
+        b"// Pr\xe9f\xe9rences\nt('legacy.banner');\n",
+    )
+    .unwrap();
+    let expected = ["common.save", "legacy.footer"];
+
+    for extra in [&[][..], &["--key-case-insensitive"]] {
+        let mut low_memory = extra.to_vec();
+        low_memory.push("--low-memory");
+        assert_eq!(unused(&dir, extra), expected);
+        assert_eq!(unused(&dir, &low_memory), expected);
+    }
+}
+
+// Large sources are mapped rather than read; blanking their comments must
+// leave the file on disk untouched.
+#[test]
+fn large_sources_are_scanned_in_place() {
+    let dir = fixture("unused-keys-large");
+    let source = format!(
+        "{}// t('common.save');\n",
+        "const label = 'status.active';\n".repeat(4096)
+    );
+    dir.write("src/generated.ts", &source);
+    dir.write("src/app.ts", "t('common.saveAndExit');\n");
+    let expected = [
+        "common.cancel",
+        "common.close",
+        "common.save",
+        "errors.notFound",
+        "errors.timeout",
+        "legacy.banner",
+        "legacy.footer",
+    ];
+
+    assert_eq!(unused(&dir, &[]), expected);
+    assert_eq!(unused(&dir, &["--key-case-insensitive"]), expected);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("src/generated.ts")).unwrap(),
+        source
+    );
+}
+
+#[test]
+fn in_calls_mode_only_translation_calls_use_keys() {
+    let dir = fixture("unused-keys-calls");
+    dir.write("translation-check.toml", "[usage]\nmode = \"calls\"\n");
+    dir.write(
+        "src/app.component.html",
+        "<h1>{{ 'common.save' | translate }}</h1>\n<p>{{ 'common.missing' | translate }}</p>\n",
+    );
+    dir.write(
+        "src/legacy.ts",
+        "this.translate.instant('legacy.footer');\nconst label = 'legacy.banner';\n",
+    );
+    let expected = ["common.close", "legacy.banner"];
+    assert_eq!(unused(&dir, &["--ext", "ts,vue,html"]), expected);
+
+    let output = run(&[
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "i18n",
+        "--src-dir",
+        "src",
+        "--ext",
+        "ts,vue,html",
+        "--format",
+        "json",
+    ]);
+    let report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let undefined: Vec<(&str, &str, u64)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|finding| finding["check"] == "undefined_key")
+        .map(|finding| {
+            (
+                finding["key"].as_str().unwrap(),
+                finding["file"].as_str().unwrap(),
+                finding["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(undefined, [("common.missing", "src/app.component.html", 2)]);
+
+    dir.write(
+        "translation-check.toml",
+        "[usage]\nmode = \"calls\"\ncall_patterns = ['translate\\(']\n",
+    );
+    let output = run(&["--cwd", dir.path().to_str().unwrap(), "i18n"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("it captures no key"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn declared_dynamic_keys_are_always_used() {
+    let dir = fixture("unused-keys-dynamic");
+    dir.write(
+        "translation-check.toml",
+        "[usage]\ndynamic_keys = [\"legacy.*\"]\n",
+    );
+    assert_eq!(unused(&dir, &[]), ["common.save"]);
+    assert_eq!(unused(&dir, &["--low-memory"]), ["common.save"]);
+}
+
+#[test]
+fn repeat_runs_only_scan_the_sources_modified_since() {
+    let dir = fixture("unused-keys-cache");
+    let expected = ["common.save", "legacy.banner", "legacy.footer"];
+    let cache = dir.path().join(".translation-check-cache/usage-scan.json");
+    let hits = |extra: &[&str]| {
+        let mut args = vec![
+            "--cwd",
+            dir.path().to_str().unwrap(),
+            "i18n",
+            "--src-dir",
+            "src",
+            "--only",
+            "unused-keys",
+            "--timings",
+        ];
+        args.extend_from_slice(extra);
+        let stderr = String::from_utf8_lossy(&run(&args).stderr).to_string();
+        stderr
+            .lines()
+            .find_map(|line| line.strip_prefix("Scan cache: "))
+            .map(|line| line.split(' ').next().unwrap().to_string())
+    };
+
+    assert_eq!(unused(&dir, &[]), expected);
+    let scans: Value = serde_json::from_str(&std::fs::read_to_string(&cache).unwrap()).unwrap();
+    assert_eq!(
+        scans["files"]["src/Banner.vue"]["exact"],
+        serde_json::json!(["a.b"])
+    );
+    assert_eq!(hits(&[]).as_deref(), Some("2"));
+    assert_eq!(unused(&dir, &[]), expected);
+
+    dir.write(
+        "src/Banner.vue",
+        "<p>{{ $t('a.b') }} {{ $t('legacy.banner') }}</p>\n",
+    );
+    assert_eq!(hits(&[]).as_deref(), Some("1"));
+    assert_eq!(unused(&dir, &[]), ["common.save", "legacy.footer"]);
+
+    // Other base keys may be quoted in the files cached for the old ones.
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{"common": {"saveAndExit": "Enregistrer et quitter"}, "a": {"b": "B"}, "status": {"new": "Nouveau"}, "legacy": {"banner": "Bannière"}}"#,
+    );
+    assert_eq!(hits(&[]).as_deref(), Some("0"));
+    assert_eq!(hits(&[]).as_deref(), Some("2"));
+
+    std::fs::remove_file(&cache).unwrap();
+    assert_eq!(hits(&["--no-cache"]), None);
+    assert!(!cache.exists());
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::{TempDir, run};
+use serde_json::Value;
+
+fn verify(dir: &TempDir, args: &[&str]) -> (Option<i32>, String, String) {
+    let mut all = vec![
+        "--cwd",
+        dir.path().to_str().unwrap(),
+        "verify-bundle",
+        "i18n",
+        "--color",
+        "never",
+    ];
+    all.extend_from_slice(args);
+    let output = run(&all);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+fn sources(dir: &TempDir) {
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Titre", "cart": { "empty": "Panier vide", "total": "Total" } }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Titel", "cart": { "empty": "Leer", "total": "Summe" } }"#,
+    );
+    dir.write("i18n/nl/common.json", r#"{ "title": "Titel" }"#);
+}
+
+// The bundler inlined French and German in the main chunk, and dropped
+// `cart.total` from German; an older `cart.legacy` still ships in French.
+// Dutch went missing altogether.
+#[test]
+fn minified_scripts_are_compared_with_the_sources() {
+    let dir = TempDir::new("verify-bundle-js");
+    sources(&dir);
+    dir.write(
+        "translation-check.toml",
+        "[[bundle.assets]]\nfiles = \"assets/*.js\"\npath = \"messages\"\n",
+    );
+    dir.write(
+        "dist/assets/index-4f2a.js",
+        r#"import{c as e}from"./vendor.js";const t={mode:"production"},n={"debug":false};const r=JSON.parse('{"messages":{"fr":{"title":"Titre","cart":{"empty":"Panier vide","total":"Total","legacy":"Ancien"}},"de":{"title":"Titel","cart":{"empty":"Leer"}}}}');e(r,t,n);"#,
+    );
+    dir.write(
+        "dist/assets/vendor.js",
+        r#"export const c=(a,b)=>{return{"messages":{"de":{"title":"Titel"}}}};"#,
+    );
+
+    let (code, stdout, stderr) = verify(&dir, &["--dist", "dist"]);
+    assert_eq!(code, Some(1), "{}", stderr);
+    for line in [
+        "   - Key: cart.total | File: i18n/de/common.json\n",
+        "   - Key: cart.legacy | File: dist/assets/index-4f2a.js\n",
+        "   - Keys: 1 | File: dist\n",
+    ] {
+        assert!(stdout.contains(line), "{}", stdout);
+    }
+    assert!(!stdout.contains("Key: title"), "{}", stdout);
+    assert!(
+        stderr.contains("Read 2 built files holding 2 of 3 languages"),
+        "{}",
+        stderr
+    );
+
+    let (_, stdout, _) = verify(&dir, &["--dist", "dist", "--format", "json"]);
+    let report: Value = serde_json::from_str(&stdout).unwrap();
+    let checks: Vec<(&str, &str)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            (
+                finding["check"].as_str().unwrap(),
+                finding["lang"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            ("bundle_missing_key", "de"),
+            ("bundle_stale_key", "fr"),
+            ("bundle_missing_language", "nl"),
+        ]
+    );
+    assert_eq!(report["config"]["dist"], "dist");
+}
+
+#[test]
+fn copied_translation_files_are_read_per_language() {
+    let dir = TempDir::new("verify-bundle-json");
+    sources(&dir);
+    dir.write(
+        "dist/locales/fr.json",
+        r#"{"title":"Titre","cart":{"empty":"Panier vide","total":"Total"}}"#,
+    );
+    dir.write(
+        "dist/locales/de.json",
+        r#"{"title":"Titel","cart":{"empty":"Leer","total":"Summe"}}"#,
+    );
+    dir.write("dist/locales/nl.json", r#"{"title":"Titel"}"#);
+
+    let (code, stdout, stderr) = verify(&dir, &["--dist", "dist"]);
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(
+        stderr.contains("Read 3 built files holding 3 of 3 languages"),
+        "{}",
+        stderr
+    );
+
+    let (code, _, stderr) = verify(&dir, &[]);
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("`verify-bundle` requires `--dist <dir>`"),
+        "{}",
+        stderr
+    );
+    let (code, _, stderr) = verify(&dir, &["--dist", "build"]);
+    assert_eq!(code, Some(2));
+    assert!(stderr.contains("no build output in build"), "{}", stderr);
+}
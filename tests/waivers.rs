@@ -0,0 +1,181 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use regex::Regex;
+use std::fs;
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {name}", "save": "Enregistrer", "cancel": "Annuler" }"#,
+    ),
+    (
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo", "extra": "Extra" }"#,
+    ),
+];
+
+fn check(dir: &TempDir, extra: &[&str]) -> (Option<i32>, String, String) {
+    let i18n = dir.path().join("i18n");
+    let mut args = vec![i18n.to_str().unwrap(), "--only", "consistency"];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn emitted_waivers_silence_the_current_findings() {
+    let dir = fixture("waivers-emit", &PROJECT);
+    let emitted = dir.path().join("waivers.toml");
+
+    let (code, _, stderr) = check(&dir, &["--emit-waivers", emitted.to_str().unwrap()]);
+    assert_eq!(code, Some(1));
+    assert!(stderr.contains("4 waivers written to"), "{}", stderr);
+
+    let waivers = fs::read_to_string(&emitted).unwrap();
+    assert!(waivers.contains("# ❌ Missing keys (2)\n\n"), "{}", waivers);
+    assert!(
+        waivers
+            .contains("[[waiver]]\ncheck = \"missing_key\"\nlang = \"de\"\nkey = \"cancel\"\n\n"),
+        "{}",
+        waivers
+    );
+    assert!(
+        Regex::new(r#"# Expected variables \(FR\): \{"name"\} \| .* \(found \d{4}-\d{2}-\d{2}\)\n\[\[waiver\]\]\ncheck = "variable_mismatch""#)
+            .unwrap()
+            .is_match(&waivers),
+        "{}",
+        waivers
+    );
+
+    let (code, stdout, _) = check(&dir, &["--waivers", emitted.to_str().unwrap()]);
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(stdout.contains("🙈 Waived findings: 4\n"), "{}", stdout);
+    assert!(
+        stdout.contains("✅ No translation issues found."),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn expired_and_unused_waivers_are_reported() {
+    let dir = fixture("waivers-expiry", &PROJECT);
+    let waivers = dir.write(
+        "waivers.toml",
+        r#"
+[[waiver]]
+check = "missing_key"
+lang = "de"
+expires = "2999-12-31"
+
+[[waiver]]
+check = "extra_key"
+key = "extra"
+expires = "2000-01-01"
+
+[[waiver]]
+check = "variable_mismatch"
+key = "renamed"
+"#,
+    );
+
+    let (code, stdout, _) = check(&dir, &["--waivers", waivers.to_str().unwrap()]);
+
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+    // The expired waiver no longer applies.
+    assert!(
+        stdout.contains("⚠️ Extra keys:\n   - Key: extra"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "⏰ Expired waivers:\n   - Expired: 2000-01-01 | Waiver: #2 extra_key extra | File: "
+        ),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains(
+            "🙈 Waivers matching no finding:\n   - Waiver: #3 variable_mismatch renamed | File: "
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("🙈 Waived findings: 2\n"), "{}", stdout);
+}
+
+#[test]
+fn invalid_waivers_are_rejected() {
+    let dir = fixture("waivers-invalid", &PROJECT);
+    let waivers = dir.write(
+        "waivers.toml",
+        "[[waiver]]\ncheck = \"missing_key\"\nexpires = \"next year\"\n",
+    );
+
+    let (code, _, stderr) = check(&dir, &["--waivers", waivers.to_str().unwrap()]);
+
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("`expires` of waiver #1 must be a `YYYY-MM-DD` date"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn ignores_in_the_config_match_key_patterns() {
+    let dir = TempDir::new("waivers-ignore");
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour", "legal": { "terms": "Conditions", "cookies": { "banner": "Cookies" } }, "emails": { "welcome": "Bienvenue" } }"#,
+    );
+    dir.write(
+        "i18n/de/common.json",
+        r#"{ "title": "Hallo", "emails": { "welcome": "Willkommen" }, "extra": "Extra" }"#,
+    );
+    let config = dir.write(
+        "translation-check.toml",
+        r#"
+[[ignore]]
+key = "legal.**"
+
+[[ignore]]
+check = "extra_key"
+lang = "de"
+
+[[ignore]]
+check = "missing_key"
+key = "emails.*"
+"#,
+    );
+
+    let (code, stdout, _) = check(&dir, &["--config", config.to_str().unwrap()]);
+
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(!stdout.contains("Missing keys"), "{}", stdout);
+    assert!(!stdout.contains("Extra keys"), "{}", stdout);
+    assert!(
+        stdout.contains(
+            "🙈 Waivers matching no finding:\n   - Waiver: #3 missing_key emails.* | File: "
+        ),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("🙈 Waived findings: 3\n"), "{}", stdout);
+
+    let config = dir.write("invalid.toml", "[[ignore]]\nlang = \"de\"\n");
+    let (code, _, stderr) = check(&dir, &["--config", config.to_str().unwrap()]);
+    assert_eq!(code, Some(2));
+    assert!(
+        stderr.contains("ignore #1 needs a `check` or a `key`"),
+        "{}",
+        stderr
+    );
+}
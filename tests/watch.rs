@@ -0,0 +1,125 @@
+mod common;
+
+use common::{TempDir, run};
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+// A `--watch` run of `dir` and the lines it prints on stderr.
+struct Watching {
+    child: Child,
+    lines: Receiver<String>,
+}
+
+impl Watching {
+    fn start(dir: &TempDir, args: &[&str]) -> Watching {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_check_translations"))
+            .args(["--cwd", dir.path().to_str().unwrap(), "--watch"])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let (sender, lines) = mpsc::channel();
+        let stderr = BufReader::new(child.stderr.take().unwrap());
+        thread::spawn(move || {
+            for line in stderr.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Watching { child, lines }
+    }
+
+    // The lines printed before one holding `text`.
+    fn wait_for(&self, text: &str) -> String {
+        let mut seen = Vec::new();
+        while let Ok(line) = self.lines.recv_timeout(Duration::from_secs(20)) {
+            if line.contains(text) {
+                return seen.join("\n");
+            }
+            seen.push(line);
+        }
+        panic!("never printed `{}`: {:?}", text, seen);
+    }
+}
+
+impl Drop for Watching {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn watch_checks_the_changed_files_again() {
+    let dir = TempDir::new("watch");
+    dir.write("i18n/fr/common.json", r#"{"title": "Titre"}"#);
+    dir.write("i18n/de/common.json", r#"{"title": "Titel"}"#);
+    dir.write("src/app.js", "t('title')");
+    // A base language given isn't detected, which reads every language.
+    let watching = Watching::start(
+        &dir,
+        &["--src-dir", "src", "--base-lang", "fr", "--timings", "i18n"],
+    );
+
+    watching.wait_for("Watching i18n and src for changes");
+    // Coarse modification times can't tell writes in the same second apart.
+    thread::sleep(Duration::from_millis(1100));
+    dir.write(
+        "i18n/de/common.json",
+        r#"{"title": "Titel", "extra": "Extra"}"#,
+    );
+    let second = watching.wait_for("Watching i18n and src for changes");
+    assert!(
+        second.contains("Changed: i18n/de/common.json"),
+        "{}",
+        second
+    );
+    assert!(
+        second.contains("Checking changed files only: de and 0 source files"),
+        "{}",
+        second
+    );
+    // The base language is read again, but only the changed file is
+    // flattened again.
+    assert!(
+        second.contains("translation files flattened: 1 of 2"),
+        "{}",
+        second
+    );
+}
+
+#[test]
+fn watch_sees_the_files_of_new_folders() {
+    let dir = TempDir::new("watch_new_folders");
+    dir.write("i18n/fr/common.json", r#"{"title": "Titre"}"#);
+    dir.write("src/app.js", "t('title')");
+    let watching = Watching::start(&dir, &["--src-dir", "src", "i18n"]);
+
+    watching.wait_for("Watching i18n and src for changes");
+    dir.write("i18n/es/common.json", r#"{"title": "Título"}"#);
+    let second = watching.wait_for("Watching i18n and src for changes");
+    assert!(
+        second.contains("Changed: i18n/es/common.json"),
+        "{}",
+        second
+    );
+
+    thread::sleep(Duration::from_millis(1100));
+    dir.write("src/pages/home.js", "t('title')");
+    let third = watching.wait_for("Watching i18n and src for changes");
+    assert!(third.contains("Changed: src/pages/home.js"), "{}", third);
+}
+
+#[test]
+fn watch_needs_every_run() {
+    let output = run(&["--watch", "--update-state"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "`--watch` re-runs the check on every change and can't be combined with `--update-state`"
+    ));
+}
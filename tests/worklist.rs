@@ -0,0 +1,183 @@
+mod common;
+
+use common::{TempDir, fixture, run};
+use std::fs;
+
+const PROJECT: [(&str, &str); 2] = [
+    (
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {name}", "save": "Enregistrer", "cancel": "Annuler", "help": "Aide, \"FAQ\"" }"#,
+    ),
+    ("i18n/it/common.json", r#"{ "title": "Ciao", "save": "" }"#),
+];
+
+fn worklist(dir: &TempDir, extra: &[&str]) -> String {
+    let i18n = dir.path().join("i18n");
+    let mut args = vec!["worklist", "--lang", "it", i18n.to_str().unwrap()];
+    args.extend_from_slice(extra);
+    let output = run(&args);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn markdown_lists_each_section_as_a_checklist() {
+    let dir = fixture("worklist-markdown", &PROJECT);
+    let output = dir.path().join("it-todo.md");
+
+    let i18n = dir.path().join("i18n");
+    let result = run(&[
+        "worklist",
+        "--lang",
+        "it",
+        "--output",
+        output.to_str().unwrap(),
+        i18n.to_str().unwrap(),
+    ]);
+    assert_eq!(result.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("4 items written to"), "{}", stderr);
+
+    let text = fs::read_to_string(&output).unwrap();
+    let it = dir.path().join("i18n/it/common.json");
+    assert!(
+        text.starts_with("# Translation worklist: IT\n\n4 items.\n"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(&format!(
+            "## Missing translations (2)\n\n- [ ] `cancel` in `{0}`\n  Base:\n  > Annuler\n- [ ] `help` in `{0}`\n",
+            it.display()
+        )),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(&format!(
+            "## Empty translations (1)\n\n- [ ] `save` in `{}`\n  Base:\n  > Enregistrer\n",
+            it.display()
+        )),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("## Variable mismatches (1)\n\n- [ ] `title` in `"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("  > Bonjour {name}\n  Current:\n  > Ciao\n"),
+        "{}",
+        text
+    );
+    assert!(
+        text.find("## Missing").unwrap() < text.find("## Empty").unwrap()
+            && text.find("## Empty").unwrap() < text.find("## Variable").unwrap(),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn sections_can_be_picked_and_reordered() {
+    let dir = fixture("worklist-sections", &PROJECT);
+
+    let text = worklist(&dir, &["--sections", "mismatch,missing"]);
+    assert!(!text.contains("## Empty"), "{}", text);
+    assert!(
+        text.find("## Variable").unwrap() < text.find("## Missing").unwrap(),
+        "{}",
+        text
+    );
+
+    dir.write(
+        "translation-check.toml",
+        "[worklist]\nsections = [\"empty\"]\n",
+    );
+    let config = dir.path().join("translation-check.toml");
+    let text = worklist(&dir, &["--config", config.to_str().unwrap()]);
+    assert!(
+        text.contains("1 item.\n\n## Empty translations (1)"),
+        "{}",
+        text
+    );
+
+    let i18n = dir.path().join("i18n");
+    let output = run(&[
+        "worklist",
+        "--lang",
+        "it",
+        "--sections",
+        "later",
+        i18n.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("unknown worklist section `later` in `--sections`")
+    );
+}
+
+#[test]
+fn csv_quotes_values_and_drops_finished_items() {
+    let dir = fixture("worklist-csv", &PROJECT);
+
+    let text = worklist(&dir, &["--format", "csv"]);
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next(),
+        Some("section,key,file,base_value,value,note,context")
+    );
+    assert!(text.contains(",\"Aide, \"\"FAQ\"\"\",,,\n"), "{}", text);
+    assert_eq!(lines.count(), 4);
+
+    dir.write(
+        "i18n/it/common.json",
+        r#"{ "title": "Ciao {name}", "save": "Salva", "cancel": "Annulla", "help": "Aiuto" }"#,
+    );
+    let text = worklist(&dir, &["--format", "csv"]);
+    assert_eq!(text, "section,key,file,base_value,value,note,context\n");
+}
+
+#[test]
+fn base_changes_since_the_snapshot_are_stale() {
+    let dir = fixture("worklist-stale", &PROJECT);
+    dir.write(
+        "i18n/it/common.json",
+        r#"{ "title": "Ciao {name}", "save": "Salva", "cancel": "Annulla", "help": "Aiuto" }"#,
+    );
+    let i18n = dir.path().join("i18n");
+    let snapshot = dir.path().join("schema.json");
+    let output = run(&[
+        i18n.to_str().unwrap(),
+        "--only",
+        "consistency",
+        "--schema-snapshot",
+        snapshot.to_str().unwrap(),
+        "--update-schema-snapshot",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    dir.write(
+        "i18n/fr/common.json",
+        r#"{ "title": "Bonjour {firstName}", "save": "Enregistrer", "cancel": "Annuler", "help": "Aide" }"#,
+    );
+    let text = worklist(&dir, &["--schema-snapshot", snapshot.to_str().unwrap()]);
+    assert!(
+        text.contains("## Translations to update after a base change (1)\n\n- [ ] `title` in `"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("  > Bonjour {firstName}\n  Current:\n  > Ciao {name}\n"),
+        "{}",
+        text
+    );
+    assert!(!text.contains("## Variable mismatches"), "{}", text);
+}